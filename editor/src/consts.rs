@@ -56,6 +56,10 @@ pub const DEFAULT_STROKE_WIDTH: f64 = 2.;
 pub const SELECTION_TOLERANCE: f64 = 5.;
 pub const DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD: f64 = 15.;
 pub const SELECTION_DRAG_ANGLE: f64 = 90.;
+/// How close (in viewport pixels) the cursor may stay to the last vertex of a lasso polygon before it adds another one instead of moving the last one.
+pub const LASSO_MIN_VERTEX_DISTANCE: f64 = 1.;
+/// How far (in viewport pixels) a vertex may stray from the straight run of a lasso polygon and still be merged into it.
+pub const LASSO_COLLINEARITY_TOLERANCE: f64 = 0.5;
 
 // PIVOT
 pub const PIVOT_CROSSHAIR_THICKNESS: f64 = 1.;
@@ -102,6 +106,59 @@ pub const HIDE_HANDLE_DISTANCE: f64 = 3.;
 pub const HANDLE_ROTATE_SNAP_ANGLE: f64 = 15.;
 pub const SEGMENT_INSERTION_DISTANCE: f64 = 7.5;
 pub const SEGMENT_OVERLAY_SIZE: f64 = 10.;
+/// How far (in viewport pixels) the cursor may move before cycling through overlapping segments starts again from the upper one.
+pub const SEGMENT_CYCLE_RESET_DISTANCE: f64 = 4.;
+pub const PATH_OFFSET_SCROLL_RATE: f64 = 0.05;
+/// Box and lasso selections over more points than this are processed across several frames to keep the editor responsive.
+pub const PATH_SELECTION_CHUNK_SIZE: usize = 20_000;
+/// Default distance (in viewport pixels) from a point within which a missed click that starts a marquee may be promoted into dragging that point.
+pub const PATH_DRAG_PROMOTION_DISTANCE: f64 = SELECTION_THRESHOLD * 1.5;
+/// How far (in viewport pixels) the cursor may travel after a missed click before the marquee can no longer be promoted into a point drag.
+pub const PATH_DRAG_PROMOTION_WINDOW: f64 = 8.;
+/// Minimum cosine between the cursor's movement and the direction toward the missed point for the marquee to be promoted into a point drag.
+pub const PATH_DRAG_PROMOTION_ALIGNMENT: f64 = 0.7;
+/// Nudges arriving within this many milliseconds of the previous one in the same direction count as a held arrow key and are accelerated.
+pub const PATH_NUDGE_REPEAT_INTERVAL: u64 = 100;
+/// After this many milliseconds without a nudge, the held arrow key is considered released and its moves are committed as one undo step.
+pub const PATH_NUDGE_SESSION_TIMEOUT: u64 = 600;
+/// The nudge distance doubles after each run of this many consecutive repeated nudges.
+pub const PATH_NUDGE_ACCELERATION_STEP: u32 = 10;
+/// Default upper limit for the multiplier applied to repeated nudges.
+pub const PATH_NUDGE_ACCELERATION_CAP: f64 = 8.;
+/// Distance (in viewport pixels) from an anchor to each of its width handles while the point has the default width of 1.
+pub const PATH_WIDTH_HANDLE_LENGTH: f64 = 20.;
+/// Default radius (in viewport pixels) of the brush which reshapes paths by dragging across them.
+pub const PATH_RESHAPE_DEFAULT_RADIUS: f64 = 40.;
+/// How much (in viewport pixels) the bracket keys grow or shrink the radius of the reshape brush.
+pub const PATH_RESHAPE_RADIUS_CHANGE_KEYBOARD: f64 = 5.;
+/// The farthest (in viewport pixels) a segment reshaped by the brush may stray from the deformed path before it is split with a new anchor.
+pub const PATH_RESHAPE_FIT_TOLERANCE: f64 = 0.5;
+/// How many parts each segment reshaped by the brush is sampled in to be deformed and fitted again.
+pub const PATH_RESHAPE_SAMPLES: usize = 16;
+/// Radius (in viewport pixels) of the ring highlighting the point under the cursor, which a click would select.
+pub const PATH_HOVER_RING_RADIUS: f64 = 7.;
+/// How many milliseconds the route selected between two anchors stays highlighted.
+pub const PATH_ROUTE_HIGHLIGHT_DURATION: u64 = 800;
+/// Distance (in viewport pixels) along the selected subpaths between the arrows showing which direction they run in.
+pub const PATH_DIRECTION_ARROW_SPACING: f64 = 80.;
+/// Length (in viewport pixels) of each side of the arrowheads showing which direction the selected subpaths run in.
+pub const PATH_DIRECTION_ARROW_SIZE: f64 = 6.;
+/// How many parts each segment is sampled in to measure the distance along it when spacing out the direction arrows.
+pub const PATH_DIRECTION_ARROW_SAMPLES: usize = 16;
+/// Deleting points or breaking paths on a layer with more non-layer nodes than this downstream first asks for confirmation, unless turned off in the preferences.
+pub const DESTRUCTIVE_POINT_EDIT_DEPENDENTS_THRESHOLD: usize = 8;
+/// The Point Table panel lists at most this many of the selected points, with a notice when more are selected.
+pub const POINT_TABLE_MAX_ROWS: usize = 1000;
+/// Default largest deviation from a straight line (in degrees) of handles flagged as colinear which repairing colinearity realigns rather than unflags.
+pub const COLINEARITY_REPAIR_TOLERANCE: f64 = 5.;
+/// Handles flagged as colinear which deviate from a straight line by less than this (in radians) are considered already aligned.
+pub const COLINEARITY_REPAIR_EPSILON: f64 = 1e-9;
+/// How many points each segment is sampled at when fitting the one replacing them as anchors are dissolved while keeping the curve's shape.
+pub const DISSOLVE_FIT_SAMPLES: usize = 8;
+/// Default distance (in document space) within which selected anchors are merged by distance, enough to catch the duplicates stacked up by imported paths.
+pub const MERGE_BY_DISTANCE_TOLERANCE: f64 = 0.01;
+/// Selected points whose X or Y coordinates (in document space) differ by less than this are shown as sharing one value in the coordinate fields.
+pub const COMMON_COORDINATE_EPSILON: f64 = 1e-9;
 
 // PEN TOOL
 pub const CREATE_CURVE_THRESHOLD: f64 = 5.;
@@ -130,8 +187,40 @@ pub const COLOR_OVERLAY_GRAY: &str = "#cccccc";
 pub const COLOR_OVERLAY_WHITE: &str = "#ffffff";
 pub const COLOR_OVERLAY_LABEL_BACKGROUND: &str = "#000000cc";
 
+// DEBUG RENDERING
+/// Characters per line before the text dump of an output without a visualization wraps onto the next line
+pub const DEBUG_RENDER_TEXT_LINE_LENGTH: usize = 120;
+/// Lines shown in the text dump of an output without a visualization before the rest is cut off
+pub const DEBUG_RENDER_TEXT_MAX_LINES: usize = 200;
+/// Vertical spacing between the lines of the canvas probe's tooltip
+pub const CANVAS_PROBE_LINE_HEIGHT: f64 = 20.;
+
+// GRAPH EXECUTION
+/// Change in the number of pending viewport renders which is reported to the frontend even if rendering didn't start or stop
+pub const GRAPH_BUSY_STATE_REPORT_STEP: usize = 4;
+/// Milliseconds after the response to the last interactive (reduced quality) render before a final quality render is queued, if no other render was requested
+pub const FINAL_RENDER_DELAY_MS: f64 = 150.;
+/// Milliseconds before the application IO is initialized again after it first fails, doubling with each further failure
+pub const APPLICATION_IO_RETRY_DELAY_MS: f64 = 1000.;
+/// The longest wait in milliseconds between attempts at initializing the application IO
+pub const APPLICATION_IO_MAX_RETRY_DELAY_MS: f64 = 60_000.;
+/// Milliseconds between checks of whether the external resources loaded by the graph, like linked images, have changed
+pub const EXTERNAL_RESOURCE_POLL_INTERVAL_MS: f64 = 2000.;
+/// Points an inspected vector data table may have before the spreadsheet is sent a summary of it instead, and reads its rows a page at a time
+pub const INSPECT_SUMMARY_THRESHOLD: usize = 10_000;
+/// Rows from either end of a large inspected table included in its summary
+pub const INSPECT_SUMMARY_EDGE_ROWS: usize = 100;
+/// Rows of a large inspected table the spreadsheet shows at once
+pub const SPREADSHEET_PAGE_ROWS: usize = 100;
+
 // DOCUMENT
 pub const DEFAULT_DOCUMENT_NAME: &str = "Untitled Document";
 pub const FILE_SAVE_SUFFIX: &str = ".graphite";
 pub const MAX_UNDO_HISTORY_LEN: usize = 100; // TODO: Add this to user preferences
 pub const AUTO_SAVE_TIMEOUT_SECONDS: u64 = 15;
+
+// NUMBER DISPLAY
+/// Default number of decimal places that coordinates, lengths, and other measurements are rounded to when shown.
+pub const DISPLAY_DECIMAL_PLACES: u32 = 3;
+/// The most decimal places the display precision preference can be set to.
+pub const DISPLAY_DECIMAL_PLACES_MAX: u32 = 8;
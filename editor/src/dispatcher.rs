@@ -2,11 +2,14 @@ use crate::messages::debug::utility_types::MessageLoggingVerbosity;
 use crate::messages::dialog::DialogMessageData;
 use crate::messages::portfolio::document::node_graph::document_node_definitions;
 use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ToolType;
 
 #[derive(Debug, Default)]
 pub struct Dispatcher {
 	buffered_queue: Option<Vec<VecDeque<Message>>>,
 	message_queues: Vec<VecDeque<Message>>,
+	/// Whether the overlays have been asked to redraw since they were last drawn, which happens once after all other queued messages are processed.
+	overlays_draw_requested: bool,
 	pub responses: Vec<FrontendMessage>,
 	pub message_handlers: DispatcherMessageHandlers,
 }
@@ -45,12 +48,13 @@ const SIDE_EFFECT_FREE_MESSAGES: &[MessageDiscriminant] = &[
 		PropertiesPanelMessageDiscriminant::Refresh,
 	))),
 	MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::DocumentStructureChanged)),
-	MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::Overlays(OverlaysMessageDiscriminant::Draw))),
 	MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::RenderRulers)),
 	MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::RenderScrollbars)),
 	MessageDiscriminant::Frontend(FrontendMessageDiscriminant::UpdateDocumentLayerStructure),
 	MessageDiscriminant::Frontend(FrontendMessageDiscriminant::TriggerFontLoad),
 ];
+/// Requests to redraw the overlays are coalesced into one redraw after everything else queued has been processed, since a single pointer move can request several.
+const OVERLAYS_DRAW: MessageDiscriminant = MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::Overlays(OverlaysMessageDiscriminant::Draw)));
 const DEBUG_MESSAGE_BLOCK_LIST: &[MessageDiscriminant] = &[MessageDiscriminant::Broadcast(BroadcastMessageDiscriminant::TriggerEvent(BroadcastEventDiscriminant::AnimationFrame))];
 // TODO: Find a way to combine these with the list above. We use strings for now since these are the standard variant names used by multiple messages. But having these also type-checked would be best.
 const DEBUG_MESSAGE_ENDING_BLOCK_LIST: &[&str] = &["PointerMove", "PointerOutsideViewport", "Overlays", "Draw", "CurrentTime", "Time"];
@@ -103,7 +107,25 @@ impl Dispatcher {
 		// If we are not maintaining the buffer, simply add to the current queue
 		Self::schedule_execution(&mut self.message_queues, process_after_all_current, [message]);
 
-		while let Some(message) = self.message_queues.last_mut().and_then(VecDeque::pop_front) {
+		// The overlays draw requested while processing the queue is only let through once the queue is empty
+		let mut drawing_overlays = false;
+		loop {
+			let Some(message) = self.message_queues.last_mut().and_then(VecDeque::pop_front) else {
+				// While a render is buffering messages, the draw waits for it since the overlays are drawn again once it's displayed
+				if self.buffered_queue.is_some() || !std::mem::take(&mut self.overlays_draw_requested) {
+					break;
+				}
+				drawing_overlays = true;
+				Self::schedule_execution(&mut self.message_queues, false, [OverlaysMessage::Draw.into()]);
+				continue;
+			};
+
+			if message.to_discriminant() == OVERLAYS_DRAW && !std::mem::take(&mut drawing_overlays) {
+				self.overlays_draw_requested = true;
+				self.cleanup_queues(false);
+				continue;
+			}
+
 			// Skip processing of this message if it will be processed later (at the end of the shallowest level queue)
 			if SIDE_EFFECT_FREE_MESSAGES.contains(&message.to_discriminant()) {
 				let already_in_queue = self.message_queues.first().filter(|queue| queue.contains(&message)).is_some();
@@ -143,6 +165,8 @@ impl Dispatcher {
 						local_transforms,
 						click_targets,
 						clip_targets,
+						instance_transforms,
+						subpath_click_regions,
 					} = render_metadata;
 
 					// Run these update state messages immediately
@@ -152,7 +176,9 @@ impl Dispatcher {
 							local_transforms,
 						},
 						DocumentMessage::UpdateClickTargets { click_targets },
+						DocumentMessage::UpdateSubpathClickRegions { subpath_click_regions },
 						DocumentMessage::UpdateClipTargets { clip_targets },
+						DocumentMessage::UpdateInstanceTransforms { instance_transforms },
 					];
 					Self::schedule_execution(&mut self.message_queues, false, messages.map(Message::from));
 				}
@@ -267,6 +293,7 @@ impl Dispatcher {
 						persistent_data: &self.message_handlers.portfolio_message_handler.persistent_data,
 						node_graph: &self.message_handlers.portfolio_message_handler.executor,
 						preferences: &self.message_handlers.preferences_message_handler,
+						point_table_open: self.message_handlers.portfolio_message_handler.point_table.point_table_open,
 					};
 
 					self.message_handlers.tool_message_handler.process_message(message, &mut queue, data);
@@ -302,6 +329,10 @@ impl Dispatcher {
 		list
 	}
 
+	pub fn active_tool_type(&self) -> ToolType {
+		self.message_handlers.tool_message_handler.tool_state.tool_data.active_tool_type
+	}
+
 	pub fn poll_node_graph_evaluation(&mut self, responses: &mut VecDeque<Message>) -> Result<(), String> {
 		self.message_handlers.portfolio_message_handler.poll_node_graph_evaluation(responses)
 	}
@@ -0,0 +1,10 @@
+use crate::messages::prelude::*;
+
+#[impl_message(Message, DialogMessage, AddPointAtLengthDialog)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum AddPointAtLengthDialogMessage {
+	Percentage(bool),
+	Amount(f64),
+
+	Submit,
+}
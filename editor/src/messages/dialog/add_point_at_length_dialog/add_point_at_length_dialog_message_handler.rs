@@ -0,0 +1,116 @@
+use crate::messages::layout::utility_types::number_format::format_number;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::measure::DistanceAlongPath;
+use crate::messages::tool::utility_types::ToolType;
+
+/// A dialog to insert an anchor on the selected layer's path at a distance along it, given as a percentage of the path's length or as an absolute length.
+#[derive(Debug, Clone, Default)]
+pub struct AddPointAtLengthDialogMessageHandler {
+	pub layer: Option<LayerNodeIdentifier>,
+	/// The length of the layer's path in document space.
+	pub path_length: f64,
+	/// Whether the amount is a percentage of the path's length, rather than a length.
+	pub percentage: bool,
+	pub amount: f64,
+	pub display_decimal_places: u32,
+}
+
+impl MessageHandler<AddPointAtLengthDialogMessage, ()> for AddPointAtLengthDialogMessageHandler {
+	fn process_message(&mut self, message: AddPointAtLengthDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			AddPointAtLengthDialogMessage::Percentage(percentage) => {
+				// Keep the same place along the path when switching between the two ways of giving it
+				if percentage != self.percentage && self.path_length > 0. {
+					self.amount = if percentage {
+						self.amount / self.path_length * 100.
+					} else {
+						self.amount * self.path_length / 100.
+					};
+				}
+				self.percentage = percentage;
+			}
+			AddPointAtLengthDialogMessage::Amount(amount) => self.amount = amount,
+			AddPointAtLengthDialogMessage::Submit => {
+				if let Some(layer) = self.layer {
+					let distance = if self.percentage {
+						DistanceAlongPath::Percentage(self.amount)
+					} else {
+						DistanceAlongPath::Length(self.amount)
+					};
+
+					// The insertion is made by the Path tool so the new anchor is left selected there, ready to be edited
+					responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Path });
+					responses.add(PathToolMessage::AddPointAtLength { layer, distance });
+				}
+			}
+		}
+
+		self.send_dialog_to_frontend(responses);
+	}
+
+	advertise_actions! {AddPointAtLengthDialogUpdate;}
+}
+
+impl DialogLayoutHolder for AddPointAtLengthDialogMessageHandler {
+	const ICON: &'static str = "VectorPathTool";
+	const TITLE: &'static str = "Add Point at Length";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![
+			TextButton::new("OK")
+				.emphasized(true)
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![AddPointAtLengthDialogMessage::Submit.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for AddPointAtLengthDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let path_length = vec![
+			TextLabel::new("Path Length").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new(format!("{} px", format_number(self.path_length, self.display_decimal_places))).widget_holder(),
+		];
+
+		let entries = [(true, "Percentage"), (false, "Length")]
+			.into_iter()
+			.map(|(percentage, name)| RadioEntryData::new(name).label(name).on_update(move |_| AddPointAtLengthDialogMessage::Percentage(percentage).into()))
+			.collect();
+		let measure = vec![
+			TextLabel::new("Measure As").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(if self.percentage { 0 } else { 1 })).widget_holder(),
+		];
+
+		let (unit, max) = if self.percentage { ("%", 100.) } else { (" px", self.path_length) };
+		let distance = vec![
+			TextLabel::new("Distance").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.amount))
+				.unit(unit)
+				.display_decimal_places(self.display_decimal_places)
+				.min(0.)
+				.max(max)
+				.on_update(|number_input: &NumberInput| AddPointAtLengthDialogMessage::Amount(number_input.value.unwrap()).into())
+				.min_width(200)
+				.widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row { widgets: path_length },
+			LayoutGroup::Row { widgets: measure },
+			LayoutGroup::Row { widgets: distance },
+		]))
+	}
+}
@@ -0,0 +1,7 @@
+mod add_point_at_length_dialog_message;
+mod add_point_at_length_dialog_message_handler;
+
+#[doc(inline)]
+pub use add_point_at_length_dialog_message::{AddPointAtLengthDialogMessage, AddPointAtLengthDialogMessageDiscriminant};
+#[doc(inline)]
+pub use add_point_at_length_dialog_message_handler::AddPointAtLengthDialogMessageHandler;
@@ -1,10 +1,14 @@
 use crate::messages::prelude::*;
+use crate::messages::tool::tool_messages::path_tool::DestructivePointEdit;
+use crate::node_graph_executor::DocumentStatistics;
 
 #[impl_message(Message, Dialog)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum DialogMessage {
 	// Sub-messages
 	#[child]
+	AddPointAtLengthDialog(AddPointAtLengthDialogMessage),
+	#[child]
 	ExportDialog(ExportDialogMessage),
 	#[child]
 	NewDocumentDialog(NewDocumentDialogMessage),
@@ -20,7 +24,11 @@ pub enum DialogMessage {
 		title: String,
 		description: String,
 	},
+	DisplayDocumentStatistics {
+		statistics: DocumentStatistics,
+	},
 	RequestAboutGraphiteDialog,
+	RequestAddPointAtLengthDialog,
 	RequestAboutGraphiteDialogWithLocalizedCommitDate {
 		localized_commit_date: String,
 		localized_commit_year: String,
@@ -29,6 +37,10 @@ pub enum DialogMessage {
 		issue: Option<u32>,
 	},
 	RequestDemoArtworkDialog,
+	RequestDestructivePointEditDialog {
+		dependents: usize,
+		edit: DestructivePointEdit,
+	},
 	RequestExportDialog,
 	RequestLicensesDialogWithLocalizedCommitDate {
 		localized_commit_year: String,
@@ -1,6 +1,8 @@
-use super::simple_dialogs::{self, AboutGraphiteDialog, ComingSoonDialog, DemoArtworkDialog, LicensesDialog};
+use super::simple_dialogs::{self, AboutGraphiteDialog, ComingSoonDialog, DemoArtworkDialog, DestructivePointEditDialog, DocumentStatisticsDialog, LicensesDialog};
+use crate::messages::frontend::utility_types::ExportBounds;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::shape_editor::ShapeState;
 
 pub struct DialogMessageData<'a> {
 	pub portfolio: &'a PortfolioMessageHandler,
@@ -10,6 +12,7 @@ pub struct DialogMessageData<'a> {
 /// Stores the dialogs which require state. These are the ones that have their own message handlers, and are not the ones defined in `simple_dialogs`.
 #[derive(Debug, Default, Clone)]
 pub struct DialogMessageHandler {
+	add_point_at_length_dialog: AddPointAtLengthDialogMessageHandler,
 	export_dialog: ExportDialogMessageHandler,
 	new_document_dialog: NewDocumentDialogMessageHandler,
 	preferences_dialog: PreferencesDialogMessageHandler,
@@ -20,6 +23,7 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 		let DialogMessageData { portfolio, preferences } = data;
 
 		match message {
+			DialogMessage::AddPointAtLengthDialog(message) => self.add_point_at_length_dialog.process_message(message, responses, ()),
 			DialogMessage::ExportDialog(message) => self.export_dialog.process_message(message, responses, ExportDialogMessageData { portfolio }),
 			DialogMessage::NewDocumentDialog(message) => self.new_document_dialog.process_message(message, responses, ()),
 			DialogMessage::PreferencesDialog(message) => self.preferences_dialog.process_message(message, responses, PreferencesDialogMessageData { preferences }),
@@ -43,11 +47,43 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 				let dialog = simple_dialogs::ErrorDialog { title, description };
 				dialog.send_dialog_to_frontend(responses);
 			}
+			DialogMessage::DisplayDocumentStatistics { statistics } => {
+				if let Some(document) = portfolio.active_document() {
+					let dialog = DocumentStatisticsDialog::new(document, &statistics, preferences.display_decimal_places);
+					dialog.send_dialog_to_frontend(responses);
+				}
+			}
 			DialogMessage::RequestAboutGraphiteDialog => {
 				responses.add(FrontendMessage::TriggerAboutGraphiteLocalizedCommitDate {
 					commit_date: env!("GRAPHITE_GIT_COMMIT_DATE").into(),
 				});
 			}
+			DialogMessage::RequestAddPointAtLengthDialog => {
+				let Some(document) = portfolio.active_document() else { return };
+
+				// The point is added along the path of the only selected layer
+				let mut selected_layers = document.network_interface.selected_nodes().selected_layers(document.metadata());
+				let path = match (selected_layers.next(), selected_layers.next()) {
+					(Some(layer), None) => document.network_interface.compute_modified_vector(layer).map(|vector_data| (layer, vector_data)),
+					_ => None,
+				};
+				let Some((layer, vector_data)) = path.filter(|(_, vector_data)| !vector_data.segment_domain.ids().is_empty()) else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Can't add a point along the path".into(),
+						description: "Select a single layer with a path to add a point at a length along it.".into(),
+					});
+					return;
+				};
+
+				self.add_point_at_length_dialog = AddPointAtLengthDialogMessageHandler {
+					layer: Some(layer),
+					path_length: ShapeState::path_arc_length(&vector_data, document.metadata().transform_to_document(layer)).total(),
+					percentage: true,
+					amount: 50.,
+					display_decimal_places: preferences.display_decimal_places,
+				};
+				self.add_point_at_length_dialog.send_dialog_to_frontend(responses);
+			}
 			DialogMessage::RequestAboutGraphiteDialogWithLocalizedCommitDate {
 				localized_commit_date,
 				localized_commit_year,
@@ -67,6 +103,10 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 				let dialog = DemoArtworkDialog;
 				dialog.send_dialog_to_frontend(responses);
 			}
+			DialogMessage::RequestDestructivePointEditDialog { dependents, edit } => {
+				let dialog = DestructivePointEditDialog { dependents, edit };
+				dialog.send_dialog_to_frontend(responses);
+			}
 			DialogMessage::RequestExportDialog => {
 				if let Some(document) = portfolio.active_document() {
 					let artboards = document
@@ -85,8 +125,17 @@ impl MessageHandler<DialogMessage, DialogMessageData<'_>> for DialogMessageHandl
 						.collect();
 
 					self.export_dialog.artboards = artboards;
+					self.export_dialog.display_decimal_places = preferences.display_decimal_places;
 					self.export_dialog.has_selection = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().is_some();
+					// The custom bounds start out covering the artwork until the document has its own
+					if let Some(custom_bounds) = document.custom_export_bounds.or_else(|| document.network_interface.document_bounds_document_space(true)) {
+						self.export_dialog.custom_bounds = custom_bounds;
+					}
+					if let ExportBounds::Custom(_) = self.export_dialog.bounds {
+						self.export_dialog.bounds = ExportBounds::Custom(self.export_dialog.custom_bounds);
+					}
 					self.export_dialog.send_dialog_to_frontend(responses);
+					responses.add(self.export_dialog.describe_export());
 				}
 			}
 			DialogMessage::RequestLicensesDialogWithLocalizedCommitDate { localized_commit_year } => {
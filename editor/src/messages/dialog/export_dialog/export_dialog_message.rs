@@ -1,5 +1,6 @@
-use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::frontend::utility_types::{ExportBounds, ExportStage, FileType};
 use crate::messages::prelude::*;
+use crate::node_graph_executor::ExportDescription;
 
 #[impl_message(Message, DialogMessage, ExportDialog)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -7,7 +8,10 @@ pub enum ExportDialogMessage {
 	FileType(FileType),
 	ScaleFactor(f64),
 	TransparentBackground(bool),
+	ExpandStrokes(bool),
 	ExportBounds(ExportBounds),
 
 	Submit,
+	Progress { stage: ExportStage, fraction: f64 },
+	UpdateDescription { description: Option<ExportDescription> },
 }
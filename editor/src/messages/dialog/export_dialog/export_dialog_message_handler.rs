@@ -1,7 +1,12 @@
-use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::consts::DISPLAY_DECIMAL_PLACES;
+use crate::messages::frontend::utility_types::{ExportBounds, ExportStage, FileType};
+use crate::messages::layout::utility_types::number_format::format_number;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ToolType;
+use crate::node_graph_executor::ExportDescription;
+use glam::DVec2;
 
 pub struct ExportDialogMessageData<'a> {
 	pub portfolio: &'a PortfolioMessageHandler,
@@ -14,8 +19,17 @@ pub struct ExportDialogMessageHandler {
 	pub scale_factor: f64,
 	pub bounds: ExportBounds,
 	pub transparent_background: bool,
+	pub expand_strokes: bool,
 	pub artboards: HashMap<LayerNodeIdentifier, String>,
 	pub has_selection: bool,
+	/// The rectangle in document space used by the custom bounds, which is stored in the document once chosen.
+	pub custom_bounds: [DVec2; 2],
+	/// The stage and progress of the export submitted from the dialog, which stays open showing them until the export is over.
+	pub progress: Option<(ExportStage, f64)>,
+	/// The number of decimal places the numbers in the dialog are rounded to when shown.
+	pub display_decimal_places: u32,
+	/// What the export would produce with the current settings, or `None` if there's nothing to export.
+	pub description: Option<ExportDescription>,
 }
 
 impl Default for ExportDialogMessageHandler {
@@ -25,8 +39,13 @@ impl Default for ExportDialogMessageHandler {
 			scale_factor: 1.,
 			bounds: Default::default(),
 			transparent_background: false,
+			expand_strokes: false,
 			artboards: Default::default(),
 			has_selection: false,
+			custom_bounds: [DVec2::ZERO, DVec2::splat(100.)],
+			progress: None,
+			display_decimal_places: DISPLAY_DECIMAL_PLACES,
+			description: None,
 		}
 	}
 }
@@ -39,7 +58,14 @@ impl MessageHandler<ExportDialogMessage, ExportDialogMessageData<'_>> for Export
 			ExportDialogMessage::FileType(export_type) => self.file_type = export_type,
 			ExportDialogMessage::ScaleFactor(factor) => self.scale_factor = factor,
 			ExportDialogMessage::TransparentBackground(transparent_background) => self.transparent_background = transparent_background,
-			ExportDialogMessage::ExportBounds(export_area) => self.bounds = export_area,
+			ExportDialogMessage::ExpandStrokes(expand_strokes) => self.expand_strokes = expand_strokes,
+			ExportDialogMessage::ExportBounds(export_area) => {
+				if let ExportBounds::Custom(custom_bounds) = export_area {
+					self.custom_bounds = custom_bounds;
+					responses.add(DocumentMessage::SetCustomExportBounds { bounds: Some(custom_bounds) });
+				}
+				self.bounds = export_area;
+			}
 
 			ExportDialogMessage::Submit => responses.add_front(PortfolioMessage::SubmitDocumentExport {
 				file_name: portfolio.active_document().map(|document| document.name.clone()).unwrap_or_default(),
@@ -47,30 +73,64 @@ impl MessageHandler<ExportDialogMessage, ExportDialogMessageData<'_>> for Export
 				scale_factor: self.scale_factor,
 				bounds: self.bounds,
 				transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
+				expand_strokes: self.expand_strokes,
 			}),
+			ExportDialogMessage::Progress { stage, fraction } => {
+				responses.add(FrontendMessage::UpdateExportProgress { stage, fraction });
+
+				if stage.is_over() {
+					if self.progress.take().is_some() {
+						responses.add(FrontendMessage::DisplayDialogDismiss);
+					}
+				} else {
+					self.progress = Some((stage, fraction));
+					// Only the contents are updated, so a dialog the user has dismissed isn't opened again
+					self.send_layout(responses, LayoutTarget::DialogColumn1);
+					self.send_layout_buttons(responses, LayoutTarget::DialogButtons);
+				}
+				return;
+			}
+			ExportDialogMessage::UpdateDescription { description } => {
+				self.description = description;
+				self.send_layout(responses, LayoutTarget::DialogColumn1);
+				return;
+			}
 		}
 
+		responses.add(self.describe_export());
 		self.send_dialog_to_frontend(responses);
 	}
 
 	advertise_actions! {ExportDialogUpdate;}
 }
 
+impl ExportDialogMessageHandler {
+	/// Asks for a description of what the export would produce with the current settings, which arrives as [`ExportDialogMessage::UpdateDescription`].
+	pub fn describe_export(&self) -> Message {
+		PortfolioMessage::DescribeDocumentExport {
+			file_type: self.file_type,
+			scale_factor: self.scale_factor,
+			bounds: self.bounds,
+			transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
+			expand_strokes: self.expand_strokes,
+		}
+		.into()
+	}
+}
+
 impl DialogLayoutHolder for ExportDialogMessageHandler {
 	const ICON: &'static str = "File";
 	const TITLE: &'static str = "Export";
 
 	fn layout_buttons(&self) -> Layout {
+		// While the export is underway, cancelling it is the only choice left
+		if self.progress.is_some() {
+			let widgets = vec![TextButton::new("Cancel Export").on_update(|_| PortfolioMessage::CancelExport.into()).widget_holder()];
+			return Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]));
+		}
+
 		let widgets = vec![
-			TextButton::new("Export")
-				.emphasized(true)
-				.on_update(|_| {
-					DialogMessage::CloseDialogAndThen {
-						followups: vec![ExportDialogMessage::Submit.into()],
-					}
-					.into()
-				})
-				.widget_holder(),
+			TextButton::new("Export").emphasized(true).on_update(|_| ExportDialogMessage::Submit.into()).widget_holder(),
 			TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
 		];
 
@@ -96,6 +156,7 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
 			NumberInput::new(Some(self.scale_factor))
 				.unit("")
+				.display_decimal_places(self.display_decimal_places)
 				.min(0.)
 				.max((1_u64 << f64::MANTISSA_DIGITS) as f64)
 				.disabled(self.file_type == FileType::Svg)
@@ -109,14 +170,19 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			(ExportBounds::Selection, "Selection".to_string(), !self.has_selection),
 		];
 		let artboards = self.artboards.iter().map(|(&layer, name)| (ExportBounds::Artboard(layer), name.to_string(), false)).collect();
-		let groups = [standard_bounds, artboards];
+		let custom_bounds = vec![(ExportBounds::Custom(self.custom_bounds), "Custom".to_string(), false)];
+		let groups = [standard_bounds, artboards, custom_bounds];
 
 		let current_bounds = if !self.has_selection && self.bounds == ExportBounds::Selection {
 			ExportBounds::AllArtwork
 		} else {
 			self.bounds
 		};
-		let index = groups.iter().flatten().position(|(bounds, _, _)| *bounds == current_bounds).unwrap();
+		let index = groups
+			.iter()
+			.flatten()
+			.position(|(bounds, _, _)| *bounds == current_bounds || matches!((bounds, current_bounds), (ExportBounds::Custom(_), ExportBounds::Custom(_))))
+			.unwrap_or_default();
 
 		let mut entries = groups
 			.into_iter()
@@ -143,6 +209,43 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			DropdownInput::new(entries).selected_index(Some(index as u32)).widget_holder(),
 		];
 
+		let [corner1, corner2] = self.custom_bounds;
+		let (position, size) = (corner1.min(corner2), (corner2 - corner1).abs());
+		let display_decimal_places = self.display_decimal_places;
+		let custom_bounds_input = |label: &'static str, value: f64, update: fn(DVec2, DVec2, f64) -> [DVec2; 2]| {
+			NumberInput::new(Some(value))
+				.label(label)
+				.unit(" px")
+				.display_decimal_places(display_decimal_places)
+				.min_width(100)
+				.on_update(move |number_input: &NumberInput| ExportDialogMessage::ExportBounds(ExportBounds::Custom(update(position, size, number_input.value.unwrap_or(value)))).into())
+		};
+		let custom_area = vec![
+			TextLabel::new("Rectangle").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			custom_bounds_input("X", position.x, |position, size, x| [DVec2::new(x, position.y), DVec2::new(x, position.y) + size]).widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			custom_bounds_input("Y", position.y, |position, size, y| [DVec2::new(position.x, y), DVec2::new(position.x, y) + size]).widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			custom_bounds_input("W", size.x, |position, size, width| [position, position + DVec2::new(width, size.y)])
+				.min(0.)
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			custom_bounds_input("H", size.y, |position, size, height| [position, position + DVec2::new(size.x, height)])
+				.min(0.)
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextButton::new("Edit on Canvas")
+				.tooltip("Close this dialog to drag the rectangle and its handles on the canvas, then press Enter to return")
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![ToolMessage::ActivateTool { tool_type: ToolType::Artboard }.into(), ArtboardToolMessage::EditExportBounds.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
 		let transparent_background = vec![
 			TextLabel::new("Transparency").table_align(true).min_width(100).widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
@@ -152,11 +255,59 @@ impl LayoutHolder for ExportDialogMessageHandler {
 				.widget_holder(),
 		];
 
-		Layout::WidgetLayout(WidgetLayout::new(vec![
+		let expand_strokes = vec![
+			TextLabel::new("Outline Strokes")
+				.table_align(true)
+				.min_width(100)
+				.tooltip("Convert strokes into filled shapes, for software and machines (like laser cutters) that don't support strokes")
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.expand_strokes)
+				.on_update(move |value: &CheckboxInput| ExportDialogMessage::ExpandStrokes(value.checked).into())
+				.widget_holder(),
+		];
+
+		let mut rows = vec![
 			LayoutGroup::Row { widgets: export_type },
 			LayoutGroup::Row { widgets: resolution },
 			LayoutGroup::Row { widgets: export_area },
-			LayoutGroup::Row { widgets: transparent_background },
-		]))
+		];
+		if matches!(current_bounds, ExportBounds::Custom(_)) {
+			rows.push(LayoutGroup::Row { widgets: custom_area });
+		}
+		rows.extend([LayoutGroup::Row { widgets: transparent_background }, LayoutGroup::Row { widgets: expand_strokes }]);
+
+		let output = match &self.description {
+			Some(description) => {
+				let artboards = match description.artboards.len() {
+					1 => "1 artboard".to_string(),
+					count => format!("{count} artboards"),
+				};
+				let layers = match description.layer_count {
+					1 => "1 layer".to_string(),
+					count => format!("{count} layers"),
+				};
+				let [width, height] = [description.size.x, description.size.y].map(|length| format_number(length, self.display_decimal_places));
+				format!("{width}×{height} px, {artboards}, {layers}")
+			}
+			None => "Nothing to export".to_string(),
+		};
+		let output = vec![
+			TextLabel::new("Output").table_align(true).min_width(100).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new(output).widget_holder(),
+		];
+		rows.push(LayoutGroup::Row { widgets: output });
+
+		if let Some((stage, fraction)) = self.progress {
+			let progress = vec![
+				TextLabel::new("Progress").table_align(true).min_width(100).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(format!("{}… {}%", stage.label(), format_number(fraction * 100., 0))).widget_holder(),
+			];
+			rows.push(LayoutGroup::Row { widgets: progress });
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
 	}
 }
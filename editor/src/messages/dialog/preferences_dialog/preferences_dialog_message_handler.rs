@@ -1,6 +1,7 @@
-use crate::consts::{VIEWPORT_ZOOM_WHEEL_RATE, VIEWPORT_ZOOM_WHEEL_RATE_CHANGE};
+use crate::consts::{DISPLAY_DECIMAL_PLACES, DISPLAY_DECIMAL_PLACES_MAX, PATH_DRAG_PROMOTION_DISTANCE, PATH_NUDGE_ACCELERATION_CAP, VIEWPORT_ZOOM_WHEEL_RATE, VIEWPORT_ZOOM_WHEEL_RATE_CHANGE};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::node_graph::utility_types::GraphWireStyle;
+use crate::messages::portfolio::document::utility_types::misc::ViewportRenderer;
 use crate::messages::preferences::SelectionMode;
 use crate::messages::prelude::*;
 
@@ -131,6 +132,96 @@ impl PreferencesDialogMessageHandler {
 			selection_mode,
 		];
 
+		let drag_promotion_tooltip = "When a Path tool click narrowly misses a point and the drag heads toward it, drag the point instead of starting a selection box";
+		let drag_promotion = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.path_drag_promotion)
+				.tooltip(drag_promotion_tooltip)
+				.on_update(|checkbox_input: &CheckboxInput| PreferencesMessage::PathDragPromotion { enabled: checkbox_input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Forgiving Point Drags").table_align(true).tooltip(drag_promotion_tooltip).widget_holder(),
+		];
+
+		let drag_promotion_distance_tooltip = "How far from a point (in pixels) a missed click may land and still drag that point";
+		let drag_promotion_distance = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.path_drag_promotion_distance))
+				.tooltip(drag_promotion_distance_tooltip)
+				.unit(" px")
+				.min(0.)
+				.max(50.)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::PathDragPromotionDistance {
+						distance: number_input.value.unwrap_or(PATH_DRAG_PROMOTION_DISTANCE),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let nudge_acceleration_tooltip = "How many times faster holding an arrow key can move selected points in the Path tool (1 disables the acceleration)";
+		let nudge_acceleration_label = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Nudge Acceleration").tooltip(nudge_acceleration_tooltip).widget_holder(),
+		];
+		let nudge_acceleration = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.path_nudge_acceleration_cap))
+				.tooltip(nudge_acceleration_tooltip)
+				.unit("×")
+				.int()
+				.min(1.)
+				.max(64.)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::PathNudgeAccelerationCap {
+						cap: number_input.value.unwrap_or(PATH_NUDGE_ACCELERATION_CAP),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let confirm_destructive_point_edits_tooltip = "Ask before deleting points or breaking paths on a layer that many other nodes, like boolean operations and instancers, depend on";
+		let confirm_destructive_point_edits = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.confirm_destructive_point_edits)
+				.tooltip(confirm_destructive_point_edits_tooltip)
+				.on_update(|checkbox_input: &CheckboxInput| PreferencesMessage::ConfirmDestructivePointEdits { enabled: checkbox_input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Confirm Edits to Depended-On Paths")
+				.table_align(true)
+				.tooltip(confirm_destructive_point_edits_tooltip)
+				.widget_holder(),
+		];
+
+		let display_decimal_places_tooltip = "How many decimal places coordinates, lengths, and angles are rounded to when shown (values are always kept and typed at full precision)";
+		let display_decimal_places_label = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Displayed Decimal Places").tooltip(display_decimal_places_tooltip).widget_holder(),
+		];
+		let display_decimal_places = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.display_decimal_places as f64))
+				.tooltip(display_decimal_places_tooltip)
+				.int()
+				.min(0.)
+				.max(DISPLAY_DECIMAL_PLACES_MAX as f64)
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::DisplayDecimalPlaces {
+						decimal_places: number_input.value.map_or(DISPLAY_DECIMAL_PLACES, |value| value as u32),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
 		// ============
 		// EXPERIMENTAL
 		// ============
@@ -177,6 +268,31 @@ impl PreferencesDialogMessageHandler {
 				.widget_holder(),
 		];
 
+		let viewport_renderer_tooltip = "Renderer used to draw the artwork of documents that haven't chosen their own in the View menu";
+		let viewport_renderer_label = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Default Renderer").tooltip(viewport_renderer_tooltip).widget_holder(),
+		];
+		let viewport_renderer = RadioInput::new(
+			[ViewportRenderer::Svg, ViewportRenderer::Canvas]
+				.into_iter()
+				.map(|renderer| {
+					RadioEntryData::new(renderer.to_string())
+						.label(renderer.to_string())
+						.tooltip(renderer.tooltip_description())
+						.on_update(move |_| PreferencesMessage::ViewportRenderer { renderer }.into())
+				})
+				.collect(),
+		)
+		.selected_index(Some(preferences.viewport_renderer as u32))
+		.widget_holder();
+		let viewport_renderer = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			viewport_renderer,
+		];
+
 		let vector_mesh_tooltip = "Allow tools to produce vector meshes, where more than two segments can connect to an anchor point.\n\nCurrently this does not properly handle line joins and fills.";
 		let vector_meshes = vec![
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
@@ -188,6 +304,28 @@ impl PreferencesDialogMessageHandler {
 			TextLabel::new("Vector Meshes").table_align(true).tooltip(vector_mesh_tooltip).widget_holder(),
 		];
 
+		let cost_heatmap_tooltip = "Overlay the artwork with each layer's bounds, colored from green to red by how long the layer took to evaluate in the last render";
+		let cost_heatmap = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.cost_heatmap)
+				.tooltip(cost_heatmap_tooltip)
+				.on_update(|checkbox_input: &CheckboxInput| PreferencesMessage::CostHeatmap { enabled: checkbox_input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Layer Cost Heatmap").table_align(true).tooltip(cost_heatmap_tooltip).widget_holder(),
+		];
+
+		let path_gesture_recording_tooltip = "Record the gestures made with the Path tool, which can then be exported from its tool options and replayed to reproduce a bug";
+		let path_gesture_recording = vec![
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.path_gesture_recording)
+				.tooltip(path_gesture_recording_tooltip)
+				.on_update(|checkbox_input: &CheckboxInput| PreferencesMessage::PathGestureRecording { enabled: checkbox_input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Record Path Tool Gestures").table_align(true).tooltip(path_gesture_recording_tooltip).widget_holder(),
+		];
+
 		// TODO: Reenable when Imaginate is restored
 		// let imaginate_server_hostname = vec![
 		// 	TextLabel::new("Imaginate").min_width(60).italic(true).widget_holder(),
@@ -217,11 +355,26 @@ impl PreferencesDialogMessageHandler {
 			LayoutGroup::Row { widgets: editing_header },
 			LayoutGroup::Row { widgets: selection_label },
 			LayoutGroup::Row { widgets: selection_mode },
+			LayoutGroup::Row { widgets: drag_promotion },
+			LayoutGroup::Row { widgets: drag_promotion_distance },
+			LayoutGroup::Row { widgets: nudge_acceleration_label },
+			LayoutGroup::Row { widgets: nudge_acceleration },
+			LayoutGroup::Row {
+				widgets: confirm_destructive_point_edits,
+			},
+			LayoutGroup::Row {
+				widgets: display_decimal_places_label,
+			},
+			LayoutGroup::Row { widgets: display_decimal_places },
 			LayoutGroup::Row { widgets: experimental_header },
 			LayoutGroup::Row { widgets: node_graph_wires_label },
 			LayoutGroup::Row { widgets: graph_wire_style },
 			LayoutGroup::Row { widgets: use_vello },
+			LayoutGroup::Row { widgets: viewport_renderer_label },
+			LayoutGroup::Row { widgets: viewport_renderer },
 			LayoutGroup::Row { widgets: vector_meshes },
+			LayoutGroup::Row { widgets: cost_heatmap },
+			LayoutGroup::Row { widgets: path_gesture_recording },
 			// LayoutGroup::Row { widgets: imaginate_server_hostname },
 			// LayoutGroup::Row { widgets: imaginate_refresh_frequency },
 		]))
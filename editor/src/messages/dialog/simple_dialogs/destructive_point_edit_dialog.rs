@@ -0,0 +1,66 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+use crate::messages::tool::tool_messages::path_tool::DestructivePointEdit;
+
+/// A dialog for confirming the deletion of points or breaking of paths on a layer which many other nodes depend on.
+pub struct DestructivePointEditDialog {
+	/// The number of nodes downstream from the edited layer which aren't layers themselves.
+	pub dependents: usize,
+	pub edit: DestructivePointEdit,
+}
+
+impl DialogLayoutHolder for DestructivePointEditDialog {
+	const ICON: &'static str = "Warning";
+	const TITLE: &'static str = "Editing Path";
+
+	fn layout_buttons(&self) -> Layout {
+		let edit = self.edit;
+		let widgets = vec![
+			TextButton::new("Proceed")
+				.emphasized(true)
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![PathToolMessage::ApplyDestructivePointEdit { edit }.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Proceed and Don't Ask Again")
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![
+							PreferencesMessage::ConfirmDestructivePointEdits { enabled: false }.into(),
+							PathToolMessage::ApplyDestructivePointEdit { edit }.into(),
+						],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for DestructivePointEditDialog {
+	fn layout(&self) -> Layout {
+		let nodes = if self.dependents == 1 { "node depends" } else { "nodes depend" };
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new("Edit a path other nodes depend on?").bold(true).widget_holder()],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new(format!(
+						"{} {nodes} on this layer, such as boolean operations and instancers.\nDeleting points or breaking the path may change large parts of the document.",
+						self.dependents
+					))
+					.multiline(true)
+					.widget_holder(),
+				],
+			},
+		]))
+	}
+}
@@ -0,0 +1,83 @@
+use crate::messages::layout::utility_types::number_format::format_number;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::prelude::*;
+use crate::node_graph_executor::{DocumentStatistics, PathStatistics};
+
+/// A dialog listing the number of points, segments, and subpaths, and the path length, of every layer in the document.
+pub struct DocumentStatisticsDialog {
+	pub layers: Vec<(String, PathStatistics)>,
+	pub total: PathStatistics,
+	/// The number of decimal places the path lengths are rounded to.
+	pub decimal_places: u32,
+}
+
+impl DocumentStatisticsDialog {
+	pub fn new(document: &DocumentMessageHandler, statistics: &DocumentStatistics, decimal_places: u32) -> Self {
+		let mut layers = Vec::new();
+		let mut total = PathStatistics::default();
+
+		for layer in document.metadata().all_layers() {
+			let Some(&layer_statistics) = statistics.layers.get(&layer.to_node()) else { continue };
+
+			// Group layers already include their children, so only the outermost measured layers contribute to the total
+			let measured_ancestor = layer
+				.ancestors(document.metadata())
+				.skip(1)
+				.any(|ancestor| ancestor != LayerNodeIdentifier::ROOT_PARENT && statistics.layers.contains_key(&ancestor.to_node()));
+			if !measured_ancestor {
+				total.add(layer_statistics);
+			}
+
+			layers.push((document.network_interface.display_name(&layer.to_node(), &[]), layer_statistics));
+		}
+
+		Self { layers, total, decimal_places }
+	}
+
+	fn statistics_row(&self, name: &str, statistics: &PathStatistics, bold: bool) -> LayoutGroup {
+		let PathStatistics { points, segments, subpaths, length } = statistics;
+		let columns = [
+			name.to_string(),
+			points.to_string(),
+			segments.to_string(),
+			subpaths.to_string(),
+			format_number(*length, self.decimal_places),
+		];
+
+		LayoutGroup::Row {
+			widgets: columns
+				.into_iter()
+				.map(|column| TextLabel::new(column).bold(bold).table_align(true).min_width(80).widget_holder())
+				.collect(),
+		}
+	}
+}
+
+impl DialogLayoutHolder for DocumentStatisticsDialog {
+	const ICON: &'static str = "File";
+	const TITLE: &'static str = "Document Statistics";
+
+	fn layout_buttons(&self) -> Layout {
+		let widgets = vec![TextButton::new("OK").emphasized(true).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder()];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+impl LayoutHolder for DocumentStatisticsDialog {
+	fn layout(&self) -> Layout {
+		let header = ["Layer", "Points", "Segments", "Subpaths", "Length"];
+		let mut rows = vec![LayoutGroup::Row {
+			widgets: header
+				.into_iter()
+				.map(|column| TextLabel::new(column).italic(true).table_align(true).min_width(80).widget_holder())
+				.collect(),
+		}];
+
+		rows.extend(self.layers.iter().map(|(name, statistics)| self.statistics_row(name, statistics, false)));
+		rows.push(self.statistics_row("Total", &self.total, true));
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
+	}
+}
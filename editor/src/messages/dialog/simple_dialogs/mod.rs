@@ -3,6 +3,8 @@ mod close_all_documents_dialog;
 mod close_document_dialog;
 mod coming_soon_dialog;
 mod demo_artwork_dialog;
+mod destructive_point_edit_dialog;
+mod document_statistics_dialog;
 mod error_dialog;
 mod licenses_dialog;
 
@@ -12,5 +14,7 @@ pub use close_document_dialog::CloseDocumentDialog;
 pub use coming_soon_dialog::ComingSoonDialog;
 pub use demo_artwork_dialog::ARTWORK;
 pub use demo_artwork_dialog::DemoArtworkDialog;
+pub use destructive_point_edit_dialog::DestructivePointEditDialog;
+pub use document_statistics_dialog::DocumentStatisticsDialog;
 pub use error_dialog::ErrorDialog;
 pub use licenses_dialog::LicensesDialog;
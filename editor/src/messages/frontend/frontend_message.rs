@@ -1,4 +1,4 @@
-use super::utility_types::{FrontendDocumentDetails, MouseCursorIcon};
+use super::utility_types::{ExportStage, FrontendDocumentDetails, MouseCursorIcon};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::node_graph::utility_types::{
 	BoxSelection, ContextMenuInformation, FrontendClickTargets, FrontendGraphInput, FrontendGraphOutput, FrontendNode, FrontendNodeType, FrontendNodeWire, Transform, WirePath,
@@ -6,6 +6,7 @@ use crate::messages::portfolio::document::node_graph::utility_types::{
 use crate::messages::portfolio::document::utility_types::nodes::{JsRawBuffer, LayerPanelEntry, RawBuffer};
 use crate::messages::prelude::*;
 use crate::messages::tool::utility_types::HintData;
+use crate::node_graph_executor::ViewportId;
 use graph_craft::document::NodeId;
 use graphene_core::raster::color::Color;
 use graphene_core::text::Font;
@@ -40,6 +41,9 @@ pub enum FrontendMessage {
 	DisplayEditableTextboxTransform {
 		transform: [f64; 6],
 	},
+	DisplayLinkedAssetsUpdated {
+		urls: Vec<String>,
+	},
 	DisplayRemoveEditableTextbox,
 
 	// Send prefix: Send global, static data to the frontend that is never updated
@@ -86,6 +90,7 @@ pub enum FrontendMessage {
 	TriggerLoadRestAutoSaveDocuments,
 	TriggerLoadPreferences,
 	TriggerOpenDocument,
+	TriggerOpenEditSession,
 	TriggerPaste,
 	TriggerSavePreferences {
 		preferences: PreferencesMessageHandler,
@@ -157,6 +162,14 @@ pub enum FrontendMessage {
 		layout_target: LayoutTarget,
 		diff: Vec<WidgetDiff>,
 	},
+	UpdatePointTableState {
+		open: bool,
+	},
+	UpdatePointTableLayout {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		diff: Vec<WidgetDiff>,
+	},
 	UpdateImportReorderIndex {
 		#[serde(rename = "importIndex")]
 		index: Option<usize>,
@@ -189,8 +202,15 @@ pub enum FrontendMessage {
 		diff: Vec<WidgetDiff>,
 	},
 	UpdateDocumentArtwork {
+		#[serde(rename = "viewportId")]
+		viewport_id: ViewportId,
 		svg: String,
 	},
+	UpdateDocumentArtworkDirtyRegion {
+		/// The viewport space bounds of the artwork changed by the latest render of the main viewport, or `None` if all of it may have changed.
+		#[serde(rename = "dirtyRegion")]
+		dirty_region: Option<[(f64, f64); 2]>,
+	},
 	UpdateDocumentBarLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -274,6 +294,33 @@ pub enum FrontendMessage {
 		id: NodeId,
 		value: String,
 	},
+	UpdateRenderStatistics {
+		#[serde(rename = "totalTime")]
+		total_time: f64,
+		#[serde(rename = "executionTime")]
+		execution_time: f64,
+		#[serde(rename = "nodeCount")]
+		node_count: usize,
+		#[serde(rename = "mostlyCached")]
+		mostly_cached: bool,
+	},
+	UpdateGraphBusyState {
+		pending: usize,
+		#[serde(rename = "pendingExports")]
+		pending_exports: usize,
+	},
+	UpdateApplicationIoStatus {
+		#[serde(rename = "unavailableReason")]
+		unavailable_reason: Option<String>,
+	},
+	UpdateExportProgress {
+		stage: ExportStage,
+		fraction: f64,
+	},
+	UpdateLayerPreview {
+		id: NodeId,
+		preview: Option<String>,
+	},
 	UpdateOpenDocumentsList {
 		#[serde(rename = "openDocuments")]
 		open_documents: Vec<FrontendDocumentDetails>,
@@ -1,5 +1,6 @@
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::prelude::*;
+use glam::DVec2;
 
 #[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
 pub struct FrontendDocumentDetails {
@@ -47,10 +48,52 @@ impl FileType {
 	}
 }
 
+/// The stages an export goes through, ending with the one it finished, was cancelled, or failed at.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum ExportStage {
+	#[default]
+	Compile,
+	Evaluate,
+	Encode,
+	Finished,
+	Cancelled,
+	Failed,
+}
+
+impl ExportStage {
+	/// How far along the export is once it reaches this stage, from 0 to 1.
+	pub fn fraction(self) -> f64 {
+		match self {
+			ExportStage::Compile => 0.,
+			ExportStage::Evaluate => 1. / 3.,
+			ExportStage::Encode => 2. / 3.,
+			ExportStage::Finished | ExportStage::Cancelled | ExportStage::Failed => 1.,
+		}
+	}
+
+	/// Whether the export is over, so it will report no further progress.
+	pub fn is_over(self) -> bool {
+		matches!(self, ExportStage::Finished | ExportStage::Cancelled | ExportStage::Failed)
+	}
+
+	pub fn label(self) -> &'static str {
+		match self {
+			ExportStage::Compile => "Compiling",
+			ExportStage::Evaluate => "Evaluating",
+			ExportStage::Encode => "Encoding",
+			ExportStage::Finished => "Finished",
+			ExportStage::Cancelled => "Cancelled",
+			ExportStage::Failed => "Failed",
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
 pub enum ExportBounds {
 	#[default]
 	AllArtwork,
 	Selection,
 	Artboard(LayerNodeIdentifier),
+	/// A rectangle given by two opposite corners in document space.
+	Custom([DVec2; 2]),
 }
@@ -1,4 +1,4 @@
-use crate::consts::{BIG_NUDGE_AMOUNT, BRUSH_SIZE_CHANGE_KEYBOARD, NUDGE_AMOUNT};
+use crate::consts::{BIG_NUDGE_AMOUNT, BRUSH_SIZE_CHANGE_KEYBOARD, NUDGE_AMOUNT, PATH_RESHAPE_RADIUS_CHANGE_KEYBOARD};
 use crate::messages::input_mapper::key_mapping::MappingVariant;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeyStates};
 use crate::messages::input_mapper::utility_types::input_mouse::MouseButton;
@@ -54,6 +54,9 @@ pub fn input_mappings() -> Mapping {
 		// Hack to prevent Left Click + Accel + Z combo (this effectively blocks you from making a double undo with AbortTransaction)
 		entry!(KeyDown(KeyZ); modifiers=[Accel, MouseLeft], action_dispatch=DocumentMessage::Noop),
 		//
+		// Only available while the canvas probe debug mode is enabled, taking priority over the tools
+		entry!(KeyDown(MouseLeft); action_dispatch=DocumentMessage::ProbeCanvas),
+		//
 		// NodeGraphMessage
 		entry!(KeyDown(MouseLeft); action_dispatch=NodeGraphMessage::PointerDown {shift_click: false, control_click: false, alt_click: false, right_click: false}),
 		entry!(KeyDown(MouseLeft); modifiers=[Shift], action_dispatch=NodeGraphMessage::PointerDown {shift_click: true, control_click: false, alt_click: false, right_click: false}),
@@ -134,6 +137,8 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
 		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
 		entry!(KeyDown(ArrowRight); action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(Enter); action_dispatch=ArtboardToolMessage::ConfirmExportBounds),
+		entry!(KeyDown(Escape); action_dispatch=ArtboardToolMessage::ConfirmExportBounds),
 		entry!(KeyDown(MouseRight); action_dispatch=ArtboardToolMessage::Abort),
 		entry!(KeyDown(Escape); action_dispatch=ArtboardToolMessage::Abort),
 		//
@@ -211,21 +216,38 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(Backspace); modifiers=[Accel], action_dispatch=PathToolMessage::DeleteAndBreakPath),
 		entry!(KeyDown(Delete); modifiers=[Shift], action_dispatch=PathToolMessage::BreakPath),
 		entry!(KeyDown(Backspace); modifiers=[Shift], action_dispatch=PathToolMessage::BreakPath),
+		entry!(KeyDown(Delete); modifiers=[Alt], action_dispatch=PathToolMessage::DissolvePreservingShape),
+		entry!(KeyDown(Backspace); modifiers=[Alt], action_dispatch=PathToolMessage::DissolvePreservingShape),
 		entry!(KeyDownNoRepeat(Tab); action_dispatch=PathToolMessage::SwapSelectedHandles),
+		entry!(KeyDownNoRepeat(Tab); action_dispatch=PathToolMessage::CycleSegment),
+		entry!(KeyDownNoRepeat(KeyK); action_dispatch=PathToolMessage::CopySegmentShape),
+		entry!(KeyDownNoRepeat(KeyH); modifiers=[Alt], action_dispatch=PathToolMessage::CycleOverlayMode),
 		entry!(KeyDown(MouseLeft); action_dispatch=PathToolMessage::MouseDown { extend_selection: Shift, lasso_select: Control, handle_drag_from_anchor: Alt }),
+		entry!(KeyDown(MouseRight); modifiers=[Shift], action_dispatch=PathToolMessage::PlacePivot),
 		entry!(KeyDown(MouseRight); action_dispatch=PathToolMessage::RightClick),
 		entry!(KeyDown(Escape); action_dispatch=PathToolMessage::Escape),
 		entry!(KeyDown(KeyG); action_dispatch=PathToolMessage::GRS { key: KeyG }),
+		entry!(KeyDown(KeyR); modifiers=[Shift], action_dispatch=PathToolMessage::RetractHandles),
+		entry!(KeyDown(KeyC); modifiers=[Shift], action_dispatch=PathToolMessage::ToggleSelectedPointsSmoothSharp),
 		entry!(KeyDown(KeyR); action_dispatch=PathToolMessage::GRS { key: KeyR }),
 		entry!(KeyDown(KeyS); action_dispatch=PathToolMessage::GRS { key: KeyS }),
-		entry!(PointerMove; refresh_keys=[KeyC, Space, Control, Shift, Alt], action_dispatch=PathToolMessage::PointerMove { toggle_colinear: KeyC, equidistant: Alt, move_anchor_with_handles: Space, snap_angle: Shift, lock_angle: Control, delete_segment: Alt }),
+		entry!(PointerMove; refresh_keys=[KeyC, KeyW, KeyD, Space, Control, Shift, Alt], action_dispatch=PathToolMessage::PointerMove { toggle_colinear: KeyC, equidistant: Alt, move_anchor_with_handles: Space, snap_angle: Shift, lock_angle: Control, lock_length: KeyD, delete_segment: Alt, parallel_handles: KeyW, extend_selection: Shift, shrink_selection: Alt }),
 		entry!(KeyDown(Delete); action_dispatch=PathToolMessage::Delete),
 		entry!(KeyDown(KeyA); modifiers=[Accel], action_dispatch=PathToolMessage::SelectAllAnchors),
 		entry!(KeyDown(KeyA); modifiers=[Accel, Shift], action_dispatch=PathToolMessage::DeselectAllPoints),
+		entry!(KeyDown(KeyA); modifiers=[Accel, Alt], action_dispatch=PathToolMessage::SelectAllAnchorsInDocument),
+		entry!(KeyDown(KeyC); modifiers=[Accel, Alt], action_dispatch=PathToolMessage::CopySelectedPoints),
+		entry!(KeyDown(KeyV); modifiers=[Accel, Alt], action_dispatch=PathToolMessage::PasteAtCursor),
+		entry!(KeyDown(KeyV); modifiers=[Accel, Alt, Shift], action_dispatch=PathToolMessage::PasteInPlace),
+		entry!(KeyDown(KeyB); modifiers=[Accel], action_dispatch=PathToolMessage::SelectBetween { longer_route: Shift }),
+		entry!(KeyDown(Equal); modifiers=[Alt], action_dispatch=PathToolMessage::GrowSelection),
+		entry!(KeyDown(Minus); modifiers=[Alt], action_dispatch=PathToolMessage::ShrinkSelection),
 		entry!(KeyDown(Backspace); action_dispatch=PathToolMessage::Delete),
 		entry!(KeyUp(MouseLeft); action_dispatch=PathToolMessage::DragStop { extend_selection: Shift, shrink_selection: Alt }),
 		entry!(KeyDown(Enter); action_dispatch=PathToolMessage::Enter { extend_selection: Shift, shrink_selection: Alt }),
+		entry!(WheelScroll; modifiers=[KeyO], action_dispatch=PathToolMessage::OffsetScroll),
 		entry!(DoubleClick(MouseButton::Left); action_dispatch=PathToolMessage::FlipSmoothSharp),
+		entry!(KeyDown(ArrowRight); modifiers=[Alt], action_dispatch=PathToolMessage::SelectNextPoint { extend: Shift }),
 		entry!(KeyDown(ArrowRight); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: 0. }),
 		entry!(KeyDown(ArrowRight); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0. }),
 		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
@@ -251,6 +273,9 @@ pub fn input_mappings() -> Mapping {
 		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
 		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
 		entry!(KeyDown(KeyJ); modifiers=[Accel], action_dispatch=ToolMessage::Path(PathToolMessage::ClosePath)),
+		entry!(KeyDown(KeyJ); modifiers=[Accel, Shift], action_dispatch=PathToolMessage::JoinEndpoints),
+		entry!(KeyDown(BracketLeft); action_dispatch=PathToolMessage::AdjustReshapeRadius { change: -PATH_RESHAPE_RADIUS_CHANGE_KEYBOARD }),
+		entry!(KeyDown(BracketRight); action_dispatch=PathToolMessage::AdjustReshapeRadius { change: PATH_RESHAPE_RADIUS_CHANGE_KEYBOARD }),
 		//
 		// PenToolMessage
 		entry!(PointerMove; refresh_keys=[Control, Alt, Shift, KeyC], action_dispatch=PenToolMessage::PointerMove { snap_angle: Shift, break_handle: Alt, lock_angle: Control, colinear: KeyC, move_anchor_with_handles: Space }),
@@ -78,12 +78,23 @@ impl ScrollDelta {
 	}
 }
 
+/// The pressure and tilt reported by a pen tablet, which other pointing devices lack.
+#[derive(Debug, Copy, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PenState {
+	/// From 0 (no pressure) to 1 (the most pressure the pen can report).
+	pub pressure: f64,
+	/// The angles in degrees, from -90 to 90, which the pen leans away from upright towards the right (X) and towards the user (Y).
+	pub tilt: DVec2,
+}
+
 // TODO: Document the difference between this and EditorMouseState
 #[derive(Debug, Copy, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct MouseState {
 	pub position: ViewportPosition,
 	pub mouse_keys: MouseKeys,
 	pub scroll_delta: ScrollDelta,
+	/// `None` unless the pointer is a pen.
+	pub pen: Option<PenState>,
 }
 
 impl MouseState {
@@ -100,6 +111,8 @@ pub struct EditorMouseState {
 	pub editor_position: EditorPosition,
 	pub mouse_keys: MouseKeys,
 	pub scroll_delta: ScrollDelta,
+	/// `None` unless the pointer is a pen.
+	pub pen: Option<PenState>,
 }
 
 impl EditorMouseState {
@@ -110,14 +123,22 @@ impl EditorMouseState {
 			editor_position,
 			mouse_keys,
 			scroll_delta: ScrollDelta::default(),
+			pen: None,
 		}
 	}
 
+	/// Adds the pressure and tilt of a pen, if the pointer is one, as indicated by the pressure being given.
+	pub fn with_pen(mut self, pressure: Option<f64>, tilt: DVec2) -> Self {
+		self.pen = pressure.map(|pressure| PenState { pressure, tilt });
+		self
+	}
+
 	pub fn to_mouse_state(&self, active_viewport_bounds: &ViewportBounds) -> MouseState {
 		MouseState {
 			position: self.editor_position - active_viewport_bounds.top_left,
 			mouse_keys: self.mouse_keys,
 			scroll_delta: self.scroll_delta,
+			pen: self.pen,
 		}
 	}
 }
@@ -1,17 +1,52 @@
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, ModifierKeys};
-use crate::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, ViewportBounds};
+use crate::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, MouseKeys, ViewportBounds};
 use crate::messages::prelude::*;
+use glam::DVec2;
 
 #[impl_message(Message, InputPreprocessor)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum InputPreprocessorMessage {
-	BoundsOfViewports { bounds_of_viewports: Vec<ViewportBounds> },
-	DoubleClick { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
-	KeyDown { key: Key, key_repeat: bool, modifier_keys: ModifierKeys },
-	KeyUp { key: Key, key_repeat: bool, modifier_keys: ModifierKeys },
-	PointerDown { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
-	PointerMove { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
-	PointerUp { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
-	CurrentTime { timestamp: u64 },
-	WheelScroll { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
+	BoundsOfViewports {
+		bounds_of_viewports: Vec<ViewportBounds>,
+	},
+	DoubleClick {
+		editor_mouse_state: EditorMouseState,
+		modifier_keys: ModifierKeys,
+	},
+	KeyDown {
+		key: Key,
+		key_repeat: bool,
+		modifier_keys: ModifierKeys,
+	},
+	KeyUp {
+		key: Key,
+		key_repeat: bool,
+		modifier_keys: ModifierKeys,
+	},
+	PointerDown {
+		editor_mouse_state: EditorMouseState,
+		modifier_keys: ModifierKeys,
+	},
+	PointerMove {
+		editor_mouse_state: EditorMouseState,
+		modifier_keys: ModifierKeys,
+	},
+	PointerUp {
+		editor_mouse_state: EditorMouseState,
+		modifier_keys: ModifierKeys,
+	},
+	/// Sets the input state recorded alongside a replayed message, without sending any input events of its own.
+	RestoreState {
+		mouse_position: DVec2,
+		mouse_keys: MouseKeys,
+		modifier_keys: ModifierKeys,
+		time: u64,
+	},
+	CurrentTime {
+		timestamp: u64,
+	},
+	WheelScroll {
+		editor_mouse_state: EditorMouseState,
+		modifier_keys: ModifierKeys,
+	},
 }
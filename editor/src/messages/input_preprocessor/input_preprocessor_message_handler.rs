@@ -95,6 +95,18 @@ impl MessageHandler<InputPreprocessorMessage, InputPreprocessorMessageData> for
 
 				self.translate_mouse_event(mouse_state, false, responses);
 			}
+			InputPreprocessorMessage::RestoreState {
+				mouse_position,
+				mouse_keys,
+				modifier_keys,
+				time,
+			} => {
+				// The key events for the changed modifiers are discarded, since the messages they caused are replayed as well
+				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, &mut VecDeque::new());
+				self.mouse.position = mouse_position;
+				self.mouse.mouse_keys = mouse_keys;
+				self.time = time;
+			}
 			InputPreprocessorMessage::CurrentTime { timestamp } => {
 				responses.add(AnimationMessage::SetTime(timestamp as f64));
 				self.time = timestamp;
@@ -206,6 +218,7 @@ mod test {
 			editor_position: (4., 809.).into(),
 			mouse_keys: MouseKeys::default(),
 			scroll_delta: ScrollDelta::default(),
+			pen: None,
 		};
 		let modifier_keys = ModifierKeys::ALT;
 		let message = InputPreprocessorMessage::PointerMove { editor_mouse_state, modifier_keys };
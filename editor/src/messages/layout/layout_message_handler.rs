@@ -96,6 +96,7 @@ impl LayoutMessageHandler {
 					WidgetValueAction::Update => {
 						let update_value = value.as_bool().expect("CheckboxInput update was not of type: bool");
 						checkbox_input.checked = update_value;
+						checkbox_input.indeterminate = false;
 						(checkbox_input.on_update.callback)(checkbox_input)
 					}
 				};
@@ -263,6 +264,13 @@ impl LayoutMessageHandler {
 					Value::String(str) => match str.as_str() {
 						"Increment" => responses.add((number_input.increment_callback_increase.callback)(number_input)),
 						"Decrement" => responses.add((number_input.increment_callback_decrease.callback)(number_input)),
+						expression if number_input.forwards_expressions => {
+							number_input.value = None;
+							number_input.expression = Some(expression.to_string());
+							let callback_message = (number_input.on_update.callback)(number_input);
+							number_input.expression = None;
+							responses.add(callback_message);
+						}
 						_ => panic!("Invalid string found when updating `NumberInput`"),
 					},
 					_ => {}
@@ -427,6 +435,7 @@ impl LayoutMessageHandler {
 			LayoutTarget::LayersPanelControlBar => FrontendMessage::UpdateLayersPanelControlBarLayout { layout_target, diff },
 			LayoutTarget::MenuBar => unreachable!("Menu bar is not diffed"),
 			LayoutTarget::NodeGraphControlBar => FrontendMessage::UpdateNodeGraphControlBarLayout { layout_target, diff },
+			LayoutTarget::PointTable => FrontendMessage::UpdatePointTableLayout { layout_target, diff },
 			LayoutTarget::PropertiesSections => FrontendMessage::UpdatePropertyPanelSectionsLayout { layout_target, diff },
 			LayoutTarget::Spreadsheet => FrontendMessage::UpdateSpreadsheetLayout { layout_target, diff },
 			LayoutTarget::ToolOptions => FrontendMessage::UpdateToolOptionsLayout { layout_target, diff },
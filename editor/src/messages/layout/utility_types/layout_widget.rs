@@ -37,6 +37,8 @@ pub enum LayoutTarget {
 	MenuBar,
 	/// Bar at the top of the node graph containing the location and the "Preview" and "Hide" buttons.
 	NodeGraphControlBar,
+	/// The table of the selected points and their coordinates in the Point Table panel.
+	PointTable,
 	/// The body of the Properties panel containing many collapsable sections.
 	PropertiesSections,
 	/// The spredsheet panel allows for the visualisation of data in the graph.
@@ -1,4 +1,5 @@
 pub mod layout_widget;
+pub mod number_format;
 pub mod widgets;
 
 pub mod widget_prelude {
@@ -0,0 +1,98 @@
+//! Shared formatting for numbers shown to the user, so readouts across the editor round consistently.
+//! Rounding is only ever applied for display: values are stored and parsed at full precision.
+
+use glam::DVec2;
+use math_parser::ast;
+use math_parser::context::{EvalContext, NothingMap, ValueProvider};
+use math_parser::value::Value;
+
+/// Formats a number rounded to at most the given number of decimal places, without trailing zeros, so `99.999999999` reads as `100`.
+pub fn format_number(value: f64, decimal_places: u32) -> String {
+	if !value.is_finite() {
+		return value.to_string();
+	}
+
+	let formatted = format!("{value:.*}", decimal_places as usize);
+	let trimmed = if formatted.contains('.') {
+		formatted.trim_end_matches('0').trim_end_matches('.')
+	} else {
+		&formatted
+	};
+
+	// Avoid showing "-0" for tiny negative values that round to zero
+	if trimmed == "-0" { "0".to_string() } else { trimmed.to_string() }
+}
+
+/// Formats a point as `(x, y)` with each coordinate rounded by [`format_number`].
+pub fn format_point(point: DVec2, decimal_places: u32) -> String {
+	format!("({}, {})", format_number(point.x, decimal_places), format_number(point.y, decimal_places))
+}
+
+/// Parses a typed or pasted number at full precision, ignoring surrounding whitespace and a trailing unit like `px`, `°`, or `%`.
+pub fn parse_number(text: &str) -> Option<f64> {
+	let text = text.trim();
+	let number = text.trim_end_matches(|c: char| c.is_alphabetic() || c == '°' || c == '%').trim_end();
+
+	number.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// Evaluates a math expression typed into a number field, like `120/3+5` or `x+10`, with the given variables substituted by name regardless of case.
+/// Expressions which don't parse, use unknown variables, or don't come out to a finite real number (like `1/0`) give `None`.
+pub fn evaluate_expression(expression: &str, variables: &[(&str, f64)]) -> Option<f64> {
+	let (node, _unit) = ast::Node::try_parse_from_str(expression.trim()).ok()?;
+	let value = node.eval(&EvalContext::new(ExpressionVariables(variables), NothingMap)).ok()?;
+
+	value.as_real().filter(|value| value.is_finite())
+}
+
+/// The variables an expression typed into a number field may refer to.
+struct ExpressionVariables<'a>(&'a [(&'a str, f64)]);
+
+impl ValueProvider for ExpressionVariables<'_> {
+	fn get_value(&self, name: &str) -> Option<Value> {
+		self.0.iter().find(|(variable, _)| variable.eq_ignore_ascii_case(name)).map(|&(_, value)| Value::from_f64(value))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn display_rounds_without_trailing_zeros() {
+		assert_eq!(format_number(99.999999999, 3), "100");
+		assert_eq!(format_number(0.12345, 3), "0.123");
+		assert_eq!(format_number(1.5, 3), "1.5");
+		assert_eq!(format_number(12., 0), "12");
+		assert_eq!(format_number(-0.0001, 3), "0");
+		assert_eq!(format_point(DVec2::new(1.23456, -7.), 2), "(1.23, -7)");
+	}
+
+	#[test]
+	fn parsing_keeps_the_precision_display_rounds_away() {
+		let typed = "0.12345";
+		let value = parse_number(typed).unwrap();
+
+		assert_eq!(value, 0.12345);
+		assert_eq!(format_number(value, 3), "0.123");
+		assert_eq!(parse_number(&format_number(value, 8)), Some(value), "Showing enough decimal places should round trip");
+		assert_eq!(parse_number(" 10 px "), Some(10.));
+		assert_eq!(parse_number("-3px"), Some(-3.));
+		assert_eq!(parse_number("45°"), Some(45.));
+		assert_eq!(parse_number("px"), None);
+	}
+
+	#[test]
+	fn expressions_substitute_variables_and_reject_non_finite_results() {
+		let variables = [("x", 50.), ("y", -20.)];
+
+		assert_eq!(evaluate_expression("120/3+5", &variables), Some(45.));
+		assert_eq!(evaluate_expression(" x+10 ", &variables), Some(60.));
+		assert_eq!(evaluate_expression("X*2 - y", &variables), Some(120.));
+		assert_eq!(evaluate_expression("1/0", &variables), None, "Division by zero shouldn't move anything to infinity");
+		assert_eq!(evaluate_expression("0/0", &variables), None);
+		assert_eq!(evaluate_expression("sqrt(-1)", &variables), None);
+		assert_eq!(evaluate_expression("z+1", &variables), None, "Unknown variables should be rejected");
+		assert_eq!(evaluate_expression("x+", &variables), None);
+	}
+}
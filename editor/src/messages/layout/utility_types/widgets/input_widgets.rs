@@ -12,6 +12,9 @@ pub struct CheckboxInput {
 	#[widget_builder(constructor)]
 	pub checked: bool,
 
+	/// Shows the mixed state of a checkbox standing for several values which disagree, until it is clicked to check it.
+	pub indeterminate: bool,
+
 	pub disabled: bool,
 
 	pub icon: String,
@@ -35,6 +38,7 @@ impl Default for CheckboxInput {
 	fn default() -> Self {
 		Self {
 			checked: false,
+			indeterminate: false,
 			disabled: false,
 			icon: "Checkmark".into(),
 			tooltip: Default::default(),
@@ -204,10 +208,22 @@ pub struct NumberInput {
 	#[serde(rename = "rangeMax")]
 	pub range_max: Option<f64>,
 
+	/// Text the frontend can't evaluate on its own (such as an expression referring to variables like `x`) is sent back to the `on_update` callback in `expression` instead of being discarded.
+	#[serde(rename = "forwardsExpressions")]
+	pub forwards_expressions: bool,
+
+	/// The text typed by the user while `on_update` is being called with a forwarded expression, in which case `value` is `None`.
+	#[serde(skip)]
+	#[widget_builder(skip)]
+	pub expression: Option<String>,
+
 	// Styling
 	#[serde(rename = "minWidth")]
 	pub min_width: u32,
 
+	/// Marks the input as holding a rejected value.
+	pub invalid: bool,
+
 	// Callbacks
 	#[serde(skip)]
 	#[derivative(Debug = "ignore", PartialEq = "ignore")]
@@ -3,16 +3,17 @@ use crate::messages::input_mapper::utility_types::input_keyboard::Key;
 use crate::messages::portfolio::document::overlays::utility_types::OverlayContext;
 use crate::messages::portfolio::document::overlays::utility_types::OverlaysType;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GridSnapping};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GridSnapping, ViewportRenderer};
 use crate::messages::portfolio::utility_types::PanelType;
 use crate::messages::prelude::*;
-use glam::DAffine2;
+use crate::messages::tool::tool_messages::path_tool::DestructivePointEdit;
+use glam::{DAffine2, DVec2};
 use graph_craft::document::NodeId;
 use graphene_core::Color;
 use graphene_core::raster::BlendMode;
 use graphene_core::raster::Image;
 use graphene_core::vector::style::ViewMode;
-use graphene_std::renderer::ClickTarget;
+use graphene_std::renderer::{ClickTarget, SubpathClickRegion};
 use graphene_std::transform::Footprint;
 
 #[impl_message(Message, PortfolioMessage, Document)]
@@ -32,12 +33,25 @@ pub enum DocumentMessage {
 	PropertiesPanel(PropertiesPanelMessage),
 
 	// Messages
+	AddDirtyRegion {
+		bounds: [DVec2; 2],
+	},
+	/// Counts an operation which created ids in the layer, so the next one derives different ids.
+	AdvanceIdCounter {
+		layer: LayerNodeIdentifier,
+	},
 	AlignSelectedLayers {
 		axis: AlignAxis,
 		aggregate: AlignAggregate,
 	},
 	RemoveArtboards,
+	CanvasProbeOverlays(OverlayContext),
 	ClearLayersPanel,
+	/// Applies the edit to the selected points of the layers, first asking for confirmation if many other nodes depend on one of them.
+	ConfirmDestructivePointEdit {
+		layers: Vec<LayerNodeIdentifier>,
+		edit: DestructivePointEdit,
+	},
 	CreateEmptyFolder,
 	DeleteNode {
 		node_id: NodeId,
@@ -104,6 +118,7 @@ pub enum DocumentMessage {
 		mouse: Option<(f64, f64)>,
 		parent_and_insert_index: Option<(LayerNodeIdentifier, usize)>,
 	},
+	ProbeCanvas,
 	Redo,
 	RenameDocument {
 		new_name: String,
@@ -154,6 +169,15 @@ pub enum DocumentMessage {
 		closure: Option<for<'a> fn(&'a mut SnappingState) -> &'a mut bool>,
 		snapping_state: bool,
 	},
+	SetCustomExportBounds {
+		bounds: Option<[DVec2; 2]>,
+	},
+	SetDeterministicIds {
+		enabled: bool,
+	},
+	SetSnappingTolerance {
+		tolerance: f64,
+	},
 	SetToNodeOrLayer {
 		node_id: NodeId,
 		is_layer: bool,
@@ -161,6 +185,9 @@ pub enum DocumentMessage {
 	SetViewMode {
 		view_mode: ViewMode,
 	},
+	SetViewportRenderer {
+		renderer: Option<ViewportRenderer>,
+	},
 	AddTransaction,
 	StartTransaction,
 	EndTransaction,
@@ -185,9 +212,19 @@ pub enum DocumentMessage {
 	UpdateClickTargets {
 		click_targets: HashMap<NodeId, Vec<ClickTarget>>,
 	},
+	UpdateSubpathClickRegions {
+		subpath_click_regions: Option<HashMap<NodeId, Vec<SubpathClickRegion>>>,
+	},
 	UpdateClipTargets {
 		clip_targets: HashSet<NodeId>,
 	},
+	UpdateInstanceTransforms {
+		instance_transforms: HashMap<NodeId, Vec<DAffine2>>,
+	},
+	UpdateCanvasProbeColor {
+		position: DVec2,
+		color: Option<Color>,
+	},
 	Undo,
 	UngroupSelectedLayers,
 	UngroupLayer {
@@ -196,6 +233,8 @@ pub enum DocumentMessage {
 	PTZUpdate,
 	SelectionStepBack,
 	SelectionStepForward,
+	/// Moves a canvas frame rendered with a previous document-to-viewport transform to where the overlays, drawn with the current one, expect it.
+	SyncCanvasFrameTransform,
 	WrapContentInArtboard {
 		place_artboard_at_origin: bool,
 	},
@@ -1,12 +1,15 @@
 use super::node_graph::document_node_definitions;
 use super::node_graph::utility_types::Transform;
 use super::overlays::utility_types::Pivot;
+use super::utility_types::canvas_probe::CanvasProbe;
 use super::utility_types::error::EditorError;
 use super::utility_types::misc::{GroupFolderType, SNAP_FUNCTIONS_FOR_BOUNDING_BOXES, SNAP_FUNCTIONS_FOR_PATHS, SnappingOptions, SnappingState};
 use super::utility_types::network_interface::{self, NodeNetworkInterface, TransactionStatus};
 use super::utility_types::nodes::{CollapsedLayers, SelectedNodes};
 use crate::application::{GRAPHITE_GIT_COMMIT_HASH, generate_uuid};
-use crate::consts::{ASYMPTOTIC_EFFECT, COLOR_OVERLAY_GRAY, DEFAULT_DOCUMENT_NAME, FILE_SAVE_SUFFIX, SCALE_EFFECT, SCROLLBAR_SPACING, VIEWPORT_ROTATE_SNAP_INTERVAL};
+use crate::consts::{
+	ASYMPTOTIC_EFFECT, COLOR_OVERLAY_GRAY, DEFAULT_DOCUMENT_NAME, DESTRUCTIVE_POINT_EDIT_DEPENDENTS_THRESHOLD, FILE_SAVE_SUFFIX, SCALE_EFFECT, SCROLLBAR_SPACING, VIEWPORT_ROTATE_SNAP_INTERVAL,
+};
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
@@ -14,8 +17,8 @@ use crate::messages::portfolio::document::node_graph::NodeGraphHandlerData;
 use crate::messages::portfolio::document::overlays::grid_overlays::{grid_overlay, overlay_options};
 use crate::messages::portfolio::document::overlays::utility_types::{OverlaysType, OverlaysVisibilitySettings};
 use crate::messages::portfolio::document::properties_panel::utility_types::PropertiesPanelMessageHandlerData;
-use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, FlipAxis, PTZ};
+use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier, SubpathIndex};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, FlipAxis, PTZ, ViewportRenderer};
 use crate::messages::portfolio::document::utility_types::network_interface::{FlowType, InputConnector, NodeTemplate};
 use crate::messages::portfolio::document::utility_types::nodes::RawBuffer;
 use crate::messages::portfolio::utility_types::PersistentData;
@@ -31,6 +34,7 @@ use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{NodeId, NodeInput, NodeNetwork, OldNodeNetwork};
 use graphene_core::raster::BlendMode;
 use graphene_core::raster::image::ImageFrameTable;
+use graphene_core::vector::ManipulatorPointId;
 use graphene_core::vector::style::ViewMode;
 use graphene_std::renderer::{ClickTarget, Quad};
 use graphene_std::vector::{PointId, path_bool_lib};
@@ -58,7 +62,7 @@ pub struct DocumentMessageHandler {
 	#[serde(skip)]
 	pub node_graph_handler: NodeGraphMessageHandler,
 	#[serde(skip)]
-	overlays_message_handler: OverlaysMessageHandler,
+	pub(crate) overlays_message_handler: OverlaysMessageHandler,
 	#[serde(skip)]
 	properties_panel_message_handler: PropertiesPanelMessageHandler,
 
@@ -83,6 +87,8 @@ pub struct DocumentMessageHandler {
 	/// The current view mode that the user has set for rendering the document within the viewport.
 	/// This is usually "Normal" but can be set to "Outline" or "Pixels" to see the canvas differently.
 	pub view_mode: ViewMode,
+	/// The renderer chosen for drawing this document's artwork in the viewport, or `None` to follow the default set in the preferences.
+	pub viewport_renderer: Option<ViewportRenderer>,
 	/// Sets whether or not all the viewport overlays should be drawn on top of the artwork.
 	/// This includes tool interaction visualizations (like the transform cage and path anchors/handles), the grid, and more.
 	pub overlays_visibility_settings: OverlaysVisibilitySettings,
@@ -94,6 +100,13 @@ pub struct DocumentMessageHandler {
 	pub graph_view_overlay_open: bool,
 	/// The current opacity of the faded node graph background that covers up the artwork.
 	pub graph_fade_artwork_percentage: f64,
+	/// The rectangle, given by two opposite corners in document space, most recently chosen as the custom bounds for exporting the document.
+	pub custom_export_bounds: Option<[DVec2; 2]>,
+	/// Whether the points and segments created by the Path tool get ids derived from the editing operation instead of random ones,
+	/// so the same edits made to copies of the document create the same ids, which keeps them from conflicting when the copies are merged.
+	pub deterministic_ids: bool,
+	/// The number of operations which created ids in each layer, which is part of the context the ids are derived from so repeated operations don't derive the same ids.
+	pub id_counters: HashMap<NodeId, u64>,
 
 	// =============================================
 	// Fields omitted from the saved document format
@@ -105,12 +118,15 @@ pub struct DocumentMessageHandler {
 	/// Path to network that is currently selected. Updated based on the most recently clicked panel.
 	#[serde(skip)]
 	selection_network_path: Vec<NodeId>,
-	/// Stack of document network snapshots for previous history states.
+	/// The points selected with the Path tool in each layer, kept up to date by it so every history state records the point selection to restore along with it.
 	#[serde(skip)]
-	document_undo_history: VecDeque<NodeNetworkInterface>,
-	/// Stack of document network snapshots for future history states.
+	pub selected_points: Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>,
+	/// Stack of document network snapshots, paired with the selected points, for previous history states.
 	#[serde(skip)]
-	document_redo_history: VecDeque<NodeNetworkInterface>,
+	document_undo_history: VecDeque<(NodeNetworkInterface, Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>)>,
+	/// Stack of document network snapshots, paired with the selected points, for future history states.
+	#[serde(skip)]
+	document_redo_history: VecDeque<(NodeNetworkInterface, Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>)>,
 	/// Hash of the document snapshot that was most recently saved to disk by the user.
 	#[serde(skip)]
 	saved_hash: Option<u64>,
@@ -124,6 +140,12 @@ pub struct DocumentMessageHandler {
 	/// Whether or not the editor has executed the network to render the document yet. If this is opened as an inactive tab, it won't be loaded initially because the active tab is prioritized.
 	#[serde(skip)]
 	pub is_loaded: bool,
+	/// The document space bounds of the artwork that tools have reported changing since the last viewport render was submitted, if any reported it.
+	#[serde(skip)]
+	pub dirty_region: Option<[DVec2; 2]>,
+	/// What was found under the last click on the canvas while probing it, which is shown until probing is turned off.
+	#[serde(skip)]
+	pub canvas_probe: Option<CanvasProbe>,
 }
 
 impl Default for DocumentMessageHandler {
@@ -146,22 +168,29 @@ impl Default for DocumentMessageHandler {
 			document_ptz: PTZ::default(),
 			document_mode: DocumentMode::DesignMode,
 			view_mode: ViewMode::default(),
+			viewport_renderer: None,
 			overlays_visibility_settings: OverlaysVisibilitySettings::default(),
 			rulers_visible: true,
 			graph_view_overlay_open: false,
 			snapping_state: SnappingState::default(),
 			graph_fade_artwork_percentage: 80.,
+			custom_export_bounds: None,
+			deterministic_ids: false,
+			id_counters: HashMap::new(),
 			// =============================================
 			// Fields omitted from the saved document format
 			// =============================================
 			breadcrumb_network_path: Vec::new(),
 			selection_network_path: Vec::new(),
+			selected_points: Vec::new(),
 			document_undo_history: VecDeque::new(),
 			document_redo_history: VecDeque::new(),
 			saved_hash: None,
 			auto_saved_hash: None,
 			layer_range_selection_reference: None,
 			is_loaded: false,
+			dirty_region: None,
+			canvas_probe: None,
 		}
 	}
 }
@@ -250,6 +279,15 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				let mut graph_operation_message_handler = GraphOperationMessageHandler {};
 				graph_operation_message_handler.process_message(message, responses, data);
 			}
+			DocumentMessage::AddDirtyRegion { bounds } => {
+				self.dirty_region = Some(match self.dirty_region {
+					Some(dirty_region) => Quad::combine_bounds(dirty_region, bounds),
+					None => bounds,
+				});
+			}
+			DocumentMessage::AdvanceIdCounter { layer } => {
+				*self.id_counters.entry(layer.to_node()).or_default() += 1;
+			}
 			DocumentMessage::AlignSelectedLayers { axis, aggregate } => {
 				let axis = match axis {
 					AlignAxis::X => DVec2::X,
@@ -291,6 +329,11 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			DocumentMessage::RemoveArtboards => {
 				responses.add(GraphOperationMessage::RemoveArtboards);
 			}
+			DocumentMessage::CanvasProbeOverlays(mut overlay_context) => {
+				if let Some(probe) = &self.canvas_probe {
+					probe.draw(&mut overlay_context, self);
+				}
+			}
 			DocumentMessage::ClearLayersPanel => {
 				// Send an empty layer list
 				let data_buffer: RawBuffer = Self::default().serialize_root();
@@ -302,6 +345,19 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					layout_target: LayoutTarget::LayersPanelControlBar,
 				});
 			}
+			DocumentMessage::ConfirmDestructivePointEdit { layers, edit } => {
+				let dependents = layers
+					.iter()
+					.map(|layer| self.network_interface.downstream_dependent_count(&layer.to_node(), &[]))
+					.max()
+					.unwrap_or_default();
+
+				if dependents > DESTRUCTIVE_POINT_EDIT_DEPENDENTS_THRESHOLD {
+					responses.add(DialogMessage::RequestDestructivePointEditDialog { dependents, edit });
+				} else {
+					responses.add(PathToolMessage::ApplyDestructivePointEdit { edit });
+				}
+			}
 			DocumentMessage::CreateEmptyFolder => {
 				let selected_nodes = self.network_interface.selected_nodes();
 				let id = NodeId::new();
@@ -537,6 +593,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			DocumentMessage::GridVisibility(enabled) => {
 				self.snapping_state.grid_snapping = enabled;
 				responses.add(OverlaysMessage::Draw);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
 			}
 			DocumentMessage::GroupSelectedLayers { group_folder_type } => {
 				responses.add(DocumentMessage::AddTransaction);
@@ -899,6 +956,15 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer.to_node()] });
 				responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Select });
 			}
+			DocumentMessage::ProbeCanvas => {
+				let probe = CanvasProbe::new(self, ipp);
+				// The color of raster content is only known to the node runtime, so it's sampled there and arrives later
+				if let Some(layer) = probe.layer {
+					executor.probe_color(layer.to_node(), probe.position, self.metadata().downstream_transform_to_document(layer));
+				}
+				self.canvas_probe = Some(probe);
+				responses.add(OverlaysMessage::Draw);
+			}
 			DocumentMessage::Redo => {
 				if self.network_interface.transaction_status() != TransactionStatus::Finished {
 					return;
@@ -1184,6 +1250,16 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				if let Some(closure) = closure {
 					*closure(&mut self.snapping_state) = snapping_state;
 				}
+				responses.add(OverlaysMessage::Draw);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+			}
+			DocumentMessage::SetCustomExportBounds { bounds } => {
+				self.custom_export_bounds = bounds;
+				responses.add(OverlaysMessage::Draw);
+			}
+			DocumentMessage::SetSnappingTolerance { tolerance } => {
+				self.snapping_state.tolerance = tolerance.max(1.);
+				responses.add(OverlaysMessage::Draw);
 			}
 			DocumentMessage::SetToNodeOrLayer { node_id, is_layer } => {
 				responses.add(DocumentMessage::StartTransaction);
@@ -1194,6 +1270,15 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				self.view_mode = view_mode;
 				responses.add_front(NodeGraphMessage::RunDocumentGraph);
 			}
+			DocumentMessage::SetDeterministicIds { enabled } => {
+				self.deterministic_ids = enabled;
+				responses.add(MenuBarMessage::SendLayout);
+			}
+			DocumentMessage::SetViewportRenderer { renderer } => {
+				self.viewport_renderer = renderer;
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(MenuBarMessage::SendLayout);
+			}
 			DocumentMessage::AddTransaction => {
 				// Reverse order since they are added to the front
 				responses.add_front(DocumentMessage::CommitTransaction);
@@ -1203,7 +1288,7 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 			DocumentMessage::StartTransaction => {
 				self.network_interface.start_transaction();
 				let network_interface_clone = self.network_interface.clone();
-				self.document_undo_history.push_back(network_interface_clone);
+				self.document_undo_history.push_back((network_interface_clone, self.selected_points.clone()));
 				if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
 					self.document_undo_history.pop_front();
 				}
@@ -1305,9 +1390,33 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 					.collect();
 				self.network_interface.update_click_targets(layer_click_targets);
 			}
+			DocumentMessage::UpdateSubpathClickRegions { subpath_click_regions } => {
+				let layer_subpath_click_regions = subpath_click_regions
+					.unwrap_or_default()
+					.into_iter()
+					.filter(|(node_id, _)| self.network_interface.document_network().nodes.contains_key(node_id) && self.network_interface.is_layer(node_id, &[]))
+					.map(|(node_id, regions)| (LayerNodeIdentifier::new(node_id, &self.network_interface, &[]), regions))
+					.collect();
+				self.network_interface.update_subpath_click_regions(layer_subpath_click_regions);
+			}
 			DocumentMessage::UpdateClipTargets { clip_targets } => {
 				self.network_interface.update_clip_targets(clip_targets);
 			}
+			DocumentMessage::UpdateInstanceTransforms { instance_transforms } => {
+				let layer_instance_transforms = instance_transforms
+					.into_iter()
+					.filter(|(node_id, _)| self.network_interface.document_network().nodes.contains_key(node_id) && self.network_interface.is_layer(node_id, &[]))
+					.map(|(node_id, transforms)| (LayerNodeIdentifier::new(node_id, &self.network_interface, &[]), transforms))
+					.collect();
+				self.network_interface.update_instance_transforms(layer_instance_transforms);
+			}
+			DocumentMessage::UpdateCanvasProbeColor { position, color } => {
+				// A response to an earlier probe, since replaced by another click, is ignored
+				if let Some(probe) = self.canvas_probe.as_mut().filter(|probe| probe.position == position) {
+					probe.color = color;
+					responses.add(OverlaysMessage::Draw);
+				}
+			}
 			DocumentMessage::Undo => {
 				if self.network_interface.transaction_status() != TransactionStatus::Finished {
 					return;
@@ -1385,6 +1494,8 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				if !self.graph_view_overlay_open {
 					let transform = self.navigation_handler.calculate_offset_transform(ipp.viewport_bounds.center(), &self.document_ptz);
 					self.network_interface.set_document_to_viewport_transform(transform);
+					// Keep a canvas frame rendered with the previous transform aligned with the overlays until it's rendered again
+					responses.add(DocumentMessage::SyncCanvasFrameTransform);
 					// Ensure selection box is kept in sync with the pointer when the PTZ changes
 					responses.add(SelectToolMessage::PointerMove(SelectToolPointerKeys {
 						axis_align: Key::Shift,
@@ -1426,6 +1537,9 @@ impl MessageHandler<DocumentMessage, DocumentMessageData<'_>> for DocumentMessag
 				self.network_interface.selection_step_forward(&self.selection_network_path);
 				responses.add(BroadcastEvent::SelectionChanged);
 			}
+			DocumentMessage::SyncCanvasFrameTransform => {
+				executor.sync_canvas_frame_transform(self.metadata().document_to_viewport, responses);
+			}
 			DocumentMessage::WrapContentInArtboard { place_artboard_at_origin } => {
 				// Get bounding box of all layers
 				let bounds = self.network_interface.document_bounds_document_space(false);
@@ -1653,6 +1767,23 @@ impl DocumentMessageHandler {
 		self.click_list(ipp).last()
 	}
 
+	/// Find the deepest layer that has been clicked on from a location in viewport space, along with which of its subpaths was hit.
+	/// This requires the subpath click regions to have been collected during the last render, which only happens while the Path tool is active.
+	pub fn click_subpath(&self, ipp: &InputPreprocessorMessageHandler) -> Option<(LayerNodeIdentifier, SubpathIndex)> {
+		let layer = self.click(ipp)?;
+		let click_targets = self.metadata().click_targets(layer)?;
+		let regions = self.metadata().subpath_click_regions(layer)?;
+
+		let document_to_viewport = self.navigation_handler.calculate_offset_transform(ipp.viewport_bounds.center(), &self.document_ptz);
+		let point = document_to_viewport.inverse().transform_point2(ipp.mouse.position);
+		let transform = self.metadata().transform_to_document(layer);
+
+		let subpath_index = regions
+			.iter()
+			.position(|region| click_targets.get(region.click_target_index).is_some_and(|click_target| click_target.intersect_point(point, transform)))?;
+		Some((layer, subpath_index))
+	}
+
 	/// Get the combined bounding box of the click targets of the selected visible layers in viewport space
 	pub fn selected_visible_layers_bounding_box_viewport(&self) -> Option<[DVec2; 2]> {
 		self.network_interface
@@ -1810,17 +1941,20 @@ impl DocumentMessageHandler {
 	}
 
 	pub fn undo_with_history(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
-		let Some(previous_network) = self.undo(ipp, responses) else { return };
+		let Some(previous_state) = self.undo(ipp, responses) else { return };
+
+		// Reselect the points that were selected when this history state was left, as far as they still exist
+		responses.add(PathToolMessage::SelectPoints { points: self.selected_points.clone() });
 
-		self.document_redo_history.push_back(previous_network);
+		self.document_redo_history.push_back(previous_state);
 		if self.document_redo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
 			self.document_redo_history.pop_front();
 		}
 	}
 
-	pub fn undo(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<NodeNetworkInterface> {
+	pub fn undo(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<(NodeNetworkInterface, Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>)> {
 		// If there is no history return and don't broadcast SelectionChanged
-		let mut network_interface = self.document_undo_history.pop_back()?;
+		let (mut network_interface, selected_points) = self.document_undo_history.pop_back()?;
 
 		// Set the previous network navigation metadata to the current navigation metadata
 		network_interface.copy_all_navigation_metadata(&self.network_interface);
@@ -1834,6 +1968,7 @@ impl DocumentMessageHandler {
 		network_interface.load_structure();
 
 		let previous_network = std::mem::replace(&mut self.network_interface, network_interface);
+		let previous_selected_points = std::mem::replace(&mut self.selected_points, selected_points);
 
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
@@ -1842,21 +1977,24 @@ impl DocumentMessageHandler {
 		// TODO: Remove once the footprint is used to load the imports/export distances from the edge
 		responses.add(NodeGraphMessage::SetGridAlignedEdges);
 		responses.add(Message::StartBuffer);
-		Some(previous_network)
+		Some((previous_network, previous_selected_points))
 	}
 	pub fn redo_with_history(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
 		// Push the UpdateOpenDocumentsList message to the queue in order to update the save status of the open documents
-		let Some(previous_network) = self.redo(ipp, responses) else { return };
+		let Some(previous_state) = self.redo(ipp, responses) else { return };
+
+		// Reselect the points that were selected when this history state was left, as far as they still exist
+		responses.add(PathToolMessage::SelectPoints { points: self.selected_points.clone() });
 
-		self.document_undo_history.push_back(previous_network);
+		self.document_undo_history.push_back(previous_state);
 		if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
 			self.document_undo_history.pop_front();
 		}
 	}
 
-	pub fn redo(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<NodeNetworkInterface> {
+	pub fn redo(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) -> Option<(NodeNetworkInterface, Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>)> {
 		// If there is no history return and don't broadcast SelectionChanged
-		let mut network_interface = self.document_redo_history.pop_back()?;
+		let (mut network_interface, selected_points) = self.document_redo_history.pop_back()?;
 
 		// Set the previous network navigation metadata to the current navigation metadata
 		network_interface.copy_all_navigation_metadata(&self.network_interface);
@@ -1867,16 +2005,22 @@ impl DocumentMessageHandler {
 		network_interface.set_document_to_viewport_transform(transform);
 
 		let previous_network = std::mem::replace(&mut self.network_interface, network_interface);
+		let previous_selected_points = std::mem::replace(&mut self.selected_points, selected_points);
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 		responses.add(NodeGraphMessage::SelectedNodesUpdated);
 		responses.add(NodeGraphMessage::ForceRunDocumentGraph);
 
-		Some(previous_network)
+		Some((previous_network, previous_selected_points))
+	}
+
+	/// The number of steps which can currently be undone.
+	pub fn undo_history_len(&self) -> usize {
+		self.document_undo_history.len()
 	}
 
 	pub fn current_hash(&self) -> Option<u64> {
-		self.document_undo_history.iter().last().map(|network| network.document_network().current_hash())
+		self.document_undo_history.iter().last().map(|(network, _)| network.document_network().current_hash())
 	}
 
 	pub fn is_auto_saved(&self) -> bool {
@@ -2484,6 +2628,11 @@ impl DocumentMessageHandler {
 			.selected_nodes()
 			.selected_layers(self.metadata())
 			.all(|layer| self.network_interface.is_locked(&layer.to_node(), &[]));
+		let selection_all_edit_locked = self
+			.network_interface
+			.selected_nodes()
+			.selected_layers(self.metadata())
+			.all(|layer| self.network_interface.is_edit_locked(&layer.to_node(), &[]));
 
 		let widgets = vec![
 			DropdownInput::new(blend_mode_menu_entries)
@@ -2544,6 +2693,15 @@ impl DocumentMessageHandler {
 				.on_update(|_| NodeGraphMessage::ToggleSelectedLocked.into())
 				.disabled(!has_selection)
 				.widget_holder(),
+			IconButton::new("Edit", 24)
+				.tooltip(if selection_all_edit_locked {
+					"Allow Point Editing of Selected"
+				} else {
+					"Prevent Point Editing of Selected\n(The layers stay selectable, but the Path tool can't modify their points)"
+				})
+				.on_update(|_| NodeGraphMessage::ToggleSelectedEditLocked.into())
+				.disabled(!has_selection)
+				.widget_holder(),
 			IconButton::new(if selection_all_visible { "EyeVisible" } else { "EyeHidden" }, 24)
 				.hover_icon(Some((if selection_all_visible { "EyeHide" } else { "EyeShow" }).into()))
 				.tooltip(if selection_all_visible { "Hide Selected" } else { "Show Selected" })
@@ -2917,6 +3075,7 @@ impl Iterator for ClickXRayIter<'_> {
 #[cfg(test)]
 mod document_message_handler_tests {
 	use super::*;
+	use crate::messages::input_mapper::utility_types::input_mouse::MouseState;
 	use crate::test_utils::test_prelude::*;
 
 	#[tokio::test]
@@ -3179,4 +3338,52 @@ mod document_message_handler_tests {
 
 		assert!(distance < 1., "Rectangle should maintain its viewport position after moving between transformed groups");
 	}
+
+	/// Two squares side by side in one layer, with anchors from (0, 0) to (100, 100) and from (200, 0) to (300, 100).
+	fn two_squares() -> Vec<Subpath<PointId>> {
+		vec![
+			Subpath::new_rect(DVec2::new(0., 0.), DVec2::new(100., 100.)),
+			Subpath::new_rect(DVec2::new(200., 0.), DVec2::new(300., 100.)),
+		]
+	}
+
+	fn mouse_at(position: DVec2) -> InputPreprocessorMessageHandler {
+		InputPreprocessorMessageHandler {
+			mouse: MouseState { position, ..Default::default() },
+			..Default::default()
+		}
+	}
+
+	#[tokio::test]
+	async fn click_subpath_finds_which_subpath_of_a_compound_path_was_clicked() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.select_tool(ToolType::Path).await;
+		let layer = editor.draw_subpaths(two_squares()).await;
+
+		let document = editor.active_document();
+		let regions = document.metadata().subpath_click_regions(layer).expect("The Path tool should have the regions collected");
+		let segment_ranges = regions.iter().map(|region| region.segment_ranges.clone()).collect::<Vec<_>>();
+		assert_eq!(segment_ranges, vec![vec![0..4], vec![4..8]]);
+
+		assert_eq!(document.click_subpath(&mouse_at(DVec2::new(50., 50.))), Some((layer, 0)));
+		assert_eq!(document.click_subpath(&mouse_at(DVec2::new(250., 50.))), Some((layer, 1)));
+		assert_eq!(document.click_subpath(&mouse_at(DVec2::new(150., 50.))), None, "The gap between the squares isn't part of either");
+	}
+
+	#[tokio::test]
+	async fn subpath_click_regions_are_only_collected_while_the_path_tool_is_active() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.select_tool(ToolType::Select).await;
+		let layer = editor.draw_subpaths(two_squares()).await;
+
+		let document = editor.active_document();
+		assert_eq!(document.metadata().subpath_click_regions(layer), None);
+		assert_eq!(document.click(&mouse_at(DVec2::new(250., 50.))), Some(layer));
+		assert_eq!(document.click_subpath(&mouse_at(DVec2::new(250., 50.))), None);
+
+		editor.select_tool(ToolType::Path).await;
+		assert_eq!(editor.active_document().metadata().subpath_click_regions(layer).map(Vec::len), Some(2));
+	}
 }
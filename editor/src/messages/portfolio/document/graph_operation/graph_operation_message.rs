@@ -53,6 +53,15 @@ pub enum GraphOperationMessage {
 		layer: LayerNodeIdentifier,
 		modification_type: VectorModificationType,
 	},
+	/// Applies several modifications to the layer's Path node in order, as one message instead of one per modification.
+	VectorBatch {
+		layer: LayerNodeIdentifier,
+		modifications: Vec<VectorModificationType>,
+	},
+	/// Bakes the edits of the layer's Path node into the vector data it modifies, leaving the same path with no edits to apply.
+	FlattenPathEdits {
+		layer: LayerNodeIdentifier,
+	},
 	Brush {
 		layer: LayerNodeIdentifier,
 		strokes: Vec<BrushStroke>,
@@ -1,5 +1,5 @@
 use super::transform_utils;
-use super::utility_types::ModifyInputsContext;
+use super::utility_types::{ModifyInputsContext, warn_non_finite_edit_skipped};
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
 use crate::messages::portfolio::document::utility_types::network_interface::{InputConnector, NodeNetworkInterface, OutputConnector};
@@ -91,10 +91,44 @@ impl MessageHandler<GraphOperationMessage, GraphOperationMessageData<'_>> for Gr
 					log::error!("Cannot run Vector on ROOT_PARENT");
 					return;
 				}
+				// A single non-finite coordinate would break the rendering and hit testing of the layer from then on
+				if !modification_type.is_finite() {
+					warn_non_finite_edit_skipped();
+					return;
+				}
 				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
 					modify_inputs.vector_modify(modification_type);
 				}
 			}
+			GraphOperationMessage::VectorBatch { layer, modifications } => {
+				if layer == LayerNodeIdentifier::ROOT_PARENT {
+					log::error!("Cannot run VectorBatch on ROOT_PARENT");
+					return;
+				}
+				// Non-finite modifications are skipped individually, just as they would be when sent one message at a time
+				let modifications = modifications
+					.into_iter()
+					.filter(|modification_type| {
+						let finite = modification_type.is_finite();
+						if !finite {
+							warn_non_finite_edit_skipped();
+						}
+						finite
+					})
+					.collect::<Vec<_>>();
+				if modifications.is_empty() {
+					return;
+				}
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
+					modify_inputs.vector_modify_batch(modifications);
+				}
+			}
+			GraphOperationMessage::FlattenPathEdits { layer } => {
+				let Some(vector_data) = network_interface.compute_modified_vector(layer) else { return };
+				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
+					modify_inputs.flatten_path_edits(vector_data);
+				}
+			}
 			GraphOperationMessage::Brush { layer, strokes } => {
 				if let Some(mut modify_inputs) = ModifyInputsContext::new_with_layer(layer, network_interface, responses) {
 					modify_inputs.brush_modify(strokes);
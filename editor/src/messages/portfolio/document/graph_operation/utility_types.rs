@@ -17,6 +17,15 @@ use graphene_core::vector::{PointId, VectorModificationType};
 use graphene_core::{Artboard, Color};
 use graphene_std::GraphicGroupTable;
 use graphene_std::vector::{VectorData, VectorDataTable};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Logs that a path edit with non-finite coordinates was skipped, but only the first time so a degenerate drag doesn't flood the log.
+pub fn warn_non_finite_edit_skipped() {
+	static WARNED: AtomicBool = AtomicBool::new(false);
+	if !WARNED.swap(true, Ordering::Relaxed) {
+		log::warn!("Skipped a path edit with non-finite coordinates");
+	}
+}
 
 #[derive(PartialEq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub enum TransformIn {
@@ -451,6 +460,46 @@ impl<'a> ModifyInputsContext<'a> {
 		self.responses.add(NodeGraphMessage::RunDocumentGraph);
 	}
 
+	/// Applies each modification in order, then refreshes and reruns the graph once for all of them.
+	pub fn vector_modify_batch(&mut self, modifications: Vec<VectorModificationType>) {
+		let Some(path_node_id) = self.existing_node_id("Path", true) else { return };
+		for modification_type in modifications {
+			self.network_interface.vector_modify(&path_node_id, modification_type);
+		}
+		self.responses.add(PropertiesPanelMessage::Refresh);
+		self.responses.add(NodeGraphMessage::RunDocumentGraph);
+	}
+
+	/// Replaces the input of the layer's Path node with `vector_data`, which must already have the node's edits applied, and clears the edits.
+	/// The point and segment IDs are kept, so anything referring to them (such as the selected points) stays valid.
+	/// Nodes which only fed the Path node's input are deleted since their output is no longer used.
+	pub fn flatten_path_edits(&mut self, vector_data: VectorData) {
+		let Some(path_node_id) = self.existing_node_id("Path", false) else { return };
+
+		let upstream_node_id = match self.network_interface.document_node(&path_node_id, &[]).and_then(|node| node.inputs.first()) {
+			Some(NodeInput::Node { node_id, .. }) => Some(*node_id),
+			_ => None,
+		};
+
+		let vector_data = TaggedValue::VectorData(VectorDataTable::new(vector_data));
+		self.set_input_with_refresh(InputConnector::node(path_node_id, 0), NodeInput::value(vector_data, false), true);
+		let modification = TaggedValue::VectorModification(Default::default());
+		self.set_input_with_refresh(InputConnector::node(path_node_id, 1), NodeInput::value(modification, false), false);
+
+		// Layers are left in place, since they are part of the layer stack rather than this layer's own chain
+		let Some(upstream_node_id) = upstream_node_id.filter(|node_id| !self.network_interface.is_layer(node_id, &[])) else {
+			return;
+		};
+		let still_used = self
+			.network_interface
+			.outward_wires(&[])
+			.and_then(|outward_wires| outward_wires.get(&OutputConnector::node(upstream_node_id, 0)))
+			.is_some_and(|downstream| !downstream.is_empty());
+		if !still_used {
+			self.network_interface.delete_nodes(vec![upstream_node_id], true, &[]);
+		}
+	}
+
 	pub fn brush_modify(&mut self, strokes: Vec<BrushStroke>) {
 		let Some(brush_node_id) = self.existing_node_id("Brush", true) else { return };
 		self.set_input_with_refresh(InputConnector::node(brush_node_id, 2), NodeInput::value(TaggedValue::BrushStrokes(strokes), false), false);
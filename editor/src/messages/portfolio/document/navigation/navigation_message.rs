@@ -1,4 +1,5 @@
 use crate::messages::input_mapper::utility_types::input_keyboard::Key;
+use crate::messages::portfolio::document::utility_types::misc::PTZ;
 use crate::messages::prelude::*;
 use glam::DVec2;
 
@@ -14,6 +15,7 @@ pub enum NavigationMessage {
 	CanvasPanAbort { x_not_y_axis: bool },
 	CanvasPanByViewportFraction { delta: DVec2 },
 	CanvasPanMouseWheel { use_y_as_x: bool },
+	CanvasPTZSet { ptz: PTZ },
 	CanvasTiltResetAndZoomTo100Percent,
 	CanvasTiltSet { angle_radians: f64 },
 	CanvasZoomDecrease { center_on_mouse: bool },
@@ -195,6 +195,17 @@ impl MessageHandler<NavigationMessage, NavigationMessageData<'_>> for Navigation
 				responses.add(DocumentMessage::PTZUpdate);
 				responses.add(NodeGraphMessage::SetGridAlignedEdges);
 			}
+			NavigationMessage::CanvasPTZSet { ptz: new_ptz } => {
+				let Some(ptz) = get_ptz_mut(document_ptz, network_interface, graph_view_overlay_open, breadcrumb_network_path) else {
+					log::error!("Could not get mutable PTZ in CanvasPTZSet");
+					return;
+				};
+				*ptz = new_ptz;
+				responses.add(DocumentMessage::PTZUpdate);
+				if !graph_view_overlay_open {
+					responses.add(PortfolioMessage::UpdateDocumentWidgets);
+				}
+			}
 			NavigationMessage::CanvasTiltSet { angle_radians } => {
 				let Some(ptz) = get_ptz_mut(document_ptz, network_interface, graph_view_overlay_open, breadcrumb_network_path) else {
 					log::error!("Could not get mutable PTZ in CanvasTiltSet");
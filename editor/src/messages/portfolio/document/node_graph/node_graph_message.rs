@@ -183,6 +183,14 @@ pub enum NodeGraphMessage {
 		node_id: NodeId,
 		locked: bool,
 	},
+	ToggleSelectedEditLocked,
+	ToggleEditLocked {
+		node_id: NodeId,
+	},
+	SetEditLocked {
+		node_id: NodeId,
+		edit_locked: bool,
+	},
 	ToggleSelectedIsPinned,
 	ToggleSelectedVisibility,
 	ToggleVisibility {
@@ -1561,6 +1561,42 @@ impl<'a> MessageHandler<NodeGraphMessage, NodeGraphHandlerData<'a>> for NodeGrap
 			NodeGraphMessage::SetLocked { node_id, locked } => {
 				network_interface.set_locked(&node_id, selection_network_path, locked);
 			}
+			NodeGraphMessage::ToggleSelectedEditLocked => {
+				let Some(selected_nodes) = network_interface.selected_nodes_in_nested_network(selection_network_path) else {
+					log::error!("Could not get selected nodes in NodeGraphMessage::ToggleSelectedEditLocked");
+					return;
+				};
+				let node_ids = selected_nodes.selected_nodes().cloned().collect::<Vec<_>>();
+
+				// If any of the selected layers are editable, edit lock them all. Otherwise, make them all editable.
+				let edit_locked = !node_ids.iter().all(|node_id| network_interface.is_edit_locked(node_id, selection_network_path));
+
+				responses.add(DocumentMessage::AddTransaction);
+
+				for node_id in &node_ids {
+					responses.add(NodeGraphMessage::SetEditLocked { node_id: *node_id, edit_locked });
+				}
+
+				responses.add(NodeGraphMessage::SetLockedOrVisibilitySideEffects { node_ids });
+				// Let the active tool drop any point selection it holds on the newly edit locked layers
+				responses.add(BroadcastEvent::SelectionChanged);
+			}
+			NodeGraphMessage::ToggleEditLocked { node_id } => {
+				let Some(node_metadata) = network_interface.document_network_metadata().persistent_metadata.node_metadata.get(&node_id) else {
+					log::error!("Cannot get node {:?} in NodeGraphMessage::ToggleEditLocked", node_id);
+					return;
+				};
+
+				let edit_locked = !node_metadata.persistent_metadata.edit_locked;
+
+				responses.add(DocumentMessage::AddTransaction);
+				responses.add(NodeGraphMessage::SetEditLocked { node_id, edit_locked });
+				responses.add(NodeGraphMessage::SetLockedOrVisibilitySideEffects { node_ids: vec![node_id] });
+				responses.add(BroadcastEvent::SelectionChanged);
+			}
+			NodeGraphMessage::SetEditLocked { node_id, edit_locked } => {
+				network_interface.set_edit_locked(&node_id, selection_network_path, edit_locked);
+			}
 			NodeGraphMessage::ToggleSelectedIsPinned => {
 				let Some(selected_nodes) = network_interface.selected_nodes_in_nested_network(selection_network_path) else {
 					log::error!("Could not get selected nodes in NodeGraphMessage::ToggleSelectedIsPinned");
@@ -2431,6 +2467,7 @@ impl NodeGraphMessageHandler {
 					parents_visible,
 					unlocked: !network_interface.is_locked(&node_id, &[]),
 					parents_unlocked,
+					edit_locked: network_interface.is_edit_locked(&node_id, &[]),
 					parent_id: layer
 						.parent(network_interface.document_metadata())
 						.and_then(|parent| if parent != LayerNodeIdentifier::ROOT_PARENT { Some(parent.to_node()) } else { None }),
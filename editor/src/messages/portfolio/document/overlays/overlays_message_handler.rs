@@ -10,6 +10,8 @@ pub struct OverlaysMessageData<'a> {
 #[derive(Debug, Clone, Default)]
 pub struct OverlaysMessageHandler {
 	pub overlay_providers: HashSet<OverlayProvider>,
+	/// The number of times the overlays have been drawn, which tests use to check that redundant redraws are coalesced.
+	pub(crate) draw_count: usize,
 	#[cfg(target_arch = "wasm32")]
 	canvas: Option<web_sys::HtmlCanvasElement>,
 	#[cfg(target_arch = "wasm32")]
@@ -20,6 +22,10 @@ impl MessageHandler<OverlaysMessage, OverlaysMessageData<'_>> for OverlaysMessag
 	fn process_message(&mut self, message: OverlaysMessage, responses: &mut VecDeque<Message>, data: OverlaysMessageData) {
 		let OverlaysMessageData { visibility_settings, ipp, .. } = data;
 
+		if matches!(message, OverlaysMessage::Draw) {
+			self.draw_count += 1;
+		}
+
 		match message {
 			#[cfg(target_arch = "wasm32")]
 			OverlaysMessage::Draw => {
@@ -52,17 +58,26 @@ impl MessageHandler<OverlaysMessage, OverlaysMessageData<'_>> for OverlaysMessag
 
 				if visibility_settings.all() {
 					responses.add(DocumentMessage::GridOverlays(OverlayContext {
-						render_context: context.clone(),
+						render_context: Some(context.clone()),
+						size: size.as_dvec2(),
+						device_pixel_ratio,
+						visibility_settings: visibility_settings.clone(),
+						recording: None,
+					}));
+					responses.add(DocumentMessage::CanvasProbeOverlays(OverlayContext {
+						render_context: Some(context.clone()),
 						size: size.as_dvec2(),
 						device_pixel_ratio,
 						visibility_settings: visibility_settings.clone(),
+						recording: None,
 					}));
 					for provider in &self.overlay_providers {
 						responses.add(provider(OverlayContext {
-							render_context: context.clone(),
+							render_context: Some(context.clone()),
 							size: size.as_dvec2(),
 							device_pixel_ratio,
 							visibility_settings: visibility_settings.clone(),
+							recording: None,
 						}));
 					}
 				}
@@ -15,12 +15,9 @@ pub fn overlay_canvas_element() -> Option<web_sys::HtmlCanvasElement> {
 	canvas.dyn_into::<web_sys::HtmlCanvasElement>().ok()
 }
 
-pub fn overlay_canvas_context() -> web_sys::CanvasRenderingContext2d {
-	let create_context = || {
-		let context = overlay_canvas_element()?.get_context("2d").ok().flatten()?;
-		context.dyn_into().ok()
-	};
-	create_context().expect("Failed to get canvas context")
+pub fn overlay_canvas_context() -> Option<web_sys::CanvasRenderingContext2d> {
+	let context = overlay_canvas_element()?.get_context("2d").ok().flatten()?;
+	context.dyn_into().ok()
 }
 
 pub fn selected_segments(document: &DocumentMessageHandler, shape_editor: &mut ShapeState) -> Vec<SegmentId> {
@@ -123,6 +120,16 @@ pub fn path_overlays(document: &DocumentMessageHandler, draw_handles: DrawHandle
 			overlay_context.outline_vector(&vector_data, transform);
 		}
 
+		// Edit locked layers only show their anchors, each with a padlock badge, since none of their points can be selected or modified
+		if ShapeState::layer_edit_locked(&document.network_interface, layer) {
+			if display_anchors {
+				for &position in vector_data.point_domain.positions() {
+					overlay_context.locked_anchor(transform.transform_point2(position));
+				}
+			}
+			continue;
+		}
+
 		let selected = shape_editor.selected_shape_state.get(&layer);
 		let is_selected = |point: ManipulatorPointId| selected.is_some_and(|selected| selected.is_selected(point));
 
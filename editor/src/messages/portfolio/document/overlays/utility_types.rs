@@ -1,7 +1,7 @@
 use super::utility_functions::overlay_canvas_context;
 use crate::consts::{
-	COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_RED, COLOR_OVERLAY_WHITE, COLOR_OVERLAY_YELLOW, COMPASS_ROSE_ARROW_SIZE, COMPASS_ROSE_HOVER_RING_DIAMETER, COMPASS_ROSE_MAIN_RING_DIAMETER,
-	COMPASS_ROSE_RING_INNER_DIAMETER, MANIPULATOR_GROUP_MARKER_SIZE, PIVOT_CROSSHAIR_LENGTH, PIVOT_CROSSHAIR_THICKNESS, PIVOT_DIAMETER,
+	COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GRAY, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_RED, COLOR_OVERLAY_WHITE, COLOR_OVERLAY_YELLOW, COMPASS_ROSE_ARROW_SIZE, COMPASS_ROSE_HOVER_RING_DIAMETER,
+	COMPASS_ROSE_MAIN_RING_DIAMETER, COMPASS_ROSE_RING_INNER_DIAMETER, MANIPULATOR_GROUP_MARKER_SIZE, PIVOT_CROSSHAIR_LENGTH, PIVOT_CROSSHAIR_THICKNESS, PIVOT_DIAMETER,
 };
 use crate::messages::prelude::Message;
 use bezier_rs::{Bezier, Subpath};
@@ -11,7 +11,9 @@ use glam::{DAffine2, DVec2};
 use graphene_core::Color;
 use graphene_core::renderer::Quad;
 use graphene_std::vector::{PointId, SegmentId, VectorData};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{OffscreenCanvas, OffscreenCanvasRenderingContext2d};
 
@@ -127,24 +129,200 @@ pub struct OverlayContext {
 	// Serde functionality isn't used but is required by the message system macros
 	#[serde(skip, default = "overlay_canvas_context")]
 	#[specta(skip)]
-	pub render_context: web_sys::CanvasRenderingContext2d,
+	pub render_context: Option<web_sys::CanvasRenderingContext2d>,
 	pub size: DVec2,
 	// The device pixel ratio is a property provided by the browser window and is the CSS pixel size divided by the physical monitor's pixel size.
 	// It allows better pixel density of visualizations on high-DPI displays where the OS display scaling is not 100%, or where the browser is zoomed.
 	pub device_pixel_ratio: f64,
 	pub visibility_settings: OverlaysVisibilitySettings,
+	/// When set, drawing commands are captured here instead of being drawn to the canvas.
+	#[serde(skip)]
+	#[specta(skip)]
+	pub recording: Option<OverlayRecording>,
 }
 // Message hashing isn't used but is required by the message system macros
 impl core::hash::Hash for OverlayContext {
 	fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
 }
 
+/// A drawing command captured by an [`OverlayContext`] in recording mode. Coordinates are in viewport space and rounded to hundredths of a pixel.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum OverlayCommand {
+	Polygon {
+		points: Vec<DVec2>,
+		stroke_color: String,
+		fill_color: Option<String>,
+		dashed: bool,
+	},
+	Triangle {
+		base: DVec2,
+		direction: DVec2,
+		size: f64,
+		fill_color: String,
+		stroke_color: String,
+	},
+	Line {
+		start: DVec2,
+		end: DVec2,
+		color: String,
+		thickness: f64,
+		dashed: bool,
+	},
+	ManipulatorHandle {
+		position: DVec2,
+		selected: bool,
+		color: String,
+	},
+	ManipulatorAnchor {
+		position: DVec2,
+		selected: bool,
+		color: String,
+	},
+	LockedAnchor {
+		position: DVec2,
+	},
+	Square {
+		position: DVec2,
+		size: f64,
+		fill_color: String,
+		stroke_color: String,
+	},
+	Pixel {
+		position: DVec2,
+		color: String,
+	},
+	Circle {
+		position: DVec2,
+		radius: f64,
+		fill_color: String,
+		stroke_color: String,
+	},
+	Arc {
+		center: DVec2,
+		radius: f64,
+		start_from: f64,
+		end_at: f64,
+	},
+	CompassRose {
+		center: DVec2,
+		angle: f64,
+		hover_ring: bool,
+	},
+	Pivot {
+		position: DVec2,
+		angle: f64,
+	},
+	/// Each curve is listed as its start point, any handles, and its end point.
+	Outline {
+		curves: Vec<Vec<DVec2>>,
+		color: String,
+	},
+	FillPath {
+		curves: Vec<Vec<DVec2>>,
+		color: String,
+	},
+	Text {
+		text: String,
+		font_color: String,
+		background_color: Option<String>,
+		transform: [f64; 6],
+		pivot: [Pivot; 2],
+	},
+}
+
+/// The list of commands captured by a recording [`OverlayContext`].
+/// It's shared so the commands can be read back after the context has been consumed by the overlay provider it was sent to.
+#[derive(Clone, Debug, Default)]
+pub struct OverlayRecording(Rc<RefCell<Vec<OverlayCommand>>>);
+
+// Two recordings are only equal if they share the same list of commands
+impl PartialEq for OverlayRecording {
+	fn eq(&self, other: &Self) -> bool {
+		Rc::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl OverlayRecording {
+	pub fn commands(&self) -> Vec<OverlayCommand> {
+		self.0.borrow().clone()
+	}
+
+	pub fn clear(&self) {
+		self.0.borrow_mut().clear();
+	}
+
+	/// Serializes the recorded commands with one command per line, suitable for comparing against a golden file.
+	pub fn to_golden(&self) -> String {
+		self.0
+			.borrow()
+			.iter()
+			.map(|command| serde_json::to_string(command).expect("Overlay commands should be serializable") + "\n")
+			.collect()
+	}
+}
+
+fn recorded_point(point: DVec2) -> DVec2 {
+	(point * 100.).round() / 100.
+}
+
+fn recorded_value(value: f64) -> f64 {
+	(value * 100.).round() / 100.
+}
+
+fn recorded_curve(bezier: Bezier) -> Vec<DVec2> {
+	let handles = match bezier.handles {
+		bezier_rs::BezierHandles::Linear => vec![],
+		bezier_rs::BezierHandles::Quadratic { handle } => vec![handle],
+		bezier_rs::BezierHandles::Cubic { handle_start, handle_end } => vec![handle_start, handle_end],
+	};
+	std::iter::once(bezier.start).chain(handles).chain(std::iter::once(bezier.end)).map(recorded_point).collect()
+}
+
+fn recorded_subpaths(subpaths: impl Iterator<Item = impl Borrow<Subpath<PointId>>>, transform: DAffine2) -> Vec<Vec<DVec2>> {
+	subpaths
+		.flat_map(|subpath| subpath.borrow().iter().collect::<Vec<_>>())
+		.map(|bezier| recorded_curve(bezier.apply_transformation(|point| transform.transform_point2(point))))
+		.collect()
+}
+
 impl OverlayContext {
+	/// Creates a context which records its drawing commands into `recording` instead of drawing them to the overlays canvas, which is used by tests.
+	pub fn new_recording(size: DVec2, visibility_settings: OverlaysVisibilitySettings, recording: OverlayRecording) -> Self {
+		Self {
+			render_context: None,
+			size,
+			device_pixel_ratio: 1.,
+			visibility_settings,
+			recording: Some(recording),
+		}
+	}
+
+	fn render_context(&self) -> &web_sys::CanvasRenderingContext2d {
+		self.render_context.as_ref().expect("Overlay context should have a canvas when it isn't recording")
+	}
+
+	/// Captures the command instead of drawing it if this context is in recording mode. Returns `true` if the command was recorded.
+	fn record(&self, command: impl FnOnce() -> OverlayCommand) -> bool {
+		let Some(recording) = &self.recording else { return false };
+		recording.0.borrow_mut().push(command());
+		true
+	}
+
 	pub fn quad(&mut self, quad: Quad, stroke_color: Option<&str>, color_fill: Option<&str>) {
 		self.dashed_polygon(&quad.0, stroke_color, color_fill, None, None, None);
 	}
 
 	pub fn draw_triangle(&mut self, base: DVec2, direction: DVec2, size: f64, color_fill: Option<&str>, color_stroke: Option<&str>) {
+		if self.record(|| OverlayCommand::Triangle {
+			base: recorded_point(base),
+			direction: recorded_point(direction),
+			size: recorded_value(size),
+			fill_color: color_fill.unwrap_or(COLOR_OVERLAY_WHITE).to_string(),
+			stroke_color: color_stroke.unwrap_or(COLOR_OVERLAY_BLUE).to_string(),
+		}) {
+			return;
+		}
+
 		let color_fill = color_fill.unwrap_or(COLOR_OVERLAY_WHITE);
 		let color_stroke = color_stroke.unwrap_or(COLOR_OVERLAY_BLUE);
 		let normal = direction.perp();
@@ -154,16 +332,16 @@ impl OverlayContext {
 
 		self.start_dpi_aware_transform();
 
-		self.render_context.begin_path();
-		self.render_context.move_to(top.x, top.y);
-		self.render_context.line_to(edge1.x, edge1.y);
-		self.render_context.line_to(edge2.x, edge2.y);
-		self.render_context.close_path();
+		self.render_context().begin_path();
+		self.render_context().move_to(top.x, top.y);
+		self.render_context().line_to(edge1.x, edge1.y);
+		self.render_context().line_to(edge2.x, edge2.y);
+		self.render_context().close_path();
 
-		self.render_context.set_fill_style_str(color_fill);
-		self.render_context.set_stroke_style_str(color_stroke);
-		self.render_context.fill();
-		self.render_context.stroke();
+		self.render_context().set_fill_style_str(color_fill);
+		self.render_context().set_stroke_style_str(color_stroke);
+		self.render_context().fill();
+		self.render_context().stroke();
 
 		self.end_dpi_aware_transform();
 	}
@@ -181,6 +359,15 @@ impl OverlayContext {
 			return;
 		}
 
+		if self.record(|| OverlayCommand::Polygon {
+			points: polygon.iter().copied().map(recorded_point).collect(),
+			stroke_color: stroke_color.unwrap_or(COLOR_OVERLAY_BLUE).to_string(),
+			fill_color: color_fill.map(str::to_string),
+			dashed: dash_width.is_some(),
+		}) {
+			return;
+		}
+
 		self.start_dpi_aware_transform();
 
 		// Set the dash pattern
@@ -192,7 +379,7 @@ impl OverlayContext {
 
 			if let Some(dash_offset) = dash_offset {
 				if dash_offset != 0. {
-					self.render_context.set_line_dash_offset(dash_offset);
+					self.render_context().set_line_dash_offset(dash_offset);
 				}
 			}
 
@@ -202,21 +389,21 @@ impl OverlayContext {
 				.ok();
 		}
 
-		self.render_context.begin_path();
-		self.render_context.move_to(polygon.last().unwrap().x.round() - 0.5, polygon.last().unwrap().y.round() - 0.5);
+		self.render_context().begin_path();
+		self.render_context().move_to(polygon.last().unwrap().x.round() - 0.5, polygon.last().unwrap().y.round() - 0.5);
 
 		for point in polygon {
-			self.render_context.line_to(point.x.round() - 0.5, point.y.round() - 0.5);
+			self.render_context().line_to(point.x.round() - 0.5, point.y.round() - 0.5);
 		}
 
 		if let Some(color_fill) = color_fill {
-			self.render_context.set_fill_style_str(color_fill);
-			self.render_context.fill();
+			self.render_context().set_fill_style_str(color_fill);
+			self.render_context().fill();
 		}
 
 		let stroke_color = stroke_color.unwrap_or(COLOR_OVERLAY_BLUE);
-		self.render_context.set_stroke_style_str(stroke_color);
-		self.render_context.stroke();
+		self.render_context().set_stroke_style_str(stroke_color);
+		self.render_context().stroke();
 
 		// Reset the dash pattern back to solid
 		if dash_width.is_some() {
@@ -226,7 +413,7 @@ impl OverlayContext {
 				.ok();
 		}
 		if dash_offset.is_some() && dash_offset != Some(0.) {
-			self.render_context.set_line_dash_offset(0.);
+			self.render_context().set_line_dash_offset(0.);
 		}
 
 		self.end_dpi_aware_transform();
@@ -238,6 +425,16 @@ impl OverlayContext {
 
 	#[allow(clippy::too_many_arguments)]
 	pub fn dashed_line(&mut self, start: DVec2, end: DVec2, color: Option<&str>, thickness: Option<f64>, dash_width: Option<f64>, dash_gap_width: Option<f64>, dash_offset: Option<f64>) {
+		if self.record(|| OverlayCommand::Line {
+			start: recorded_point(start),
+			end: recorded_point(end),
+			color: color.unwrap_or(COLOR_OVERLAY_BLUE).to_string(),
+			thickness: thickness.unwrap_or(1.),
+			dashed: dash_width.is_some(),
+		}) {
+			return;
+		}
+
 		self.start_dpi_aware_transform();
 
 		// Set the dash pattern
@@ -249,7 +446,7 @@ impl OverlayContext {
 
 			if let Some(dash_offset) = dash_offset {
 				if dash_offset != 0. {
-					self.render_context.set_line_dash_offset(dash_offset);
+					self.render_context().set_line_dash_offset(dash_offset);
 				}
 			}
 
@@ -262,13 +459,13 @@ impl OverlayContext {
 		let start = start.round() - DVec2::splat(0.5);
 		let end = end.round() - DVec2::splat(0.5);
 
-		self.render_context.begin_path();
-		self.render_context.move_to(start.x, start.y);
-		self.render_context.line_to(end.x, end.y);
-		self.render_context.set_line_width(thickness.unwrap_or(1.));
-		self.render_context.set_stroke_style_str(color.unwrap_or(COLOR_OVERLAY_BLUE));
-		self.render_context.stroke();
-		self.render_context.set_line_width(1.);
+		self.render_context().begin_path();
+		self.render_context().move_to(start.x, start.y);
+		self.render_context().line_to(end.x, end.y);
+		self.render_context().set_line_width(thickness.unwrap_or(1.));
+		self.render_context().set_stroke_style_str(color.unwrap_or(COLOR_OVERLAY_BLUE));
+		self.render_context().stroke();
+		self.render_context().set_line_width(1.);
 
 		// Reset the dash pattern back to solid
 		if dash_width.is_some() {
@@ -278,37 +475,83 @@ impl OverlayContext {
 				.ok();
 		}
 		if dash_offset.is_some() && dash_offset != Some(0.) {
-			self.render_context.set_line_dash_offset(0.);
+			self.render_context().set_line_dash_offset(0.);
 		}
 
 		self.end_dpi_aware_transform();
 	}
 
 	pub fn manipulator_handle(&mut self, position: DVec2, selected: bool, color: Option<&str>) {
+		if self.record(|| OverlayCommand::ManipulatorHandle {
+			position: recorded_point(position),
+			selected,
+			color: color.unwrap_or(COLOR_OVERLAY_BLUE).to_string(),
+		}) {
+			return;
+		}
+
 		self.start_dpi_aware_transform();
 
 		let position = position.round() - DVec2::splat(0.5);
 
-		self.render_context.begin_path();
+		self.render_context().begin_path();
 		self.render_context
 			.arc(position.x, position.y, MANIPULATOR_GROUP_MARKER_SIZE / 2., 0., TAU)
 			.expect("Failed to draw the circle");
 
 		let fill = if selected { COLOR_OVERLAY_BLUE } else { COLOR_OVERLAY_WHITE };
-		self.render_context.set_fill_style_str(fill);
-		self.render_context.set_stroke_style_str(color.unwrap_or(COLOR_OVERLAY_BLUE));
-		self.render_context.fill();
-		self.render_context.stroke();
+		self.render_context().set_fill_style_str(fill);
+		self.render_context().set_stroke_style_str(color.unwrap_or(COLOR_OVERLAY_BLUE));
+		self.render_context().fill();
+		self.render_context().stroke();
 
 		self.end_dpi_aware_transform();
 	}
 
 	pub fn manipulator_anchor(&mut self, position: DVec2, selected: bool, color: Option<&str>) {
+		if self.record(|| OverlayCommand::ManipulatorAnchor {
+			position: recorded_point(position),
+			selected,
+			color: color.unwrap_or(COLOR_OVERLAY_BLUE).to_string(),
+		}) {
+			return;
+		}
+
 		let color_stroke = color.unwrap_or(COLOR_OVERLAY_BLUE);
 		let color_fill = if selected { color_stroke } else { COLOR_OVERLAY_WHITE };
 		self.square(position, None, Some(color_fill), Some(color_stroke));
 	}
 
+	/// Draws an anchor which can't be edited because its layer is edit locked, with a small padlock badge beside it.
+	pub fn locked_anchor(&mut self, position: DVec2) {
+		if self.record(|| OverlayCommand::LockedAnchor { position: recorded_point(position) }) {
+			return;
+		}
+
+		self.square(position, None, Some(COLOR_OVERLAY_WHITE), Some(COLOR_OVERLAY_GRAY));
+
+		let badge = position.round() - DVec2::splat(0.5) + DVec2::new(MANIPULATOR_GROUP_MARKER_SIZE, -MANIPULATOR_GROUP_MARKER_SIZE);
+		let body_size = MANIPULATOR_GROUP_MARKER_SIZE * 0.75;
+
+		self.start_dpi_aware_transform();
+
+		// Shackle
+		self.render_context().begin_path();
+		self.render_context
+			.arc(badge.x, badge.y - body_size / 2., body_size / 3., std::f64::consts::PI, TAU)
+			.expect("Failed to draw the padlock shackle");
+		self.render_context().set_stroke_style_str(COLOR_OVERLAY_GRAY);
+		self.render_context().stroke();
+
+		// Body
+		self.render_context().begin_path();
+		self.render_context().rect(badge.x - body_size / 2., badge.y - body_size / 2., body_size, body_size);
+		self.render_context().set_fill_style_str(COLOR_OVERLAY_GRAY);
+		self.render_context().fill();
+
+		self.end_dpi_aware_transform();
+	}
+
 	/// Transforms the canvas context to adjust for DPI scaling
 	///
 	/// Overwrites all existing tranforms. This operation can be reversed with [`Self::reset_transform`].
@@ -323,10 +566,19 @@ impl OverlayContext {
 	///
 	/// Warning: this function doesn't only reset the DPI scaling adjustment, it resets the entire transform.
 	fn end_dpi_aware_transform(&self) {
-		self.render_context.reset_transform().expect("transform should be able to be reset to be able to account for DPI");
+		self.render_context().reset_transform().expect("transform should be able to be reset to be able to account for DPI");
 	}
 
 	pub fn square(&mut self, position: DVec2, size: Option<f64>, color_fill: Option<&str>, color_stroke: Option<&str>) {
+		if self.record(|| OverlayCommand::Square {
+			position: recorded_point(position),
+			size: size.unwrap_or(MANIPULATOR_GROUP_MARKER_SIZE),
+			fill_color: color_fill.unwrap_or(COLOR_OVERLAY_WHITE).to_string(),
+			stroke_color: color_stroke.unwrap_or(COLOR_OVERLAY_BLUE).to_string(),
+		}) {
+			return;
+		}
+
 		let size = size.unwrap_or(MANIPULATOR_GROUP_MARKER_SIZE);
 		let color_fill = color_fill.unwrap_or(COLOR_OVERLAY_WHITE);
 		let color_stroke = color_stroke.unwrap_or(COLOR_OVERLAY_BLUE);
@@ -336,17 +588,24 @@ impl OverlayContext {
 
 		self.start_dpi_aware_transform();
 
-		self.render_context.begin_path();
-		self.render_context.rect(corner.x, corner.y, size, size);
-		self.render_context.set_fill_style_str(color_fill);
-		self.render_context.set_stroke_style_str(color_stroke);
-		self.render_context.fill();
-		self.render_context.stroke();
+		self.render_context().begin_path();
+		self.render_context().rect(corner.x, corner.y, size, size);
+		self.render_context().set_fill_style_str(color_fill);
+		self.render_context().set_stroke_style_str(color_stroke);
+		self.render_context().fill();
+		self.render_context().stroke();
 
 		self.end_dpi_aware_transform();
 	}
 
 	pub fn pixel(&mut self, position: DVec2, color: Option<&str>) {
+		if self.record(|| OverlayCommand::Pixel {
+			position: recorded_point(position),
+			color: color.unwrap_or(COLOR_OVERLAY_WHITE).to_string(),
+		}) {
+			return;
+		}
+
 		let size = 1.;
 		let color_fill = color.unwrap_or(COLOR_OVERLAY_WHITE);
 
@@ -355,38 +614,56 @@ impl OverlayContext {
 
 		self.start_dpi_aware_transform();
 
-		self.render_context.begin_path();
-		self.render_context.rect(corner.x, corner.y, size, size);
-		self.render_context.set_fill_style_str(color_fill);
-		self.render_context.fill();
+		self.render_context().begin_path();
+		self.render_context().rect(corner.x, corner.y, size, size);
+		self.render_context().set_fill_style_str(color_fill);
+		self.render_context().fill();
 
 		self.end_dpi_aware_transform();
 	}
 
 	pub fn circle(&mut self, position: DVec2, radius: f64, color_fill: Option<&str>, color_stroke: Option<&str>) {
+		if self.record(|| OverlayCommand::Circle {
+			position: recorded_point(position),
+			radius: recorded_value(radius),
+			fill_color: color_fill.unwrap_or(COLOR_OVERLAY_WHITE).to_string(),
+			stroke_color: color_stroke.unwrap_or(COLOR_OVERLAY_BLUE).to_string(),
+		}) {
+			return;
+		}
+
 		let color_fill = color_fill.unwrap_or(COLOR_OVERLAY_WHITE);
 		let color_stroke = color_stroke.unwrap_or(COLOR_OVERLAY_BLUE);
 		let position = position.round();
 
 		self.start_dpi_aware_transform();
 
-		self.render_context.begin_path();
-		self.render_context.arc(position.x, position.y, radius, 0., TAU).expect("Failed to draw the circle");
-		self.render_context.set_fill_style_str(color_fill);
-		self.render_context.set_stroke_style_str(color_stroke);
-		self.render_context.fill();
-		self.render_context.stroke();
+		self.render_context().begin_path();
+		self.render_context().arc(position.x, position.y, radius, 0., TAU).expect("Failed to draw the circle");
+		self.render_context().set_fill_style_str(color_fill);
+		self.render_context().set_stroke_style_str(color_stroke);
+		self.render_context().fill();
+		self.render_context().stroke();
 
 		self.end_dpi_aware_transform();
 	}
 
 	pub fn draw_arc(&mut self, center: DVec2, radius: f64, start_from: f64, end_at: f64) {
+		if self.record(|| OverlayCommand::Arc {
+			center: recorded_point(center),
+			radius: recorded_value(radius),
+			start_from: recorded_value(start_from),
+			end_at: recorded_value(end_at),
+		}) {
+			return;
+		}
+
 		let segments = ((end_at - start_from).abs() / (std::f64::consts::PI / 4.)).ceil() as usize;
 		let step = (end_at - start_from) / segments as f64;
 		let half_step = step / 2.;
 		let factor = 4. / 3. * half_step.sin() / (1. + half_step.cos());
 
-		self.render_context.begin_path();
+		self.render_context().begin_path();
 
 		for i in 0..segments {
 			let start_angle = start_from + step * i as f64;
@@ -409,7 +686,7 @@ impl OverlayContext {
 			self.bezier_command(bezier, DAffine2::IDENTITY, i == 0);
 		}
 
-		self.render_context.stroke();
+		self.render_context().stroke();
 	}
 
 	pub fn draw_angle(&mut self, pivot: DVec2, radius: f64, arc_radius: f64, offset_angle: f64, angle: f64) {
@@ -453,12 +730,20 @@ impl OverlayContext {
 
 		let Some(show_hover_ring) = show_compass_with_hover_ring else { return };
 
+		if self.record(|| OverlayCommand::CompassRose {
+			center: recorded_point(compass_center),
+			angle: recorded_value(angle),
+			hover_ring: show_hover_ring,
+		}) {
+			return;
+		}
+
 		self.start_dpi_aware_transform();
 
 		let center = compass_center.round() - DVec2::splat(0.5);
 
 		// Save the old line width to restore it later
-		let old_line_width = self.render_context.line_width();
+		let old_line_width = self.render_context().line_width();
 
 		// Hover ring
 		if show_hover_ring {
@@ -468,15 +753,15 @@ impl OverlayContext {
 				.to_rgba_hex_srgb();
 			fill_color.insert(0, '#');
 
-			self.render_context.set_line_width(HOVER_RING_STROKE_WIDTH);
-			self.render_context.begin_path();
-			self.render_context.arc(center.x, center.y, HOVER_RING_CENTERLINE_RADIUS, 0., TAU).expect("Failed to draw hover ring");
-			self.render_context.set_stroke_style_str(&fill_color);
-			self.render_context.stroke();
+			self.render_context().set_line_width(HOVER_RING_STROKE_WIDTH);
+			self.render_context().begin_path();
+			self.render_context().arc(center.x, center.y, HOVER_RING_CENTERLINE_RADIUS, 0., TAU).expect("Failed to draw hover ring");
+			self.render_context().set_stroke_style_str(&fill_color);
+			self.render_context().stroke();
 		}
 
 		// Arrows
-		self.render_context.set_line_width(0.01);
+		self.render_context().set_line_width(0.01);
 		for i in 0..4 {
 			let direction = DVec2::from_angle(i as f64 * FRAC_PI_2 + angle);
 			let color = if i % 2 == 0 { COLOR_OVERLAY_RED } else { COLOR_OVERLAY_GREEN };
@@ -489,31 +774,38 @@ impl OverlayContext {
 			let side1 = center + r * DVec2::new(cos * direction.x - sin * direction.y, sin * direction.x + direction.y * cos);
 			let side2 = center + r * DVec2::new(cos * direction.x + sin * direction.y, -sin * direction.x + direction.y * cos);
 
-			self.render_context.begin_path();
-			self.render_context.move_to(tip.x, tip.y);
-			self.render_context.line_to(side1.x, side1.y);
-			self.render_context.line_to(base.x, base.y);
-			self.render_context.line_to(side2.x, side2.y);
-			self.render_context.close_path();
-
-			self.render_context.set_fill_style_str(color);
-			self.render_context.fill();
-			self.render_context.set_stroke_style_str(color);
-			self.render_context.stroke();
+			self.render_context().begin_path();
+			self.render_context().move_to(tip.x, tip.y);
+			self.render_context().line_to(side1.x, side1.y);
+			self.render_context().line_to(base.x, base.y);
+			self.render_context().line_to(side2.x, side2.y);
+			self.render_context().close_path();
+
+			self.render_context().set_fill_style_str(color);
+			self.render_context().fill();
+			self.render_context().set_stroke_style_str(color);
+			self.render_context().stroke();
 		}
 
 		// Main ring
-		self.render_context.set_line_width(MAIN_RING_STROKE_WIDTH);
-		self.render_context.begin_path();
-		self.render_context.arc(center.x, center.y, MAIN_RING_CENTERLINE_RADIUS, 0., TAU).expect("Failed to draw main ring");
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_BLUE);
-		self.render_context.stroke();
+		self.render_context().set_line_width(MAIN_RING_STROKE_WIDTH);
+		self.render_context().begin_path();
+		self.render_context().arc(center.x, center.y, MAIN_RING_CENTERLINE_RADIUS, 0., TAU).expect("Failed to draw main ring");
+		self.render_context().set_stroke_style_str(COLOR_OVERLAY_BLUE);
+		self.render_context().stroke();
 
 		// Restore the old line width
-		self.render_context.set_line_width(old_line_width);
+		self.render_context().set_line_width(old_line_width);
 	}
 
 	pub fn pivot(&mut self, position: DVec2, angle: f64) {
+		if self.record(|| OverlayCommand::Pivot {
+			position: recorded_point(position),
+			angle: recorded_value(angle),
+		}) {
+			return;
+		}
+
 		let uv = DVec2::from_angle(angle);
 		let (x, y) = (position.round() - DVec2::splat(0.5)).into();
 
@@ -521,39 +813,49 @@ impl OverlayContext {
 
 		// Circle
 
-		self.render_context.begin_path();
-		self.render_context.arc(x, y, PIVOT_DIAMETER / 2., 0., TAU).expect("Failed to draw the circle");
-		self.render_context.set_fill_style_str(COLOR_OVERLAY_YELLOW);
-		self.render_context.fill();
+		self.render_context().begin_path();
+		self.render_context().arc(x, y, PIVOT_DIAMETER / 2., 0., TAU).expect("Failed to draw the circle");
+		self.render_context().set_fill_style_str(COLOR_OVERLAY_YELLOW);
+		self.render_context().fill();
 
 		// Crosshair
 
 		// Round line caps add half the stroke width to the length on each end, so we subtract that here before halving to get the radius
 		const CROSSHAIR_RADIUS: f64 = (PIVOT_CROSSHAIR_LENGTH - PIVOT_CROSSHAIR_THICKNESS) / 2.;
 
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_YELLOW);
-		self.render_context.set_line_cap("round");
+		self.render_context().set_stroke_style_str(COLOR_OVERLAY_YELLOW);
+		self.render_context().set_line_cap("round");
 
-		self.render_context.begin_path();
-		self.render_context.move_to(x + CROSSHAIR_RADIUS * uv.x, y + CROSSHAIR_RADIUS * uv.y);
-		self.render_context.line_to(x - CROSSHAIR_RADIUS * uv.x, y - CROSSHAIR_RADIUS * uv.y);
-		self.render_context.stroke();
+		self.render_context().begin_path();
+		self.render_context().move_to(x + CROSSHAIR_RADIUS * uv.x, y + CROSSHAIR_RADIUS * uv.y);
+		self.render_context().line_to(x - CROSSHAIR_RADIUS * uv.x, y - CROSSHAIR_RADIUS * uv.y);
+		self.render_context().stroke();
 
-		self.render_context.begin_path();
-		self.render_context.move_to(x - CROSSHAIR_RADIUS * uv.y, y + CROSSHAIR_RADIUS * uv.x);
-		self.render_context.line_to(x + CROSSHAIR_RADIUS * uv.y, y - CROSSHAIR_RADIUS * uv.x);
-		self.render_context.stroke();
+		self.render_context().begin_path();
+		self.render_context().move_to(x - CROSSHAIR_RADIUS * uv.y, y + CROSSHAIR_RADIUS * uv.x);
+		self.render_context().line_to(x + CROSSHAIR_RADIUS * uv.y, y - CROSSHAIR_RADIUS * uv.x);
+		self.render_context().stroke();
 
-		self.render_context.set_line_cap("butt");
+		self.render_context().set_line_cap("butt");
 
 		self.end_dpi_aware_transform();
 	}
 
 	/// Used by the Pen and Path tools to outline the path of the shape.
 	pub fn outline_vector(&mut self, vector_data: &VectorData, transform: DAffine2) {
+		if self.record(|| OverlayCommand::Outline {
+			curves: vector_data
+				.segment_bezier_iter()
+				.map(|(_, bezier, _, _)| recorded_curve(bezier.apply_transformation(|point| transform.transform_point2(point))))
+				.collect(),
+			color: COLOR_OVERLAY_BLUE.to_string(),
+		}) {
+			return;
+		}
+
 		self.start_dpi_aware_transform();
 
-		self.render_context.begin_path();
+		self.render_context().begin_path();
 		let mut last_point = None;
 		for (_, bezier, start_id, end_id) in vector_data.segment_bezier_iter() {
 			let move_to = last_point != Some(start_id);
@@ -562,20 +864,27 @@ impl OverlayContext {
 			self.bezier_command(bezier, transform, move_to);
 		}
 
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_BLUE);
-		self.render_context.stroke();
+		self.render_context().set_stroke_style_str(COLOR_OVERLAY_BLUE);
+		self.render_context().stroke();
 
 		self.end_dpi_aware_transform();
 	}
 
 	/// Used by the Pen tool in order to show how the bezier curve would look like.
 	pub fn outline_bezier(&mut self, bezier: Bezier, transform: DAffine2) {
+		if self.record(|| OverlayCommand::Outline {
+			curves: vec![recorded_curve(bezier.apply_transformation(|point| transform.transform_point2(point)))],
+			color: COLOR_OVERLAY_BLUE.to_string(),
+		}) {
+			return;
+		}
+
 		self.start_dpi_aware_transform();
 
-		self.render_context.begin_path();
+		self.render_context().begin_path();
 		self.bezier_command(bezier, transform, true);
-		self.render_context.set_stroke_style_str(COLOR_OVERLAY_BLUE);
-		self.render_context.stroke();
+		self.render_context().set_stroke_style_str(COLOR_OVERLAY_BLUE);
+		self.render_context().stroke();
 
 		self.end_dpi_aware_transform();
 	}
@@ -585,13 +894,13 @@ impl OverlayContext {
 
 		let Bezier { start, end, handles } = bezier.apply_transformation(|point| transform.transform_point2(point));
 		if move_to {
-			self.render_context.move_to(start.x, start.y);
+			self.render_context().move_to(start.x, start.y);
 		}
 
 		match handles {
-			bezier_rs::BezierHandles::Linear => self.render_context.line_to(end.x, end.y),
-			bezier_rs::BezierHandles::Quadratic { handle } => self.render_context.quadratic_curve_to(handle.x, handle.y, end.x, end.y),
-			bezier_rs::BezierHandles::Cubic { handle_start, handle_end } => self.render_context.bezier_curve_to(handle_start.x, handle_start.y, handle_end.x, handle_end.y, end.x, end.y),
+			bezier_rs::BezierHandles::Linear => self.render_context().line_to(end.x, end.y),
+			bezier_rs::BezierHandles::Quadratic { handle } => self.render_context().quadratic_curve_to(handle.x, handle.y, end.x, end.y),
+			bezier_rs::BezierHandles::Cubic { handle_start, handle_end } => self.render_context().bezier_curve_to(handle_start.x, handle_start.y, handle_end.x, handle_end.y, end.x, end.y),
 		}
 
 		self.end_dpi_aware_transform();
@@ -600,7 +909,7 @@ impl OverlayContext {
 	fn push_path(&mut self, subpaths: impl Iterator<Item = impl Borrow<Subpath<PointId>>>, transform: DAffine2) {
 		self.start_dpi_aware_transform();
 
-		self.render_context.begin_path();
+		self.render_context().begin_path();
 		for subpath in subpaths {
 			let subpath = subpath.borrow();
 			let mut curves = subpath.iter().peekable();
@@ -609,14 +918,14 @@ impl OverlayContext {
 				continue;
 			};
 
-			self.render_context.move_to(transform.transform_point2(first.start()).x, transform.transform_point2(first.start()).y);
+			self.render_context().move_to(transform.transform_point2(first.start()).x, transform.transform_point2(first.start()).y);
 			for curve in curves {
 				match curve.handles {
 					bezier_rs::BezierHandles::Linear => {
 						let a = transform.transform_point2(curve.end());
 						let a = a.round() - DVec2::splat(0.5);
 
-						self.render_context.line_to(a.x, a.y)
+						self.render_context().line_to(a.x, a.y)
 					}
 					bezier_rs::BezierHandles::Quadratic { handle } => {
 						let a = transform.transform_point2(handle);
@@ -624,7 +933,7 @@ impl OverlayContext {
 						let a = a.round() - DVec2::splat(0.5);
 						let b = b.round() - DVec2::splat(0.5);
 
-						self.render_context.quadratic_curve_to(a.x, a.y, b.x, b.y)
+						self.render_context().quadratic_curve_to(a.x, a.y, b.x, b.y)
 					}
 					bezier_rs::BezierHandles::Cubic { handle_start, handle_end } => {
 						let a = transform.transform_point2(handle_start);
@@ -634,13 +943,13 @@ impl OverlayContext {
 						let b = b.round() - DVec2::splat(0.5);
 						let c = c.round() - DVec2::splat(0.5);
 
-						self.render_context.bezier_curve_to(a.x, a.y, b.x, b.y, c.x, c.y)
+						self.render_context().bezier_curve_to(a.x, a.y, b.x, b.y, c.x, c.y)
 					}
 				}
 			}
 
 			if subpath.closed() {
-				self.render_context.close_path();
+				self.render_context().close_path();
 			}
 		}
 
@@ -649,20 +958,35 @@ impl OverlayContext {
 
 	/// Used by the Select tool to outline a path selected or hovered.
 	pub fn outline(&mut self, subpaths: impl Iterator<Item = impl Borrow<Subpath<PointId>>>, transform: DAffine2, color: Option<&str>) {
+		if self.recording.is_some() {
+			let curves = recorded_subpaths(subpaths, transform);
+			self.record(|| OverlayCommand::Outline {
+				curves,
+				color: color.unwrap_or(COLOR_OVERLAY_BLUE).to_string(),
+			});
+			return;
+		}
+
 		self.push_path(subpaths, transform);
 
 		let color = color.unwrap_or(COLOR_OVERLAY_BLUE);
-		self.render_context.set_stroke_style_str(color);
-		self.render_context.stroke();
+		self.render_context().set_stroke_style_str(color);
+		self.render_context().stroke();
 	}
 
 	/// Fills the area inside the path. Assumes `color` is in gamma space.
 	/// Used by the Pen tool to show the path being closed.
 	pub fn fill_path(&mut self, subpaths: impl Iterator<Item = impl Borrow<Subpath<PointId>>>, transform: DAffine2, color: &str) {
+		if self.recording.is_some() {
+			let curves = recorded_subpaths(subpaths, transform);
+			self.record(|| OverlayCommand::FillPath { curves, color: color.to_string() });
+			return;
+		}
+
 		self.push_path(subpaths, transform);
 
-		self.render_context.set_fill_style_str(color);
-		self.render_context.fill();
+		self.render_context().set_fill_style_str(color);
+		self.render_context().fill();
 	}
 
 	/// Fills the area inside the path with a pattern. Assumes `color` is in gamma space.
@@ -671,6 +995,15 @@ impl OverlayContext {
 		const PATTERN_WIDTH: usize = 4;
 		const PATTERN_HEIGHT: usize = 4;
 
+		if self.recording.is_some() {
+			let curves = recorded_subpaths(subpaths, transform);
+			self.record(|| OverlayCommand::FillPath {
+				curves,
+				color: format!("#{}", color.to_rgba_hex_srgb()),
+			});
+			return;
+		}
+
 		let pattern_canvas = OffscreenCanvas::new(PATTERN_WIDTH as u32, PATTERN_HEIGHT as u32).unwrap();
 		let pattern_context: OffscreenCanvasRenderingContext2d = pattern_canvas
 			.get_context("2d")
@@ -696,20 +1029,35 @@ impl OverlayContext {
 
 		let image_data = web_sys::ImageData::new_with_u8_clamped_array_and_sh(wasm_bindgen::Clamped(&mut data), PATTERN_WIDTH as u32, PATTERN_HEIGHT as u32).unwrap();
 		pattern_context.put_image_data(&image_data, 0., 0.).unwrap();
-		let pattern = self.render_context.create_pattern_with_offscreen_canvas(&pattern_canvas, "repeat").unwrap().unwrap();
+		let pattern = self.render_context().create_pattern_with_offscreen_canvas(&pattern_canvas, "repeat").unwrap().unwrap();
 
 		self.push_path(subpaths, transform);
 
-		self.render_context.set_fill_style_canvas_pattern(&pattern);
-		self.render_context.fill();
+		self.render_context().set_fill_style_canvas_pattern(&pattern);
+		self.render_context().fill();
 	}
 
 	pub fn get_width(&self, text: &str) -> f64 {
-		self.render_context.measure_text(text).expect("Failed to measure text dimensions").width()
+		// Without a canvas to measure with, recordings assume a fixed width per character
+		if self.recording.is_some() {
+			return text.chars().count() as f64 * 6.;
+		}
+
+		self.render_context().measure_text(text).expect("Failed to measure text dimensions").width()
 	}
 
 	pub fn text(&self, text: &str, font_color: &str, background_color: Option<&str>, transform: DAffine2, padding: f64, pivot: [Pivot; 2]) {
-		let metrics = self.render_context.measure_text(text).expect("Failed to measure the text dimensions");
+		if self.record(|| OverlayCommand::Text {
+			text: text.to_string(),
+			font_color: font_color.to_string(),
+			background_color: background_color.map(str::to_string),
+			transform: transform.to_cols_array().map(recorded_value),
+			pivot,
+		}) {
+			return;
+		}
+
+		let metrics = self.render_context().measure_text(text).expect("Failed to measure the text dimensions");
 		let x = match pivot[0] {
 			Pivot::Start => padding,
 			Pivot::Middle => -(metrics.actual_bounding_box_right() + metrics.actual_bounding_box_left()) / 2.,
@@ -722,11 +1070,13 @@ impl OverlayContext {
 		};
 
 		let [a, b, c, d, e, f] = (DAffine2::from_scale(DVec2::splat(self.device_pixel_ratio)) * transform * DAffine2::from_translation(DVec2::new(x, y))).to_cols_array();
-		self.render_context.set_transform(a, b, c, d, e, f).expect("Failed to rotate the render context to the specified angle");
+		self.render_context()
+			.set_transform(a, b, c, d, e, f)
+			.expect("Failed to rotate the render context to the specified angle");
 
 		if let Some(background) = background_color {
-			self.render_context.set_fill_style_str(background);
-			self.render_context.fill_rect(
+			self.render_context().set_fill_style_str(background);
+			self.render_context().fill_rect(
 				-padding,
 				padding,
 				metrics.actual_bounding_box_right() - metrics.actual_bounding_box_left() + padding * 2.,
@@ -734,13 +1084,14 @@ impl OverlayContext {
 			);
 		}
 
-		self.render_context.set_font("12px Source Sans Pro, Arial, sans-serif");
-		self.render_context.set_fill_style_str(font_color);
-		self.render_context.fill_text(text, 0., 0.).expect("Failed to draw the text at the calculated position");
-		self.render_context.reset_transform().expect("Failed to reset the render context transform");
+		self.render_context().set_font("12px Source Sans Pro, Arial, sans-serif");
+		self.render_context().set_fill_style_str(font_color);
+		self.render_context().fill_text(text, 0., 0.).expect("Failed to draw the text at the calculated position");
+		self.render_context().reset_transform().expect("Failed to reset the render context transform");
 	}
 }
 
+#[derive(PartialEq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub enum Pivot {
 	Start,
 	Middle,
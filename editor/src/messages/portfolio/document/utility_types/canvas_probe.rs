@@ -0,0 +1,139 @@
+use super::document_metadata::LayerNodeIdentifier;
+use super::network_interface::NodeNetworkInterface;
+use crate::consts::{CANVAS_PROBE_LINE_HEIGHT, COLOR_OVERLAY_BLUE, COLOR_OVERLAY_LABEL_BACKGROUND, COLOR_OVERLAY_WHITE};
+use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, Pivot};
+use crate::messages::prelude::*;
+use bezier_rs::TValue;
+use glam::{DAffine2, DVec2};
+use graphene_core::Color;
+use graphene_core::vector::PointId;
+use graphene_std::vector::SegmentId;
+
+/// What was found under a position clicked on the canvas while probing, to help debug why the artwork looks the way it does there.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CanvasProbe {
+	/// The probed position in document space.
+	pub position: DVec2,
+	/// The topmost layer with a click target under the position.
+	pub layer: Option<LayerNodeIdentifier>,
+	/// The point of the layer's vector data nearest to the position, along with where it is in document space.
+	pub nearest_point: Option<(PointId, DVec2)>,
+	/// The segment of the layer's vector data nearest to the position, along with its closest location to the position in document space.
+	pub nearest_segment: Option<(SegmentId, DVec2)>,
+	/// The color of the layer's raster content at the position, which is filled in once the node runtime has sampled it.
+	pub color: Option<Color>,
+}
+
+impl CanvasProbe {
+	/// Probes the document under the cursor using the click targets and vector data of the last render.
+	pub fn new(document: &DocumentMessageHandler, ipp: &InputPreprocessorMessageHandler) -> Self {
+		let position = document.metadata().document_to_viewport.inverse().transform_point2(ipp.mouse.position);
+		let layer = document.click(ipp);
+
+		let mut probe = Self {
+			position,
+			layer,
+			nearest_point: None,
+			nearest_segment: None,
+			color: None,
+		};
+		let Some(layer) = layer else { return probe };
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else {
+			return probe;
+		};
+		let transform = document.metadata().transform_to_document(layer);
+		let closest = |(_, a): &(_, DVec2), (_, b): &(_, DVec2)| a.distance_squared(position).total_cmp(&b.distance_squared(position));
+
+		probe.nearest_point = vector_data
+			.point_domain
+			.ids()
+			.iter()
+			.zip(vector_data.point_domain.positions())
+			.map(|(&point, &point_position)| (point, transform.transform_point2(point_position)))
+			.min_by(closest);
+		probe.nearest_segment = vector_data
+			.segment_bezier_iter()
+			.map(|(segment, bezier, _, _)| {
+				let bezier = bezier.apply_transformation(|point| transform.transform_point2(point));
+				(segment, bezier.evaluate(TValue::Parametric(bezier.project(position))))
+			})
+			.min_by(closest);
+
+		probe
+	}
+
+	/// The lines of the tooltip describing what was found.
+	pub fn description(&self, network_interface: &NodeNetworkInterface) -> Vec<String> {
+		let format_position = |position: DVec2| format!("({:.2}, {:.2})", position.x, position.y);
+
+		let layer = match self.layer {
+			Some(layer) => network_interface.display_name(&layer.to_node(), &[]),
+			None => "None".to_string(),
+		};
+		let mut lines = vec![format!("Position: {}", format_position(self.position)), format!("Layer: {layer}")];
+
+		if let Some(color) = self.color {
+			lines.push(format!("Color: #{}", color.to_rgba_hex_srgb()));
+		}
+		if let Some((point, position)) = self.nearest_point {
+			lines.push(format!("Nearest Point: {} at {}", point.inner(), format_position(position)));
+		}
+		if let Some((segment, position)) = self.nearest_segment {
+			let distance = position.distance(self.position);
+			lines.push(format!("Nearest Segment: {} at {} ({distance:.2} away)", segment.inner(), format_position(position)));
+		}
+
+		lines
+	}
+
+	/// Marks the probed position and the nearest point and segment location, with the tooltip beside the probed position.
+	pub fn draw(&self, overlay_context: &mut OverlayContext, document: &DocumentMessageHandler) {
+		let document_to_viewport = document.metadata().document_to_viewport;
+		let position = document_to_viewport.transform_point2(self.position);
+
+		if let Some((_, segment_position)) = self.nearest_segment {
+			overlay_context.line(position, document_to_viewport.transform_point2(segment_position), Some(COLOR_OVERLAY_BLUE), None);
+		}
+		if let Some((_, point_position)) = self.nearest_point {
+			overlay_context.manipulator_anchor(document_to_viewport.transform_point2(point_position), true, None);
+		}
+		overlay_context.circle(position, 3., Some(COLOR_OVERLAY_WHITE), Some(COLOR_OVERLAY_BLUE));
+
+		for (index, line) in self.description(&document.network_interface).iter().enumerate() {
+			let transform = DAffine2::from_translation(position + DVec2::new(8., 8. + index as f64 * CANVAS_PROBE_LINE_HEIGHT));
+			overlay_context.text(line, COLOR_OVERLAY_WHITE, Some(COLOR_OVERLAY_LABEL_BACKGROUND), transform, 4., [Pivot::Start, Pivot::Start]);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::test_utils::test_prelude::*;
+
+	#[tokio::test]
+	async fn clicking_while_probing_finds_the_layer_and_nearest_geometry() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+		let layer_count = editor.active_document().metadata().all_layers().count();
+
+		editor.handle_message(PortfolioMessage::ToggleCanvasProbe).await;
+		editor.move_mouse(90., 60., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(90., 60., ModifierKeys::empty()).await;
+
+		let document = editor.active_document();
+		let probe = document.canvas_probe.clone().expect("Clicking the canvas should probe it");
+		let document_to_viewport = document.metadata().document_to_viewport;
+		assert_eq!(probe.layer, Some(layer));
+		let (_, nearest_point) = probe.nearest_point.expect("The rectangle's corners should be found");
+		assert!(document_to_viewport.transform_point2(nearest_point).abs_diff_eq(DVec2::new(100., 100.), 1e-6));
+		let (_, nearest_segment) = probe.nearest_segment.expect("The rectangle's sides should be found");
+		assert!(document_to_viewport.transform_point2(nearest_segment).abs_diff_eq(DVec2::new(100., 60.), 1e-6));
+		assert_eq!(document.metadata().all_layers().count(), layer_count, "The click shouldn't reach the active tool");
+
+		// Turning probing off removes the result
+		editor.handle_message(PortfolioMessage::ToggleCanvasProbe).await;
+		assert!(editor.active_document().canvas_probe.is_none());
+	}
+}
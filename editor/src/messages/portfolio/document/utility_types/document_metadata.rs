@@ -3,12 +3,16 @@ use crate::messages::portfolio::document::graph_operation::transform_utils;
 use crate::messages::portfolio::document::graph_operation::utility_types::ModifyInputsContext;
 use glam::{DAffine2, DVec2};
 use graph_craft::document::NodeId;
-use graphene_core::renderer::ClickTarget;
 use graphene_core::renderer::Quad;
+use graphene_core::renderer::{ClickTarget, SubpathClickRegion};
 use graphene_core::transform::Footprint;
 use graphene_std::vector::{PointId, VectorData};
 use std::collections::{HashMap, HashSet};
 use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The index of a subpath within a layer's click targets, as found by [`DocumentMetadata::subpath_click_regions`].
+pub type SubpathIndex = usize;
 
 // ================
 // DocumentMetadata
@@ -22,10 +26,16 @@ pub struct DocumentMetadata {
 	pub local_transforms: HashMap<NodeId, DAffine2>,
 	pub structure: HashMap<LayerNodeIdentifier, NodeRelations>,
 	pub click_targets: HashMap<LayerNodeIdentifier, Vec<ClickTarget>>,
+	/// Only populated while the render metadata includes subpath click regions (i.e. when the Path tool is active).
+	pub subpath_click_regions: HashMap<LayerNodeIdentifier, Vec<SubpathClickRegion>>,
 	pub clip_targets: HashSet<NodeId>,
+	/// The transforms of the copies of each instanced layer's geometry, relative to that geometry, for layers whose content is repeated by a node such as Repeat.
+	pub instance_transforms: HashMap<LayerNodeIdentifier, Vec<DAffine2>>,
 	pub vector_modify: HashMap<NodeId, VectorData>,
 	/// Transform from document space to viewport space.
 	pub document_to_viewport: DAffine2,
+	/// Replaced by a never before used value whenever the vector data of any layer may have changed, so caches built from that data know to rebuild.
+	pub vector_revision: u64,
 }
 
 impl Default for DocumentMetadata {
@@ -36,12 +46,21 @@ impl Default for DocumentMetadata {
 			structure: HashMap::new(),
 			vector_modify: HashMap::new(),
 			click_targets: HashMap::new(),
+			subpath_click_regions: HashMap::new(),
 			clip_targets: HashSet::new(),
+			instance_transforms: HashMap::new(),
 			document_to_viewport: DAffine2::IDENTITY,
+			vector_revision: next_vector_revision(),
 		}
 	}
 }
 
+/// A revision number distinct from all others handed out, even those of other documents.
+pub fn next_vector_revision() -> u64 {
+	static NEXT_REVISION: AtomicU64 = AtomicU64::new(0);
+	NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
 // =================================
 // DocumentMetadata: Layer iterators
 // =================================
@@ -59,6 +78,15 @@ impl DocumentMetadata {
 		self.click_targets.get(&layer)
 	}
 
+	pub fn subpath_click_regions(&self, layer: LayerNodeIdentifier) -> Option<&Vec<SubpathClickRegion>> {
+		self.subpath_click_regions.get(&layer)
+	}
+
+	/// The transforms of the copies of the layer's geometry made by an instancing node, or an empty slice if the layer isn't instanced.
+	pub fn instance_transforms(&self, layer: LayerNodeIdentifier) -> &[DAffine2] {
+		self.instance_transforms.get(&layer).map_or(&[], Vec::as_slice)
+	}
+
 	/// Access the [`NodeRelations`] of a layer.
 	fn get_relations(&self, node_identifier: LayerNodeIdentifier) -> Option<&NodeRelations> {
 		self.structure.get(&node_identifier)
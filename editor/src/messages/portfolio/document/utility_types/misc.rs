@@ -54,8 +54,40 @@ impl DocumentMode {
 	}
 }
 
+/// The renderer used to draw a document's artwork in the viewport.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum ViewportRenderer {
+	Svg = 0,
+	Canvas = 1,
+}
+
+impl Default for ViewportRenderer {
+	fn default() -> Self {
+		// Builds with a canvas renderer use it unless told otherwise
+		if cfg!(any(feature = "resvg", feature = "vello")) { Self::Canvas } else { Self::Svg }
+	}
+}
+
+impl fmt::Display for ViewportRenderer {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			ViewportRenderer::Svg => write!(f, "SVG"),
+			ViewportRenderer::Canvas => write!(f, "Canvas"),
+		}
+	}
+}
+
+impl ViewportRenderer {
+	pub fn tooltip_description(&self) -> &'static str {
+		match self {
+			ViewportRenderer::Svg => "Draw the artwork as SVG in the page, which gives the most faithful results",
+			ViewportRenderer::Canvas => "Draw the artwork to a canvas, which is faster for documents with thousands of elements (falls back to SVG if the canvas can't be set up)",
+		}
+	}
+}
+
 /// SnappingState determines the current individual snapping states
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct SnappingState {
 	pub snapping_enabled: bool,
@@ -101,6 +133,7 @@ impl SnappingState {
 				PathSnapTarget::IntersectionPoint => self.path.path_intersection_point,
 				PathSnapTarget::PerpendicularToEndpoint => self.path.perpendicular_from_endpoint,
 			},
+			SnapTarget::Artboard(ArtboardSnapTarget::CenterLine) => self.artboards && self.path.artboard_center_lines,
 			SnapTarget::Artboard(_) => self.artboards,
 			SnapTarget::Grid(_) => self.grid_snapping,
 			SnapTarget::Alignment(AlignmentSnapTarget::AlignWithAnchorPoint) => self.path.align_with_anchor_point,
@@ -111,7 +144,7 @@ impl SnappingState {
 	}
 }
 
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct BoundingBoxSnapping {
 	pub center_point: bool,
@@ -133,7 +166,7 @@ impl Default for BoundingBoxSnapping {
 	}
 }
 
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct PathSnapping {
 	pub anchor_point: bool,
@@ -144,6 +177,7 @@ pub struct PathSnapping {
 	pub path_intersection_point: bool,
 	pub align_with_anchor_point: bool, // TODO: Rename
 	pub perpendicular_from_endpoint: bool,
+	pub artboard_center_lines: bool,
 }
 
 impl Default for PathSnapping {
@@ -157,6 +191,7 @@ impl Default for PathSnapping {
 			path_intersection_point: true,
 			align_with_anchor_point: true,
 			perpendicular_from_endpoint: true,
+			artboard_center_lines: true,
 		}
 	}
 }
@@ -408,11 +443,11 @@ pub const SNAP_FUNCTIONS_FOR_BOUNDING_BOXES: [(&str, GetSnapState, &str); 5] = [
 		"Snaps to a consistent distance offset established by the bounding boxes of nearby layers\n(due to a bug, 'Center Points' and 'Corner Points' must be enabled)",
 	),
 ];
-pub const SNAP_FUNCTIONS_FOR_PATHS: [(&str, GetSnapState, &str); 7] = [
+pub const SNAP_FUNCTIONS_FOR_PATHS: [(&str, GetSnapState, &str); 8] = [
 	(
 		"Align with Anchor Points",
 		(|snapping_state: &mut SnappingState| &mut snapping_state.path.align_with_anchor_point) as GetSnapState,
-		"Snaps to horizontal/vertical alignment with the anchor points of any vector path",
+		"Snaps to horizontal/vertical alignment with the anchor points of any vector path, including the other anchors of the paths whose points are being dragged",
 	),
 	(
 		"Anchor Points",
@@ -451,6 +486,11 @@ pub const SNAP_FUNCTIONS_FOR_PATHS: [(&str, GetSnapState, &str); 7] = [
 		// TODO: Fix the bug/limitation that requires 'Intersections of Paths' to be enabled
 		"Snaps a line to a point tangent to a vector path\n(due to a bug, 'Intersections of Paths' must be enabled)",
 	),
+	(
+		"Artboard Center Lines",
+		(|snapping_state: &mut SnappingState| &mut snapping_state.path.artboard_center_lines) as GetSnapState,
+		"Snaps dragged points to the horizontal and vertical lines through the center of the artboard containing their layer",
+	),
 ];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -501,6 +541,7 @@ pub enum ArtboardSnapTarget {
 	CornerPoint,
 	CenterPoint,
 	AlongEdge,
+	CenterLine,
 }
 
 impl fmt::Display for ArtboardSnapTarget {
@@ -509,6 +550,7 @@ impl fmt::Display for ArtboardSnapTarget {
 			ArtboardSnapTarget::CornerPoint => write!(f, "Artboard: Corner Point"),
 			ArtboardSnapTarget::CenterPoint => write!(f, "Artboard: Center Point"),
 			ArtboardSnapTarget::AlongEdge => write!(f, "Artboard: Along Edge"),
+			ArtboardSnapTarget::CenterLine => write!(f, "Artboard: Center Line"),
 		}
 	}
 }
@@ -1,3 +1,4 @@
+pub mod canvas_probe;
 pub mod clipboards;
 pub mod document_metadata;
 pub mod error;
@@ -1,4 +1,4 @@
-use super::document_metadata::{DocumentMetadata, LayerNodeIdentifier, NodeRelations};
+use super::document_metadata::{DocumentMetadata, LayerNodeIdentifier, NodeRelations, next_vector_revision};
 use super::misc::PTZ;
 use super::nodes::SelectedNodes;
 use crate::consts::{EXPORTS_TO_RIGHT_EDGE_PIXEL_GAP, EXPORTS_TO_TOP_EDGE_PIXEL_GAP, GRID_SIZE, IMPORTS_TO_LEFT_EDGE_PIXEL_GAP, IMPORTS_TO_TOP_EDGE_PIXEL_GAP};
@@ -12,7 +12,7 @@ use glam::{DAffine2, DVec2, IVec2};
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{DocumentNode, DocumentNodeImplementation, NodeId, NodeInput, NodeNetwork, OldDocumentNodeImplementation, OldNodeNetwork};
 use graph_craft::{Type, concrete};
-use graphene_std::renderer::{ClickTarget, Quad};
+use graphene_std::renderer::{ClickTarget, Quad, SubpathClickRegion};
 use graphene_std::transform::Footprint;
 use graphene_std::vector::{PointId, VectorData, VectorModificationType};
 use interpreted_executor::dynamic_executor::ResolvedDocumentNodeTypes;
@@ -206,6 +206,49 @@ impl NodeNetworkInterface {
 		layers
 	}
 
+	/// Returns the number of nodes downstream from a node which aren't layers, such as boolean operations and instancers, whose results depend on the node's output.
+	pub fn downstream_dependent_count(&mut self, node_id: &NodeId, network_path: &[NodeId]) -> usize {
+		let cached = self
+			.network_metadata(network_path)
+			.and_then(|network_metadata| match &network_metadata.transient_metadata.downstream_dependents {
+				TransientMetadata::Loaded(downstream_dependents) => downstream_dependents.get(node_id).copied(),
+				TransientMetadata::Unloaded => None,
+			});
+		if let Some(count) = cached {
+			return count;
+		}
+
+		let mut downstream_nodes = HashSet::new();
+		let mut stack = vec![*node_id];
+		while let Some(current_node) = stack.pop() {
+			let number_of_outputs = self.number_of_outputs(&current_node, network_path);
+			let Some(outward_wires) = self.outward_wires(network_path) else {
+				log::error!("Could not get outward wires in downstream_dependent_count");
+				return 0;
+			};
+			let downstream = (0..number_of_outputs)
+				.filter_map(|output_index| outward_wires.get(&OutputConnector::node(current_node, output_index)))
+				.flatten()
+				.filter_map(|input_connector| input_connector.node_id())
+				.collect::<Vec<_>>();
+			stack.extend(downstream.into_iter().filter(|&downstream_node| downstream_nodes.insert(downstream_node)));
+		}
+		let count = downstream_nodes.iter().filter(|downstream_node| !self.is_layer(downstream_node, network_path)).count();
+
+		let Some(network_metadata) = self.network_metadata_mut(network_path) else {
+			log::error!("Could not get nested network_metadata in downstream_dependent_count");
+			return count;
+		};
+		let downstream_dependents = &mut network_metadata.transient_metadata.downstream_dependents;
+		if !downstream_dependents.is_loaded() {
+			*downstream_dependents = TransientMetadata::Loaded(HashMap::new());
+		}
+		if let TransientMetadata::Loaded(downstream_dependents) = downstream_dependents {
+			downstream_dependents.insert(*node_id, count);
+		}
+		count
+	}
+
 	pub fn chain_width(&self, node_id: &NodeId, network_path: &[NodeId]) -> u32 {
 		if self.number_of_displayed_inputs(node_id, network_path) > 1 {
 			let mut last_chain_node_distance = 0u32;
@@ -1214,6 +1257,14 @@ impl NodeNetworkInterface {
 		node_metadata.persistent_metadata.locked
 	}
 
+	pub fn is_edit_locked(&self, node_id: &NodeId, network_path: &[NodeId]) -> bool {
+		let Some(node_metadata) = self.node_metadata(node_id, network_path) else {
+			log::error!("Could not get persistent node metadata in is_edit_locked for node {node_id}");
+			return false;
+		};
+		node_metadata.persistent_metadata.edit_locked
+	}
+
 	pub fn is_pinned(&self, node_id: &NodeId, network_path: &[NodeId]) -> bool {
 		let Some(node_metadata) = self.node_metadata(node_id, network_path) else {
 			log::error!("Could not get persistent node metadata in is_pinned for node {node_id}");
@@ -1693,6 +1744,7 @@ impl NodeNetworkInterface {
 	}
 
 	pub fn transaction_modified(&mut self) {
+		self.document_metadata.vector_revision = next_vector_revision();
 		if self.transaction_status == TransactionStatus::Started {
 			self.transaction_status = TransactionStatus::Modified;
 		}
@@ -2415,6 +2467,7 @@ impl NodeNetworkInterface {
 			return;
 		};
 		network_metadata.transient_metadata.outward_wires.unload();
+		network_metadata.transient_metadata.downstream_dependents.unload();
 	}
 
 	pub fn layer_width(&mut self, node_id: &NodeId, network_path: &[NodeId]) -> Option<u32> {
@@ -3262,6 +3315,7 @@ impl NodeNetworkInterface {
 		self.document_metadata.local_transforms.retain(|node, _| nodes.contains(node));
 		self.document_metadata.vector_modify.retain(|node, _| nodes.contains(node));
 		self.document_metadata.click_targets.retain(|layer, _| self.document_metadata.structure.contains_key(layer));
+		self.document_metadata.instance_transforms.retain(|layer, _| self.document_metadata.structure.contains_key(layer));
 	}
 
 	/// Update the cached transforms of the layers
@@ -3273,6 +3327,17 @@ impl NodeNetworkInterface {
 	/// Update the cached click targets of the layers
 	pub fn update_click_targets(&mut self, new_click_targets: HashMap<LayerNodeIdentifier, Vec<ClickTarget>>) {
 		self.document_metadata.click_targets = new_click_targets;
+		self.document_metadata.vector_revision = next_vector_revision();
+	}
+
+	/// Update the cached subpath click regions of the layers
+	pub fn update_subpath_click_regions(&mut self, new_subpath_click_regions: HashMap<LayerNodeIdentifier, Vec<SubpathClickRegion>>) {
+		self.document_metadata.subpath_click_regions = new_subpath_click_regions;
+	}
+
+	/// Update the cached transforms of the copies of instanced layers
+	pub fn update_instance_transforms(&mut self, new_instance_transforms: HashMap<LayerNodeIdentifier, Vec<DAffine2>>) {
+		self.document_metadata.instance_transforms = new_instance_transforms;
 	}
 
 	/// Update the cached clip targets of the layers
@@ -3283,6 +3348,7 @@ impl NodeNetworkInterface {
 	/// Update the vector modify of the layers
 	pub fn update_vector_modify(&mut self, new_vector_modify: HashMap<NodeId, VectorData>) {
 		self.document_metadata.vector_modify = new_vector_modify;
+		self.document_metadata.vector_revision = next_vector_revision();
 	}
 }
 
@@ -3352,6 +3418,38 @@ impl NodeNetworkInterface {
 		self.transaction_modified();
 	}
 
+	/// Repairs non-finite coordinates in the path edits stored throughout the document, returning how many were repaired.
+	pub fn sanitize_vector_modifications(&mut self) -> usize {
+		fn sanitize(network: &mut NodeNetwork) -> usize {
+			let mut repaired = 0;
+			for node in network.nodes.values_mut() {
+				for input in &mut node.inputs {
+					if !matches!(input.as_value(), Some(TaggedValue::VectorModification(_))) {
+						continue;
+					}
+					let mut value = input.as_value_mut();
+					if let Some(TaggedValue::VectorModification(modification)) = value.as_deref_mut() {
+						repaired += modification.sanitize();
+					}
+				}
+				if let DocumentNodeImplementation::Network(nested_network) = &mut node.implementation {
+					repaired += sanitize(nested_network);
+				}
+			}
+			repaired
+		}
+
+		let Some(network) = self.network_mut(&[]) else {
+			log::error!("Could not get document network in sanitize_vector_modifications");
+			return 0;
+		};
+		let repaired = sanitize(network);
+		if repaired > 0 {
+			self.transaction_modified();
+		}
+		repaired
+	}
+
 	/// Inserts a new export at insert index. If the insert index is -1 it is inserted at the end. The output_name is used by the encapsulating node.
 	pub fn add_export(&mut self, default_value: TaggedValue, insert_index: isize, output_name: &str, network_path: &[NodeId]) {
 		let Some(network) = self.network_mut(network_path) else {
@@ -4495,6 +4593,16 @@ impl NodeNetworkInterface {
 		self.transaction_modified();
 	}
 
+	pub fn set_edit_locked(&mut self, node_id: &NodeId, network_path: &[NodeId], edit_locked: bool) {
+		let Some(node_metadata) = self.node_metadata_mut(node_id, network_path) else {
+			log::error!("Could not get node {node_id} in set_edit_locked");
+			return;
+		};
+
+		node_metadata.persistent_metadata.edit_locked = edit_locked;
+		self.transaction_modified();
+	}
+
 	pub fn set_to_node_or_layer(&mut self, node_id: &NodeId, network_path: &[NodeId], is_layer: bool) {
 		// If a layer is set to a node, set upstream nodes to absolute position, and upstream siblings to absolute position
 		let child_id = { self.upstream_flow_back_from_nodes(vec![*node_id], network_path, FlowType::HorizontalFlow).nth(1) };
@@ -6072,6 +6180,8 @@ pub struct NodeNetworkTransientMetadata {
 	// node_group_bounding_box: Vec<(Subpath<ManipulatorGroupId>, Vec<Nodes>)>,
 	/// Cache for all outward wire connections
 	pub outward_wires: TransientMetadata<HashMap<OutputConnector, Vec<InputConnector>>>,
+	/// Cache for the number of non-layer nodes downstream from each node queried so far, which is unloaded along with the outward wires it is found from.
+	pub downstream_dependents: TransientMetadata<HashMap<NodeId, usize>>,
 	// TODO: Cache all wire paths instead of calculating in Graph.svelte
 	// pub wire_paths: Vec<WirePath>
 	/// All export connector click targets
@@ -6344,6 +6454,10 @@ pub struct DocumentNodePersistentMetadata {
 	/// Represents the lock icon for locking/unlocking the node in the graph UI. When locked, a node cannot be moved in the graph UI.
 	#[serde(default)]
 	pub locked: bool,
+	/// Protects a layer's points from being edited by the Path tool while keeping the layer itself visible and selectable as a whole.
+	/// Unlike `locked`, this doesn't remove the layer from selection.
+	#[serde(default)]
+	pub edit_locked: bool,
 	/// Indicates that the node will be shown in the Properties panel when it would otherwise be empty, letting a user easily edit its properties by just deselecting everything.
 	#[serde(default)]
 	pub pinned: bool,
@@ -6364,6 +6478,7 @@ impl Default for DocumentNodePersistentMetadata {
 			has_primary_output: true,
 			pinned: false,
 			locked: false,
+			edit_locked: false,
 			node_type_metadata: NodeTypePersistentMetadata::default(),
 			network_metadata: None,
 		}
@@ -6410,6 +6525,7 @@ impl From<DocumentNodePersistentMetadataInputNames> for DocumentNodePersistentMe
 			output_names: old.output_names,
 			has_primary_output: old.has_primary_output,
 			locked: old.locked,
+			edit_locked: false,
 			pinned: old.pinned,
 			node_type_metadata: old.node_type_metadata,
 			network_metadata: old.network_metadata,
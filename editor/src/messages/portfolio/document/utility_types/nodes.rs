@@ -48,6 +48,8 @@ pub struct LayerPanelEntry {
 	pub unlocked: bool,
 	#[serde(rename = "parentsUnlocked")]
 	pub parents_unlocked: bool,
+	#[serde(rename = "editLocked")]
+	pub edit_locked: bool,
 	#[serde(rename = "parentId")]
 	pub parent_id: Option<NodeId>,
 	pub selected: bool,
@@ -86,6 +88,16 @@ impl SelectedNodes {
 		})
 	}
 
+	pub fn layer_edit_locked(&self, layer: LayerNodeIdentifier, network_interface: &NodeNetworkInterface) -> bool {
+		layer.ancestors(network_interface.document_metadata()).any(|layer| {
+			if layer != LayerNodeIdentifier::ROOT_PARENT {
+				network_interface.is_edit_locked(&layer.to_node(), &[])
+			} else {
+				false
+			}
+		})
+	}
+
 	pub fn selected_unlocked_layers<'a>(&'a self, network_interface: &'a NodeNetworkInterface) -> impl Iterator<Item = LayerNodeIdentifier> + 'a {
 		self.selected_layers(network_interface.document_metadata())
 			.filter(move |&layer| !self.layer_locked(layer, network_interface))
@@ -2,7 +2,7 @@ use crate::messages::debug::utility_types::MessageLoggingVerbosity;
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GroupFolderType};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis, GroupFolderType, ViewportRenderer};
 use crate::messages::prelude::*;
 use graphene_std::vector::misc::BooleanOperation;
 
@@ -10,13 +10,17 @@ use graphene_std::vector::misc::BooleanOperation;
 pub struct MenuBarMessageHandler {
 	pub has_active_document: bool,
 	pub rulers_visible: bool,
+	pub viewport_renderer: Option<ViewportRenderer>,
 	pub node_graph_open: bool,
 	pub has_selected_nodes: bool,
 	pub has_selected_layers: bool,
 	pub has_selection_history: (bool, bool),
 	pub spreadsheet_view_open: bool,
+	pub point_table_open: bool,
 	pub message_logging_verbosity: MessageLoggingVerbosity,
 	pub reset_node_definitions_on_open: bool,
+	pub canvas_probe: bool,
+	pub deterministic_ids: bool,
 }
 
 impl MessageHandler<MenuBarMessage, ()> for MenuBarMessageHandler {
@@ -42,6 +46,9 @@ impl LayoutHolder for MenuBarMessageHandler {
 		let message_logging_verbosity_names = self.message_logging_verbosity == MessageLoggingVerbosity::Names;
 		let message_logging_verbosity_contents = self.message_logging_verbosity == MessageLoggingVerbosity::Contents;
 		let reset_node_definitions_on_open = self.reset_node_definitions_on_open;
+		let canvas_probe = self.canvas_probe;
+		let deterministic_ids = self.deterministic_ids;
+		let viewport_renderer = self.viewport_renderer;
 
 		let menu_bar_entries = vec![
 			MenuBarEntry {
@@ -201,6 +208,13 @@ impl LayoutHolder for MenuBarMessageHandler {
 						disabled: no_active_document,
 						..MenuBarEntry::default()
 					}],
+					vec![MenuBarEntry {
+						label: "Deterministic Point IDs".into(),
+						icon: Some(if deterministic_ids { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+						action: MenuBarEntry::create_action(move |_| DocumentMessage::SetDeterministicIds { enabled: !deterministic_ids }.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
 				]),
 			),
 			MenuBarEntry::new_root(
@@ -433,6 +447,12 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
+					vec![MenuBarEntry {
+						label: "Add Point at Length…".into(),
+						action: MenuBarEntry::create_action(|_| DialogMessage::RequestAddPointAtLengthDialog.into()),
+						disabled: no_active_document || !has_selected_layers,
+						..MenuBarEntry::default()
+					}],
 				]),
 			),
 			MenuBarEntry::new_root(
@@ -565,6 +585,31 @@ impl LayoutHolder for MenuBarMessageHandler {
 						disabled: no_active_document,
 						..MenuBarEntry::default()
 					}],
+					vec![MenuBarEntry {
+						label: "Renderer".into(),
+						action: MenuBarEntry::no_action(),
+						disabled: no_active_document,
+						children: MenuBarEntryChildren(vec![
+							vec![MenuBarEntry {
+								label: "Preference Default".into(),
+								icon: viewport_renderer.is_none().then_some("SmallDot".into()),
+								action: MenuBarEntry::create_action(|_| DocumentMessage::SetViewportRenderer { renderer: None }.into()),
+								disabled: no_active_document,
+								..MenuBarEntry::default()
+							}],
+							[ViewportRenderer::Svg, ViewportRenderer::Canvas]
+								.into_iter()
+								.map(|renderer| MenuBarEntry {
+									label: renderer.to_string(),
+									icon: (viewport_renderer == Some(renderer)).then_some("SmallDot".into()),
+									action: MenuBarEntry::create_action(move |_| DocumentMessage::SetViewportRenderer { renderer: Some(renderer) }.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								})
+								.collect(),
+						]),
+						..MenuBarEntry::default()
+					}],
 					vec![MenuBarEntry {
 						label: "Window: Spreadsheet".into(),
 						icon: Some(if self.spreadsheet_view_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
@@ -572,6 +617,19 @@ impl LayoutHolder for MenuBarMessageHandler {
 						disabled: no_active_document,
 						..MenuBarEntry::default()
 					}],
+					vec![MenuBarEntry {
+						label: "Window: Point Table".into(),
+						icon: Some(if self.point_table_open { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+						action: MenuBarEntry::create_action(|_| PointTableMessage::ToggleOpen.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
+					vec![MenuBarEntry {
+						label: "Document Statistics…".into(),
+						action: MenuBarEntry::create_action(|_| PortfolioMessage::RequestDocumentStatistics.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
 				]),
 			),
 			MenuBarEntry::new_root(
@@ -670,6 +728,13 @@ impl LayoutHolder for MenuBarMessageHandler {
 									..MenuBarEntry::default()
 								},
 							],
+							vec![MenuBarEntry {
+								label: "Probe Canvas on Click".into(),
+								icon: Some(if canvas_probe { "CheckboxChecked" } else { "CheckboxUnchecked" }.into()),
+								action: MenuBarEntry::create_action(|_| PortfolioMessage::ToggleCanvasProbe.into()),
+								disabled: no_active_document,
+								..MenuBarEntry::default()
+							}],
 							vec![MenuBarEntry {
 								label: "Trigger a Crash".into(),
 								icon: Some("Warning".into()),
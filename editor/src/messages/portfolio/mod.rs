@@ -3,6 +3,7 @@ mod portfolio_message_handler;
 
 pub mod document;
 pub mod menu_bar;
+pub mod point_table;
 pub mod spreadsheet;
 pub mod utility_types;
 
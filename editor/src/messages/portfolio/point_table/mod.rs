@@ -0,0 +1,7 @@
+mod point_table_message;
+mod point_table_message_handler;
+
+#[doc(inline)]
+pub use point_table_message::*;
+#[doc(inline)]
+pub use point_table_message_handler::*;
@@ -0,0 +1,112 @@
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::prelude::*;
+use glam::DVec2;
+use graphene_core::vector::ManipulatorPointId;
+
+/// The Point Table panel lists the selected points of the Path tool so their coordinates can be edited numerically.
+#[impl_message(Message, PortfolioMessage, PointTable)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub enum PointTableMessage {
+	ToggleOpen,
+
+	UpdateRows {
+		rows: Vec<PointTableRow>,
+		/// The number of selected points, which may exceed the number of rows when the selection is truncated.
+		total: usize,
+		/// The number of decimal places the coordinates are rounded to when shown.
+		decimal_places: u32,
+	},
+
+	SortBy {
+		sort: PointTableSort,
+	},
+	SetCell {
+		layer: LayerNodeIdentifier,
+		point: ManipulatorPointId,
+		column: PointTableColumn,
+		value: f64,
+	},
+	/// Fills the editable cells starting at the given row and table column with the tab, comma, or whitespace separated values of each pasted line.
+	PasteCoordinates {
+		text: String,
+		row: usize,
+		column: usize,
+	},
+}
+
+/// One selected point, with its coordinates in document space.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PointTableRow {
+	pub layer: LayerNodeIdentifier,
+	pub layer_name: String,
+	pub point: ManipulatorPointId,
+	pub position: DVec2,
+	/// The position of the anchor the point belongs to, which is the point itself for an anchor.
+	pub anchor: DVec2,
+}
+
+impl PointTableRow {
+	/// The offset of a handle from its anchor, or `None` if the point is an anchor.
+	pub fn handle_offset(&self) -> Option<DVec2> {
+		self.point.as_handle().map(|_| self.position - self.anchor)
+	}
+
+	pub fn value(&self, column: PointTableColumn) -> Option<f64> {
+		match column {
+			PointTableColumn::X => Some(self.position.x),
+			PointTableColumn::Y => Some(self.position.y),
+			PointTableColumn::HandleOffsetX => self.handle_offset().map(|offset| offset.x),
+			PointTableColumn::HandleOffsetY => self.handle_offset().map(|offset| offset.y),
+		}
+	}
+
+	/// Returns the row with one of its cells replaced, keeping the anchor fixed. Handle offset columns can't be set on an anchor.
+	#[must_use]
+	pub fn with_value(self, column: PointTableColumn, value: f64) -> Option<Self> {
+		let position = match column {
+			PointTableColumn::X => DVec2::new(value, self.position.y),
+			PointTableColumn::Y => DVec2::new(self.position.x, value),
+			PointTableColumn::HandleOffsetX => self.anchor + DVec2::new(value, self.handle_offset()?.y),
+			PointTableColumn::HandleOffsetY => self.anchor + DVec2::new(self.handle_offset()?.x, value),
+		};
+		Some(Self { position, ..self })
+	}
+
+	pub fn reposition(&self) -> PointReposition {
+		PointReposition {
+			layer: self.layer,
+			point: self.point,
+			position: self.position,
+		}
+	}
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Default, Debug, serde::Serialize, serde::Deserialize)]
+pub enum PointTableSort {
+	/// Grouped by layer, in the order the points were created.
+	#[default]
+	Layer,
+	X,
+	Y,
+}
+
+/// The editable columns of the table, in the order they are shown.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub enum PointTableColumn {
+	X,
+	Y,
+	HandleOffsetX,
+	HandleOffsetY,
+}
+
+impl PointTableColumn {
+	pub const ALL: [Self; 4] = [Self::X, Self::Y, Self::HandleOffsetX, Self::HandleOffsetY];
+}
+
+/// Moves a point to a new position in document space.
+#[derive(PartialEq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PointReposition {
+	pub layer: LayerNodeIdentifier,
+	pub point: ManipulatorPointId,
+	pub position: DVec2,
+}
@@ -0,0 +1,208 @@
+use super::{PointTableColumn, PointTableRow, PointTableSort};
+use crate::messages::layout::utility_types::layout_widget::{Layout, LayoutGroup, LayoutTarget, WidgetLayout};
+use crate::messages::layout::utility_types::number_format::parse_number;
+use crate::messages::prelude::*;
+use crate::messages::tool::tool_messages::tool_prelude::*;
+use graphene_core::vector::ManipulatorPointId;
+
+/// The index of the first editable column of the table, following the layer and point columns.
+const FIRST_EDITABLE_COLUMN: usize = 2;
+
+/// The Point Table panel lists the selected points of the Path tool so their coordinates can be edited numerically.
+#[derive(Default, Debug, Clone)]
+pub struct PointTableMessageHandler {
+	/// Sets whether or not the point table is drawn.
+	pub point_table_open: bool,
+	/// The listed points, grouped by layer.
+	rows: Vec<PointTableRow>,
+	/// The number of selected points, which may exceed the number of rows.
+	total: usize,
+	sort: PointTableSort,
+	decimal_places: u32,
+}
+
+impl MessageHandler<PointTableMessage, ()> for PointTableMessageHandler {
+	fn process_message(&mut self, message: PointTableMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			PointTableMessage::ToggleOpen => {
+				self.point_table_open = !self.point_table_open;
+				// Ask the Path tool for the current selection
+				if self.point_table_open {
+					responses.add(PathToolMessage::SelectedPointUpdated);
+				}
+				// Update checked UI state for open
+				responses.add(MenuBarMessage::SendLayout);
+				self.update_layout(responses);
+			}
+
+			PointTableMessage::UpdateRows { rows, total, decimal_places } => {
+				self.rows = rows;
+				self.total = total;
+				self.decimal_places = decimal_places;
+				self.update_layout(responses);
+			}
+
+			PointTableMessage::SortBy { sort } => {
+				self.sort = sort;
+				self.update_layout(responses);
+			}
+			PointTableMessage::SetCell { layer, point, column, value } => {
+				let Some(row) = self.rows.iter().find(|row| row.layer == layer && row.point == point) else {
+					return;
+				};
+				let Some(row) = row.clone().with_value(column, value) else { return };
+
+				responses.add(PathToolMessage::RepositionPoints { repositions: vec![row.reposition()] });
+			}
+			PointTableMessage::PasteCoordinates { text, row, column } => {
+				let first_column = column.saturating_sub(FIRST_EDITABLE_COLUMN);
+				let sorted = self.sorted_rows();
+
+				let repositions = text
+					.lines()
+					.filter(|line| !line.trim().is_empty())
+					.zip(sorted.into_iter().skip(row))
+					.filter_map(|(line, row)| {
+						let cells = parse_pasted_line(line);
+						let columns = PointTableColumn::ALL.into_iter().skip(first_column);
+						let edited = cells.zip(columns).fold(row.clone(), |row, (value, column)| match value {
+							Some(value) => row.clone().with_value(column, value).unwrap_or(row),
+							None => row,
+						});
+						(edited != *row).then(|| edited.reposition())
+					})
+					.collect::<Vec<_>>();
+
+				if !repositions.is_empty() {
+					responses.add(PathToolMessage::RepositionPoints { repositions });
+				}
+			}
+		}
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(PointTableMessage;)
+	}
+}
+
+impl PointTableMessageHandler {
+	fn sorted_rows(&self) -> Vec<&PointTableRow> {
+		let mut rows = self.rows.iter().collect::<Vec<_>>();
+		match self.sort {
+			PointTableSort::Layer => {}
+			PointTableSort::X => rows.sort_by(|a, b| a.position.x.total_cmp(&b.position.x)),
+			PointTableSort::Y => rows.sort_by(|a, b| a.position.y.total_cmp(&b.position.y)),
+		}
+		rows
+	}
+
+	fn update_layout(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdatePointTableState { open: self.point_table_open });
+		if !self.point_table_open {
+			return;
+		}
+
+		let entries = [PointTableSort::Layer, PointTableSort::X, PointTableSort::Y]
+			.into_iter()
+			.map(|sort| {
+				RadioEntryData::new(format!("{sort:?}"))
+					.label(format!("{sort:?}"))
+					.on_update(move |_| PointTableMessage::SortBy { sort }.into())
+			})
+			.collect();
+		let sort = vec![
+			TextLabel::new("Sort By").widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			RadioInput::new(entries).selected_index(Some(self.sort as u32)).widget_holder(),
+		];
+		let mut layout = vec![LayoutGroup::Row { widgets: sort }];
+
+		if self.total > self.rows.len() {
+			let notice = format!("Showing the first {} of {} selected points", self.rows.len(), self.total);
+			layout.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new(notice).italic(true).widget_holder()],
+			});
+		}
+
+		if self.rows.is_empty() {
+			let empty = "Select points with the Path tool to list them here";
+			layout.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new(empty).widget_holder()],
+			});
+		} else {
+			let headings = ["Layer", "Point", "X", "Y", "Handle ΔX", "Handle ΔY"];
+			let mut rows = vec![headings.iter().map(|text| TextLabel::new(*text).widget_holder()).collect::<Vec<_>>()];
+			rows.extend(self.sorted_rows().into_iter().map(|row| {
+				let mut cells = vec![TextLabel::new(&row.layer_name).widget_holder(), TextLabel::new(point_label(row.point)).widget_holder()];
+				cells.extend(PointTableColumn::ALL.into_iter().map(|column| cell(row, column, self.decimal_places)));
+				cells
+			}));
+			layout.push(LayoutGroup::Table { rows });
+		}
+
+		responses.add(LayoutMessage::SendLayout {
+			layout: Layout::WidgetLayout(WidgetLayout { layout }),
+			layout_target: LayoutTarget::PointTable,
+		});
+	}
+}
+
+fn cell(row: &PointTableRow, column: PointTableColumn, decimal_places: u32) -> WidgetHolder {
+	let Some(value) = row.value(column) else {
+		return TextLabel::new("-").widget_holder();
+	};
+
+	let (layer, point) = (row.layer, row.point);
+	NumberInput::new(Some(value))
+		.unit(" px")
+		.min_width(100)
+		.display_decimal_places(decimal_places)
+		.min(-((1_u64 << f64::MANTISSA_DIGITS) as f64))
+		.max((1_u64 << f64::MANTISSA_DIGITS) as f64)
+		.on_update(move |number_input: &NumberInput| {
+			if let Some(value) = number_input.value {
+				PointTableMessage::SetCell { layer, point, column, value }.into()
+			} else {
+				Message::NoOp
+			}
+		})
+		.widget_holder()
+}
+
+fn point_label(point: ManipulatorPointId) -> String {
+	match point {
+		ManipulatorPointId::Anchor(point) => format!("Anchor {}", point.inner()),
+		ManipulatorPointId::PrimaryHandle(segment) => format!("Start handle {}", segment.inner()),
+		ManipulatorPointId::EndHandle(segment) => format!("End handle {}", segment.inner()),
+	}
+}
+
+/// Splits a pasted line into cells by tabs (as copied from a spreadsheet), else commas or semicolons, else whitespace.
+/// Cells that aren't numbers are `None` so they leave the corresponding value unchanged.
+fn parse_pasted_line(line: &str) -> impl Iterator<Item = Option<f64>> + '_ {
+	let cells: Box<dyn Iterator<Item = &str>> = if line.contains('\t') {
+		Box::new(line.split('\t'))
+	} else if line.contains([',', ';']) {
+		Box::new(line.split([',', ';']))
+	} else {
+		Box::new(line.split_whitespace())
+	};
+
+	cells.map(parse_number)
+}
+
+#[cfg(test)]
+mod test {
+	use super::parse_pasted_line;
+
+	#[test]
+	fn pasted_lines_split_on_the_first_separator_kind_present() {
+		let parse = |line| parse_pasted_line(line).collect::<Vec<_>>();
+
+		assert_eq!(parse("10\t20.5"), vec![Some(10.), Some(20.5)]);
+		assert_eq!(parse("10 px, -3px"), vec![Some(10.), Some(-3.)]);
+		assert_eq!(parse("1;2;3"), vec![Some(1.), Some(2.), Some(3.)]);
+		assert_eq!(parse("  4   5 "), vec![Some(4.), Some(5.)]);
+		assert_eq!(parse("\t7"), vec![None, Some(7.)]);
+	}
+}
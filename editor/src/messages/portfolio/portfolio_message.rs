@@ -3,9 +3,13 @@ use super::utility_types::PanelType;
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
 use crate::messages::prelude::*;
+use crate::messages::tool::tool_messages::path_tool::PathOverlayMode;
+use graph_craft::document::NodeId;
 use graphene_core::Color;
+use graphene_core::application_io::RenderQualityHint;
 use graphene_core::raster::Image;
 use graphene_core::text::Font;
+use graphene_core::vector::ManipulatorPointId;
 
 #[impl_message(Message, Portfolio)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -17,6 +21,8 @@ pub enum PortfolioMessage {
 	Document(DocumentMessage),
 	#[child]
 	Spreadsheet(SpreadsheetMessage),
+	#[child]
+	PointTable(PointTableMessage),
 
 	// Messages
 	DocumentPassMessage {
@@ -28,6 +34,7 @@ pub enum PortfolioMessage {
 	AutoSaveDocument {
 		document_id: DocumentId,
 	},
+	CancelExport,
 	CloseActiveDocumentWithConfirmation,
 	CloseAllDocuments,
 	CloseAllDocumentsWithConfirmation,
@@ -46,8 +53,20 @@ pub enum PortfolioMessage {
 	DeleteDocument {
 		document_id: DocumentId,
 	},
+	DescribeDocumentExport {
+		file_type: FileType,
+		scale_factor: f64,
+		bounds: ExportBounds,
+		transparent_background: bool,
+		expand_strokes: bool,
+	},
 	DestroyAllDocuments,
 	EditorPreferences,
+	/// Downloads the active document's viewport and selected layers, along with the Path tool's selected points and overlay mode, as an edit session file.
+	ExportEditSession {
+		selected_points: Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>,
+		overlay_mode: PathOverlayMode,
+	},
 	FontLoaded {
 		font_family: String,
 		font_style: String,
@@ -58,6 +77,10 @@ pub enum PortfolioMessage {
 	// ImaginatePollServerStatus,
 	// ImaginateServerHostname,
 	Import,
+	/// Restores the viewport and selections of an edit session file saved with the active document, reporting the layers and points which no longer exist in it.
+	ImportEditSession {
+		session: String,
+	},
 	LoadDocumentResources {
 		document_id: DocumentId,
 	},
@@ -69,10 +92,12 @@ pub enum PortfolioMessage {
 	},
 	NextDocument,
 	OpenDocument,
+	OpenEditSession,
 	OpenDocumentFile {
 		document_name: String,
 		document_serialized_content: String,
 	},
+	ToggleCanvasProbe,
 	ToggleResetNodesToDefinitionsOnOpen,
 	OpenDocumentFileWithId {
 		document_id: DocumentId,
@@ -103,12 +128,24 @@ pub enum PortfolioMessage {
 		parent_and_insert_index: Option<(LayerNodeIdentifier, usize)>,
 	},
 	PrevDocument,
+	RequestDocumentStatistics,
+	RequestInspectRows {
+		start: usize,
+		count: usize,
+	},
+	RequestLayerPreview {
+		layer_node: NodeId,
+		max_size: f64,
+	},
 	SetActivePanel {
 		panel: PanelType,
 	},
 	SetDevicePixelRatio {
 		ratio: f64,
 	},
+	SetRenderQuality {
+		quality: RenderQualityHint,
+	},
 	SelectDocument {
 		document_id: DocumentId,
 	},
@@ -118,6 +155,7 @@ pub enum PortfolioMessage {
 		scale_factor: f64,
 		bounds: ExportBounds,
 		transparent_background: bool,
+		expand_strokes: bool,
 	},
 	SubmitActiveGraphRender,
 	SubmitGraphRender {
@@ -1,13 +1,14 @@
 use super::document::utility_types::document_metadata::LayerNodeIdentifier;
 use super::document::utility_types::network_interface::{self, InputConnector, OutputConnector};
+use super::point_table::PointTableMessageHandler;
 use super::spreadsheet::SpreadsheetMessageHandler;
-use super::utility_types::{PanelType, PersistentData};
+use super::utility_types::{EditSession, PanelType, PersistentData};
 use crate::application::generate_uuid;
-use crate::consts::DEFAULT_DOCUMENT_NAME;
+use crate::consts::{DEFAULT_DOCUMENT_NAME, FILE_SAVE_SUFFIX};
 use crate::messages::animation::TimingInformation;
 use crate::messages::debug::utility_types::MessageLoggingVerbosity;
 use crate::messages::dialog::simple_dialogs;
-use crate::messages::frontend::utility_types::FrontendDocumentDetails;
+use crate::messages::frontend::utility_types::{ExportStage, FrontendDocumentDetails};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::DocumentMessageData;
 use crate::messages::portfolio::document::node_graph::document_node_definitions::resolve_document_node_type;
@@ -15,6 +16,7 @@ use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard,
 use crate::messages::portfolio::document::utility_types::nodes::SelectedNodes;
 use crate::messages::preferences::SelectionMode;
 use crate::messages::prelude::*;
+use crate::messages::tool::tool_messages::path_tool::PathOptionsUpdate;
 use crate::messages::tool::utility_types::{HintData, HintGroup, ToolType};
 use crate::node_graph_executor::{ExportConfig, NodeGraphExecutor};
 use bezier_rs::Subpath;
@@ -49,8 +51,12 @@ pub struct PortfolioMessageHandler {
 	pub selection_mode: SelectionMode,
 	/// The spreadsheet UI allows for instance data to be previewed.
 	pub spreadsheet: SpreadsheetMessageHandler,
+	/// The Point Table panel lists the selected points of the Path tool so their coordinates can be edited numerically.
+	pub point_table: PointTableMessageHandler,
 	device_pixel_ratio: Option<f64>,
 	pub reset_node_definitions_on_open: bool,
+	/// Whether clicking the canvas probes what's under the cursor instead of using the active tool, for debugging.
+	pub canvas_probe: bool,
 }
 
 impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMessageHandler {
@@ -70,17 +76,23 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			PortfolioMessage::MenuBar(message) => {
 				self.menu_bar_message_handler.has_active_document = false;
 				self.menu_bar_message_handler.rulers_visible = false;
+				self.menu_bar_message_handler.viewport_renderer = None;
+				self.menu_bar_message_handler.deterministic_ids = false;
 				self.menu_bar_message_handler.node_graph_open = false;
 				self.menu_bar_message_handler.has_selected_nodes = false;
 				self.menu_bar_message_handler.has_selected_layers = false;
 				self.menu_bar_message_handler.has_selection_history = (false, false);
 				self.menu_bar_message_handler.spreadsheet_view_open = self.spreadsheet.spreadsheet_view_open;
+				self.menu_bar_message_handler.point_table_open = self.point_table.point_table_open;
 				self.menu_bar_message_handler.message_logging_verbosity = message_logging_verbosity;
 				self.menu_bar_message_handler.reset_node_definitions_on_open = reset_node_definitions_on_open;
+				self.menu_bar_message_handler.canvas_probe = self.canvas_probe;
 
 				if let Some(document) = self.active_document_id.and_then(|document_id| self.documents.get_mut(&document_id)) {
 					self.menu_bar_message_handler.has_active_document = true;
 					self.menu_bar_message_handler.rulers_visible = document.rulers_visible;
+					self.menu_bar_message_handler.viewport_renderer = document.viewport_renderer;
+					self.menu_bar_message_handler.deterministic_ids = document.deterministic_ids;
 					self.menu_bar_message_handler.node_graph_open = document.is_graph_overlay_open();
 					let selected_nodes = document.network_interface.selected_nodes();
 					self.menu_bar_message_handler.has_selected_nodes = selected_nodes.selected_nodes().next().is_some();
@@ -95,6 +107,10 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			}
 			PortfolioMessage::Spreadsheet(message) => {
 				self.spreadsheet.process_message(message, responses, ());
+				self.executor.set_inspect_follows_selection(self.spreadsheet.spreadsheet_view_open && self.spreadsheet.follow_selection);
+			}
+			PortfolioMessage::PointTable(message) => {
+				self.point_table.process_message(message, responses, ());
 			}
 			PortfolioMessage::Document(message) => {
 				if let Some(document_id) = self.active_document_id {
@@ -156,6 +172,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					},
 				})
 			}
+			PortfolioMessage::CancelExport => self.executor.cancel_exports(responses),
 			PortfolioMessage::CloseActiveDocumentWithConfirmation => {
 				if let Some(document_id) = self.active_document_id {
 					responses.add(PortfolioMessage::CloseDocumentWithConfirmation { document_id });
@@ -294,6 +311,26 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					responses.add(PortfolioMessage::SelectDocument { document_id });
 				}
 			}
+			PortfolioMessage::DescribeDocumentExport {
+				file_type,
+				scale_factor,
+				bounds,
+				transparent_background,
+				expand_strokes,
+			} => {
+				if let Some(document) = self.active_document() {
+					let export_config = ExportConfig {
+						file_type,
+						scale_factor,
+						bounds,
+						transparent_background,
+						expand_strokes,
+						..Default::default()
+					};
+					let description = self.executor.describe_export(document, &export_config).ok();
+					responses.add(ExportDialogMessage::UpdateDescription { description });
+				}
+			}
 			PortfolioMessage::DestroyAllDocuments => {
 				// Empty the list of internal document data
 				self.documents.clear();
@@ -319,6 +356,8 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 						timing_information,
 						inspect_node,
 						true,
+						*current_tool == ToolType::Path,
+						preferences.viewport_renderer,
 					);
 				}
 
@@ -351,7 +390,26 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 			// 	self.persistent_data.imaginate.poll_server_check();
 			// 	responses.add(PropertiesPanelMessage::Refresh);
 			// }
-			PortfolioMessage::EditorPreferences => self.executor.update_editor_preferences(preferences.editor_preferences()),
+			PortfolioMessage::EditorPreferences => {
+				self.executor.update_editor_preferences(preferences.editor_preferences());
+				self.executor.set_cost_heatmap(preferences.cost_heatmap);
+			}
+			PortfolioMessage::ExportEditSession { selected_points, overlay_mode } => {
+				let Some(document) = self.active_document() else { return };
+				let session = EditSession {
+					ptz: document.document_ptz,
+					selected_layers: document.network_interface.selected_nodes().selected_layers(document.metadata()).collect(),
+					selected_points,
+					overlay_mode,
+				};
+				match serde_json::to_string_pretty(&session) {
+					Ok(content) => responses.add(FrontendMessage::TriggerDownloadTextFile {
+						document: content,
+						name: format!("{} edit session.json", document.name.trim_end_matches(FILE_SAVE_SUFFIX)),
+					}),
+					Err(error) => log::warn!("Could not export the edit session: {error}"),
+				}
+			}
 			// PortfolioMessage::ImaginateServerHostname => {
 			// 	self.persistent_data.imaginate.set_host_name(&preferences.imaginate_server_hostname);
 			// }
@@ -359,6 +417,39 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				// This portfolio message wraps the frontend message so it can be listed as an action, which isn't possible for frontend messages
 				responses.add(FrontendMessage::TriggerImport);
 			}
+			PortfolioMessage::ImportEditSession { session } => {
+				let Some(document) = self.active_document() else { return };
+				let mut session = match serde_json::from_str::<EditSession>(&session) {
+					Ok(session) => session,
+					Err(error) => {
+						responses.add(DialogMessage::DisplayDialogError {
+							title: "Failed to import edit session".into(),
+							description: format!("The file isn't a valid edit session.\n\n{error}"),
+						});
+						return;
+					}
+				};
+				let unresolved = session.retain_resolved(document);
+
+				// The points are selected last, once the Path tool has been activated with the layers selected
+				responses.add(NavigationMessage::CanvasPTZSet { ptz: session.ptz });
+				responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Path });
+				responses.add(NodeGraphMessage::SelectedNodesSet {
+					nodes: session.selected_layers.iter().map(|layer| layer.to_node()).collect(),
+				});
+				responses.add(PathToolMessage::UpdateOptions(PathOptionsUpdate::OverlayModeType(session.overlay_mode)));
+				responses.add(PathToolMessage::SelectPoints { points: session.selected_points });
+
+				if !unresolved.is_empty() {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Edit session partly restored".into(),
+						description: format!(
+							"The document has changed since the edit session was saved, so these could not be found and were left unselected:\n\n{}",
+							unresolved.join("\n")
+						),
+					});
+				}
+			}
 			PortfolioMessage::LoadDocumentResources { document_id } => {
 				if let Some(document) = self.document_mut(document_id) {
 					document.load_layer_resources(responses);
@@ -396,6 +487,9 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				// This portfolio message wraps the frontend message so it can be listed as an action, which isn't possible for frontend messages
 				responses.add(FrontendMessage::TriggerOpenDocument);
 			}
+			PortfolioMessage::OpenEditSession => {
+				responses.add(FrontendMessage::TriggerOpenEditSession);
+			}
 			PortfolioMessage::OpenDocumentFile {
 				document_name,
 				document_serialized_content,
@@ -411,6 +505,16 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				});
 				responses.add(PortfolioMessage::SelectDocument { document_id });
 			}
+			PortfolioMessage::ToggleCanvasProbe => {
+				self.canvas_probe = !self.canvas_probe;
+				if !self.canvas_probe {
+					for document in self.documents.values_mut() {
+						document.canvas_probe = None;
+					}
+					responses.add(OverlaysMessage::Draw);
+				}
+				responses.add(MenuBarMessage::SendLayout);
+			}
 			PortfolioMessage::ToggleResetNodesToDefinitionsOnOpen => {
 				self.reset_node_definitions_on_open = !self.reset_node_definitions_on_open;
 				responses.add(MenuBarMessage::SendLayout);
@@ -939,6 +1043,12 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					return;
 				}
 
+				// Repair path edits left with non-finite coordinates, which would break the rendering and hit testing of their layers
+				let repaired = document.network_interface.sanitize_vector_modifications();
+				if repaired > 0 {
+					log::warn!("Repaired {repaired} non-finite coordinates in the path edits of document '{document_name}'");
+				}
+
 				// Ensure layers are positioned as stacks if they upstream siblings of another layer
 				document.network_interface.load_structure();
 				let all_layers = LayerNodeIdentifier::ROOT_PARENT.descendants(document.network_interface.document_metadata()).collect::<Vec<_>>();
@@ -1090,6 +1200,21 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					responses.add(PortfolioMessage::SelectDocument { document_id: prev_id });
 				}
 			}
+			PortfolioMessage::RequestDocumentStatistics => {
+				if self.active_document().is_some() {
+					self.executor.request_document_statistics();
+				}
+			}
+			PortfolioMessage::RequestInspectRows { start, count } => {
+				if self.active_document().is_some() {
+					self.executor.request_inspect_rows(start, count);
+				}
+			}
+			PortfolioMessage::RequestLayerPreview { layer_node, max_size } => {
+				if self.active_document().is_some() {
+					self.executor.request_layer_preview(layer_node, max_size);
+				}
+			}
 			PortfolioMessage::SetActivePanel { panel } => {
 				self.active_panel = panel;
 				responses.add(DocumentMessage::SetActivePanel { active_panel: self.active_panel });
@@ -1098,6 +1223,9 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				self.device_pixel_ratio = Some(ratio);
 				responses.add(OverlaysMessage::Draw);
 			}
+			PortfolioMessage::SetRenderQuality { quality } => {
+				self.executor.set_render_quality(quality);
+			}
 			PortfolioMessage::SelectDocument { document_id } => {
 				// Auto-save the document we are leaving
 				let mut node_graph_open = false;
@@ -1149,6 +1277,7 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 				scale_factor,
 				bounds,
 				transparent_background,
+				expand_strokes,
 			} => {
 				let document = self.active_document_id.and_then(|id| self.documents.get_mut(&id)).expect("Tried to render non-existent document");
 				let export_config = ExportConfig {
@@ -1157,11 +1286,16 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					scale_factor,
 					bounds,
 					transparent_background,
+					expand_strokes,
 					..Default::default()
 				};
-				let result = self.executor.submit_document_export(document, export_config);
+				let result = self.executor.submit_document_export(document, export_config, responses);
 
 				if let Err(description) = result {
+					responses.add(ExportDialogMessage::Progress {
+						stage: ExportStage::Failed,
+						fraction: ExportStage::Failed.fraction(),
+					});
 					responses.add(DialogMessage::DisplayDialogError {
 						title: "Unable to export document".to_string(),
 						description,
@@ -1181,6 +1315,8 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 					timing_information,
 					inspect_node,
 					ignore_hash,
+					*current_tool == ToolType::Path,
+					preferences.viewport_renderer,
 				);
 
 				if let Err(description) = result {
@@ -1243,6 +1379,13 @@ impl MessageHandler<PortfolioMessage, PortfolioMessageData<'_>> for PortfolioMes
 		if let Some(document) = self.active_document() {
 			common.extend(document.actions());
 
+			// Clicking the canvas probes it instead of using the active tool
+			if self.canvas_probe && !document.graph_view_overlay_open {
+				common.extend(actions!(DocumentMessageDiscriminant;
+					ProbeCanvas,
+				));
+			}
+
 			// Extend with actions that must have a selected layer
 			if document.network_interface.selected_nodes().selected_layers(document.metadata()).next().is_some() {
 				common.extend(actions!(PortfolioMessageDiscriminant;
@@ -1356,7 +1499,10 @@ impl PortfolioMessageHandler {
 				// It's a mystery why the `/text>` tag above needs to be missing its `<`, but when it exists it prints the `<` character in the text. However this works with it removed.
 				.to_string();
 			responses.add(Message::EndBuffer(graphene_std::renderer::RenderMetadata::default()));
-			responses.add(FrontendMessage::UpdateDocumentArtwork { svg: error });
+			responses.add(FrontendMessage::UpdateDocumentArtwork {
+				viewport_id: crate::node_graph_executor::MAIN_VIEWPORT_ID,
+				svg: error,
+			});
 		}
 		result
 	}
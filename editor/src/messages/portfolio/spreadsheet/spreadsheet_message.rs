@@ -1,16 +1,23 @@
 use crate::messages::prelude::*;
-use crate::node_graph_executor::InspectResult;
+use crate::node_graph_executor::{InspectResult, InspectRow};
+use graph_craft::document::NodeId;
 
 /// The spreadsheet UI allows for instance data to be previewed.
 #[impl_message(Message, PortfolioMessage, Spreadsheet)]
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum SpreadsheetMessage {
 	ToggleOpen,
+	ToggleFollowSelection,
 
 	UpdateLayout {
 		#[serde(skip)]
 		inspect_result: InspectResult,
 	},
+	UpdateRows {
+		inspect_node: NodeId,
+		start: usize,
+		rows: Vec<InspectRow>,
+	},
 
 	PushToInstancePath {
 		index: usize,
@@ -22,6 +29,9 @@ pub enum SpreadsheetMessage {
 	ViewVectorDataDomain {
 		domain: VectorDataDomain,
 	},
+	ViewRows {
+		start: usize,
+	},
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Default, Debug, serde::Serialize, serde::Deserialize)]
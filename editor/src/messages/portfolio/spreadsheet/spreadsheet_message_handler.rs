@@ -1,7 +1,9 @@
 use super::VectorDataDomain;
+use crate::consts::SPREADSHEET_PAGE_ROWS;
 use crate::messages::layout::utility_types::layout_widget::{Layout, LayoutGroup, LayoutTarget, WidgetLayout};
 use crate::messages::prelude::*;
 use crate::messages::tool::tool_messages::tool_prelude::*;
+use crate::node_graph_executor::{InspectRow, InspectSummary};
 use graph_craft::document::NodeId;
 use graphene_core::Context;
 use graphene_core::GraphicGroupTable;
@@ -17,8 +19,16 @@ use std::sync::Arc;
 pub struct SpreadsheetMessageHandler {
 	/// Sets whether or not the spreadsheet is drawn.
 	pub spreadsheet_view_open: bool,
+	/// Sets whether the inspected node follows the selected layer.
+	pub follow_selection: bool,
 	inspect_node: Option<NodeId>,
 	introspected_data: Option<Arc<dyn Any + Send + Sync>>,
+	/// Stands in for the introspected data when it's a table too large to have been sent whole.
+	summary: Option<InspectSummary>,
+	/// The first row of the page of the summarized table being viewed.
+	page_start: usize,
+	/// The rows of the page being viewed, empty until they arrive from the runtime.
+	page_rows: Vec<InspectRow>,
 	instances_path: Vec<usize>,
 	viewing_vector_data_domain: VectorDataDomain,
 }
@@ -36,12 +46,28 @@ impl MessageHandler<SpreadsheetMessage, ()> for SpreadsheetMessageHandler {
 				responses.add(MenuBarMessage::SendLayout);
 				self.update_layout(responses);
 			}
+			SpreadsheetMessage::ToggleFollowSelection => {
+				self.follow_selection = !self.follow_selection;
+				// Run the graph to inspect the selected layer
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				self.update_layout(responses);
+			}
 
 			SpreadsheetMessage::UpdateLayout { mut inspect_result } => {
 				self.inspect_node = Some(inspect_result.inspect_node);
 				self.introspected_data = inspect_result.take_data();
+				self.summary = inspect_result.summary;
+				self.show_page(responses);
 				self.update_layout(responses)
 			}
+			SpreadsheetMessage::UpdateRows { inspect_node, start, rows } => {
+				// Rows of a page which is no longer viewed, or of a node which is no longer inspected, arrived too late
+				if self.summary.is_none() || self.inspect_node != Some(inspect_node) || self.page_start != start {
+					return;
+				}
+				self.page_rows = rows;
+				self.update_layout(responses);
+			}
 
 			SpreadsheetMessage::PushToInstancePath { index } => {
 				self.instances_path.push(index);
@@ -56,6 +82,11 @@ impl MessageHandler<SpreadsheetMessage, ()> for SpreadsheetMessageHandler {
 				self.viewing_vector_data_domain = domain;
 				self.update_layout(responses);
 			}
+			SpreadsheetMessage::ViewRows { start } => {
+				self.page_start = start;
+				self.show_page(responses);
+				self.update_layout(responses);
+			}
 		}
 	}
 
@@ -65,6 +96,32 @@ impl MessageHandler<SpreadsheetMessage, ()> for SpreadsheetMessageHandler {
 }
 
 impl SpreadsheetMessageHandler {
+	/// Fills in the rows of the summarized table's current page, from the summary if it includes them or otherwise by asking the runtime for them.
+	fn show_page(&mut self, responses: &mut VecDeque<Message>) {
+		self.page_rows.clear();
+		let Some(summary) = &self.summary else {
+			self.page_start = 0;
+			return;
+		};
+
+		// Keep the page within the table, starting on a multiple of the page size
+		let last_row = summary.point_count.saturating_sub(1);
+		self.page_start = self.page_start.min(last_row) / SPREADSHEET_PAGE_ROWS * SPREADSHEET_PAGE_ROWS;
+		let page_end = (self.page_start + SPREADSHEET_PAGE_ROWS).min(summary.point_count);
+		let tail_start = summary.point_count - summary.tail.len();
+
+		if page_end <= summary.head.len() {
+			self.page_rows = summary.head[self.page_start..page_end].to_vec();
+		} else if self.page_start >= tail_start {
+			self.page_rows = summary.tail[self.page_start - tail_start..page_end - tail_start].to_vec();
+		} else {
+			responses.add(PortfolioMessage::RequestInspectRows {
+				start: self.page_start,
+				count: page_end - self.page_start,
+			});
+		}
+	}
+
 	fn update_layout(&mut self, responses: &mut VecDeque<Message>) {
 		responses.add(FrontendMessage::UpdateSpreadsheetState {
 			node: self.inspect_node,
@@ -79,12 +136,15 @@ impl SpreadsheetMessageHandler {
 			breadcrumbs: Vec::new(),
 			vector_data_domain: self.viewing_vector_data_domain,
 		};
-		let mut layout = self
-			.introspected_data
-			.as_ref()
-			.map(|instrospected_data| generate_layout(instrospected_data, &mut layout_data))
-			.unwrap_or_else(|| Some(label("No data")))
-			.unwrap_or_else(|| label("Failed to downcast data"));
+		let mut layout = match &self.summary {
+			Some(summary) => summary_layout(summary, self.page_start, &self.page_rows),
+			None => self
+				.introspected_data
+				.as_ref()
+				.map(|instrospected_data| generate_layout(instrospected_data, &mut layout_data))
+				.unwrap_or_else(|| Some(label("No data")))
+				.unwrap_or_else(|| label("Failed to downcast data")),
+		};
 
 		if layout_data.breadcrumbs.len() > 1 {
 			let breadcrumb = BreadcrumbTrailButtons::new(layout_data.breadcrumbs)
@@ -93,6 +153,16 @@ impl SpreadsheetMessageHandler {
 			layout.insert(0, LayoutGroup::Row { widgets: vec![breadcrumb] });
 		}
 
+		let follow_selection_tooltip = "Inspect the data of the selected layer, updating as the selection changes";
+		let follow_selection = vec![
+			CheckboxInput::new(self.follow_selection)
+				.on_update(|_| SpreadsheetMessage::ToggleFollowSelection.into())
+				.tooltip(follow_selection_tooltip)
+				.widget_holder(),
+			TextLabel::new("Follow Selection").tooltip(follow_selection_tooltip).widget_holder(),
+		];
+		layout.insert(0, LayoutGroup::Row { widgets: follow_selection });
+
 		responses.add(LayoutMessage::SendLayout {
 			layout: Layout::WidgetLayout(WidgetLayout { layout }),
 			layout_target: LayoutTarget::Spreadsheet,
@@ -127,6 +197,54 @@ fn generate_layout(introspected_data: &Arc<dyn std::any::Any + Send + Sync + 'st
 	}
 }
 
+/// Shows the summary of a table too large to have been sent whole, along with the page of its rows being viewed.
+fn summary_layout(summary: &InspectSummary, page_start: usize, page_rows: &[InspectRow]) -> Vec<LayoutGroup> {
+	let description = format!(
+		"Vector Data (instances={}, points={}, segments={}, regions={})",
+		summary.instance_count, summary.point_count, summary.segment_count, summary.region_count
+	);
+	let bounds = summary.position_bounds.map_or_else(|| "-".to_string(), |[min, max]| format!("{min} to {max}"));
+
+	let page_end = (page_start + SPREADSHEET_PAGE_ROWS).min(summary.point_count);
+	let navigation = vec![
+		TextButton::new("Previous")
+			.disabled(page_start == 0)
+			.on_update(move |_| {
+				SpreadsheetMessage::ViewRows {
+					start: page_start.saturating_sub(SPREADSHEET_PAGE_ROWS),
+				}
+				.into()
+			})
+			.widget_holder(),
+		TextLabel::new(format!("Rows {}–{} of {}", page_start + 1, page_end, summary.point_count)).widget_holder(),
+		TextButton::new("Next")
+			.disabled(page_end >= summary.point_count)
+			.on_update(move |_| SpreadsheetMessage::ViewRows { start: page_end }.into())
+			.widget_holder(),
+	];
+
+	let mut rows = vec![column_headings(&["", "instance", "id", "position"])];
+	rows.extend(page_rows.iter().enumerate().map(|(index, row)| {
+		vec![
+			TextLabel::new(format!("{}", page_start + index)).widget_holder(),
+			TextLabel::new(format!("{}", row.instance)).widget_holder(),
+			TextLabel::new(format!("{}", row.id)).widget_holder(),
+			TextLabel::new(format!("{}", row.position)).widget_holder(),
+		]
+	}));
+
+	vec![
+		LayoutGroup::Row {
+			widgets: vec![TextLabel::new(description).widget_holder()],
+		},
+		LayoutGroup::Row {
+			widgets: vec![TextLabel::new(format!("Position bounds: {bounds}")).widget_holder()],
+		},
+		LayoutGroup::Row { widgets: navigation },
+		LayoutGroup::Table { rows },
+	]
+}
+
 fn column_headings(value: &[&str]) -> Vec<WidgetHolder> {
 	value.iter().map(|text| TextLabel::new(*text).widget_holder()).collect()
 }
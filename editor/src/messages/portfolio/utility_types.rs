@@ -1,3 +1,8 @@
+use super::document::utility_types::document_metadata::LayerNodeIdentifier;
+use super::document::utility_types::misc::PTZ;
+use crate::messages::prelude::*;
+use crate::messages::tool::tool_messages::path_tool::PathOverlayMode;
+use graphene_core::vector::ManipulatorPointId;
 use graphene_std::text::FontCache;
 
 #[derive(Debug, Default)]
@@ -45,6 +50,7 @@ pub enum PanelType {
 	Layers,
 	Properties,
 	Spreadsheet,
+	PointTable,
 }
 
 impl From<String> for PanelType {
@@ -54,7 +60,54 @@ impl From<String> for PanelType {
 			"Layers" => PanelType::Layers,
 			"Properties" => PanelType::Properties,
 			"Spreadsheet" => PanelType::Spreadsheet,
+			"PointTable" => PanelType::PointTable,
 			_ => panic!("Unknown panel type: {}", value),
 		}
 	}
 }
+
+/// The viewport and selections in a document, saved to a file alongside it so someone opening the same document can be shown exactly which layers and points are being discussed.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EditSession {
+	pub ptz: PTZ,
+	pub selected_layers: Vec<LayerNodeIdentifier>,
+	pub selected_points: Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>,
+	pub overlay_mode: PathOverlayMode,
+}
+
+impl EditSession {
+	/// Removes the layers and points which don't exist in the document, which may have changed since the session was saved, and describes each of them.
+	pub fn retain_resolved(&mut self, document: &DocumentMessageHandler) -> Vec<String> {
+		let mut unresolved = Vec::new();
+		let metadata = document.metadata();
+
+		self.selected_layers.retain(|&layer| {
+			let exists = metadata.layer_exists(layer);
+			if !exists {
+				unresolved.push(format!("Layer {}", layer.to_node()));
+			}
+			exists
+		});
+		self.selected_points.retain_mut(|(layer, points)| {
+			let vector_data = metadata.layer_exists(*layer).then(|| document.network_interface.compute_modified_vector(*layer)).flatten();
+			let Some(vector_data) = vector_data else {
+				let description = format!("Layer {}", layer.to_node());
+				if !unresolved.contains(&description) {
+					unresolved.push(description);
+				}
+				return false;
+			};
+
+			points.retain(|point| {
+				let exists = point.get_position(&vector_data).is_some();
+				if !exists {
+					unresolved.push(format!("{point:?} in layer {}", layer.to_node()));
+				}
+				exists
+			});
+			!points.is_empty()
+		});
+
+		unresolved
+	}
+}
@@ -1,4 +1,5 @@
 use crate::messages::portfolio::document::node_graph::utility_types::GraphWireStyle;
+use crate::messages::portfolio::document::utility_types::misc::ViewportRenderer;
 use crate::messages::preferences::SelectionMode;
 use crate::messages::prelude::*;
 
@@ -11,11 +12,19 @@ pub enum PreferencesMessage {
 
 	// Per-preference messages
 	UseVello { use_vello: bool },
+	ViewportRenderer { renderer: ViewportRenderer },
 	SelectionMode { selection_mode: SelectionMode },
 	VectorMeshes { enabled: bool },
+	CostHeatmap { enabled: bool },
+	PathGestureRecording { enabled: bool },
 	ModifyLayout { zoom_with_scroll: bool },
 	GraphWireStyle { style: GraphWireStyle },
 	ViewportZoomWheelRate { rate: f64 },
+	PathDragPromotion { enabled: bool },
+	PathDragPromotionDistance { distance: f64 },
+	PathNudgeAccelerationCap { cap: f64 },
+	ConfirmDestructivePointEdits { enabled: bool },
+	DisplayDecimalPlaces { decimal_places: u32 },
 	// ImaginateRefreshFrequency { seconds: f64 },
 	// ImaginateServerHostname { hostname: String },
 }
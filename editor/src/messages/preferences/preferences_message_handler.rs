@@ -1,20 +1,36 @@
-use crate::consts::VIEWPORT_ZOOM_WHEEL_RATE;
+use crate::consts::{DISPLAY_DECIMAL_PLACES, DISPLAY_DECIMAL_PLACES_MAX, PATH_DRAG_PROMOTION_DISTANCE, PATH_NUDGE_ACCELERATION_CAP, VIEWPORT_ZOOM_WHEEL_RATE};
 use crate::messages::input_mapper::key_mapping::MappingVariant;
 use crate::messages::portfolio::document::node_graph::utility_types::GraphWireStyle;
+use crate::messages::portfolio::document::utility_types::misc::ViewportRenderer;
 use crate::messages::preferences::SelectionMode;
 use crate::messages::prelude::*;
 use graph_craft::wasm_application_io::EditorPreferences;
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+// Preferences saved before a field was added are missing it, so it falls back to its default instead of the whole load failing
+#[serde(default)]
 pub struct PreferencesMessageHandler {
 	// pub imaginate_server_hostname: String,
 	// pub imaginate_refresh_frequency: f64,
 	pub selection_mode: SelectionMode,
 	pub zoom_with_scroll: bool,
 	pub use_vello: bool,
+	/// The renderer used by documents that haven't chosen one of their own.
+	pub viewport_renderer: ViewportRenderer,
 	pub vector_meshes: bool,
+	/// Whether the viewport is overlaid with a debug heatmap of how long each layer took to evaluate.
+	pub cost_heatmap: bool,
+	/// Whether the messages processed by the Path tool are recorded, so they can be exported and replayed when reporting a bug.
+	pub path_gesture_recording: bool,
 	pub graph_wire_style: GraphWireStyle,
 	pub viewport_zoom_wheel_rate: f64,
+	pub path_drag_promotion: bool,
+	pub path_drag_promotion_distance: f64,
+	pub path_nudge_acceleration_cap: f64,
+	/// Whether deleting points or breaking paths on a layer that many other nodes depend on asks for confirmation first.
+	pub confirm_destructive_point_edits: bool,
+	/// How many decimal places coordinates and measurements are rounded to when shown, which never affects the stored values.
+	pub display_decimal_places: u32,
 }
 
 impl PreferencesMessageHandler {
@@ -42,9 +58,17 @@ impl Default for PreferencesMessageHandler {
 			selection_mode: SelectionMode::Touched,
 			zoom_with_scroll: matches!(MappingVariant::default(), MappingVariant::ZoomWithScroll),
 			use_vello: EditorPreferences::default().use_vello,
+			viewport_renderer: ViewportRenderer::default(),
 			vector_meshes: false,
+			cost_heatmap: false,
+			path_gesture_recording: false,
 			graph_wire_style: GraphWireStyle::default(),
 			viewport_zoom_wheel_rate: VIEWPORT_ZOOM_WHEEL_RATE,
+			path_drag_promotion: true,
+			path_drag_promotion_distance: PATH_DRAG_PROMOTION_DISTANCE,
+			path_nudge_acceleration_cap: PATH_NUDGE_ACCELERATION_CAP,
+			confirm_destructive_point_edits: true,
+			display_decimal_places: DISPLAY_DECIMAL_PLACES,
 		}
 	}
 }
@@ -81,9 +105,21 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 				responses.add(PortfolioMessage::UpdateVelloPreference);
 				responses.add(PortfolioMessage::EditorPreferences);
 			}
+			PreferencesMessage::ViewportRenderer { renderer } => {
+				self.viewport_renderer = renderer;
+				responses.add(PortfolioMessage::SubmitActiveGraphRender);
+			}
 			PreferencesMessage::VectorMeshes { enabled } => {
 				self.vector_meshes = enabled;
 			}
+			PreferencesMessage::CostHeatmap { enabled } => {
+				self.cost_heatmap = enabled;
+				responses.add(PortfolioMessage::EditorPreferences);
+				responses.add(PortfolioMessage::SubmitActiveGraphRender);
+			}
+			PreferencesMessage::PathGestureRecording { enabled } => {
+				self.path_gesture_recording = enabled;
+			}
 			PreferencesMessage::ModifyLayout { zoom_with_scroll } => {
 				self.zoom_with_scroll = zoom_with_scroll;
 
@@ -100,6 +136,23 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 			PreferencesMessage::ViewportZoomWheelRate { rate } => {
 				self.viewport_zoom_wheel_rate = rate;
 			}
+			PreferencesMessage::PathDragPromotion { enabled } => {
+				self.path_drag_promotion = enabled;
+			}
+			PreferencesMessage::PathDragPromotionDistance { distance } => {
+				self.path_drag_promotion_distance = distance.max(0.);
+			}
+			PreferencesMessage::PathNudgeAccelerationCap { cap } => {
+				self.path_nudge_acceleration_cap = cap.max(1.);
+			}
+			PreferencesMessage::ConfirmDestructivePointEdits { enabled } => {
+				self.confirm_destructive_point_edits = enabled;
+			}
+			PreferencesMessage::DisplayDecimalPlaces { decimal_places } => {
+				self.display_decimal_places = decimal_places.min(DISPLAY_DECIMAL_PLACES_MAX);
+				// Redraw the readouts shown on the canvas with the new precision
+				responses.add(OverlaysMessage::Draw);
+			}
 		}
 		// TODO: Reenable when Imaginate is restored (and move back up one line since the auto-formatter doesn't like it in that block)
 		// PreferencesMessage::ImaginateRefreshFrequency { seconds } => {
@@ -135,3 +188,42 @@ fn refresh_dialog(responses: &mut VecDeque<Message>) {
 		followups: vec![DialogMessage::RequestPreferencesDialog.into()],
 	});
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Preferences as they were saved before any of the Path tool and renderer preferences were added.
+	const OLD_PREFERENCES: &str = r#"{"selection_mode":"Enclosed","zoom_with_scroll":true,"use_vello":true,"vector_meshes":true,"graph_wire_style":"GridAligned","viewport_zoom_wheel_rate":0.5}"#;
+
+	#[test]
+	fn loading_old_preferences_keeps_their_values() {
+		let mut preferences = PreferencesMessageHandler::default();
+		let mut responses = VecDeque::new();
+		preferences.process_message(
+			PreferencesMessage::Load {
+				preferences: OLD_PREFERENCES.to_string(),
+			},
+			&mut responses,
+			(),
+		);
+
+		assert_eq!(preferences.selection_mode, SelectionMode::Enclosed);
+		assert!(preferences.zoom_with_scroll);
+		assert!(preferences.use_vello);
+		assert!(preferences.vector_meshes);
+		assert_eq!(preferences.graph_wire_style, GraphWireStyle::GridAligned);
+		assert_eq!(preferences.viewport_zoom_wheel_rate, 0.5);
+
+		// The preferences added since fall back to their defaults
+		let default = PreferencesMessageHandler::default();
+		assert_eq!(preferences.path_drag_promotion, default.path_drag_promotion);
+		assert_eq!(preferences.path_drag_promotion_distance, default.path_drag_promotion_distance);
+		assert_eq!(preferences.path_nudge_acceleration_cap, default.path_nudge_acceleration_cap);
+		assert_eq!(preferences.viewport_renderer, default.viewport_renderer);
+		assert_eq!(preferences.cost_heatmap, default.cost_heatmap);
+		assert_eq!(preferences.path_gesture_recording, default.path_gesture_recording);
+		assert_eq!(preferences.confirm_destructive_point_edits, default.confirm_destructive_point_edits);
+		assert_eq!(preferences.display_decimal_places, default.display_decimal_places);
+	}
+}
@@ -0,0 +1,93 @@
+use crate::messages::prelude::*;
+use crate::messages::tool::tool_messages::tool_prelude::*;
+
+/// The position of a [`ChunkedOperation`] within the work it walks through, which advances one item at a time.
+pub trait ChunkedCursor {
+	/// The fraction of the work done so far, between 0 and 1.
+	fn progress(&self) -> f64;
+}
+
+/// Splits a long-running operation across multiple animation frames so it doesn't block the message loop.
+///
+/// The work is walked by a cursor which finds each next item itself, so nothing has to be gathered up front before the first chunk.
+/// Each time the `continuation` message is received (once per animation frame), the owner calls [`Self::process_chunk`] to advance the cursor by at most `chunk_size` items.
+/// Results are accumulated into an output which is only handed back by [`Self::finish`] once the cursor has reached the end,
+/// so intermediate results can never be mistaken for the final result. The operation can be abandoned at any time with [`Self::cancel`].
+#[derive(Clone, Debug)]
+pub struct ChunkedOperation<Cursor, Output> {
+	cursor: Cursor,
+	chunk_size: usize,
+	output: Output,
+	continuation: Message,
+	complete: bool,
+}
+
+impl<Cursor: ChunkedCursor, Output> ChunkedOperation<Cursor, Output> {
+	/// Begins the operation by subscribing the `continuation` message to be sent on every animation frame until the operation is finished or cancelled.
+	pub fn start(cursor: Cursor, chunk_size: usize, output: Output, continuation: Message, responses: &mut VecDeque<Message>) -> Self {
+		responses.add(BroadcastMessage::SubscribeEvent {
+			on: BroadcastEvent::AnimationFrame,
+			send: Box::new(continuation.clone()),
+		});
+
+		Self {
+			cursor,
+			chunk_size: chunk_size.max(1),
+			output,
+			continuation,
+			complete: false,
+		}
+	}
+
+	/// Processes the next chunk by calling `step` at most `chunk_size` times, each advancing the cursor by one item and accumulating into the output.
+	/// `step` returns `false` once the cursor has no items left. Returns `true` once the whole operation has been processed.
+	pub fn process_chunk(&mut self, mut step: impl FnMut(&mut Cursor, &mut Output) -> bool) -> bool {
+		for _ in 0..self.chunk_size {
+			if self.complete {
+				break;
+			}
+			self.complete = !step(&mut self.cursor, &mut self.output);
+		}
+
+		self.complete
+	}
+
+	pub fn is_complete(&self) -> bool {
+		self.complete
+	}
+
+	/// The fraction of the work processed so far, between 0 and 1.
+	pub fn progress(&self) -> f64 {
+		if self.complete { 1. } else { self.cursor.progress().clamp(0., 1.) }
+	}
+
+	/// Ends the operation, returning the accumulated output only if every item has been processed.
+	pub fn finish(self, responses: &mut VecDeque<Message>) -> Option<Output> {
+		let complete = self.is_complete();
+		Self::unsubscribe(self.continuation, responses);
+
+		complete.then_some(self.output)
+	}
+
+	/// Abandons the operation, discarding any accumulated output.
+	pub fn cancel(self, responses: &mut VecDeque<Message>) {
+		Self::unsubscribe(self.continuation, responses);
+	}
+
+	/// Input hints showing the progress of the operation and how to cancel it.
+	pub fn progress_hints(&self, label: &str) -> HintData {
+		let percent = (self.progress() * 100.).floor();
+
+		HintData(vec![
+			HintGroup(vec![HintInfo::label(format!("{label} ({percent}%)"))]),
+			HintGroup(vec![HintInfo::keys([Key::Escape], "Cancel")]),
+		])
+	}
+
+	fn unsubscribe(continuation: Message, responses: &mut VecDeque<Message>) {
+		responses.add(BroadcastMessage::UnsubscribeEvent {
+			on: BroadcastEvent::AnimationFrame,
+			message: Box::new(continuation),
+		});
+	}
+}
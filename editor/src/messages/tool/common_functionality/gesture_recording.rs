@@ -0,0 +1,139 @@
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, ModifierKeys};
+use crate::messages::input_mapper::utility_types::input_mouse::MouseKeys;
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::misc::PTZ;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::shape_editor::ShapeState;
+use glam::DVec2;
+use graphene_core::vector::ManipulatorPointId;
+use std::time::Duration;
+
+/// A message processed by the Path tool, along with the input state it was processed with.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RecordedGesture {
+	/// Milliseconds since the recording began.
+	pub time: u64,
+	pub mouse_position: DVec2,
+	pub mouse_keys: MouseKeys,
+	pub modifier_keys: ModifierKeys,
+	pub message: PathToolMessage,
+}
+
+/// The messages processed by the Path tool while gesture recording is enabled, along with the viewport and selection they began from so they can be replayed deterministically.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GestureRecording {
+	/// The editor time, in milliseconds, when the recording began.
+	pub start_time: u64,
+	pub ptz: PTZ,
+	pub selected_layers: Vec<LayerNodeIdentifier>,
+	pub selected_points: Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>,
+	pub gestures: Vec<RecordedGesture>,
+}
+
+impl GestureRecording {
+	/// Begins a recording from the current viewport and selection.
+	pub fn start(document: &DocumentMessageHandler, shape_editor: &ShapeState, time: u64) -> Self {
+		let selected_layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect();
+		let selected_points = shape_editor.selected_shape_state.iter().map(|(&layer, state)| (layer, state.selected().collect())).collect();
+
+		Self {
+			start_time: time,
+			ptz: document.document_ptz,
+			selected_layers,
+			selected_points,
+			gestures: Vec::new(),
+		}
+	}
+
+	/// Appends a message along with the current input state, decoding the modifier keys from the keyboard state.
+	pub fn record(&mut self, message: PathToolMessage, input: &InputPreprocessorMessageHandler) {
+		let modifier_keys = [
+			(Key::Shift, ModifierKeys::SHIFT),
+			(Key::Alt, ModifierKeys::ALT),
+			(Key::Control, ModifierKeys::CONTROL),
+			(Key::Meta, ModifierKeys::META_OR_COMMAND),
+			(Key::Command, ModifierKeys::META_OR_COMMAND),
+		]
+		.into_iter()
+		.filter(|&(key, _)| input.keyboard.get(key as usize))
+		.fold(ModifierKeys::empty(), |modifier_keys, (_, modifier)| modifier_keys | modifier);
+
+		self.gestures.push(RecordedGesture {
+			time: input.time.saturating_sub(self.start_time),
+			mouse_position: input.mouse.position,
+			mouse_keys: input.mouse.mouse_keys,
+			modifier_keys,
+			message,
+		});
+	}
+
+	/// Restores the viewport and the selection the recording began from.
+	pub fn restore_initial_state(&self, shape_editor: &mut ShapeState, responses: &mut VecDeque<Message>) {
+		responses.add(NavigationMessage::CanvasPTZSet { ptz: self.ptz });
+		responses.add(NodeGraphMessage::SelectedNodesSet {
+			nodes: self.selected_layers.iter().map(|layer| layer.to_node()).collect(),
+		});
+
+		shape_editor.set_selected_layers(self.selected_points.iter().map(|&(layer, _)| layer).collect());
+		for (layer, points) in &self.selected_points {
+			let Some(state) = shape_editor.selected_shape_state.get_mut(layer) else { continue };
+			state.clear_points_force();
+			for &point in points {
+				state.select_point(point);
+			}
+		}
+	}
+
+	/// Sends a recorded message after restoring the input state it was recorded with.
+	pub fn replay_gesture(&self, gesture: RecordedGesture, responses: &mut VecDeque<Message>) {
+		responses.add(InputPreprocessorMessage::RestoreState {
+			mouse_position: gesture.mouse_position,
+			mouse_keys: gesture.mouse_keys,
+			modifier_keys: gesture.modifier_keys,
+			time: self.start_time + gesture.time,
+		});
+		responses.add(gesture.message);
+	}
+
+	/// Sends every recorded message at once, without waiting between them.
+	pub fn replay_all(&self, responses: &mut VecDeque<Message>) {
+		for gesture in &self.gestures {
+			self.replay_gesture(gesture.clone(), responses);
+		}
+	}
+}
+
+/// A recording being replayed across animation frames, at a multiple of the speed it was recorded at.
+pub struct GestureReplay {
+	recording: GestureRecording,
+	next_gesture: usize,
+	speed: f64,
+	/// Milliseconds of the recording replayed so far. This is measured from the frame durations, since the editor time is restored to the recorded time of each gesture.
+	elapsed: f64,
+}
+
+impl GestureReplay {
+	pub fn new(recording: GestureRecording, speed: f64) -> Self {
+		Self {
+			recording,
+			next_gesture: 0,
+			speed,
+			elapsed: 0.,
+		}
+	}
+
+	/// Replays the gestures which have become due over the last frame, returning `true` once every gesture has been replayed.
+	pub fn advance(&mut self, frame_duration: Duration, responses: &mut VecDeque<Message>) -> bool {
+		self.elapsed += frame_duration.as_secs_f64() * 1000. * self.speed;
+
+		while let Some(gesture) = self.recording.gestures.get(self.next_gesture) {
+			if gesture.time as f64 > self.elapsed {
+				break;
+			}
+			self.recording.replay_gesture(gesture.clone(), responses);
+			self.next_gesture += 1;
+		}
+
+		self.next_gesture >= self.recording.gestures.len()
+	}
+}
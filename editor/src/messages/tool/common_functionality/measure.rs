@@ -1,6 +1,8 @@
 use crate::consts::COLOR_OVERLAY_BLUE;
+use crate::messages::layout::utility_types::number_format::format_number;
 use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, Pivot};
 use crate::messages::tool::tool_messages::tool_prelude::*;
+use bezier_rs::Bezier;
 use graphene_std::renderer::Rect;
 
 /// Draws a dashed line between two points transformed by the given affine transformation.
@@ -12,18 +14,22 @@ fn draw_dashed_line(line_start: DVec2, line_end: DVec2, transform: DAffine2, ove
 }
 
 /// Draws a solid line with a length annotation between two points transformed by the given affine transformations.
-fn draw_line_with_length(line_start: DVec2, line_end: DVec2, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext, label_alignment: LabelAlignment) {
+fn draw_line_with_length(
+	line_start: DVec2,
+	line_end: DVec2,
+	transform: DAffine2,
+	document_to_viewport: DAffine2,
+	decimal_places: u32,
+	overlay_context: &mut OverlayContext,
+	label_alignment: LabelAlignment,
+) {
 	let transform_to_document = document_to_viewport.inverse() * transform;
 	let min_viewport = transform.transform_point2(line_start);
 	let max_viewport = transform.transform_point2(line_end);
 
 	overlay_context.line(min_viewport, max_viewport, None, None);
 
-	// Remove trailing zeros from the formatted string
-	let length = format!("{:.2}", transform_to_document.transform_vector2(line_end - line_start).length())
-		.trim_end_matches('0')
-		.trim_end_matches('.')
-		.to_string();
+	let length = format_number(transform_to_document.transform_vector2(line_end - line_start).length(), decimal_places);
 
 	const TOLERANCE: f64 = 0.01;
 	if transform_to_document.transform_vector2(line_end - line_start).length() >= TOLERANCE {
@@ -72,7 +78,7 @@ fn does_overlap_x(selected_bounds: Rect, hovered_bounds: Rect) -> bool {
 }
 
 /// Draws measurements when both X and Y axes are involved in the overlap between selected and hovered bounds.
-fn draw_zero_axis_crossings(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+fn draw_zero_axis_crossings(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, decimal_places: u32, overlay_context: &mut OverlayContext) {
 	let (selected_min, selected_max) = (selected_bounds.min(), selected_bounds.max());
 	let (hovered_min, hovered_max) = (hovered_bounds.min(), hovered_bounds.max());
 
@@ -88,7 +94,7 @@ fn draw_zero_axis_crossings(selected_bounds: Rect, hovered_bounds: Rect, transfo
 	let line_start = DVec2::new(selected_x, selected_y);
 	let line_end = DVec2::new(hovered_x, selected_y);
 	let label_alignment = LabelAlignment::new(false, false, !selected_on_bottom);
-	draw_line_with_length(line_start, line_end, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(line_start, line_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	// Draw horizontal dashed line
 	let line_start = DVec2::new(selected_x, hovered_y);
@@ -99,7 +105,7 @@ fn draw_zero_axis_crossings(selected_bounds: Rect, hovered_bounds: Rect, transfo
 	let line_start = DVec2::new(selected_x, selected_y);
 	let line_end = DVec2::new(selected_x, hovered_y);
 	let label_alignment = LabelAlignment::new(true, !selected_on_right, false);
-	draw_line_with_length(line_start, line_end, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(line_start, line_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	// Draw vertical dashed line
 	let line_start = DVec2::new(hovered_x, selected_y);
@@ -108,7 +114,7 @@ fn draw_zero_axis_crossings(selected_bounds: Rect, hovered_bounds: Rect, transfo
 }
 
 /// Draws measurements when only one axis is involved in the overlap between selected and hovered bounds.
-fn draw_single_axis_zero_crossings(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+fn draw_single_axis_zero_crossings(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, decimal_places: u32, overlay_context: &mut OverlayContext) {
 	let (selected_min, selected_max) = (selected_bounds.min(), selected_bounds.max());
 	let (hovered_min, hovered_max) = (hovered_bounds.min(), hovered_bounds.max());
 
@@ -127,13 +133,13 @@ fn draw_single_axis_zero_crossings(selected_bounds: Rect, hovered_bounds: Rect,
 		let line_start = DVec2::new(f64::min(hovered_max.x, selected_max.x), selected_facing_edge);
 		let line_end = DVec2::new(f64::max(hovered_min.x, selected_min.x), selected_facing_edge);
 		let label_alignment = LabelAlignment::new(false, false, selected_on_bottom);
-		draw_line_with_length(line_start, line_end, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(line_start, line_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		// Draw vertical solid line with length
 		let line_start = DVec2::new(vertical_line_start_x, selected_facing_edge);
 		let line_end = DVec2::new(vertical_line_start_x, hovered_facing_edge);
 		let label_alignment = LabelAlignment::new(true, !selected_on_right, false);
-		draw_line_with_length(line_start, line_end, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(line_start, line_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		// Draw vertical dashed line
 		let dashed_line_start = DVec2::new(dashed_vertical_line_start_x, selected_facing_edge);
@@ -149,13 +155,13 @@ fn draw_single_axis_zero_crossings(selected_bounds: Rect, hovered_bounds: Rect,
 		let line_start = DVec2::new(selected_facing_edge, f64::min(hovered_max.y, selected_max.y));
 		let line_end = DVec2::new(selected_facing_edge, f64::max(hovered_min.y, selected_min.y));
 		let label_alignment = LabelAlignment::new(true, selected_on_right, false);
-		draw_line_with_length(line_start, line_end, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(line_start, line_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		// Draw horizontal solid line with length
 		let line_start = DVec2::new(selected_facing_edge, horizontal_line_start_y);
 		let line_end = DVec2::new(hovered_facing_edge, horizontal_line_start_y);
 		let label_alignment = LabelAlignment::new(false, false, !selected_on_bottom);
-		draw_line_with_length(line_start, line_end, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(line_start, line_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		// Draw horizontal dashed line
 		let dashed_line_start = DVec2::new(selected_facing_edge, dashed_horizontal_line_start_y);
@@ -165,7 +171,7 @@ fn draw_single_axis_zero_crossings(selected_bounds: Rect, hovered_bounds: Rect,
 }
 
 /// Draws measurements when only one axis is involved and there is one crossing between selected and hovered bounds.
-fn draw_single_axis_one_crossings(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+fn draw_single_axis_one_crossings(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, decimal_places: u32, overlay_context: &mut OverlayContext) {
 	let (selected_min, selected_max) = (selected_bounds.min(), selected_bounds.max());
 	let (hovered_min, hovered_max) = (hovered_bounds.min(), hovered_bounds.max());
 
@@ -188,7 +194,7 @@ fn draw_single_axis_one_crossings(selected_bounds: Rect, hovered_bounds: Rect, t
 		let line_start = DVec2::new(vertical_line_start, selected_facing_edge);
 		let line_end = DVec2::new(vertical_line_start, hovered_facing_edge);
 		let label_alignment = LabelAlignment::new(true, true, false);
-		draw_line_with_length(line_start, line_end, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(line_start, line_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 	} else if overlap_x {
 		let selected_facing_edge = if hovered_max.x < selected_min.x { selected_min.x } else { selected_max.x };
 		let hovered_facing_edge = if hovered_max.x < selected_min.x { hovered_max.x } else { hovered_min.x };
@@ -202,12 +208,12 @@ fn draw_single_axis_one_crossings(selected_bounds: Rect, hovered_bounds: Rect, t
 		let line_start = DVec2::new(selected_facing_edge, horizontal_line_start_y);
 		let line_end = DVec2::new(hovered_facing_edge, horizontal_line_start_y);
 		let label_alignment = LabelAlignment::new(false, false, true);
-		draw_line_with_length(line_start, line_end, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(line_start, line_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 	}
 }
 
 /// Draws measurements for cases where lines cross on both X and Y axes, handling diagonal intersections.
-fn draw_two_axis_one_one_crossing(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+fn draw_two_axis_one_one_crossing(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, decimal_places: u32, overlay_context: &mut OverlayContext) {
 	let (selected_min, selected_max) = (selected_bounds.min(), selected_bounds.max());
 	let (hovered_min, hovered_max) = (hovered_bounds.min(), hovered_bounds.max());
 
@@ -228,23 +234,23 @@ fn draw_two_axis_one_one_crossing(selected_bounds: Rect, hovered_bounds: Rect, t
 	let top_x_start = DVec2::new(f64::min(selected_max.x, hovered_max.x), top_y_bound);
 	let top_x_end = DVec2::new(f64::max(selected_max.x, hovered_max.x), top_y_bound);
 	let label_alignment = LabelAlignment::new(false, false, true);
-	draw_line_with_length(top_x_start, top_x_end, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(top_x_start, top_x_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	let bottom_x_start = DVec2::new(f64::min(selected_min.x, hovered_min.x), bottom_y_bound);
 	let bottom_x_end = DVec2::new(f64::max(selected_min.x, hovered_min.x), bottom_y_bound);
 	let label_alignment = LabelAlignment::new(false, false, false);
-	draw_line_with_length(bottom_x_start, bottom_x_end, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(bottom_x_start, bottom_x_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	// Draw vertical solid lines with length
 	let top_y_start = DVec2::new(top_x_bound, f64::min(selected_min.y, hovered_min.y));
 	let top_y_end = DVec2::new(top_x_bound, f64::max(selected_min.y, hovered_min.y));
 	let label_alignment = LabelAlignment::new(true, false, false);
-	draw_line_with_length(top_y_start, top_y_end, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(top_y_start, top_y_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	let bottom_y_start = DVec2::new(bottom_x_bound, f64::min(selected_max.y, hovered_max.y));
 	let bottom_y_end = DVec2::new(bottom_x_bound, f64::max(selected_max.y, hovered_max.y));
 	let label_alignment = LabelAlignment::new(true, true, false);
-	draw_line_with_length(bottom_y_start, bottom_y_end, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(bottom_y_start, bottom_y_end, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 }
 
 /// Draws measurements for partial overlaps with two vertical or horizontal edge intersections.
@@ -253,6 +259,7 @@ fn draw_two_axis_one_one_two_zero_crossing(
 	hovered_bounds: Rect,
 	transform: DAffine2,
 	document_to_viewport: DAffine2,
+	decimal_places: u32,
 	overlay_context: &mut OverlayContext,
 	two_vertical_edge_intersect: bool,
 ) {
@@ -271,12 +278,12 @@ fn draw_two_axis_one_one_two_zero_crossing(
 		let y_start_left = DVec2::new(hovered_min.x, f64::min(selected_bound_edge, hovered_bound_edge));
 		let y_end_left = DVec2::new(hovered_min.x, f64::max(selected_bound_edge, hovered_bound_edge));
 		let label_alignment = LabelAlignment::new(true, true, false);
-		draw_line_with_length(y_start_left, y_end_left, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(y_start_left, y_end_left, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		let y_start_right = DVec2::new(hovered_max.x, f64::min(selected_bound_edge, hovered_bound_edge));
 		let y_end_right = DVec2::new(hovered_max.x, f64::max(selected_bound_edge, hovered_bound_edge));
 		let label_alignment = LabelAlignment::new(true, false, false);
-		draw_line_with_length(y_start_right, y_end_right, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(y_start_right, y_end_right, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		// Draw horizontal solid lines with length
 		let horizontal_line_y_bound = if selected_bounds.center().y >= hovered_bounds.center().y {
@@ -288,12 +295,12 @@ fn draw_two_axis_one_one_two_zero_crossing(
 		let x_start_left = DVec2::new(hovered_min.x, horizontal_line_y_bound);
 		let x_end_left = DVec2::new(selected_min.x, horizontal_line_y_bound);
 		let label_alignment = LabelAlignment::new(false, false, false);
-		draw_line_with_length(x_start_left, x_end_left, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(x_start_left, x_end_left, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		let x_start_right = DVec2::new(hovered_max.x, horizontal_line_y_bound);
 		let x_end_right = DVec2::new(selected_max.x, horizontal_line_y_bound);
 		let label_alignment = LabelAlignment::new(false, false, false);
-		draw_line_with_length(x_start_right, x_end_right, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(x_start_right, x_end_right, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 	} else {
 		let selected_bound_edge = if selected_bounds.center().x >= hovered_bounds.center().x {
 			selected_max.x
@@ -313,12 +320,12 @@ fn draw_two_axis_one_one_two_zero_crossing(
 		let y_start_up = DVec2::new(vertical_line_x, selected_min.y);
 		let y_end_up = DVec2::new(vertical_line_x, hovered_min.y);
 		let label_alignment = LabelAlignment::new(true, false, false);
-		draw_line_with_length(y_start_up, y_end_up, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(y_start_up, y_end_up, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		let y_start_down = DVec2::new(vertical_line_x, selected_max.y);
 		let y_end_down = DVec2::new(vertical_line_x, hovered_max.y);
 		let label_alignment = LabelAlignment::new(true, false, false);
-		draw_line_with_length(y_start_down, y_end_down, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(y_start_down, y_end_down, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		// Draw horizontal solid lines with length
 		let horizontal_line_inner_x = if selected_bounds.center().x >= hovered_bounds.center().x {
@@ -329,17 +336,17 @@ fn draw_two_axis_one_one_two_zero_crossing(
 		let x_start_up = DVec2::new(vertical_line_x, f64::min(selected_min.y, hovered_min.y));
 		let x_end_up = DVec2::new(horizontal_line_inner_x, f64::min(selected_min.y, hovered_min.y));
 		let label_alignment = LabelAlignment::new(false, false, true);
-		draw_line_with_length(x_start_up, x_end_up, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(x_start_up, x_end_up, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 		let x_start_down = DVec2::new(vertical_line_x, f64::max(selected_max.y, hovered_max.y));
 		let x_end_down = DVec2::new(horizontal_line_inner_x, f64::max(selected_max.y, hovered_max.y));
 		let label_alignment = LabelAlignment::new(false, false, false);
-		draw_line_with_length(x_start_down, x_end_down, transform, document_to_viewport, overlay_context, label_alignment);
+		draw_line_with_length(x_start_down, x_end_down, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 	}
 }
 
 /// Draws measurements for cases with two vertical and two horizontal zero crossings.
-fn draw_two_axis_two_zero_zero_two(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+fn draw_two_axis_two_zero_zero_two(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, decimal_places: u32, overlay_context: &mut OverlayContext) {
 	let (selected_min, selected_max) = (selected_bounds.min(), selected_bounds.max());
 	let (hovered_min, hovered_max) = (hovered_bounds.min(), hovered_bounds.max());
 
@@ -347,49 +354,81 @@ fn draw_two_axis_two_zero_zero_two(selected_bounds: Rect, hovered_bounds: Rect,
 	let y_start_left_top = DVec2::new(f64::min(hovered_min.x, selected_min.x), f64::min(hovered_min.y, selected_min.y));
 	let y_end_left_top = DVec2::new(f64::min(hovered_min.x, selected_min.x), f64::max(hovered_min.y, selected_min.y));
 	let label_alignment = LabelAlignment::new(true, true, false);
-	draw_line_with_length(y_start_left_top, y_end_left_top, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(y_start_left_top, y_end_left_top, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 	let label_alignment = LabelAlignment::new(true, true, false);
-	draw_line_with_length(y_start_left_top, y_end_left_top, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(y_start_left_top, y_end_left_top, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	let y_start_left_bottom = DVec2::new(f64::min(hovered_min.x, selected_min.x), f64::min(hovered_max.y, selected_max.y));
 	let y_end_left_bottom = DVec2::new(f64::min(hovered_min.x, selected_min.x), f64::max(hovered_max.y, selected_max.y));
 	let label_alignment = LabelAlignment::new(true, true, false);
-	draw_line_with_length(y_start_left_bottom, y_end_left_bottom, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(
+		y_start_left_bottom,
+		y_end_left_bottom,
+		transform,
+		document_to_viewport,
+		decimal_places,
+		overlay_context,
+		label_alignment,
+	);
 
 	let y_start_right_top = DVec2::new(f64::max(hovered_max.x, selected_max.x), f64::min(hovered_min.y, selected_min.y));
 	let y_end_right_top = DVec2::new(f64::max(hovered_max.x, selected_max.x), f64::max(hovered_min.y, selected_min.y));
 	let label_alignment = LabelAlignment::new(true, false, false);
-	draw_line_with_length(y_start_right_top, y_end_right_top, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(y_start_right_top, y_end_right_top, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	let y_start_right_bottom = DVec2::new(f64::max(hovered_max.x, selected_max.x), f64::min(hovered_max.y, selected_max.y));
 	let y_end_right_bottom = DVec2::new(f64::max(hovered_max.x, selected_max.x), f64::max(hovered_max.y, selected_max.y));
 	let label_alignment = LabelAlignment::new(true, false, false);
-	draw_line_with_length(y_start_right_bottom, y_end_right_bottom, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(
+		y_start_right_bottom,
+		y_end_right_bottom,
+		transform,
+		document_to_viewport,
+		decimal_places,
+		overlay_context,
+		label_alignment,
+	);
 
 	// Draw horizontal solid lines with length
 	let x_start_left_top = DVec2::new(f64::min(hovered_min.x, selected_min.x), f64::min(hovered_min.y, selected_min.y));
 	let x_end_left_top = DVec2::new(f64::max(hovered_min.x, selected_min.x), f64::min(hovered_min.y, selected_min.y));
 	let label_alignment = LabelAlignment::new(false, false, true);
-	draw_line_with_length(x_start_left_top, x_end_left_top, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(x_start_left_top, x_end_left_top, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	let x_start_right_top = DVec2::new(f64::min(hovered_max.x, selected_max.x), f64::min(hovered_min.y, selected_min.y));
 	let x_end_right_top = DVec2::new(f64::max(hovered_max.x, selected_max.x), f64::min(hovered_min.y, selected_min.y));
 	let label_alignment = LabelAlignment::new(false, false, true);
-	draw_line_with_length(x_start_right_top, x_end_right_top, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(x_start_right_top, x_end_right_top, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	let x_start_left_bottom = DVec2::new(f64::min(hovered_min.x, selected_min.x), f64::max(hovered_max.y, selected_max.y));
 	let x_end_left_bottom = DVec2::new(f64::max(hovered_min.x, selected_min.x), f64::max(hovered_max.y, selected_max.y));
 	let label_alignment = LabelAlignment::new(false, false, false);
-	draw_line_with_length(x_start_left_bottom, x_end_left_bottom, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(
+		x_start_left_bottom,
+		x_end_left_bottom,
+		transform,
+		document_to_viewport,
+		decimal_places,
+		overlay_context,
+		label_alignment,
+	);
 
 	let x_start_right_bottom = DVec2::new(f64::min(hovered_max.x, selected_max.x), f64::max(hovered_max.y, selected_max.y));
 	let x_end_right_bottom = DVec2::new(f64::max(hovered_max.x, selected_max.x), f64::max(hovered_max.y, selected_max.y));
 	let label_alignment = LabelAlignment::new(false, false, false);
-	draw_line_with_length(x_start_right_bottom, x_end_right_bottom, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(
+		x_start_right_bottom,
+		x_end_right_bottom,
+		transform,
+		document_to_viewport,
+		decimal_places,
+		overlay_context,
+		label_alignment,
+	);
 }
 
 /// Draws measurements where selected and hovered bounds have two vertical edges crossing each other.
-fn draw_two_axis_two_zero_two_zero(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+fn draw_two_axis_two_zero_two_zero(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, decimal_places: u32, overlay_context: &mut OverlayContext) {
 	let (selected_min, selected_max) = (selected_bounds.min(), selected_bounds.max());
 	let (hovered_min, hovered_max) = (hovered_bounds.min(), hovered_bounds.max());
 
@@ -397,27 +436,27 @@ fn draw_two_axis_two_zero_two_zero(selected_bounds: Rect, hovered_bounds: Rect,
 	let x_start_left = DVec2::new(f64::max(hovered_min.x, selected_min.x), selected_bounds.center().y);
 	let x_end_left = DVec2::new(f64::min(hovered_min.x, selected_min.x), selected_bounds.center().y);
 	let label_alignment = LabelAlignment::new(false, false, true);
-	draw_line_with_length(x_start_left, x_end_left, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(x_start_left, x_end_left, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	let x_start_right = DVec2::new(f64::min(hovered_max.x, selected_max.x), selected_bounds.center().y);
 	let x_end_right = DVec2::new(f64::max(hovered_max.x, selected_max.x), selected_bounds.center().y);
 	let label_alignment = LabelAlignment::new(false, false, true);
-	draw_line_with_length(x_start_right, x_end_right, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(x_start_right, x_end_right, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	// Draw vertical solid lines with length
 	let y_start_top = DVec2::new(selected_bounds.center().x, f64::max(hovered_min.y, selected_min.y));
 	let y_end_top = DVec2::new(selected_bounds.center().x, f64::min(hovered_min.y, selected_min.y));
 	let label_alignment = LabelAlignment::new(true, false, false);
-	draw_line_with_length(y_start_top, y_end_top, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(y_start_top, y_end_top, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 
 	let y_start_bottom = DVec2::new(selected_bounds.center().x, f64::min(hovered_max.y, selected_max.y));
 	let y_end_bottom = DVec2::new(selected_bounds.center().x, f64::max(hovered_max.y, selected_max.y));
 	let label_alignment = LabelAlignment::new(true, false, false);
-	draw_line_with_length(y_start_bottom, y_end_bottom, transform, document_to_viewport, overlay_context, label_alignment);
+	draw_line_with_length(y_start_bottom, y_end_bottom, transform, document_to_viewport, decimal_places, overlay_context, label_alignment);
 }
 
 /// Handles overlapping scenarios involving two axes between selected and hovered bounds.
-fn handle_two_axis_overlap(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+fn handle_two_axis_overlap(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, decimal_places: u32, overlay_context: &mut OverlayContext) {
 	let (selected_min, selected_max) = (selected_bounds.min(), selected_bounds.max());
 	let (hovered_min, hovered_max) = (hovered_bounds.min(), hovered_bounds.max());
 
@@ -431,19 +470,19 @@ fn handle_two_axis_overlap(selected_bounds: Rect, hovered_bounds: Rect, transfor
 
 	// Determine the overlap case based on edge crossings
 	match ((selected_x_crosses, hovered_x_crosses), (selected_y_crosses, hovered_y_crosses)) {
-		((1, 1), (1, 1)) => draw_two_axis_one_one_crossing(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context),
-		((1, 1), (2, 0)) => draw_two_axis_one_one_two_zero_crossing(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context, true),
-		((1, 1), (0, 2)) => draw_two_axis_one_one_two_zero_crossing(hovered_bounds, selected_bounds, transform, document_to_viewport, overlay_context, true),
-		((2, 0), (1, 1)) => draw_two_axis_one_one_two_zero_crossing(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context, false),
-		((0, 2), (1, 1)) => draw_two_axis_one_one_two_zero_crossing(hovered_bounds, selected_bounds, transform, document_to_viewport, overlay_context, false),
-		((2, 0), (0, 2)) | ((0, 2), (2, 0)) => draw_two_axis_two_zero_zero_two(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context),
-		((2, 0), (2, 0)) | ((0, 2), (0, 2)) => draw_two_axis_two_zero_two_zero(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context),
+		((1, 1), (1, 1)) => draw_two_axis_one_one_crossing(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context),
+		((1, 1), (2, 0)) => draw_two_axis_one_one_two_zero_crossing(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context, true),
+		((1, 1), (0, 2)) => draw_two_axis_one_one_two_zero_crossing(hovered_bounds, selected_bounds, transform, document_to_viewport, decimal_places, overlay_context, true),
+		((2, 0), (1, 1)) => draw_two_axis_one_one_two_zero_crossing(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context, false),
+		((0, 2), (1, 1)) => draw_two_axis_one_one_two_zero_crossing(hovered_bounds, selected_bounds, transform, document_to_viewport, decimal_places, overlay_context, false),
+		((2, 0), (0, 2)) | ((0, 2), (2, 0)) => draw_two_axis_two_zero_zero_two(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context),
+		((2, 0), (2, 0)) | ((0, 2), (0, 2)) => draw_two_axis_two_zero_two_zero(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context),
 		_ => (),
 	}
 }
 
 /// Overlays measurement lines between selected and hovered bounds based on their spatial relationships.
-pub fn overlay(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, overlay_context: &mut OverlayContext) {
+pub fn overlay(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2, document_to_viewport: DAffine2, decimal_places: u32, overlay_context: &mut OverlayContext) {
 	draw_dashed_rect_outline(selected_bounds, transform, overlay_context);
 	draw_dashed_rect_outline(hovered_bounds, transform, overlay_context);
 	let (selected_min, selected_max) = (selected_bounds.min(), selected_bounds.max());
@@ -471,10 +510,10 @@ pub fn overlay(selected_bounds: Rect, hovered_bounds: Rect, transform: DAffine2,
 
 	// Handle each overlap case
 	match (overlap_axes, centerline_crosses) {
-		(0, _) => draw_zero_axis_crossings(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context),
-		(1, 0) => draw_single_axis_zero_crossings(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context),
-		(1, 1) | (1, 2) => draw_single_axis_one_crossings(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context),
-		(2, _) => handle_two_axis_overlap(selected_bounds, hovered_bounds, transform, document_to_viewport, overlay_context),
+		(0, _) => draw_zero_axis_crossings(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context),
+		(1, 0) => draw_single_axis_zero_crossings(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context),
+		(1, 1) | (1, 2) => draw_single_axis_one_crossings(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context),
+		(2, _) => handle_two_axis_overlap(selected_bounds, hovered_bounds, transform, document_to_viewport, decimal_places, overlay_context),
 		_ => (), // Fallback case, should not typically happen
 	}
 }
@@ -494,3 +533,54 @@ impl LabelAlignment {
 		}
 	}
 }
+
+/// A distance along a path, measured from its start either as a percentage of its total length or as an absolute length.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum DistanceAlongPath {
+	Percentage(f64),
+	Length(f64),
+}
+
+/// The arc lengths of a sequence of segments laid end to end, used to find the place a given distance along them falls.
+#[derive(Clone, Debug, Default)]
+pub struct PathArcLength {
+	beziers: Vec<Bezier>,
+	lengths: Vec<f64>,
+}
+
+impl PathArcLength {
+	pub fn new(beziers: Vec<Bezier>) -> Self {
+		let lengths = beziers.iter().map(|bezier| bezier.length(None)).collect();
+		Self { beziers, lengths }
+	}
+
+	/// The length of all the segments together.
+	pub fn total(&self) -> f64 {
+		self.lengths.iter().sum()
+	}
+
+	/// The index of the segment which the distance along the path falls on, together with the parametric `t` value of that place on the segment.
+	/// Distances outside the path are clamped to its ends, and `None` is returned if there are no segments.
+	pub fn locate(&self, distance: DistanceAlongPath) -> Option<(usize, f64)> {
+		// The same error bound the bezier library uses for its euclidean `t` values
+		const EUCLIDEAN_ERROR_BOUND: f64 = 0.001;
+
+		let total = self.total();
+		let distance = match distance {
+			DistanceAlongPath::Percentage(percentage) => total * percentage / 100.,
+			DistanceAlongPath::Length(length) => length,
+		};
+		let mut remaining = distance.clamp(0., total);
+
+		for (index, (bezier, &length)) in self.beziers.iter().zip(&self.lengths).enumerate() {
+			let last = index + 1 == self.beziers.len();
+			if remaining <= length || last {
+				let ratio = if length > 0. { (remaining / length).clamp(0., 1.) } else { 0. };
+				return Some((index, bezier.euclidean_to_parametric_with_total_length(ratio, EUCLIDEAN_ERROR_BOUND, length)));
+			}
+			remaining -= length;
+		}
+
+		None
+	}
+}
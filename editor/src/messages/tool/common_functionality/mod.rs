@@ -1,6 +1,8 @@
 pub mod auto_panning;
+pub mod chunked_operation;
 pub mod color_selector;
 pub mod compass_rose;
+pub mod gesture_recording;
 pub mod graph_modification_utils;
 pub mod measure;
 pub mod pivot;
@@ -1,30 +1,337 @@
 use super::graph_modification_utils::{self, merge_layers};
+use super::measure::{DistanceAlongPath, PathArcLength};
 use super::snapping::{SnapCache, SnapCandidatePoint, SnapData, SnapManager, SnappedPoint};
-use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier};
-use crate::messages::portfolio::document::utility_types::misc::{PathSnapSource, SnapSource};
+use crate::consts::{COLINEARITY_REPAIR_EPSILON, COMMON_COORDINATE_EPSILON, DISSOLVE_FIT_SAMPLES};
+use crate::messages::portfolio::document::graph_operation::utility_types::warn_non_finite_edit_skipped;
+use crate::messages::portfolio::document::utility_types::document_metadata::{DocumentMetadata, LayerNodeIdentifier, SubpathIndex};
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, PathSnapSource, SnapSource};
 use crate::messages::portfolio::document::utility_types::network_interface::NodeNetworkInterface;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::chunked_operation::ChunkedCursor;
 use crate::messages::tool::common_functionality::snapping::SnapTypeConfiguration;
-use crate::messages::tool::tool_messages::path_tool::PointSelectState;
+use crate::messages::tool::tool_messages::path_tool::{NewPointType, PointSelectState};
 use bezier_rs::{Bezier, BezierHandles, Subpath, TValue};
 use glam::{DAffine2, DVec2};
 use graphene_core::transform::Transform;
 use graphene_core::vector::{ManipulatorPointId, PointId, VectorData, VectorModificationType};
-use graphene_std::vector::{HandleId, SegmentId};
+use graphene_std::vector::{HandleId, HandleType, IndexedSegment, SegmentBvh, SegmentId};
+use std::hash::Hash;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub enum SelectionChange {
+	#[default]
 	Clear,
 	Extend,
 	Shrink,
 }
 
+impl SelectionChange {
+	/// The change made by a box or lasso selection while the modifiers to extend or shrink the selection are held, with shrinking taking precedence.
+	pub fn from_modifiers(extend_selection: bool, shrink_selection: bool) -> Self {
+		if shrink_selection {
+			Self::Shrink
+		} else if extend_selection {
+			Self::Extend
+		} else {
+			Self::Clear
+		}
+	}
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum SelectionShape<'a> {
 	Box([DVec2; 2]),
 	Lasso(&'a Vec<DVec2>),
 }
 
+/// The selection shape in an owned form which is ready for hit testing, so that it can outlive the message which started the selection.
+#[derive(Clone, Debug)]
+pub enum SelectionHitTester {
+	Box([DVec2; 2]),
+	Lasso(Subpath<PointId>),
+}
+
+impl SelectionHitTester {
+	/// Returns `None` if the shape can't contain anything, which is the case for a lasso of fewer than two points.
+	pub fn new(selection_shape: SelectionShape) -> Option<Self> {
+		match selection_shape {
+			SelectionShape::Box(quad) => Some(Self::Box(quad)),
+			SelectionShape::Lasso(polygon) if polygon.len() >= 2 => Some(Self::Lasso(Subpath::from_anchors_linear(polygon.to_vec(), true))),
+			SelectionShape::Lasso(_) => None,
+		}
+	}
+
+	pub fn contains(&self, position: DVec2) -> bool {
+		match self {
+			Self::Box(quad) => quad[0].min(quad[1]).cmple(position).all() && quad[0].max(quad[1]).cmpge(position).all(),
+			Self::Lasso(polygon) => polygon.contains_point(position),
+		}
+	}
+}
+
+/// An anchor or handle picked by a [`SelectionScan`], with its position in viewport space.
+#[derive(Clone, Copy, Debug)]
+pub struct SelectionCandidate {
+	pub layer: LayerNodeIdentifier,
+	pub id: ManipulatorPointId,
+	pub position: DVec2,
+	/// Whether the point may become selected, as opposed to only being deselected.
+	pub selectable: bool,
+}
+
+/// The handles which a box or lasso selection hit-tests along with the anchors, so it only grabs those shown in the overlays.
+#[derive(Clone, Debug)]
+pub enum SelectionHandles {
+	None,
+	All,
+	/// The handles of only these segments.
+	OfSegments(HashSet<SegmentId>),
+}
+
+impl SelectionHandles {
+	fn includes(&self, segment: SegmentId) -> bool {
+		match self {
+			Self::None => false,
+			Self::All => true,
+			Self::OfSegments(segments) => segments.contains(&segment),
+		}
+	}
+}
+
+/// What a [`SelectionScan`] picks out of the points of each layer it walks through.
+#[derive(Clone, Debug)]
+pub enum SelectionScanTarget {
+	/// The anchors, and the given handles, inside a box or lasso selection.
+	Shape { hit_tester: SelectionHitTester, handles: SelectionHandles },
+	/// Every anchor.
+	AllAnchors,
+	/// The anchors which are sharp or smooth like any of the `selected` anchors. Both kinds are gathered while scanning, and only those of the kinds found selected are kept at the end.
+	SameKind {
+		selected: HashMap<LayerNodeIdentifier, HashSet<PointId>>,
+		sharp: Vec<SelectionCandidate>,
+		smooth: Vec<SelectionCandidate>,
+		selected_sharp: bool,
+		selected_smooth: bool,
+	},
+}
+
+/// The points found by a [`SelectionScan`] so far.
+#[derive(Clone, Debug, Default)]
+pub struct SelectionScanHits {
+	pub points: Vec<SelectionCandidate>,
+	/// The layers which could be scanned, as they're unlocked for editing and have vector data.
+	pub layers: Vec<LayerNodeIdentifier>,
+}
+
+/// The layer a [`SelectionScan`] is partway through.
+#[derive(Clone, Debug)]
+struct ScannedLayer {
+	layer: LayerNodeIdentifier,
+	vector_data: VectorData,
+	transform: DAffine2,
+	/// The next item to scan, counting the two handles of every segment before the anchors.
+	index: usize,
+	/// The anchors found so far to have a handle pulled away from them, which are only tracked when selecting anchors by kind.
+	smooth_anchors: HashSet<PointId>,
+}
+
+impl ScannedLayer {
+	fn load(network_interface: &NodeNetworkInterface, layer: LayerNodeIdentifier) -> Option<Self> {
+		if ShapeState::layer_edit_locked(network_interface, layer) {
+			return None;
+		}
+		let vector_data = network_interface.compute_modified_vector(layer)?;
+
+		debug_assert_eq!(vector_data.segment_domain.ids().len(), vector_data.start_point().count());
+		debug_assert_eq!(vector_data.segment_domain.ids().len(), vector_data.end_point().count());
+
+		Some(Self {
+			layer,
+			transform: network_interface.document_metadata().transform_to_viewport(layer),
+			vector_data,
+			index: 0,
+			smooth_anchors: HashSet::new(),
+		})
+	}
+
+	fn item_count(&self) -> usize {
+		2 * self.vector_data.segment_domain.ids().len() + self.vector_data.point_domain.ids().len()
+	}
+}
+
+/// A cursor walking through the points of several layers one at a time, for a selection which is built up across the chunks of a `ChunkedOperation`.
+/// Each layer's vector data is only computed once the scan reaches it.
+#[derive(Clone, Debug)]
+pub struct SelectionScan {
+	target: SelectionScanTarget,
+	layers: Vec<LayerNodeIdentifier>,
+	layer_index: usize,
+	current: Option<ScannedLayer>,
+}
+
+impl SelectionScan {
+	pub fn new(layers: Vec<LayerNodeIdentifier>, target: SelectionScanTarget) -> Self {
+		Self {
+			target,
+			layers,
+			layer_index: 0,
+			current: None,
+		}
+	}
+
+	/// Scans the next handle or anchor, adding it to `hits` if it's picked. Returns `false` once every layer has been scanned.
+	pub fn step(&mut self, network_interface: &NodeNetworkInterface, hits: &mut SelectionScanHits) -> bool {
+		loop {
+			let Some(scanned) = &mut self.current else {
+				let Some(&layer) = self.layers.get(self.layer_index) else {
+					self.target.finish(hits);
+					return false;
+				};
+
+				self.current = ScannedLayer::load(network_interface, layer);
+				if self.current.is_some() {
+					hits.layers.push(layer);
+				} else {
+					self.layer_index += 1;
+				}
+				continue;
+			};
+
+			let handle_count = 2 * scanned.vector_data.segment_domain.ids().len();
+			let index = scanned.index;
+			if index >= scanned.item_count() {
+				self.current = None;
+				self.layer_index += 1;
+				continue;
+			}
+
+			scanned.index += 1;
+			if index < handle_count {
+				self.target.scan_handle(scanned, index / 2, index % 2 == 1, hits);
+			} else {
+				self.target.scan_anchor(scanned, index - handle_count, hits);
+			}
+			return true;
+		}
+	}
+}
+
+impl ChunkedCursor for SelectionScan {
+	fn progress(&self) -> f64 {
+		if self.layers.is_empty() {
+			return 1.;
+		}
+
+		let layer_progress = self.current.as_ref().map_or(0., |scanned| scanned.index as f64 / scanned.item_count().max(1) as f64);
+		(self.layer_index as f64 + layer_progress) / self.layers.len() as f64
+	}
+}
+
+impl SelectionScanTarget {
+	/// Selects every anchor of the given layers which is sharp or smooth like any of their selected anchors.
+	pub fn same_kind(selected: HashMap<LayerNodeIdentifier, HashSet<PointId>>) -> Self {
+		Self::SameKind {
+			selected,
+			sharp: Vec::new(),
+			smooth: Vec::new(),
+			selected_sharp: false,
+			selected_smooth: false,
+		}
+	}
+
+	fn scan_handle(&mut self, scanned: &mut ScannedLayer, segment_index: usize, end: bool, hits: &mut SelectionScanHits) {
+		let (start_anchor, end_anchor, bezier) = scanned.vector_data.segment_points_from_index(segment_index);
+		let (anchor, anchor_position, position) = if end {
+			(end_anchor, bezier.end, bezier.handle_end())
+		} else {
+			(start_anchor, bezier.start, bezier.handle_start())
+		};
+		let Some(position) = position else { return };
+
+		match self {
+			Self::Shape { hit_tester, handles } => {
+				let segment = scanned.vector_data.segment_domain.ids()[segment_index];
+				let viewport_position = scanned.transform.transform_point2(position);
+				if !handles.includes(segment) || !hit_tester.contains(viewport_position) {
+					return;
+				}
+
+				let id = if end {
+					ManipulatorPointId::EndHandle(segment)
+				} else {
+					ManipulatorPointId::PrimaryHandle(segment)
+				};
+				hits.points.push(SelectionCandidate {
+					layer: scanned.layer,
+					id,
+					position: viewport_position,
+					// Only the handles which are of nonzero length can be selected, but any handle can be deselected
+					selectable: position != anchor_position,
+				});
+			}
+			Self::AllAnchors => {}
+			// Matches `ShapeState::is_sharp_anchor`, gathered over the segments so each anchor's kind is known once the anchors are reached
+			Self::SameKind { .. } => {
+				if !position.abs_diff_eq(anchor_position, 1e-5) {
+					scanned.smooth_anchors.insert(anchor);
+				}
+			}
+		}
+	}
+
+	fn scan_anchor(&mut self, scanned: &ScannedLayer, point_index: usize, hits: &mut SelectionScanHits) {
+		let id = scanned.vector_data.point_domain.ids()[point_index];
+		let candidate = SelectionCandidate {
+			layer: scanned.layer,
+			id: ManipulatorPointId::Anchor(id),
+			position: scanned.transform.transform_point2(scanned.vector_data.point_domain.positions()[point_index]),
+			selectable: true,
+		};
+
+		match self {
+			Self::Shape { hit_tester, .. } => {
+				if hit_tester.contains(candidate.position) {
+					hits.points.push(candidate);
+				}
+			}
+			Self::AllAnchors => hits.points.push(candidate),
+			Self::SameKind {
+				selected,
+				sharp,
+				smooth,
+				selected_sharp,
+				selected_smooth,
+			} => {
+				let is_smooth = scanned.smooth_anchors.contains(&id);
+				let (kind, kind_selected) = if is_smooth { (smooth, selected_smooth) } else { (sharp, selected_sharp) };
+				if selected.get(&scanned.layer).is_some_and(|anchors| anchors.contains(&id)) {
+					*kind_selected = true;
+				}
+				kind.push(candidate);
+			}
+		}
+	}
+
+	/// Adds the points which could only be picked once every layer had been scanned.
+	fn finish(&mut self, hits: &mut SelectionScanHits) {
+		if let Self::SameKind {
+			sharp,
+			smooth,
+			selected_sharp,
+			selected_smooth,
+			..
+		} = self
+		{
+			if *selected_sharp {
+				hits.points.append(sharp);
+			}
+			if *selected_smooth {
+				hits.points.append(smooth);
+			}
+		}
+	}
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SelectionShapeType {
 	Box,
@@ -93,12 +400,28 @@ pub type SelectedShapeState = HashMap<LayerNodeIdentifier, SelectedLayerState>;
 pub struct ShapeState {
 	// The layers we can select and edit manipulators (anchors and handles) from
 	pub selected_shape_state: SelectedShapeState,
+	/// The segments of recently hovered layers, indexed for finding the closest one without measuring them all.
+	segment_indexes: HashMap<LayerNodeIdentifier, LayerSegmentIndex>,
+	/// The layer and transform of the instanced copy that the last found point was picked on, so drags follow that copy's orientation.
+	picked_instance: Option<(LayerNodeIdentifier, DAffine2)>,
+	/// The selected points of each layer when the Path tool was last left, which are selected again upon returning to it.
+	remembered_points: HashMap<LayerNodeIdentifier, HashSet<ManipulatorPointId>>,
+}
+
+/// A layer's segments indexed for hover queries, which stays valid until the document's vector revision changes.
+#[derive(Debug)]
+struct LayerSegmentIndex {
+	revision: u64,
+	bvh: SegmentBvh,
+	/// The handle that each handle is kept colinear with, if any.
+	colinear: HashMap<HandleId, HandleId>,
 }
 
 #[derive(Debug)]
 pub struct SelectedPointsInfo {
 	pub points: Vec<ManipulatorPointInfo>,
-	pub offset: DVec2,
+	/// The point under the cursor, which the drag and its snapping are measured from
+	pub grabbed: ManipulatorPointInfo,
 	pub vector_data: VectorData,
 }
 
@@ -110,6 +433,125 @@ pub struct ManipulatorPointInfo {
 
 pub type OpposingHandleLengths = HashMap<LayerNodeIdentifier, HashMap<HandleId, f64>>;
 
+/// The handles of a segment described relative to its chord (the line between its anchors), so they can be fitted onto a segment of any length and orientation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NormalizedSegmentShape {
+	/// The start and end handles, each as its length in proportion to the chord length and its angle from the chord's direction.
+	pub handles: [Option<(f64, f64)>; 2],
+}
+
+impl NormalizedSegmentShape {
+	/// Returns `None` if the anchors coincide, since there is no chord to measure the handles against.
+	pub fn from_bezier(bezier: &Bezier) -> Option<Self> {
+		let chord = bezier.end - bezier.start;
+		let chord_length = chord.length();
+		if chord_length < f64::EPSILON {
+			return None;
+		}
+
+		let normalize = |handle: Option<DVec2>, anchor: DVec2| handle.map(|handle| ((handle - anchor).length() / chord_length, chord.angle_to(handle - anchor)));
+		let handles = [normalize(bezier.handle_start(), bezier.start), normalize(bezier.handle_end(), bezier.end)];
+
+		Some(Self { handles })
+	}
+
+	/// The handle positions relative to their anchors, for a segment running from `start` to `end`.
+	pub fn relative_handles(&self, start: DVec2, end: DVec2) -> [Option<DVec2>; 2] {
+		let chord = end - start;
+		self.handles.map(|handle| handle.map(|(proportion, angle)| DVec2::from_angle(angle).rotate(chord) * proportion))
+	}
+}
+
+/// A change to a pair of handles flagged as colinear which don't quite form a straight line through their anchor, found by [`ShapeState::colinearity_repairs`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColinearityRepair {
+	/// Rotates both handles onto the average of their axes, keeping their lengths. The positions are relative to the anchor.
+	Realign { handles: [HandleId; 2], relative_positions: [DVec2; 2] },
+	/// Clears the colinear flag of handles deviating too far to have been meant as colinear.
+	Unflag { handles: [HandleId; 2] },
+}
+
+/// The anchors along a route through the segments connecting two anchors, found by [`ShapeState::route_between_anchors`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnchorRoute {
+	/// The anchors in order from the start of the route to its end, including both.
+	pub points: Vec<PointId>,
+	/// The segments between each consecutive pair of the anchors.
+	pub segments: Vec<SegmentId>,
+}
+
+/// Why the anchors between the selected anchors couldn't be selected by [`ShapeState::select_between`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectBetweenError {
+	/// Other than exactly two anchors are selected.
+	NotTwoAnchors,
+	/// The two selected anchors aren't connected, being on different subpaths or layers.
+	NotConnected,
+}
+
+/// Why the selected anchors couldn't be joined by [`ShapeState::join_selected_endpoints`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JoinEndpointsError {
+	/// Other than exactly two anchors are selected.
+	NotTwoAnchors,
+	/// A selected anchor isn't the end of an open subpath, since it's connected to other than exactly one segment.
+	NotEndpoints,
+}
+
+/// Anchors and the segments between them copied by the Path tool, kept in document space so they can be pasted into any layer.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CopiedPoints {
+	/// The positions of the copied anchors.
+	pub anchors: Vec<DVec2>,
+	/// The segments whose anchors were both copied, each as the indices of its anchors in `anchors` and the positions of its handles.
+	pub segments: Vec<([usize; 2], [Option<DVec2>; 2])>,
+	/// The pairs of copied handles kept colinear, each handle as the index of its segment in `segments` and which of its handles it is.
+	pub colinear: Vec<[(usize, HandleType); 2]>,
+}
+
+impl CopiedPoints {
+	/// The middle of the bounding box of the copied anchors.
+	pub fn center(&self) -> DVec2 {
+		let min = self.anchors.iter().copied().fold(DVec2::INFINITY, DVec2::min);
+		let max = self.anchors.iter().copied().fold(DVec2::NEG_INFINITY, DVec2::max);
+		(min + max) / 2.
+	}
+}
+
+/// The source of ids for the points and segments an editing operation adds to a layer.
+/// They are random, unless the document asks for deterministic ids, in which case each is derived from the layer, the count of operations which created ids in it, and the context given for that id.
+#[derive(Clone, Copy, Debug)]
+pub struct OperationIds {
+	layer: LayerNodeIdentifier,
+	/// The layer's operation count to derive the ids with, or `None` if they are random.
+	counter: Option<u64>,
+}
+
+impl OperationIds {
+	/// Begins an operation creating ids in the layer, counting it in the document if its ids are derived.
+	pub fn new(document: &DocumentMessageHandler, layer: LayerNodeIdentifier, responses: &mut VecDeque<Message>) -> Self {
+		let counter = document.deterministic_ids.then(|| {
+			responses.add(DocumentMessage::AdvanceIdCounter { layer });
+			document.id_counters.get(&layer.to_node()).copied().unwrap_or_default()
+		});
+		Self { layer, counter }
+	}
+
+	pub fn point(&self, context: impl Hash) -> PointId {
+		match self.counter {
+			Some(counter) => PointId::derive((self.layer.to_node(), counter, context)),
+			None => PointId::generate(),
+		}
+	}
+
+	pub fn segment(&self, context: impl Hash) -> SegmentId {
+		match self.counter {
+			Some(counter) => SegmentId::derive((self.layer.to_node(), counter, context)),
+			None => SegmentId::generate(),
+		}
+	}
+}
+
 pub struct ClosestSegment {
 	layer: LayerNodeIdentifier,
 	segment: SegmentId,
@@ -134,10 +576,20 @@ impl ClosestSegment {
 		self.points
 	}
 
+	/// The segment's curve in layer space.
+	pub fn bezier(&self) -> Bezier {
+		self.bezier
+	}
+
 	pub fn closest_point_to_viewport(&self) -> DVec2 {
 		self.bezier_point_to_viewport
 	}
 
+	/// The middle of the segment in document space, at half of its parametric length.
+	pub fn midpoint_in_document(&self, document_metadata: &DocumentMetadata) -> DVec2 {
+		document_metadata.transform_to_document(self.layer).transform_point2(self.bezier.evaluate(TValue::Parametric(0.5)))
+	}
+
 	/// Updates this [`ClosestSegment`] with the viewport-space location of the closest point on the segment to the given mouse position.
 	pub fn update_closest_point(&mut self, document_metadata: &DocumentMetadata, mouse_position: DVec2) {
 		let transform = document_metadata.transform_to_viewport(self.layer);
@@ -175,21 +627,65 @@ impl ClosestSegment {
 		(first_handle, second_handle)
 	}
 
-	pub fn adjusted_insert(&self, responses: &mut VecDeque<Message>) -> PointId {
-		let layer = self.layer;
+	/// The handles, relative to their anchors, of the two segments which replace this one when a point is inserted, and whether the new point's handles are colinear.
+	fn inserted_segment_handles(&self, new_point_type: NewPointType) -> ([Option<DVec2>; 2], [Option<DVec2>; 2], bool) {
 		let [first, second] = self.bezier.split(TValue::Parametric(self.t));
+		let linear = matches!(self.bezier.handles, BezierHandles::Linear);
+
+		match new_point_type {
+			// Retract the new point's handles, keeping the handles of the segment's anchors
+			NewPointType::Sharp if !linear => {
+				let [first, second] = [first.to_cubic(), second.to_cubic()];
+				let first_handles = [first.handle_start().map(|handle| handle - first.start), Some(DVec2::ZERO)];
+				let second_handles = [Some(DVec2::ZERO), second.handle_end().map(|handle| handle - second.end)];
+				(first_handles, second_handles, false)
+			}
+			// Give the new point on a straight segment handles along it, which keeps the segment straight
+			NewPointType::Smooth if linear => {
+				let first_handles = [Some(DVec2::ZERO), Some((first.start - first.end) / 3.)];
+				let second_handles = [Some((second.end - second.start) / 3.), Some(DVec2::ZERO)];
+				(first_handles, second_handles, true)
+			}
+			_ => {
+				let first_handles = [first.handle_start().map(|handle| handle - first.start), first.handle_end().map(|handle| handle - first.end)];
+				let second_handles = [second.handle_start().map(|handle| handle - second.start), second.handle_end().map(|handle| handle - second.end)];
+				(first_handles, second_handles, self.bezier.handle_end().is_some())
+			}
+		}
+	}
+
+	/// The viewport positions of the handles the point inserted at the closest point would have, excluding retracted ones.
+	pub fn inserted_handle_positions(&self, document_metadata: &DocumentMetadata, new_point_type: NewPointType) -> Vec<DVec2> {
+		let transform = document_metadata.transform_to_viewport(self.layer);
+		let point = self.bezier.evaluate(TValue::Parametric(self.t));
+		let (first_handles, second_handles, _) = self.inserted_segment_handles(new_point_type);
+
+		[first_handles[1], second_handles[0]]
+			.into_iter()
+			.flatten()
+			.filter(|handle| handle.length_squared() > 0.)
+			.map(|handle| transform.transform_point2(point + handle))
+			.collect()
+	}
+
+	pub fn adjusted_insert(&self, document: &DocumentMessageHandler, new_point_type: NewPointType, responses: &mut VecDeque<Message>) -> PointId {
+		let layer = self.layer;
+		let [first, _] = self.bezier.split(TValue::Parametric(self.t));
+		let (first_handles, second_handles, colinear) = self.inserted_segment_handles(new_point_type);
+		let ids = OperationIds::new(document, layer, responses);
+		let context = (self.segment, self.t.to_bits());
 
 		// Point
-		let midpoint = PointId::generate();
+		let midpoint = ids.point(context);
 		let modification_type = VectorModificationType::InsertPoint { id: midpoint, position: first.end };
 		responses.add(GraphOperationMessage::Vector { layer, modification_type });
 
 		// First segment
-		let segment_ids = [SegmentId::generate(), SegmentId::generate()];
+		let segment_ids = [ids.segment((context, 0)), ids.segment((context, 1))];
 		let modification_type = VectorModificationType::InsertSegment {
 			id: segment_ids[0],
 			points: [self.points[0], midpoint],
-			handles: [first.handle_start().map(|handle| handle - first.start), first.handle_end().map(|handle| handle - first.end)],
+			handles: first_handles,
 		};
 		responses.add(GraphOperationMessage::Vector { layer, modification_type });
 
@@ -197,12 +693,12 @@ impl ClosestSegment {
 		let modification_type = VectorModificationType::InsertSegment {
 			id: segment_ids[1],
 			points: [midpoint, self.points[1]],
-			handles: [second.handle_start().map(|handle| handle - second.start), second.handle_end().map(|handle| handle - second.end)],
+			handles: second_handles,
 		};
 		responses.add(GraphOperationMessage::Vector { layer, modification_type });
 
 		// G1 continuous on new handles
-		if self.bezier.handle_end().is_some() {
+		if colinear {
 			let handles = [HandleId::end(segment_ids[0]), HandleId::primary(segment_ids[1])];
 			let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: true };
 			responses.add(GraphOperationMessage::Vector { layer, modification_type });
@@ -223,8 +719,15 @@ impl ClosestSegment {
 		midpoint
 	}
 
-	pub fn adjusted_insert_and_select(&self, shape_editor: &mut ShapeState, responses: &mut VecDeque<Message>, extend_selection: bool) {
-		let id = self.adjusted_insert(responses);
+	pub fn adjusted_insert_and_select(
+		&self,
+		shape_editor: &mut ShapeState,
+		document: &DocumentMessageHandler,
+		new_point_type: NewPointType,
+		responses: &mut VecDeque<Message>,
+		extend_selection: bool,
+	) {
+		let id = self.adjusted_insert(document, new_point_type, responses);
 		shape_editor.select_anchor_point_by_id(self.layer, id, extend_selection)
 	}
 
@@ -249,6 +752,82 @@ impl ClosestSegment {
 		.unwrap_or(DVec2::ZERO);
 		tangent.perp()
 	}
+
+	pub fn normalized_shape(&self) -> Option<NormalizedSegmentShape> {
+		NormalizedSegmentShape::from_bezier(&self.bezier)
+	}
+
+	/// Replaces this segment's handles with the given shape, fitted onto its chord.
+	pub fn apply_normalized_shape(&self, shape: &NormalizedSegmentShape, responses: &mut VecDeque<Message>) {
+		let handles = shape.relative_handles(self.bezier.start, self.bezier.end);
+		let modification_type = VectorModificationType::SetHandles { segment: self.segment, handles };
+		responses.add(GraphOperationMessage::Vector { layer: self.layer, modification_type });
+	}
+
+	/// The handle positions which bend this segment so it passes through `target` at the `t` it was grabbed at, or `None` if it was grabbed at one of its ends.
+	/// The movement is shared between the two handles with the weighting used by Inkscape, so the handle nearer the grabbed point does more of the bending.
+	/// A straight segment is given handles a third of the way along it to bend from, which keeps it straight until it's moved.
+	pub fn bent_handles(&self, target: DVec2) -> Option<[DVec2; 2]> {
+		let t = self.t;
+		if t * (1. - t) < f64::EPSILON {
+			return None;
+		}
+
+		let cubic = match self.bezier.handles {
+			BezierHandles::Linear => Bezier::from_cubic_dvec2(
+				self.bezier.start,
+				self.bezier.start + (self.bezier.end - self.bezier.start) / 3.,
+				self.bezier.end + (self.bezier.start - self.bezier.end) / 3.,
+				self.bezier.end,
+			),
+			_ => self.bezier.to_cubic(),
+		};
+		let (Some(handle_start), Some(handle_end)) = (cubic.handle_start(), cubic.handle_end()) else {
+			return None;
+		};
+
+		let weight = if t <= 1. / 6. {
+			0.
+		} else if t <= 0.5 {
+			((6. * t - 1.) / 2.).powi(3) / 2.
+		} else if t <= 5. / 6. {
+			(1. - ((6. * (1. - t) - 1.) / 2.).powi(3)) / 2. + 0.5
+		} else {
+			1.
+		};
+		let delta = target - cubic.evaluate(TValue::Parametric(t));
+		let start_offset = delta * (1. - weight) / (3. * t * (1. - t).powi(2));
+		let end_offset = delta * weight / (3. * t.powi(2) * (1. - t));
+
+		Some([handle_start + start_offset, handle_end + end_offset])
+	}
+
+	/// Bends this segment so it passes through `target` (in document space) at the `t` it was grabbed at, as found by [`Self::bent_handles`].
+	/// Handles kept colinear with the bent ones are turned to stay opposite them, keeping their lengths.
+	pub fn bend_through(&self, target: DVec2, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		let layer = self.layer;
+		let target = document.metadata().transform_to_document(layer).inverse().transform_point2(target);
+		let Some(handles) = self.bent_handles(target) else { return };
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { return };
+
+		let relative_handles = [handles[0] - self.bezier.start, handles[1] - self.bezier.end];
+		let modification_type = VectorModificationType::SetHandles {
+			segment: self.segment,
+			handles: relative_handles.map(Some),
+		};
+		responses.add(GraphOperationMessage::Vector { layer, modification_type });
+
+		for ((other, relative_handle), anchor) in self.colinear.into_iter().zip(relative_handles).zip([self.bezier.start, self.bezier.end]) {
+			let Some(other) = other else { continue };
+			let Some(other_position) = other.to_manipulator_point().get_position(&vector_data) else {
+				continue;
+			};
+			let Some(direction) = (-relative_handle).try_normalize() else { continue };
+
+			let modification_type = other.set_relative_position(direction * other_position.distance(anchor));
+			responses.add(GraphOperationMessage::Vector { layer, modification_type });
+		}
+	}
 }
 
 // TODO Consider keeping a list of selected manipulators to minimize traversals of the layers
@@ -285,30 +864,7 @@ impl ShapeState {
 				return;
 			}
 
-			if layer1 == layer2 {
-				if start_point == end_point {
-					return;
-				}
-
-				let segment_id = SegmentId::generate();
-				let modification_type = VectorModificationType::InsertSegment {
-					id: segment_id,
-					points: [end_point, start_point],
-					handles: [None, None],
-				};
-				responses.add(GraphOperationMessage::Vector { layer: layer1, modification_type });
-			} else {
-				// Merge the layers
-				merge_layers(document, layer1, layer2, responses);
-				// Create segment between the two points
-				let segment_id = SegmentId::generate();
-				let modification_type = VectorModificationType::InsertSegment {
-					id: segment_id,
-					points: [end_point, start_point],
-					handles: [None, None],
-				};
-				responses.add(GraphOperationMessage::Vector { layer: layer1, modification_type });
-			}
+			Self::join_endpoints(document, [(layer1, start_point), (layer2, end_point)], responses);
 			return;
 		}
 
@@ -328,7 +884,7 @@ impl ShapeState {
 				let start_point = endpoints[0];
 				let end_point = endpoints[1];
 
-				let segment_id = SegmentId::generate();
+				let segment_id = OperationIds::new(document, layer, responses).segment((start_point, end_point));
 				let modification_type = VectorModificationType::InsertSegment {
 					id: segment_id,
 					points: [end_point, start_point],
@@ -339,8 +895,62 @@ impl ShapeState {
 		}
 	}
 
-	// Snap, returning a viewport delta
-	pub fn snap(&self, snap_manager: &mut SnapManager, snap_cache: &SnapCache, document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler, previous_mouse: DVec2) -> DVec2 {
+	/// Joins two endpoints with a straight segment, first merging the second layer into the first if they are in different layers.
+	pub fn join_endpoints(document: &DocumentMessageHandler, [(layer1, start_point), (layer2, end_point)]: [(LayerNodeIdentifier, PointId); 2], responses: &mut VecDeque<Message>) {
+		if layer1 == layer2 && start_point == end_point {
+			return;
+		}
+
+		// Merge the layers, which does nothing for endpoints in the same layer
+		merge_layers(document, layer1, layer2, responses);
+
+		// Create segment between the two points
+		let segment_id = OperationIds::new(document, layer1, responses).segment((start_point, end_point));
+		let modification_type = VectorModificationType::InsertSegment {
+			id: segment_id,
+			points: [end_point, start_point],
+			handles: [None, None],
+		};
+		responses.add(GraphOperationMessage::Vector { layer: layer1, modification_type });
+	}
+
+	/// The only two selected anchors, if both are endpoints of open subpaths, so they can be joined with [`Self::join_endpoints`].
+	pub fn selected_endpoints_to_join(&self, document: &DocumentMessageHandler) -> Result<[(LayerNodeIdentifier, PointId); 2], JoinEndpointsError> {
+		// In layer stack order, so it's always the lower layer that is merged
+		let anchors = self
+			.sorted_selected_layers(document.metadata())
+			.flat_map(|layer| {
+				let state = &self.selected_shape_state[&layer];
+				state.selected_points.iter().filter_map(|point| point.as_anchor()).map(move |point| (layer, point))
+			})
+			.collect::<Vec<_>>();
+		let [start, end] = anchors[..] else {
+			return Err(JoinEndpointsError::NotTwoAnchors);
+		};
+
+		let is_endpoint = |(layer, point): (LayerNodeIdentifier, PointId)| {
+			document
+				.network_interface
+				.compute_modified_vector(layer)
+				.is_some_and(|vector_data| vector_data.all_connected(point).count() == 1)
+		};
+		if !is_endpoint(start) || !is_endpoint(end) {
+			return Err(JoinEndpointsError::NotEndpoints);
+		}
+
+		Ok([start, end])
+	}
+
+	// Snap, returning a viewport delta. If `grabbed` is given, only that point is used as the snap source.
+	pub fn snap(
+		&self,
+		snap_manager: &mut SnapManager,
+		snap_cache: &SnapCache,
+		document: &DocumentMessageHandler,
+		input: &InputPreprocessorMessageHandler,
+		previous_mouse: DVec2,
+		grabbed: Option<ManipulatorPointInfo>,
+	) -> DVec2 {
 		let snap_data = SnapData::new_snap_cache(document, input, snap_cache);
 
 		let mouse_delta = document
@@ -359,6 +969,10 @@ impl ShapeState {
 			let to_document = document.metadata().transform_to_document(*layer);
 
 			for &selected in &state.selected_points {
+				if grabbed.is_some_and(|grabbed| grabbed != ManipulatorPointInfo { layer: *layer, point_id: selected }) {
+					continue;
+				}
+
 				let source = match selected {
 					ManipulatorPointId::Anchor(_) if vector_data.colinear(selected) => SnapSource::Path(PathSnapSource::AnchorPointWithColinearHandles),
 					ManipulatorPointId::Anchor(_) => SnapSource::Path(PathSnapSource::AnchorPointWithFreeHandles),
@@ -391,7 +1005,7 @@ impl ShapeState {
 	}
 
 	/// Select/deselect the first point within the selection threshold.
-	/// Returns a tuple of the points if found and the offset, or `None` otherwise.
+	/// Returns a tuple of the points if found and the grabbed point, or `None` otherwise.
 	pub fn change_point_selection(&mut self, network_interface: &NodeNetworkInterface, mouse_position: DVec2, select_threshold: f64, extend_selection: bool) -> Option<Option<SelectedPointsInfo>> {
 		if self.selected_shape_state.is_empty() {
 			return None;
@@ -399,7 +1013,11 @@ impl ShapeState {
 
 		if let Some((layer, manipulator_point_id)) = self.find_nearest_point_indices(network_interface, mouse_position, select_threshold) {
 			let vector_data = network_interface.compute_modified_vector(layer)?;
-			let point_position = manipulator_point_id.get_position(&vector_data)?;
+			manipulator_point_id.get_position(&vector_data)?;
+			let grabbed = ManipulatorPointInfo {
+				layer,
+				point_id: manipulator_point_id,
+			};
 
 			let selected_shape_state = self.selected_shape_state.get(&layer)?;
 			let already_selected = selected_shape_state.is_selected(manipulator_point_id);
@@ -407,9 +1025,6 @@ impl ShapeState {
 			// Should we select or deselect the point?
 			let new_selected = if already_selected { !extend_selection } else { true };
 
-			// Offset to snap the selected point to the cursor
-			let offset = mouse_position - network_interface.document_metadata().transform_to_viewport(layer).transform_point2(point_position);
-
 			// This is selecting the manipulator only for now, next to generalize to points
 			if new_selected {
 				let retain_existing_selection = extend_selection || already_selected;
@@ -427,7 +1042,7 @@ impl ShapeState {
 					.flat_map(|(layer, state)| state.selected_points.iter().map(|&point_id| ManipulatorPointInfo { layer: *layer, point_id }))
 					.collect();
 
-				return Some(Some(SelectedPointsInfo { points, offset, vector_data }));
+				return Some(Some(SelectedPointsInfo { points, grabbed, vector_data }));
 			} else {
 				let selected_shape_state = self.selected_shape_state.get_mut(&layer)?;
 				selected_shape_state.deselect_point(manipulator_point_id);
@@ -445,14 +1060,15 @@ impl ShapeState {
 
 		if let Some((layer, manipulator_point_id)) = self.find_nearest_point_indices(network_interface, mouse_position, select_threshold) {
 			let vector_data = network_interface.compute_modified_vector(layer)?;
-			let point_position = manipulator_point_id.get_position(&vector_data)?;
+			manipulator_point_id.get_position(&vector_data)?;
+			let grabbed = ManipulatorPointInfo {
+				layer,
+				point_id: manipulator_point_id,
+			};
 
 			let selected_shape_state = self.selected_shape_state.get(&layer)?;
 			let already_selected = selected_shape_state.is_selected(manipulator_point_id);
 
-			// Offset to snap the selected point to the cursor
-			let offset = mouse_position - network_interface.document_metadata().transform_to_viewport(layer).transform_point2(point_position);
-
 			// Gather current selection information
 			let points = self
 				.selected_shape_state
@@ -460,7 +1076,7 @@ impl ShapeState {
 				.flat_map(|(layer, state)| state.selected_points.iter().map(|&point_id| ManipulatorPointInfo { layer: *layer, point_id }))
 				.collect();
 
-			let selection_info = SelectedPointsInfo { points, offset, vector_data };
+			let selection_info = SelectedPointsInfo { points, grabbed, vector_data };
 
 			// Return the current selection state and info
 			return Some((already_selected, Some(selection_info)));
@@ -480,6 +1096,9 @@ impl ShapeState {
 
 	/// Selects all anchors connected to the selected subpath, and deselects all handles, for the given layer.
 	pub fn select_connected_anchors(&mut self, document: &DocumentMessageHandler, layer: LayerNodeIdentifier, mouse: DVec2) {
+		if Self::layer_edit_locked(&document.network_interface, layer) {
+			return;
+		}
 		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else {
 			return;
 		};
@@ -513,6 +1132,28 @@ impl ShapeState {
 		}
 	}
 
+	/// Selects the anchors of the given subpath (as found by [`DocumentMessageHandler::click_subpath`]), and deselects all handles, for the given layer.
+	pub fn select_subpath_anchors(&mut self, document: &DocumentMessageHandler, layer: LayerNodeIdentifier, subpath_index: SubpathIndex) {
+		if Self::layer_edit_locked(&document.network_interface, layer) {
+			return;
+		}
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else {
+			return;
+		};
+		let Some(region) = document.metadata().subpath_click_regions(layer).and_then(|regions| regions.get(subpath_index)) else {
+			return;
+		};
+		let state = self.selected_shape_state.entry(layer).or_default();
+
+		state.clear_points();
+		let segment_count = vector_data.segment_domain.ids().len();
+		for segment_index in region.segment_ranges.iter().flat_map(|range| range.clone()).filter(|&segment_index| segment_index < segment_count) {
+			let (start, end, _) = vector_data.segment_points_from_index(segment_index);
+			state.select_point(ManipulatorPointId::Anchor(start));
+			state.select_point(ManipulatorPointId::Anchor(end));
+		}
+	}
+
 	/// Selects all anchors, and deselects all handles, for the given layer.
 	pub fn select_all_anchors_in_layer(&mut self, document: &DocumentMessageHandler, layer: LayerNodeIdentifier) {
 		let state = self.selected_shape_state.entry(layer).or_default();
@@ -526,9 +1167,24 @@ impl ShapeState {
 		}
 	}
 
+	/// Whether every anchor of the selected layers which aren't edit locked is already selected.
+	pub fn all_anchors_selected_in_selected_layers(&self, network_interface: &NodeNetworkInterface) -> bool {
+		self.selected_shape_state
+			.iter()
+			.filter(|&(&layer, _)| !Self::layer_edit_locked(network_interface, layer))
+			.all(|(&layer, state)| {
+				network_interface
+					.compute_modified_vector(layer)
+					.is_none_or(|vector_data| vector_data.point_domain.ids().iter().all(|&point| state.is_selected(ManipulatorPointId::Anchor(point))))
+			})
+	}
+
 	/// Internal helper function that selects all anchors, and deselects all handles, for a layer given its [`LayerNodeIdentifier`] and [`SelectedLayerState`].
 	fn select_all_anchors_in_layer_with_state(document: &DocumentMessageHandler, layer: LayerNodeIdentifier, state: &mut SelectedLayerState) {
-		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else {
+		if Self::layer_edit_locked(&document.network_interface, layer) {
+			return;
+		}
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else {
 			return;
 		};
 
@@ -570,8 +1226,9 @@ impl ShapeState {
 		}
 	}
 
-	/// Deselects all the anchors across every selected layer.
-	pub fn deselect_all_anchors(&mut self) {
+	/// Deselects all the anchors across every selected layer, returning whether any were deselected.
+	pub fn deselect_all_anchors(&mut self) -> bool {
+		let selected_count = self.selected_points().count();
 		for (_, state) in self.selected_shape_state.iter_mut() {
 			let selected_anchor_points: Vec<ManipulatorPointId> = state.selected_points.iter().filter(|selected_point| selected_point.as_anchor().is_some()).cloned().collect();
 
@@ -579,10 +1236,12 @@ impl ShapeState {
 				state.deselect_point(point);
 			}
 		}
+		self.selected_points().count() != selected_count
 	}
 
-	/// Deselects all the handles across every selected layer.
-	pub fn deselect_all_handles(&mut self) {
+	/// Deselects all the handles across every selected layer, returning whether any were deselected.
+	pub fn deselect_all_handles(&mut self) -> bool {
+		let selected_count = self.selected_points().count();
 		for (_, state) in self.selected_shape_state.iter_mut() {
 			let selected_handle_points: Vec<ManipulatorPointId> = state.selected_points.iter().filter(|selected_point| selected_point.as_handle().is_some()).cloned().collect();
 
@@ -590,6 +1249,7 @@ impl ShapeState {
 				state.deselect_point(point);
 			}
 		}
+		self.selected_points().count() != selected_count
 	}
 
 	/// Set the shapes we consider for selection, we will choose draggable manipulators from these shapes.
@@ -600,6 +1260,24 @@ impl ShapeState {
 		}
 	}
 
+	/// Remembers the selected points of each layer, so they can be selected again with [`Self::restore_remembered_points`] even if their layers stop being edited in the meantime.
+	pub fn remember_selected_points(&mut self) {
+		self.remembered_points = self.selected_shape_state.iter().map(|(&layer, state)| (layer, state.selected_points.clone())).collect();
+	}
+
+	/// Selects the remembered points again in each layer that's still being edited, dropping those which no longer exist, then forgets them.
+	pub fn restore_remembered_points(&mut self, network_interface: &NodeNetworkInterface) {
+		for (layer, points) in std::mem::take(&mut self.remembered_points) {
+			let Some(state) = self.selected_shape_state.get_mut(&layer) else { continue };
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+
+			state.clear_points();
+			for point in points.into_iter().filter(|point| point.get_position(&vector_data).is_some()) {
+				state.select_point(point);
+			}
+		}
+	}
+
 	/// Returns an iterator over the currently selected layers to get their [`LayerNodeIdentifier`]s.
 	pub fn selected_layers(&self) -> impl Iterator<Item = &LayerNodeIdentifier> {
 		self.selected_shape_state.keys()
@@ -621,6 +1299,12 @@ impl ShapeState {
 		self.selected_shape_state.values().flat_map(|state| &state.selected_points)
 	}
 
+	/// The selected points of each layer that has any.
+	pub fn selected_points_by_layer(&self) -> Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)> {
+		let layers = self.selected_shape_state.iter().filter(|(_, state)| state.selected_points_count() > 0);
+		layers.map(|(&layer, state)| (layer, state.selected().collect())).collect()
+	}
+
 	pub fn selected_points_in_layer(&self, layer: LayerNodeIdentifier) -> Option<&HashSet<ManipulatorPointId>> {
 		self.selected_shape_state.get(&layer).map(|state| &state.selected_points)
 	}
@@ -640,11 +1324,15 @@ impl ShapeState {
 	}
 
 	pub fn move_anchor(&self, point: PointId, vector_data: &VectorData, delta: DVec2, layer: LayerNodeIdentifier, selected: Option<&SelectedLayerState>, responses: &mut VecDeque<Message>) {
+		let mut modifications = Vec::new();
+		Self::push_anchor_move(point, vector_data, delta, selected, &mut modifications);
+		responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+	}
+
+	/// Queues the modifications moving an anchor by `delta`, along with the shared handle of any quadratic segment ending at it.
+	fn push_anchor_move(point: PointId, vector_data: &VectorData, delta: DVec2, selected: Option<&SelectedLayerState>, modifications: &mut Vec<VectorModificationType>) {
 		// Move anchor
-		responses.add(GraphOperationMessage::Vector {
-			layer,
-			modification_type: VectorModificationType::ApplyPointDelta { point, delta },
-		});
+		modifications.push(VectorModificationType::ApplyPointDelta { point, delta });
 
 		// Move the other handle for a quadratic bezier
 		for segment in vector_data.end_connected(point) {
@@ -656,9 +1344,7 @@ impl ShapeState {
 				}
 
 				let relative_position = handle - bezier.start + delta;
-				let modification_type = VectorModificationType::SetPrimaryHandle { segment, relative_position };
-
-				responses.add(GraphOperationMessage::Vector { layer, modification_type });
+				modifications.push(VectorModificationType::SetPrimaryHandle { segment, relative_position });
 			}
 		}
 	}
@@ -785,7 +1471,18 @@ impl ShapeState {
 	/// If both or neither handles are selected, the angle of both handles will be averaged from their current angles, weighted by their lengths.
 	/// Assumes all selected manipulators have handles that are already not colinear.
 	pub fn convert_selected_manipulators_to_colinear_handles(&self, responses: &mut VecDeque<Message>, document: &DocumentMessageHandler) {
+		self.convert_manipulators_to_colinear_handles(responses, document, false);
+	}
+
+	/// Like [`Self::convert_selected_manipulators_to_colinear_handles`], but leaves alone the selected anchors without two handles (endpoints, and sharp corners with retracted handles).
+	/// Returns how many of the selected anchors were converted, and how many were skipped.
+	pub fn convert_selected_smooth_manipulators_to_colinear_handles(&self, responses: &mut VecDeque<Message>, document: &DocumentMessageHandler) -> (usize, usize) {
+		self.convert_manipulators_to_colinear_handles(responses, document, true)
+	}
+
+	fn convert_manipulators_to_colinear_handles(&self, responses: &mut VecDeque<Message>, document: &DocumentMessageHandler, require_both_handles: bool) -> (usize, usize) {
 		let mut skip_set = HashSet::new();
+		let (mut converted, mut skipped) = (0, 0);
 
 		for (&layer, layer_state) in self.selected_shape_state.iter() {
 			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else {
@@ -794,7 +1491,12 @@ impl ShapeState {
 			let transform = document.metadata().transform_to_document(layer);
 
 			for &point in layer_state.selected_points.iter() {
-				let Some(handles) = point.get_handle_pair(&vector_data) else { continue };
+				let Some(handles) = point.get_handle_pair(&vector_data) else {
+					if matches!(point, ManipulatorPointId::Anchor(_)) {
+						skipped += 1;
+					}
+					continue;
+				};
 				if skip_set.contains(&handles) || skip_set.contains(&[handles[1], handles[0]]) {
 					continue;
 				};
@@ -807,6 +1509,15 @@ impl ShapeState {
 				let Some(anchor_id) = point.get_anchor(&vector_data) else { continue };
 				let Some(anchor) = vector_data.point_domain.position_from_id(anchor_id) else { continue };
 
+				// Anchors which are already colinear need no conversion
+				if require_both_handles && vector_data.colinear(point) {
+					continue;
+				}
+				if require_both_handles && !handle_positions.iter().all(|position| position.is_some_and(|position| !position.abs_diff_eq(anchor, 1e-5))) {
+					skipped += 1;
+					continue;
+				}
+
 				let anchor_points = handles.map(|handle| vector_data.other_point(handle.segment, anchor_id));
 				let anchor_positions = anchor_points.map(|point| point.and_then(|point| vector_data.point_domain.position_from_id(point)));
 
@@ -858,12 +1569,45 @@ impl ShapeState {
 				}
 				let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: true };
 				responses.add(GraphOperationMessage::Vector { layer, modification_type });
+				converted += 1;
 			}
 		}
+
+		(converted, skipped)
 	}
 
 	/// Move the selected points by dragging the mouse.
 	#[allow(clippy::too_many_arguments)]
+	/// The document space bounds of the selected points and their handles, covering both where they are and where they will be after [`Self::move_selected_points`] moves them by the viewport space `delta`.
+	pub fn selected_points_dirty_region(&self, document: &DocumentMessageHandler, delta: DVec2) -> Option<[DVec2; 2]> {
+		let delta = document.metadata().document_to_viewport.inverse().transform_vector2(delta);
+		let mut bounds: Option<[DVec2; 2]> = None;
+
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+			let transform = document.metadata().transform_to_document(layer);
+
+			for point in state.selected() {
+				let Some(anchor) = point.get_anchor(&vector_data) else { continue };
+				let handles = vector_data.all_connected(anchor).map(|handle| handle.to_manipulator_point());
+
+				for manipulator in std::iter::once(ManipulatorPointId::Anchor(anchor)).chain(handles) {
+					let Some(position) = manipulator.get_position(&vector_data) else { continue };
+					let position = transform.transform_point2(position);
+
+					for position in [position, position + delta] {
+						bounds = Some(match bounds {
+							Some([min, max]) => [min.min(position), max.max(position)],
+							None => [position, position],
+						});
+					}
+				}
+			}
+		}
+
+		bounds
+	}
+
 	pub fn move_selected_points(
 		&self,
 		handle_lengths: Option<OpposingHandleLengths>,
@@ -881,19 +1625,29 @@ impl ShapeState {
 
 			let opposing_handles = handle_lengths.as_ref().and_then(|handle_lengths| handle_lengths.get(&layer));
 
-			let transform_to_viewport_space = document.metadata().transform_to_viewport(layer);
-			let transform_to_document_space = document.metadata().transform_to_document(layer);
+			// Dragging a point of an instanced copy moves its source point along that copy's orientation
+			let instance_transform = self.picked_instance_transform(layer);
+			let transform_to_viewport_space = document.metadata().transform_to_viewport(layer) * instance_transform;
+			let transform_to_document_space = document.metadata().transform_to_document(layer) * instance_transform;
 			let delta_transform = if in_viewport_space {
 				transform_to_viewport_space
 			} else {
 				DAffine2::from_angle(document.document_ptz.tilt()) * transform_to_document_space
 			};
 			let delta = delta_transform.inverse().transform_vector2(delta);
+			// A degenerate layer transform or snapping result can't be turned into a usable delta
+			if !delta.is_finite() {
+				warn_non_finite_edit_skipped();
+				continue;
+			}
+
+			// Every edit to this layer is sent as one message, so dragging many points doesn't flood the message queue
+			let mut modifications = Vec::new();
 
 			for &point in state.selected_points.iter() {
 				let handle = match point {
 					ManipulatorPointId::Anchor(point) => {
-						self.move_anchor(point, &vector_data, delta, layer, Some(state), responses);
+						Self::push_anchor_move(point, &vector_data, delta, Some(state), &mut modifications);
 						continue;
 					}
 					ManipulatorPointId::PrimaryHandle(segment) => HandleId::primary(segment),
@@ -909,50 +1663,1025 @@ impl ShapeState {
 
 				let Some(handle_position) = point.get_position(&vector_data) else { continue };
 				let handle_position = handle_position + delta;
+				if !handle_position.is_finite() {
+					warn_non_finite_edit_skipped();
+					continue;
+				}
+
+				modifications.push(handle.set_relative_position(handle_position - anchor_position));
+
+				let Some(other) = vector_data.other_colinear_handle(handle) else { continue };
+
+				if skip_opposite_handle {
+					continue;
+				}
+
+				if state.is_selected(other.to_manipulator_point()) {
+					// If two colinear handles are being dragged at the same time but not the anchor, it is necessary to break the colinear state.
+					let handles = [handle, other];
+					modifications.push(VectorModificationType::SetG1Continuous { handles, enabled: false });
+					continue;
+				}
+
+				let new_relative = if equidistant {
+					-(handle_position - anchor_position)
+				}
+				// If the handle is very close to the anchor, return the original position
+				else if (handle_position - anchor_position).length_squared() < f64::EPSILON * 1e5 {
+					let Some(opposite_handle_position) = opposite_handle_position else { continue };
+					opposite_handle_position - anchor_position
+				} else {
+					// TODO: Is this equivalent to `transform_to_document_space`? If changed, the before and after should be tested.
+					let transform = document.metadata().document_to_viewport.inverse() * transform_to_viewport_space;
+					let Some(other_position) = other.to_manipulator_point().get_position(&vector_data) else {
+						continue;
+					};
+					let direction = transform.transform_vector2(handle_position - anchor_position).try_normalize();
+					let opposing_handle = opposing_handles.and_then(|handles| handles.get(&other));
+					let length = opposing_handle.copied().unwrap_or_else(|| transform.transform_vector2(other_position - anchor_position).length());
+					direction.map_or(other_position - anchor_position, |direction| transform.inverse().transform_vector2(-direction * length))
+				};
+
+				// The opposite handle's direction comes from normalizing, which fails for handles of zero length
+				if !new_relative.is_finite() {
+					warn_non_finite_edit_skipped();
+					continue;
+				}
+
+				if !was_alt_dragging {
+					modifications.push(other.set_relative_position(new_relative));
+				}
+			}
+
+			if !modifications.is_empty() {
+				responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+			}
+		}
+	}
+
+	/// Rotates each selected handle around its own anchor by `angle` and scales its length by `scale`, both measured in viewport space.
+	/// Handles whose anchor is also selected are left alone. Unselected colinear partners are rotated by the same angle so they stay colinear.
+	pub fn rotate_and_scale_selected_handles(&self, document: &DocumentMessageHandler, angle: f64, scale: f64, responses: &mut VecDeque<Message>) {
+		let rotation = DVec2::from_angle(angle);
+
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+			let transform = document.metadata().transform_to_viewport(layer);
+
+			let polar_delta = |point: ManipulatorPointId, scale: f64| {
+				let anchor = point.get_anchor(&vector_data)?;
+				let relative = point.get_position(&vector_data)? - vector_data.point_domain.position_from_id(anchor)?;
+				let new_relative = transform.inverse().transform_vector2(rotation.rotate(transform.transform_vector2(relative)) * scale);
+				Some(new_relative - relative)
+			};
+			let move_handle = |handle: HandleId, delta: DVec2| match handle.ty {
+				HandleType::Primary => VectorModificationType::ApplyPrimaryDelta { segment: handle.segment, delta },
+				HandleType::End => VectorModificationType::ApplyEndDelta { segment: handle.segment, delta },
+			};
+
+			let mut modifications = Vec::new();
+
+			for &point in &state.selected_points {
+				let Some(handle) = point.as_handle() else { continue };
+				let Some(anchor) = point.get_anchor(&vector_data) else { continue };
+				if state.is_selected(ManipulatorPointId::Anchor(anchor)) {
+					continue;
+				}
+
+				let Some(delta) = polar_delta(point, scale) else { continue };
+				modifications.push(move_handle(handle, delta));
+
+				let Some(other) = vector_data.other_colinear_handle(handle) else { continue };
+				if state.is_selected(other.to_manipulator_point()) {
+					continue;
+				}
+				if let Some(delta) = polar_delta(other.to_manipulator_point(), 1.) {
+					modifications.push(move_handle(other, delta));
+				}
+			}
+
+			if !modifications.is_empty() {
+				responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+			}
+		}
+	}
+
+	/// Smooths the handles of every selected anchor, leaving the anchors in place. See [`Self::smoothed_segment_handles`].
+	/// The pair of handles at each selected anchor between two segments is marked as colinear once smoothing has lined them up.
+	pub fn smooth_selected_points(&self, network_interface: &NodeNetworkInterface, strength: f64, responses: &mut VecDeque<Message>) {
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+
+			let is_selected = |point| state.is_selected(ManipulatorPointId::Anchor(point));
+			let smoothed = Self::smoothed_segment_handles(&vector_data, is_selected, strength);
+			let smoothed_handle = |handle: HandleId| {
+				let (_, handles) = smoothed.iter().find(|(segment, _)| *segment == handle.segment)?;
+				Some(if handle.ty == HandleType::Primary { handles[0] } else { handles[1] })
+			};
+
+			// Partial smoothing only lines up the handles which were nearly colinear already, so the others are left unflagged
+			let colinear = state
+				.selected_points
+				.iter()
+				.filter_map(|point| point.as_anchor())
+				.filter_map(|anchor| match vector_data.all_connected(anchor).collect::<Vec<_>>()[..] {
+					[first, second] => Some([first, second]),
+					_ => None,
+				})
+				.filter(|&handles| {
+					let [Some(first), Some(second)] = handles.map(smoothed_handle) else { return false };
+					let (Some(first), Some(second)) = (first.try_normalize(), (-second).try_normalize()) else {
+						return false;
+					};
+					first.angle_to(second).abs() < COLINEARITY_REPAIR_EPSILON
+				})
+				.map(|handles| VectorModificationType::SetG1Continuous { handles, enabled: true });
+
+			let modifications = smoothed
+				.iter()
+				.map(|&(segment, handles)| VectorModificationType::SetHandles { segment, handles: handles.map(Some) })
+				.chain(colinear)
+				.collect::<Vec<_>>();
+
+			if !modifications.is_empty() {
+				responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+			}
+		}
+	}
+
+	/// The cubic handles, relative to their anchors, of each segment connected to a selected anchor once the selected anchors are smoothed.
+	/// Each selected anchor's handles are blended by `strength` (from 0 to 1) towards the Catmull-Rom tangent through its two neighboring anchors, each reaching a third of the way
+	/// to the neighbor on its side, or towards a one-sided tangent to its only neighbor for an endpoint. Anchors connected to more than two segments are left alone.
+	pub fn smoothed_segment_handles(vector_data: &VectorData, is_selected: impl Fn(PointId) -> bool, strength: f64) -> Vec<(SegmentId, [DVec2; 2])> {
+		let strength = strength.clamp(0., 1.);
+		let neighbor_position = |segment: SegmentId, anchor: PointId| vector_data.other_point(segment, anchor).and_then(|point| vector_data.point_domain.position_from_id(point));
+
+		// The smoothed handle, relative to the anchor, of the given segment's end at that anchor
+		let smoothed_handle = |segment: SegmentId, anchor: PointId| -> Option<DVec2> {
+			if !is_selected(anchor) {
+				return None;
+			}
+			let position = vector_data.point_domain.position_from_id(anchor)?;
+
+			match vector_data.all_connected(anchor).map(|handle| handle.segment).collect::<Vec<_>>()[..] {
+				[_] => Some((neighbor_position(segment, anchor)? - position) / 3.),
+				[first, second] => {
+					let other_segment = if segment == first { second } else { first };
+					let neighbor = neighbor_position(segment, anchor)?;
+					let tangent = (neighbor - neighbor_position(other_segment, anchor)?).try_normalize()?;
+					Some(tangent * position.distance(neighbor) / 3.)
+				}
+				_ => None,
+			}
+		};
+
+		vector_data
+			.segment_bezier_iter()
+			.filter_map(|(segment, bezier, start, end)| {
+				let targets = [smoothed_handle(segment, start), smoothed_handle(segment, end)];
+				if targets.iter().all(Option::is_none) {
+					return None;
+				}
+
+				// Straight segments keep their unselected ends retracted rather than gaining handles a third of the way along
+				let current = match bezier.handles {
+					BezierHandles::Linear => [DVec2::ZERO; 2],
+					_ => {
+						let cubic = bezier.to_cubic();
+						[cubic.handle_start().unwrap_or(cubic.start) - cubic.start, cubic.handle_end().unwrap_or(cubic.end) - cubic.end]
+					}
+				};
+
+				let handles = [0, 1].map(|index| targets[index].map_or(current[index], |target| current[index].lerp(target, strength)));
+				Some((segment, handles))
+			})
+			.collect()
+	}
+
+	/// The selected anchors of every selected layer, along with their positions in document space.
+	fn selected_anchors_in_document(&self, network_interface: &NodeNetworkInterface) -> Vec<(LayerNodeIdentifier, PointId, DVec2)> {
+		let mut anchors = Vec::new();
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+			let transform = network_interface.document_metadata().transform_to_document(layer) * self.picked_instance_transform(layer);
+
+			anchors.extend(
+				state
+					.selected_points
+					.iter()
+					.filter_map(|point| point.as_anchor())
+					.filter_map(|point| vector_data.point_domain.position_from_id(point).map(|position| (layer, point, transform.transform_point2(position)))),
+			);
+		}
+		anchors
+	}
+
+	/// Moves anchors of the selected layers to new positions in document space, with their handles following along. Returns how many anchors were moved.
+	fn move_anchors_in_document(&self, network_interface: &NodeNetworkInterface, moves: Vec<(LayerNodeIdentifier, PointId, DVec2)>, responses: &mut VecDeque<Message>) -> usize {
+		let mut moved = 0;
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+			let to_layer = (network_interface.document_metadata().transform_to_document(layer) * self.picked_instance_transform(layer)).inverse();
+
+			let mut modifications = Vec::new();
+			for &(_, point, delta) in moves.iter().filter(|(moved_layer, _, _)| *moved_layer == layer) {
+				let delta = to_layer.transform_vector2(delta);
+				// A degenerate layer transform can't be turned into a usable delta
+				if !delta.is_finite() {
+					warn_non_finite_edit_skipped();
+					continue;
+				}
+				if delta == DVec2::ZERO {
+					continue;
+				}
+
+				Self::push_anchor_move(point, &vector_data, delta, Some(state), &mut modifications);
+				moved += 1;
+			}
+
+			if !modifications.is_empty() {
+				responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+			}
+		}
+		moved
+	}
+
+	/// Aligns the selected anchors across all selected layers to the smallest or largest of their coordinates along `axis` in document space,
+	/// or to the average of them for [`AlignAggregate::Center`]. Returns how many anchors were moved.
+	pub fn align_selected_points(&self, network_interface: &NodeNetworkInterface, axis: AlignAxis, aggregate: AlignAggregate, responses: &mut VecDeque<Message>) -> usize {
+		let anchors = self.selected_anchors_in_document(network_interface);
+		if anchors.len() < 2 {
+			return 0;
+		}
+
+		let direction = match axis {
+			AlignAxis::X => DVec2::X,
+			AlignAxis::Y => DVec2::Y,
+		};
+		let coordinates = anchors.iter().map(|(_, _, position)| position.dot(direction));
+		let target = match aggregate {
+			AlignAggregate::Min => coordinates.fold(f64::INFINITY, f64::min),
+			AlignAggregate::Max => coordinates.fold(f64::NEG_INFINITY, f64::max),
+			AlignAggregate::Center => coordinates.sum::<f64>() / anchors.len() as f64,
+		};
+
+		let moves = anchors
+			.into_iter()
+			.map(|(layer, point, position)| (layer, point, (target - position.dot(direction)) * direction))
+			.collect();
+		self.move_anchors_in_document(network_interface, moves, responses)
+	}
+
+	/// Spaces the selected anchors across all selected layers evenly along `axis` in document space between the two outermost ones, keeping their order along it.
+	/// Returns how many anchors were moved.
+	pub fn distribute_selected_points(&self, network_interface: &NodeNetworkInterface, axis: AlignAxis, responses: &mut VecDeque<Message>) -> usize {
+		let mut anchors = self.selected_anchors_in_document(network_interface);
+		if anchors.len() < 3 {
+			return 0;
+		}
+
+		let direction = match axis {
+			AlignAxis::X => DVec2::X,
+			AlignAxis::Y => DVec2::Y,
+		};
+		anchors.sort_by(|(_, _, a), (_, _, b)| a.dot(direction).total_cmp(&b.dot(direction)));
+		let first = anchors[0].2.dot(direction);
+		let step = (anchors[anchors.len() - 1].2.dot(direction) - first) / (anchors.len() - 1) as f64;
+
+		let moves = anchors
+			.into_iter()
+			.enumerate()
+			.map(|(index, (layer, point, position))| (layer, point, (first + step * index as f64 - position.dot(direction)) * direction))
+			.collect();
+		self.move_anchors_in_document(network_interface, moves, responses)
+	}
+
+	/// The X and Y document space coordinates shared by all the selected points across all selected layers, each `None` if they differ along it or none are selected.
+	pub fn selected_points_common_coordinates(&self, network_interface: &NodeNetworkInterface) -> (Option<f64>, Option<f64>) {
+		let mut positions = self.selected_shape_state.iter().flat_map(|(&layer, state)| {
+			let vector_data = network_interface.compute_modified_vector(layer);
+			let transform = network_interface.document_metadata().transform_to_document(layer);
+			state
+				.selected()
+				.filter_map(move |point| vector_data.as_ref().and_then(|vector_data| point.get_position(vector_data)))
+				.map(move |position| transform.transform_point2(position))
+		});
+
+		let Some(first) = positions.next() else { return (None, None) };
+		let (mut x, mut y) = (Some(first.x), Some(first.y));
+		for position in positions {
+			x = x.filter(|&x| (x - position.x).abs() < COMMON_COORDINATE_EPSILON);
+			y = y.filter(|&y| (y - position.y).abs() < COMMON_COORDINATE_EPSILON);
+		}
+		(x, y)
+	}
+
+	/// Moves every selected point across all selected layers to `value` along `axis` in document space, leaving its other coordinate untouched.
+	/// Handles are moved relative to their anchors, so those whose anchor is also selected only make up what following it left over. Returns how many points were moved.
+	pub fn set_selected_points_coordinate(&self, network_interface: &NodeNetworkInterface, axis: AlignAxis, value: f64, responses: &mut VecDeque<Message>) -> usize {
+		let direction = match axis {
+			AlignAxis::X => DVec2::X,
+			AlignAxis::Y => DVec2::Y,
+		};
+
+		let mut moved = 0;
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+			let transform = network_interface.document_metadata().transform_to_document(layer);
+			let positions = state
+				.selected()
+				.filter_map(|point| point.get_position(&vector_data).map(|position| (point, transform.transform_point2(position))))
+				.collect::<HashMap<_, _>>();
+			let delta_to_value = |position: DVec2| (value - position.dot(direction)) * direction;
+
+			for (&point, &position) in &positions {
+				let anchor = point.as_handle().and(point.get_anchor(&vector_data));
+				let anchor_delta = anchor
+					.and_then(|anchor| positions.get(&ManipulatorPointId::Anchor(anchor)))
+					.map_or(DVec2::ZERO, |&anchor| delta_to_value(anchor));
+				let delta = delta_to_value(position) - anchor_delta;
+				if delta == DVec2::ZERO {
+					continue;
+				}
+
+				if self.reposition_control_point(&point, network_interface, position + delta, layer, responses).is_some() {
+					moved += 1;
+				}
+			}
+		}
+		moved
+	}
+
+	/// The route from `start` to `end` through the fewest segments, leaving out the `excluded` segments, or `None` if they aren't connected.
+	fn shortest_route(vector_data: &VectorData, start: PointId, end: PointId, excluded: &HashSet<SegmentId>) -> Option<AnchorRoute> {
+		// Breadth-first, remembering the anchor and segment each anchor was first reached through
+		let mut reached_from = HashMap::new();
+		let mut visited = HashSet::from([start]);
+		let mut queue = VecDeque::from([start]);
+
+		while let Some(point) = queue.pop_front() {
+			if point == end {
+				let mut route = AnchorRoute {
+					points: vec![end],
+					segments: Vec::new(),
+				};
+				while let Some(&(previous, segment)) = reached_from.get(route.points.last()?) {
+					route.points.push(previous);
+					route.segments.push(segment);
+				}
+				route.points.reverse();
+				route.segments.reverse();
+				return Some(route);
+			}
 
-				let modification_type = handle.set_relative_position(handle_position - anchor_position);
+			for segment in vector_data.all_connected(point).map(|handle| handle.segment) {
+				if excluded.contains(&segment) {
+					continue;
+				}
+				let Some(next) = vector_data.other_point(segment, point) else { continue };
+				if visited.insert(next) {
+					reached_from.insert(next, (point, segment));
+					queue.push_back(next);
+				}
+			}
+		}
+
+		None
+	}
+
+	/// The route between two anchors through the segments connecting them, or `None` if they are on different subpaths.
+	/// It passes through the fewest segments, unless `longer_route` is set for anchors on a closed subpath, where it goes the other way around instead.
+	pub fn route_between_anchors(vector_data: &VectorData, start: PointId, end: PointId, longer_route: bool) -> Option<AnchorRoute> {
+		let shortest = Self::shortest_route(vector_data, start, end, &HashSet::new())?;
+		if !longer_route {
+			return Some(shortest);
+		}
+
+		// The other way around is the shortest route which avoids every segment of the first one, if there is such a route
+		let excluded = shortest.segments.iter().copied().collect();
+		Some(Self::shortest_route(vector_data, start, end, &excluded).unwrap_or(shortest))
+	}
+
+	/// Selects every anchor along the route between the only two selected anchors, returning their layer and the route. See [`Self::route_between_anchors`].
+	pub fn select_between(&mut self, network_interface: &NodeNetworkInterface, longer_route: bool) -> Result<(LayerNodeIdentifier, AnchorRoute), SelectBetweenError> {
+		let anchors = self
+			.selected_shape_state
+			.iter()
+			.flat_map(|(&layer, state)| state.selected_points.iter().filter_map(|point| point.as_anchor()).map(move |point| (layer, point)))
+			.collect::<Vec<_>>();
+		let [(layer, start), (end_layer, end)] = anchors[..] else {
+			return Err(SelectBetweenError::NotTwoAnchors);
+		};
+		if layer != end_layer {
+			return Err(SelectBetweenError::NotConnected);
+		}
+
+		let vector_data = network_interface.compute_modified_vector(layer).ok_or(SelectBetweenError::NotConnected)?;
+		let route = Self::route_between_anchors(&vector_data, start, end, longer_route).ok_or(SelectBetweenError::NotConnected)?;
+		if let Some(state) = self.selected_shape_state.get_mut(&layer) {
+			for &point in &route.points {
+				state.select_point(ManipulatorPointId::Anchor(point));
+			}
+		}
+
+		Ok((layer, route))
+	}
+
+	/// The anchors joined to each anchor by a segment.
+	pub fn anchor_adjacency(vector_data: &VectorData) -> HashMap<PointId, Vec<PointId>> {
+		let mut adjacency: HashMap<PointId, Vec<PointId>> = HashMap::new();
+		for (_, _, start, end) in vector_data.segment_bezier_iter() {
+			adjacency.entry(start).or_default().push(end);
+			adjacency.entry(end).or_default().push(start);
+		}
+		adjacency
+	}
+
+	/// Selects every anchor joined by a segment to a selected anchor, so repeating it grows the selection outward along the path one ring at a time.
+	pub fn grow_selection(&mut self, network_interface: &NodeNetworkInterface) {
+		for (&layer, state) in self.selected_shape_state.iter_mut() {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+			let adjacency = Self::anchor_adjacency(&vector_data);
+
+			let neighbors = state
+				.selected_points
+				.iter()
+				.filter_map(|point| point.as_anchor())
+				.filter_map(|anchor| adjacency.get(&anchor))
+				.flatten()
+				.copied()
+				.collect::<Vec<_>>();
+			for neighbor in neighbors {
+				state.select_point(ManipulatorPointId::Anchor(neighbor));
+			}
+		}
+	}
+
+	/// Moves the selection from the point at `from`, or else the only selected point, to the anchor after it along its subpath, or adds that anchor to the selection if `extend` is set.
+	/// A handle moves the selection to its own anchor first. Closed subpaths wrap around, while nothing is selected past the last anchor of an open one.
+	/// Returns the newly selected anchor, from which the next one can be found.
+	pub fn select_next_anchor(
+		&mut self,
+		network_interface: &NodeNetworkInterface,
+		from: Option<(LayerNodeIdentifier, ManipulatorPointId)>,
+		extend: bool,
+	) -> Option<(LayerNodeIdentifier, ManipulatorPointId)> {
+		let (layer, point) = from.or_else(|| {
+			let mut selected = self.selected_shape_state.iter().flat_map(|(&layer, state)| state.selected().map(move |point| (layer, point)));
+			selected.next().filter(|_| selected.next().is_none())
+		})?;
+		let vector_data = network_interface.compute_modified_vector(layer)?;
+
+		let next = match point {
+			ManipulatorPointId::Anchor(anchor) => vector_data.segment_bezier_iter().find(|&(_, _, start, _)| start == anchor).map(|(_, _, _, end)| end)?,
+			handle => handle.get_anchor(&vector_data)?,
+		};
+		let next = ManipulatorPointId::Anchor(next);
+
+		if !extend {
+			self.deselect_all_points();
+		}
+		self.selected_shape_state.entry(layer).or_default().select_point(next);
+		Some((layer, next))
+	}
+
+	/// Deselects the anchors on the frontier of the selection, which are those joined by a segment to an unselected anchor, so repeating it shrinks the selection inward one ring at a time.
+	pub fn shrink_selection(&mut self, network_interface: &NodeNetworkInterface) {
+		for (&layer, state) in self.selected_shape_state.iter_mut() {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+			let adjacency = Self::anchor_adjacency(&vector_data);
+
+			let frontier = state
+				.selected_points
+				.iter()
+				.filter_map(|point| point.as_anchor())
+				.filter(|anchor| {
+					let neighbors = adjacency.get(anchor).map_or(&[][..], Vec::as_slice);
+					neighbors.iter().any(|&neighbor| !state.is_selected(ManipulatorPointId::Anchor(neighbor)))
+				})
+				.collect::<Vec<_>>();
+			for anchor in frontier {
+				state.deselect_point(ManipulatorPointId::Anchor(anchor));
+			}
+		}
+	}
+
+	/// Reverses the direction of every subpath containing a selected anchor, by swapping the start and end of each of its segments along with their handles.
+	/// The primary and end handles of those segments trade places, so their colinear flags and selection are carried over to keep the same handles paired and selected.
+	pub fn reverse_selected_subpaths(&mut self, network_interface: &NodeNetworkInterface, responses: &mut VecDeque<Message>) {
+		for (&layer, state) in self.selected_shape_state.iter_mut() {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+
+			// The segments of the subpaths, found by walking outward from the selected anchors
+			let mut segments = HashSet::new();
+			let mut visited = HashSet::new();
+			let mut queue = state.selected_points.iter().filter_map(|point| point.as_anchor()).collect::<VecDeque<_>>();
+			while let Some(point) = queue.pop_front() {
+				if !visited.insert(point) {
+					continue;
+				}
+				for handle in vector_data.all_connected(point) {
+					segments.insert(handle.segment);
+					queue.extend(vector_data.other_point(handle.segment, point));
+				}
+			}
+			if segments.is_empty() {
+				continue;
+			}
+
+			let mut modifications = Vec::new();
+			for (segment, bezier, start, end) in vector_data.segment_bezier_iter().filter(|(segment, ..)| segments.contains(segment)) {
+				let reversed = bezier.reversed();
+				let handles = match reversed.handles {
+					BezierHandles::Linear => [None, None],
+					BezierHandles::Quadratic { handle } => [Some(handle - reversed.start), None],
+					BezierHandles::Cubic { handle_start, handle_end } => [Some(handle_start - reversed.start), Some(handle_end - reversed.end)],
+				};
+				modifications.push(VectorModificationType::SetStartPoint { segment, id: end });
+				modifications.push(VectorModificationType::SetEndPoint { segment, id: start });
+				modifications.push(VectorModificationType::SetHandles { segment, handles });
+			}
+
+			// Every pair is unflagged before any is flagged again, since a reversed pair can be the same as another pair the other way around
+			let flip = |handle: HandleId| if segments.contains(&handle.segment) { handle.opposite() } else { handle };
+			let colinear = vector_data
+				.colinear_manipulators
+				.iter()
+				.filter(|handles| handles.iter().any(|handle| segments.contains(&handle.segment)))
+				.copied()
+				.collect::<Vec<_>>();
+			modifications.extend(colinear.iter().map(|&handles| VectorModificationType::SetG1Continuous { handles, enabled: false }));
+			let flipped = colinear.iter().map(|handles| handles.map(flip));
+			modifications.extend(flipped.map(|handles| VectorModificationType::SetG1Continuous { handles, enabled: true }));
+
+			responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+
+			state.selected_points = state
+				.selected_points
+				.iter()
+				.map(|&point| point.as_handle().map_or(point, |handle| flip(handle).to_manipulator_point()))
+				.collect();
+		}
+	}
+
+	/// Whether any selected anchor is joined to exactly two segments, so it can be rounded by [`Self::fillet_selected_points`].
+	pub fn has_fillet_corner(&self, network_interface: &NodeNetworkInterface) -> bool {
+		self.selected_shape_state.iter().any(|(&layer, state)| {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { return false };
+			state.selected_points.iter().filter_map(|point| point.as_anchor()).any(|anchor| {
+				let segments = vector_data.all_connected(anchor).map(|handle| handle.segment).collect::<Vec<_>>();
+				matches!(segments[..], [first, second] if first != second)
+			})
+		})
+	}
+
+	/// Rounds each selected anchor joined to exactly two segments, replacing it with a new anchor on each of those segments `radius` along it in document space, and a segment approximating a circular arc between them.
+	/// The radius at each anchor is clamped to half the shorter of its segments, so the corners rounded at both ends of a segment never overlap. The new anchors are selected in place of the rounded ones.
+	/// Returns how many anchors were rounded.
+	pub fn fillet_selected_points(&mut self, document: &DocumentMessageHandler, radius: f64, responses: &mut VecDeque<Message>) -> usize {
+		if !radius.is_finite() || radius <= 0. {
+			return 0;
+		}
+
+		let mut filleted = 0;
+		let layers = self.selected_shape_state.keys().copied().collect::<Vec<_>>();
+		for layer in layers {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+			let to_document = document.metadata().transform_to_document(layer) * self.picked_instance_transform(layer);
+			let to_layer = to_document.inverse();
+			if !to_layer.is_finite() {
+				warn_non_finite_edit_skipped();
+				continue;
+			}
+			let Some(state) = self.selected_shape_state.get_mut(&layer) else { continue };
+
+			// The segments are measured in document space, so the radius is the same regardless of the layer's transform
+			let beziers = vector_data
+				.segment_bezier_iter()
+				.map(|(segment, bezier, start, end)| (segment, (bezier.apply_transformation(|point| to_document.transform_point2(point)), start, end)))
+				.collect::<HashMap<_, _>>();
+			let corners = state
+				.selected_points
+				.iter()
+				.filter_map(|point| point.as_anchor())
+				.filter_map(|anchor| {
+					let segments = match vector_data.all_connected(anchor).map(|handle| handle.segment).collect::<Vec<_>>()[..] {
+						[first, second] if first != second => [first, second],
+						_ => return None,
+					};
+					let shorter = segments
+						.iter()
+						.map(|segment| beziers.get(segment).map_or(0., |(bezier, ..)| bezier.length(None)))
+						.fold(f64::INFINITY, f64::min);
+					let radius = radius.min(shorter / 2.);
+					(radius > 0.).then_some((anchor, segments, radius))
+				})
+				.collect::<Vec<_>>();
+			if corners.is_empty() {
+				continue;
+			}
+
+			let ids = OperationIds::new(document, layer, responses);
+
+			// Where each segment is cut by the corners at its start and end, as the distance from that end and the new anchor placed there
+			let mut cuts = HashMap::<SegmentId, [Option<(f64, PointId)>; 2]>::new();
+			for &(anchor, segments, radius) in &corners {
+				for (index, segment) in segments.into_iter().enumerate() {
+					let end = usize::from(beziers[&segment].1 != anchor);
+					cuts.entry(segment).or_default()[end] = Some((radius, ids.point((anchor, index))));
+				}
+			}
+
+			let (mut insertions, mut removals, mut colinear) = (Vec::new(), Vec::new(), Vec::new());
+			// The new anchor cutting each end of a segment, its position in document space, the direction from it towards the rounded corner, and the cubic handle at it of what remains of the segment
+			let mut cut_anchors = HashMap::new();
+			for (&segment, &ends) in &cuts {
+				let (bezier, start, end) = beziers[&segment];
+				let length = bezier.length(None);
+				let t = [ends[0].map_or(0., |(radius, _)| radius / length), ends[1].map_or(1., |(radius, _)| 1. - radius / length)].map(|ratio| bezier.euclidean_to_parametric(ratio, 1e-6));
+				let remainder = bezier.trim(TValue::Parametric(t[0]), TValue::Parametric(t[1]));
+				let towards_corner = [-bezier.tangent(TValue::Parametric(t[0])), bezier.tangent(TValue::Parametric(t[1]))];
+				let remainder_id = ids.segment((segment, "remainder"));
+
+				// Corners cutting both ends of the segment at its middle share the anchor placed there, leaving nothing of the segment between them
+				let meet = matches!(ends, [Some((start_radius, _)), Some((end_radius, _))] if start_radius + end_radius >= length * (1. - 1e-9));
+				let mut points = [start, end];
+				for (index, cut) in ends.into_iter().enumerate() {
+					let Some((_, id)) = cut else { continue };
+					let id = if meet { ends[0].map_or(id, |(_, id)| id) } else { id };
+					let position = if index == 0 { remainder.start } else { remainder.end };
+					if !meet || index == 0 {
+						insertions.push(VectorModificationType::InsertPoint {
+							id,
+							position: to_layer.transform_point2(position),
+						});
+					}
+					points[index] = id;
+
+					let handle = (!meet && matches!(remainder.handles, BezierHandles::Cubic { .. })).then(|| if index == 0 { HandleId::primary(remainder_id) } else { HandleId::end(remainder_id) });
+					cut_anchors.insert((segment, index), (id, position, towards_corner[index], handle));
+				}
+
+				if !meet {
+					let remainder = remainder.apply_transformation(|point| to_layer.transform_point2(point));
+					let handles = match remainder.handles {
+						BezierHandles::Linear => [None, None],
+						BezierHandles::Quadratic { handle } => [Some(handle - remainder.start), None],
+						BezierHandles::Cubic { handle_start, handle_end } => [Some(handle_start - remainder.start), Some(handle_end - remainder.end)],
+					};
+					insertions.push(VectorModificationType::InsertSegment { id: remainder_id, points, handles });
+
+					// The ends which weren't cut keep their handles paired with those of the neighboring segments
+					let uncut_ends = [(HandleId::primary(segment), HandleId::primary(remainder_id)), (HandleId::end(segment), HandleId::end(remainder_id))];
+					for (_, (handle, new_handle)) in ends.iter().zip(uncut_ends).filter(|(cut, _)| cut.is_none()) {
+						if let Some(other) = vector_data.other_colinear_handle(handle) {
+							colinear.push([other, new_handle]);
+						}
+					}
+				}
+				removals.push(VectorModificationType::RemoveSegment { id: segment });
+			}
+
+			for &(anchor, segments, radius) in &corners {
+				removals.push(VectorModificationType::RemovePoint { id: anchor });
+
+				// The arc follows the direction of the path, from the segment ending at the corner to the one starting there
+				let mut sides = segments.map(|segment| {
+					let end = usize::from(beziers[&segment].1 != anchor);
+					(end, cut_anchors[&(segment, end)])
+				});
+				if sides[0].0 == 0 && sides[1].0 == 1 {
+					sides.swap(0, 1);
+				}
+				let [(_, (start, start_position, start_direction, start_handle)), (_, (end, end_position, end_direction, end_handle))] = sides;
+
+				// The handle length placing the arc's midpoint on the circle tangent to both segments where they are cut
+				let turn = std::f64::consts::PI - start_direction.angle_to(end_direction).abs();
+				let handle_length = radius * if turn < 1e-6 { 2. / 3. } else { 4. / 3. * (turn / 4.).tan() / (turn / 2.).tan() };
+				let handles = [
+					to_layer.transform_point2(start_position + start_direction * handle_length) - to_layer.transform_point2(start_position),
+					to_layer.transform_point2(end_position + end_direction * handle_length) - to_layer.transform_point2(end_position),
+				];
+
+				let id = ids.segment((anchor, "arc"));
+				insertions.push(VectorModificationType::InsertSegment {
+					id,
+					points: [start, end],
+					handles: handles.map(Some),
+				});
+				colinear.extend(start_handle.map(|handle| [HandleId::primary(id), handle]));
+				colinear.extend(end_handle.map(|handle| [HandleId::end(id), handle]));
+
+				state.deselect_point(ManipulatorPointId::Anchor(anchor));
+				state.select_point(ManipulatorPointId::Anchor(start));
+				state.select_point(ManipulatorPointId::Anchor(end));
+				filleted += 1;
+			}
+
+			let mut modifications = insertions;
+			modifications.extend(removals);
+			modifications.extend(colinear.into_iter().map(|handles| VectorModificationType::SetG1Continuous { handles, enabled: true }));
+			responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+		}
+		filleted
+	}
+
+	/// Inserts an anchor at the middle (`t = 0.5`) of each segment between two selected anchors, subdividing the segment so its shape is kept exactly.
+	/// The new anchors are selected in place of the previous selection. Returns how many anchors were inserted.
+	pub fn insert_selected_midpoints(&mut self, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) -> usize {
+		let mut midpoints = Vec::new();
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+			let is_selected = |point| state.is_selected(ManipulatorPointId::Anchor(point));
+			let segments = vector_data
+				.segment_bezier_iter()
+				.filter(|&(_, _, start, end)| is_selected(start) && is_selected(end))
+				.collect::<Vec<_>>();
+			if segments.is_empty() {
+				continue;
+			}
+
+			let ids = OperationIds::new(document, layer, responses);
+			let relative_handles = |bezier: &Bezier| [bezier.handle_start().map(|handle| handle - bezier.start), bezier.handle_end().map(|handle| handle - bezier.end)];
+
+			let (mut insertions, mut removals, mut colinear) = (Vec::new(), Vec::new(), Vec::new());
+			// The two halves replacing each subdivided segment
+			let mut halves = HashMap::new();
+			for (segment, bezier, start, end) in segments {
+				let [first, second] = bezier.split(TValue::Parametric(0.5));
+				let midpoint = ids.point(segment);
+				let half_ids = [ids.segment((segment, 0)), ids.segment((segment, 1))];
+
+				insertions.push(VectorModificationType::InsertPoint { id: midpoint, position: first.end });
+				insertions.push(VectorModificationType::InsertSegment {
+					id: half_ids[0],
+					points: [start, midpoint],
+					handles: relative_handles(&first),
+				});
+				insertions.push(VectorModificationType::InsertSegment {
+					id: half_ids[1],
+					points: [midpoint, end],
+					handles: relative_handles(&second),
+				});
+				if bezier.handle_end().is_some() {
+					colinear.push([HandleId::end(half_ids[0]), HandleId::primary(half_ids[1])]);
+				}
+				removals.push(VectorModificationType::RemoveSegment { id: segment });
+
+				halves.insert(segment, half_ids);
+				midpoints.push((layer, midpoint));
+			}
+
+			// Handles kept colinear across the anchors at the ends of subdivided segments are paired again with the halves now ending there
+			let replacement = |handle: HandleId| match (halves.get(&handle.segment), handle.ty) {
+				(Some(&[first, _]), HandleType::Primary) => HandleId::primary(first),
+				(Some(&[_, second]), HandleType::End) => HandleId::end(second),
+				(None, _) => handle,
+			};
+			for &[first, second] in &vector_data.colinear_manipulators {
+				if halves.contains_key(&first.segment) || halves.contains_key(&second.segment) {
+					colinear.push([replacement(first), replacement(second)]);
+				}
+			}
+
+			let mut modifications = insertions;
+			modifications.extend(removals);
+			modifications.extend(colinear.into_iter().map(|handles| VectorModificationType::SetG1Continuous { handles, enabled: true }));
+			responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+		}
+
+		if !midpoints.is_empty() {
+			self.deselect_all_points();
+			for &(layer, midpoint) in &midpoints {
+				self.selected_shape_state.entry(layer).or_default().select_point(ManipulatorPointId::Anchor(midpoint));
+			}
+		}
+		midpoints.len()
+	}
+
+	/// Copies the selected anchors of every layer along with the segments between them, leaving out the segments leading to an unselected anchor.
+	/// Returns `None` if no anchors are selected.
+	pub fn copy_selected_points(&self, document: &DocumentMessageHandler) -> Option<CopiedPoints> {
+		let mut copied = CopiedPoints::default();
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+			let to_document = document.metadata().transform_to_document(layer);
+
+			let mut anchor_indices = HashMap::new();
+			for (&id, &position) in vector_data.point_domain.ids().iter().zip(vector_data.point_domain.positions()) {
+				if state.is_selected(ManipulatorPointId::Anchor(id)) {
+					anchor_indices.insert(id, copied.anchors.len());
+					copied.anchors.push(to_document.transform_point2(position));
+				}
+			}
+
+			let mut segment_indices = HashMap::new();
+			for (segment, bezier, start, end) in vector_data.segment_bezier_iter() {
+				let (Some(&start), Some(&end)) = (anchor_indices.get(&start), anchor_indices.get(&end)) else {
+					continue;
+				};
+				let handles = [bezier.handle_start(), bezier.handle_end()].map(|handle| handle.map(|handle| to_document.transform_point2(handle)));
+				segment_indices.insert(segment, copied.segments.len());
+				copied.segments.push(([start, end], handles));
+			}
+
+			for &[first, second] in &vector_data.colinear_manipulators {
+				let (Some(&first_index), Some(&second_index)) = (segment_indices.get(&first.segment), segment_indices.get(&second.segment)) else {
+					continue;
+				};
+				copied.colinear.push([(first_index, first.ty), (second_index, second.ty)]);
+			}
+		}
+
+		(!copied.anchors.is_empty()).then_some(copied)
+	}
+
+	/// Adds the copied anchors and segments to the layer under new ids, moved by `offset` in document space.
+	/// The pasted anchors are selected in place of the previous selection.
+	pub fn paste_points(&mut self, document: &DocumentMessageHandler, layer: LayerNodeIdentifier, copied: &CopiedPoints, offset: DVec2, responses: &mut VecDeque<Message>) {
+		let to_layer = document.metadata().transform_to_document(layer).inverse();
+		let ids = OperationIds::new(document, layer, responses);
+
+		let anchors = copied
+			.anchors
+			.iter()
+			.enumerate()
+			.map(|(index, &position)| (ids.point(index), to_layer.transform_point2(position + offset)))
+			.collect::<Vec<_>>();
+		let segments = (0..copied.segments.len()).map(|index| ids.segment(index)).collect::<Vec<_>>();
+
+		let mut modifications = anchors.iter().map(|&(id, position)| VectorModificationType::InsertPoint { id, position }).collect::<Vec<_>>();
+		for (&id, &([start, end], handles)) in segments.iter().zip(&copied.segments) {
+			let [(start, start_position), (end, end_position)] = [anchors[start], anchors[end]];
+			let [handle_start, handle_end] = handles.map(|handle| handle.map(|handle| to_layer.transform_point2(handle + offset)));
+			let handles = [handle_start.map(|handle| handle - start_position), handle_end.map(|handle| handle - end_position)];
+			modifications.push(VectorModificationType::InsertSegment { id, points: [start, end], handles });
+		}
+		let handle_id = |(index, ty): (usize, HandleType)| HandleId { ty, segment: segments[index] };
+		for &[first, second] in &copied.colinear {
+			let handles = [handle_id(first), handle_id(second)];
+			modifications.push(VectorModificationType::SetG1Continuous { handles, enabled: true });
+		}
+		responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+
+		self.deselect_all_points();
+		let state = self.selected_shape_state.entry(layer).or_default();
+		for &(id, _) in &anchors {
+			state.select_point(ManipulatorPointId::Anchor(id));
+		}
+	}
+
+	/// Merges each cluster of selected anchors, chained together by lying within `tolerance` (in document space) of one another, into the first of them placed at their centroid.
+	/// The merged anchor takes over the segments of the others, except for those running between anchors of the cluster, which would collapse onto it so are removed.
+	/// The merged anchors are selected in place of the previous selection. Returns how many anchors were merged into others.
+	pub fn merge_selected_points_by_distance(&mut self, document: &DocumentMessageHandler, tolerance: f64, responses: &mut VecDeque<Message>) -> usize {
+		let (mut survivors, mut merged) = (Vec::new(), 0);
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+			let to_document = document.metadata().transform_to_document(layer);
+
+			// In the order of the layer's points, so the same anchor of a cluster always survives
+			let anchors = vector_data
+				.point_domain
+				.ids()
+				.iter()
+				.zip(vector_data.point_domain.positions())
+				.filter(|&(&id, _)| state.is_selected(ManipulatorPointId::Anchor(id)))
+				.map(|(&id, &position)| (id, position, to_document.transform_point2(position)))
+				.collect::<Vec<_>>();
+
+			// Grow each cluster from its first anchor by every anchor within the tolerance of one already in it
+			let mut clustered = vec![false; anchors.len()];
+			let mut clusters = Vec::new();
+			for first in 0..anchors.len() {
+				if clustered[first] {
+					continue;
+				}
+				clustered[first] = true;
+				let mut cluster = vec![first];
+				let mut next = 0;
+				while let Some(&current) = cluster.get(next) {
+					next += 1;
+					for other in 0..anchors.len() {
+						if !clustered[other] && anchors[current].2.distance(anchors[other].2) <= tolerance {
+							clustered[other] = true;
+							cluster.push(other);
+						}
+					}
+				}
+				if cluster.len() > 1 {
+					clusters.push(cluster);
+				}
+			}
+			if clusters.is_empty() {
+				continue;
+			}
+
+			let mut modifications = Vec::new();
+			// The anchor each anchor of a cluster is merged into
+			let mut merged_into = HashMap::new();
+			for cluster in &clusters {
+				let (survivor, position, _) = anchors[cluster[0]];
+				let centroid = cluster.iter().map(|&index| anchors[index].1).sum::<DVec2>() / cluster.len() as f64;
+				modifications.push(VectorModificationType::ApplyPointDelta {
+					point: survivor,
+					delta: centroid - position,
+				});
+
+				merged_into.extend(cluster.iter().map(|&index| (anchors[index].0, survivor)));
+				modifications.extend(cluster[1..].iter().map(|&index| VectorModificationType::RemovePoint { id: anchors[index].0 }));
+				merged += cluster.len() - 1;
+				survivors.push((layer, survivor));
+			}
+
+			for (segment, _, start, end) in vector_data.segment_bezier_iter() {
+				let [new_start, new_end] = [start, end].map(|point| merged_into.get(&point).copied().unwrap_or(point));
+				if new_start == new_end && [new_start, new_end] != [start, end] {
+					modifications.push(VectorModificationType::RemoveSegment { id: segment });
+					continue;
+				}
+				if new_start != start {
+					modifications.push(VectorModificationType::SetStartPoint { segment, id: new_start });
+				}
+				if new_end != end {
+					modifications.push(VectorModificationType::SetEndPoint { segment, id: new_end });
+				}
+			}
+
+			responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+		}
+
+		if !survivors.is_empty() {
+			self.deselect_all_points();
+			for &(layer, survivor) in &survivors {
+				self.selected_shape_state.entry(layer).or_default().select_point(ManipulatorPointId::Anchor(survivor));
+			}
+		}
+		merged
+	}
+
+	/// Repairs the handles flagged as colinear at the selected anchors which aren't quite on one line, as is common in paths imported from other tools.
+	/// Returns how many anchors had their handles realigned, and how many had their colinear flag cleared.
+	pub fn repair_selected_colinearity(&self, network_interface: &NodeNetworkInterface, tolerance: f64, responses: &mut VecDeque<Message>) -> (usize, usize) {
+		let (mut realigned, mut unflagged) = (0, 0);
+
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+
+			let is_selected = |point| state.is_selected(ManipulatorPointId::Anchor(point));
+			let mut modifications = Vec::new();
+			for repair in Self::colinearity_repairs(&vector_data, is_selected, tolerance) {
+				match repair {
+					ColinearityRepair::Realign { handles, relative_positions } => {
+						modifications.extend(handles.into_iter().zip(relative_positions).map(|(handle, position)| handle.set_relative_position(position)));
+						realigned += 1;
+					}
+					ColinearityRepair::Unflag { handles } => {
+						modifications.push(VectorModificationType::SetG1Continuous { handles, enabled: false });
+						unflagged += 1;
+					}
+				}
+			}
 
-				responses.add(GraphOperationMessage::Vector { layer, modification_type });
+			if !modifications.is_empty() {
+				responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+			}
+		}
 
-				let Some(other) = vector_data.other_colinear_handle(handle) else { continue };
+		(realigned, unflagged)
+	}
 
-				if skip_opposite_handle {
-					continue;
+	/// The repairs to the pairs of handles flagged as colinear at the selected anchors which deviate from a straight line through their anchor.
+	/// Pairs deviating by at most `tolerance` (in radians) are realigned onto the average of their axes, and those deviating further are unflagged.
+	/// Pairs with a retracted handle have no axis to repair, so they are left alone.
+	pub fn colinearity_repairs(vector_data: &VectorData, is_selected: impl Fn(PointId) -> bool, tolerance: f64) -> Vec<ColinearityRepair> {
+		vector_data
+			.colinear_manipulators
+			.iter()
+			.filter_map(|&handles| {
+				let anchor = handles[0].to_manipulator_point().get_anchor(vector_data)?;
+				if !is_selected(anchor) {
+					return None;
 				}
-
-				if state.is_selected(other.to_manipulator_point()) {
-					// If two colinear handles are being dragged at the same time but not the anchor, it is necessary to break the colinear state.
-					let handles = [handle, other];
-					let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: false };
-					responses.add(GraphOperationMessage::Vector { layer, modification_type });
-					continue;
+				let anchor_position = vector_data.point_domain.position_from_id(anchor)?;
+				let [first, second] = handles.map(|handle| handle.to_manipulator_point().get_position(vector_data).map(|position| position - anchor_position));
+				let (first, second) = (first?, second?);
+
+				// Colinear handles point in opposite directions, so the second is flipped to compare it with the first
+				let directions = [first.try_normalize()?, (-second).try_normalize()?];
+				let deviation = directions[0].angle_to(directions[1]).abs();
+				if deviation < COLINEARITY_REPAIR_EPSILON {
+					return None;
 				}
-
-				let new_relative = if equidistant {
-					-(handle_position - anchor_position)
+				if deviation > tolerance {
+					return Some(ColinearityRepair::Unflag { handles });
 				}
-				// If the handle is very close to the anchor, return the original position
-				else if (handle_position - anchor_position).length_squared() < f64::EPSILON * 1e5 {
-					let Some(opposite_handle_position) = opposite_handle_position else { continue };
-					opposite_handle_position - anchor_position
-				} else {
-					// TODO: Is this equivalent to `transform_to_document_space`? If changed, the before and after should be tested.
-					let transform = document.metadata().document_to_viewport.inverse() * transform_to_viewport_space;
-					let Some(other_position) = other.to_manipulator_point().get_position(&vector_data) else {
-						continue;
-					};
-					let direction = transform.transform_vector2(handle_position - anchor_position).try_normalize();
-					let opposing_handle = opposing_handles.and_then(|handles| handles.get(&other));
-					let length = opposing_handle.copied().unwrap_or_else(|| transform.transform_vector2(other_position - anchor_position).length());
-					direction.map_or(other_position - anchor_position, |direction| transform.inverse().transform_vector2(-direction * length))
-				};
 
-				if !was_alt_dragging {
-					let modification_type = other.set_relative_position(new_relative);
-					responses.add(GraphOperationMessage::Vector { layer, modification_type });
-				}
-			}
-		}
+				let axis = (directions[0] + directions[1]).try_normalize()?;
+				let relative_positions = [axis * first.length(), -axis * second.length()];
+				Some(ColinearityRepair::Realign { handles, relative_positions })
+			})
+			.collect()
 	}
 
 	/// The opposing handle lengths.
@@ -1150,9 +2879,181 @@ impl ShapeState {
 		}
 	}
 
+	/// Dissolves the selected anchors like [`Self::delete_selected_points`], but joins the neighbors of each run of dissolved anchors with one segment
+	/// fit by [`Self::merged_segment_fit`] to the segments it replaces, so the curve keeps its shape. Anchors without exactly two connected segments,
+	/// and runs which close a loop or end at another selected anchor, fall back to being dissolved as usual.
+	pub fn dissolve_selected_points_preserving_shape(&mut self, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		for (&layer, state) in &mut self.selected_shape_state {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+
+			let selected_anchors = state.selected_points.iter().filter_map(|point| point.as_anchor()).collect::<HashSet<_>>();
+			let dissolvable = |anchor: PointId| {
+				let mut segments = vector_data.all_connected(anchor).map(|handle| handle.segment);
+				matches!((segments.next(), segments.next(), segments.next()), (Some(first), Some(second), None) if first != second) && selected_anchors.contains(&anchor)
+			};
+
+			// Follows the segments leaving `anchor` through `handle` until reaching an anchor which isn't dissolved, returning the dissolved anchors passed,
+			// the segments with their curves facing away from `anchor`, and the handle and anchor the run ends at, or `None` if the run loops back to `anchor`
+			let follow = |anchor: PointId, mut handle: HandleId| {
+				let (mut anchors, mut curves) = (Vec::new(), Vec::new());
+				loop {
+					let bezier = vector_data.segment_from_id(handle.segment)?;
+					curves.push((handle.segment, if handle.ty == HandleType::Primary { bezier } else { bezier.reverse() }));
+
+					let end = handle.opposite();
+					let next = end.to_manipulator_point().get_anchor(&vector_data)?;
+					if next == anchor {
+						return None;
+					}
+					if !dissolvable(next) {
+						return Some((anchors, curves, end, next));
+					}
+					anchors.push(next);
+					handle = vector_data.all_connected(next).find(|other| other.segment != end.segment)?;
+				}
+			};
+
+			let mut visited = HashSet::new();
+			let mut runs = Vec::new();
+			for &anchor in &selected_anchors {
+				if visited.contains(&anchor) || !dissolvable(anchor) {
+					continue;
+				}
+				let mut handles = vector_data.all_connected(anchor);
+				let (Some(backward), Some(forward)) = (handles.next(), handles.next()) else { continue };
+				let (Some((before, before_curves, start, start_anchor)), Some((after, after_curves, end, end_anchor))) = (follow(anchor, backward), follow(anchor, forward)) else {
+					continue;
+				};
+
+				let anchors = before.into_iter().rev().chain(std::iter::once(anchor)).chain(after).collect::<Vec<_>>();
+				visited.extend(anchors.iter().copied());
+
+				// A run back to the anchor it started from, or onto an anchor which is deleted anyway, has no segment to keep
+				if start_anchor == end_anchor || selected_anchors.contains(&start_anchor) || selected_anchors.contains(&end_anchor) {
+					continue;
+				}
+
+				let curves = before_curves
+					.into_iter()
+					.rev()
+					.map(|(segment, bezier)| (segment, bezier.reverse()))
+					.chain(after_curves)
+					.collect::<Vec<_>>();
+				runs.push((anchors, curves, [start, end], [start_anchor, end_anchor]));
+			}
+			if runs.is_empty() {
+				continue;
+			}
+
+			let ids = OperationIds::new(document, layer, responses);
+			let (mut insertions, mut removals) = (Vec::new(), Vec::new());
+			let (mut dissolved, mut removed_segments) = (HashSet::new(), HashSet::new());
+			// The handles at the ends of each run, replaced by those of the segment merging it
+			let mut replaced = HashMap::new();
+			for (anchors, curves, [start, end], points) in runs {
+				let beziers = curves.iter().map(|&(_, bezier)| bezier).collect::<Vec<_>>();
+				let Some(merged) = Self::merged_segment_fit(&beziers) else { continue };
+
+				let segment = ids.segment(anchors[0]);
+				insertions.push(VectorModificationType::InsertSegment {
+					id: segment,
+					points,
+					handles: [merged.handle_start().map(|handle| handle - merged.start), merged.handle_end().map(|handle| handle - merged.end)],
+				});
+				removals.extend(anchors.iter().map(|&id| VectorModificationType::RemovePoint { id }));
+				removals.extend(curves.iter().map(|&(id, _)| VectorModificationType::RemoveSegment { id }));
+
+				replaced.insert(start, HandleId::primary(segment));
+				replaced.insert(end, HandleId::end(segment));
+				dissolved.extend(anchors);
+				removed_segments.extend(curves.into_iter().map(|(segment, _)| segment));
+			}
+
+			let colinear = vector_data
+				.colinear_manipulators
+				.iter()
+				.filter(|handles| handles.iter().any(|handle| replaced.contains_key(handle)))
+				.map(|handles| handles.map(|handle| replaced.get(&handle).copied().unwrap_or(handle)));
+
+			let mut modifications = insertions;
+			modifications.extend(removals);
+			modifications.extend(colinear.map(|handles| VectorModificationType::SetG1Continuous { handles, enabled: true }));
+			responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+
+			state.selected_points.retain(|point| match point {
+				ManipulatorPointId::Anchor(anchor) => !dissolved.contains(anchor),
+				_ => point.get_segment().is_none_or(|segment| !removed_segments.contains(&segment)),
+			});
+		}
+
+		self.delete_selected_points(document, responses);
+	}
+
+	/// Fits one cubic segment to a chain of joined segments, keeping the ends of the chain and the directions it leaves them in.
+	/// Each segment is merged into the ones before it by least squares over samples of both, placed along the merged curve by how the handles meeting at their joint divide it,
+	/// which recovers a cubic exactly if the two were made by splitting it. Returns `None` if there's no direction to leave an end in, as for a chain collapsed to a point.
+	pub fn merged_segment_fit(curves: &[Bezier]) -> Option<Bezier> {
+		let (first, rest) = curves.split_first()?;
+		rest.iter().try_fold(first.to_cubic(), |merged, next| Self::merged_pair_fit(merged, next.to_cubic()))
+	}
+
+	fn merged_pair_fit(first: Bezier, second: Bezier) -> Option<Bezier> {
+		let (start, end) = (first.start, second.end);
+		let points = first.get_points().chain(second.get_points()).collect::<Vec<_>>();
+		let start_direction = points.iter().find_map(|&point| (point - start).try_normalize())?;
+		let end_direction = points.iter().rev().find_map(|&point| (point - end).try_normalize())?;
+
+		// Splitting a cubic at `t` leaves the handles meeting at the joint in the ratio `t : 1 - t`, but without both of them the lengths of the curves have to do
+		let joint_handles = [first.end.distance(first.handle_end()?), second.start.distance(second.handle_start()?)];
+		let [first_length, second_length] = if joint_handles.iter().all(|&length| length > f64::EPSILON) {
+			joint_handles
+		} else {
+			[first.length(None), second.length(None)]
+		};
+		let split = first_length / (first_length + second_length).max(f64::EPSILON);
+
+		let samples = (1..DISSOLVE_FIT_SAMPLES).map(|index| index as f64 / DISSOLVE_FIT_SAMPLES as f64);
+		let targets = samples
+			.clone()
+			.map(|t| (split * t, first.evaluate(TValue::Parametric(t))))
+			.chain(std::iter::once((split, first.end)))
+			.chain(samples.map(|t| (split + (1. - split) * t, second.evaluate(TValue::Parametric(t)))));
+
+		// Solve the normal equations for the handle lengths along the fixed directions which minimize the squared distances to the samples
+		let (mut products, mut projections) = ([0.; 3], [0.; 2]);
+		for (t, target) in targets {
+			let weights = [(1. - t).powi(3), 3. * (1. - t).powi(2) * t, 3. * (1. - t) * t.powi(2), t.powi(3)];
+			let residual = target - start * (weights[0] + weights[1]) - end * (weights[2] + weights[3]);
+			let [start_basis, end_basis] = [start_direction * weights[1], end_direction * weights[2]];
+
+			products[0] += start_basis.length_squared();
+			products[1] += start_basis.dot(end_basis);
+			products[2] += end_basis.length_squared();
+			projections[0] += start_basis.dot(residual);
+			projections[1] += end_basis.dot(residual);
+		}
+		let determinant = products[0] * products[2] - products[1] * products[1];
+		let lengths = [
+			(projections[0] * products[2] - projections[1] * products[1]) / determinant,
+			(products[0] * projections[1] - products[1] * projections[0]) / determinant,
+		];
+
+		// Handles pointing back past their anchors don't follow the chain, so the usual third of the distance between the ends is used instead
+		let [start_length, end_length] = if determinant.abs() > f64::EPSILON && lengths.iter().all(|&length| length.is_finite() && length > 0.) {
+			lengths
+		} else {
+			[start.distance(end) / 3.; 2]
+		};
+		Some(Bezier::from_cubic_dvec2(start, start + start_direction * start_length, end + end_direction * end_length, end))
+	}
+
 	pub fn break_path_at_selected_point(&self, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
 		for (&layer, state) in &self.selected_shape_state {
 			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+			if state.selected_points.is_empty() {
+				continue;
+			}
+			let ids = OperationIds::new(document, layer, responses);
 
 			for &delete in &state.selected_points {
 				let Some(point) = delete.get_anchor(&vector_data) else { continue };
@@ -1175,7 +3076,7 @@ impl ShapeState {
 					}
 
 					// Create new point
-					let id = PointId::generate();
+					let id = ids.point((point, handle.segment));
 					let modification_type = VectorModificationType::InsertPoint { id, position: pos };
 
 					responses.add(GraphOperationMessage::Vector { layer, modification_type });
@@ -1216,6 +3117,41 @@ impl ShapeState {
 		}
 	}
 
+	/// Retracts the given handles of a layer onto their anchors, which makes the ends of their segments sharp, so none of them are kept colinear with another handle any longer.
+	pub fn retract_handles(layer: LayerNodeIdentifier, vector_data: &VectorData, handles: &[HandleId], responses: &mut VecDeque<Message>) {
+		for handle in handles {
+			let modification_type = handle.set_relative_position(DVec2::ZERO);
+			responses.add(GraphOperationMessage::Vector { layer, modification_type });
+			for &handles in &vector_data.colinear_manipulators {
+				if handles.contains(handle) {
+					let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: false };
+					responses.add(GraphOperationMessage::Vector { layer, modification_type });
+				}
+			}
+		}
+	}
+
+	/// Retracts the selected handles, and both handles of the selected anchors, as done by [`Self::retract_handles`].
+	pub fn retract_selected_handles(&self, network_interface: &NodeNetworkInterface, responses: &mut VecDeque<Message>) {
+		for (&layer, state) in &self.selected_shape_state {
+			let Some(vector_data) = network_interface.compute_modified_vector(layer) else {
+				continue;
+			};
+
+			let mut handles = Vec::new();
+			for &point in &state.selected_points {
+				match point {
+					ManipulatorPointId::Anchor(point) => handles.extend(vector_data.all_connected(point)),
+					_ => handles.extend(point.as_handle()),
+				}
+			}
+			handles.sort_unstable();
+			handles.dedup();
+
+			Self::retract_handles(layer, &vector_data, &handles, responses);
+		}
+	}
+
 	/// Disable colinear handles colinear.
 	pub fn disable_colinear_handles_state_on_selected(&self, network_interface: &NodeNetworkInterface, responses: &mut VecDeque<Message>) {
 		for (&layer, state) in &self.selected_shape_state {
@@ -1239,41 +3175,108 @@ impl ShapeState {
 		}
 	}
 
+	/// Whether the points of the given layer are protected from Path tool edits because it, or one of its ancestors, is edit locked.
+	pub fn layer_edit_locked(network_interface: &NodeNetworkInterface, layer: LayerNodeIdentifier) -> bool {
+		network_interface.selected_nodes().layer_edit_locked(layer, network_interface)
+	}
+
+	/// Deselects every point belonging to a layer which has since become edit locked.
+	pub fn deselect_points_in_edit_locked_layers(&mut self, network_interface: &NodeNetworkInterface) {
+		for (&layer, state) in self.selected_shape_state.iter_mut() {
+			if Self::layer_edit_locked(network_interface, layer) {
+				state.clear_points_force();
+			}
+		}
+	}
+
+	/// Find an edit locked layer with a [ManipulatorPoint] within the selection threshold, used to explain why that point can't be selected.
+	pub fn find_nearest_edit_locked_layer(&self, network_interface: &NodeNetworkInterface, mouse_position: DVec2, select_threshold: f64) -> Option<LayerNodeIdentifier> {
+		let select_threshold_squared = select_threshold * select_threshold;
+
+		self.selected_shape_state.keys().copied().find(|&layer| {
+			Self::layer_edit_locked(network_interface, layer)
+				&& Self::closest_point_in_layer(network_interface, layer, mouse_position, true).is_some_and(|(_, distance_squared, _)| distance_squared < select_threshold_squared)
+		})
+	}
+
 	/// Find a [ManipulatorPoint] that is within the selection threshold and return the layer path, an index to the [ManipulatorGroup], and an enum index for [ManipulatorPoint].
 	pub fn find_nearest_point_indices(&mut self, network_interface: &NodeNetworkInterface, mouse_position: DVec2, select_threshold: f64) -> Option<(LayerNodeIdentifier, ManipulatorPointId)> {
 		if self.selected_shape_state.is_empty() {
 			return None;
 		}
 
-		let select_threshold_squared = select_threshold * select_threshold;
-
 		// Find the closest control point among all elements of shapes_to_modify
-		for &layer in self.selected_shape_state.keys() {
-			if let Some((manipulator_point_id, distance_squared)) = Self::closest_point_in_layer(network_interface, layer, mouse_position) {
-				// Choose the first point under the threshold
-				if distance_squared < select_threshold_squared {
-					trace!("Selecting... manipulator point: {manipulator_point_id:?}");
-					return Some((layer, manipulator_point_id));
-				}
-			}
+		let layers = self.selected_shape_state.keys().copied().filter(|&layer| !Self::layer_edit_locked(network_interface, layer));
+		let (layer, manipulator_point_id, instance_transform) = Self::find_nearest_point_in_layers(network_interface, layers, mouse_position, select_threshold, true)?;
+		trace!("Selecting... manipulator point: {manipulator_point_id:?}");
+
+		self.picked_instance = (instance_transform != DAffine2::IDENTITY).then_some((layer, instance_transform));
+		Some((layer, manipulator_point_id))
+	}
+
+	/// The transform of the instanced copy of the layer's geometry that its points were last picked on, which is the identity for the source geometry itself.
+	pub fn picked_instance_transform(&self, layer: LayerNodeIdentifier) -> DAffine2 {
+		self.picked_instance
+			.filter(|&(picked_layer, _)| picked_layer == layer)
+			.map_or(DAffine2::IDENTITY, |(_, transform)| transform)
+	}
+
+	/// Finds the first anchor within `select_threshold` of the mouse among every visible layer of the document, whether or not it is selected.
+	pub fn find_nearest_anchor_in_document(network_interface: &NodeNetworkInterface, mouse_position: DVec2, select_threshold: f64) -> Option<(LayerNodeIdentifier, PointId)> {
+		let layers = network_interface.document_metadata().all_layers().filter(|layer| network_interface.is_visible(&layer.to_node(), &[]));
+
+		match Self::find_nearest_point_in_layers(network_interface, layers, mouse_position, select_threshold, false)? {
+			(layer, ManipulatorPointId::Anchor(point), _) => Some((layer, point)),
+			_ => None,
 		}
+	}
 
-		None
+	/// Chooses the first of the `layers` with a point (or handle, if `include_handles` is set) within `select_threshold` of the mouse, along with the transform of the instanced copy it was found on.
+	fn find_nearest_point_in_layers(
+		network_interface: &NodeNetworkInterface,
+		layers: impl IntoIterator<Item = LayerNodeIdentifier>,
+		mouse_position: DVec2,
+		select_threshold: f64,
+		include_handles: bool,
+	) -> Option<(LayerNodeIdentifier, ManipulatorPointId, DAffine2)> {
+		let select_threshold_squared = select_threshold * select_threshold;
+
+		layers.into_iter().find_map(|layer| {
+			let (manipulator_point_id, distance_squared, instance_transform) = Self::closest_point_in_layer(network_interface, layer, mouse_position, include_handles)?;
+			(distance_squared < select_threshold_squared).then_some((layer, manipulator_point_id, instance_transform))
+		})
 	}
 
 	// TODO Use quadtree or some equivalent spatial acceleration structure to improve this to O(log(n))
 	/// Find the closest manipulator, manipulator point, and distance so we can select path elements.
 	/// Brute force comparison to determine which manipulator (handle or anchor) we want to select taking O(n) time.
-	/// Return value is an `Option` of the tuple representing `(ManipulatorPointId, distance squared)`.
-	fn closest_point_in_layer(network_interface: &NodeNetworkInterface, layer: LayerNodeIdentifier, pos: glam::DVec2) -> Option<(ManipulatorPointId, f64)> {
+	/// Every instanced copy of the layer's geometry is searched, with points found on a copy mapped back to the source point they were made from.
+	/// Return value is an `Option` of the tuple representing `(ManipulatorPointId, distance squared, transform of the copy it was found on)`.
+	fn closest_point_in_layer(network_interface: &NodeNetworkInterface, layer: LayerNodeIdentifier, pos: glam::DVec2, include_handles: bool) -> Option<(ManipulatorPointId, f64, DAffine2)> {
+		let vector_data = network_interface.compute_modified_vector(layer)?;
+		let metadata = network_interface.document_metadata();
+		let transform_to_viewport = metadata.transform_to_viewport(layer);
+
+		let instance_transforms = metadata.instance_transforms(layer);
+		let instance_transforms = if instance_transforms.is_empty() { &[DAffine2::IDENTITY][..] } else { instance_transforms };
+
+		// Prefer the earliest copy at equal distances, so overlapping copies pick the source geometry
+		instance_transforms
+			.iter()
+			.filter_map(|&instance_transform| {
+				let (manipulator_point, distance_squared) = Self::closest_point_in_vector(&vector_data, transform_to_viewport * instance_transform, pos, include_handles)?;
+				Some((manipulator_point, distance_squared, instance_transform))
+			})
+			.reduce(|closest, candidate| if candidate.1 < closest.1 { candidate } else { closest })
+	}
+
+	/// Find the closest manipulator of the vector data, as seen through the `viewspace` transform, and its distance squared from `pos`.
+	fn closest_point_in_vector(vector_data: &VectorData, viewspace: DAffine2, pos: DVec2, include_handles: bool) -> Option<(ManipulatorPointId, f64)> {
 		let mut closest_distance_squared: f64 = f64::MAX;
 		let mut manipulator_point = None;
 
-		let vector_data = network_interface.compute_modified_vector(layer)?;
-		let viewspace = network_interface.document_metadata().transform_to_viewport(layer);
-
 		// Handles
-		for (segment_id, bezier, _, _) in vector_data.segment_bezier_iter() {
+		for (segment_id, bezier, _, _) in vector_data.segment_bezier_iter().filter(|_| include_handles) {
 			let bezier = bezier.apply_transformation(|point| viewspace.transform_point2(point));
 			let valid = |handle: DVec2, control: DVec2| handle.distance_squared(control) > crate::consts::HIDE_HANDLE_DISTANCE.powi(2);
 
@@ -1304,8 +3307,34 @@ impl ShapeState {
 		manipulator_point.map(|id| (id, closest_distance_squared))
 	}
 
-	/// Find the `t` value along the path segment we have clicked upon, together with that segment ID.
-	fn closest_segment(&self, network_interface: &NodeNetworkInterface, layer: LayerNodeIdentifier, position: glam::DVec2, tolerance: f64) -> Option<ClosestSegment> {
+	/// The index of the segments of the layer's modified vector data, which is rebuilt once the vector data may have changed since it was built.
+	fn segment_index(&mut self, network_interface: &NodeNetworkInterface, layer: LayerNodeIdentifier) -> Option<&LayerSegmentIndex> {
+		let revision = network_interface.document_metadata().vector_revision;
+		self.segment_indexes.retain(|_, index| index.revision == revision);
+
+		if !self.segment_indexes.contains_key(&layer) {
+			let vector_data = network_interface.compute_modified_vector(layer)?;
+			let bvh = SegmentBvh::build_from(&vector_data);
+			let colinear = vector_data.colinear_manipulators.iter().flat_map(|&[a, b]| [(a, b), (b, a)]).collect();
+			self.segment_indexes.insert(layer, LayerSegmentIndex { revision, bvh, colinear });
+		}
+
+		self.segment_indexes.get(&layer)
+	}
+
+	/// Find the `t` value along the path segment we have clicked upon, together with that segment ID. Any `excluded` segments are skipped.
+	fn closest_segment(
+		&mut self,
+		network_interface: &NodeNetworkInterface,
+		layer: LayerNodeIdentifier,
+		position: glam::DVec2,
+		tolerance: f64,
+		excluded: &[(LayerNodeIdentifier, SegmentId)],
+	) -> Option<ClosestSegment> {
+		if Self::layer_edit_locked(network_interface, layer) {
+			return None;
+		}
+
 		let transform = network_interface.document_metadata().transform_to_viewport(layer);
 		let layer_pos = transform.inverse().transform_point2(position);
 
@@ -1314,9 +3343,18 @@ impl ShapeState {
 		let mut closest = None;
 		let mut closest_distance_squared: f64 = tolerance * tolerance;
 
-		let vector_data = network_interface.compute_modified_vector(layer)?;
+		// Segments within the tolerance in viewport space are within this distance in layer space, since no direction is shrunk by more than the determinant divided by the Frobenius norm
+		let determinant = transform.matrix2.determinant().abs();
+		let frobenius_norm = (transform.matrix2.x_axis.length_squared() + transform.matrix2.y_axis.length_squared()).sqrt();
+		let layer_tolerance = if determinant > f64::EPSILON { tolerance * frobenius_norm / determinant } else { f64::INFINITY };
+
+		let index = self.segment_index(network_interface, layer)?;
+
+		for &IndexedSegment { id: segment, mut bezier, start, end } in index.bvh.segments_near(layer_pos, layer_tolerance) {
+			if excluded.contains(&(layer, segment)) {
+				continue;
+			}
 
-		for (segment, mut bezier, start, end) in vector_data.segment_bezier_iter() {
 			let t = bezier.project(layer_pos);
 			let layerspace = bezier.evaluate(TValue::Parametric(t));
 
@@ -1338,10 +3376,8 @@ impl ShapeState {
 					}
 				}
 
-				let primary_handle = vector_data.colinear_manipulators.iter().find(|handles| handles.contains(&HandleId::primary(segment)));
-				let end_handle = vector_data.colinear_manipulators.iter().find(|handles| handles.contains(&HandleId::end(segment)));
-				let primary_handle = primary_handle.and_then(|&handles| handles.into_iter().find(|handle| handle.segment != segment));
-				let end_handle = end_handle.and_then(|&handles| handles.into_iter().find(|handle| handle.segment != segment));
+				let primary_handle = index.colinear.get(&HandleId::primary(segment)).copied().filter(|handle| handle.segment != segment);
+				let end_handle = index.colinear.get(&HandleId::end(segment)).copied().filter(|handle| handle.segment != segment);
 
 				closest = Some(ClosestSegment {
 					segment,
@@ -1359,14 +3395,103 @@ impl ShapeState {
 		closest
 	}
 
+	/// The segments in the order they run along the subpaths, each with its bezier facing that way and whether that is the reverse of how the segment is stored.
+	fn segments_along_path(vector_data: &VectorData) -> Vec<(SegmentId, Bezier, bool)> {
+		// Segments are looked up by their endpoints, and taken out once used so parallel segments between the same anchors are each found once
+		let mut by_points: HashMap<(PointId, PointId), Vec<(SegmentId, Bezier)>> = HashMap::new();
+		for (segment, bezier, start, end) in vector_data.segment_bezier_iter() {
+			by_points.entry((start, end)).or_default().push((segment, bezier));
+		}
+
+		let mut segments = Vec::new();
+		for subpath in vector_data.stroke_bezier_paths() {
+			let groups = subpath.manipulator_groups();
+			let closing = subpath.closed().then(|| groups.last().zip(groups.first())).flatten().map(|(last, first)| (last.id, first.id));
+
+			for (start, end) in groups.windows(2).map(|pair| (pair[0].id, pair[1].id)).chain(closing) {
+				if let Some((segment, bezier)) = by_points.get_mut(&(start, end)).and_then(Vec::pop) {
+					segments.push((segment, bezier, false));
+				} else if let Some((segment, bezier)) = by_points.get_mut(&(end, start)).and_then(Vec::pop) {
+					segments.push((segment, bezier.reversed(), true));
+				}
+			}
+		}
+		segments
+	}
+
+	/// The arc lengths in document space of the segments laid end to end along the subpaths, which is how distances along a layer's path are measured.
+	pub fn path_arc_length(vector_data: &VectorData, layer_to_document: DAffine2) -> PathArcLength {
+		let beziers = Self::segments_along_path(vector_data)
+			.into_iter()
+			.map(|(_, bezier, _)| bezier.apply_transformation(|point| layer_to_document.transform_point2(point)))
+			.collect();
+		PathArcLength::new(beziers)
+	}
+
+	/// The place on the layer's path at the distance along it, measured in document space, as a segment that a point can be inserted on.
+	pub fn segment_at_distance(&mut self, network_interface: &NodeNetworkInterface, layer: LayerNodeIdentifier, distance: DistanceAlongPath) -> Option<ClosestSegment> {
+		if Self::layer_edit_locked(network_interface, layer) {
+			return None;
+		}
+
+		let vector_data = network_interface.compute_modified_vector(layer)?;
+		let metadata = network_interface.document_metadata();
+		let segments = Self::segments_along_path(&vector_data);
+		let (index, t) = Self::path_arc_length(&vector_data, metadata.transform_to_document(layer)).locate(distance)?;
+
+		// The place was found facing along the path, so it's flipped back for segments stored the other way around
+		let (segment, _, reversed) = segments[index];
+		let t = if reversed { 1. - t } else { t };
+		let (_, bezier, start, end) = vector_data.segment_bezier_iter().find(|&(id, ..)| id == segment)?;
+		let bezier_point_to_viewport = metadata.transform_to_viewport(layer).transform_point2(bezier.evaluate(TValue::Parametric(t)));
+
+		const STROKE_WIDTH_PERCENT: f64 = 0.7;
+		let stroke_width = graph_modification_utils::get_stroke_width(layer, network_interface).unwrap_or(1.) as f64 * STROKE_WIDTH_PERCENT;
+
+		let index = self.segment_index(network_interface, layer)?;
+		let primary_handle = index.colinear.get(&HandleId::primary(segment)).copied().filter(|handle| handle.segment != segment);
+		let end_handle = index.colinear.get(&HandleId::end(segment)).copied().filter(|handle| handle.segment != segment);
+
+		Some(ClosestSegment {
+			segment,
+			bezier,
+			points: [start, end],
+			colinear: [primary_handle, end_handle],
+			t,
+			bezier_point_to_viewport,
+			layer,
+			stroke_width,
+		})
+	}
+
 	/// find closest to the position segment on selected layers. If there is more than one layers with close enough segment it return upper from them
-	pub fn upper_closest_segment(&self, network_interface: &NodeNetworkInterface, position: glam::DVec2, tolerance: f64) -> Option<ClosestSegment> {
-		let closest_seg = |layer| self.closest_segment(network_interface, layer, position, tolerance);
-		match self.selected_shape_state.len() {
-			0 => None,
-			1 => self.selected_layers().next().copied().and_then(closest_seg),
-			_ => self.sorted_selected_layers(network_interface.document_metadata()).find_map(closest_seg),
+	pub fn upper_closest_segment(&mut self, network_interface: &NodeNetworkInterface, position: glam::DVec2, tolerance: f64) -> Option<ClosestSegment> {
+		self.upper_closest_segment_excluding(network_interface, position, tolerance, &[])
+	}
+
+	/// Like [`Self::upper_closest_segment`], but skipping the `excluded` segments so the segments beneath them can be targeted.
+	pub fn upper_closest_segment_excluding(
+		&mut self,
+		network_interface: &NodeNetworkInterface,
+		position: glam::DVec2,
+		tolerance: f64,
+		excluded: &[(LayerNodeIdentifier, SegmentId)],
+	) -> Option<ClosestSegment> {
+		let layers = match self.selected_shape_state.len() {
+			0 => return None,
+			1 => self.selected_layers().copied().collect::<Vec<_>>(),
+			_ => self.sorted_selected_layers(network_interface.document_metadata()).collect(),
+		};
+		layers.into_iter().find_map(|layer| self.closest_segment(network_interface, layer, position, tolerance, excluded))
+	}
+
+	/// Counts the segments within the tolerance of the position across the selected layers, which are the segments that can be cycled through when they overlap.
+	pub fn closest_segment_count(&mut self, network_interface: &NodeNetworkInterface, position: glam::DVec2, tolerance: f64) -> usize {
+		let mut found = Vec::new();
+		while let Some(closest) = self.upper_closest_segment_excluding(network_interface, position, tolerance, &found) {
+			found.push((closest.layer(), closest.segment()));
 		}
+		found.len()
 	}
 	pub fn get_dragging_state(&self, network_interface: &NodeNetworkInterface) -> PointSelectState {
 		for &layer in self.selected_shape_state.keys() {
@@ -1479,11 +3604,25 @@ impl ShapeState {
 			}
 		}
 	}
+
+	/// Checks by comparing the handle positions to the anchor if this manipulator group is a point, in which case double clicking it would make it smooth rather than sharp.
+	pub fn is_sharp_anchor(vector_data: &VectorData, anchor: PointId) -> bool {
+		let Some(position) = vector_data.point_domain.position_from_id(anchor) else { return false };
+
+		vector_data
+			.all_connected(anchor)
+			.filter_map(|handle| handle.to_manipulator_point().get_position(vector_data))
+			.all(|handle| position.abs_diff_eq(handle, 1e-5))
+	}
+
 	/// Converts a nearby clicked anchor point's handles between sharp (zero-length handles) and smooth (pulled-apart handle(s)).
 	/// If both handles aren't zero-length, they are set that. If both are zero-length, they are stretched apart by a reasonable amount.
 	/// This can can be activated by double clicking on an anchor with the Path tool.
 	pub fn flip_smooth_sharp(&self, network_interface: &NodeNetworkInterface, target: glam::DVec2, tolerance: f64, responses: &mut VecDeque<Message>) -> bool {
 		let mut process_layer = |layer| {
+			if Self::layer_edit_locked(network_interface, layer) {
+				return None;
+			}
 			let vector_data = network_interface.compute_modified_vector(layer)?;
 			let transform_to_screenspace = network_interface.document_metadata().transform_to_viewport(layer);
 
@@ -1497,56 +3636,16 @@ impl ShapeState {
 
 				if distance_squared < closest_distance_squared {
 					closest_distance_squared = distance_squared;
-					result = Some((id, anchor));
+					result = Some(id);
 				}
 			}
 
-			let (id, anchor) = result?;
-			let handles = vector_data.all_connected(id);
-			let mut positions = handles
-				.filter_map(|handle| handle.to_manipulator_point().get_position(&vector_data))
-				.filter(|&handle| !anchor.abs_diff_eq(handle, 1e-5));
-
-			// Check by comparing the handle positions to the anchor if this manipulator group is a point
-			let already_sharp = positions.next().is_none();
-			if already_sharp {
+			let id = result?;
+			if Self::is_sharp_anchor(&vector_data, id) {
 				self.convert_manipulator_handles_to_colinear(&vector_data, id, responses, layer);
 			} else {
-				for handle in vector_data.all_connected(id) {
-					let Some(bezier) = vector_data.segment_from_id(handle.segment) else { continue };
-
-					match bezier.handles {
-						BezierHandles::Linear => {}
-						BezierHandles::Quadratic { .. } => {
-							let segment = handle.segment;
-							// Convert to linear
-							let modification_type = VectorModificationType::SetHandles { segment, handles: [None; 2] };
-							responses.add(GraphOperationMessage::Vector { layer, modification_type });
-
-							// Set the manipulator to have non-colinear handles
-							for &handles in &vector_data.colinear_manipulators {
-								if handles.contains(&HandleId::primary(segment)) {
-									let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: false };
-									responses.add(GraphOperationMessage::Vector { layer, modification_type });
-								}
-							}
-						}
-						BezierHandles::Cubic { .. } => {
-							// Set handle position to anchor position
-							let modification_type = handle.set_relative_position(DVec2::ZERO);
-							responses.add(GraphOperationMessage::Vector { layer, modification_type });
-
-							// Set the manipulator to have non-colinear handles
-							for &handles in &vector_data.colinear_manipulators {
-								if handles.contains(&handle) {
-									let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: false };
-									responses.add(GraphOperationMessage::Vector { layer, modification_type });
-								}
-							}
-						}
-					}
-				}
-			};
+				Self::make_anchor_sharp(&vector_data, id, layer, responses);
+			}
 
 			Some(true)
 		};
@@ -1560,79 +3659,134 @@ impl ShapeState {
 		false
 	}
 
-	pub fn select_all_in_shape(&mut self, network_interface: &NodeNetworkInterface, selection_shape: SelectionShape, selection_change: SelectionChange) {
-		for (&layer, state) in &mut self.selected_shape_state {
-			if selection_change == SelectionChange::Clear {
-				state.clear_points_force()
+	/// Converts all the selected anchors at once: if any of them is sharp, the sharp ones are made smooth with colinear handles, otherwise they are all made sharp.
+	/// Returns how many anchors were converted.
+	pub fn toggle_selected_anchors_smooth_sharp(&self, network_interface: &NodeNetworkInterface, responses: &mut VecDeque<Message>) -> usize {
+		let layers = self
+			.selected_shape_state
+			.iter()
+			.filter(|&(&layer, _)| !Self::layer_edit_locked(network_interface, layer))
+			.filter_map(|(&layer, state)| network_interface.compute_modified_vector(layer).map(|vector_data| (layer, state, vector_data)))
+			.collect::<Vec<_>>();
+		let selected_anchors = |state: &SelectedLayerState| state.selected().filter_map(|point| point.as_anchor()).collect::<Vec<_>>();
+
+		let make_smooth = layers
+			.iter()
+			.any(|(_, state, vector_data)| selected_anchors(state).into_iter().any(|anchor| Self::is_sharp_anchor(vector_data, anchor)));
+
+		let mut converted = 0;
+		for (layer, state, vector_data) in &layers {
+			for anchor in selected_anchors(state) {
+				if make_smooth {
+					if Self::is_sharp_anchor(vector_data, anchor) {
+						self.convert_manipulator_handles_to_colinear(vector_data, anchor, responses, *layer);
+						converted += 1;
+					}
+				} else {
+					Self::make_anchor_sharp(vector_data, anchor, *layer, responses);
+					converted += 1;
+				}
 			}
+		}
+		converted
+	}
 
-			let vector_data = network_interface.compute_modified_vector(layer);
-			let Some(vector_data) = vector_data else { continue };
-			let transform = network_interface.document_metadata().transform_to_viewport(layer);
+	/// Makes an anchor sharp by retracting its handles into it and breaking their colinearity.
+	fn make_anchor_sharp(vector_data: &VectorData, anchor: PointId, layer: LayerNodeIdentifier, responses: &mut VecDeque<Message>) {
+		for handle in vector_data.all_connected(anchor) {
+			let Some(bezier) = vector_data.segment_from_id(handle.segment) else { continue };
 
-			assert_eq!(vector_data.segment_domain.ids().len(), vector_data.start_point().count());
-			assert_eq!(vector_data.segment_domain.ids().len(), vector_data.end_point().count());
-			for start in vector_data.start_point() {
-				assert!(vector_data.point_domain.ids().contains(&start));
-			}
-			for end in vector_data.end_point() {
-				assert!(vector_data.point_domain.ids().contains(&end));
-			}
+			match bezier.handles {
+				BezierHandles::Linear => {}
+				BezierHandles::Quadratic { .. } => {
+					let segment = handle.segment;
+					// Convert to linear
+					let modification_type = VectorModificationType::SetHandles { segment, handles: [None; 2] };
+					responses.add(GraphOperationMessage::Vector { layer, modification_type });
 
-			let polygon_subpath = if let SelectionShape::Lasso(polygon) = selection_shape {
-				if polygon.len() < 2 {
-					return;
+					// Set the manipulator to have non-colinear handles
+					for &handles in &vector_data.colinear_manipulators {
+						if handles.contains(&HandleId::primary(segment)) {
+							let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: false };
+							responses.add(GraphOperationMessage::Vector { layer, modification_type });
+						}
+					}
 				}
-				let polygon: Subpath<PointId> = Subpath::from_anchors_linear(polygon.to_vec(), true);
-				Some(polygon)
-			} else {
-				None
-			};
-
-			for (id, bezier, _, _) in vector_data.segment_bezier_iter() {
-				for (position, id) in [(bezier.handle_start(), ManipulatorPointId::PrimaryHandle(id)), (bezier.handle_end(), ManipulatorPointId::EndHandle(id))] {
-					let Some(position) = position else { continue };
-					let transformed_position = transform.transform_point2(position);
-
-					let select = match selection_shape {
-						SelectionShape::Box(quad) => quad[0].min(quad[1]).cmple(transformed_position).all() && quad[0].max(quad[1]).cmpge(transformed_position).all(),
-						SelectionShape::Lasso(_) => polygon_subpath
-							.as_ref()
-							.expect("If `selection_shape` is a polygon then subpath is constructed beforehand.")
-							.contains_point(transformed_position),
-					};
+				BezierHandles::Cubic { .. } => {
+					// Set handle position to anchor position
+					let modification_type = handle.set_relative_position(DVec2::ZERO);
+					responses.add(GraphOperationMessage::Vector { layer, modification_type });
 
-					if select {
-						match selection_change {
-							SelectionChange::Shrink => state.deselect_point(id),
-							_ => {
-								// Select only the handles which are of nonzero length
-								if let Some(handle) = id.as_handle() {
-									if handle.length(&vector_data) > 0. {
-										state.select_point(id)
-									}
-								}
-							}
+					// Set the manipulator to have non-colinear handles
+					for &handles in &vector_data.colinear_manipulators {
+						if handles.contains(&handle) {
+							let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: false };
+							responses.add(GraphOperationMessage::Vector { layer, modification_type });
 						}
 					}
 				}
 			}
+		}
+	}
 
-			for (&id, &position) in vector_data.point_domain.ids().iter().zip(vector_data.point_domain.positions()) {
-				let transformed_position = transform.transform_point2(position);
-
-				let select = match selection_shape {
-					SelectionShape::Box(quad) => quad[0].min(quad[1]).cmple(transformed_position).all() && quad[0].max(quad[1]).cmpge(transformed_position).all(),
-					SelectionShape::Lasso(_) => polygon_subpath
-						.as_ref()
-						.expect("If `selection_shape` is a polygon then subpath is constructed beforehand.")
-						.contains_point(transformed_position),
-				};
+	pub fn select_all_in_shape(&mut self, network_interface: &NodeNetworkInterface, selection_shape: SelectionShape, selection_change: SelectionChange, handles: SelectionHandles) {
+		let Some(hit_tester) = SelectionHitTester::new(selection_shape) else {
+			self.apply_shape_selection(&[], selection_change);
+			return;
+		};
+
+		let mut scan = self.selection_scan(SelectionScanTarget::Shape { hit_tester, handles });
+		let mut hits = SelectionScanHits::default();
+		while scan.step(network_interface, &mut hits) {}
+		self.apply_shape_selection(&hits.points, selection_change);
+	}
+
+	/// Begins scanning the points of the selected layers, which happens as the scan is stepped through rather than all at once.
+	pub fn selection_scan(&self, target: SelectionScanTarget) -> SelectionScan {
+		SelectionScan::new(self.selected_shape_state.keys().copied().collect(), target)
+	}
+
+	/// Begins scanning for every anchor of the selected layers which is sharp or smooth like any of the anchors already selected.
+	pub fn same_kind_selection_scan(&self) -> SelectionScan {
+		let selected = self
+			.selected_shape_state
+			.iter()
+			.map(|(&layer, state)| (layer, state.selected().filter_map(|point| point.as_anchor()).collect()))
+			.collect();
+		self.selection_scan(SelectionScanTarget::same_kind(selected))
+	}
+
+	/// Begins scanning for the anchors of every visible and unlocked layer in the document, whose scanned layers should become the selected layers.
+	pub fn document_anchors_selection_scan(document: &DocumentMessageHandler) -> SelectionScan {
+		let network_interface = &document.network_interface;
+		let selected_nodes = network_interface.selected_nodes();
+		let layers = document
+			.metadata()
+			.all_layers()
+			.filter(|&layer| !layer.has_children(document.metadata()) && !network_interface.is_artboard(&layer.to_node(), &[]))
+			.filter(|&layer| selected_nodes.layer_visible(layer, network_interface) && !selected_nodes.layer_locked(layer, network_interface))
+			.collect();
+
+		SelectionScan::new(layers, SelectionScanTarget::AllAnchors)
+	}
+
+	/// Applies the result of a box or lasso selection, given the candidates which were found inside the selection shape.
+	pub fn apply_shape_selection(&mut self, hits: &[SelectionCandidate], selection_change: SelectionChange) {
+		if selection_change == SelectionChange::Clear {
+			for state in self.selected_shape_state.values_mut() {
+				state.clear_points_force();
+			}
+		}
+
+		for hit in hits {
+			// Layers which stopped being selected since the candidates were collected are ignored
+			let Some(state) = self.selected_shape_state.get_mut(&hit.layer) else { continue };
 
-				if select {
-					match selection_change {
-						SelectionChange::Shrink => state.deselect_point(ManipulatorPointId::Anchor(id)),
-						_ => state.select_point(ManipulatorPointId::Anchor(id)),
+			match selection_change {
+				SelectionChange::Shrink => state.deselect_point(hit.id),
+				_ => {
+					if hit.selectable {
+						state.select_point(hit.id);
 					}
 				}
 			}
@@ -7,7 +7,7 @@ mod snap_results;
 use crate::consts::{COLOR_OVERLAY_BLUE, COLOR_OVERLAY_LABEL_BACKGROUND, COLOR_OVERLAY_WHITE};
 use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, Pivot};
 use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
-use crate::messages::portfolio::document::utility_types::misc::{GridSnapTarget, PathSnapTarget, SnapTarget};
+use crate::messages::portfolio::document::utility_types::misc::{GridSnapTarget, PathSnapTarget, SnapSource, SnapTarget};
 use crate::messages::prelude::*;
 pub use alignment_snapper::*;
 use bezier_rs::TValue;
@@ -461,8 +461,16 @@ impl SnapManager {
 
 			let align = [ind.alignment_target_x, ind.alignment_target_y].map(|target| target.map(|target| to_viewport.transform_point2(target)));
 			let any_align = align.iter().flatten().next().is_some();
+			let viewport_diagonal = snap_data.input.viewport_bounds.size().length();
 			for &target in align.iter().flatten() {
-				overlay_context.line(viewport, target, None, None);
+				// Path points are guided by dashed lines running across the whole viewport, through the anchors they line up with
+				match (target - viewport).try_normalize().filter(|_| matches!(ind.source, SnapSource::Path(_))) {
+					Some(direction) => {
+						let [start, end] = [-viewport_diagonal, viewport_diagonal].map(|distance| viewport + direction * distance);
+						overlay_context.dashed_line(start, end, None, None, Some(4.), Some(4.), Some(0.5));
+					}
+					None => overlay_context.line(viewport, target, None, None),
+				}
 			}
 			for &target in align.iter().flatten() {
 				overlay_context.manipulator_handle(target, false, None);
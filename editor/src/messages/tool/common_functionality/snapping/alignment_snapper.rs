@@ -105,8 +105,8 @@ impl AlignmentSnapper {
 					Quad::intersect_rays(target_point.document_point, DVec2::X, origin, direction),
 				]
 			} else {
-				let Some(quad) = target_point.quad.map(|quad| quad.0) else { continue };
-				let edges = [quad[1] - quad[0], quad[3] - quad[0]];
+				// Anchors have no bounds to line up with, so they're aligned with along the document's axes
+				let edges = target_point.quad.map_or([DVec2::X, DVec2::Y], |Quad(quad)| [quad[1] - quad[0], quad[3] - quad[0]]);
 				edges.map(|edge| edge.try_normalize().map(|edge| (point.document_point - target_position).project_onto(edge) + target_position))
 			};
 
@@ -47,6 +47,32 @@ impl LayerSnapper {
 		}
 	}
 
+	/// Adds the horizontal and vertical lines through the center of the artboard.
+	fn add_artboard_center_lines(&mut self, document: &DocumentMessageHandler, artboard: LayerNodeIdentifier) {
+		let target = SnapTarget::Artboard(ArtboardSnapTarget::CenterLine);
+		let Some([min, max]) = document.metadata().bounding_box_with_transform(artboard, document.metadata().transform_to_document(artboard)) else {
+			return;
+		};
+		if !min.is_finite() || !max.is_finite() {
+			return;
+		}
+
+		let center = (min + max) / 2.;
+		let center_lines = [
+			Bezier::from_linear_dvec2(DVec2::new(center.x, min.y), DVec2::new(center.x, max.y)),
+			Bezier::from_linear_dvec2(DVec2::new(min.x, center.y), DVec2::new(max.x, center.y)),
+		];
+		for document_curve in center_lines {
+			self.paths_to_snap.push(SnapCandidatePath {
+				document_curve,
+				layer: artboard,
+				start: PointId::new(),
+				target,
+				bounds: Some(Quad::from_box([min, max])),
+			});
+		}
+	}
+
 	pub fn collect_paths(&mut self, snap_data: &mut SnapData, first_point: bool) {
 		if !first_point {
 			return;
@@ -60,6 +86,24 @@ impl LayerSnapper {
 			}
 			self.add_layer_bounds(document, layer, SnapTarget::Artboard(ArtboardSnapTarget::AlongEdge));
 		}
+		// Points being dragged also snap to the center lines of the artboards containing their layers
+		if document.snapping_state.target_enabled(SnapTarget::Artboard(ArtboardSnapTarget::CenterLine)) {
+			let dragged_layers = snap_data.node_snap_cache.into_iter().flat_map(|cache| cache.manipulators.keys());
+			let mut artboards = dragged_layers
+				.filter_map(|&layer| {
+					layer
+						.ancestors(document.metadata())
+						.find(|&ancestor| ancestor != LayerNodeIdentifier::ROOT_PARENT && document.network_interface.is_artboard(&ancestor.to_node(), &[]))
+				})
+				.filter(|artboard| !snap_data.ignore.contains(artboard))
+				.collect::<Vec<_>>();
+			artboards.sort_unstable();
+			artboards.dedup();
+
+			for artboard in artboards {
+				self.add_artboard_center_lines(document, artboard);
+			}
+		}
 		for &layer in snap_data.get_candidates() {
 			let transform = document.metadata().transform_to_document(layer);
 			if !transform.is_finite() {
@@ -352,6 +396,16 @@ impl SnapCandidatePoint {
 		Self::new_source(document_point, SnapSource::Path(PathSnapSource::AnchorPointWithFreeHandles))
 	}
 
+	/// An anchor which dragged points line up with horizontally or vertically, rather than snap onto.
+	pub fn alignment_anchor(document_point: DVec2) -> Self {
+		Self::new(
+			document_point,
+			SnapSource::Path(PathSnapSource::AnchorPointWithFreeHandles),
+			SnapTarget::Alignment(AlignmentSnapTarget::AlignWithAnchorPoint),
+			None,
+		)
+	}
+
 	pub fn handle_neighbors(document_point: DVec2, neighbors: impl Into<Vec<DVec2>>) -> Self {
 		let mut point = Self::new_source(document_point, SnapSource::Path(PathSnapSource::AnchorPointWithFreeHandles));
 		point.neighbors = neighbors.into();
@@ -18,6 +18,8 @@ pub struct ToolMessageData<'a> {
 	pub persistent_data: &'a PersistentData,
 	pub node_graph: &'a NodeGraphExecutor,
 	pub preferences: &'a PreferencesMessageHandler,
+	/// Whether the Point Table panel is open and should be kept up to date with the selected points.
+	pub point_table_open: bool,
 }
 
 #[derive(Debug, Default)]
@@ -37,6 +39,7 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 			persistent_data,
 			node_graph,
 			preferences,
+			point_table_open,
 		} = data;
 		let font_cache = &persistent_data.font_cache;
 
@@ -73,6 +76,10 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 				if self.tool_is_active && tool_type == old_tool {
 					return;
 				}
+				// Remember the point selection when leaving the Path tool, so returning to it restores the selection even if its layers were deselected in the meantime
+				if self.tool_is_active && old_tool == ToolType::Path {
+					self.shape_editor.remember_selected_points();
+				}
 				self.tool_is_active = true;
 
 				// Send the old and new tools a transition to their FSM Abort states
@@ -87,6 +94,7 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 							shape_editor: &mut self.shape_editor,
 							node_graph,
 							preferences,
+							point_table_open,
 						};
 
 						if let Some(tool_abort_message) = tool.event_to_message_map().tool_abort {
@@ -117,6 +125,11 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 				// Store the new active tool
 				tool_data.active_tool_type = tool_type;
 
+				// The Path tool relies on subpath click regions which are only collected by renders made while it is active
+				if tool_type == ToolType::Path {
+					responses.add(PortfolioMessage::SubmitActiveGraphRender);
+				}
+
 				// Subscribe new tool
 				tool_data.tools.get(&tool_type).unwrap().activate(responses);
 
@@ -182,6 +195,7 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 					shape_editor: &mut self.shape_editor,
 					node_graph,
 					preferences,
+					point_table_open,
 				};
 
 				// Set initial hints and cursor
@@ -274,6 +288,7 @@ impl MessageHandler<ToolMessage, ToolMessageData<'_>> for ToolMessageHandler {
 							shape_editor: &mut self.shape_editor,
 							node_graph,
 							preferences,
+							point_table_open,
 						};
 						if matches!(tool_message, ToolMessage::UpdateHints) {
 							if graph_view_overlay_open {
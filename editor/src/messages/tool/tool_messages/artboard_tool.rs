@@ -34,6 +34,8 @@ pub enum ArtboardToolMessage {
 	PointerMove { constrain_axis_or_aspect: Key, center: Key },
 	PointerOutsideViewport { constrain_axis_or_aspect: Key, center: Key },
 	PointerUp,
+	EditExportBounds,
+	ConfirmExportBounds,
 }
 
 impl ToolMetadata for ArtboardTool {
@@ -50,7 +52,24 @@ impl ToolMetadata for ArtboardTool {
 
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for ArtboardTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
+		let was_editing_export_bounds = self.data.editing_export_bounds;
+		let previous_state = self.fsm_state;
+		let update_hints = matches!(message, ToolMessage::UpdateHints);
+
 		self.fsm_state.process_event(message, &mut self.data, tool_data, &(), responses, false);
+
+		// The Ready hints are replaced by those for editing the export bounds while they are edited
+		let ready = matches!(self.fsm_state, ArtboardToolFsmState::Ready { .. });
+		if self.data.editing_export_bounds && ready && (update_hints || !was_editing_export_bounds || previous_state != self.fsm_state) {
+			responses.add(FrontendMessage::UpdateInputHints {
+				hint_data: HintData(vec![
+					HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Draw, Move, or Resize Export Bounds")]),
+					HintGroup(vec![HintInfo::keys([Key::Enter], "Return to Export"), HintInfo::keys([Key::Escape], "").prepend_slash()]),
+				]),
+			});
+		} else if was_editing_export_bounds && !self.data.editing_export_bounds {
+			self.fsm_state.update_hints(responses);
+		}
 	}
 
 	fn actions(&self) -> ActionList {
@@ -61,6 +80,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Artboar
 		);
 
 		let additional = match self.fsm_state {
+			ArtboardToolFsmState::Ready { .. } if self.data.editing_export_bounds => actions!(ArtboardToolMessageDiscriminant; PointerDown, ConfirmExportBounds),
 			ArtboardToolFsmState::Ready { .. } => actions!(ArtboardToolMessageDiscriminant; PointerDown),
 			_ => actions!(ArtboardToolMessageDiscriminant; PointerUp, Abort),
 		};
@@ -112,15 +132,24 @@ struct ArtboardToolData {
 	snap_candidates: Vec<SnapCandidatePoint>,
 	dragging_current_artboard_location: glam::IVec2,
 	draw: Resize,
+	/// Whether the document's custom export bounds are edited in place of the artboards, after being chosen from the export dialog.
+	editing_export_bounds: bool,
+	/// The custom export bounds from before the current drag, restored if it is aborted.
+	export_bounds_before_drag: Option<[DVec2; 2]>,
 }
 
 impl ArtboardToolData {
 	fn get_snap_candidates(&mut self, document: &DocumentMessageHandler, _input: &InputPreprocessorMessageHandler) {
 		self.snap_candidates.clear();
 
-		let Some(layer) = self.selected_artboard else { return };
+		let bounds = if self.editing_export_bounds {
+			Self::export_bounds(document)
+		} else {
+			let Some(layer) = self.selected_artboard else { return };
+			document.metadata().bounding_box_with_transform(layer, document.metadata().transform_to_document(layer))
+		};
 
-		if let Some(bounds) = document.metadata().bounding_box_with_transform(layer, document.metadata().transform_to_document(layer)) {
+		if let Some(bounds) = bounds {
 			snapping::get_bbox_points(Quad::from_box(bounds), &mut self.snap_candidates, snapping::BBoxSnapValues::ARTBOARD, document);
 		}
 	}
@@ -143,6 +172,42 @@ impl ArtboardToolData {
 		}
 	}
 
+	/// The document's custom export bounds, ordered as the minimum and maximum corners.
+	fn export_bounds(document: &DocumentMessageHandler) -> Option<[DVec2; 2]> {
+		document.custom_export_bounds.map(|[corner1, corner2]| [corner1.min(corner2), corner1.max(corner2)])
+	}
+
+	fn resize_export_bounds(&mut self, responses: &mut VecDeque<Message>, document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler, from_center: bool, constrain_square: bool) {
+		let Some(bounds) = &self.bounding_box_manager else {
+			return;
+		};
+		let Some(movement) = &bounds.selected_edges else {
+			return;
+		};
+
+		let center = from_center.then_some(bounds.center_of_transformation);
+		let snap = Some(SizeSnapData {
+			manager: &mut self.snap_manager,
+			points: &mut self.snap_candidates,
+			snap_data: SnapData::new(document, input),
+		});
+		let (min, size) = movement.new_size(input.mouse.position, bounds.transform, center, constrain_square, snap);
+		let max = min + size;
+
+		responses.add(DocumentMessage::SetCustomExportBounds {
+			bounds: Some([min.min(max), min.max(max)]),
+		});
+	}
+
+	/// Stops editing the custom export bounds and returns to the export dialog.
+	fn finish_editing_export_bounds(&mut self, responses: &mut VecDeque<Message>) {
+		self.editing_export_bounds = false;
+		self.bounding_box_manager.take();
+
+		responses.add(DialogMessage::RequestExportDialog);
+		responses.add(OverlaysMessage::Draw);
+	}
+
 	fn hovered_artboard(document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler) -> Option<LayerNodeIdentifier> {
 		document.click_xray(input).find(|&layer| document.network_interface.is_artboard(&layer.to_node(), &[]))
 	}
@@ -226,8 +291,13 @@ impl Fsm for ArtboardToolFsmState {
 		match (self, event) {
 			(state, ArtboardToolMessage::Overlays(mut overlay_context)) => {
 				let display_transform_cage = overlay_context.visibility_settings.transform_cage();
-				if display_transform_cage && state != ArtboardToolFsmState::Drawing {
-					if let Some(bounds) = tool_data.selected_artboard.and_then(|layer| document.metadata().bounding_box_document(layer)) {
+				let bounds = if tool_data.editing_export_bounds {
+					ArtboardToolData::export_bounds(document)
+				} else {
+					tool_data.selected_artboard.and_then(|layer| document.metadata().bounding_box_document(layer))
+				};
+				if display_transform_cage && (state != ArtboardToolFsmState::Drawing || tool_data.editing_export_bounds) {
+					if let Some(bounds) = bounds {
 						let bounding_box_manager = tool_data.bounding_box_manager.get_or_insert(BoundingBoxManager::default());
 						bounding_box_manager.bounds = bounds;
 						bounding_box_manager.transform = document.metadata().document_to_viewport;
@@ -242,6 +312,25 @@ impl Fsm for ArtboardToolFsmState {
 
 				self
 			}
+			(ArtboardToolFsmState::Ready { .. }, ArtboardToolMessage::PointerDown) if tool_data.editing_export_bounds => {
+				let to_document = document.metadata().document_to_viewport.inverse();
+				tool_data.drag_start = to_document.transform_point2(input.mouse.position);
+				tool_data.drag_current = to_document.transform_point2(input.mouse.position);
+				tool_data.export_bounds_before_drag = document.custom_export_bounds;
+
+				let inside = ArtboardToolData::export_bounds(document).is_some_and(|[min, max]| tool_data.drag_start.cmpge(min).all() && tool_data.drag_start.cmple(max).all());
+				if let Some(selected_edges) = tool_data.check_dragging_bounds(input.mouse.position) {
+					tool_data.start_resizing(selected_edges, document, input);
+					tool_data.get_snap_candidates(document, input);
+					ArtboardToolFsmState::ResizingBounds
+				} else if inside {
+					tool_data.get_snap_candidates(document, input);
+					ArtboardToolFsmState::Dragging
+				} else {
+					tool_data.draw.start(document, input);
+					ArtboardToolFsmState::Drawing
+				}
+			}
 			(ArtboardToolFsmState::Ready { .. }, ArtboardToolMessage::PointerDown) => {
 				let to_viewport = document.metadata().document_to_viewport;
 				let to_document = to_viewport.inverse();
@@ -266,7 +355,11 @@ impl Fsm for ArtboardToolFsmState {
 			(ArtboardToolFsmState::ResizingBounds, ArtboardToolMessage::PointerMove { constrain_axis_or_aspect, center }) => {
 				let from_center = input.keyboard.get(center as usize);
 				let constrain_square = input.keyboard.get(constrain_axis_or_aspect as usize);
-				tool_data.resize_artboard(responses, document, input, from_center, constrain_square);
+				if tool_data.editing_export_bounds {
+					tool_data.resize_export_bounds(responses, document, input, from_center, constrain_square);
+				} else {
+					tool_data.resize_artboard(responses, document, input, from_center, constrain_square);
+				}
 
 				// Auto-panning
 				let messages = [
@@ -281,7 +374,10 @@ impl Fsm for ArtboardToolFsmState {
 				if let Some(bounds) = &mut tool_data.bounding_box_manager {
 					let axis_align = input.keyboard.get(constrain_axis_or_aspect as usize);
 
-					let ignore = tool_data.selected_artboard.map_or(Vec::new(), |layer| vec![layer]);
+					let ignore = match tool_data.selected_artboard {
+						Some(layer) if !tool_data.editing_export_bounds => vec![layer],
+						_ => Vec::new(),
+					};
 					let snap_data = SnapData::ignore(document, input, &ignore);
 					let document_to_viewport = document.metadata().document_to_viewport;
 					let [start, current] = [tool_data.drag_start, tool_data.drag_current].map(|point| document_to_viewport.transform_point2(point));
@@ -290,6 +386,18 @@ impl Fsm for ArtboardToolFsmState {
 					let size = bounds.bounds[1] - bounds.bounds[0];
 					let position = bounds.bounds[0] + bounds.transform.inverse().transform_vector2(mouse_delta);
 
+					// Unlike artboards, the export bounds aren't rounded to whole pixels
+					if tool_data.editing_export_bounds {
+						responses.add(DocumentMessage::SetCustomExportBounds {
+							bounds: Some([position, position + size]),
+						});
+
+						tool_data.drag_current += (document_to_viewport.inverse() * bounds.transform).transform_vector2(position - bounds.bounds[0]);
+						bounds.bounds = [position, position + size];
+
+						return ArtboardToolFsmState::Dragging;
+					}
+
 					if tool_data.selected_artboard.unwrap() == LayerNodeIdentifier::ROOT_PARENT {
 						log::error!("Selected artboard cannot be ROOT_PARENT");
 						return ArtboardToolFsmState::Ready { hovered };
@@ -320,7 +428,11 @@ impl Fsm for ArtboardToolFsmState {
 				let [start, end] = tool_data.draw.calculate_points_ignore_layer(document, input, center, constrain_axis_or_aspect, true);
 				let viewport_to_document = document.metadata().document_to_viewport.inverse();
 				let [start, end] = [start, end].map(|point| viewport_to_document.transform_point2(point));
-				if let Some(artboard) = tool_data.selected_artboard {
+				if tool_data.editing_export_bounds {
+					responses.add(DocumentMessage::SetCustomExportBounds {
+						bounds: Some([start.min(end), start.max(end)]),
+					});
+				} else if let Some(artboard) = tool_data.selected_artboard {
 					assert_ne!(artboard, LayerNodeIdentifier::ROOT_PARENT, "Selected artboard cannot be ROOT_PARENT");
 
 					responses.add(GraphOperationMessage::ResizeArtboard {
@@ -406,7 +518,11 @@ impl Fsm for ArtboardToolFsmState {
 				state
 			}
 			(ArtboardToolFsmState::Drawing | ArtboardToolFsmState::ResizingBounds | ArtboardToolFsmState::Dragging, ArtboardToolMessage::PointerUp) => {
-				responses.add(DocumentMessage::EndTransaction);
+				// The export bounds aren't part of the document's history, so no transaction is open while editing them
+				if !tool_data.editing_export_bounds {
+					responses.add(DocumentMessage::EndTransaction);
+				}
+				tool_data.export_bounds_before_drag = None;
 
 				tool_data.snap_manager.cleanup(responses);
 
@@ -513,6 +629,38 @@ impl Fsm for ArtboardToolFsmState {
 
 				ArtboardToolFsmState::Ready { hovered }
 			}
+			(_, ArtboardToolMessage::EditExportBounds) => {
+				tool_data.editing_export_bounds = true;
+				if document.custom_export_bounds.is_none() {
+					responses.add(DocumentMessage::SetCustomExportBounds {
+						bounds: document.network_interface.document_bounds_document_space(true),
+					});
+				}
+				responses.add(OverlaysMessage::Draw);
+
+				ArtboardToolFsmState::Ready { hovered }
+			}
+			(ArtboardToolFsmState::Ready { .. }, ArtboardToolMessage::ConfirmExportBounds) => {
+				tool_data.finish_editing_export_bounds(responses);
+
+				ArtboardToolFsmState::Ready { hovered }
+			}
+			// Leaving and returning to the tool stops editing the export bounds
+			(ArtboardToolFsmState::Ready { .. }, ArtboardToolMessage::Abort) => {
+				tool_data.editing_export_bounds = false;
+
+				self
+			}
+			(ArtboardToolFsmState::Dragging | ArtboardToolFsmState::Drawing | ArtboardToolFsmState::ResizingBounds, ArtboardToolMessage::Abort) if tool_data.editing_export_bounds => {
+				responses.add(DocumentMessage::SetCustomExportBounds {
+					bounds: tool_data.export_bounds_before_drag.take(),
+				});
+
+				tool_data.snap_manager.cleanup(responses);
+				responses.add(OverlaysMessage::Draw);
+
+				ArtboardToolFsmState::Ready { hovered }
+			}
 			(ArtboardToolFsmState::Dragging | ArtboardToolFsmState::Drawing | ArtboardToolFsmState::ResizingBounds, ArtboardToolMessage::Abort) => {
 				responses.add(DocumentMessage::AbortTransaction);
 
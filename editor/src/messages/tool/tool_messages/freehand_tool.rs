@@ -458,6 +458,7 @@ mod test_freehand {
 					editor_position: last_initial_point,
 					mouse_keys: MouseKeys::empty(),
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::empty(),
 			)
@@ -517,6 +518,7 @@ mod test_freehand {
 					editor_position: last_extension_point,
 					mouse_keys: MouseKeys::empty(),
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::empty(),
 			)
@@ -575,6 +577,7 @@ mod test_freehand {
 					editor_position: last_initial_point,
 					mouse_keys: MouseKeys::empty(),
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::empty(),
 			)
@@ -610,6 +613,7 @@ mod test_freehand {
 					editor_position: first_second_point,
 					mouse_keys: MouseKeys::LEFT,
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::SHIFT,
 			)
@@ -626,6 +630,7 @@ mod test_freehand {
 					editor_position: last_second_point,
 					mouse_keys: MouseKeys::empty(),
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::SHIFT,
 			)
@@ -699,6 +704,7 @@ mod test_freehand {
 					editor_position: last_point,
 					mouse_keys: MouseKeys::empty(),
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::empty(),
 			)
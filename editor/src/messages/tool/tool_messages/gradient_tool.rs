@@ -759,6 +759,7 @@ mod test_gradient {
 					editor_position: end_pos,
 					mouse_keys: MouseKeys::empty(),
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::empty(),
 			)
@@ -811,6 +812,7 @@ mod test_gradient {
 					editor_position: click_position,
 					mouse_keys: MouseKeys::LEFT,
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::empty(),
 			)
@@ -825,6 +827,7 @@ mod test_gradient {
 					editor_position: drag_position,
 					mouse_keys: MouseKeys::empty(),
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::empty(),
 			)
@@ -885,6 +888,7 @@ mod test_gradient {
 					editor_position: position2,
 					mouse_keys: MouseKeys::empty(),
 					scroll_delta: ScrollDelta::default(),
+					pen: None,
 				},
 				ModifierKeys::empty(),
 			)
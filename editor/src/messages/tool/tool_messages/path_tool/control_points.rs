@@ -0,0 +1,167 @@
+use super::*;
+use crate::messages::tool::common_functionality::graph_modification_utils::NodeGraphLayer;
+use graph_craft::document::value::TaggedValue;
+
+/// The control points of a procedural layer, such as a polygon's vertices, which are stored as a `Vec<DVec2>` node parameter rather than as vector data.
+/// They are edited in place of the generated vector data by writing the changed list back to the node's input.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ControlPoints {
+	pub(super) layer: LayerNodeIdentifier,
+	pub(super) node_id: NodeId,
+	pub(super) input_index: usize,
+	/// The control points in the layer's local space.
+	pub(super) points: Vec<DVec2>,
+	/// The transform from the layer's local space to viewport space.
+	pub(super) transform: DAffine2,
+}
+
+impl ControlPoints {
+	/// Finds the first node in the layer's chain with a `Vec<DVec2>` input set to a value that isn't exposed as a connection.
+	pub(super) fn find(document: &DocumentMessageHandler, layer: LayerNodeIdentifier) -> Option<Self> {
+		let network_interface = &document.network_interface;
+		let layer_node = layer.to_node();
+
+		NodeGraphLayer::new(layer, network_interface)
+			.horizontal_layer_flow()
+			// Take until a different layer is reached
+			.take_while(|&node_id| node_id == layer_node || !network_interface.is_layer(&node_id, &[]))
+			.filter(|&node_id| node_id != layer_node)
+			.find_map(|node_id| {
+				let inputs = network_interface.document_network().nodes.get(&node_id)?.inputs.iter();
+				inputs.enumerate().find_map(|(input_index, input)| match input.as_value().filter(|_| !input.is_exposed())? {
+					TaggedValue::VecDVec2(points) => Some(Self {
+						layer,
+						node_id,
+						input_index,
+						points: points.clone(),
+						transform: document.metadata().transform_to_viewport(layer),
+					}),
+					_ => None,
+				})
+			})
+	}
+
+	/// The position of each control point in viewport space.
+	pub(super) fn viewport_positions(&self) -> impl Iterator<Item = DVec2> + '_ {
+		self.points.iter().map(|&point| self.transform.transform_point2(point))
+	}
+
+	/// The index of the control point closest to the viewport position, if one is within the selection threshold.
+	pub(super) fn hovered(&self, position: DVec2) -> Option<usize> {
+		self.viewport_positions()
+			.map(|point| point.distance_squared(position))
+			.enumerate()
+			.filter(|&(_, distance_squared)| distance_squared <= SELECTION_THRESHOLD.powi(2))
+			.min_by(|(_, a), (_, b)| a.total_cmp(b))
+			.map(|(index, _)| index)
+	}
+
+	/// Where a control point would be inserted to split the edge of the closed outline closest to the viewport position, as the index it would take and its viewport position.
+	/// This is `None` unless the edge is within the insertion distance.
+	pub(super) fn insertion(&self, position: DVec2) -> Option<(usize, DVec2)> {
+		let points = self.viewport_positions().collect::<Vec<_>>();
+		if points.len() < 2 {
+			return None;
+		}
+
+		(0..points.len())
+			.map(|index| {
+				let (start, end) = (points[index], points[(index + 1) % points.len()]);
+				let t = (position - start).dot(end - start) / (end - start).length_squared().max(f64::EPSILON);
+				let closest = start.lerp(end, t.clamp(0., 1.));
+				(index + 1, closest, closest.distance_squared(position))
+			})
+			.filter(|&(_, _, distance_squared)| distance_squared <= SEGMENT_INSERTION_DISTANCE.powi(2))
+			.min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+			.map(|(index, closest, _)| (index, closest))
+	}
+
+	/// Moves the control point to the viewport position.
+	pub(super) fn move_point(&mut self, index: usize, position: DVec2, responses: &mut VecDeque<Message>) {
+		let Some(point) = self.points.get_mut(index) else { return };
+		*point = self.transform.inverse().transform_point2(position);
+		self.write_back(responses);
+	}
+
+	/// Grows the list with a control point at the viewport position, placed at the index.
+	pub(super) fn insert_point(&mut self, index: usize, position: DVec2, responses: &mut VecDeque<Message>) {
+		self.points.insert(index.min(self.points.len()), self.transform.inverse().transform_point2(position));
+		self.write_back(responses);
+	}
+
+	/// Shrinks the list by removing the control point, as long as another is left for the node to generate its shape from.
+	pub(super) fn delete_point(&mut self, index: usize, responses: &mut VecDeque<Message>) -> bool {
+		if index >= self.points.len() || self.points.len() <= 1 {
+			return false;
+		}
+		self.points.remove(index);
+		self.write_back(responses);
+		true
+	}
+
+	fn write_back(&self, responses: &mut VecDeque<Message>) {
+		responses.add(NodeGraphMessage::SetInputValue {
+			node_id: self.node_id,
+			input_index: self.input_index,
+			value: TaggedValue::VecDVec2(self.points.clone()),
+		});
+	}
+}
+
+/// A control point being dragged, which follows the cursor's movement from where it was grabbed.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ControlPointDrag {
+	/// The control points as last written back, so the drag is drawn before the document is updated.
+	pub(super) control_points: ControlPoints,
+	pub(super) index: usize,
+	/// The viewport positions of the control point and the cursor when it was grabbed.
+	start: (DVec2, DVec2),
+}
+
+impl ControlPointDrag {
+	pub(super) fn new(control_points: ControlPoints, index: usize, mouse_position: DVec2) -> Option<Self> {
+		let point = control_points.viewport_positions().nth(index)?;
+		Some(Self {
+			control_points,
+			index,
+			start: (point, mouse_position),
+		})
+	}
+
+	pub(super) fn drag_to(&mut self, mouse_position: DVec2, responses: &mut VecDeque<Message>) {
+		let (point, mouse_start) = self.start;
+		self.control_points.move_point(self.index, point + mouse_position - mouse_start, responses);
+	}
+}
+
+/// The control points of the selected layers that can be edited.
+pub(super) fn selected_control_points(document: &DocumentMessageHandler) -> Vec<ControlPoints> {
+	document
+		.network_interface
+		.selected_nodes()
+		.selected_layers(document.metadata())
+		.filter(|&layer| !ShapeState::layer_edit_locked(&document.network_interface, layer))
+		.filter_map(|layer| ControlPoints::find(document, layer))
+		.collect()
+}
+
+/// The control points with a point closest to the viewport position, along with that point's index, if one is within the selection threshold.
+pub(super) fn hovered_control_point(document: &DocumentMessageHandler, position: DVec2) -> Option<(ControlPoints, usize)> {
+	selected_control_points(document)
+		.into_iter()
+		.filter_map(|control_points| {
+			let index = control_points.hovered(position)?;
+			let distance_squared = control_points.viewport_positions().nth(index)?.distance_squared(position);
+			Some((distance_squared, control_points, index))
+		})
+		.min_by(|(a, _, _), (b, _, _)| a.total_cmp(b))
+		.map(|(_, control_points, index)| (control_points, index))
+}
+
+/// The control points with an edge closest to the viewport position, along with where a point would be inserted, if one is within the insertion distance.
+pub(super) fn control_point_insertion(document: &DocumentMessageHandler, position: DVec2) -> Option<(ControlPoints, usize, DVec2)> {
+	selected_control_points(document).into_iter().find_map(|control_points| {
+		let (index, insertion) = control_points.insertion(position)?;
+		Some((control_points, index, insertion))
+	})
+}
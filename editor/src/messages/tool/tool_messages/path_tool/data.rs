@@ -0,0 +1,1456 @@
+use super::*;
+
+/// A selection over too many points to be applied at once, which is instead scanned across several frames and only applied once the scan finishes.
+pub(super) struct ChunkedSelection {
+	pub(super) operation: ChunkedOperation<SelectionScan, SelectionScanHits>,
+	pub(super) selection_change: SelectionChange,
+	/// Whether the scanned layers become the selected layers, as when selecting every anchor in the document.
+	pub(super) select_scanned_layers: bool,
+}
+
+/// Consecutive arrow key nudges in one direction, which are accelerated while the key is held and undone as a single step.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct NudgeSession {
+	pub(super) direction: DVec2,
+	pub(super) repeats: u32,
+	pub(super) last_nudge_time: u64,
+	/// Whether the session started its own transaction, rather than nudging within one already opened by a drag.
+	pub(super) owns_transaction: bool,
+}
+
+/// The route last selected between two anchors, which is highlighted for a moment so it's clear which way around the path it went.
+pub(super) struct RouteHighlight {
+	pub(super) layer: LayerNodeIdentifier,
+	pub(super) route: AnchorRoute,
+	pub(super) shown_at: u64,
+}
+
+/// The measurements shown beside the cursor while dragging points, with any snapped or locked angle and length already applied.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum DragReadout {
+	/// The dragged handle's angle in degrees, measured from the chosen reference direction, and its length in document space.
+	Handle { degrees: f64, length: f64 },
+	/// How far, in document space, the dragged points have moved since the drag began.
+	Points { delta: DVec2 },
+}
+
+/// A segment pressed on without a point nearby, which has a point inserted on it when released, or is moved by its two anchors if dragged first.
+/// With the delete segment modifier held at the press, it's instead deleted when released, or bent through the cursor if dragged first.
+pub(super) struct SegmentDrag {
+	pub(super) segment: ClosestSegment,
+	pub(super) extend_selection: bool,
+	/// Whether the delete segment modifier was held at the press.
+	pub(super) bend: bool,
+	/// The selection from before the press, restored once the segment's anchors, which are selected while dragging, have been moved.
+	pub(super) saved_selection: Vec<ManipulatorPointId>,
+	/// The middle of the segment in document space when it was pressed, which is what snaps while dragging.
+	pub(super) midpoint: DVec2,
+	/// How far the anchors have been moved in document space, or `None` until the cursor has moved far enough for the press to become a drag.
+	pub(super) offset: Option<DVec2>,
+}
+
+/// The multiplier for a nudge repeated `repeats` times in a row, which doubles after each run of [`PATH_NUDGE_ACCELERATION_STEP`] repeats up to `cap`.
+pub(super) fn nudge_acceleration(repeats: u32, cap: f64) -> f64 {
+	let doublings = (repeats / PATH_NUDGE_ACCELERATION_STEP).min(16) as i32;
+	2_f64.powi(doublings).min(cap.max(1.))
+}
+
+#[derive(Default)]
+pub(super) struct PathToolData {
+	pub(super) snap_manager: SnapManager,
+	pub(super) lasso_polygon: Vec<DVec2>,
+	/// The points selected before the current box or lasso selection began, which are selected again if it's cancelled.
+	pub(super) selection_before_marquee: Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>,
+	/// How the box or lasso selection changes the selection with the modifiers currently held, which its overlay shows before it's applied.
+	pub(super) marquee_selection_change: SelectionChange,
+	pub(super) selection_mode: Option<SelectionMode>,
+	pub(super) drag_start_pos: DVec2,
+	pub(super) previous_mouse_position: DVec2,
+	/// The point grabbed at the start of the current drag, used as the snap source
+	pub(super) grabbed_point: Option<ManipulatorPointInfo>,
+	pub(super) toggle_colinear_debounce: bool,
+	pub(super) opposing_handle_lengths: Option<OpposingHandleLengths>,
+	/// Describes information about the selected point(s), if any, across one or multiple shapes and manipulator point types (anchor or handle).
+	/// The available information varies depending on whether `None`, `One`, or `Multiple` points are currently selected.
+	/// NOTE: It must be updated using `update_selection_status` to ensure `can_toggle_colinearity` and `can_fillet` stay synchronized with the current selection.
+	pub(super) selection_status: SelectionStatus,
+	/// `true` if we can change the current selection to colinear or not.
+	pub(super) can_toggle_colinearity: bool,
+	/// `true` if a selected anchor is joined to two segments, so the selected corners can be rounded.
+	pub(super) can_fillet: bool,
+	pub(super) segment: Option<ClosestSegment>,
+	/// The overlapping segments offered so far by cycling through those under the cursor, which are skipped when finding the next one.
+	pub(super) cycled_segments: Vec<(LayerNodeIdentifier, SegmentId)>,
+	/// Where the cursor was when cycling through overlapping segments began.
+	pub(super) segment_cycle_origin: DVec2,
+	/// How many segments overlap under the cursor where cycling began.
+	pub(super) segment_cycle_count: usize,
+	/// The hover and selection context of the Ready hints last sent, so they are only sent again once it changes, or `None` if other hints have replaced them.
+	pub(super) ready_hint_context: Option<ReadyHintContext>,
+	/// The width handle being dragged, holding the width last set from the drag.
+	pub(super) width_drag: Option<WidthHandle>,
+	/// The segment pressed on, until it's released or its drag ends.
+	pub(super) segment_drag: Option<SegmentDrag>,
+	/// The control point of a procedural layer being dragged, holding the control points last written back by the drag.
+	pub(super) control_point_drag: Option<ControlPointDrag>,
+	/// The control point of a procedural layer last clicked, which is removed by deleting.
+	pub(super) selected_control_point: Option<(LayerNodeIdentifier, usize)>,
+	pub(super) snap_cache: SnapCache,
+	pub(super) double_click_handled: bool,
+	pub(super) delete_segment_pressed: bool,
+	pub(super) auto_panning: AutoPanning,
+	pub(super) saved_points_before_anchor_select_toggle: Vec<ManipulatorPointId>,
+	pub(super) select_anchor_toggled: bool,
+	pub(super) saved_points_before_handle_drag: Vec<ManipulatorPointId>,
+	pub(super) handle_drag_toggle: bool,
+	pub(super) dragging_state: DraggingState,
+	pub(super) current_selected_handle_id: Option<ManipulatorPointId>,
+	/// The handle under the cursor when the drag began, whose polar change is applied to all selected handles when dragging them in parallel.
+	pub(super) parallel_drag_handle: Option<(LayerNodeIdentifier, ManipulatorPointId)>,
+	pub(super) angle: f64,
+	/// The point under the cursor while ready, which is highlighted as what a click would select.
+	pub(super) hovered_point: Option<(LayerNodeIdentifier, ManipulatorPointId)>,
+	/// The anchor last moved to by selecting the next point, which the one after it is found from while it stays selected.
+	pub(super) traversed_point: Option<(LayerNodeIdentifier, ManipulatorPointId)>,
+	/// The point whose X or Y field was last given an expression which couldn't be evaluated, until that field is given a valid one.
+	pub(super) rejected_coordinate: Option<(LayerNodeIdentifier, ManipulatorPointId, AlignAxis)>,
+	/// The handle being dragged and the document space angle its snapped angle is measured from, found once at the start of the drag.
+	pub(super) angle_reference: Option<(ManipulatorPointId, f64)>,
+	/// The measurements of the current drag shown beside the cursor, until the drag stops.
+	pub(super) drag_readout: Option<DragReadout>,
+	/// How far, in document space, the selected points have been moved since the drag began.
+	pub(super) dragged_delta: DVec2,
+	pub(super) opposite_handle_position: Option<DVec2>,
+	pub(super) last_clicked_point_was_selected: bool,
+	pub(super) snapping_axis: Option<Axis>,
+	pub(super) alt_clicked_on_anchor: bool,
+	pub(super) alt_dragging_from_anchor: bool,
+	pub(super) angle_locked: bool,
+	/// The handle whose length is kept while the lock length modifier is held, along with that length in layer space.
+	pub(super) locked_handle_length: Option<(ManipulatorPointId, f64)>,
+	pub(super) temporary_colinear_handles: bool,
+	pub(super) offset_preview_active: bool,
+	pub(super) chunked_selection: Option<ChunkedSelection>,
+	/// Set once the cursor has moved too far from where the marquee began for it to still be promoted into a point drag.
+	pub(super) drag_promotion_expired: bool,
+	pub(super) nudge_session: Option<NudgeSession>,
+	/// Whether a transaction started by this tool is still waiting for its matching end or abort.
+	pub(super) transaction_open: bool,
+	/// A copy of the document's snapping settings shown in the tool options.
+	pub(super) snapping_state: SnappingState,
+	/// A copy of the preferred number of decimal places that coordinates and angles are shown with.
+	pub(super) display_decimal_places: u32,
+	/// The shape copied from a segment, which is applied to each segment clicked until the copy mode is exited.
+	pub(super) segment_shape_source: Option<NormalizedSegmentShape>,
+	/// Whether the next click samples the position of an anchor instead of editing the selection.
+	pub(super) sampling_position: bool,
+	/// The document space position of the last sampled anchor, shown in the X/Y fields until it is applied.
+	pub(super) sampled_position: Option<DVec2>,
+	/// The messages processed while gesture recording is enabled, since the recording began or was last exported.
+	pub(super) gesture_recording: Option<GestureRecording>,
+	/// A gesture recording being replayed across animation frames.
+	pub(super) gesture_replay: Option<GestureReplay>,
+	/// The route last selected between two anchors, while it's still highlighted.
+	pub(super) route_highlight: Option<RouteHighlight>,
+	/// The document space position placed to rotate and scale the selected points about with R and S, instead of the middle of the selection.
+	pub(super) custom_pivot: Option<DVec2>,
+	/// The points last copied, which can be pasted into any layer.
+	pub(super) copied_points: Option<CopiedPoints>,
+}
+
+impl PathToolData {
+	pub(super) fn save_points_before_anchor_toggle(&mut self, points: Vec<ManipulatorPointId>) -> PathToolFsmState {
+		self.saved_points_before_anchor_select_toggle = points;
+		PathToolFsmState::Dragging(self.dragging_state)
+	}
+
+	/// Starts a transaction unless one is already open, in which case the open one also covers the upcoming changes.
+	pub(super) fn start_transaction(&mut self, responses: &mut VecDeque<Message>) {
+		if !self.transaction_open {
+			self.transaction_open = true;
+			responses.add(DocumentMessage::StartTransaction);
+		}
+	}
+
+	/// Ends the open transaction, if any, committing it as an undo step when the document was modified.
+	pub(super) fn end_transaction(&mut self, responses: &mut VecDeque<Message>) {
+		if std::mem::take(&mut self.transaction_open) {
+			responses.add(DocumentMessage::EndTransaction);
+		}
+	}
+
+	/// Aborts the open transaction, if any, reverting the changes made since it started.
+	pub(super) fn abort_transaction(&mut self, responses: &mut VecDeque<Message>) {
+		if std::mem::take(&mut self.transaction_open) {
+			responses.add(DocumentMessage::AbortTransaction);
+		}
+	}
+
+	pub(super) fn remove_saved_points(&mut self) {
+		self.saved_points_before_anchor_select_toggle.clear();
+	}
+
+	/// Selects the points inside the drawn box or lasso. Selections over many points are split into chunks which are processed on each animation frame.
+	pub(super) fn select_in_shape(
+		&mut self,
+		shape_editor: &mut ShapeState,
+		document: &DocumentMessageHandler,
+		selection_shape: SelectionShapeType,
+		selection_change: SelectionChange,
+		tool_options: &PathToolOptions,
+		responses: &mut VecDeque<Message>,
+	) {
+		self.cancel_chunked_selection(responses);
+
+		let selection_shape = match selection_shape {
+			SelectionShapeType::Box => SelectionShape::Box([self.drag_start_pos, self.previous_mouse_position]),
+			SelectionShapeType::Lasso => SelectionShape::Lasso(&self.lasso_polygon),
+		};
+		let Some(hit_tester) = SelectionHitTester::new(selection_shape) else {
+			shape_editor.apply_shape_selection(&[], selection_change);
+			return;
+		};
+
+		// Only the handles shown by the overlay mode can be grabbed, unless only anchors are to be selected
+		let handles = match tool_options.path_overlay_mode {
+			_ if tool_options.marquee_anchors_only || !document.overlays_visibility_settings.handles() => SelectionHandles::None,
+			PathOverlayMode::AllHandles => SelectionHandles::All,
+			PathOverlayMode::SelectedPointHandles | PathOverlayMode::FrontierHandles => SelectionHandles::OfSegments(selected_segments(document, shape_editor).into_iter().collect()),
+		};
+		let scan = shape_editor.selection_scan(SelectionScanTarget::Shape { hit_tester, handles });
+		self.start_chunked_selection(scan, selection_change, false, shape_editor, document, responses);
+	}
+
+	/// Begins a selection which scans for its points in chunks of [`PATH_SELECTION_CHUNK_SIZE`], continuing on each animation frame until the scan finishes.
+	/// The first chunk is scanned straight away, so a selection over few enough points is applied immediately.
+	pub(super) fn start_chunked_selection(
+		&mut self,
+		scan: SelectionScan,
+		selection_change: SelectionChange,
+		select_scanned_layers: bool,
+		shape_editor: &mut ShapeState,
+		document: &DocumentMessageHandler,
+		responses: &mut VecDeque<Message>,
+	) {
+		self.cancel_chunked_selection(responses);
+
+		let operation = ChunkedOperation::start(
+			scan,
+			PATH_SELECTION_CHUNK_SIZE,
+			SelectionScanHits::default(),
+			PathToolMessage::ContinueChunkedSelection.into(),
+			responses,
+		);
+		self.chunked_selection = Some(ChunkedSelection {
+			operation,
+			selection_change,
+			select_scanned_layers,
+		});
+		self.continue_chunked_selection(shape_editor, document, responses);
+	}
+
+	/// Scans the next chunk of the ongoing chunked selection, applying the selection only once the scan has finished. Returns whether it finished.
+	pub(super) fn continue_chunked_selection(&mut self, shape_editor: &mut ShapeState, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) -> bool {
+		let Some(selection) = self.chunked_selection.as_mut() else { return false };
+		let network_interface = &document.network_interface;
+		if !selection.operation.process_chunk(|scan, hits| scan.step(network_interface, hits)) {
+			return false;
+		}
+
+		let selection = self.chunked_selection.take().expect("The chunked selection was just processed");
+		if let Some(hits) = selection.operation.finish(responses) {
+			if selection.select_scanned_layers {
+				shape_editor.set_selected_layers(hits.layers.clone());
+				responses.add(NodeGraphMessage::SelectedNodesSet {
+					nodes: hits.layers.iter().map(|layer| layer.to_node()).collect(),
+				});
+			}
+			shape_editor.apply_shape_selection(&hits.points, selection.selection_change);
+		}
+
+		responses.add(OverlaysMessage::Draw);
+		responses.add(PathToolMessage::SelectedPointUpdated);
+		true
+	}
+
+	/// Pastes the copied points into the first selected layer which can be edited, moved by `offset` in document space, as one undoable step.
+	pub(super) fn paste_points(&self, shape_editor: &mut ShapeState, document: &DocumentMessageHandler, offset: DVec2, responses: &mut VecDeque<Message>) {
+		let Some(copied) = &self.copied_points else { return };
+		let network_interface = &document.network_interface;
+		let mut layers = network_interface.selected_nodes().selected_layers(document.metadata());
+		let Some(layer) = layers.find(|&layer| !ShapeState::layer_edit_locked(network_interface, layer) && network_interface.compute_modified_vector(layer).is_some()) else {
+			return;
+		};
+
+		responses.add(DocumentMessage::StartTransaction);
+		shape_editor.paste_points(document, layer, copied, offset, responses);
+		responses.add(DocumentMessage::EndTransaction);
+		responses.add(PathToolMessage::SelectedPointUpdated);
+		responses.add(OverlaysMessage::Draw);
+	}
+
+	/// Cancels the box or lasso selection, along with the chunked selection it may have started, selecting the points from before it again.
+	pub(super) fn cancel_marquee(&mut self, shape_editor: &mut ShapeState, responses: &mut VecDeque<Message>) {
+		self.cancel_chunked_selection(responses);
+		self.lasso_polygon.clear();
+
+		shape_editor.deselect_all_points();
+		for (layer, points) in std::mem::take(&mut self.selection_before_marquee) {
+			let Some(state) = shape_editor.selected_shape_state.get_mut(&layer) else { continue };
+			for point in points {
+				state.select_point(point);
+			}
+		}
+
+		responses.add(PathToolMessage::SelectedPointUpdated);
+		responses.add(OverlaysMessage::Draw);
+	}
+
+	pub(super) fn cancel_chunked_selection(&mut self, responses: &mut VecDeque<Message>) {
+		if let Some(selection) = self.chunked_selection.take() {
+			selection.operation.cancel(responses);
+		}
+	}
+
+	pub fn selection_quad(&self) -> Quad {
+		let bbox = self.selection_box();
+		Quad::from_box(bbox)
+	}
+
+	pub fn calculate_selection_mode_from_direction(&mut self) -> SelectionMode {
+		let bbox = self.selection_box();
+		let above_threshold = bbox[1].distance_squared(bbox[0]) > DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD.powi(2);
+
+		if self.selection_mode.is_none() && above_threshold {
+			let mode = if bbox[1].x < bbox[0].x {
+				SelectionMode::Touched
+			} else {
+				// This also covers the case where they're equal: the area is zero, so we use `Enclosed` to ensure the selection ends up empty, as nothing will be enclosed by an empty area
+				SelectionMode::Enclosed
+			};
+			self.selection_mode = Some(mode);
+		}
+
+		self.selection_mode.unwrap_or(SelectionMode::Touched)
+	}
+
+	pub fn selection_box(&self) -> [DVec2; 2] {
+		if self.previous_mouse_position == self.drag_start_pos {
+			let tolerance = DVec2::splat(SELECTION_TOLERANCE);
+			[self.drag_start_pos - tolerance, self.drag_start_pos + tolerance]
+		} else {
+			[self.drag_start_pos, self.previous_mouse_position]
+		}
+	}
+
+	pub(super) fn update_selection_status(&mut self, shape_editor: &mut ShapeState, document: &DocumentMessageHandler) {
+		let selection_status = get_selection_status(&document.network_interface, shape_editor);
+
+		self.can_toggle_colinearity = match &selection_status {
+			SelectionStatus::None => false,
+			SelectionStatus::One(single_selected_point) => {
+				let vector_data = document.network_interface.compute_modified_vector(single_selected_point.layer).unwrap();
+				single_selected_point.id.get_handle_pair(&vector_data).is_some()
+			}
+			SelectionStatus::Multiple(_) => true,
+		};
+		self.can_fillet = shape_editor.has_fillet_corner(&document.network_interface);
+		// The pivot is placed for transforming the current selection, so it's forgotten along with it
+		if matches!(selection_status, SelectionStatus::None) {
+			self.custom_pivot = None;
+		}
+		self.selection_status = selection_status;
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn mouse_down(
+		&mut self,
+		shape_editor: &mut ShapeState,
+		document: &DocumentMessageHandler,
+		input: &InputPreprocessorMessageHandler,
+		responses: &mut VecDeque<Message>,
+		extend_selection: bool,
+		lasso_select: bool,
+		handle_drag_from_anchor: bool,
+	) -> PathToolFsmState {
+		self.double_click_handled = false;
+		self.opposing_handle_lengths = None;
+		self.locked_handle_length = None;
+		self.cycled_segments.clear();
+		self.hovered_point = None;
+
+		self.drag_start_pos = input.mouse.position;
+
+		let old_selection = shape_editor.selected_points().cloned().collect::<Vec<_>>();
+
+		// Check if the point is already selected; if not, select the first point within the threshold (in pixels)
+		if let Some((already_selected, mut selection_info)) = shape_editor.get_point_selection_state(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD) {
+			self.start_transaction(responses);
+
+			self.last_clicked_point_was_selected = already_selected;
+			self.parallel_drag_handle = shape_editor
+				.find_nearest_point_indices(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD)
+				.filter(|(_, point)| point.as_handle().is_some());
+
+			// If the point is already selected and shift (`extend_selection`) is used, keep the selection unchanged.
+			// Otherwise, select the first point within the threshold.
+			if !(already_selected && extend_selection) {
+				if let Some(updated_selection_info) = shape_editor.change_point_selection(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD, extend_selection) {
+					selection_info = updated_selection_info;
+				}
+			}
+
+			if let Some(selected_points) = selection_info {
+				self.drag_start_pos = input.mouse.position;
+
+				// If selected points contain only handles and there was some selection before, then it is stored and becomes restored upon release
+				let mut dragging_only_handles = true;
+				for point in &selected_points.points {
+					if matches!(point.point_id, ManipulatorPointId::Anchor(_)) {
+						dragging_only_handles = false;
+						break;
+					}
+				}
+				if dragging_only_handles && !self.handle_drag_toggle && !old_selection.is_empty() {
+					self.saved_points_before_handle_drag = old_selection;
+				}
+
+				if handle_drag_from_anchor {
+					if let Some((layer, point)) = shape_editor.find_nearest_point_indices(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD) {
+						// Check that selected point is an anchor
+						if let (Some(point_id), Some(vector_data)) = (point.as_anchor(), document.network_interface.compute_modified_vector(layer)) {
+							let handles = vector_data.all_connected(point_id).collect::<Vec<_>>();
+							self.alt_clicked_on_anchor = true;
+							ShapeState::retract_handles(layer, &vector_data, &handles, responses);
+
+							let manipulator_point_id = handles[0].to_manipulator_point();
+							shape_editor.deselect_all_points();
+							shape_editor.select_points_by_manipulator_id(&vec![manipulator_point_id]);
+							responses.add(PathToolMessage::SelectedPointUpdated);
+						}
+					}
+				}
+
+				self.start_dragging_point(selected_points, input.mouse.position, document, shape_editor);
+				responses.add(OverlaysMessage::Draw);
+			}
+			PathToolFsmState::Dragging(self.dragging_state)
+		}
+		// The point nearby belongs to an edit locked layer, so explain why it can't be dragged
+		else if shape_editor
+			.find_nearest_edit_locked_layer(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD)
+			.is_some()
+		{
+			let hint_data = HintData(vec![HintGroup(vec![HintInfo::label(
+				"This layer's points are edit locked. Toggle its edit lock in the Layers panel to modify them.",
+			)])]);
+			responses.add(FrontendMessage::UpdateInputHints { hint_data });
+
+			PathToolFsmState::Ready
+		}
+		// We didn't find a point nearby, so we will see if there is a segment to insert a point on or drag
+		else if let Some(closed_segment) = self.segment.take() {
+			self.start_transaction(responses);
+
+			if let Some(shape) = &self.segment_shape_source {
+				closed_segment.apply_normalized_shape(shape, responses);
+			} else {
+				// Whether the segment is changed or dragged is decided once it's known if the cursor moves before being released
+				self.segment_drag = Some(SegmentDrag {
+					midpoint: closed_segment.midpoint_in_document(document.metadata()),
+					segment: closed_segment,
+					extend_selection,
+					bend: self.delete_segment_pressed,
+					saved_selection: old_selection,
+					offset: None,
+				});
+				return PathToolFsmState::DraggingSegment;
+			}
+
+			self.end_transaction(responses);
+
+			PathToolFsmState::Ready
+		}
+		// We didn't find a segment, so consider selecting the nearest shape instead
+		else if let Some(layer) = document.click(input) {
+			shape_editor.deselect_all_points();
+			if extend_selection {
+				responses.add(NodeGraphMessage::SelectedNodesAdd { nodes: vec![layer.to_node()] });
+			} else {
+				responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![layer.to_node()] });
+			}
+			self.drag_start_pos = input.mouse.position;
+			self.previous_mouse_position = document.metadata().document_to_viewport.inverse().transform_point2(input.mouse.position);
+
+			self.start_transaction(responses);
+
+			PathToolFsmState::Dragging(self.dragging_state)
+		}
+		// Start drawing
+		else {
+			self.drag_start_pos = input.mouse.position;
+			self.previous_mouse_position = document.metadata().document_to_viewport.inverse().transform_point2(input.mouse.position);
+			self.drag_promotion_expired = false;
+			self.selection_before_marquee = shape_editor.selected_points_by_layer();
+			self.marquee_selection_change = SelectionChange::from_modifiers(extend_selection, false);
+
+			let selection_shape = if lasso_select { SelectionShapeType::Lasso } else { SelectionShapeType::Box };
+			PathToolFsmState::Drawing { selection_shape }
+		}
+	}
+
+	/// Moves the anchors of the pressed segment along with the cursor, with the segment's middle snapped, once the cursor has moved far enough for the press to become a drag.
+	/// The anchors are selected for the duration of the drag so they move, along with their handles, like any other dragged selection.
+	/// If the segment is being bent instead, its handles are changed so it passes through the cursor where it was pressed.
+	pub(super) fn drag_segment(&mut self, shape_editor: &mut ShapeState, document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
+		let Some(segment_drag) = &mut self.segment_drag else { return };
+		if segment_drag.offset.is_none() && self.drag_start_pos.distance(input.mouse.position) <= DRAG_THRESHOLD {
+			return;
+		}
+
+		let document_to_viewport = document.metadata().document_to_viewport;
+		if segment_drag.bend {
+			let target = document_to_viewport.inverse().transform_point2(input.mouse.position);
+			segment_drag.segment.bend_through(target, document, responses);
+			segment_drag.offset = Some(target - segment_drag.midpoint);
+			return;
+		}
+
+		let (layer, midpoint) = (segment_drag.segment.layer(), segment_drag.midpoint);
+		let offset = match segment_drag.offset {
+			Some(offset) => offset,
+			None => {
+				let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { return };
+				let points = segment_drag.segment.points().map(|point| ManipulatorPointInfo {
+					layer,
+					point_id: ManipulatorPointId::Anchor(point),
+				});
+
+				shape_editor.deselect_all_points();
+				let state = shape_editor.selected_shape_state.entry(layer).or_default();
+				for point in points {
+					state.select_point(point.point_id);
+				}
+				let selected_points = SelectedPointsInfo {
+					points: points.to_vec(),
+					grabbed: points[0],
+					vector_data,
+				};
+				self.start_dragging_point(selected_points, self.drag_start_pos, document, shape_editor);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				DVec2::ZERO
+			}
+		};
+
+		// The anchors themselves are left out of the snap targets, since they are what's moving
+		let target = midpoint + document_to_viewport.inverse().transform_vector2(input.mouse.position - self.drag_start_pos);
+		let snap_data = SnapData::new_snap_cache(document, input, &self.snap_cache);
+		let snap_result = self.snap_manager.free_snap(&snap_data, &SnapCandidatePoint::handle(target), Default::default());
+		self.snap_manager.update_indicator(snap_result.clone());
+
+		let delta = document_to_viewport.transform_vector2(snap_result.snapped_point_document - midpoint - offset);
+		report_dirty_region(shape_editor, document, delta, responses);
+		shape_editor.move_selected_points(None, document, delta, false, true, false, None, false, responses);
+		if let Some(segment_drag) = &mut self.segment_drag {
+			segment_drag.offset = Some(snap_result.snapped_point_document - midpoint);
+		}
+	}
+
+	/// Ends the press on a segment, inserting a point where it was pressed (or deleting the segment) if it wasn't dragged, or otherwise restoring the selection from before the drag.
+	pub(super) fn finish_segment_drag(&mut self, shape_editor: &mut ShapeState, document: &DocumentMessageHandler, new_point_type: NewPointType, responses: &mut VecDeque<Message>) {
+		let Some(segment_drag) = self.segment_drag.take() else { return };
+		let segment = &segment_drag.segment;
+		if segment_drag.offset.is_some() {
+			shape_editor.deselect_all_points();
+			shape_editor.select_points_by_manipulator_id(&segment_drag.saved_selection);
+		} else if segment_drag.bend {
+			if let Some(vector_data) = document.network_interface.compute_modified_vector(segment.layer()) {
+				shape_editor.dissolve_segment(responses, segment.layer(), &vector_data, segment.segment(), segment.points());
+			}
+		} else {
+			segment.adjusted_insert_and_select(shape_editor, document, new_point_type, responses, segment_drag.extend_selection);
+		}
+		responses.add(PathToolMessage::SelectedPointUpdated);
+	}
+
+	/// Cancels the press on a segment, restoring the selection from before it was dragged.
+	pub(super) fn cancel_segment_drag(&mut self, shape_editor: &mut ShapeState) {
+		let Some(segment_drag) = self.segment_drag.take() else { return };
+		if segment_drag.offset.is_some() {
+			shape_editor.deselect_all_points();
+			shape_editor.select_points_by_manipulator_id(&segment_drag.saved_selection);
+		}
+	}
+
+	/// Begins dragging the selection so the grabbed point follows the cursor's movement from `drag_origin` (in viewport space).
+	pub(super) fn start_dragging_point(&mut self, selected_points: SelectedPointsInfo, drag_origin: DVec2, document: &DocumentMessageHandler, shape_editor: &mut ShapeState) {
+		let mut manipulators = HashMap::with_hasher(NoHashBuilder);
+		let mut unselected = Vec::new();
+		for (&layer, state) in &shape_editor.selected_shape_state {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else {
+				continue;
+			};
+			let transform = document.metadata().transform_to_document(layer);
+
+			let mut layer_manipulators = HashSet::with_hasher(NoHashBuilder);
+			for point in state.selected() {
+				let Some(anchor) = point.get_anchor(&vector_data) else { continue };
+				layer_manipulators.insert(anchor);
+				let Some([handle1, handle2]) = point.get_handle_pair(&vector_data) else { continue };
+				let Some(handle) = point.as_handle() else { continue };
+				// Check which handle is selected and which is opposite
+				let opposite = if handle == handle1 { handle2 } else { handle1 };
+
+				self.opposite_handle_position = if self.opposite_handle_position.is_none() {
+					opposite.to_manipulator_point().get_position(&vector_data)
+				} else {
+					self.opposite_handle_position
+				};
+			}
+			for (&id, &position) in vector_data.point_domain.ids().iter().zip(vector_data.point_domain.positions()) {
+				if layer_manipulators.contains(&id) {
+					continue;
+				}
+				unselected.push(SnapCandidatePoint::alignment_anchor(transform.transform_point2(position)))
+			}
+			if !layer_manipulators.is_empty() {
+				manipulators.insert(layer, layer_manipulators);
+			}
+		}
+		self.snap_cache = SnapCache { manipulators, unselected };
+
+		// Alt-dragging a handle out of an anchor changes the selection, so only snap from the grabbed point while it's still selected
+		let grabbed = selected_points.grabbed;
+		let grabbed_is_selected = shape_editor.selected_shape_state.get(&grabbed.layer).is_some_and(|state| state.is_selected(grabbed.point_id));
+		self.grabbed_point = grabbed_is_selected.then_some(grabbed);
+		self.angle_reference = None;
+		self.drag_readout = None;
+		self.dragged_delta = DVec2::ZERO;
+
+		let viewport_to_document = document.metadata().document_to_viewport.inverse();
+		self.previous_mouse_position = viewport_to_document.transform_point2(drag_origin);
+	}
+
+	/// Scales the nudge `delta` by how long its arrow key has been held, starting a new undoable session if the direction changed or the key was released.
+	pub(super) fn accelerated_nudge(&mut self, delta: DVec2, time: u64, acceleration_cap: f64, responses: &mut VecDeque<Message>) -> DVec2 {
+		let direction = delta.normalize_or_zero();
+
+		match &mut self.nudge_session {
+			Some(session) if session.direction == direction && time.saturating_sub(session.last_nudge_time) <= PATH_NUDGE_SESSION_TIMEOUT => {
+				// Only nudges arriving at the key repeat rate accelerate, so separate taps keep moving by the base amount
+				let repeated = time.saturating_sub(session.last_nudge_time) <= PATH_NUDGE_REPEAT_INTERVAL;
+				session.repeats = if repeated { session.repeats + 1 } else { 0 };
+				session.last_nudge_time = time;
+			}
+			_ => {
+				self.end_nudge_session(responses);
+
+				let owns_transaction = !self.transaction_open;
+				self.start_transaction(responses);
+				responses.add(BroadcastMessage::SubscribeEvent {
+					on: BroadcastEvent::AnimationFrame,
+					send: Box::new(PathToolMessage::NudgeTimeout.into()),
+				});
+				self.nudge_session = Some(NudgeSession {
+					direction,
+					repeats: 0,
+					last_nudge_time: time,
+					owns_transaction,
+				});
+			}
+		}
+
+		let repeats = self.nudge_session.map_or(0, |session| session.repeats);
+		delta * nudge_acceleration(repeats, acceleration_cap)
+	}
+
+	/// Commits the moves of the current nudge session as one undo step.
+	pub(super) fn end_nudge_session(&mut self, responses: &mut VecDeque<Message>) {
+		let Some(session) = self.nudge_session.take() else { return };
+
+		responses.add(BroadcastMessage::UnsubscribeEvent {
+			on: BroadcastEvent::AnimationFrame,
+			message: Box::new(PathToolMessage::NudgeTimeout.into()),
+		});
+		if session.owns_transaction {
+			self.end_transaction(responses);
+		}
+	}
+
+	/// Highlights the route just selected between two anchors until [`PATH_ROUTE_HIGHLIGHT_DURATION`] has passed, replacing any earlier highlight.
+	pub(super) fn show_route_highlight(&mut self, layer: LayerNodeIdentifier, route: AnchorRoute, time: u64, responses: &mut VecDeque<Message>) {
+		if self.route_highlight.is_none() {
+			responses.add(BroadcastMessage::SubscribeEvent {
+				on: BroadcastEvent::AnimationFrame,
+				send: Box::new(PathToolMessage::RouteHighlightTimeout.into()),
+			});
+		}
+		self.route_highlight = Some(RouteHighlight { layer, route, shown_at: time });
+	}
+
+	/// Removes the highlight of the route selected between two anchors, if it's still shown.
+	pub(super) fn end_route_highlight(&mut self, responses: &mut VecDeque<Message>) {
+		if self.route_highlight.take().is_none() {
+			return;
+		}
+
+		responses.add(BroadcastMessage::UnsubscribeEvent {
+			on: BroadcastEvent::AnimationFrame,
+			message: Box::new(PathToolMessage::RouteHighlightTimeout.into()),
+		});
+		responses.add(OverlaysMessage::Draw);
+	}
+
+	/// Cancels a just-started marquee in favor of dragging a point if the click narrowly missed that point and the cursor is now heading through it.
+	/// Returns the new dragging state if the promotion happened.
+	pub(super) fn try_promote_to_dragging(
+		&mut self,
+		shape_editor: &mut ShapeState,
+		document: &DocumentMessageHandler,
+		input: &InputPreprocessorMessageHandler,
+		preferences: &PreferencesMessageHandler,
+		responses: &mut VecDeque<Message>,
+	) -> Option<PathToolFsmState> {
+		if !preferences.path_drag_promotion || self.drag_promotion_expired {
+			return None;
+		}
+
+		let movement = input.mouse.position - self.drag_start_pos;
+		if movement.length() > PATH_DRAG_PROMOTION_WINDOW {
+			self.drag_promotion_expired = true;
+			return None;
+		}
+		if movement.length() < DRAG_THRESHOLD {
+			return None;
+		}
+
+		let distance = preferences.path_drag_promotion_distance;
+		let (layer, point) = shape_editor.find_nearest_point_indices(&document.network_interface, self.drag_start_pos, distance)?;
+		let vector_data = document.network_interface.compute_modified_vector(layer)?;
+		let point_position = document.metadata().transform_to_viewport(layer).transform_point2(point.get_position(&vector_data)?);
+
+		// Only promote if the cursor is moving towards (and possibly past) the missed point
+		let toward_point = point_position - self.drag_start_pos;
+		if toward_point.length() < f64::EPSILON || movement.normalize().dot(toward_point.normalize()) < PATH_DRAG_PROMOTION_ALIGNMENT {
+			return None;
+		}
+
+		let Some(selected_points) = shape_editor.change_point_selection(&document.network_interface, self.drag_start_pos, distance, false).flatten() else {
+			return None;
+		};
+
+		self.start_transaction(responses);
+
+		self.drag_promotion_expired = true;
+		self.last_clicked_point_was_selected = false;
+		self.lasso_polygon.clear();
+		self.parallel_drag_handle = point.as_handle().is_some().then_some((layer, point));
+
+		// Drag from the missed point's position so it jumps under the cursor
+		self.start_dragging_point(selected_points, point_position, document, shape_editor);
+
+		responses.add(PathToolMessage::SelectedPointUpdated);
+		responses.add(OverlaysMessage::Draw);
+
+		Some(PathToolFsmState::Dragging(self.dragging_state))
+	}
+
+	pub(super) fn update_colinear(&mut self, equidistant: bool, toggle_colinear: bool, shape_editor: &mut ShapeState, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) -> bool {
+		// Check handle colinear state
+		let is_colinear = self
+			.selection_status
+			.angle()
+			.map(|angle| match angle {
+				ManipulatorAngle::Colinear => true,
+				ManipulatorAngle::Free | ManipulatorAngle::Mixed => false,
+			})
+			.unwrap_or(false);
+
+		// Check if the toggle_colinear key has just been pressed
+		if toggle_colinear && !self.toggle_colinear_debounce {
+			self.opposing_handle_lengths = None;
+			if is_colinear {
+				shape_editor.disable_colinear_handles_state_on_selected(&document.network_interface, responses);
+			} else {
+				shape_editor.convert_selected_manipulators_to_colinear_handles(responses, document);
+			}
+			self.toggle_colinear_debounce = true;
+			return true;
+		}
+		self.toggle_colinear_debounce = toggle_colinear;
+
+		if equidistant && self.opposing_handle_lengths.is_none() {
+			if !is_colinear {
+				// Try to get selected handle info
+				let Some((_, _, selected_handle_id)) = self.try_get_selected_handle_and_anchor(shape_editor, document) else {
+					self.opposing_handle_lengths = Some(shape_editor.opposing_handle_lengths(document));
+					return false;
+				};
+
+				let Some((layer, _)) = shape_editor.selected_shape_state.iter().next() else {
+					self.opposing_handle_lengths = Some(shape_editor.opposing_handle_lengths(document));
+					return false;
+				};
+
+				let Some(vector_data) = document.network_interface.compute_modified_vector(*layer) else {
+					self.opposing_handle_lengths = Some(shape_editor.opposing_handle_lengths(document));
+					return false;
+				};
+
+				// Check if handle has a pair (to ignore handles of edges of open paths)
+				if let Some(handle_pair) = selected_handle_id.get_handle_pair(&vector_data) {
+					let opposite_handle_length = handle_pair.iter().filter(|&&h| h.to_manipulator_point() != selected_handle_id).find_map(|&h| {
+						let opp_handle_pos = h.to_manipulator_point().get_position(&vector_data)?;
+						let opp_anchor_id = h.to_manipulator_point().get_anchor(&vector_data)?;
+						let opp_anchor_pos = vector_data.point_domain.position_from_id(opp_anchor_id)?;
+						Some((opp_handle_pos - opp_anchor_pos).length())
+					});
+
+					// Make handles colinear if opposite handle is zero length
+					if opposite_handle_length == Some(0.) {
+						shape_editor.convert_selected_manipulators_to_colinear_handles(responses, document);
+						return true;
+					}
+				}
+			}
+			self.opposing_handle_lengths = Some(shape_editor.opposing_handle_lengths(document));
+		}
+		false
+	}
+
+	/// Attempts to get a single selected handle. Also retrieves the position of the anchor it is connected to. Used for the purpose of snapping the angle.
+	pub(super) fn try_get_selected_handle_and_anchor(&self, shape_editor: &ShapeState, document: &DocumentMessageHandler) -> Option<(DVec2, DVec2, ManipulatorPointId)> {
+		// Only count selections of a single layer
+		let (layer, selection) = shape_editor.selected_shape_state.iter().next()?;
+
+		// Do not allow selections of multiple points to count
+		if selection.selected_points_count() != 1 {
+			return None;
+		}
+
+		// Only count selected handles
+		let selected_handle = selection.selected().next()?.as_handle()?;
+		let handle_id = selected_handle.to_manipulator_point();
+
+		let layer_to_document = document.metadata().transform_to_document(*layer);
+		let vector_data = document.network_interface.compute_modified_vector(*layer)?;
+
+		let handle_position_local = selected_handle.to_manipulator_point().get_position(&vector_data)?;
+		let anchor_id = selected_handle.to_manipulator_point().get_anchor(&vector_data)?;
+		let anchor_position_local = vector_data.point_domain.position_from_id(anchor_id)?;
+
+		let handle_position_document = layer_to_document.transform_point2(handle_position_local);
+		let anchor_position_document = layer_to_document.transform_point2(anchor_position_local);
+
+		Some((handle_position_document, anchor_position_document, handle_id))
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn calculate_handle_angle(
+		&mut self,
+		shape_editor: &mut ShapeState,
+		document: &DocumentMessageHandler,
+		responses: &mut VecDeque<Message>,
+		relative_vector: DVec2,
+		handle_vector: DVec2,
+		handle_id: ManipulatorPointId,
+		lock_angle: bool,
+		snap_angle: bool,
+		angle_reference: HandleAngleReference,
+	) -> f64 {
+		// Angles are measured in document space, so the increments they snap to are unaffected by the canvas rotation
+		let current_angle = -handle_vector.angle_to(DVec2::X);
+		let reference_angle = match self.angle_reference {
+			Some((id, angle)) if id == handle_id => angle,
+			_ => {
+				let angle = Self::reference_direction(shape_editor, document, handle_id, angle_reference).map_or(0., |direction| -direction.angle_to(DVec2::X));
+				self.angle_reference = Some((handle_id, angle));
+				angle
+			}
+		};
+
+		if let Some(vector_data) = shape_editor
+			.selected_shape_state
+			.iter()
+			.next()
+			.and_then(|(layer, _)| document.network_interface.compute_modified_vector(*layer))
+		{
+			if relative_vector.length() < 25. && lock_angle && !self.angle_locked {
+				if let Some(angle) = calculate_lock_angle(self, shape_editor, responses, document, &vector_data, handle_id) {
+					self.angle = angle;
+					return angle;
+				}
+			}
+		}
+
+		// When the angle is locked we use the old angle
+
+		if self.current_selected_handle_id == Some(handle_id) && lock_angle {
+			self.angle_locked = true;
+			return self.angle;
+		}
+
+		// Round the angle, as measured from the reference direction, to the closest increment
+		let mut handle_angle = current_angle;
+		if snap_angle && !lock_angle {
+			let snap_resolution = HANDLE_ROTATE_SNAP_ANGLE.to_radians();
+			handle_angle = reference_angle + ((handle_angle - reference_angle) / snap_resolution).round() * snap_resolution;
+		}
+
+		// Cache the angle and handle id for lock angle
+		self.current_selected_handle_id = Some(handle_id);
+		self.angle = handle_angle;
+
+		handle_angle
+	}
+
+	/// The document space direction that the angle of the dragged handle is measured from, or `None` when measuring from the canvas axes.
+	pub(super) fn reference_direction(shape_editor: &ShapeState, document: &DocumentMessageHandler, handle_id: ManipulatorPointId, angle_reference: HandleAngleReference) -> Option<DVec2> {
+		let (&layer, _) = shape_editor.selected_shape_state.iter().next()?;
+		let vector_data = document.network_interface.compute_modified_vector(layer)?;
+		let anchor = handle_id.get_anchor(&vector_data)?;
+		let anchor_position = vector_data.point_domain.position_from_id(anchor)?;
+
+		let direction = match angle_reference {
+			HandleAngleReference::Absolute => return None,
+			HandleAngleReference::OppositeHandle => {
+				let [_, opposite] = handle_id.get_handle_pair(&vector_data)?;
+				opposite.to_manipulator_point().get_position(&vector_data)? - anchor_position
+			}
+			HandleAngleReference::SegmentChord => {
+				let (start, end, _) = vector_data.segment_points_from_id(handle_id.as_handle()?.segment)?;
+				let other_anchor = if start == anchor { end } else { start };
+				vector_data.point_domain.position_from_id(other_anchor)? - anchor_position
+			}
+		};
+
+		document.metadata().transform_to_document(layer).transform_vector2(direction).try_normalize()
+	}
+
+	pub(super) fn apply_snapping(
+		&mut self,
+		new_handle_position: DVec2,
+		anchor_position: DVec2,
+		snap_constraint: SnapConstraint,
+		handle_position: DVec2,
+		document: &DocumentMessageHandler,
+		input: &InputPreprocessorMessageHandler,
+	) -> DVec2 {
+		let snap_data = SnapData::new(document, input);
+		let snap_point = SnapCandidatePoint::handle_neighbors(new_handle_position, [anchor_position]);
+
+		let snap_result = match snap_constraint {
+			SnapConstraint::None => self.snap_manager.free_snap(&snap_data, &snap_point, Default::default()),
+			snap_constraint => self.snap_manager.constrained_snap(&snap_data, &snap_point, snap_constraint, Default::default()),
+		};
+
+		self.snap_manager.update_indicator(snap_result.clone());
+
+		document.metadata().document_to_viewport.transform_vector2(snap_result.snapped_point_document - handle_position)
+	}
+
+	/// Sets the dragged handle to its locked length in the given document space direction, since the rounding of the viewport deltas it was moved by would otherwise change its length.
+	pub(super) fn restore_locked_handle_length(
+		&mut self,
+		handle_id: ManipulatorPointId,
+		direction: DVec2,
+		shape_editor: &ShapeState,
+		document: &DocumentMessageHandler,
+		responses: &mut VecDeque<Message>,
+	) {
+		let Some((&layer, _)) = shape_editor.selected_shape_state.iter().next() else { return };
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { return };
+		let Some(anchor_position) = handle_id.get_anchor(&vector_data).and_then(|anchor| vector_data.point_domain.position_from_id(anchor)) else {
+			return;
+		};
+		let Some(handle_position) = handle_id.get_position(&vector_data) else { return };
+
+		// The length is measured the first time, before the drag has moved the handle
+		let length = match self.locked_handle_length {
+			Some((id, length)) if id == handle_id => length,
+			_ => {
+				let length = handle_position.distance(anchor_position);
+				self.locked_handle_length = Some((handle_id, length));
+				length
+			}
+		};
+
+		let Some(direction) = document.metadata().transform_to_document(layer).inverse().transform_vector2(direction).try_normalize() else {
+			return;
+		};
+		let relative_position = direction * length;
+		let modification_type = match handle_id {
+			ManipulatorPointId::PrimaryHandle(segment) => VectorModificationType::SetPrimaryHandle { segment, relative_position },
+			ManipulatorPointId::EndHandle(segment) => VectorModificationType::SetEndHandle { segment, relative_position },
+			ManipulatorPointId::Anchor(_) => return,
+		};
+		responses.add(GraphOperationMessage::Vector { layer, modification_type });
+	}
+
+	pub(super) fn start_snap_along_axis(&mut self, shape_editor: &mut ShapeState, document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
+		// Find the negative delta to take the point to the drag start position
+		let current_mouse = input.mouse.position;
+		let drag_start = self.drag_start_pos;
+		let opposite_delta = drag_start - current_mouse;
+
+		shape_editor.move_selected_points(None, document, opposite_delta, false, true, false, None, false, responses);
+
+		// Calculate the projected delta and shift the points along that delta
+		let document_to_viewport = document.metadata().document_to_viewport;
+		let delta = current_mouse - drag_start;
+		let axis = dominant_document_axis(delta, document_to_viewport);
+		self.snapping_axis = Some(axis);
+		let projected_delta = project_onto_document_axis(delta, axis, document_to_viewport);
+
+		shape_editor.move_selected_points(None, document, projected_delta, false, true, false, None, false, responses);
+	}
+
+	pub(super) fn stop_snap_along_axis(&mut self, shape_editor: &mut ShapeState, document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
+		// Calculate the negative delta of the selection and move it back to the drag start
+		let current_mouse = input.mouse.position;
+		let drag_start = self.drag_start_pos;
+
+		let opposite_delta = drag_start - current_mouse;
+		let Some(axis) = self.snapping_axis else { return };
+		let opposite_projected_delta = project_onto_document_axis(opposite_delta, axis, document.metadata().document_to_viewport);
+
+		shape_editor.move_selected_points(None, document, opposite_projected_delta, false, true, false, None, false, responses);
+
+		// Calculate what actually would have been the original delta for the point, and apply that
+		let delta = current_mouse - drag_start;
+
+		shape_editor.move_selected_points(None, document, delta, false, true, false, None, false, responses);
+
+		self.snapping_axis = None;
+	}
+
+	pub(super) fn get_normalized_tangent(&mut self, point: PointId, segment: SegmentId, vector_data: &VectorData) -> Option<DVec2> {
+		let other_point = vector_data.other_point(segment, point)?;
+		let position = ManipulatorPointId::Anchor(point).get_position(vector_data)?;
+
+		let mut handles = vector_data.all_connected(other_point);
+		let other_handle = handles.find(|handle| handle.segment == segment)?;
+
+		let target_position = if other_handle.length(vector_data) == 0. {
+			ManipulatorPointId::Anchor(other_point).get_position(vector_data)?
+		} else {
+			other_handle.to_manipulator_point().get_position(vector_data)?
+		};
+
+		let tangent_vector = target_position - position;
+		tangent_vector.try_normalize()
+	}
+
+	/// Rotates and scales every selected handle about its own anchor by the same polar change that the cursor applies to the dragged handle.
+	/// Returns `false` if the selection isn't made up of multiple handles, in which case the regular drag should be used instead.
+	pub(super) fn drag_handles_in_parallel(
+		&mut self,
+		shape_editor: &ShapeState,
+		document: &DocumentMessageHandler,
+		input: &InputPreprocessorMessageHandler,
+		responses: &mut VecDeque<Message>,
+	) -> bool {
+		if !self.dragging_state.multiple_handles || self.dragging_state.point_select_state == PointSelectState::Anchor {
+			return false;
+		}
+		let Some((layer, dragged_handle)) = self.parallel_drag_handle else { return false };
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else {
+			return false;
+		};
+		let Some(handle_position) = dragged_handle.get_position(&vector_data) else { return false };
+		let Some(anchor_position) = dragged_handle.get_anchor(&vector_data).and_then(|anchor| vector_data.point_domain.position_from_id(anchor)) else {
+			return false;
+		};
+
+		let document_to_viewport = document.metadata().document_to_viewport;
+		let old_relative = document.metadata().transform_to_viewport(layer).transform_vector2(handle_position - anchor_position);
+		let delta = input.mouse.position - document_to_viewport.transform_point2(self.previous_mouse_position);
+		let new_relative = old_relative + delta;
+
+		// A handle lying on its anchor has no direction to measure a polar change from
+		if old_relative.length_squared() < f64::EPSILON {
+			return false;
+		}
+		if new_relative.length_squared() < f64::EPSILON {
+			return true;
+		}
+
+		let angle = old_relative.angle_to(new_relative);
+		let scale = new_relative.length() / old_relative.length();
+		shape_editor.rotate_and_scale_selected_handles(document, angle, scale, responses);
+		self.previous_mouse_position += document_to_viewport.inverse().transform_vector2(delta);
+
+		true
+	}
+
+	/// Adds the document space `delta` just applied by the drag to the readout, measuring the dragged handle when given its previous position and its anchor's.
+	fn update_drag_readout(&mut self, delta: DVec2, dragged_handle: Option<(DVec2, DVec2)>) {
+		self.dragged_delta += delta;
+		self.drag_readout = Some(match dragged_handle {
+			Some((handle_pos, anchor_pos)) => {
+				let handle_vector = handle_pos + delta - anchor_pos;
+				let reference_angle = self.angle_reference.map_or(0., |(_, angle)| angle);
+				let degrees = handle_vector
+					.try_normalize()
+					.map_or(0., |direction| DVec2::from_angle(reference_angle).angle_to(direction).to_degrees());
+				DragReadout::Handle {
+					degrees,
+					length: handle_vector.length(),
+				}
+			}
+			None => DragReadout::Points { delta: self.dragged_delta },
+		});
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn drag(
+		&mut self,
+		equidistant: bool,
+		lock_angle: bool,
+		lock_length: bool,
+		snap_angle: bool,
+		parallel_handles: bool,
+		angle_reference: HandleAngleReference,
+		shape_editor: &mut ShapeState,
+		document: &DocumentMessageHandler,
+		input: &InputPreprocessorMessageHandler,
+		responses: &mut VecDeque<Message>,
+	) {
+		self.drag_readout = None;
+		if !lock_length {
+			self.locked_handle_length = None;
+		}
+
+		if parallel_handles && self.drag_handles_in_parallel(shape_editor, document, input, responses) {
+			return;
+		}
+
+		// First check if selection is not just a single handle point
+		let selected_points = shape_editor.selected_points();
+		let single_handle_selected = selected_points.count() == 1
+			&& shape_editor
+				.selected_points()
+				.any(|point| matches!(point, ManipulatorPointId::EndHandle(_) | ManipulatorPointId::PrimaryHandle(_)));
+
+		if snap_angle && self.snapping_axis.is_none() && !single_handle_selected {
+			self.start_snap_along_axis(shape_editor, document, input, responses);
+		} else if !snap_angle && self.snapping_axis.is_some() {
+			self.stop_snap_along_axis(shape_editor, document, input, responses);
+		}
+
+		let document_to_viewport = document.metadata().document_to_viewport;
+		let previous_mouse = document_to_viewport.transform_point2(self.previous_mouse_position);
+		let current_mouse = input.mouse.position;
+		let raw_delta = document_to_viewport.inverse().transform_vector2(current_mouse - previous_mouse);
+
+		let mut length_locked_handle = None;
+		let mut dragged_handle = None;
+		let snapped_delta = if let Some((handle_pos, anchor_pos, handle_id)) = self.try_get_selected_handle_and_anchor(shape_editor, document) {
+			let cursor_pos = handle_pos + raw_delta;
+
+			let handle_angle = self.calculate_handle_angle(
+				shape_editor,
+				document,
+				responses,
+				handle_pos - anchor_pos,
+				cursor_pos - anchor_pos,
+				handle_id,
+				lock_angle,
+				snap_angle,
+				angle_reference,
+			);
+
+			dragged_handle = Some((handle_pos, anchor_pos));
+
+			let constrained_direction = DVec2::new(handle_angle.cos(), handle_angle.sin());
+
+			if lock_length {
+				// Keep the handle on the circle of its current length about the anchor
+				length_locked_handle = Some((handle_id, handle_pos, anchor_pos));
+				let radius = handle_pos.distance(anchor_pos);
+				let constrained_target = anchor_pos + constrained_direction * radius;
+
+				// A snapped or locked angle leaves nowhere along the circle to snap to
+				if lock_angle || snap_angle {
+					self.snap_manager.clear_indicator();
+					document_to_viewport.transform_vector2(constrained_target - handle_pos)
+				} else {
+					let snap_constraint = SnapConstraint::Circle { center: anchor_pos, radius };
+					self.apply_snapping(constrained_target, anchor_pos, snap_constraint, handle_pos, document, input)
+				}
+			} else {
+				let projected_length = (cursor_pos - anchor_pos).dot(constrained_direction);
+				let constrained_target = anchor_pos + constrained_direction * projected_length;
+
+				let snap_constraint = if lock_angle || snap_angle {
+					SnapConstraint::Line {
+						origin: anchor_pos,
+						direction: constrained_direction,
+					}
+				} else {
+					SnapConstraint::None
+				};
+				self.apply_snapping(constrained_target, anchor_pos, snap_constraint, handle_pos, document, input)
+			}
+		} else {
+			shape_editor.snap(&mut self.snap_manager, &self.snap_cache, document, input, previous_mouse, self.grabbed_point)
+		};
+
+		let handle_lengths = if equidistant { None } else { self.opposing_handle_lengths.take() };
+		let opposite = if lock_angle { None } else { self.opposite_handle_position };
+		let unsnapped_delta = current_mouse - previous_mouse;
+		let mut was_alt_dragging = false;
+
+		if self.snapping_axis.is_none() {
+			if self.alt_clicked_on_anchor && !self.alt_dragging_from_anchor && self.drag_start_pos.distance(input.mouse.position) > DRAG_THRESHOLD {
+				// Checking which direction the dragging begins
+				self.alt_dragging_from_anchor = true;
+				let Some(layer) = document.network_interface.selected_nodes().selected_layers(document.metadata()).next() else {
+					return;
+				};
+				let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { return };
+				let Some(point_id) = shape_editor.selected_points().next().unwrap().get_anchor(&vector_data) else {
+					return;
+				};
+
+				if vector_data.connected_count(point_id) == 2 {
+					let connected_segments: Vec<HandleId> = vector_data.all_connected(point_id).collect();
+					let segment1 = connected_segments[0];
+					let Some(tangent1) = self.get_normalized_tangent(point_id, segment1.segment, &vector_data) else {
+						return;
+					};
+					let segment2 = connected_segments[1];
+					let Some(tangent2) = self.get_normalized_tangent(point_id, segment2.segment, &vector_data) else {
+						return;
+					};
+
+					let delta = input.mouse.position - self.drag_start_pos;
+					let handle = if delta.dot(tangent1) >= delta.dot(tangent2) {
+						segment1.to_manipulator_point()
+					} else {
+						segment2.to_manipulator_point()
+					};
+
+					// Now change the selection to this handle
+					shape_editor.deselect_all_points();
+					shape_editor.select_points_by_manipulator_id(&vec![handle]);
+					responses.add(PathToolMessage::SelectionChanged);
+				}
+			}
+
+			if self.alt_dragging_from_anchor && !equidistant && self.alt_clicked_on_anchor {
+				was_alt_dragging = true;
+				self.alt_dragging_from_anchor = false;
+				self.alt_clicked_on_anchor = false;
+			}
+
+			let mut skip_opposite = false;
+			if self.temporary_colinear_handles && !lock_angle {
+				shape_editor.disable_colinear_handles_state_on_selected(&document.network_interface, responses);
+				self.temporary_colinear_handles = false;
+				skip_opposite = true;
+			}
+			report_dirty_region(shape_editor, document, snapped_delta, responses);
+			shape_editor.move_selected_points(handle_lengths, document, snapped_delta, equidistant, true, was_alt_dragging, opposite, skip_opposite, responses);
+			if let Some((handle_id, handle_pos, anchor_pos)) = length_locked_handle {
+				let handle_target = handle_pos + document_to_viewport.inverse().transform_vector2(snapped_delta);
+				self.restore_locked_handle_length(handle_id, handle_target - anchor_pos, shape_editor, document, responses);
+			}
+			self.previous_mouse_position += document_to_viewport.inverse().transform_vector2(snapped_delta);
+			self.update_drag_readout(document_to_viewport.inverse().transform_vector2(snapped_delta), dragged_handle);
+		} else {
+			let Some(axis) = self.snapping_axis else { return };
+			let projected_delta = project_onto_document_axis(unsnapped_delta, axis, document_to_viewport);
+			report_dirty_region(shape_editor, document, projected_delta, responses);
+			shape_editor.move_selected_points(handle_lengths, document, projected_delta, equidistant, true, false, opposite, false, responses);
+			self.previous_mouse_position += document_to_viewport.inverse().transform_vector2(unsnapped_delta);
+			self.update_drag_readout(document_to_viewport.inverse().transform_vector2(projected_delta), None);
+		}
+
+		if snap_angle && self.snapping_axis.is_some() {
+			let Some(current_axis) = self.snapping_axis else { return };
+			let total_delta = document_to_viewport.inverse().transform_vector2(self.drag_start_pos - input.mouse.position);
+
+			if (total_delta.x.abs() > total_delta.y.abs() && current_axis == Axis::Y) || (total_delta.y.abs() > total_delta.x.abs() && current_axis == Axis::X) {
+				self.stop_snap_along_axis(shape_editor, document, input, responses);
+				self.start_snap_along_axis(shape_editor, document, input, responses);
+			}
+		}
+	}
+}
+
+/// The document axis which the viewport space `delta` is closest to, so constraining a drag to an axis follows the document when the canvas is rotated.
+pub(super) fn dominant_document_axis(delta: DVec2, document_to_viewport: DAffine2) -> Axis {
+	let document_delta = document_to_viewport.inverse().transform_vector2(delta);
+	if document_delta.x.abs() >= document_delta.y.abs() { Axis::X } else { Axis::Y }
+}
+
+/// Projects the viewport space `delta` onto the given document axis, returning the projection in viewport space.
+pub(super) fn project_onto_document_axis(delta: DVec2, axis: Axis, document_to_viewport: DAffine2) -> DVec2 {
+	let document_delta = document_to_viewport.inverse().transform_vector2(delta);
+	let projected = match axis {
+		Axis::Y => DVec2::new(0., document_delta.y),
+		Axis::X | Axis::Both => DVec2::new(document_delta.x, 0.),
+	};
+	document_to_viewport.transform_vector2(projected)
+}
+
+/// Finds the subpaths containing at least one selected anchor in each layer, paired with their offset outline in document space.
+pub(super) fn selected_subpath_offsets(document: &DocumentMessageHandler, shape_editor: &ShapeState, distance: f64) -> Vec<(LayerNodeIdentifier, Vec<(Subpath<PointId>, Subpath<PointId>)>)> {
+	shape_editor
+		.selected_layers()
+		.filter_map(|&layer| {
+			let selected_points = shape_editor.selected_points_in_layer(layer)?;
+			let vector_data = document.network_interface.compute_modified_vector(layer)?;
+			let to_document = document.metadata().transform_to_document(layer);
+
+			let offsets = vector_data
+				.stroke_bezier_paths()
+				.filter(|subpath| subpath.manipulator_groups().iter().any(|group| selected_points.contains(&ManipulatorPointId::Anchor(group.id))))
+				.map(|subpath| {
+					let mut document_subpath = subpath.clone();
+					document_subpath.apply_transform(to_document);
+					let offset = offset_subpath_polyline(&document_subpath, distance);
+					(subpath, offset)
+				})
+				.collect::<Vec<_>>();
+
+			(!offsets.is_empty()).then_some((layer, offsets))
+		})
+		.collect()
+}
+
+/// Tells the document which part of the artwork is about to change from moving the selected points by the viewport space `delta`, so only that part needs to be redrawn.
+pub(super) fn report_dirty_region(shape_editor: &ShapeState, document: &DocumentMessageHandler, delta: DVec2, responses: &mut VecDeque<Message>) {
+	if let Some(bounds) = shape_editor.selected_points_dirty_region(document, delta) {
+		responses.add(DocumentMessage::AddDirtyRegion { bounds });
+	}
+}
+
+/// Applies the previewed offset, either replacing the selected subpaths in their layers or adding the offset outlines as a new layer.
+pub(super) fn commit_offset(document: &DocumentMessageHandler, shape_editor: &mut ShapeState, tool_options: &PathToolOptions, responses: &mut VecDeque<Message>) {
+	let offsets = selected_subpath_offsets(document, shape_editor, tool_options.offset_distance);
+	if offsets.is_empty() || tool_options.offset_distance == 0. {
+		return;
+	}
+
+	responses.add(DocumentMessage::StartTransaction);
+
+	for (layer, offsets) in offsets {
+		if tool_options.offset_replaces_path {
+			let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+			let to_layer = document.metadata().transform_to_document(layer).inverse();
+
+			for (original, mut offset) in offsets {
+				let original_points = original.manipulator_groups().iter().map(|group| group.id).collect::<HashSet<_>>();
+
+				for (segment, _, start, end) in vector_data.segment_bezier_iter() {
+					if original_points.contains(&start) || original_points.contains(&end) {
+						let modification_type = VectorModificationType::RemoveSegment { id: segment };
+						responses.add(GraphOperationMessage::Vector { layer, modification_type });
+					}
+				}
+				for &id in &original_points {
+					let modification_type = VectorModificationType::RemovePoint { id };
+					responses.add(GraphOperationMessage::Vector { layer, modification_type });
+				}
+
+				offset.apply_transform(to_layer);
+				insert_subpath(document, layer, &offset, responses);
+			}
+		} else {
+			let Some(parent) = layer.parent(document.metadata()) else { continue };
+			let to_parent = document.metadata().transform_to_document(parent).inverse();
+
+			let subpaths = offsets
+				.into_iter()
+				.map(|(_, mut offset)| {
+					offset.apply_transform(to_parent);
+					offset
+				})
+				.collect();
+			graph_modification_utils::new_vector_layer(subpaths, NodeId::new(), parent, responses);
+		}
+	}
+
+	responses.add(DocumentMessage::EndTransaction);
+
+	if tool_options.offset_replaces_path {
+		shape_editor.deselect_all_points();
+	}
+}
+
+/// Adds the points and segments of a subpath, given in layer space, to the vector data of the layer.
+pub(super) fn insert_subpath(document: &DocumentMessageHandler, layer: LayerNodeIdentifier, subpath: &Subpath<PointId>, responses: &mut VecDeque<Message>) {
+	let groups = subpath.manipulator_groups();
+	let operation_ids = OperationIds::new(document, layer, responses);
+	let ids = (0..groups.len()).map(|index| operation_ids.point(index)).collect::<Vec<_>>();
+
+	for (group, &id) in groups.iter().zip(&ids) {
+		let modification_type = VectorModificationType::InsertPoint { id, position: group.anchor };
+		responses.add(GraphOperationMessage::Vector { layer, modification_type });
+	}
+
+	for start in 0..subpath.len_segments() {
+		let end = (start + 1) % groups.len();
+		let handles = [
+			groups[start].out_handle.map(|handle| handle - groups[start].anchor),
+			groups[end].in_handle.map(|handle| handle - groups[end].anchor),
+		];
+		let modification_type = VectorModificationType::InsertSegment {
+			id: operation_ids.segment(start),
+			points: [ids[start], ids[end]],
+			handles,
+		};
+		responses.add(GraphOperationMessage::Vector { layer, modification_type });
+	}
+}
+
+pub(super) fn calculate_lock_angle(
+	tool_data: &mut PathToolData,
+	shape_state: &mut ShapeState,
+	responses: &mut VecDeque<Message>,
+	document: &DocumentMessageHandler,
+	vector_data: &VectorData,
+	handle_id: ManipulatorPointId,
+) -> Option<f64> {
+	let anchor = handle_id.get_anchor(vector_data)?;
+	let anchor_position = vector_data.point_domain.position_from_id(anchor);
+	let current_segment = handle_id.get_segment();
+	let points_connected = vector_data.connected_count(anchor);
+
+	let (anchor_position, segment) = anchor_position.zip(current_segment)?;
+	if points_connected == 1 {
+		calculate_segment_angle(anchor, segment, vector_data, false)
+	} else {
+		let opposite_handle = handle_id
+			.get_handle_pair(vector_data)
+			.iter()
+			.flatten()
+			.find(|&h| h.to_manipulator_point() != handle_id)
+			.copied()
+			.map(|h| h.to_manipulator_point());
+		let opposite_handle_position = opposite_handle.and_then(|h| h.get_position(vector_data)).filter(|pos| (pos - anchor_position).length() > 1e-6);
+
+		if let Some(opposite_pos) = opposite_handle_position {
+			if !vector_data.colinear_manipulators.iter().flatten().map(|h| h.to_manipulator_point()).any(|h| h == handle_id) {
+				shape_state.convert_selected_manipulators_to_colinear_handles(responses, document);
+				tool_data.temporary_colinear_handles = true;
+			}
+			Some(-(opposite_pos - anchor_position).angle_to(DVec2::X))
+		} else {
+			let angle_1 = vector_data
+				.adjacent_segment(&handle_id)
+				.and_then(|(_, adjacent_segment)| calculate_segment_angle(anchor, adjacent_segment, vector_data, false));
+
+			let angle_2 = calculate_segment_angle(anchor, segment, vector_data, false);
+
+			match (angle_1, angle_2) {
+				(Some(angle_1), Some(angle_2)) => Some((angle_1 + angle_2) / 2.0),
+				(Some(angle_1), None) => Some(angle_1),
+				(None, Some(angle_2)) => Some(angle_2),
+				(None, None) => None,
+			}
+		}
+	}
+}
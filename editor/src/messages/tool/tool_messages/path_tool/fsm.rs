@@ -0,0 +1,1401 @@
+use super::*;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DraggingState {
+	pub(super) point_select_state: PointSelectState,
+	pub(super) colinear: ManipulatorAngle,
+	/// Whether more than one handle is selected, which allows them to be rotated and scaled in parallel.
+	pub(super) multiple_handles: bool,
+	/// The number of copies an instancing node makes of the dragged geometry, which all follow the edit to their source, or 0 if it isn't instanced.
+	pub(super) instances: usize,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum PointSelectState {
+	HandleWithPair,
+	#[default]
+	HandleNoPair,
+	Anchor,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum PathToolFsmState {
+	#[default]
+	Ready,
+	Dragging(DraggingState),
+	Drawing {
+		selection_shape: SelectionShapeType,
+	},
+	DraggingWidth,
+	DraggingControlPoint,
+	/// A segment is pressed on, to be dragged by its anchors or have a point inserted on it if released without moving.
+	DraggingSegment,
+	Reshaping,
+}
+
+/// What is hovered and selected in the Ready state, which decides which of its hints apply.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) struct ReadyHintContext {
+	/// Whether the hovered anchor is sharp, so double clicking would make it smooth, or `None` if no anchor is hovered.
+	hovered_anchor_sharp: Option<bool>,
+	/// The number of copies an instancing node makes of the geometry of the hovered point, or 0 if it isn't instanced.
+	hovered_instances: usize,
+	points_selected: bool,
+	anchors_selected: bool,
+}
+
+impl ReadyHintContext {
+	pub(super) fn new(shape_editor: &mut ShapeState, document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler) -> Self {
+		let network_interface = &document.network_interface;
+		let hovered_point = shape_editor.find_nearest_point_indices(network_interface, input.mouse.position, SELECTION_THRESHOLD);
+		let hovered_anchor_sharp = match hovered_point {
+			Some((layer, ManipulatorPointId::Anchor(point))) => network_interface.compute_modified_vector(layer).map(|vector_data| ShapeState::is_sharp_anchor(&vector_data, point)),
+			_ => None,
+		};
+		let hovered_instances = hovered_point.map_or(0, |(layer, _)| document.metadata().instance_transforms(layer).len());
+
+		Self {
+			hovered_anchor_sharp,
+			hovered_instances,
+			points_selected: shape_editor.selected_points().next().is_some(),
+			anchors_selected: shape_editor.selected_points().any(|point| matches!(point, ManipulatorPointId::Anchor(_))),
+		}
+	}
+}
+
+impl PathToolFsmState {
+	/// The hints of the Ready state, leaving out those which don't apply to the hovered anchor and the selection.
+	pub(super) fn ready_hint_data(context: ReadyHintContext) -> HintData {
+		let mut hint_data = vec![
+			HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Select Point"), HintInfo::keys([Key::Shift], "Extend").prepend_plus()]),
+			HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Select Area"), HintInfo::keys([Key::Control], "Lasso").prepend_plus()]),
+			HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, ""), HintInfo::keys([Key::Enter], "Insert Point on Segment").prepend_slash()]),
+			HintGroup(vec![HintInfo::keys([Key::Tab], "Cycle Overlapping Segments")]),
+			HintGroup(vec![
+				HintInfo::keys_and_mouse([Key::Alt], MouseMotion::Lmb, "Delete Segment"),
+				HintInfo::keys_and_mouse([Key::Alt], MouseMotion::LmbDrag, "Bend Segment"),
+			]),
+			HintGroup(vec![HintInfo::keys([Key::KeyK], "Copy Hovered Segment Shape")]),
+			HintGroup(vec![HintInfo::keys([Key::Alt, Key::KeyH], "Cycle Handle Visibility")]),
+		];
+
+		if context.hovered_instances > 0 {
+			hint_data.push(HintGroup(vec![Self::instances_hint(context.hovered_instances)]));
+		}
+
+		if let Some(sharp) = context.hovered_anchor_sharp {
+			hint_data.push(HintGroup(vec![
+				HintInfo::mouse(MouseMotion::LmbDouble, if sharp { "Make Anchor Smooth" } else { "Make Anchor Sharp" }),
+				HintInfo::keys_and_mouse([Key::Alt], MouseMotion::Lmb, "To Sharp"),
+				HintInfo::keys_and_mouse([Key::Alt], MouseMotion::LmbDrag, "To Smooth"),
+			]));
+		}
+
+		if context.points_selected {
+			hint_data.push(HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Drag Selected")]));
+			hint_data.push(HintGroup(vec![HintInfo::multi_keys([[Key::KeyG], [Key::KeyR], [Key::KeyS]], "Grab/Rotate/Scale Selected")]));
+			hint_data.push(HintGroup(vec![HintInfo::keys_and_mouse([Key::Shift], MouseMotion::Rmb, "Place Pivot")]));
+			hint_data.push(HintGroup(vec![HintInfo::arrow_keys("Nudge Selected"), HintInfo::keys([Key::Shift], "10x").prepend_plus()]));
+			hint_data.push(HintGroup(vec![HintInfo::keys([Key::Shift, Key::KeyR], "Retract Selected Handles")]));
+		}
+
+		if context.anchors_selected {
+			hint_data.push(HintGroup(vec![
+				HintInfo::keys([Key::Accel, Key::KeyB], "Select Between Two Anchors"),
+				HintInfo::keys([Key::Shift], "Longer Way").prepend_plus(),
+			]));
+			hint_data.push(HintGroup(vec![HintInfo::keys([Key::Accel, Key::Shift, Key::KeyJ], "Join Two Endpoints")]));
+			hint_data.push(HintGroup(vec![HintInfo::keys([Key::Shift, Key::KeyC], "Make Selected Smooth/Sharp")]));
+			hint_data.push(HintGroup(vec![
+				HintInfo::keys([Key::Alt, Key::ArrowRight], "Select Next Anchor"),
+				HintInfo::keys([Key::Shift], "Extend").prepend_plus(),
+			]));
+			hint_data.push(HintGroup(vec![
+				HintInfo::keys([Key::Alt, Key::Equal], "Grow Selection"),
+				HintInfo::keys([Key::Alt, Key::Minus], "Shrink Selection"),
+			]));
+		}
+
+		hint_data.push(HintGroup(vec![
+			HintInfo::keys_and_mouse([Key::KeyO], MouseMotion::ScrollDown, "Preview Offset of Selected"),
+			HintInfo::keys([Key::Enter], "Apply").prepend_slash(),
+		]));
+
+		if context.points_selected {
+			let mut delete_hints = vec![HintInfo::keys([Key::Delete], "Delete Selected")];
+			if context.anchors_selected {
+				delete_hints.push(HintInfo::keys([Key::Accel], "No Dissolve").prepend_plus());
+				delete_hints.push(HintInfo::keys([Key::Shift], "Cut Anchor").prepend_plus());
+				delete_hints.push(HintInfo::keys([Key::Alt], "Keep Shape").prepend_plus());
+			}
+			hint_data.push(HintGroup(delete_hints));
+		}
+
+		HintData(hint_data)
+	}
+
+	/// Warns that editing a point of instanced geometry edits the source which every copy is made from.
+	fn instances_hint(instances: usize) -> HintInfo {
+		HintInfo::label(format!("Editing source of {instances} instances"))
+	}
+}
+
+impl Fsm for PathToolFsmState {
+	type ToolData = PathToolData;
+	type ToolOptions = PathToolOptions;
+
+	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, tool_options: &Self::ToolOptions, responses: &mut VecDeque<Message>) -> Self {
+		let ToolActionHandlerData {
+			document,
+			input,
+			shape_editor,
+			preferences,
+			point_table_open,
+			..
+		} = tool_action_data;
+		let ToolMessage::Path(event) = event else { return self };
+		match (self, event) {
+			(_, PathToolMessage::SelectionChanged) => {
+				// Set the newly targeted layers to visible
+				let target_layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect();
+				shape_editor.set_selected_layers(target_layers);
+				shape_editor.restore_remembered_points(&document.network_interface);
+				shape_editor.deselect_points_in_edit_locked_layers(&document.network_interface);
+
+				responses.add(OverlaysMessage::Draw);
+
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				self
+			}
+			(_, PathToolMessage::Overlays(overlay_context)) => {
+				self.draw_overlays(overlay_context, tool_data, tool_action_data, tool_options, responses);
+				self
+			}
+
+			// Mouse down
+			(PathToolFsmState::Ready, PathToolMessage::MouseDown { .. }) if tool_options.edit_widths && hovered_width_handle(document, input.mouse.position).is_some() => {
+				tool_data.offset_preview_active = false;
+				tool_data.cancel_chunked_selection(responses);
+				tool_data.end_nudge_session(responses);
+
+				tool_data.width_drag = hovered_width_handle(document, input.mouse.position);
+				tool_data.start_transaction(responses);
+				// A pen sets the width from its pressure as soon as it touches down, while the mouse waits to be dragged
+				if let Some(width_drag) = tool_data.width_drag.as_mut().filter(|_| input.mouse.pen.is_some()) {
+					width_drag.drag_to(&input.mouse, responses);
+				}
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::DraggingWidth
+			}
+			(PathToolFsmState::DraggingWidth, PathToolMessage::PointerMove { .. }) => {
+				if let Some(width_drag) = &mut tool_data.width_drag {
+					width_drag.drag_to(&input.mouse, responses);
+				}
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::DraggingWidth
+			}
+			(PathToolFsmState::DraggingWidth, PathToolMessage::DragStop { .. }) => {
+				tool_data.width_drag = None;
+				tool_data.end_transaction(responses);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::DraggingWidth, PathToolMessage::Escape | PathToolMessage::RightClick) => {
+				tool_data.width_drag = None;
+				tool_data.abort_transaction(responses);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::MouseDown { .. }) if tool_options.reshape => {
+				tool_data.offset_preview_active = false;
+				tool_data.cancel_chunked_selection(responses);
+				tool_data.end_nudge_session(responses);
+
+				// The whole stroke is undone as one step
+				tool_data.previous_mouse_position = input.mouse.position;
+				tool_data.start_transaction(responses);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Reshaping
+			}
+			(PathToolFsmState::Reshaping, PathToolMessage::PointerMove { .. }) => {
+				let delta = input.mouse.position - tool_data.previous_mouse_position;
+				reshape_paths(document, shape_editor, tool_data.previous_mouse_position, delta, tool_options.reshape_radius, responses);
+				tool_data.previous_mouse_position = input.mouse.position;
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Reshaping
+			}
+			(PathToolFsmState::Reshaping, PathToolMessage::DragStop { .. }) => {
+				tool_data.end_transaction(responses);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Reshaping, PathToolMessage::Escape | PathToolMessage::RightClick) => {
+				tool_data.abort_transaction(responses);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::DraggingSegment, PathToolMessage::PointerMove { .. }) => {
+				tool_data.drag_segment(shape_editor, document, input, responses);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::DraggingSegment
+			}
+			(PathToolFsmState::DraggingSegment, PathToolMessage::DragStop { .. }) => {
+				tool_data.finish_segment_drag(shape_editor, document, tool_options.new_point_type, responses);
+				tool_data.end_transaction(responses);
+				tool_data.snap_manager.cleanup(responses);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::DraggingSegment, PathToolMessage::Escape | PathToolMessage::RightClick) => {
+				tool_data.cancel_segment_drag(shape_editor);
+				tool_data.abort_transaction(responses);
+				tool_data.snap_manager.cleanup(responses);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			// Grab the control point of a procedural layer, or insert one on the edge between two of them, instead of editing the vector data it generates
+			(PathToolFsmState::Ready, PathToolMessage::MouseDown { .. })
+				if hovered_control_point(document, input.mouse.position).is_some() || control_point_insertion(document, input.mouse.position).is_some() =>
+			{
+				tool_data.offset_preview_active = false;
+				tool_data.cancel_chunked_selection(responses);
+				tool_data.end_nudge_session(responses);
+				shape_editor.deselect_all_points();
+
+				tool_data.start_transaction(responses);
+				let grabbed = hovered_control_point(document, input.mouse.position).or_else(|| {
+					let (mut control_points, index, position) = control_point_insertion(document, input.mouse.position)?;
+					control_points.insert_point(index, position, responses);
+					Some((control_points, index))
+				});
+				tool_data.selected_control_point = grabbed.as_ref().map(|(control_points, index)| (control_points.layer, *index));
+				tool_data.control_point_drag = grabbed.and_then(|(control_points, index)| ControlPointDrag::new(control_points, index, input.mouse.position));
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::DraggingControlPoint
+			}
+			(PathToolFsmState::DraggingControlPoint, PathToolMessage::PointerMove { .. }) => {
+				if let Some(control_point_drag) = &mut tool_data.control_point_drag {
+					control_point_drag.drag_to(input.mouse.position, responses);
+				}
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::DraggingControlPoint
+			}
+			(PathToolFsmState::DraggingControlPoint, PathToolMessage::DragStop { .. }) => {
+				tool_data.control_point_drag = None;
+				tool_data.end_transaction(responses);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::DraggingControlPoint, PathToolMessage::Escape | PathToolMessage::RightClick) => {
+				tool_data.control_point_drag = None;
+				tool_data.abort_transaction(responses);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(
+				_,
+				PathToolMessage::MouseDown {
+					extend_selection,
+					lasso_select,
+					handle_drag_from_anchor,
+					..
+				},
+			) => {
+				let extend_selection = input.keyboard.get(extend_selection as usize);
+				let lasso_select = input.keyboard.get(lasso_select as usize);
+				let handle_drag_from_anchor = input.keyboard.get(handle_drag_from_anchor as usize);
+
+				tool_data.selection_mode = None;
+				tool_data.selected_control_point = None;
+				tool_data.lasso_polygon.clear();
+				tool_data.offset_preview_active = false;
+				tool_data.cancel_chunked_selection(responses);
+				tool_data.end_nudge_session(responses);
+
+				tool_data.mouse_down(shape_editor, document, input, responses, extend_selection, lasso_select, handle_drag_from_anchor)
+			}
+			(
+				PathToolFsmState::Drawing { selection_shape },
+				PathToolMessage::PointerMove {
+					equidistant,
+					toggle_colinear,
+					move_anchor_with_handles,
+					snap_angle,
+					lock_angle,
+					lock_length,
+					delete_segment,
+					parallel_handles,
+					extend_selection,
+					shrink_selection,
+				},
+			) => {
+				if let Some(dragging) = tool_data.try_promote_to_dragging(shape_editor, document, input, preferences, responses) {
+					return dragging;
+				}
+
+				tool_data.previous_mouse_position = input.mouse.position;
+				let extend_selection = input.keyboard.get(extend_selection as usize);
+				let shrink_selection = input.keyboard.get(shrink_selection as usize);
+				tool_data.marquee_selection_change = SelectionChange::from_modifiers(extend_selection, shrink_selection);
+
+				if selection_shape == SelectionShapeType::Lasso {
+					extend_lasso(&mut tool_data.lasso_polygon, input.mouse.position);
+				}
+
+				responses.add(OverlaysMessage::Draw);
+
+				// Auto-panning
+				let messages = [
+					PathToolMessage::PointerOutsideViewport {
+						equidistant,
+						toggle_colinear,
+						move_anchor_with_handles,
+						snap_angle,
+						lock_angle,
+						lock_length,
+						delete_segment,
+						parallel_handles,
+						extend_selection,
+						shrink_selection,
+					}
+					.into(),
+					PathToolMessage::PointerMove {
+						equidistant,
+						toggle_colinear,
+						move_anchor_with_handles,
+						snap_angle,
+						lock_angle,
+						lock_length,
+						delete_segment,
+						parallel_handles,
+						extend_selection,
+						shrink_selection,
+					}
+					.into(),
+				];
+				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+
+				PathToolFsmState::Drawing { selection_shape }
+			}
+			(
+				PathToolFsmState::Dragging(_),
+				PathToolMessage::PointerMove {
+					equidistant,
+					toggle_colinear,
+					move_anchor_with_handles,
+					snap_angle,
+					lock_angle,
+					lock_length,
+					delete_segment,
+					parallel_handles,
+					extend_selection,
+					shrink_selection,
+				},
+			) => {
+				let mut selected_only_handles = true;
+
+				let selected_points = shape_editor.selected_points();
+
+				for point in selected_points {
+					if matches!(point, ManipulatorPointId::Anchor(_)) {
+						selected_only_handles = false;
+						break;
+					}
+				}
+
+				if !tool_data.saved_points_before_handle_drag.is_empty() && (tool_data.drag_start_pos.distance(input.mouse.position) > DRAG_THRESHOLD) && (selected_only_handles) {
+					tool_data.handle_drag_toggle = true;
+				}
+
+				if tool_data.selection_status.is_none() {
+					if let Some(layer) = document.click(input) {
+						shape_editor.select_all_anchors_in_layer(document, layer);
+					}
+				}
+
+				let anchor_and_handle_toggled = input.keyboard.get(move_anchor_with_handles as usize);
+				let initial_press = anchor_and_handle_toggled && !tool_data.select_anchor_toggled;
+				let released_from_toggle = tool_data.select_anchor_toggled && !anchor_and_handle_toggled;
+
+				if initial_press {
+					responses.add(PathToolMessage::SelectedPointUpdated);
+					tool_data.select_anchor_toggled = true;
+					tool_data.save_points_before_anchor_toggle(shape_editor.selected_points().cloned().collect());
+					shape_editor.select_handles_and_anchor_connected_to_current_handle(&document.network_interface);
+				} else if released_from_toggle {
+					responses.add(PathToolMessage::SelectedPointUpdated);
+					tool_data.select_anchor_toggled = false;
+					shape_editor.deselect_all_points();
+					shape_editor.select_points_by_manipulator_id(&tool_data.saved_points_before_anchor_select_toggle);
+					tool_data.remove_saved_points();
+				}
+
+				let toggle_colinear_state = input.keyboard.get(toggle_colinear as usize);
+				let equidistant_state = input.keyboard.get(equidistant as usize);
+				let lock_angle_state = input.keyboard.get(lock_angle as usize);
+				let lock_length_state = input.keyboard.get(lock_length as usize);
+				let snap_angle_state = input.keyboard.get(snap_angle as usize);
+				let parallel_handles_state = input.keyboard.get(parallel_handles as usize);
+
+				if !lock_angle_state {
+					tool_data.angle_locked = false;
+				}
+
+				if !tool_data.update_colinear(equidistant_state, toggle_colinear_state, tool_action_data.shape_editor, tool_action_data.document, responses) {
+					tool_data.drag(
+						equidistant_state,
+						lock_angle_state,
+						lock_length_state,
+						snap_angle_state,
+						parallel_handles_state,
+						tool_options.handle_angle_reference,
+						tool_action_data.shape_editor,
+						tool_action_data.document,
+						input,
+						responses,
+					);
+				}
+
+				// Auto-panning
+				let messages = [
+					PathToolMessage::PointerOutsideViewport {
+						toggle_colinear,
+						equidistant,
+						move_anchor_with_handles,
+						snap_angle,
+						lock_angle,
+						lock_length,
+						delete_segment,
+						parallel_handles,
+						extend_selection,
+						shrink_selection,
+					}
+					.into(),
+					PathToolMessage::PointerMove {
+						toggle_colinear,
+						equidistant,
+						move_anchor_with_handles,
+						snap_angle,
+						lock_angle,
+						lock_length,
+						delete_segment,
+						parallel_handles,
+						extend_selection,
+						shrink_selection,
+					}
+					.into(),
+				];
+				tool_data.auto_panning.setup_by_mouse_position(input, &messages, responses);
+
+				PathToolFsmState::Dragging(tool_data.dragging_state)
+			}
+			(PathToolFsmState::Ready, PathToolMessage::PointerMove { delete_segment, .. }) => {
+				tool_data.delete_segment_pressed = input.keyboard.get(delete_segment as usize);
+
+				// Target the upper segment again once the cursor moves away from where cycling through overlapping segments began
+				if !tool_data.cycled_segments.is_empty() && tool_data.segment_cycle_origin.distance(input.mouse.position) > SEGMENT_CYCLE_RESET_DISTANCE {
+					tool_data.cycled_segments.clear();
+					tool_data.segment = None;
+				}
+
+				// If there is a point nearby, then highlight it rather than the segment
+				tool_data.hovered_point = shape_editor.find_nearest_point_indices(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD);
+				if tool_data.hovered_point.is_some() {
+					tool_data.segment = None;
+					responses.add(OverlaysMessage::Draw)
+				}
+				// If already hovering on a segment, then recalculate its closest point
+				else if let Some(closest_segment) = &mut tool_data.segment {
+					closest_segment.update_closest_point(document.metadata(), input.mouse.position);
+					if closest_segment.too_far(input.mouse.position, SEGMENT_INSERTION_DISTANCE, document.metadata()) {
+						tool_data.segment = None;
+					}
+					responses.add(OverlaysMessage::Draw)
+				}
+				// If not, check that if there is some closest segment or not
+				else if let Some(closest_segment) = shape_editor.upper_closest_segment(&document.network_interface, input.mouse.position, SEGMENT_INSERTION_DISTANCE) {
+					tool_data.segment = Some(closest_segment);
+					responses.add(OverlaysMessage::Draw)
+				}
+
+				self
+			}
+			(PathToolFsmState::Ready, PathToolMessage::CycleSegment) => {
+				let Some(current) = &tool_data.segment else { return self };
+				let position = input.mouse.position;
+
+				if tool_data.cycled_segments.is_empty() {
+					tool_data.cycled_segments.push((current.layer(), current.segment()));
+					tool_data.segment_cycle_origin = position;
+					tool_data.segment_cycle_count = shape_editor.closest_segment_count(&document.network_interface, position, SEGMENT_INSERTION_DISTANCE);
+				}
+
+				// Target the next segment beneath those already offered, looping back around to the upper one
+				let next = match shape_editor.upper_closest_segment_excluding(&document.network_interface, position, SEGMENT_INSERTION_DISTANCE, &tool_data.cycled_segments) {
+					Some(next) => Some(next),
+					None => {
+						tool_data.cycled_segments.clear();
+						shape_editor.upper_closest_segment(&document.network_interface, position, SEGMENT_INSERTION_DISTANCE)
+					}
+				};
+				if let Some(next) = next {
+					tool_data.cycled_segments.push((next.layer(), next.segment()));
+					tool_data.segment = Some(next);
+				}
+
+				responses.add(OverlaysMessage::Draw);
+
+				self
+			}
+			(PathToolFsmState::Drawing { selection_shape: selection_type }, PathToolMessage::PointerOutsideViewport { .. }) => {
+				// Auto-panning
+				if let Some(offset) = tool_data.auto_panning.shift_viewport(input, responses) {
+					tool_data.drag_start_pos += offset;
+				}
+
+				PathToolFsmState::Drawing { selection_shape: selection_type }
+			}
+			(PathToolFsmState::Dragging(dragging_state), PathToolMessage::PointerOutsideViewport { .. }) => {
+				// Auto-panning
+				if let Some(offset) = tool_data.auto_panning.shift_viewport(input, responses) {
+					tool_data.drag_start_pos += offset;
+				}
+
+				PathToolFsmState::Dragging(dragging_state)
+			}
+			(
+				state,
+				PathToolMessage::PointerOutsideViewport {
+					equidistant,
+					toggle_colinear,
+					move_anchor_with_handles,
+					snap_angle,
+					lock_angle,
+					lock_length,
+					delete_segment,
+					parallel_handles,
+					extend_selection,
+					shrink_selection,
+				},
+			) => {
+				// Auto-panning
+				let messages = [
+					PathToolMessage::PointerOutsideViewport {
+						equidistant,
+						toggle_colinear,
+						move_anchor_with_handles,
+						snap_angle,
+						lock_angle,
+						lock_length,
+						delete_segment,
+						parallel_handles,
+						extend_selection,
+						shrink_selection,
+					}
+					.into(),
+					PathToolMessage::PointerMove {
+						equidistant,
+						toggle_colinear,
+						move_anchor_with_handles,
+						snap_angle,
+						lock_angle,
+						lock_length,
+						delete_segment,
+						parallel_handles,
+						extend_selection,
+						shrink_selection,
+					}
+					.into(),
+				];
+				tool_data.auto_panning.stop(&messages, responses);
+
+				state
+			}
+			(PathToolFsmState::Drawing { selection_shape }, PathToolMessage::Enter { extend_selection, shrink_selection }) => {
+				let extend_selection = input.keyboard.get(extend_selection as usize);
+				let shrink_selection = input.keyboard.get(shrink_selection as usize);
+				let selection_change = SelectionChange::from_modifiers(extend_selection, shrink_selection);
+
+				if tool_data.drag_start_pos == tool_data.previous_mouse_position {
+					responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![] });
+				} else {
+					tool_data.select_in_shape(shape_editor, document, selection_shape, selection_change, tool_options, responses);
+				}
+
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Dragging { .. }, PathToolMessage::Escape | PathToolMessage::RightClick) => {
+				if tool_data.handle_drag_toggle && tool_data.drag_start_pos.distance(input.mouse.position) > DRAG_THRESHOLD {
+					shape_editor.deselect_all_points();
+					shape_editor.select_points_by_manipulator_id(&tool_data.saved_points_before_handle_drag);
+
+					tool_data.saved_points_before_handle_drag.clear();
+					tool_data.handle_drag_toggle = false;
+				}
+				tool_data.abort_transaction(responses);
+				tool_data.snap_manager.cleanup(responses);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Drawing { .. }, PathToolMessage::Escape | PathToolMessage::RightClick) => {
+				tool_data.cancel_marquee(shape_editor, responses);
+				tool_data.snap_manager.cleanup(responses);
+				PathToolFsmState::Ready
+			}
+			// Mouse up
+			(PathToolFsmState::Drawing { selection_shape }, PathToolMessage::DragStop { extend_selection, shrink_selection }) => {
+				// The modifiers held on release decide the change, which the marquee's overlay has been showing since they were last changed
+				let extend_selection = input.keyboard.get(extend_selection as usize);
+				let shrink_selection = input.keyboard.get(shrink_selection as usize);
+				let select_kind = SelectionChange::from_modifiers(extend_selection, shrink_selection);
+
+				if tool_data.drag_start_pos == tool_data.previous_mouse_position {
+					responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![] });
+				} else {
+					tool_data.select_in_shape(shape_editor, document, selection_shape, select_kind, tool_options, responses);
+				}
+				responses.add(OverlaysMessage::Draw);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::DragStop { extend_selection, .. }) => {
+				let extend_selection = input.keyboard.get(extend_selection as usize);
+				let drag_occurred = tool_data.drag_start_pos.distance(input.mouse.position) > DRAG_THRESHOLD;
+				let nearest_point = shape_editor.find_nearest_point_indices(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD);
+
+				if let Some((layer, nearest_point)) = nearest_point {
+					if !drag_occurred && extend_selection {
+						let clicked_selected = shape_editor.selected_points().any(|&point| nearest_point == point);
+						if clicked_selected && tool_data.last_clicked_point_was_selected {
+							shape_editor.selected_shape_state.entry(layer).or_default().deselect_point(nearest_point);
+						} else {
+							shape_editor.selected_shape_state.entry(layer).or_default().select_point(nearest_point);
+						}
+						responses.add(OverlaysMessage::Draw);
+					}
+				}
+
+				if tool_data.temporary_colinear_handles {
+					tool_data.temporary_colinear_handles = false;
+				}
+
+				if tool_data.handle_drag_toggle && drag_occurred {
+					shape_editor.deselect_all_points();
+					shape_editor.select_points_by_manipulator_id(&tool_data.saved_points_before_handle_drag);
+
+					tool_data.saved_points_before_handle_drag.clear();
+					tool_data.handle_drag_toggle = false;
+				}
+
+				tool_data.alt_dragging_from_anchor = false;
+				tool_data.alt_clicked_on_anchor = false;
+
+				if tool_data.select_anchor_toggled {
+					shape_editor.deselect_all_points();
+					shape_editor.select_points_by_manipulator_id(&tool_data.saved_points_before_anchor_select_toggle);
+					tool_data.remove_saved_points();
+					tool_data.select_anchor_toggled = false;
+				}
+
+				if let Some((layer, nearest_point)) = nearest_point {
+					if !drag_occurred && !extend_selection {
+						let clicked_selected = shape_editor.selected_points().any(|&point| nearest_point == point);
+						if clicked_selected {
+							shape_editor.deselect_all_points();
+							shape_editor.selected_shape_state.entry(layer).or_default().select_point(nearest_point);
+							responses.add(OverlaysMessage::Draw);
+						}
+					}
+				}
+				// Deselect all points if the user clicks the filled region of the shape
+				else if tool_data.drag_start_pos.distance(input.mouse.position) <= DRAG_THRESHOLD {
+					shape_editor.deselect_all_points();
+				}
+
+				if tool_data.snapping_axis.is_some() {
+					tool_data.snapping_axis = None;
+				}
+
+				tool_data.end_transaction(responses);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				tool_data.snap_manager.cleanup(responses);
+				tool_data.opposite_handle_position = None;
+				tool_data.drag_readout = None;
+
+				PathToolFsmState::Ready
+			}
+
+			// Delete key
+			(_, PathToolMessage::Delete) if tool_data.selected_control_point.is_some() => {
+				// Shrink the control points of the procedural layer rather than deleting from the vector data it generates
+				if let Some((layer, index)) = tool_data.selected_control_point.take() {
+					if let Some(mut control_points) = ControlPoints::find(document, layer) {
+						responses.add(DocumentMessage::AddTransaction);
+						control_points.delete_point(index, responses);
+					}
+				}
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::Delete) => {
+				request_destructive_point_edit(DestructivePointEdit::Delete, shape_editor, preferences, responses);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::BreakPath) => {
+				request_destructive_point_edit(DestructivePointEdit::BreakPath, shape_editor, preferences, responses);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::DeleteAndBreakPath) => {
+				request_destructive_point_edit(DestructivePointEdit::DeleteAndBreakPath, shape_editor, preferences, responses);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::DissolvePreservingShape) => {
+				request_destructive_point_edit(DestructivePointEdit::DissolvePreservingShape, shape_editor, preferences, responses);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::ApplyDestructivePointEdit { edit }) => {
+				match edit {
+					DestructivePointEdit::Delete => {
+						// Delete the selected points and clean up overlays
+						responses.add(DocumentMessage::AddTransaction);
+						shape_editor.delete_selected_points(document, responses);
+						responses.add(PathToolMessage::SelectionChanged);
+					}
+					DestructivePointEdit::BreakPath => shape_editor.break_path_at_selected_point(document, responses),
+					DestructivePointEdit::DeleteAndBreakPath => shape_editor.delete_point_and_break_path(document, responses),
+					DestructivePointEdit::DissolvePreservingShape => {
+						responses.add(DocumentMessage::AddTransaction);
+						shape_editor.dissolve_selected_points_preserving_shape(document, responses);
+						responses.add(PathToolMessage::SelectionChanged);
+					}
+				}
+
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::FlipSmoothSharp) => {
+				// Double-clicked on a point
+				let nearest_point = shape_editor.find_nearest_point_indices(&document.network_interface, input.mouse.position, SELECTION_THRESHOLD);
+				if nearest_point.is_some() {
+					// Flip the selected point between smooth and sharp
+					if !tool_data.double_click_handled && tool_data.drag_start_pos.distance(input.mouse.position) <= DRAG_THRESHOLD {
+						responses.add(DocumentMessage::StartTransaction);
+						shape_editor.flip_smooth_sharp(&document.network_interface, input.mouse.position, SELECTION_TOLERANCE, responses);
+						responses.add(DocumentMessage::EndTransaction);
+						responses.add(PathToolMessage::SelectedPointUpdated);
+					}
+
+					return PathToolFsmState::Ready;
+				}
+
+				// Double-clicked on a filled region
+				if let Some((layer, subpath_index)) = document.click_subpath(input) {
+					// Select only the points of the subpath which was hit
+					shape_editor.select_subpath_anchors(document, layer, subpath_index);
+				} else if let Some(layer) = document.click(input) {
+					// Select all points in the layer
+					shape_editor.select_connected_anchors(document, layer, input.mouse.position);
+				}
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::PlacePivot) => {
+				tool_data.custom_pivot = Some(document.metadata().document_to_viewport.inverse().transform_point2(input.mouse.position));
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::GRS { key }) => {
+				let transform_type = match key {
+					Key::KeyG => TransformType::Grab,
+					Key::KeyR => TransformType::Rotate,
+					Key::KeyS => TransformType::Scale,
+					_ => return self,
+				};
+
+				// The pivot is sent along every time, so it's kept when switching between grabbing, rotating, and scaling during one transform
+				responses.add(TransformLayerMessage::SetPathPivot { pivot: tool_data.custom_pivot });
+				responses.add(TransformLayerMessage::BeginGRS { transform_type });
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::Escape) => {
+				// Back out one step at a time: from an ongoing mode or preview, then from the point selection, then from the layer selection
+				if tool_data.chunked_selection.is_some() || tool_data.segment_shape_source.is_some() || tool_data.offset_preview_active {
+					// The chunks of a box or lasso selection processed so far are rolled back
+					if tool_data.chunked_selection.is_some() {
+						tool_data.cancel_marquee(shape_editor, responses);
+					}
+					tool_data.segment_shape_source = None;
+					tool_data.offset_preview_active = false;
+				} else if tool_data.custom_pivot.is_some() {
+					tool_data.custom_pivot = None;
+				} else if shape_editor.selected_points().next().is_some() {
+					shape_editor.deselect_all_points();
+					responses.add(PathToolMessage::SelectedPointUpdated);
+				} else if document.network_interface.selected_nodes().selected_layers(document.metadata()).next().is_some() {
+					responses.add(NodeGraphMessage::SelectedNodesSet { nodes: vec![] });
+				}
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready.update_hints(responses);
+				tool_data.ready_hint_context = None;
+
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::Abort) => {
+				if matches!(self, PathToolFsmState::Drawing { .. }) || tool_data.chunked_selection.is_some() {
+					tool_data.cancel_marquee(shape_editor, responses);
+				}
+				tool_data.width_drag = None;
+				tool_data.cancel_segment_drag(shape_editor);
+				tool_data.offset_preview_active = false;
+				tool_data.segment_shape_source = None;
+				tool_data.sampling_position = false;
+				tool_data.sampled_position = None;
+				tool_data.cancel_chunked_selection(responses);
+				tool_data.end_route_highlight(responses);
+				// Nudges already applied are kept as their own undo step, while any other unfinished gesture is reverted
+				tool_data.end_nudge_session(responses);
+				tool_data.abort_transaction(responses);
+				tool_data.snap_manager.cleanup(responses);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::NudgeSelectedPoints { delta_x, delta_y }) => {
+				let delta = tool_data.accelerated_nudge((delta_x, delta_y).into(), input.time, preferences.path_nudge_acceleration_cap, responses);
+
+				shape_editor.move_selected_points(
+					tool_data.opposing_handle_lengths.take(),
+					document,
+					delta,
+					true,
+					false,
+					false,
+					tool_data.opposite_handle_position,
+					false,
+					responses,
+				);
+
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::NudgeTimeout) => {
+				let released = tool_data
+					.nudge_session
+					.is_some_and(|session| input.time.saturating_sub(session.last_nudge_time) > PATH_NUDGE_SESSION_TIMEOUT);
+				if released {
+					tool_data.end_nudge_session(responses);
+				}
+
+				self
+			}
+			(_, PathToolMessage::RouteHighlightTimeout) => {
+				let expired = tool_data
+					.route_highlight
+					.as_ref()
+					.is_some_and(|highlight| input.time.saturating_sub(highlight.shown_at) > PATH_ROUTE_HIGHLIGHT_DURATION);
+				if expired {
+					tool_data.end_route_highlight(responses);
+				}
+
+				self
+			}
+			(PathToolFsmState::Ready, PathToolMessage::SelectBetween { longer_route }) => {
+				let longer_route = input.keyboard.get(longer_route as usize);
+				match shape_editor.select_between(&document.network_interface, longer_route) {
+					Ok((layer, route)) => tool_data.show_route_highlight(layer, route, input.time, responses),
+					Err(error) => {
+						// Explain why nothing was selected until the hovered anchor or the selection changes, like repairing colinearity does
+						let report = match error {
+							SelectBetweenError::NotTwoAnchors => "Select Exactly Two Anchors to Select the Points Between Them",
+							SelectBetweenError::NotConnected => "The Two Selected Anchors Aren't on the Same Subpath",
+						};
+						let hint_data = HintData(vec![HintGroup(vec![HintInfo::label(report)])]);
+						responses.add(FrontendMessage::UpdateInputHints { hint_data });
+						tool_data.ready_hint_context = Some(ReadyHintContext::new(shape_editor, document, input));
+					}
+				}
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::JoinEndpoints) => {
+				match shape_editor.selected_endpoints_to_join(document) {
+					Ok(endpoints) => {
+						responses.add(DocumentMessage::StartTransaction);
+						ShapeState::join_endpoints(document, endpoints, responses);
+						responses.add(DocumentMessage::EndTransaction);
+						responses.add(PathToolMessage::SelectionChanged);
+					}
+					Err(error) => {
+						// Explain why nothing was joined until the hovered anchor or the selection changes, like selecting between anchors does
+						let report = match error {
+							JoinEndpointsError::NotTwoAnchors => "Select Exactly Two Endpoints to Join Them",
+							JoinEndpointsError::NotEndpoints => "Only Endpoints of Open Paths Can Be Joined",
+						};
+						let hint_data = HintData(vec![HintGroup(vec![HintInfo::label(report)])]);
+						responses.add(FrontendMessage::UpdateInputHints { hint_data });
+						tool_data.ready_hint_context = Some(ReadyHintContext::new(shape_editor, document, input));
+					}
+				}
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::GrowSelection) => {
+				shape_editor.grow_selection(&document.network_interface);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::ShrinkSelection) => {
+				shape_editor.shrink_selection(&document.network_interface);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::CopySelectedPoints) => {
+				// Copying with no anchors selected keeps the points copied before
+				if let Some(copied) = shape_editor.copy_selected_points(document) {
+					tool_data.copied_points = Some(copied);
+				}
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::PasteInPlace) => {
+				tool_data.paste_points(shape_editor, document, DVec2::ZERO, responses);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::PasteAtCursor) => {
+				let cursor = document.metadata().document_to_viewport.inverse().transform_point2(input.mouse.position);
+				let offset = tool_data.copied_points.as_ref().map_or(DVec2::ZERO, |copied| cursor - copied.center());
+				tool_data.paste_points(shape_editor, document, offset, responses);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::SelectOnlyAnchors) => {
+				// Nothing needs refreshing if no handles were selected
+				if shape_editor.deselect_all_handles() {
+					responses.add(PathToolMessage::SelectedPointUpdated);
+					responses.add(OverlaysMessage::Draw);
+				}
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::SelectOnlyHandles) => {
+				// Nothing needs refreshing if no anchors were selected
+				if shape_editor.deselect_all_anchors() {
+					responses.add(PathToolMessage::SelectedPointUpdated);
+					responses.add(OverlaysMessage::Draw);
+				}
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectAllAnchors) => {
+				// Selecting all again extends the selection to the whole document
+				if shape_editor.all_anchors_selected_in_selected_layers(&document.network_interface) {
+					responses.add(PathToolMessage::SelectAllAnchorsInDocument);
+					return PathToolFsmState::Ready;
+				}
+
+				tool_data.selection_before_marquee = shape_editor.selected_points_by_layer();
+				let scan = shape_editor.selection_scan(SelectionScanTarget::AllAnchors);
+				tool_data.start_chunked_selection(scan, SelectionChange::Clear, false, shape_editor, document, responses);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::SelectNextPoint { extend }) => {
+				let extend = input.keyboard.get(extend as usize);
+				let from = tool_data
+					.traversed_point
+					.filter(|(layer, point)| shape_editor.selected_shape_state.get(layer).is_some_and(|state| state.is_selected(*point)));
+
+				tool_data.traversed_point = shape_editor.select_next_anchor(&document.network_interface, from, extend).or(from);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectAllAnchorsInDocument) => {
+				// The layer selection only changes along with the point selection once the scan finishes, so cancelling it leaves both as they were
+				tool_data.selection_before_marquee = shape_editor.selected_points_by_layer();
+				let scan = ShapeState::document_anchors_selection_scan(document);
+				tool_data.start_chunked_selection(scan, SelectionChange::Clear, true, shape_editor, document, responses);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::SelectSameKind) => {
+				tool_data.selection_before_marquee = shape_editor.selected_points_by_layer();
+				let scan = shape_editor.same_kind_selection_scan();
+				tool_data.start_chunked_selection(scan, SelectionChange::Clear, false, shape_editor, document, responses);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectPoints { points }) => {
+				shape_editor.deselect_all_points();
+				for (layer, points) in points {
+					// Skip the points which no longer exist, such as after undoing the edit that inserted them
+					let Some(vector_data) = document.metadata().layer_exists(layer).then(|| document.network_interface.compute_modified_vector(layer)).flatten() else {
+						continue;
+					};
+					let state = shape_editor.selected_shape_state.entry(layer).or_default();
+					for point in points.into_iter().filter(|point| point.get_position(&vector_data).is_some()) {
+						state.select_point(point);
+					}
+				}
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::DeselectAllPoints) => {
+				shape_editor.deselect_all_points();
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectedPointXChanged { new_x }) => {
+				tool_data.rejected_coordinate.take_if(|&mut (.., axis)| axis == AlignAxis::X);
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.set_selected_points_coordinate(&document.network_interface, AlignAxis::X, new_x, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectedPointYChanged { new_y }) => {
+				tool_data.rejected_coordinate.take_if(|&mut (.., axis)| axis == AlignAxis::Y);
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.set_selected_points_coordinate(&document.network_interface, AlignAxis::Y, new_y, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectedPointCoordinateRejected { axis }) => {
+				if let Some(&SingleSelectedPoint { id, layer, .. }) = tool_data.selection_status.as_one() {
+					tool_data.rejected_coordinate = Some((layer, id, axis));
+					responses.add(PathToolMessage::SelectedPointUpdated);
+				}
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectedHandleLengthChanged { length }) => {
+				if let Some(&SingleSelectedPoint {
+					coordinates,
+					anchor_coordinates: Some(anchor),
+					id,
+					layer,
+					..
+				}) = tool_data.selection_status.as_one()
+				{
+					let direction = (coordinates - anchor).try_normalize().unwrap_or(DVec2::X);
+					shape_editor.reposition_control_point(&id, &document.network_interface, anchor + direction * length, layer, responses);
+				}
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectedHandleAngleChanged { degrees }) => {
+				if let Some(&SingleSelectedPoint {
+					coordinates,
+					anchor_coordinates: Some(anchor),
+					id,
+					layer,
+					..
+				}) = tool_data.selection_status.as_one()
+				{
+					let length = coordinates.distance(anchor);
+					shape_editor.reposition_control_point(&id, &document.network_interface, anchor + DVec2::from_angle(degrees.to_radians()) * length, layer, responses);
+				}
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::SelectedPointUpdated) => {
+				let colinear = shape_editor.selected_manipulator_angles(&document.network_interface);
+				tool_data.dragging_state = DraggingState {
+					point_select_state: shape_editor.get_dragging_state(&document.network_interface),
+					colinear,
+					multiple_handles: shape_editor.selected_points().filter(|point| point.as_handle().is_some()).nth(1).is_some(),
+					instances: shape_editor
+						.selected_shape_state
+						.iter()
+						.filter(|(_, state)| state.selected_points_count() > 0)
+						.map(|(&layer, _)| document.metadata().instance_transforms(layer).len())
+						.max()
+						.unwrap_or_default(),
+				};
+				tool_data.update_selection_status(shape_editor, document);
+				if *point_table_open {
+					let (rows, total) = point_table_rows(&document.network_interface, shape_editor);
+					let decimal_places = preferences.display_decimal_places;
+					responses.add(PointTableMessage::UpdateRows { rows, total, decimal_places });
+				}
+				self
+			}
+			(_, PathToolMessage::RepositionPoints { repositions }) => {
+				responses.add(DocumentMessage::StartTransaction);
+				for PointReposition { layer, point, position } in repositions {
+					shape_editor.reposition_control_point(&point, &document.network_interface, position, layer, responses);
+				}
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::SmoothSelectedPoints { strength }) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.smooth_selected_points(&document.network_interface, strength, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::FilletSelectedPoints { radius }) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.fillet_selected_points(document, radius, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::MergeByDistance { tolerance }) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.merge_selected_points_by_distance(document, tolerance, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::InsertMidpoints) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.insert_selected_midpoints(document, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::ManipulatorMakeHandlesColinear) => {
+				responses.add(DocumentMessage::StartTransaction);
+				let (converted, skipped) = shape_editor.convert_selected_smooth_manipulators_to_colinear_handles(responses, document);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectionChanged);
+
+				// Report the anchors which were left alone, keeping the report shown until the hovered anchor or the selection changes
+				let anchors = |count: usize| if count == 1 { "1 Anchor".to_string() } else { format!("{count} Anchors") };
+				let report = format!("Made {} Colinear, Skipped {} Without Two Handles", anchors(converted), anchors(skipped));
+				let hint_data = HintData(vec![HintGroup(vec![HintInfo::label(report)])]);
+				responses.add(FrontendMessage::UpdateInputHints { hint_data });
+				tool_data.ready_hint_context = Some(ReadyHintContext::new(shape_editor, document, input));
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::AddPointAtLength { layer, distance }) => {
+				if let Some(segment) = shape_editor.segment_at_distance(&document.network_interface, layer, distance) {
+					tool_data.start_transaction(responses);
+					segment.adjusted_insert_and_select(shape_editor, document, tool_options.new_point_type, responses, false);
+					tool_data.end_transaction(responses);
+				}
+
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::AlignSelectedPoints { axis, aggregate }) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.align_selected_points(&document.network_interface, axis, aggregate, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::DistributeSelectedPoints { axis }) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.distribute_selected_points(&document.network_interface, axis, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::ReversePathDirection) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.reverse_selected_subpaths(&document.network_interface, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::RepairColinearity { tolerance }) => {
+				responses.add(DocumentMessage::StartTransaction);
+				let (realigned, unflagged) = shape_editor.repair_selected_colinearity(&document.network_interface, tolerance, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectionChanged);
+
+				// Report the outcome until the hovered anchor or the selection changes, like making handles colinear does
+				let anchors = |count: usize| if count == 1 { "1 Anchor".to_string() } else { format!("{count} Anchors") };
+				let report = format!("Realigned {}, Unflagged {} as Not Colinear", anchors(realigned), anchors(unflagged));
+				let hint_data = HintData(vec![HintGroup(vec![HintInfo::label(report)])]);
+				responses.add(FrontendMessage::UpdateInputHints { hint_data });
+				tool_data.ready_hint_context = Some(ReadyHintContext::new(shape_editor, document, input));
+
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready | PathToolFsmState::Dragging(_), PathToolMessage::RetractHandles) => {
+				tool_data.start_transaction(responses);
+				shape_editor.retract_selected_handles(&document.network_interface, responses);
+				// While dragging, the retraction is undone along with the rest of the drag
+				if self == PathToolFsmState::Ready {
+					tool_data.end_transaction(responses);
+				}
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+
+				self
+			}
+			(PathToolFsmState::Ready, PathToolMessage::ToggleSelectedPointsSmoothSharp) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.toggle_selected_anchors_smooth_sharp(&document.network_interface, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+				PathToolFsmState::Ready
+			}
+			(_, PathToolMessage::ManipulatorMakeHandlesFree) => {
+				responses.add(DocumentMessage::StartTransaction);
+				shape_editor.disable_colinear_handles_state_on_selected(&document.network_interface, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				PathToolFsmState::Ready
+			}
+			(PathToolFsmState::Ready, PathToolMessage::Enter { .. }) if tool_data.offset_preview_active => {
+				commit_offset(document, shape_editor, tool_options, responses);
+				tool_data.offset_preview_active = false;
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			// Insert a point where the hovered segment's preview shows it, like clicking would
+			(PathToolFsmState::Ready, PathToolMessage::Enter { extend_selection, .. }) if tool_data.segment.is_some() && !tool_data.delete_segment_pressed => {
+				let extend_selection = input.keyboard.get(extend_selection as usize);
+
+				if let Some(segment) = tool_data.segment.take() {
+					tool_data.start_transaction(responses);
+					segment.adjusted_insert_and_select(shape_editor, document, tool_options.new_point_type, responses, extend_selection);
+					tool_data.end_transaction(responses);
+				}
+
+				responses.add(PathToolMessage::SelectedPointUpdated);
+				responses.add(OverlaysMessage::Draw);
+
+				PathToolFsmState::Ready
+			}
+			// Finish the drag from the keyboard exactly as releasing the mouse would
+			(PathToolFsmState::Dragging(_), PathToolMessage::Enter { extend_selection, shrink_selection }) => {
+				responses.add(PathToolMessage::DragStop { extend_selection, shrink_selection });
+
+				self
+			}
+			(_, _) => PathToolFsmState::Ready,
+		}
+	}
+
+	fn update_hints(&self, responses: &mut VecDeque<Message>) {
+		let hint_data = match self {
+			// Replaced with the hints for the hovered anchor and the selection once the tool next processes a message
+			PathToolFsmState::Ready => Self::ready_hint_data(ReadyHintContext::default()),
+			PathToolFsmState::Dragging(dragging_state) => {
+				let colinear = dragging_state.colinear;
+				let mut dragging_hint_data = HintData(Vec::new());
+				dragging_hint_data
+					.0
+					.push(HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]));
+				dragging_hint_data.0.push(HintGroup(vec![HintInfo::keys([Key::Enter], "Confirm")]));
+
+				let drag_anchor = HintInfo::keys([Key::Space], "Drag Anchor");
+				let parallel_handles = dragging_state.multiple_handles.then(|| HintInfo::keys([Key::KeyW], "Rotate/Scale All Handles"));
+				let toggle_group = match dragging_state.point_select_state {
+					PointSelectState::HandleNoPair | PointSelectState::HandleWithPair => {
+						let mut hints = vec![HintInfo::keys([Key::Tab], "Swap Dragged Handle")];
+						hints.push(HintInfo::keys(
+							[Key::KeyC],
+							if colinear == ManipulatorAngle::Colinear {
+								"Break Colinear Handles"
+							} else {
+								"Make Handles Colinear"
+							},
+						));
+						hints
+					}
+					PointSelectState::Anchor => Vec::new(),
+				};
+				let hold_group = match dragging_state.point_select_state {
+					PointSelectState::HandleNoPair => {
+						let mut hints = vec![];
+						if colinear != ManipulatorAngle::Free {
+							hints.push(HintInfo::keys([Key::Alt], "Equidistant Handles"));
+						}
+						hints.push(HintInfo::keys([Key::Shift], "15° Increments"));
+						hints.push(HintInfo::keys([Key::Control], "Lock Angle"));
+						hints.push(HintInfo::keys([Key::KeyD], "Lock Length"));
+						hints.push(drag_anchor);
+						hints.extend(parallel_handles);
+						hints
+					}
+					PointSelectState::HandleWithPair => {
+						let mut hints = vec![];
+						if colinear != ManipulatorAngle::Free {
+							hints.push(HintInfo::keys([Key::Alt], "Equidistant Handles"));
+						}
+						hints.push(HintInfo::keys([Key::Shift], "15° Increments"));
+						hints.push(HintInfo::keys([Key::Control], "Lock Angle"));
+						hints.push(HintInfo::keys([Key::KeyD], "Lock Length"));
+						hints.push(drag_anchor);
+						hints.extend(parallel_handles);
+						hints
+					}
+					PointSelectState::Anchor => Vec::new(),
+				};
+
+				if !toggle_group.is_empty() {
+					dragging_hint_data.0.push(HintGroup(toggle_group));
+				}
+
+				if !hold_group.is_empty() {
+					dragging_hint_data.0.push(HintGroup(hold_group));
+				}
+
+				if dragging_state.instances > 0 {
+					dragging_hint_data.0.push(HintGroup(vec![Self::instances_hint(dragging_state.instances)]));
+				}
+
+				dragging_hint_data
+			}
+			PathToolFsmState::DraggingWidth => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]),
+				HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Set Width")]),
+			]),
+			PathToolFsmState::Reshaping => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]),
+				HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Reshape Paths")]),
+				HintGroup(vec![HintInfo::multi_keys([[Key::BracketLeft], [Key::BracketRight]], "Shrink/Grow Brush")]),
+			]),
+			PathToolFsmState::DraggingControlPoint => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]),
+				HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Drag Control Point")]),
+			]),
+			PathToolFsmState::DraggingSegment => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]),
+				HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Drag Segment")]),
+			]),
+			PathToolFsmState::Drawing { .. } => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Rmb, ""), HintInfo::keys([Key::Escape], "Cancel").prepend_slash()]),
+				HintGroup(vec![
+					HintInfo::mouse(MouseMotion::LmbDrag, "Select Area"),
+					HintInfo::keys([Key::Shift], "Extend").prepend_plus(),
+					HintInfo::keys([Key::Alt], "Subtract").prepend_plus(),
+				]),
+			]),
+		};
+
+		responses.add(FrontendMessage::UpdateInputHints { hint_data });
+	}
+
+	fn update_cursor(&self, responses: &mut VecDeque<Message>) {
+		responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Default });
+	}
+}
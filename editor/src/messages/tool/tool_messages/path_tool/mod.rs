@@ -0,0 +1,4659 @@
+use super::select_tool::extend_lasso;
+use super::tool_prelude::*;
+use crate::consts::{
+	COLINEARITY_REPAIR_TOLERANCE, COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_LABEL_BACKGROUND, COLOR_OVERLAY_RED, COLOR_OVERLAY_WHITE, DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD,
+	DRAG_THRESHOLD, HANDLE_ROTATE_SNAP_ANGLE, MERGE_BY_DISTANCE_TOLERANCE, PATH_DIRECTION_ARROW_SAMPLES, PATH_DIRECTION_ARROW_SIZE, PATH_DIRECTION_ARROW_SPACING, PATH_DRAG_PROMOTION_ALIGNMENT,
+	PATH_DRAG_PROMOTION_WINDOW, PATH_HOVER_RING_RADIUS, PATH_NUDGE_ACCELERATION_STEP, PATH_NUDGE_REPEAT_INTERVAL, PATH_NUDGE_SESSION_TIMEOUT, PATH_OFFSET_SCROLL_RATE, PATH_RESHAPE_DEFAULT_RADIUS,
+	PATH_ROUTE_HIGHLIGHT_DURATION, PATH_SELECTION_CHUNK_SIZE, PATH_WIDTH_HANDLE_LENGTH, POINT_TABLE_MAX_ROWS, SEGMENT_CYCLE_RESET_DISTANCE, SEGMENT_INSERTION_DISTANCE, SEGMENT_OVERLAY_SIZE,
+	SELECTION_THRESHOLD, SELECTION_TOLERANCE,
+};
+use crate::messages::layout::utility_types::number_format::{evaluate_expression, format_number};
+use crate::messages::portfolio::document::overlays::utility_functions::{path_overlays, selected_segments};
+use crate::messages::portfolio::document::overlays::utility_types::{DrawHandles, OverlayContext, Pivot};
+use crate::messages::portfolio::document::utility_types::document_metadata::LayerNodeIdentifier;
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, SNAP_FUNCTIONS_FOR_PATHS, SnappingOptions, SnappingState};
+use crate::messages::portfolio::document::utility_types::network_interface::NodeNetworkInterface;
+use crate::messages::portfolio::document::utility_types::transformation::{Axis, TransformType};
+use crate::messages::portfolio::point_table::{PointReposition, PointTableRow};
+use crate::messages::preferences::SelectionMode;
+use crate::messages::tool::common_functionality::auto_panning::AutoPanning;
+use crate::messages::tool::common_functionality::chunked_operation::ChunkedOperation;
+use crate::messages::tool::common_functionality::gesture_recording::{GestureRecording, GestureReplay};
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::measure::DistanceAlongPath;
+use crate::messages::tool::common_functionality::shape_editor::{
+	AnchorRoute, ClosestSegment, CopiedPoints, JoinEndpointsError, ManipulatorAngle, ManipulatorPointInfo, NormalizedSegmentShape, OperationIds, OpposingHandleLengths, SelectBetweenError,
+	SelectedPointsInfo, SelectionChange, SelectionHandles, SelectionHitTester, SelectionScan, SelectionScanHits, SelectionScanTarget, SelectionShape, SelectionShapeType, ShapeState,
+};
+use crate::messages::tool::common_functionality::snapping::{SnapCache, SnapCandidatePoint, SnapConstraint, SnapData, SnapManager};
+use crate::messages::tool::common_functionality::utility_functions::calculate_segment_angle;
+use bezier_rs::{Subpath, TValue};
+use graph_craft::document::NodeId;
+use graphene_core::application_io::RenderQualityHint;
+use graphene_core::renderer::Quad;
+use graphene_core::vector::algorithms::offset_subpath::offset_subpath_polyline;
+use graphene_core::vector::{ManipulatorPointId, PointId, VectorModificationType};
+use graphene_std::vector::{HandleId, NoHashBuilder, SegmentId, VectorData};
+use std::fmt;
+use std::vec;
+
+mod control_points;
+mod data;
+mod fsm;
+mod options;
+mod overlays;
+mod reshape;
+mod selection_status;
+mod width;
+
+use control_points::*;
+use data::*;
+pub use fsm::{DraggingState, PointSelectState};
+use fsm::{PathToolFsmState, ReadyHintContext};
+pub use options::{HandleAngleReference, NewPointType, PathOptionsUpdate, PathOverlayMode, PathToolOptions};
+use reshape::*;
+use selection_status::*;
+use width::*;
+
+#[derive(Default)]
+pub struct PathTool {
+	fsm_state: PathToolFsmState,
+	tool_data: PathToolData,
+	options: PathToolOptions,
+}
+
+#[impl_message(Message, ToolMessage, Path)]
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum PathToolMessage {
+	// Standard messages
+	Abort,
+	Overlays(OverlayContext),
+	SelectionChanged,
+
+	// Tool-specific messages
+	/// Inserts an anchor on the layer's path at the distance along it, splitting the segment there like clicking it would, and selects the new anchor.
+	AddPointAtLength {
+		layer: LayerNodeIdentifier,
+		distance: DistanceAlongPath,
+	},
+	/// Grows or shrinks the radius of the reshape brush by `change` viewport pixels.
+	AdjustReshapeRadius {
+		change: f64,
+	},
+	/// Aligns the selected anchors to the smallest, largest, or average of their coordinates along the axis in document space, as one undoable step.
+	AlignSelectedPoints {
+		axis: AlignAxis,
+		aggregate: AlignAggregate,
+	},
+	ApplyDestructivePointEdit {
+		edit: DestructivePointEdit,
+	},
+	ApplySampledPosition,
+	BreakPath,
+	ContinueChunkedSelection,
+	ContinueGestureReplay,
+	DeselectAllPoints,
+	Delete,
+	DeleteAndBreakPath,
+	/// Dissolves the selected anchors like [`Self::Delete`], but refits the segment joining their neighbors so the curve keeps its shape.
+	DissolvePreservingShape,
+	/// Spaces the selected anchors evenly along the axis in document space between the two outermost ones, as one undoable step.
+	DistributeSelectedPoints {
+		axis: AlignAxis,
+	},
+	DragStop {
+		extend_selection: Key,
+		shrink_selection: Key,
+	},
+	Enter {
+		extend_selection: Key,
+		shrink_selection: Key,
+	},
+	Escape,
+	ClosePath,
+	ColinearityToleranceChanged {
+		tolerance: f64,
+	},
+	CopySegmentShape,
+	/// Copies the selected anchors, along with their handles and the segments between them, to be pasted into any layer by the Path tool.
+	CopySelectedPoints,
+	CycleOverlayMode,
+	CycleSegment,
+	ExportGestureRecording,
+	FilletRadiusChanged {
+		radius: f64,
+	},
+	/// Rounds each selected anchor joined to exactly two segments with an arc `radius` along them in document space, as one undoable step.
+	FilletSelectedPoints {
+		radius: f64,
+	},
+	FlattenPathEdits,
+	FlipSmoothSharp,
+	/// Selects every anchor joined by a segment to a selected anchor.
+	GrowSelection,
+	/// Inserts an anchor at the middle of each segment between two selected anchors, keeping its shape, and selects the new anchors, as one undoable step.
+	InsertMidpoints,
+	/// Joins the only two selected anchors, which must be endpoints of open paths, with a straight segment, merging their layers if they are in different ones.
+	JoinEndpoints,
+	GRS {
+		// Should be `Key::KeyG` (Grab), `Key::KeyR` (Rotate), or `Key::KeyS` (Scale)
+		key: Key,
+	},
+	ManipulatorMakeHandlesFree,
+	ManipulatorMakeHandlesColinear,
+	/// Merges each cluster of selected anchors within `tolerance` of one another in document space into one anchor at their centroid, as one undoable step.
+	MergeByDistance {
+		tolerance: f64,
+	},
+	MergeToleranceChanged {
+		tolerance: f64,
+	},
+	MouseDown {
+		extend_selection: Key,
+		lasso_select: Key,
+		handle_drag_from_anchor: Key,
+	},
+	NudgeSelectedPoints {
+		delta_x: f64,
+		delta_y: f64,
+	},
+	NudgeTimeout,
+	OffsetDistanceChanged {
+		distance: f64,
+	},
+	OffsetScroll,
+	/// Pastes the copied points into the targeted layer centered on the cursor, selecting them, as one undoable step.
+	PasteAtCursor,
+	/// Pastes the copied points into the targeted layer where they were copied from, selecting them, as one undoable step.
+	PasteInPlace,
+	/// Places the pivot which rotating and scaling the selected points with R and S happens about at the cursor.
+	PlacePivot,
+	/// Reverses the direction of every subpath containing a selected anchor, as one undoable step.
+	ReversePathDirection,
+	/// Replays a gesture recording exported as JSON, at a multiple of the speed it was recorded at. An infinite speed replays it all at once.
+	ReplayGestures {
+		recording: String,
+		speed: f64,
+	},
+	PointerMove {
+		equidistant: Key,
+		toggle_colinear: Key,
+		move_anchor_with_handles: Key,
+		snap_angle: Key,
+		lock_angle: Key,
+		lock_length: Key,
+		delete_segment: Key,
+		parallel_handles: Key,
+		extend_selection: Key,
+		shrink_selection: Key,
+	},
+	PointerOutsideViewport {
+		equidistant: Key,
+		toggle_colinear: Key,
+		move_anchor_with_handles: Key,
+		snap_angle: Key,
+		lock_angle: Key,
+		lock_length: Key,
+		delete_segment: Key,
+		parallel_handles: Key,
+		extend_selection: Key,
+		shrink_selection: Key,
+	},
+	/// Realigns the handles flagged as colinear at the selected anchors which deviate from a straight line by at most `tolerance` (in radians), and unflags the others.
+	RepairColinearity {
+		tolerance: f64,
+	},
+	ReshapeRadiusChanged {
+		radius: f64,
+	},
+	/// Moves each point to its new position in document space, as one undoable step.
+	RepositionPoints {
+		#[specta(skip)]
+		repositions: Vec<PointReposition>,
+	},
+	/// Retracts the selected handles, and both handles of the selected anchors, making the ends of their segments sharp.
+	RetractHandles,
+	RightClick,
+	/// Ends the highlight of the route last selected between two anchors once it has been shown long enough.
+	RouteHighlightTimeout,
+	SamplePosition,
+	/// Exports the viewport and selections as an edit session file, which restores them when imported with the same document open.
+	SaveEditSession,
+	/// Selects all the anchors of the selected layers, or of every layer in the document if they are already selected.
+	SelectAllAnchors,
+	/// Selects every visible and unlocked vector layer in the document along with all their anchors.
+	SelectAllAnchorsInDocument,
+	/// Moves the selection to the anchor after the only selected point along its subpath, or adds it to the selection while `extend` is held.
+	SelectNextPoint {
+		extend: Key,
+	},
+	/// Deselects the selected handles, keeping only the selected anchors.
+	SelectOnlyAnchors,
+	/// Deselects the selected anchors, keeping only the selected handles.
+	SelectOnlyHandles,
+	/// Selects every anchor of the selected layers which is sharp or smooth like any of the selected anchors.
+	SelectSameKind,
+	/// Selects every anchor along the route between the only two selected anchors, which goes the longer way around a closed subpath while `longer_route` is held.
+	SelectBetween {
+		longer_route: Key,
+	},
+	/// Turns the only selected point, if it's a handle, to point this many degrees from the X axis in document space, keeping its length.
+	SelectedHandleAngleChanged {
+		degrees: f64,
+	},
+	/// Moves the only selected point, if it's a handle, to this distance from its anchor in document space, keeping its angle.
+	SelectedHandleLengthChanged {
+		length: f64,
+	},
+	SelectedPointUpdated,
+	SelectedPointXChanged {
+		new_x: f64,
+	},
+	SelectedPointYChanged {
+		new_y: f64,
+	},
+	/// Marks the only selected point's X or Y field as holding an expression which didn't evaluate to a finite coordinate.
+	SelectedPointCoordinateRejected {
+		axis: AlignAxis,
+	},
+	/// Replaces the selected points with these, adding their layers to those being edited and skipping any points that no longer exist.
+	SelectPoints {
+		#[specta(skip)]
+		points: Vec<(LayerNodeIdentifier, Vec<ManipulatorPointId>)>,
+	},
+	/// Deselects the selected anchors joined by a segment to an unselected anchor.
+	ShrinkSelection,
+	SmoothingStrengthChanged {
+		strength: f64,
+	},
+	/// Recomputes the handles of the selected anchors from their neighboring anchors, blended by `strength` (from 0 to 1), and marks the lined up pairs as colinear, as one undoable step.
+	SmoothSelectedPoints {
+		strength: f64,
+	},
+	SwapSelectedHandles,
+	/// Makes the selected anchors smooth if any of them is sharp, or otherwise makes them all sharp, as one undoable step.
+	ToggleSelectedPointsSmoothSharp,
+	UpdateOptions(PathOptionsUpdate),
+}
+
+/// An edit to the selected points which can break apart the geometry that other nodes depend on, so it may be confirmed before being applied.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum DestructivePointEdit {
+	Delete,
+	BreakPath,
+	DeleteAndBreakPath,
+	DissolvePreservingShape,
+}
+
+/// Applies the edit to the selected points, or first has the document confirm it when enabled in the preferences, since their layers may have many downstream dependents.
+fn request_destructive_point_edit(edit: DestructivePointEdit, shape_editor: &ShapeState, preferences: &PreferencesMessageHandler, responses: &mut VecDeque<Message>) {
+	if !preferences.confirm_destructive_point_edits {
+		responses.add(PathToolMessage::ApplyDestructivePointEdit { edit });
+		return;
+	}
+
+	let layers = shape_editor
+		.selected_shape_state
+		.iter()
+		.filter(|(_, state)| state.selected_points_count() > 0)
+		.map(|(&layer, _)| layer)
+		.collect();
+	responses.add(DocumentMessage::ConfirmDestructivePointEdit { layers, edit });
+}
+
+impl ToolMetadata for PathTool {
+	fn icon_name(&self) -> String {
+		"VectorPathTool".into()
+	}
+	fn tooltip(&self) -> String {
+		"Path Tool".into()
+	}
+	fn tool_type(&self) -> crate::messages::tool::utility_types::ToolType {
+		ToolType::Path
+	}
+}
+
+impl PathTool {
+	/// Adds the message to the gesture recording, beginning a new recording from the current viewport and selection if there isn't one.
+	fn record_gesture(&mut self, message: &ToolMessage, tool_data: &ToolActionHandlerData, responses: &mut VecDeque<Message>) {
+		let ToolMessage::Path(message) = message else { return };
+		// Drawing overlays and working with recordings aren't part of the gestures, and messages the tool sends itself are left out since replaying the gestures sends them again
+		if matches!(
+			message,
+			PathToolMessage::Overlays(_)
+				| PathToolMessage::ContinueGestureReplay
+				| PathToolMessage::ExportGestureRecording
+				| PathToolMessage::ReplayGestures { .. }
+				| PathToolMessage::SaveEditSession
+				| PathToolMessage::SelectionChanged
+				| PathToolMessage::SelectedPointUpdated
+				| PathToolMessage::ContinueChunkedSelection
+				| PathToolMessage::NudgeTimeout
+				| PathToolMessage::RouteHighlightTimeout
+				| PathToolMessage::ApplyDestructivePointEdit { .. }
+		) {
+			return;
+		}
+
+		if self.tool_data.gesture_recording.is_none() {
+			self.tool_data.gesture_recording = Some(GestureRecording::start(tool_data.document, tool_data.shape_editor, tool_data.input.time));
+			// Shows the button for exporting the recording
+			self.send_layout(responses, LayoutTarget::ToolOptions);
+		}
+		if let Some(recording) = &mut self.tool_data.gesture_recording {
+			recording.record(message.clone(), tool_data.input);
+		}
+	}
+
+	fn stop_gesture_replay(&mut self, responses: &mut VecDeque<Message>) {
+		if self.tool_data.gesture_replay.take().is_some() {
+			responses.add(BroadcastMessage::UnsubscribeEvent {
+				on: BroadcastEvent::AnimationFrame,
+				message: Box::new(PathToolMessage::ContinueGestureReplay.into()),
+			});
+		}
+	}
+}
+
+impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for PathTool {
+	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
+		let updating_point = message == ToolMessage::Path(PathToolMessage::SelectedPointUpdated);
+		let drawing_overlays = matches!(message, ToolMessage::Path(PathToolMessage::Overlays(_)));
+		if message == ToolMessage::UpdateHints {
+			self.tool_data.ready_hint_context = None;
+		}
+		let previous_state = self.fsm_state;
+
+		if tool_data.preferences.path_gesture_recording {
+			self.record_gesture(&message, tool_data, responses);
+		}
+
+		// Record the selection from before this message changes it, which a transaction started by the message saves as the selection its undo restores
+		tool_data.document.selected_points = tool_data.shape_editor.selected_points_by_layer();
+
+		// Keep the snapping popover in sync with changes made to the document's snapping settings from anywhere
+		if self.tool_data.snapping_state != tool_data.document.snapping_state {
+			self.tool_data.snapping_state = tool_data.document.snapping_state.clone();
+			self.send_layout(responses, LayoutTarget::ToolOptions);
+		}
+		if self.tool_data.display_decimal_places != tool_data.preferences.display_decimal_places {
+			self.tool_data.display_decimal_places = tool_data.preferences.display_decimal_places;
+			self.send_layout(responses, LayoutTarget::ToolOptions);
+		}
+
+		match message {
+			ToolMessage::Path(PathToolMessage::UpdateOptions(action)) => {
+				match action {
+					PathOptionsUpdate::OverlayModeType(overlay_mode_type) => {
+						self.options.path_overlay_mode = overlay_mode_type;
+						responses.add(OverlaysMessage::Draw);
+					}
+					PathOptionsUpdate::OffsetReplacesPath(offset_replaces_path) => {
+						self.options.offset_replaces_path = offset_replaces_path;
+					}
+					PathOptionsUpdate::HandleAngleReference(handle_angle_reference) => {
+						self.options.handle_angle_reference = handle_angle_reference;
+					}
+					PathOptionsUpdate::NewPointType(new_point_type) => {
+						self.options.new_point_type = new_point_type;
+						responses.add(OverlaysMessage::Draw);
+					}
+					PathOptionsUpdate::EditWidths(edit_widths) => {
+						self.options.edit_widths = edit_widths;
+						responses.add(OverlaysMessage::Draw);
+					}
+					PathOptionsUpdate::ShowDirection(show_direction) => {
+						self.options.show_direction = show_direction;
+						responses.add(OverlaysMessage::Draw);
+					}
+					PathOptionsUpdate::Reshape(reshape) => {
+						self.options.reshape = reshape;
+						responses.add(OverlaysMessage::Draw);
+					}
+					PathOptionsUpdate::MarqueeAnchorsOnly(marquee_anchors_only) => {
+						self.options.marquee_anchors_only = marquee_anchors_only;
+					}
+				}
+
+				// Options may also be changed by shortcuts, so the widgets are refreshed to show the applied value
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			ToolMessage::Path(PathToolMessage::CycleOverlayMode) => {
+				let overlay_mode_type = self.options.path_overlay_mode.next();
+				responses.add(PathToolMessage::UpdateOptions(PathOptionsUpdate::OverlayModeType(overlay_mode_type)));
+			}
+			ToolMessage::Path(PathToolMessage::OffsetDistanceChanged { distance }) => {
+				self.options.offset_distance = distance;
+				self.tool_data.offset_preview_active = true;
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+				responses.add(OverlaysMessage::Draw);
+			}
+			ToolMessage::Path(PathToolMessage::SmoothingStrengthChanged { strength }) => {
+				self.options.smoothing_strength = strength;
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			ToolMessage::Path(PathToolMessage::ReshapeRadiusChanged { radius }) => {
+				self.options.reshape_radius = radius.max(1.);
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+				responses.add(OverlaysMessage::Draw);
+			}
+			ToolMessage::Path(PathToolMessage::AdjustReshapeRadius { change }) => {
+				let radius = self.options.reshape_radius + change;
+				responses.add(PathToolMessage::ReshapeRadiusChanged { radius });
+			}
+			ToolMessage::Path(PathToolMessage::FilletRadiusChanged { radius }) => {
+				self.options.fillet_radius = radius;
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			ToolMessage::Path(PathToolMessage::ColinearityToleranceChanged { tolerance }) => {
+				self.options.colinearity_tolerance = tolerance;
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			ToolMessage::Path(PathToolMessage::MergeToleranceChanged { tolerance }) => {
+				self.options.merge_tolerance = tolerance;
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			ToolMessage::Path(PathToolMessage::OffsetScroll) => {
+				let delta = -tool_data.input.mouse.scroll_delta.y * PATH_OFFSET_SCROLL_RATE;
+				self.options.offset_distance = ((self.options.offset_distance + delta) * 10.).round() / 10.;
+				self.tool_data.offset_preview_active = true;
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+				responses.add(OverlaysMessage::Draw);
+			}
+			ToolMessage::Path(PathToolMessage::ContinueChunkedSelection) => {
+				if self.tool_data.continue_chunked_selection(tool_data.shape_editor, tool_data.document, responses) {
+					self.fsm_state.update_hints(responses);
+				}
+			}
+			ToolMessage::Path(PathToolMessage::ClosePath) => {
+				responses.add(DocumentMessage::AddTransaction);
+				tool_data.shape_editor.close_selected_path(tool_data.document, responses);
+				responses.add(DocumentMessage::EndTransaction);
+				responses.add(OverlaysMessage::Draw);
+			}
+			ToolMessage::Path(PathToolMessage::CopySegmentShape) => {
+				let Some(shape) = self.tool_data.segment.as_ref().and_then(ClosestSegment::normalized_shape) else {
+					return;
+				};
+
+				self.tool_data.segment_shape_source = Some(shape);
+				responses.add(OverlaysMessage::Draw);
+			}
+			ToolMessage::Path(PathToolMessage::SamplePosition) => {
+				self.tool_data.sampling_position = true;
+				responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Crosshair });
+			}
+			ToolMessage::Path(PathToolMessage::MouseDown { .. }) if self.tool_data.sampling_position => {
+				// The selection is left alone, so the sampled position can then be applied to the selected point
+				let document = tool_data.document;
+				let Some((layer, point)) = ShapeState::find_nearest_anchor_in_document(&document.network_interface, tool_data.input.mouse.position, SELECTION_THRESHOLD) else {
+					return;
+				};
+				let Some(position) = document
+					.network_interface
+					.compute_modified_vector(layer)
+					.and_then(|vector_data| vector_data.point_domain.position_from_id(point))
+				else {
+					return;
+				};
+
+				self.tool_data.sampled_position = Some(document.metadata().transform_to_document(layer).transform_point2(position));
+				self.tool_data.sampling_position = false;
+				responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Default });
+				self.fsm_state.update_hints(responses);
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+				return;
+			}
+			ToolMessage::Path(PathToolMessage::Escape) if self.tool_data.sampling_position => {
+				self.tool_data.sampling_position = false;
+				responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Default });
+				self.fsm_state.update_hints(responses);
+				return;
+			}
+			ToolMessage::Path(PathToolMessage::ApplySampledPosition) => {
+				let Some(position) = self.tool_data.sampled_position.take() else { return };
+				if let Some(&SingleSelectedPoint { id, layer, .. }) = self.tool_data.selection_status.as_one() {
+					responses.add(DocumentMessage::AddTransaction);
+					tool_data.shape_editor.reposition_control_point(&id, &tool_data.document.network_interface, position, layer, responses);
+					responses.add(PathToolMessage::SelectedPointUpdated);
+				}
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			ToolMessage::Path(PathToolMessage::ExportGestureRecording) => {
+				let Some(recording) = self.tool_data.gesture_recording.take() else { return };
+				match serde_json::to_string_pretty(&recording) {
+					Ok(document) => responses.add(FrontendMessage::TriggerDownloadTextFile {
+						document,
+						name: "path-tool-gestures.json".to_string(),
+					}),
+					Err(error) => log::warn!("Could not export the Path tool gesture recording: {error}"),
+				}
+				self.send_layout(responses, LayoutTarget::ToolOptions);
+			}
+			ToolMessage::Path(PathToolMessage::SaveEditSession) => {
+				let selected_points = tool_data.shape_editor.selected_points_by_layer();
+				responses.add(PortfolioMessage::ExportEditSession {
+					selected_points,
+					overlay_mode: self.options.path_overlay_mode,
+				});
+			}
+			ToolMessage::Path(PathToolMessage::ReplayGestures { recording, speed }) => {
+				if speed.is_nan() || speed <= 0. {
+					log::warn!("Cannot replay Path tool gestures at a speed of {speed}");
+					return;
+				}
+				let recording = match serde_json::from_str::<GestureRecording>(&recording) {
+					Ok(recording) => recording,
+					Err(error) => {
+						log::warn!("Could not read the Path tool gesture recording: {error}");
+						return;
+					}
+				};
+
+				self.stop_gesture_replay(responses);
+				recording.restore_initial_state(tool_data.shape_editor, responses);
+				if speed.is_infinite() {
+					recording.replay_all(responses);
+				} else {
+					responses.add(BroadcastMessage::SubscribeEvent {
+						on: BroadcastEvent::AnimationFrame,
+						send: Box::new(PathToolMessage::ContinueGestureReplay.into()),
+					});
+					self.tool_data.gesture_replay = Some(GestureReplay::new(recording, speed));
+				}
+			}
+			ToolMessage::Path(PathToolMessage::ContinueGestureReplay) => {
+				let frame_duration = tool_data.input.frame_time.frame_duration().unwrap_or_default();
+				if self.tool_data.gesture_replay.as_mut().is_none_or(|replay| replay.advance(frame_duration, responses)) {
+					self.stop_gesture_replay(responses);
+				}
+			}
+			ToolMessage::Path(PathToolMessage::FlattenPathEdits) => {
+				let document = tool_data.document;
+				let layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect::<Vec<_>>();
+				if layers.is_empty() {
+					return;
+				}
+
+				responses.add(DocumentMessage::AddTransaction);
+				for layer in layers {
+					responses.add(GraphOperationMessage::FlattenPathEdits { layer });
+				}
+				responses.add(OverlaysMessage::Draw);
+			}
+			ToolMessage::Path(PathToolMessage::SwapSelectedHandles) => {
+				if tool_data.shape_editor.handle_with_pair_selected(&tool_data.document.network_interface) {
+					tool_data.shape_editor.alternate_selected_handles(&tool_data.document.network_interface);
+					responses.add(PathToolMessage::SelectedPointUpdated);
+					responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::None });
+					responses.add(OverlaysMessage::Draw);
+				}
+			}
+			_ => {
+				self.fsm_state.process_event(message, &mut self.tool_data, tool_data, &self.options, responses, true);
+			}
+		}
+
+		// Hints sent upon entering a state replace those of the hovered anchor and the selection
+		if self.fsm_state != previous_state {
+			self.tool_data.ready_hint_context = None;
+		}
+
+		// Renders during a drag may trade quality for responsiveness, with a final quality render following whenever the drag pauses
+		let interactive = |state: PathToolFsmState| {
+			matches!(
+				state,
+				PathToolFsmState::Dragging(_) | PathToolFsmState::Drawing { .. } | PathToolFsmState::DraggingControlPoint | PathToolFsmState::DraggingSegment | PathToolFsmState::Reshaping
+			)
+		};
+		if interactive(self.fsm_state) != interactive(previous_state) {
+			let quality = if interactive(self.fsm_state) { RenderQualityHint::Interactive } else { RenderQualityHint::Final };
+			responses.add(PortfolioMessage::SetRenderQuality { quality });
+		}
+
+		// Keep the progress of an ongoing chunked selection visible, since state transitions would otherwise replace it with the usual hints
+		if let Some(selection) = &self.tool_data.chunked_selection {
+			responses.add(FrontendMessage::UpdateInputHints {
+				hint_data: selection.operation.progress_hints("Selecting Points"),
+			});
+			self.tool_data.ready_hint_context = None;
+		}
+		// Likewise for the armed position sampling
+		else if self.tool_data.sampling_position {
+			let hint_data = HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Sample Anchor Position")]),
+				HintGroup(vec![HintInfo::keys([Key::Escape], "Cancel")]),
+			]);
+			responses.add(FrontendMessage::UpdateInputHints { hint_data });
+			self.tool_data.ready_hint_context = None;
+		}
+		// Likewise for the segment shape copy mode
+		else if self.tool_data.segment_shape_source.is_some() {
+			let hint_data = HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Apply Shape to Segment")]),
+				HintGroup(vec![HintInfo::keys([Key::KeyK], "Copy Hovered Segment Shape")]),
+				HintGroup(vec![HintInfo::keys([Key::Escape], "Done")]),
+			]);
+			responses.add(FrontendMessage::UpdateInputHints { hint_data });
+			self.tool_data.ready_hint_context = None;
+		}
+		// Otherwise show the hints for the hovered anchor and the selection, only sending them when those change rather than upon every pointer move
+		else if self.fsm_state == PathToolFsmState::Ready && !drawing_overlays {
+			let context = ReadyHintContext::new(tool_data.shape_editor, tool_data.document, tool_data.input);
+			if self.tool_data.ready_hint_context != Some(context) {
+				self.tool_data.ready_hint_context = Some(context);
+				responses.add(FrontendMessage::UpdateInputHints {
+					hint_data: PathToolFsmState::ready_hint_data(context),
+				});
+			}
+		}
+
+		if updating_point {
+			self.send_layout(responses, LayoutTarget::ToolOptions);
+		}
+	}
+
+	// Different actions depending on state may be wanted:
+	fn actions(&self) -> ActionList {
+		match self.fsm_state {
+			PathToolFsmState::Ready if self.tool_data.chunked_selection.is_some() => actions!(PathToolMessageDiscriminant;
+				Escape,
+				MouseDown,
+				PointerMove,
+			),
+			PathToolFsmState::Ready if self.tool_data.sampling_position => actions!(PathToolMessageDiscriminant;
+				Escape,
+				MouseDown,
+				PointerMove,
+			),
+			PathToolFsmState::Ready if self.tool_data.segment_shape_source.is_some() => actions!(PathToolMessageDiscriminant;
+				CopySegmentShape,
+				Escape,
+				MouseDown,
+				PointerMove,
+			),
+			PathToolFsmState::Ready => actions!(PathToolMessageDiscriminant;
+				AdjustReshapeRadius,
+				CopySegmentShape,
+				CopySelectedPoints,
+				CycleOverlayMode,
+				Escape,
+				CycleSegment,
+				UpdateOptions,
+				FlipSmoothSharp,
+				MouseDown,
+				Delete,
+				NudgeSelectedPoints,
+				Enter,
+				OffsetScroll,
+				SelectAllAnchors,
+				SelectAllAnchorsInDocument,
+				SelectNextPoint,
+				SelectBetween,
+				GrowSelection,
+				ShrinkSelection,
+				DeselectAllPoints,
+				BreakPath,
+				DeleteAndBreakPath,
+				DissolvePreservingShape,
+				ClosePath,
+				JoinEndpoints,
+				RetractHandles,
+				ToggleSelectedPointsSmoothSharp,
+				PasteAtCursor,
+				PasteInPlace,
+				PlacePivot,
+				GRS,
+				PointerMove,
+			),
+			PathToolFsmState::Dragging(_) => actions!(PathToolMessageDiscriminant;
+				CycleOverlayMode,
+				UpdateOptions,
+				Enter,
+				Escape,
+				RightClick,
+				FlipSmoothSharp,
+				DragStop,
+				PointerMove,
+				Delete,
+				BreakPath,
+				DeleteAndBreakPath,
+				DissolvePreservingShape,
+				SwapSelectedHandles,
+				RetractHandles,
+			),
+			PathToolFsmState::Drawing { .. } => actions!(PathToolMessageDiscriminant;
+				FlipSmoothSharp,
+				DragStop,
+				PointerMove,
+				Delete,
+				Enter,
+				BreakPath,
+				DeleteAndBreakPath,
+				DissolvePreservingShape,
+				Escape,
+				RightClick,
+			),
+			PathToolFsmState::Reshaping => actions!(PathToolMessageDiscriminant;
+				AdjustReshapeRadius,
+				DragStop,
+				PointerMove,
+				Escape,
+				RightClick,
+			),
+			PathToolFsmState::DraggingWidth | PathToolFsmState::DraggingControlPoint | PathToolFsmState::DraggingSegment => actions!(PathToolMessageDiscriminant;
+				DragStop,
+				PointerMove,
+				Escape,
+				RightClick,
+			),
+		}
+	}
+}
+
+impl ToolTransition for PathTool {
+	fn event_to_message_map(&self) -> EventToMessageMap {
+		EventToMessageMap {
+			tool_abort: Some(PathToolMessage::Abort.into()),
+			selection_changed: Some(PathToolMessage::SelectionChanged.into()),
+			overlay_provider: Some(|overlay_context| PathToolMessage::Overlays(overlay_context).into()),
+			..Default::default()
+		}
+	}
+}
+
+#[cfg(test)]
+mod test_path {
+	use super::{HandleAngleReference, NewPointType, NormalizedSegmentShape, PathOptionsUpdate, PathOverlayMode, nudge_acceleration};
+	use crate::consts::{COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_RED};
+	use crate::consts::{DISPLAY_DECIMAL_PLACES, PATH_DIRECTION_ARROW_SIZE, PATH_NUDGE_ACCELERATION_STEP, PATH_ROUTE_HIGHLIGHT_DURATION, PATH_SELECTION_CHUNK_SIZE, PATH_WIDTH_HANDLE_LENGTH};
+	use crate::messages::input_mapper::utility_types::input_mouse::EditorMouseState;
+	use crate::messages::layout::utility_types::layout_widget::{DiffUpdate, Widget, WidgetLayout};
+	use crate::messages::layout::utility_types::number_format::{format_number, parse_number};
+	use crate::messages::layout::utility_types::widgets::input_widgets::{CheckboxInput, NumberInput};
+	use crate::messages::portfolio::document::overlays::utility_types::{OverlayCommand, OverlayRecording};
+	use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, SNAP_FUNCTIONS_FOR_PATHS, SnappingState};
+	use crate::messages::portfolio::document::utility_types::network_interface::TransactionStatus;
+	use crate::messages::portfolio::utility_types::EditSession;
+	use crate::messages::tool::common_functionality::measure::DistanceAlongPath;
+	use crate::messages::tool::common_functionality::shape_editor::{ColinearityRepair, SelectionHitTester, SelectionShape, ShapeState};
+	use crate::test_utils::test_prelude::*;
+	use bezier_rs::{Bezier, BezierHandles, ManipulatorGroup, Subpath, SubpathTValue, TValue};
+	use graphene_core::vector::{ManipulatorPointId, PointId, VectorData, VectorModificationType};
+	use graphene_std::vector::{HandleId, SegmentId};
+
+	async fn point_positions(editor: &mut EditorTestUtils) -> Vec<DVec2> {
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		let transform = document.metadata().transform_to_viewport(layer);
+
+		vector_data.point_domain.positions().iter().map(|&position| transform.transform_point2(position)).collect()
+	}
+
+	/// Moves the mouse, returning the labels of the last hints sent in response, or `None` if none were sent.
+	fn pointer_move_hint_labels(editor: &mut EditorTestUtils, position: DVec2) -> Option<Vec<String>> {
+		let editor_mouse_state = EditorMouseState {
+			editor_position: position,
+			..Default::default()
+		};
+		let responses = editor.editor.handle_message(InputPreprocessorMessage::PointerMove {
+			editor_mouse_state,
+			modifier_keys: ModifierKeys::empty(),
+		});
+
+		responses.into_iter().rev().find_map(|message| match message {
+			FrontendMessage::UpdateInputHints { hint_data } => Some(hint_data.0.iter().flat_map(|group| &group.0).map(|hint| hint.label.to_string()).collect()),
+			_ => None,
+		})
+	}
+
+	async fn near_miss_drag(editor: &mut EditorTestUtils, path: &[DVec2]) {
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.drag_path(path, ModifierKeys::empty()).await;
+	}
+
+	#[tokio::test]
+	async fn near_miss_heading_through_point_drags_it() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+
+		// Starts 12 px from the corner at (50, 50), just outside the selection threshold, then moves towards it
+		near_miss_drag(&mut editor, &[DVec2::new(38., 50.), DVec2::new(44., 50.), DVec2::new(70., 60.)]).await;
+
+		let positions = point_positions(&mut editor).await;
+		assert!(
+			positions.iter().any(|position| position.distance(DVec2::new(70., 60.)) < 1e-3),
+			"The missed point was not dragged: {positions:?}"
+		);
+		assert!(
+			!positions.iter().any(|position| position.distance(DVec2::new(50., 50.)) < 1e-3),
+			"The missed point stayed in place: {positions:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn near_miss_heading_away_draws_marquee() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+
+		near_miss_drag(&mut editor, &[DVec2::new(38., 50.), DVec2::new(32., 50.), DVec2::new(20., 40.)]).await;
+
+		let positions = point_positions(&mut editor).await;
+		assert!(positions.iter().any(|position| position.distance(DVec2::new(50., 50.)) < 1e-3), "A point was dragged: {positions:?}");
+	}
+
+	#[tokio::test]
+	async fn near_miss_promotion_respects_preference() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.handle_message(PreferencesMessage::PathDragPromotion { enabled: false }).await;
+
+		near_miss_drag(&mut editor, &[DVec2::new(38., 50.), DVec2::new(44., 50.), DVec2::new(70., 60.)]).await;
+
+		let positions = point_positions(&mut editor).await;
+		assert!(positions.iter().any(|position| position.distance(DVec2::new(50., 50.)) < 1e-3), "A point was dragged: {positions:?}");
+		assert!(!positions.iter().any(|position| position.distance(DVec2::new(70., 60.)) < 1e-3), "A point was dragged: {positions:?}");
+	}
+
+	/// Draws a rectangle with the Path tool active, returning the undo history length afterwards.
+	async fn rect_in_path_tool(editor: &mut EditorTestUtils) -> usize {
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.active_document().undo_history_len()
+	}
+
+	async fn assert_aborted(editor: &mut EditorTestUtils, undo_history_len: usize) {
+		editor.handle_message(BroadcastEvent::ToolAbort).await;
+
+		let document = editor.active_document();
+		assert_eq!(document.undo_history_len(), undo_history_len, "The aborted gesture left an undo step");
+		assert_eq!(
+			document.network_interface.transaction_status(),
+			TransactionStatus::Finished,
+			"The aborted gesture left its transaction open"
+		);
+
+		let positions = point_positions(editor).await;
+		assert!(
+			positions.iter().any(|position| position.distance(DVec2::new(50., 50.)) < 1e-3),
+			"The aborted gesture was not reverted: {positions:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn abort_point_drag_reverts_without_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		let undo_history_len = rect_in_path_tool(&mut editor).await;
+
+		editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(80., 70., ModifierKeys::empty(), MouseKeys::LEFT).await;
+
+		assert_aborted(&mut editor, undo_history_len).await;
+	}
+
+	#[tokio::test]
+	async fn abort_promoted_drag_reverts_without_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		let undo_history_len = rect_in_path_tool(&mut editor).await;
+
+		editor.move_mouse(38., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(38., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(44., 50., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		editor.move_mouse(70., 60., ModifierKeys::empty(), MouseKeys::LEFT).await;
+
+		assert_aborted(&mut editor, undo_history_len).await;
+	}
+
+	#[tokio::test]
+	async fn abort_layer_click_leaves_no_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		let undo_history_len = rect_in_path_tool(&mut editor).await;
+
+		// Inside the filled region, away from every point and segment
+		editor.move_mouse(100., 100., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(100., 100., ModifierKeys::empty()).await;
+
+		assert_aborted(&mut editor, undo_history_len).await;
+	}
+
+	#[tokio::test]
+	async fn switching_tools_mid_drag_reverts_without_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		let undo_history_len = rect_in_path_tool(&mut editor).await;
+
+		editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(80., 70., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		editor.select_tool(ToolType::Select).await;
+
+		let document = editor.active_document();
+		assert_eq!(document.undo_history_len(), undo_history_len, "The interrupted drag left an undo step");
+		assert_eq!(
+			document.network_interface.transaction_status(),
+			TransactionStatus::Finished,
+			"The interrupted drag left its transaction open"
+		);
+	}
+
+	#[tokio::test]
+	async fn abort_after_nudge_keeps_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(50., 50.), ModifierKeys::empty()).await;
+		let undo_history_len = editor.active_document().undo_history_len();
+		editor.handle_message(PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: 0. }).await;
+		editor.handle_message(PathToolMessage::NudgeSelectedPoints { delta_x: 1., delta_y: 0. }).await;
+		editor.handle_message(BroadcastEvent::ToolAbort).await;
+
+		let document = editor.active_document();
+		assert_eq!(document.undo_history_len(), undo_history_len + 1, "The nudges were not committed as a single undo step");
+		assert_eq!(
+			document.network_interface.transaction_status(),
+			TransactionStatus::Finished,
+			"The nudge session left its transaction open"
+		);
+	}
+
+	#[tokio::test]
+	async fn dragging_point_reports_dirty_region() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+
+		editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+
+		// Move without rendering in between, so the render made for this move is the one inspected below
+		let editor_mouse_state = EditorMouseState {
+			editor_position: DVec2::new(80., 70.),
+			mouse_keys: MouseKeys::LEFT,
+			..Default::default()
+		};
+		editor.editor.handle_message(InputPreprocessorMessage::PointerMove {
+			editor_mouse_state,
+			modifier_keys: ModifierKeys::empty(),
+		});
+		editor.runtime.run().await;
+
+		let mut messages = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut messages).expect("Graph should render");
+		let dirty_region = messages
+			.into_iter()
+			.find_map(|message| match message {
+				Message::Frontend(FrontendMessage::UpdateDocumentArtworkDirtyRegion { dirty_region }) => Some(dirty_region),
+				_ => None,
+			})
+			.expect("The render should be tagged with its dirty region")
+			.expect("The drag should report the region it changed");
+
+		// The moved corner of the rectangle has no handles, so the region spans exactly its old and new positions
+		let [min, max] = dirty_region.map(DVec2::from);
+		assert!(min.abs_diff_eq(DVec2::new(50., 50.), 1e-3), "Unexpected dirty region minimum {min}");
+		assert!(max.abs_diff_eq(DVec2::new(80., 70.), 1e-3), "Unexpected dirty region maximum {max}");
+	}
+
+	/// Each segment of the selected layer with its Bézier curve in layer space and its midpoint in viewport space.
+	fn segment_curves(editor: &EditorTestUtils) -> Vec<(SegmentId, Bezier, DVec2)> {
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		let transform = document.metadata().transform_to_viewport(layer);
+
+		vector_data
+			.segment_bezier_iter()
+			.map(|(id, bezier, _, _)| (id, bezier, transform.transform_point2(bezier.evaluate(TValue::Parametric(0.5)))))
+			.collect()
+	}
+
+	fn assert_same_shape(a: &Bezier, b: &Bezier) {
+		let [a, b] = [a, b].map(|bezier| NormalizedSegmentShape::from_bezier(bezier).unwrap().handles);
+		for (a, b) in a.into_iter().zip(b) {
+			let (a, b) = (a.expect("Missing handle"), b.expect("Missing handle"));
+			assert!((a.0 - b.0).abs() < 1e-6 && (a.1 - b.1).abs() < 1e-6, "Handle {a:?} differs from {b:?}");
+		}
+	}
+
+	#[tokio::test]
+	async fn copy_segment_shape_applies_to_clicked_segments() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_ellipse(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Lengthen one handle so the source differs from the other quarters of the ellipse
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (source, source_bezier, _) = segment_curves(&editor)[0];
+		let relative_position = source_bezier.handle_start().unwrap() - source_bezier.start;
+		let modification_type = VectorModificationType::SetPrimaryHandle {
+			segment: source,
+			relative_position: relative_position * 1.5,
+		};
+		editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+
+		let segments = segment_curves(&editor);
+		let (_, source_bezier, source_midpoint) = segments[0];
+		let (target, _, target_midpoint) = segments[1];
+		editor.move_mouse(source_midpoint.x, source_midpoint.y, ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.press(Key::KeyK, ModifierKeys::empty()).await;
+
+		let undo_history_len = editor.active_document().undo_history_len();
+		editor.drag_path(&[target_midpoint], ModifierKeys::empty()).await;
+		assert_eq!(editor.active_document().undo_history_len(), undo_history_len + 1, "Each applied segment should be one undo step");
+
+		let segments = segment_curves(&editor);
+		let (_, target_bezier, _) = segments.iter().find(|(id, _, _)| *id == target).unwrap();
+		assert_same_shape(&source_bezier, target_bezier);
+
+		// After leaving the copy mode, clicking a segment inserts a point on it again
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+		let (_, _, other_midpoint) = segments[2];
+		editor.move_mouse(other_midpoint.x, other_midpoint.y, ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.drag_path(&[other_midpoint], ModifierKeys::empty()).await;
+		assert_eq!(segment_curves(&editor).len(), segments.len() + 1, "The segment should have been split by an inserted point");
+	}
+
+	/// Opens the document in a new editor and inserts a point on the segment at the position, returning the ids of the points and segments afterwards.
+	async fn insert_point_in_copy(document_serialized_content: &str, position: DVec2) -> (Vec<PointId>, Vec<SegmentId>) {
+		let mut editor = EditorTestUtils::create();
+		editor
+			.handle_message(PortfolioMessage::OpenDocumentFile {
+				document_name: "Copy".to_string(),
+				document_serialized_content: document_serialized_content.to_string(),
+			})
+			.await;
+		editor.handle_message(DocumentMessage::SelectAllLayers).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.move_mouse(position.x, position.y, ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.press(Key::Enter, ModifierKeys::empty()).await;
+
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		(vector_data.point_domain.ids().to_vec(), vector_data.segment_domain.ids().to_vec())
+	}
+
+	#[tokio::test]
+	async fn inserting_points_in_copies_of_a_document_creates_the_same_ids() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+		editor.handle_message(DocumentMessage::SetDeterministicIds { enabled: true }).await;
+		let document_serialized_content = editor.active_document().serialize_document();
+
+		let position = DVec2::new(100., 50.);
+		let (points, segments) = insert_point_in_copy(&document_serialized_content, position).await;
+		assert_eq!(points.len(), 5, "The point wasn't inserted");
+		assert_eq!(insert_point_in_copy(&document_serialized_content, position).await, (points, segments));
+	}
+
+	#[tokio::test]
+	async fn dragging_multi_selection_moves_grabbed_point_by_cursor_delta() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+		select_points(&mut editor, &[DVec2::new(50., 50.), DVec2::new(150., 50.)]).await;
+
+		// Grab the first corner slightly off its center so a jump to the cursor would be visible
+		let start = DVec2::new(52., 51.);
+		let end = DVec2::new(92., 81.);
+		editor.drag_path(&[start, end], ModifierKeys::empty()).await;
+
+		let positions = point_positions(&mut editor).await;
+		let delta = end - start;
+		for expected in [DVec2::new(50., 50.) + delta, DVec2::new(150., 50.) + delta] {
+			assert!(positions.iter().any(|position| position.abs_diff_eq(expected, 1e-3)), "No point moved to {expected}: {positions:?}");
+		}
+	}
+
+	#[tokio::test]
+	async fn enter_inserts_point_on_hovered_segment() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+		let segment_count = segment_curves(&editor).len();
+
+		editor.move_mouse(100., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.press(Key::Enter, ModifierKeys::empty()).await;
+
+		assert_eq!(segment_curves(&editor).len(), segment_count + 1, "The hovered segment should have been split by an inserted point");
+	}
+
+	#[tokio::test]
+	async fn cycling_overlapping_segments_retargets_insertion() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		let lower = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(50., 50., 250., 150.).await;
+		let upper = editor.get_selected_layer().await.unwrap();
+		editor
+			.handle_message(NodeGraphMessage::SelectedNodesSet {
+				nodes: vec![lower.to_node(), upper.to_node()],
+			})
+			.await;
+		editor.select_tool(ToolType::Path).await;
+
+		let point_count = |editor: &EditorTestUtils, layer: LayerNodeIdentifier| editor.active_document().network_interface.compute_modified_vector(layer).unwrap().point_domain.ids().len();
+		let counter_text = |recording: &OverlayRecording| {
+			recording.commands().into_iter().find_map(|command| match command {
+				OverlayCommand::Text { text, .. } if text.contains('/') => Some(text),
+				_ => None,
+			})
+		};
+
+		// Both top edges overlap under the cursor, and the upper layer's is targeted first
+		editor.move_mouse(100., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		assert_eq!(counter_text(&record_path_overlays(&mut editor).await), None, "The counter should only be shown while cycling");
+
+		editor.press(Key::Tab, ModifierKeys::empty()).await;
+		assert_eq!(counter_text(&record_path_overlays(&mut editor).await).as_deref(), Some("2/2"));
+		editor.press(Key::Tab, ModifierKeys::empty()).await;
+		assert_eq!(
+			counter_text(&record_path_overlays(&mut editor).await).as_deref(),
+			Some("1/2"),
+			"Cycling should loop back around to the upper segment"
+		);
+		editor.press(Key::Tab, ModifierKeys::empty()).await;
+
+		editor.press(Key::Enter, ModifierKeys::empty()).await;
+		assert_eq!(point_count(&editor, lower), 5, "The point should be inserted on the lower segment cycled to");
+		assert_eq!(point_count(&editor, upper), 4);
+	}
+
+	/// Inserts a point by clicking a segment of a rectangle or a circle, returning the lengths of the new point's handles.
+	async fn inserted_handle_lengths(new_point_type: NewPointType, circle: bool) -> Vec<f64> {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let position = if circle {
+			editor.drag_tool(ToolType::Ellipse, 50., 50., 150., 150., ModifierKeys::empty()).await;
+			DVec2::new(100., 100.) + DVec2::from_angle(-std::f64::consts::FRAC_PI_4) * 50.
+		} else {
+			editor.draw_rect(50., 50., 150., 150.).await;
+			DVec2::new(100., 50.)
+		};
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::NewPointType(new_point_type))).await;
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, position, ModifierKeys::empty()).await;
+
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		let transform = document.metadata().transform_to_viewport(layer);
+		let inserted = vector_data
+			.point_domain
+			.ids()
+			.iter()
+			.zip(vector_data.point_domain.positions())
+			.find(|(_, point)| transform.transform_point2(**point).distance(position) < 0.5)
+			.map(|(&id, _)| id)
+			.expect("No point was inserted where the segment was clicked");
+
+		vector_data
+			.segment_bezier_iter()
+			.filter_map(|(_, bezier, start, end)| match (start == inserted, end == inserted) {
+				(true, _) => Some(bezier.handle_start().map_or(0., |handle| handle.distance(bezier.start))),
+				(_, true) => Some(bezier.handle_end().map_or(0., |handle| handle.distance(bezier.end))),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn inserted_point_handles_follow_new_point_type() {
+		let retracted = |lengths: &[f64]| lengths.len() == 2 && lengths.iter().all(|&length| length < 1e-6);
+		let extended = |lengths: &[f64]| lengths.len() == 2 && lengths.iter().all(|&length| length > 1.);
+
+		// Match Segment only adds handles where the segment is curved
+		let lengths = inserted_handle_lengths(NewPointType::MatchSegment, false).await;
+		assert!(retracted(&lengths), "A point on a straight segment shouldn't get handles: {lengths:?}");
+		let lengths = inserted_handle_lengths(NewPointType::MatchSegment, true).await;
+		assert!(extended(&lengths), "A point on a curved segment should get handles: {lengths:?}");
+
+		// Smooth adds handles along straight segments, a third of the way to the neighboring anchors
+		let lengths = inserted_handle_lengths(NewPointType::Smooth, false).await;
+		assert!(
+			lengths.len() == 2 && lengths.iter().all(|&length| (length - 50. / 3.).abs() < 1e-6),
+			"Unexpected handle lengths {lengths:?}"
+		);
+		let lengths = inserted_handle_lengths(NewPointType::Smooth, true).await;
+		assert!(extended(&lengths), "A point on a curved segment should get handles: {lengths:?}");
+
+		// Sharp always retracts them
+		let lengths = inserted_handle_lengths(NewPointType::Sharp, false).await;
+		assert!(retracted(&lengths), "Unexpected handle lengths {lengths:?}");
+		let lengths = inserted_handle_lengths(NewPointType::Sharp, true).await;
+		assert!(retracted(&lengths), "Unexpected handle lengths {lengths:?}");
+	}
+
+	#[tokio::test]
+	async fn sharp_insert_preview_has_no_ghost_handles() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+		editor.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::NewPointType(NewPointType::Sharp))).await;
+
+		let on_segment = DVec2::new(100., 100.) + DVec2::from_angle(-std::f64::consts::FRAC_PI_4) * 50.;
+		editor.move_mouse(on_segment.x, on_segment.y, ModifierKeys::empty(), MouseKeys::empty()).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+		assert!(handle_positions(&recording).is_empty(), "No handles should be previewed for a sharp point");
+	}
+
+	#[tokio::test]
+	async fn dragging_points_sends_one_vector_message_per_layer() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		let before = point_positions(&mut editor).await;
+
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let mut shape_editor = ShapeState::default();
+		shape_editor.set_selected_layers(vec![layer]);
+		shape_editor.select_all_anchors_in_layer(document, layer);
+
+		let delta = DVec2::new(10., 5.);
+		let mut responses = VecDeque::new();
+		shape_editor.move_selected_points(None, document, delta, false, true, false, None, false, &mut responses);
+
+		let graph_operations = responses
+			.iter()
+			.filter(|message| matches!(message, Message::Portfolio(PortfolioMessage::Document(DocumentMessage::GraphOperation(_)))))
+			.count();
+		assert_eq!(graph_operations, 1, "Moving every anchor of one layer should take a single message");
+
+		for message in responses {
+			editor.handle_message(message).await;
+		}
+		let after = point_positions(&mut editor).await;
+		assert_eq!(before.len(), after.len());
+		for (before, after) in before.into_iter().zip(after) {
+			assert!((after - before - delta).length() < 1e-6, "Expected {before} to move by {delta}, but it is now at {after}");
+		}
+	}
+
+	#[tokio::test]
+	async fn dragging_handles_in_parallel_rotates_and_scales_them_alike() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		let transform = document.metadata().transform_to_viewport(layer);
+
+		// Handles at two different anchors, each with a colinear partner which is left unselected
+		let segments = vector_data.segment_domain.ids();
+		let [dragged, other] = [segments[0], segments[2]].map(ManipulatorPointId::PrimaryHandle);
+		let [dragged_partner, other_partner] = [dragged, other].map(|handle| vector_data.other_colinear_handle(handle.as_handle().unwrap()).unwrap().to_manipulator_point());
+		let [dragged_position, other_position] = [dragged, other].map(|handle| transform.transform_point2(handle.get_position(&vector_data).unwrap()));
+		let handles = [dragged, other, dragged_partner, other_partner];
+		let relative_to_anchors = |editor: &EditorTestUtils| {
+			let vector_data = editor.active_document().network_interface.compute_modified_vector(layer).unwrap();
+			handles.map(|handle| handle.get_position(&vector_data).unwrap() - vector_data.point_domain.position_from_id(handle.get_anchor(&vector_data).unwrap()).unwrap())
+		};
+		let before = relative_to_anchors(&editor);
+
+		select_points(&mut editor, &[dragged_position, other_position]).await;
+		let key_repeat = false;
+		editor
+			.handle_message(InputPreprocessorMessage::KeyDown {
+				key: Key::KeyW,
+				modifier_keys: ModifierKeys::empty(),
+				key_repeat,
+			})
+			.await;
+		editor.drag_path(&[dragged_position, dragged_position + DVec2::new(10., 15.)], ModifierKeys::empty()).await;
+		editor
+			.handle_message(InputPreprocessorMessage::KeyUp {
+				key: Key::KeyW,
+				modifier_keys: ModifierKeys::empty(),
+				key_repeat,
+			})
+			.await;
+		let after = relative_to_anchors(&editor);
+
+		let angle = |index: usize| before[index].angle_to(after[index]);
+		let scale = |index: usize| after[index].length() / before[index].length();
+		assert!(angle(0).abs() > 1e-3 && (scale(0) - 1.).abs() > 1e-3, "The dragged handle should have been rotated and scaled");
+		assert!((angle(1) - angle(0)).abs() < 1e-6, "The other selected handle should turn by the same angle as the dragged one");
+		assert!((scale(1) - scale(0)).abs() < 1e-6, "The other selected handle should be scaled by the same amount as the dragged one");
+		for partner in [2, 3] {
+			assert!((angle(partner) - angle(0)).abs() < 1e-6, "The unselected colinear partners should turn along to stay colinear");
+			assert!((scale(partner) - 1.).abs() < 1e-6, "The unselected colinear partners should keep their length");
+		}
+
+		// The selected handles and their partners are moved by a single message for the layer
+		let document = editor.active_document();
+		let mut shape_editor = ShapeState::default();
+		shape_editor.set_selected_layers(vec![layer]);
+		let state = shape_editor.selected_shape_state.get_mut(&layer).unwrap();
+		state.select_point(dragged);
+		state.select_point(other);
+		let mut responses = VecDeque::new();
+		shape_editor.rotate_and_scale_selected_handles(document, 0.1, 1.5, &mut responses);
+		assert_eq!(responses.len(), 1);
+		assert!(
+			matches!(
+				responses.front(),
+				Some(Message::Portfolio(PortfolioMessage::Document(DocumentMessage::GraphOperation(GraphOperationMessage::VectorBatch { modifications, .. })))) if modifications.len() == 4
+			),
+			"Expected one batch of all four handle moves, but got {responses:?}"
+		);
+	}
+
+	/// Draws polylines of `anchors` anchors each, one above the other, returning their layers.
+	async fn draw_polylines(editor: &mut EditorTestUtils, layers: usize, anchors: usize) -> Vec<LayerNodeIdentifier> {
+		let mut drawn = Vec::new();
+		for layer in 0..layers {
+			let positions = (0..anchors).map(|index| DVec2::new(50. + 2. * index as f64, 100. + 100. * layer as f64 + 10. * (index % 2) as f64));
+			drawn.push(editor.draw_subpaths(vec![Subpath::from_anchors_linear(positions, false)]).await);
+		}
+		drawn
+	}
+
+	fn all_anchors_selected(document: &DocumentMessageHandler, layers: &[LayerNodeIdentifier]) -> ShapeState {
+		let mut shape_editor = ShapeState::default();
+		shape_editor.set_selected_layers(layers.to_vec());
+		for &layer in layers {
+			shape_editor.select_all_anchors_in_layer(document, layer);
+		}
+		shape_editor
+	}
+
+	fn anchor_positions_in_layers(editor: &EditorTestUtils, layers: &[LayerNodeIdentifier]) -> Vec<DVec2> {
+		let document = editor.active_document();
+		let positions = layers.iter().flat_map(|&layer| {
+			let transform = document.metadata().transform_to_viewport(layer);
+			let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+			vector_data.point_domain.positions().iter().map(|&position| transform.transform_point2(position)).collect::<Vec<_>>()
+		});
+		positions.collect()
+	}
+
+	#[tokio::test]
+	async fn dragging_many_points_sends_one_vector_message_per_layer_and_undoes_in_one_step() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let layers = draw_polylines(&mut editor, 2, 250).await;
+		let before = anchor_positions_in_layers(&editor, &layers);
+		assert_eq!(before.len(), 500);
+
+		// The number of messages only grows with the layers, not the points
+		let document = editor.active_document();
+		let shape_editor = all_anchors_selected(document, &layers);
+		let mut responses = VecDeque::new();
+		shape_editor.move_selected_points(None, document, DVec2::new(10., 5.), false, true, false, None, false, &mut responses);
+		let graph_operations = responses
+			.iter()
+			.filter(|message| matches!(message, Message::Portfolio(PortfolioMessage::Document(DocumentMessage::GraphOperation(_)))))
+			.count();
+		assert_eq!(graph_operations, layers.len(), "Moving 500 points should take one message per layer");
+
+		// Dragging them all with the Path tool is undone at once
+		editor
+			.handle_message(DocumentMessage::SetSnapping {
+				closure: Some(|snapping_state| &mut snapping_state.snapping_enabled),
+				snapping_state: false,
+			})
+			.await;
+		let nodes = layers.iter().map(|layer| layer.to_node()).collect();
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes }).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		let delta = DVec2::new(20., 30.);
+		editor.drag_path(&[before[0], before[0] + delta / 2., before[0] + delta], ModifierKeys::empty()).await;
+
+		let after = anchor_positions_in_layers(&editor, &layers);
+		for (before, after) in before.iter().zip(&after) {
+			assert!((*after - *before - delta).length() < 1e-6, "Expected {before} to move by {delta}, but it is now at {after}");
+		}
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		let undone = anchor_positions_in_layers(&editor, &layers);
+		for (before, undone) in before.iter().zip(&undone) {
+			assert!((*undone - *before).length() < 1e-6, "Expected {before} to be restored by one undo, but it is at {undone}");
+		}
+	}
+
+	#[tokio::test]
+	#[cfg_attr(debug_assertions, ignore = "Timing is only meaningful in optimized builds")]
+	async fn dragging_many_points_is_fast() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let layers = draw_polylines(&mut editor, 2, 250).await;
+		let document = editor.active_document();
+		let shape_editor = all_anchors_selected(document, &layers);
+
+		const FRAMES: u32 = 100;
+		let start = std::time::Instant::now();
+		for _ in 0..FRAMES {
+			let mut responses = VecDeque::new();
+			shape_editor.move_selected_points(None, document, DVec2::new(1., 1.), false, true, false, None, false, &mut responses);
+			std::hint::black_box(responses);
+		}
+
+		let per_frame = start.elapsed() / FRAMES;
+		assert!(per_frame < std::time::Duration::from_millis(2), "Moving 500 points took {per_frame:?} per frame");
+	}
+
+	#[tokio::test]
+	async fn escape_deselects_points_then_layers() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(50., 50.), ModifierKeys::empty()).await;
+		let positions = point_positions(&mut editor).await;
+
+		// The first Escape deselects the points, so nudging no longer moves any
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+		editor.press(Key::ArrowRight, ModifierKeys::empty()).await;
+		assert_eq!(point_positions(&mut editor).await, positions);
+		assert!(editor.get_selected_layer().await.is_some(), "The layer should stay selected while points were deselected");
+
+		// The second Escape deselects the layer
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+		assert!(editor.get_selected_layer().await.is_none());
+	}
+
+	#[tokio::test]
+	async fn escape_during_drag_only_cancels_the_drag() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(50., 50.), ModifierKeys::empty()).await;
+		let positions = point_positions(&mut editor).await;
+
+		editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(80., 70., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+		let editor_mouse_state = EditorMouseState {
+			editor_position: DVec2::new(80., 70.),
+			..Default::default()
+		};
+		editor.mouseup(editor_mouse_state, ModifierKeys::empty()).await;
+		assert_eq!(point_positions(&mut editor).await, positions);
+
+		// The point stays selected, so it is still nudged
+		editor.press(Key::ArrowRight, ModifierKeys::empty()).await;
+		assert_ne!(point_positions(&mut editor).await, positions);
+		assert!(editor.get_selected_layer().await.is_some());
+	}
+
+	#[tokio::test]
+	async fn hovering_highlights_the_point_or_segment_a_click_would_hit() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		let highlights = |recording: &OverlayRecording| {
+			let rings = recording
+				.commands()
+				.into_iter()
+				.filter_map(|command| match command {
+					OverlayCommand::Circle { position, .. } => Some(position),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			let strokes = recording.commands().into_iter().filter(|command| matches!(command, OverlayCommand::Outline { .. })).count();
+			(rings, strokes)
+		};
+
+		// Over a corner, the corner is ringed and no segment is stroked
+		editor.move_mouse(52., 51., ModifierKeys::empty(), MouseKeys::empty()).await;
+		let (rings, _) = highlights(&record_path_overlays(&mut editor).await);
+		assert_eq!(rings.len(), 1);
+		assert!(rings[0].abs_diff_eq(DVec2::new(50., 50.), 1e-6), "The ring should be around the corner, not {:?}", rings[0]);
+
+		// Over the middle of an edge, the ring goes away and the edge is stroked over instead
+		editor.move_mouse(100., 51., ModifierKeys::empty(), MouseKeys::empty()).await;
+		let (rings, strokes_over_edge) = highlights(&record_path_overlays(&mut editor).await);
+		assert!(rings.is_empty());
+		editor.move_mouse(100., 100., ModifierKeys::empty(), MouseKeys::empty()).await;
+		let (_, strokes_away) = highlights(&record_path_overlays(&mut editor).await);
+		assert_eq!(strokes_over_edge, strokes_away + 1, "The hovered edge should be stroked over");
+
+		// Pressing on the corner clears its highlight for the drag
+		editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(60., 60., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		let (rings, _) = highlights(&record_path_overlays(&mut editor).await);
+		assert!(rings.is_empty(), "The ring shouldn't be drawn while dragging");
+	}
+
+	#[tokio::test]
+	async fn direction_arrows_are_spaced_along_the_selected_paths_at_a_fixed_size() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+		let without_arrows = lines(&record_path_overlays(&mut editor).await);
+
+		editor.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::ShowDirection(true))).await;
+		let with_arrows = lines(&record_path_overlays(&mut editor).await);
+		let arrow_lines = with_arrows.iter().filter(|line| !without_arrows.contains(line)).collect::<Vec<_>>();
+
+		// The circle is about 314 px around, so arrows fit at 40, 120, 200, and 280 px along it, each drawn as two lines from its tip
+		assert_eq!(arrow_lines.len(), 8, "Unexpected arrow lines: {arrow_lines:?}");
+		for (tip, end, _) in arrow_lines {
+			assert!((tip.distance(DVec2::new(100., 100.)) - 50.).abs() < 0.5, "The arrow tip {tip:?} should lie on the circle");
+			assert!(
+				(tip.distance(*end) - PATH_DIRECTION_ARROW_SIZE).abs() < 1e-6,
+				"The arrow side from {tip:?} to {end:?} should have a fixed size"
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn dragging_width_handle_sets_point_width() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::EditWidths(true))).await;
+
+		let corner_width = |editor: &mut EditorTestUtils| {
+			let document = editor.active_document();
+			let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+			let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+			let transform = document.metadata().transform_to_viewport(layer);
+			let (&point, _) = vector_data
+				.point_domain
+				.ids()
+				.iter()
+				.zip(vector_data.point_domain.positions())
+				.find(|(_, position)| transform.transform_point2(**position).abs_diff_eq(DVec2::new(50., 50.), 1e-3))
+				.unwrap();
+			vector_data.point_domain.width_from_id(point).unwrap()
+		};
+		assert_eq!(corner_width(&mut editor), 1.);
+
+		// The corner's width handles lie on its diagonal, so dragging one twice as far from the corner doubles the width without moving the points
+		let positions = point_positions(&mut editor).await;
+		let diagonal = DVec2::ONE.normalize();
+		let handle = DVec2::new(50., 50.) + diagonal * PATH_WIDTH_HANDLE_LENGTH;
+		let dragged = DVec2::new(50., 50.) + diagonal * PATH_WIDTH_HANDLE_LENGTH * 2.;
+		editor.left_mousedown(handle.x, handle.y, ModifierKeys::empty()).await;
+		editor.move_mouse(dragged.x, dragged.y, ModifierKeys::empty(), MouseKeys::LEFT).await;
+		let editor_mouse_state = EditorMouseState {
+			editor_position: dragged,
+			..Default::default()
+		};
+		editor.mouseup(editor_mouse_state, ModifierKeys::empty()).await;
+		assert!((corner_width(&mut editor) - 2.).abs() < 1e-6);
+		assert_eq!(point_positions(&mut editor).await, positions);
+
+		// A pen sets the width from its pressure as soon as it presses the handle
+		let handle = DVec2::new(50., 50.) + diagonal * PATH_WIDTH_HANDLE_LENGTH * 2.;
+		let editor_mouse_state = EditorMouseState {
+			editor_position: handle,
+			mouse_keys: MouseKeys::LEFT,
+			..Default::default()
+		}
+		.with_pen(Some(0.25), DVec2::ZERO);
+		editor.mousedown(editor_mouse_state, ModifierKeys::empty()).await;
+		editor
+			.mouseup(
+				EditorMouseState {
+					mouse_keys: MouseKeys::empty(),
+					..editor_mouse_state
+				},
+				ModifierKeys::empty(),
+			)
+			.await;
+		assert!((corner_width(&mut editor) - 0.5).abs() < 1e-6);
+	}
+
+	#[tokio::test]
+	async fn destructive_point_edits_on_layers_with_many_dependents_are_confirmed() {
+		use super::DestructivePointEdit;
+		use crate::consts::DESTRUCTIVE_POINT_EDIT_DEPENDENTS_THRESHOLD;
+		use crate::messages::portfolio::document::node_graph::document_node_definitions::resolve_document_node_type;
+		use crate::messages::portfolio::document::utility_types::network_interface::{InputConnector, OutputConnector};
+		use graph_craft::document::NodeId;
+
+		/// Wires a chain of nodes onto the output of the upstream node, which each depend on it, returning the last one.
+		async fn chain_dependents(editor: &mut EditorTestUtils, mut upstream: NodeId, count: usize) -> NodeId {
+			for _ in 0..count {
+				let node_id = NodeId::new();
+				let node_template = resolve_document_node_type("Identity").expect("Identity node does not exist").default_node_template();
+				editor.handle_message(NodeGraphMessage::InsertNode { node_id, node_template }).await;
+				editor
+					.handle_message(NodeGraphMessage::CreateWire {
+						output_connector: OutputConnector::node(upstream, 0),
+						input_connector: InputConnector::node(node_id, 0),
+					})
+					.await;
+				upstream = node_id;
+			}
+			upstream
+		}
+
+		/// Deletes the selected points, returning whether a dialog was opened to confirm it instead.
+		async fn delete_opens_dialog(editor: &mut EditorTestUtils) -> bool {
+			let responses = editor.editor.handle_message(PathToolMessage::Delete);
+			editor.eval_graph().await;
+			responses.iter().any(|response| matches!(response, FrontendMessage::DisplayDialog { .. }))
+		}
+
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+		editor.select_tool(ToolType::Path).await;
+
+		// Up to the threshold, the points are deleted right away
+		let last_dependent = chain_dependents(&mut editor, layer.to_node(), DESTRUCTIVE_POINT_EDIT_DEPENDENTS_THRESHOLD).await;
+		editor.drag_path(&[DVec2::new(0., 0.)], ModifierKeys::empty()).await;
+		assert!(!delete_opens_dialog(&mut editor).await);
+		assert_eq!(point_positions(&mut editor).await.len(), 3);
+
+		// Beyond it, the deletion waits for confirmation
+		chain_dependents(&mut editor, last_dependent, 1).await;
+		editor.drag_path(&[DVec2::new(100., 100.)], ModifierKeys::empty()).await;
+		let positions = point_positions(&mut editor).await;
+		assert!(delete_opens_dialog(&mut editor).await);
+		assert_eq!(point_positions(&mut editor).await, positions);
+
+		// Confirming deletes the point that was selected when asked
+		let edit = DestructivePointEdit::Delete;
+		let followups = vec![PathToolMessage::ApplyDestructivePointEdit { edit }.into()];
+		editor.handle_message(DialogMessage::CloseDialogAndThen { followups }).await;
+		let remaining = point_positions(&mut editor).await;
+		assert_eq!(remaining.len(), 2);
+		assert!(!remaining.iter().any(|position| position.abs_diff_eq(DVec2::new(100., 100.), 1e-6)));
+
+		// Turning off the confirmation in the preferences deletes right away again
+		editor.handle_message(PreferencesMessage::ConfirmDestructivePointEdits { enabled: false }).await;
+		editor.drag_path(&[remaining[0]], ModifierKeys::empty()).await;
+		assert!(!delete_opens_dialog(&mut editor).await);
+		assert_eq!(point_positions(&mut editor).await.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn control_points_of_procedural_layer_are_edited_in_its_node_input() {
+		use crate::messages::portfolio::document::node_graph::document_node_definitions::resolve_document_node_type;
+		use graph_craft::document::value::TaggedValue;
+		use graph_craft::document::{NodeId, NodeInput};
+
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let triangle = vec![DVec2::new(0., 0.), DVec2::new(100., 0.), DVec2::new(100., 100.)];
+		let polygon = resolve_document_node_type("Polygon")
+			.expect("Polygon node does not exist")
+			.node_template_input_override([None, Some(NodeInput::value(TaggedValue::VecDVec2(triangle), false))]);
+		let id = NodeId::new();
+		let nodes = vec![(NodeId(0), polygon)];
+		editor
+			.handle_message(GraphOperationMessage::NewCustomLayer {
+				id,
+				nodes,
+				parent: LayerNodeIdentifier::ROOT_PARENT,
+				insert_index: 0,
+			})
+			.await;
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![id] }).await;
+		editor.select_tool(ToolType::Path).await;
+
+		let vertices = |editor: &EditorTestUtils| {
+			let layer = NodeGraphLayer::new(LayerNodeIdentifier::new_unchecked(id), &editor.active_document().network_interface);
+			match layer.find_input("Polygon", 1) {
+				Some(TaggedValue::VecDVec2(vertices)) => vertices.clone(),
+				input => panic!("The vertices should be a list of points, not {input:?}"),
+			}
+		};
+		let document_to_viewport = editor.active_document().metadata().document_to_viewport;
+		let viewport = |x: f64, y: f64| document_to_viewport.transform_point2(DVec2::new(x, y));
+
+		// Dragging a vertex moves it within the node's input
+		editor.drag_path(&[viewport(100., 0.), viewport(110., 5.), viewport(120., 10.)], ModifierKeys::empty()).await;
+		let dragged = vertices(&editor);
+		assert_eq!(dragged.len(), 3);
+		assert!(dragged[1].abs_diff_eq(DVec2::new(120., 10.), 1e-6), "The vertex wasn't dragged: {dragged:?}");
+
+		// Clicking the edge closing the outline grows the list with a vertex there
+		editor.drag_path(&[viewport(50., 50.)], ModifierKeys::empty()).await;
+		let inserted = vertices(&editor);
+		assert_eq!(inserted.len(), 4);
+		assert!(inserted[3].abs_diff_eq(DVec2::new(50., 50.), 1e-6), "The vertex wasn't inserted: {inserted:?}");
+
+		// Deleting shrinks the list by the vertex last clicked
+		editor.press(Key::Delete, ModifierKeys::empty()).await;
+		assert_eq!(vertices(&editor), dragged);
+	}
+
+	#[tokio::test]
+	async fn dragging_a_segment_moves_both_of_its_anchors_in_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		let original = point_positions(&mut editor).await;
+
+		// Grab the top side away from its corners
+		editor.drag_tool(ToolType::Path, 100., 50., 113., 67., ModifierKeys::empty()).await;
+		let expected = original.iter().map(|&point| if point.y == 50. { point + DVec2::new(13., 17.) } else { point }).collect::<Vec<_>>();
+		let dragged = point_positions(&mut editor).await;
+		assert!(
+			dragged.iter().zip(&expected).all(|(dragged, expected)| dragged.abs_diff_eq(*expected, 1e-6)),
+			"Expected {expected:?} but got {dragged:?}"
+		);
+		assert_eq!(dragged.len(), 4, "Dragging a segment shouldn't insert a point on it");
+		assert!(selected_anchor_positions(&mut editor).await.is_empty(), "The anchors should only be selected while dragging");
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await, original);
+
+		// Clicking the segment without dragging still inserts a point on it
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(100., 50.), ModifierKeys::empty()).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 5);
+	}
+
+	#[tokio::test]
+	async fn clicking_segments_in_a_row_inserts_a_point_each_as_separate_undo_steps() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// There's no insertion mode to return to, since hovering a segment in the Ready state always previews the point a click inserts
+		for (index, x) in [80., 120.].into_iter().enumerate() {
+			editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(x, 50.), ModifierKeys::empty()).await;
+			assert_eq!(point_positions(&mut editor).await.len(), 5 + index);
+		}
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		let positions = point_positions(&mut editor).await;
+		assert!(
+			positions.len() == 5 && positions.iter().any(|position| position.abs_diff_eq(DVec2::new(80., 50.), 1e-6)),
+			"Undo should only remove the last point: {positions:?}"
+		);
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 4);
+	}
+
+	#[tokio::test]
+	async fn undo_and_redo_restore_the_point_selection_of_each_history_state() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Undoing a deletion selects the deleted points again
+		let deleted = [DVec2::new(50., 50.), DVec2::new(150., 50.)];
+		select_points(&mut editor, &deleted).await;
+		editor.press(Key::Delete, ModifierKeys::empty()).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 2);
+		assert!(selected_anchor_positions(&mut editor).await.is_empty());
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 4);
+		assert_eq!(selected_anchor_positions(&mut editor).await, deleted);
+
+		// Redoing it leaves nothing selected, as after the deletion
+		editor.handle_message(DocumentMessage::Redo).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 2);
+		assert!(selected_anchor_positions(&mut editor).await.is_empty());
+		editor.handle_message(DocumentMessage::Undo).await;
+
+		// Undoing an insertion drops the inserted point from the selection rather than keeping its missing id
+		editor.handle_message(PathToolMessage::DeselectAllPoints).await;
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(100., 150.), ModifierKeys::empty()).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 5);
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 4);
+		assert!(selected_anchor_positions(&mut editor).await.is_empty());
+	}
+
+	#[tokio::test]
+	async fn escape_cancels_a_segment_drag_and_restores_the_selection() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		let original = point_positions(&mut editor).await;
+		select_points(&mut editor, &[DVec2::new(150., 150.)]).await;
+
+		editor.move_mouse(100., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(100., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(110., 70., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		assert_ne!(point_positions(&mut editor).await, original);
+
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+		assert_eq!(point_positions(&mut editor).await, original);
+		assert_eq!(selected_anchor_positions(&mut editor).await, vec![DVec2::new(150., 150.)]);
+	}
+
+	#[tokio::test]
+	async fn returning_to_the_path_tool_restores_the_point_selection_of_reselected_layers() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		let first = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(200., 50., 300., 150.).await;
+		let second = editor.get_selected_layer().await.unwrap();
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![first.to_node()] }).await;
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[DVec2::new(50., 50.), DVec2::new(150., 150.)]).await;
+
+		// Selecting another layer in the Select tool and then the first one again drops its point selection, which the Path tool remembers
+		editor.select_tool(ToolType::Select).await;
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![second.to_node()] }).await;
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![first.to_node()] }).await;
+		editor.select_tool(ToolType::Path).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await, vec![DVec2::new(50., 50.), DVec2::new(150., 150.)]);
+
+		// The memory is only for returning to the tool, so a layer reselected within it starts without any selected points
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![second.to_node()] }).await;
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![first.to_node()] }).await;
+		assert!(selected_anchor_positions(&mut editor).await.is_empty());
+	}
+
+	#[tokio::test]
+	async fn box_selection_previews_the_modifiers_held_and_applies_those_held_on_release() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[DVec2::new(150., 150.)]).await;
+
+		editor.left_mousedown(20., 20., ModifierKeys::empty()).await;
+		let tints = [
+			(ModifierKeys::SHIFT, COLOR_OVERLAY_GREEN),
+			(ModifierKeys::ALT, COLOR_OVERLAY_RED),
+			(ModifierKeys::empty(), COLOR_OVERLAY_BLUE),
+		];
+		for (modifier_keys, expected) in tints {
+			editor.move_mouse(100., 100., modifier_keys, MouseKeys::LEFT).await;
+			let recording = record_path_overlays(&mut editor).await;
+			let marquee = recording.commands().into_iter().find_map(|command| match command {
+				OverlayCommand::Polygon { stroke_color, fill_color, .. } if fill_color.is_some() => Some(stroke_color),
+				_ => None,
+			});
+			assert_eq!(marquee.as_deref(), Some(expected), "The marquee should be tinted for {modifier_keys:?}");
+		}
+
+		// Shift is only pressed by the time the box is released, so it extends the selection
+		let editor_mouse_state = EditorMouseState {
+			editor_position: DVec2::new(100., 100.),
+			..Default::default()
+		};
+		editor.mouseup(editor_mouse_state, ModifierKeys::SHIFT).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await, vec![DVec2::new(50., 50.), DVec2::new(150., 150.)]);
+	}
+
+	#[tokio::test]
+	async fn cancelling_a_box_selection_keeps_the_previous_selection() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[DVec2::new(150., 150.)]).await;
+
+		for cancel in [PathToolMessage::Escape, PathToolMessage::RightClick] {
+			editor.left_mousedown(20., 20., ModifierKeys::empty()).await;
+			editor.move_mouse(120., 120., ModifierKeys::empty(), MouseKeys::LEFT).await;
+			editor.handle_message(cancel.clone()).await;
+			let editor_mouse_state = EditorMouseState {
+				editor_position: DVec2::new(120., 120.),
+				..Default::default()
+			};
+			editor.mouseup(editor_mouse_state, ModifierKeys::empty()).await;
+
+			let selected = selected_anchor_positions(&mut editor).await;
+			assert_eq!(selected, vec![DVec2::new(150., 150.)], "Cancelling with {cancel:?} shouldn't change the selection");
+			let document = editor.active_document();
+			let selected_layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect::<Vec<_>>();
+			assert_eq!(selected_layers, vec![layer], "Cancelling with {cancel:?} shouldn't deselect the layer");
+		}
+	}
+
+	#[tokio::test]
+	async fn dragged_segment_snaps_its_midpoint() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.drag_tool(ToolType::Artboard, 0., 0., 200., 200., ModifierKeys::empty()).await;
+		editor.draw_rect(30., 30., 80., 80.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Dragging the middle of the top side, at (55, 30), to 2 px left of the artboard's vertical center line at x = 100
+		editor.drag_tool(ToolType::Path, 55., 30., 98., 60., ModifierKeys::empty()).await;
+		let positions = point_positions(&mut editor).await;
+		for expected in [DVec2::new(75., 60.), DVec2::new(125., 60.)] {
+			assert!(
+				positions.iter().any(|position| position.abs_diff_eq(expected, 1e-6)),
+				"The top side should be moved so its middle snaps to x = 100, but the points are {positions:?}"
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn alt_dragging_a_segment_bends_it_through_the_cursor_in_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		let original = point_positions(&mut editor).await;
+
+		// Grab the middle of the top side and pull it up
+		editor.drag_tool(ToolType::Path, 100., 50., 100., 20., ModifierKeys::ALT).await;
+		assert_eq!(point_positions(&mut editor).await, original, "Bending shouldn't move the anchors");
+		let curves = segment_curves(&editor);
+		let bent = curves.iter().find(|(_, _, midpoint)| midpoint.abs_diff_eq(DVec2::new(100., 20.), 1e-6));
+		assert!(
+			bent.is_some_and(|(_, bezier, _)| matches!(bezier.handles, BezierHandles::Cubic { .. })),
+			"The top side should pass through the cursor: {curves:?}"
+		);
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert!(
+			segment_curves(&editor).iter().all(|(_, bezier, _)| matches!(bezier.handles, BezierHandles::Linear)),
+			"Undo should restore the straight sides"
+		);
+
+		// Clicking the segment without dragging still deletes it
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(100., 50.), ModifierKeys::ALT).await;
+		assert_eq!(segment_curves(&editor).len(), 3);
+	}
+
+	#[tokio::test]
+	async fn bending_a_segment_keeps_the_handles_at_its_ends_colinear() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		editor.handle_message(PathToolMessage::SmoothSelectedPoints { strength: 1. }).await;
+		editor.handle_message(PathToolMessage::DeselectAllPoints).await;
+
+		let (_, _, top) = segment_curves(&editor).into_iter().min_by(|(_, _, a), (_, _, b)| a.y.total_cmp(&b.y)).unwrap();
+		editor.drag_tool(ToolType::Path, top.x, top.y, top.x + 20., top.y - 30., ModifierKeys::ALT).await;
+
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		assert_eq!(vector_data.colinear_manipulators.len(), 4);
+		for handles in &vector_data.colinear_manipulators {
+			let [first, second] = handles.map(|handle| {
+				let point = handle.to_manipulator_point();
+				point.get_position(&vector_data).unwrap() - point.get_anchor_position(&vector_data).unwrap()
+			});
+			assert!(
+				(first.angle_to(second).abs() - std::f64::consts::PI).abs() < 1e-6,
+				"The handles {first:?} and {second:?} should point opposite ways"
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn dragged_point_snaps_to_artboard_center_line() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.drag_tool(ToolType::Artboard, 0., 0., 200., 200., ModifierKeys::empty()).await;
+		editor.draw_rect(30., 30., 80., 80.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Dragging the corner at (80, 30) to 2 px left of the artboard's vertical center line at x = 100
+		editor.move_mouse(80., 30., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(80., 30., ModifierKeys::empty()).await;
+		editor.move_mouse(90., 45., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		editor.move_mouse(98., 60., ModifierKeys::empty(), MouseKeys::LEFT).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+		let labels = recording
+			.commands()
+			.into_iter()
+			.filter_map(|command| match command {
+				OverlayCommand::Text { text, .. } => Some(text),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert!(labels.iter().any(|label| label.starts_with("[Artboard: Center Line]")), "The snap indicator was not shown: {labels:?}");
+
+		let editor_mouse_state = EditorMouseState {
+			editor_position: DVec2::new(98., 60.),
+			..Default::default()
+		};
+		editor.mouseup(editor_mouse_state, ModifierKeys::empty()).await;
+		let positions = point_positions(&mut editor).await;
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(100., 60.), 1e-9)),
+			"The point did not snap to the center line: {positions:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn dragging_anchors_shows_how_far_they_moved_until_the_drag_stops() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		let labels = |recording: &OverlayRecording| {
+			recording
+				.commands()
+				.into_iter()
+				.filter_map(|command| match command {
+					OverlayCommand::Text { text, .. } => Some(text),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+		};
+
+		// The readout adds up the whole drag rather than showing the last movement
+		editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(60., 55., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		editor.move_mouse(70., 60., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		let readout = labels(&record_path_overlays(&mut editor).await);
+		assert!(readout.iter().any(|label| label == "ΔX 20, ΔY 10"), "The readout was not shown: {readout:?}");
+
+		let editor_mouse_state = EditorMouseState {
+			editor_position: DVec2::new(70., 60.),
+			..Default::default()
+		};
+		editor.mouseup(editor_mouse_state, ModifierKeys::empty()).await;
+		let readout = labels(&record_path_overlays(&mut editor).await);
+		assert!(!readout.iter().any(|label| label.starts_with("ΔX")), "The readout should go away once the drag stops: {readout:?}");
+	}
+
+	#[tokio::test]
+	async fn dragged_point_lines_up_with_the_other_anchors_unless_turned_off() {
+		async fn drag_corner_beside_bottom_edge(editor: &mut EditorTestUtils) -> Vec<DVec2> {
+			editor.draw_rect(50., 50., 150., 150.).await;
+			editor.select_tool(ToolType::Path).await;
+
+			// Dragging the corner at (50, 50) to 3 px above the bottom corners, well to the left of the bottom edge so it can't snap onto it
+			editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+			editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+			editor.move_mouse(35., 100., ModifierKeys::empty(), MouseKeys::LEFT).await;
+			editor.move_mouse(20., 147., ModifierKeys::empty(), MouseKeys::LEFT).await;
+			let editor_mouse_state = EditorMouseState {
+				editor_position: DVec2::new(20., 147.),
+				..Default::default()
+			};
+			editor.mouseup(editor_mouse_state, ModifierKeys::empty()).await;
+			point_positions(editor).await
+		}
+		fn align_with_anchor_points(snapping_state: &mut SnappingState) -> &mut bool {
+			&mut snapping_state.path.align_with_anchor_point
+		}
+
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let positions = drag_corner_beside_bottom_edge(&mut editor).await;
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(20., 150.), 1e-9)),
+			"The point did not line up with the bottom corners: {positions:?}"
+		);
+
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor
+			.handle_message(DocumentMessage::SetSnapping {
+				closure: Some(align_with_anchor_points),
+				snapping_state: false,
+			})
+			.await;
+		let positions = drag_corner_beside_bottom_edge(&mut editor).await;
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(20., 147.), 1e-9)),
+			"The point should follow the cursor: {positions:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn snapping_popover_toggles_the_document_snap_sources() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let responses = editor.editor.handle_message(ToolMessage::ActivateToolPath);
+
+		// The contents of the snapping popover are among the tool options' widgets
+		let widgets = tool_options_widgets(&responses).collect::<Vec<_>>();
+
+		for (name, closure, tooltip) in SNAP_FUNCTIONS_FOR_PATHS {
+			let mut checkbox = widgets
+				.iter()
+				.find_map(|widget| match widget {
+					Widget::CheckboxInput(checkbox) if checkbox.tooltip == tooltip => Some(checkbox.clone()),
+					_ => None,
+				})
+				.unwrap_or_else(|| panic!("The snapping popover should have a checkbox for {name}"));
+			let enabled = *closure(&mut editor.active_document().snapping_state.clone());
+			assert_eq!(checkbox.checked, enabled, "The checkbox for {name} should show the document's snapping");
+
+			checkbox.checked = !enabled;
+			let message = (checkbox.on_update.callback)(&checkbox);
+			editor.handle_message(message).await;
+			assert_eq!(
+				*closure(&mut editor.active_document().snapping_state.clone()),
+				!enabled,
+				"Toggling {name} should update the document's snapping"
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn snapping_tolerance_sets_how_close_a_dragged_point_must_be_to_snap() {
+		async fn drag_corner_beside_bottom_edge(tolerance: f64) -> Vec<DVec2> {
+			let mut editor = EditorTestUtils::create();
+			editor.new_document().await;
+			editor.draw_rect(50., 50., 150., 150.).await;
+			editor.select_tool(ToolType::Path).await;
+			editor.handle_message(DocumentMessage::SetSnappingTolerance { tolerance }).await;
+
+			// Dragging the corner at (50, 50) to 3 px above the bottom corners, well to the left of the bottom edge so it can't snap onto it
+			editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+			editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+			editor.move_mouse(35., 100., ModifierKeys::empty(), MouseKeys::LEFT).await;
+			editor.move_mouse(20., 147., ModifierKeys::empty(), MouseKeys::LEFT).await;
+			let editor_mouse_state = EditorMouseState {
+				editor_position: DVec2::new(20., 147.),
+				..Default::default()
+			};
+			editor.mouseup(editor_mouse_state, ModifierKeys::empty()).await;
+			point_positions(&mut editor).await
+		}
+
+		let positions = drag_corner_beside_bottom_edge(8.).await;
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(20., 150.), 1e-9)),
+			"The point should line up with the bottom corners within the snap distance: {positions:?}"
+		);
+
+		let positions = drag_corner_beside_bottom_edge(2.).await;
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(20., 147.), 1e-9)),
+			"The point should follow the cursor beyond the snap distance: {positions:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn sampled_anchor_position_applies_to_selected_point() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.draw_rect(200., 200., 260., 260.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(200., 200.), ModifierKeys::empty()).await;
+		let before = point_positions(&mut editor).await;
+
+		// Sampling a corner of the other, unselected rectangle doesn't change the selection or move anything
+		editor.handle_message(PathToolMessage::SamplePosition).await;
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(150., 150.), ModifierKeys::empty()).await;
+		assert_eq!(point_positions(&mut editor).await, before);
+
+		editor.handle_message(PathToolMessage::ApplySampledPosition).await;
+		let after = point_positions(&mut editor).await;
+		let expected = before
+			.iter()
+			.map(|&point| if point.distance(DVec2::new(200., 200.)) < 1e-6 { DVec2::new(150., 150.) } else { point })
+			.collect::<Vec<_>>();
+		assert!(
+			after.iter().zip(&expected).all(|(after, expected)| after.distance(*expected) < 1e-6),
+			"Expected {expected:?} but got {after:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn escape_cancels_position_sampling() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		editor.handle_message(PathToolMessage::SamplePosition).await;
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+
+		// With sampling cancelled, pressing on the corner grabs it instead of sampling its position
+		editor.drag_tool(ToolType::Path, 50., 50., 70., 60., ModifierKeys::empty()).await;
+		assert!(point_positions(&mut editor).await.iter().any(|point| point.distance(DVec2::new(70., 60.)) < 1e-6));
+	}
+
+	#[test]
+	fn smoothed_handles_follow_catmull_rom_tangents() {
+		let anchors = [DVec2::new(0., 0.), DVec2::new(100., 0.), DVec2::new(100., 100.), DVec2::new(200., 100.)];
+		let vector_data = VectorData::from_subpath(Subpath::from_anchors_linear(anchors, false));
+		let point = |index: usize| {
+			vector_data
+				.point_domain
+				.ids()
+				.iter()
+				.zip(vector_data.point_domain.positions())
+				.find(|(_, position)| **position == anchors[index])
+				.map(|(&id, _)| id)
+				.unwrap()
+		};
+
+		// The handles of each smoothed segment, keyed by the indices of its start and end anchors
+		let smoothed = |is_selected: &dyn Fn(PointId) -> bool, strength: f64| {
+			let handles = ShapeState::smoothed_segment_handles(&vector_data, is_selected, strength).into_iter().collect::<HashMap<_, _>>();
+			vector_data
+				.segment_bezier_iter()
+				.filter_map(|(segment, bezier, _, _)| {
+					let index = |position: DVec2| anchors.iter().position(|&anchor| anchor == position).unwrap();
+					Some(((index(bezier.start), index(bezier.end)), *handles.get(&segment)?))
+				})
+				.collect::<HashMap<_, _>>()
+		};
+		let assert_handles = |actual: &HashMap<(usize, usize), [DVec2; 2]>, expected: &[((usize, usize), [DVec2; 2])]| {
+			assert_eq!(actual.len(), expected.len(), "Unexpected smoothed segments {actual:?}");
+			for (segment, expected) in expected {
+				let actual = actual[segment];
+				assert!(
+					actual.iter().zip(expected).all(|(actual, expected)| actual.distance(*expected) < 1e-9),
+					"Segment {segment:?} has handles {actual:?}, expected {expected:?}"
+				);
+			}
+		};
+
+		// Interior anchors get handles along the direction between their neighbors reaching a third of the way to each, and endpoints a third of the way to their only neighbor
+		let third = 100. / 3.;
+		let diagonal = third / std::f64::consts::SQRT_2;
+		assert_handles(
+			&smoothed(&|_| true, 1.),
+			&[
+				((0, 1), [DVec2::new(third, 0.), DVec2::splat(-diagonal)]),
+				((1, 2), [DVec2::splat(diagonal), DVec2::splat(-diagonal)]),
+				((2, 3), [DVec2::splat(diagonal), DVec2::new(-third, 0.)]),
+			],
+		);
+
+		// Partial strength blends from the current handles, and unselected anchors keep theirs
+		let selected = point(1);
+		assert_handles(
+			&smoothed(&|point| point == selected, 0.5),
+			&[((0, 1), [DVec2::ZERO, DVec2::splat(-diagonal / 2.)]), ((1, 2), [DVec2::splat(diagonal / 2.), DVec2::ZERO])],
+		);
+
+		// The handles reach a third of the way to the neighbor on their side, even when the neighbors are at different distances
+		let anchors = [DVec2::new(0., 0.), DVec2::new(30., 0.), DVec2::new(30., 90.)];
+		let vector_data = VectorData::from_subpath(Subpath::from_anchors_linear(anchors, false));
+		let handles = ShapeState::smoothed_segment_handles(&vector_data, |_| true, 1.)
+			.into_iter()
+			.map(|(_, handles)| handles)
+			.collect::<Vec<_>>();
+		let tangent = (anchors[2] - anchors[0]).normalize();
+		assert!(handles[0][1].distance(-tangent * 10.) < 1e-9, "{handles:?}");
+		assert!(handles[1][0].distance(tangent * 30.) < 1e-9, "{handles:?}");
+	}
+
+	/// The positions of the points of the selected layer in document space.
+	fn document_point_positions(editor: &EditorTestUtils) -> Vec<DVec2> {
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		let transform = document.metadata().transform_to_document(layer);
+
+		vector_data.point_domain.positions().iter().map(|&position| transform.transform_point2(position)).collect()
+	}
+
+	#[tokio::test]
+	async fn shift_constrained_drag_follows_the_document_axes_on_a_rotated_canvas() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.handle_message(NavigationMessage::CanvasTiltSet { angle_radians: 30_f64.to_radians() }).await;
+		editor.select_tool(ToolType::Path).await;
+
+		let document_to_viewport = editor.active_document().metadata().document_to_viewport;
+		let corner = DVec2::new(50., 50.);
+		let start = document_to_viewport.transform_point2(corner);
+		// Mostly along the document's X axis, which points 30° down the viewport
+		let viewport_position = |document_offset: DVec2| start + document_to_viewport.transform_vector2(document_offset);
+
+		editor.move_mouse(start.x, start.y, ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(start.x, start.y, ModifierKeys::empty()).await;
+		for offset in [DVec2::new(4., 1.), DVec2::new(40., 10.)] {
+			let position = viewport_position(offset);
+			editor.move_mouse(position.x, position.y, ModifierKeys::SHIFT, MouseKeys::LEFT).await;
+		}
+		let editor_mouse_state = EditorMouseState {
+			editor_position: viewport_position(DVec2::new(40., 10.)),
+			..Default::default()
+		};
+		editor.mouseup(editor_mouse_state, ModifierKeys::SHIFT).await;
+
+		let positions = document_point_positions(&editor);
+		let moved = DVec2::new(90., 50.);
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(moved, 1e-9)),
+			"The corner should move only along the document X axis to {moved}, got {positions:?}"
+		);
+		assert!(!positions.iter().any(|position| position.abs_diff_eq(corner, 1e-6)));
+	}
+
+	#[tokio::test]
+	async fn dragging_a_point_redraws_the_overlays_once_per_pointer_move() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(60., 55., ModifierKeys::empty(), MouseKeys::LEFT).await;
+
+		// The drag, the selection update, the snap indicator, and auto-panning each ask for a redraw, which should be drawn only once
+		let draw_count = |editor: &EditorTestUtils| editor.active_document().overlays_message_handler.draw_count;
+		for position in [(70., 60.), (80., 70.), (90., 65.)] {
+			let before = draw_count(&editor);
+			let editor_mouse_state = EditorMouseState {
+				editor_position: position.into(),
+				mouse_keys: MouseKeys::LEFT,
+				..Default::default()
+			};
+			editor.editor.handle_message(InputPreprocessorMessage::PointerMove {
+				editor_mouse_state,
+				modifier_keys: ModifierKeys::empty(),
+			});
+			assert_eq!(draw_count(&editor) - before, 1, "Moving to {position:?} should redraw the overlays exactly once");
+		}
+	}
+
+	#[test]
+	fn colinearity_repair_realigns_slightly_off_handles_and_unflags_the_rest() {
+		// The middle anchor of each path has handles flagged as colinear which are off by about 3.8°, by about 33.7°, and not at all
+		let middle_handles = [
+			(DVec2::new(-30., 2.), DVec2::new(30., 0.)),
+			(DVec2::new(-30., 0.), DVec2::new(30., 20.)),
+			(DVec2::new(-30., 0.), DVec2::new(30., 0.)),
+		];
+		let subpaths = middle_handles.iter().enumerate().map(|(index, &(in_handle, out_handle))| {
+			let y = index as f64 * 100.;
+			let middle = DVec2::new(100., y);
+			let groups = vec![
+				ManipulatorGroup::new(DVec2::new(0., y), None, Some(DVec2::new(30., y))),
+				ManipulatorGroup::new(middle, Some(middle + in_handle), Some(middle + out_handle)),
+				ManipulatorGroup::new(DVec2::new(200., y), Some(DVec2::new(170., y)), None),
+			];
+			Subpath::new(groups, false)
+		});
+		let mut vector_data = VectorData::from_subpaths(subpaths, false);
+		let middle = |index: usize| {
+			let position = DVec2::new(100., index as f64 * 100.);
+			vector_data
+				.point_domain
+				.ids()
+				.iter()
+				.zip(vector_data.point_domain.positions())
+				.find(|(_, point)| **point == position)
+				.map(|(&id, _)| id)
+				.unwrap()
+		};
+		let [slightly_off, far_off, straight] = [0, 1, 2].map(middle);
+		for anchor in [slightly_off, far_off, straight] {
+			let handles = ManipulatorPointId::Anchor(anchor).get_handle_pair(&vector_data).unwrap();
+			vector_data.colinear_manipulators.push(handles);
+		}
+		let anchor_of = |handles: [HandleId; 2]| handles[0].to_manipulator_point().get_anchor(&vector_data).unwrap();
+
+		let repairs = ShapeState::colinearity_repairs(&vector_data, |_| true, 5_f64.to_radians());
+		assert_eq!(repairs.len(), 2, "The straight handles need no repair: {repairs:?}");
+		for repair in repairs {
+			match repair {
+				ColinearityRepair::Realign {
+					handles,
+					relative_positions: [first, second],
+				} => {
+					assert_eq!(anchor_of(handles), slightly_off);
+					let original = handles.map(|handle| handle.to_manipulator_point().get_position(&vector_data).unwrap() - DVec2::new(100., 0.));
+					assert!(
+						(first.length() - original[0].length()).abs() < 1e-9 && (second.length() - original[1].length()).abs() < 1e-9,
+						"Lengths should be kept"
+					);
+					assert!(
+						first.perp_dot(second).abs() < 1e-9 && first.dot(second) < 0.,
+						"{first} and {second} should be on opposite sides of one line"
+					);
+					let rotations = [original[0].angle_to(first).abs(), original[1].angle_to(second).abs()];
+					assert!(
+						(rotations[0] - rotations[1]).abs() < 1e-9,
+						"Both handles should be rotated onto the average axis, but were rotated by {rotations:?}"
+					);
+				}
+				ColinearityRepair::Unflag { handles } => assert_eq!(anchor_of(handles), far_off),
+			}
+		}
+
+		// Only the selected anchors are repaired
+		let repairs = ShapeState::colinearity_repairs(&vector_data, |point| point == far_off, 5_f64.to_radians());
+		assert!(matches!(repairs[..], [ColinearityRepair::Unflag { .. }]), "{repairs:?}");
+
+		// A larger tolerance realigns the handles which are further off too
+		let repairs = ShapeState::colinearity_repairs(&vector_data, |_| true, 45_f64.to_radians());
+		assert_eq!(repairs.len(), 2);
+		assert!(repairs.iter().all(|repair| matches!(repair, ColinearityRepair::Realign { .. })), "{repairs:?}");
+	}
+
+	#[tokio::test]
+	async fn smoothing_selected_points_is_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		let anchors = point_positions(&mut editor).await;
+
+		editor.handle_message(PathToolMessage::SmoothSelectedPoints { strength: 1. }).await;
+		assert_eq!(point_positions(&mut editor).await, anchors, "Smoothing shouldn't move the anchors");
+		let curves = segment_curves(&editor);
+		assert_eq!(curves.len(), 4);
+		assert!(curves.iter().all(|(_, bezier, _)| matches!(bezier.handles, BezierHandles::Cubic { .. })), "Every side should be curved");
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert!(
+			segment_curves(&editor).iter().all(|(_, bezier, _)| matches!(bezier.handles, BezierHandles::Linear)),
+			"Undo should restore the straight sides"
+		);
+	}
+
+	#[tokio::test]
+	async fn smoothing_marks_the_handles_colinear_and_leaves_smooth_points_unchanged() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+
+		editor.handle_message(PathToolMessage::SmoothSelectedPoints { strength: 1. }).await;
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		assert_eq!(vector_data.colinear_manipulators.len(), 4, "Each corner's handles should be marked colinear");
+		let smoothed = segment_curves(&editor);
+
+		// Smoothing again with the same neighbors changes nothing
+		editor.handle_message(PathToolMessage::SmoothSelectedPoints { strength: 1. }).await;
+		let resmoothed = segment_curves(&editor);
+		assert!(
+			smoothed.iter().zip(&resmoothed).all(|((_, before, _), (_, after, _))| before.abs_diff_eq(after, 1e-9)),
+			"Smoothing should be idempotent, but {smoothed:?} became {resmoothed:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn smoothing_an_open_path_points_its_endpoint_handles_at_their_neighbors() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let anchors = [DVec2::new(50., 50.), DVec2::new(150., 50.), DVec2::new(150., 150.)];
+		editor.draw_spline(&anchors).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+
+		editor.handle_message(PathToolMessage::SmoothSelectedPoints { strength: 1. }).await;
+		let curves = segment_curves(&editor);
+		assert_eq!(curves.len(), 2);
+		for (index, (_, bezier, _)) in curves.iter().enumerate() {
+			let other = &curves[1 - index].1;
+			let cubic = bezier.to_cubic();
+			for (endpoint, handle, neighbor) in [(cubic.start, cubic.handle_start(), cubic.end), (cubic.end, cubic.handle_end(), cubic.start)] {
+				// The anchor shared with the other segment isn't an endpoint of the path
+				if [other.start, other.end].iter().any(|anchor| anchor.abs_diff_eq(endpoint, 1e-6)) {
+					continue;
+				}
+				let handle = handle.unwrap() - endpoint;
+				assert!(
+					handle.abs_diff_eq((neighbor - endpoint) / 3., 1e-6),
+					"The handle at {endpoint} should point a third of the way to {neighbor}, but is {handle}"
+				);
+			}
+		}
+	}
+
+	#[tokio::test]
+	async fn filleting_a_corner_replaces_it_with_an_arc_and_clamps_the_radius() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		let corners = selected_layers_document_points(&editor);
+		let corner = corners[0];
+		let viewport_corner = point_positions(&mut editor).await[0];
+
+		select_points(&mut editor, &[viewport_corner]).await;
+		editor.handle_message(PathToolMessage::FilletSelectedPoints { radius: 10. }).await;
+		let points = selected_layers_document_points(&editor);
+		assert_eq!(points.len(), 5, "The corner should be replaced by two anchors");
+		let new_points = points.iter().filter(|&&point| !corners.iter().any(|corner| corner.abs_diff_eq(point, 1e-6))).collect::<Vec<_>>();
+		assert_eq!(new_points.len(), 2);
+		assert!(
+			new_points.iter().all(|point| (point.distance(corner) - 10.).abs() < 1e-6),
+			"{new_points:?} should each be 10 px from {corner}"
+		);
+
+		// The arc bulges towards the corner like a quarter circle tangent to both sides
+		let curves = segment_curves(&editor);
+		assert_eq!(curves.len(), 5);
+		let arc = curves
+			.iter()
+			.find(|(_, bezier, _)| matches!(bezier.handles, BezierHandles::Cubic { .. }))
+			.expect("The corner should be rounded by a curve");
+		let center = new_points[0] + new_points[1] - corner;
+		let midpoint = editor.active_document().metadata().document_to_viewport.inverse().transform_point2(arc.2);
+		assert!((midpoint.distance(center) - 10.).abs() < 0.05, "{midpoint} should be on the circle around {center}");
+
+		// A radius longer than half the sides is clamped to it
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(selected_layers_document_points(&editor), corners);
+		select_points(&mut editor, &[viewport_corner]).await;
+		editor.handle_message(PathToolMessage::FilletSelectedPoints { radius: 1000. }).await;
+		let points = selected_layers_document_points(&editor);
+		let new_points = points.iter().filter(|&&point| !corners.iter().any(|corner| corner.abs_diff_eq(point, 1e-6))).collect::<Vec<_>>();
+		assert!(
+			new_points.iter().all(|point| (point.distance(corner) - 50.).abs() < 1e-6),
+			"{new_points:?} should be at the middles of the sides"
+		);
+	}
+
+	#[tokio::test]
+	async fn reshaping_pushes_the_path_under_the_brush_in_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::Reshape(true))).await;
+		editor.handle_message(PathToolMessage::ReshapeRadiusChanged { radius: 30. }).await;
+		let corners = point_positions(&mut editor).await;
+
+		// Pushing the middle of the top side up bulges it out further than its anchors can follow, so an anchor is added there
+		editor.drag_tool(ToolType::Path, 100., 50., 100., 30., ModifierKeys::empty()).await;
+		let points = point_positions(&mut editor).await;
+		assert_eq!(points.len(), 5, "An anchor should be added where the side was pushed: {points:?}");
+		assert!(
+			corners.iter().all(|corner| points.contains(corner)),
+			"The corners are outside the brush, so they should stay in place: {points:?}"
+		);
+		assert!(
+			points.iter().any(|point| point.abs_diff_eq(DVec2::new(100., 30.), 1e-6)),
+			"The middle of the side should follow the cursor: {points:?}"
+		);
+		let curves = segment_curves(&editor);
+		assert!(curves.iter().all(|(_, _, midpoint)| midpoint.y >= 30. - 1e-6), "The pushed side shouldn't overshoot the cursor");
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await, corners, "Undo should restore the path from before the stroke");
+	}
+
+	/// The anchor positions in document space of every selected layer, in layer order.
+	fn selected_layers_document_points(editor: &EditorTestUtils) -> Vec<DVec2> {
+		let document = editor.active_document();
+		document
+			.network_interface
+			.selected_nodes()
+			.selected_layers(document.metadata())
+			.flat_map(|layer| {
+				let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+				let transform = document.metadata().transform_to_document(layer);
+				vector_data.point_domain.positions().iter().map(|&position| transform.transform_point2(position)).collect::<Vec<_>>()
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn aligning_points_across_layers_is_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let first = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(200., 50., 300., 150.).await;
+		let second = editor.get_selected_layer().await.unwrap();
+		editor
+			.handle_message(NodeGraphMessage::SelectedNodesSet {
+				nodes: vec![first.to_node(), second.to_node()],
+			})
+			.await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		let original = selected_layers_document_points(&editor);
+		assert_eq!(original.len(), 8);
+
+		editor
+			.handle_message(PathToolMessage::AlignSelectedPoints {
+				axis: AlignAxis::X,
+				aggregate: AlignAggregate::Min,
+			})
+			.await;
+		let aligned = selected_layers_document_points(&editor);
+		assert!(aligned.iter().all(|position| position.x.abs() < 1e-6), "Every anchor should be at the leftmost X: {aligned:?}");
+		assert!(
+			aligned.iter().zip(&original).all(|(aligned, original)| (aligned.y - original.y).abs() < 1e-6),
+			"The other axis should be kept"
+		);
+
+		editor
+			.handle_message(PathToolMessage::AlignSelectedPoints {
+				axis: AlignAxis::Y,
+				aggregate: AlignAggregate::Center,
+			})
+			.await;
+		let average = original.iter().map(|position| position.y).sum::<f64>() / original.len() as f64;
+		assert!(
+			selected_layers_document_points(&editor).iter().all(|position| (position.y - average).abs() < 1e-6),
+			"Every anchor should be at the average Y"
+		);
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		editor.handle_message(DocumentMessage::Undo).await;
+		let restored = selected_layers_document_points(&editor);
+		assert!(
+			restored.iter().zip(&original).all(|(restored, original)| restored.abs_diff_eq(*original, 1e-6)),
+			"Two undos should revert both alignments"
+		);
+	}
+
+	#[tokio::test]
+	async fn distributing_points_spaces_them_evenly_and_keeps_their_handles() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_ellipse(0., 0., 100., 60.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		let relative_handles = |editor: &EditorTestUtils| {
+			let document = editor.active_document();
+			let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+			let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+			vector_data
+				.segment_bezier_iter()
+				.map(|(_, bezier, _, _)| {
+					let cubic = bezier.to_cubic();
+					[cubic.handle_start().unwrap() - cubic.start, cubic.handle_end().unwrap() - cubic.end]
+				})
+				.collect::<Vec<_>>()
+		};
+		let handles = relative_handles(&editor);
+
+		editor.handle_message(PathToolMessage::DistributeSelectedPoints { axis: AlignAxis::X }).await;
+		let mut xs = selected_layers_document_points(&editor).iter().map(|position| position.x).collect::<Vec<_>>();
+		xs.sort_by(f64::total_cmp);
+		let step = (xs[xs.len() - 1] - xs[0]) / (xs.len() - 1) as f64;
+		assert!(xs[0].abs() < 1e-6 && (xs[xs.len() - 1] - 100.).abs() < 1e-6, "The outermost anchors should stay put: {xs:?}");
+		assert!(
+			xs.iter().enumerate().all(|(index, x)| (x - step * index as f64).abs() < 1e-6),
+			"The anchors should be evenly spaced: {xs:?}"
+		);
+		let moved_handles = relative_handles(&editor);
+		assert_eq!(moved_handles.len(), handles.len());
+		let unchanged = moved_handles.iter().flatten().zip(handles.iter().flatten()).all(|(moved, original)| moved.abs_diff_eq(*original, 1e-6));
+		assert!(unchanged, "The handles should travel with their anchors: {moved_handles:?} instead of {handles:?}");
+	}
+
+	#[test]
+	fn routes_between_anchors_follow_the_segments_connecting_them() {
+		let open = [DVec2::new(0., 0.), DVec2::new(100., 0.), DVec2::new(200., 0.), DVec2::new(300., 0.), DVec2::new(400., 0.)];
+		let closed = [0., 60., 120., 180., 240., 300.].map(|degrees: f64| DVec2::new(0., 500.) + DVec2::from_angle(degrees.to_radians()) * 100.);
+		let other = [DVec2::new(0., 1000.), DVec2::new(100., 1000.)];
+		let subpaths = [
+			Subpath::from_anchors_linear(open, false),
+			Subpath::from_anchors_linear(closed, true),
+			Subpath::from_anchors_linear(other, false),
+		];
+		let vector_data = VectorData::from_subpaths(subpaths, false);
+		let point = |position: DVec2| {
+			vector_data
+				.point_domain
+				.ids()
+				.iter()
+				.zip(vector_data.point_domain.positions())
+				.find(|(_, point)| point.distance(position) < 1e-9)
+				.map(|(&id, _)| id)
+				.unwrap()
+		};
+		let route_positions = |start: DVec2, end: DVec2, longer_route: bool| {
+			let route = ShapeState::route_between_anchors(&vector_data, point(start), point(end), longer_route)?;
+			assert_eq!(route.segments.len() + 1, route.points.len(), "Each consecutive pair of anchors should be joined by a segment");
+			Some(route.points.iter().map(|&id| vector_data.point_domain.position_from_id(id).unwrap()).collect::<Vec<_>>())
+		};
+
+		// An open path has only one route, even when the longer one is asked for
+		assert_eq!(route_positions(open[1], open[4], false), Some(open[1..].to_vec()));
+		assert_eq!(route_positions(open[4], open[1], true), Some(open[1..].iter().rev().copied().collect()));
+
+		// A closed path can be gone around either way
+		let around_closed = |indices: &[usize]| Some(indices.iter().map(|&index| closed[index]).collect::<Vec<_>>());
+		assert_eq!(
+			route_positions(closed[0], closed[2], false),
+			around_closed(&[0, 1, 2]),
+			"The shorter route goes through the anchor between them"
+		);
+		assert_eq!(
+			route_positions(closed[0], closed[2], true),
+			around_closed(&[0, 5, 4, 3, 2]),
+			"The longer route goes the other way around"
+		);
+
+		// Anchors on different subpaths aren't connected
+		assert_eq!(route_positions(open[0], other[0], false), None);
+		assert_eq!(route_positions(closed[0], other[1], true), None);
+	}
+
+	#[tokio::test]
+	async fn selecting_between_two_anchors_selects_the_route_and_highlights_it() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		let selected_anchors = |recording: &OverlayRecording| {
+			recording
+				.commands()
+				.into_iter()
+				.filter(|command| matches!(command, OverlayCommand::ManipulatorAnchor { selected: true, .. }))
+				.count()
+		};
+		let arrows = |recording: &OverlayRecording| recording.commands().into_iter().filter(|command| matches!(command, OverlayCommand::Triangle { .. })).count();
+
+		// With only one anchor selected, there's nothing to select between
+		select_points(&mut editor, &[DVec2::new(100., 50.)]).await;
+		editor.handle_message(PathToolMessage::SelectBetween { longer_route: Key::Shift }).await;
+		let recording = record_path_overlays(&mut editor).await;
+		assert_eq!((selected_anchors(&recording), arrows(&recording)), (1, 0));
+
+		// Neighboring anchors are joined by the one segment between them
+		select_points(&mut editor, &[DVec2::new(100., 50.), DVec2::new(150., 100.)]).await;
+		editor.handle_message(PathToolMessage::SelectBetween { longer_route: Key::Shift }).await;
+		let recording = record_path_overlays(&mut editor).await;
+		assert_eq!((selected_anchors(&recording), arrows(&recording)), (2, 1), "Only the shorter route should be selected and highlighted");
+
+		// Holding the modifier goes the other way around the circle, through every anchor
+		select_points(&mut editor, &[DVec2::new(100., 50.), DVec2::new(150., 100.)]).await;
+		editor.move_mouse(400., 400., ModifierKeys::SHIFT, MouseKeys::empty()).await;
+		editor.handle_message(PathToolMessage::SelectBetween { longer_route: Key::Shift }).await;
+		let recording = record_path_overlays(&mut editor).await;
+		assert_eq!((selected_anchors(&recording), arrows(&recording)), (4, 3), "The longer route should be selected and highlighted");
+
+		// The highlight goes away on its own
+		editor
+			.handle_message(InputPreprocessorMessage::CurrentTime {
+				timestamp: PATH_ROUTE_HIGHLIGHT_DURATION * 2,
+			})
+			.await;
+		editor.handle_message(PathToolMessage::RouteHighlightTimeout).await;
+		assert_eq!(arrows(&record_path_overlays(&mut editor).await), 0);
+		assert_eq!(selected_anchors(&record_path_overlays(&mut editor).await), 4, "The selection should be kept");
+	}
+
+	/// The positions of the selected anchors shown in the overlays, from left to right.
+	async fn selected_anchor_positions(editor: &mut EditorTestUtils) -> Vec<DVec2> {
+		let recording = record_path_overlays(editor).await;
+		let mut positions = recording
+			.commands()
+			.into_iter()
+			.filter_map(|command| match command {
+				OverlayCommand::ManipulatorAnchor { position, selected: true, .. } => Some(position),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		positions.sort_by(|a, b| a.x.total_cmp(&b.x));
+		positions
+	}
+
+	#[tokio::test]
+	async fn growing_and_shrinking_the_selection_moves_one_anchor_along_the_path_at_a_time() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let anchors = [0, 1, 2, 3, 4].map(|index| DVec2::new(50. + index as f64 * 100., 50.));
+		editor.draw_spline(&anchors).await;
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[anchors[1]]).await;
+
+		for expected in [&anchors[..3], &anchors[..4], &anchors[..]] {
+			editor.handle_message(PathToolMessage::GrowSelection).await;
+			assert_eq!(selected_anchor_positions(&mut editor).await, expected, "Growing should add the neighbors of the selected anchors");
+		}
+
+		// Once the whole open path is selected, no selected anchor has an unselected neighbor
+		editor.handle_message(PathToolMessage::ShrinkSelection).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await, anchors);
+
+		select_points(&mut editor, &anchors[1..4]).await;
+		editor.handle_message(PathToolMessage::ShrinkSelection).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await, [anchors[2]], "Shrinking should remove the anchors beside unselected ones");
+		editor.handle_message(PathToolMessage::ShrinkSelection).await;
+		assert!(selected_anchor_positions(&mut editor).await.is_empty());
+	}
+
+	#[tokio::test]
+	async fn adding_a_point_at_length_inserts_and_selects_it_along_the_path() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 200., 100.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+		editor.select_tool(ToolType::Path).await;
+
+		// Measured independently by the bezier library along the first subpath, which is where distances are measured from
+		let expected_at = |editor: &EditorTestUtils, fraction: f64| {
+			let document = editor.active_document();
+			let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+			let subpath = vector_data.stroke_bezier_paths().next().unwrap();
+			document
+				.metadata()
+				.transform_to_viewport(layer)
+				.transform_point2(subpath.evaluate(SubpathTValue::GlobalEuclidean(fraction)))
+		};
+
+		let expected = expected_at(&editor, 0.25);
+		let distance = DistanceAlongPath::Percentage(25.);
+		editor.handle_message(PathToolMessage::AddPointAtLength { layer, distance }).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 5);
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert!(
+			selected.len() == 1 && selected[0].abs_diff_eq(expected, 1e-3),
+			"The new anchor should be selected a quarter along the path: {selected:?}"
+		);
+
+		// The perimeter is 600 px, so this is three quarters along it
+		let expected = expected_at(&editor, 0.75);
+		let distance = DistanceAlongPath::Length(450.);
+		editor.handle_message(PathToolMessage::AddPointAtLength { layer, distance }).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 6);
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert!(
+			selected.len() == 1 && selected[0].abs_diff_eq(expected, 1e-3),
+			"Only the second new anchor should be selected: {selected:?}"
+		);
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 5, "Each insertion should be one undo step");
+	}
+
+	#[tokio::test]
+	async fn joining_endpoints_connects_them_and_refuses_other_anchors() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let anchors = [DVec2::new(50., 50.), DVec2::new(150., 150.), DVec2::new(250., 50.)];
+		editor.draw_spline(&anchors).await;
+		editor.select_tool(ToolType::Path).await;
+		let segment_count = |editor: &EditorTestUtils| {
+			let document = editor.active_document();
+			let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+			document.network_interface.compute_modified_vector(layer).unwrap().segment_domain.ids().len()
+		};
+		assert_eq!(segment_count(&editor), 2);
+
+		// The middle anchor has two segments, so it isn't an endpoint
+		select_points(&mut editor, &anchors[..2]).await;
+		editor.handle_message(PathToolMessage::JoinEndpoints).await;
+		assert_eq!(segment_count(&editor), 2, "Only endpoints should be joined");
+
+		select_points(&mut editor, &[anchors[0], anchors[2]]).await;
+		editor.handle_message(PathToolMessage::JoinEndpoints).await;
+		assert_eq!(segment_count(&editor), 3, "The endpoints should be joined by a new segment");
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(segment_count(&editor), 2, "Joining should be one undo step");
+	}
+
+	#[tokio::test]
+	async fn reversing_a_subpath_swaps_its_segment_ends_and_keeps_its_shape() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		let anchors = [DVec2::new(50., 50.), DVec2::new(150., 150.), DVec2::new(250., 50.)];
+		editor.draw_spline(&anchors).await;
+		editor.select_tool(ToolType::Path).await;
+		let segments = |editor: &EditorTestUtils| {
+			let document = editor.active_document();
+			let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+			let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+			vector_data.segment_bezier_iter().collect::<Vec<_>>()
+		};
+		let original = segments(&editor);
+
+		select_points(&mut editor, &anchors[..1]).await;
+		editor.handle_message(PathToolMessage::ReversePathDirection).await;
+		let reversed = segments(&editor);
+		assert_eq!(reversed.len(), original.len());
+		for ((segment, bezier, start, end), (reversed_segment, reversed_bezier, reversed_start, reversed_end)) in original.iter().zip(&reversed) {
+			assert_eq!((segment, start, end), (reversed_segment, reversed_end, reversed_start), "Every segment should have its ends swapped");
+			for t in [0., 0.25, 0.5, 1.] {
+				let position = bezier.evaluate(TValue::Parametric(t));
+				assert!(position.abs_diff_eq(reversed_bezier.evaluate(TValue::Parametric(1. - t)), 1e-6), "The segment should keep its shape");
+			}
+		}
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		let restored = segments(&editor);
+		assert!(
+			restored
+				.iter()
+				.zip(&original)
+				.all(|(restored, original)| (restored.0, restored.2, restored.3) == (original.0, original.2, original.3))
+		);
+	}
+
+	/// Finds the last update of the number input with the given label among the layout diffs sent to the frontend.
+	fn tool_options_widgets(responses: &[FrontendMessage]) -> impl Iterator<Item = Widget> + '_ {
+		let widgets = responses.iter().flat_map(|message| match message {
+			FrontendMessage::UpdateToolOptionsLayout { diff, .. } => diff.iter().map(|diff| diff.new_value.clone()).collect(),
+			_ => Vec::new(),
+		});
+		widgets
+			.flat_map(|new_value| match new_value {
+				DiffUpdate::SubLayout(layout) => WidgetLayout::new(layout).iter().cloned().collect(),
+				DiffUpdate::LayoutGroup(group) => WidgetLayout::new(vec![group]).iter().cloned().collect(),
+				DiffUpdate::Widget(widget) => vec![widget],
+			})
+			.map(|widget| widget.widget)
+	}
+
+	fn last_number_input(responses: &[FrontendMessage], label: &str) -> Option<NumberInput> {
+		tool_options_widgets(responses)
+			.filter_map(|widget| match widget {
+				Widget::NumberInput(number_input) if number_input.label == label => Some(number_input),
+				_ => None,
+			})
+			.last()
+	}
+
+	fn last_colinear_handles_checkbox(responses: &[FrontendMessage]) -> Option<CheckboxInput> {
+		tool_options_widgets(responses)
+			.filter_map(|widget| match widget {
+				Widget::CheckboxInput(checkbox_input) if checkbox_input.tooltip.starts_with("Keep both handles unbent") => Some(checkbox_input),
+				_ => None,
+			})
+			.last()
+	}
+
+	#[tokio::test]
+	async fn typed_coordinates_keep_full_precision_while_displayed_rounded() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(50., 50.), ModifierKeys::empty()).await;
+
+		let new_x = parse_number("0.12345").unwrap();
+		let mut responses = editor.editor.handle_message(PathToolMessage::SelectedPointXChanged { new_x });
+		responses.extend(editor.editor.handle_message(PathToolMessage::SelectedPointUpdated));
+
+		// The field is told to round for display only, while the value it echoes back and commits is exactly what was typed
+		let x_input = last_number_input(&responses, "X").expect("The X field should be updated");
+		assert_eq!(x_input.value, Some(0.12345));
+		assert_eq!(x_input.display_decimal_places, DISPLAY_DECIMAL_PLACES);
+		assert_eq!(format_number(x_input.value.unwrap(), x_input.display_decimal_places), "0.123");
+
+		editor.eval_graph().await;
+		let positions = point_positions(&mut editor).await;
+		assert!(
+			positions.iter().any(|position| (position.x - 0.12345).abs() < 1e-9),
+			"The moved point should keep every typed digit, got {positions:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn coordinate_fields_evaluate_expressions_using_the_current_coordinates() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, DVec2::new(50., 50.), ModifierKeys::empty()).await;
+
+		let responses = editor.editor.handle_message(PathToolMessage::SelectedPointUpdated);
+		let mut x_input = last_number_input(&responses, "X").expect("The X field should be updated");
+		assert!(x_input.forwards_expressions && !x_input.invalid);
+
+		// `x` is the point's current X coordinate
+		x_input.expression = Some("x+10".to_string());
+		let message = (x_input.on_update.callback)(&x_input);
+		editor.handle_message(message).await;
+		editor.eval_graph().await;
+		let positions = point_positions(&mut editor).await;
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(60., 50.), 1e-9)),
+			"The point should move to x+10, got {positions:?}"
+		);
+
+		// Division by zero is rejected, leaving the point in place and marking the field
+		let responses = editor.editor.handle_message(PathToolMessage::SelectedPointUpdated);
+		let mut x_input = last_number_input(&responses, "X").expect("The X field should be updated");
+		x_input.expression = Some("1/0".to_string());
+		let message = (x_input.on_update.callback)(&x_input);
+		let responses = editor.editor.handle_message(message);
+		assert!(last_number_input(&responses, "X").is_some_and(|input| input.invalid));
+		assert!(point_positions(&mut editor).await.iter().all(|position| position.is_finite()));
+
+		// A valid value clears the mark
+		let mut responses = editor.editor.handle_message(PathToolMessage::SelectedPointXChanged { new_x: 70. });
+		responses.extend(editor.editor.handle_message(PathToolMessage::SelectedPointUpdated));
+		assert!(last_number_input(&responses, "X").is_some_and(|input| !input.invalid));
+	}
+
+	#[tokio::test]
+	async fn coordinate_fields_set_the_coordinate_for_every_selected_point() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[DVec2::new(50., 50.), DVec2::new(150., 50.)]).await;
+
+		// The anchors share their Y, while their differing X is left blank rather than shown as 0
+		let responses = editor.editor.handle_message(PathToolMessage::SelectedPointUpdated);
+		let x_input = last_number_input(&responses, "X").expect("The X field should be updated");
+		let y_input = last_number_input(&responses, "Y").expect("The Y field should be updated");
+		assert!(!x_input.disabled && x_input.value.is_none());
+		assert!(!y_input.disabled && y_input.value == Some(50.));
+
+		editor.handle_message(PathToolMessage::SelectedPointXChanged { new_x: 80. }).await;
+		editor.eval_graph().await;
+		let positions = point_positions(&mut editor).await;
+		let at = |target: DVec2| positions.iter().filter(|position| position.abs_diff_eq(target, 1e-9)).count();
+		assert_eq!(at(DVec2::new(80., 50.)), 2, "Both anchors should move to the typed X, keeping their Y, got {positions:?}");
+		assert_eq!(at(DVec2::new(150., 150.)), 1, "The unselected anchors should stay in place, got {positions:?}");
+		let responses = editor.editor.handle_message(PathToolMessage::SelectedPointUpdated);
+		assert_eq!(last_number_input(&responses, "X").and_then(|input| input.value), Some(80.));
+
+		// A handle selected along with its anchor ends up at the typed coordinate too, rather than also following its anchor
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, _, _) = segment_curves(&editor)[0];
+		let relative_position = DVec2::new(30., 40.);
+		let modification_type = VectorModificationType::SetPrimaryHandle { segment, relative_position };
+		editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+		let vector_data = editor.active_document().network_interface.compute_modified_vector(layer).unwrap();
+		let handle = ManipulatorPointId::PrimaryHandle(segment);
+		let anchor = ManipulatorPointId::Anchor(handle.get_anchor(&vector_data).unwrap());
+		let points = vec![(layer, vec![anchor, handle])];
+		editor.handle_message(PathToolMessage::SelectPoints { points }).await;
+		editor.handle_message(PathToolMessage::SelectedPointYChanged { new_y: 20. }).await;
+
+		let vector_data = editor.active_document().network_interface.compute_modified_vector(layer).unwrap();
+		let to_document = editor.active_document().metadata().transform_to_document(layer);
+		let [anchor_position, handle_position] = [anchor, handle].map(|point| to_document.transform_point2(point.get_position(&vector_data).unwrap()));
+		assert!(
+			(anchor_position.y - 20.).abs() < 1e-9 && (handle_position.y - 20.).abs() < 1e-9,
+			"Got {anchor_position} and {handle_position}"
+		);
+	}
+
+	#[tokio::test]
+	async fn handle_length_and_angle_fields_edit_the_only_selected_handle() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, _, _) = segment_curves(&editor)[0];
+		let modification_type = VectorModificationType::SetPrimaryHandle {
+			segment,
+			relative_position: DVec2::new(30., 40.),
+		};
+		editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+		let handle = ManipulatorPointId::PrimaryHandle(segment);
+		editor.handle_message(PathToolMessage::SelectPoints { points: vec![(layer, vec![handle])] }).await;
+
+		// The fields show the handle measured in document space
+		let to_document = editor.active_document().metadata().transform_to_document(layer);
+		let relative = to_document.transform_vector2(DVec2::new(30., 40.));
+		let responses = editor.editor.handle_message(PathToolMessage::SelectedPointUpdated);
+		let length_input = last_number_input(&responses, "Length").expect("The Length field should be updated");
+		let angle_input = last_number_input(&responses, "Angle").expect("The Angle field should be updated");
+		assert!(!length_input.disabled && !angle_input.disabled);
+		assert!((length_input.value.unwrap() - relative.length()).abs() < 1e-9);
+		assert!((angle_input.value.unwrap() - relative.to_angle().to_degrees()).abs() < 1e-9);
+
+		editor.handle_message(PathToolMessage::SelectedHandleLengthChanged { length: 100. }).await;
+		editor.handle_message(PathToolMessage::SelectedPointUpdated).await;
+		editor.handle_message(PathToolMessage::SelectedHandleAngleChanged { degrees: 90. }).await;
+
+		let document = editor.active_document();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		let [position, anchor] = [handle.get_position(&vector_data), handle.get_anchor_position(&vector_data)].map(|position| to_document.transform_point2(position.unwrap()));
+		assert!(
+			(position - anchor).abs_diff_eq(DVec2::new(0., 100.), 1e-6),
+			"The handle should be 100 px straight down from its anchor, but is at {:?}",
+			position - anchor
+		);
+
+		// The fields don't apply to anchors
+		let anchor = ManipulatorPointId::Anchor(vector_data.point_domain.ids()[0]);
+		editor.handle_message(PathToolMessage::SelectPoints { points: vec![(layer, vec![anchor])] }).await;
+		let responses = editor.editor.handle_message(PathToolMessage::SelectedPointUpdated);
+		assert!(last_number_input(&responses, "Length").is_some_and(|input| input.disabled && input.value.is_none()));
+	}
+
+	#[tokio::test]
+	async fn pasting_into_the_point_table_moves_the_selected_points_in_one_undo_step() {
+		use crate::messages::portfolio::point_table::PointTableSort;
+
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PointTableMessage::ToggleOpen).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		editor.handle_message(PointTableMessage::SortBy { sort: PointTableSort::X }).await;
+		let before = point_positions(&mut editor).await;
+
+		// Sorted by X, the left corners come first, so pasting a column into the X cells moves the left side left and the right side right
+		let text = "40\n40\n160 px\n160 px".to_string();
+		editor.handle_message(PointTableMessage::PasteCoordinates { text, row: 0, column: 2 }).await;
+		let after = point_positions(&mut editor).await;
+		let expected = before.iter().map(|&point| DVec2::new(if point.x < 100. { 40. } else { 160. }, point.y)).collect::<Vec<_>>();
+		assert!(
+			after.iter().zip(&expected).all(|(after, expected)| after.distance(*expected) < 1e-6),
+			"Expected {expected:?} but got {after:?}"
+		);
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await, before, "Undo should restore every pasted point at once");
+	}
+
+	#[tokio::test]
+	async fn recorded_gestures_replay_from_the_recorded_selection() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		let original = point_positions(&mut editor).await;
+
+		editor.handle_message(PreferencesMessage::PathGestureRecording { enabled: true }).await;
+		editor.drag_tool(ToolType::Path, 50., 50., 70., 60., ModifierKeys::empty()).await;
+		let dragged = point_positions(&mut editor).await;
+		assert_ne!(dragged, original);
+
+		let recording = editor
+			.editor
+			.handle_message(PathToolMessage::ExportGestureRecording)
+			.into_iter()
+			.find_map(|message| match message {
+				FrontendMessage::TriggerDownloadTextFile { document, .. } => Some(document),
+				_ => None,
+			})
+			.expect("The recording should be downloaded");
+		editor.handle_message(PreferencesMessage::PathGestureRecording { enabled: false }).await;
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await, original);
+
+		editor.handle_message(PathToolMessage::ReplayGestures { recording, speed: f64::INFINITY }).await;
+		assert_eq!(point_positions(&mut editor).await, dragged, "Replaying the recording should drag the corner again");
+	}
+
+	#[tokio::test]
+	async fn recorded_gesture_fixture_replays_to_the_same_path() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		let original = point_positions(&mut editor).await;
+
+		// Box select the top edge, drag it, then drag a single unselected corner
+		editor.handle_message(PreferencesMessage::PathGestureRecording { enabled: true }).await;
+		editor.drag_tool(ToolType::Path, 30., 30., 170., 70., ModifierKeys::empty()).await;
+		editor.drag_tool(ToolType::Path, 50., 50., 60., 80., ModifierKeys::empty()).await;
+		editor.drag_tool(ToolType::Path, 150., 150., 170., 160., ModifierKeys::empty()).await;
+		let edited = point_positions(&mut editor).await;
+
+		let recording = editor
+			.editor
+			.handle_message(PathToolMessage::ExportGestureRecording)
+			.into_iter()
+			.find_map(|message| match message {
+				FrontendMessage::TriggerDownloadTextFile { document, .. } => Some(document),
+				_ => None,
+			})
+			.expect("The recording should be downloaded");
+		editor.handle_message(PreferencesMessage::PathGestureRecording { enabled: false }).await;
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await, original);
+
+		editor.handle_message(PathToolMessage::ReplayGestures { recording, speed: f64::INFINITY }).await;
+		assert_eq!(point_positions(&mut editor).await, edited, "Replaying the fixture should reproduce every edit");
+	}
+
+	#[tokio::test]
+	async fn gestures_are_not_recorded_unless_enabled() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.drag_tool(ToolType::Path, 50., 50., 70., 60., ModifierKeys::empty()).await;
+
+		let exported = editor
+			.editor
+			.handle_message(PathToolMessage::ExportGestureRecording)
+			.into_iter()
+			.any(|message| matches!(message, FrontendMessage::TriggerDownloadTextFile { .. }));
+		assert!(!exported, "Nothing should have been recorded");
+	}
+
+	#[tokio::test]
+	async fn enter_confirms_point_drag() {
+		let mut editor = EditorTestUtils::create();
+		let undo_history_len = rect_in_path_tool(&mut editor).await;
+
+		editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(50., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(80., 70., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		editor.press(Key::Enter, ModifierKeys::empty()).await;
+		assert_eq!(editor.active_document().undo_history_len(), undo_history_len + 1, "Confirming should commit the drag as one undo step");
+
+		// Moving the still held mouse no longer drags the point
+		editor.move_mouse(120., 90., ModifierKeys::empty(), MouseKeys::LEFT).await;
+		let positions = point_positions(&mut editor).await;
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(80., 70.), 1e-3)),
+			"The point should stay where it was confirmed: {positions:?}"
+		);
+	}
+
+	#[tokio::test]
+	async fn segment_hover_follows_path_edits() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+
+		// Hover the top edge so its segment gets indexed, then move one of its corners
+		editor.move_mouse(100., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.drag_path(&[DVec2::new(50., 50.), DVec2::new(50., 20.)], ModifierKeys::empty()).await;
+		let segment_count = segment_curves(&editor).len();
+
+		// Clicking where the edge now lies inserts a point on it
+		editor.move_mouse(400., 400., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.move_mouse(100., 35., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.drag_path(&[DVec2::new(100., 35.)], ModifierKeys::empty()).await;
+		assert_eq!(segment_curves(&editor).len(), segment_count + 1, "The moved segment should have been split by an inserted point");
+	}
+
+	#[tokio::test]
+	async fn snapping_handle_angle_relative_to_opposite_handle() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_ellipse(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor
+			.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::HandleAngleReference(HandleAngleReference::OppositeHandle)))
+			.await;
+
+		// Free the handles around one anchor and turn the opposite handle away from the canvas axes
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, bezier, _) = segment_curves(&editor)[0];
+		let (previous, _, _) = segment_curves(&editor).into_iter().find(|(_, curve, _)| curve.end.abs_diff_eq(bezier.start, 1e-6)).unwrap();
+		let handles = [HandleId::primary(segment), HandleId::end(previous)];
+		editor
+			.handle_message(GraphOperationMessage::Vector {
+				layer,
+				modification_type: VectorModificationType::SetG1Continuous { handles, enabled: false },
+			})
+			.await;
+		let relative_position = DVec2::from_angle(10_f64.to_radians()) * 30.;
+		editor
+			.handle_message(GraphOperationMessage::Vector {
+				layer,
+				modification_type: VectorModificationType::SetEndHandle { segment: previous, relative_position },
+			})
+			.await;
+
+		let transform = editor.active_document().metadata().transform_to_viewport(layer);
+		let anchor = transform.transform_point2(bezier.start);
+		let handle = transform.transform_point2(bezier.handle_start().unwrap());
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, anchor, ModifierKeys::empty()).await;
+
+		// Drag the handle to a few degrees off perpendicular to the opposite handle while snapping its angle
+		let opposite_direction = transform.transform_vector2(relative_position).normalize();
+		let target = anchor + DVec2::from_angle(4_f64.to_radians()).rotate(opposite_direction.perp()) * 40.;
+		editor.move_mouse(handle.x, handle.y, ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(handle.x, handle.y, ModifierKeys::empty()).await;
+		editor.move_mouse(target.x, target.y, ModifierKeys::SHIFT, MouseKeys::LEFT).await;
+		let editor_mouse_state = EditorMouseState {
+			editor_position: target,
+			..Default::default()
+		};
+		editor.mouseup(editor_mouse_state, ModifierKeys::SHIFT).await;
+
+		let (_, bezier, _) = segment_curves(&editor).into_iter().find(|(id, _, _)| *id == segment).unwrap();
+		let (_, previous_bezier, _) = segment_curves(&editor).into_iter().find(|(id, _, _)| *id == previous).unwrap();
+		let handle_direction = (bezier.handle_start().unwrap() - bezier.start).normalize();
+		let opposite_direction = (previous_bezier.handle_end().unwrap() - previous_bezier.end).normalize();
+		assert!(
+			handle_direction.dot(opposite_direction).abs() < 1e-9,
+			"The handle should be exactly perpendicular to the opposite handle, not {handle_direction}"
+		);
+	}
+
+	#[tokio::test]
+	async fn ready_hints_follow_the_hovered_anchor_and_the_selection() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::DeselectAllPoints).await;
+
+		// Give one segment a handle, making its start anchor smooth while its end anchor stays sharp
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, bezier, _) = segment_curves(&editor)[0];
+		let modification_type = VectorModificationType::SetPrimaryHandle {
+			segment,
+			relative_position: DVec2::new(30., 40.),
+		};
+		editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+		let transform = editor.active_document().metadata().transform_to_viewport(layer);
+		let smooth_anchor = transform.transform_point2(bezier.start);
+		let sharp_anchor = transform.transform_point2(bezier.end);
+		let has = |labels: &[String], label: &str| labels.iter().any(|existing| existing == label);
+
+		let labels = pointer_move_hint_labels(&mut editor, smooth_anchor).expect("Hovering an anchor should update the hints");
+		assert!(has(&labels, "Make Anchor Sharp"));
+		assert!(!has(&labels, "Delete Selected"), "Nothing is selected yet");
+
+		// Moving while hovering the same anchor doesn't send the hints again
+		assert_eq!(pointer_move_hint_labels(&mut editor, smooth_anchor + DVec2::X), None);
+
+		let labels = pointer_move_hint_labels(&mut editor, sharp_anchor).expect("Hovering another kind of anchor should update the hints");
+		assert!(has(&labels, "Make Anchor Smooth"));
+
+		let labels = pointer_move_hint_labels(&mut editor, DVec2::new(100., 100.)).expect("Leaving the anchor should update the hints");
+		assert!(!has(&labels, "Make Anchor Smooth") && !has(&labels, "Make Anchor Sharp"));
+
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, sharp_anchor, ModifierKeys::empty()).await;
+		let labels = pointer_move_hint_labels(&mut editor, DVec2::new(100., 100.)).expect("Leaving the anchor should update the hints");
+		assert!(has(&labels, "Delete Selected") && has(&labels, "No Dissolve"));
+	}
+
+	#[tokio::test]
+	async fn making_mixed_selection_colinear_skips_anchors_without_two_handles() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Open the rectangle into two endpoints and two sharp corners, then pull a pair of handles which aren't colinear out of one corner, leaving the other handles retracted
+		let layer = editor.get_selected_layer().await.unwrap();
+		let vector_data = editor.active_document().network_interface.compute_modified_vector(layer).unwrap();
+		let segments = vector_data.segment_bezier_iter().map(|(id, _, start, end)| (id, start, end)).collect::<Vec<_>>();
+		let (removed, first_endpoint, last_endpoint) = segments[0];
+		let [(incoming, _, smooth), (outgoing, _, sharp)] = segments[1..].iter().flat_map(|&a| segments[1..].iter().map(move |&b| [a, b])).find(|[a, b]| a.2 == b.1).unwrap();
+		for modification_type in [
+			VectorModificationType::RemoveSegment { id: removed },
+			VectorModificationType::SetHandles {
+				segment: incoming,
+				handles: [Some(DVec2::ZERO), Some(DVec2::new(10., 20.))],
+			},
+			VectorModificationType::SetHandles {
+				segment: outgoing,
+				handles: [Some(DVec2::new(20., 10.)), Some(DVec2::ZERO)],
+			},
+		] {
+			editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+		}
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+
+		let responses = editor.editor.handle_message(PathToolMessage::ManipulatorMakeHandlesColinear);
+		editor.eval_graph().await;
+		let reported = responses.iter().any(|message| match message {
+			FrontendMessage::UpdateInputHints { hint_data } => hint_data
+				.0
+				.iter()
+				.flat_map(|group| &group.0)
+				.any(|hint| hint.label == "Made 1 Anchor Colinear, Skipped 3 Anchors Without Two Handles"),
+			_ => false,
+		});
+		assert!(reported, "The converted and skipped anchors should be reported");
+
+		// Only the smooth anchor became colinear, while the sharp corner and the endpoints didn't grow handles
+		let vector_data = editor.active_document().network_interface.compute_modified_vector(layer).unwrap();
+		assert!(vector_data.colinear(ManipulatorPointId::Anchor(smooth)));
+		for point in [sharp, first_endpoint, last_endpoint] {
+			assert!(!vector_data.colinear(ManipulatorPointId::Anchor(point)));
+			assert!(ShapeState::is_sharp_anchor(&vector_data, point), "Anchor {point:?} should have been left alone");
+		}
+	}
+
+	#[tokio::test]
+	async fn colinear_handles_checkbox_is_indeterminate_for_mixed_selections() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_ellipse(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Free the handles around one of the otherwise colinear anchors
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, bezier, _) = segment_curves(&editor)[0];
+		let (previous, _, _) = segment_curves(&editor).into_iter().find(|(_, curve, _)| curve.end.abs_diff_eq(bezier.start, 1e-6)).unwrap();
+		let handles = [HandleId::primary(segment), HandleId::end(previous)];
+		let modification_type = VectorModificationType::SetG1Continuous { handles, enabled: false };
+		editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+
+		let responses = editor.editor.handle_message(PathToolMessage::SelectedPointUpdated);
+		let mut checkbox = last_colinear_handles_checkbox(&responses).expect("The Colinear Handles checkbox should be updated");
+		assert!(checkbox.indeterminate && !checkbox.checked, "A mix of colinear and free handles should be shown as indeterminate");
+
+		// Clicking it makes everything colinear first, and clicking again makes everything free
+		for (checked, state) in [(true, "colinear"), (false, "free")] {
+			checkbox.checked = checked;
+			checkbox.indeterminate = false;
+			let message = (checkbox.on_update.callback)(&checkbox);
+			editor.handle_message(message).await;
+
+			let responses = editor.editor.handle_message(PathToolMessage::SelectedPointUpdated);
+			checkbox = last_colinear_handles_checkbox(&responses).expect("The Colinear Handles checkbox should be updated");
+			assert!(checkbox.checked == checked && !checkbox.indeterminate, "Every anchor should be {state}");
+		}
+	}
+
+	#[tokio::test]
+	async fn toggling_smooth_sharp_converts_all_the_selected_anchors() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[DVec2::new(50., 50.), DVec2::new(150., 50.)]).await;
+
+		let layer = editor.get_selected_layer().await.unwrap();
+		let sharp_anchors = |editor: &EditorTestUtils| {
+			let vector_data = editor.active_document().network_interface.compute_modified_vector(layer).unwrap();
+			vector_data.point_domain.ids().iter().filter(|&&point| ShapeState::is_sharp_anchor(&vector_data, point)).count()
+		};
+
+		// The corners are sharp, so the selected ones are made smooth, and then sharp again
+		editor.handle_message(PathToolMessage::ToggleSelectedPointsSmoothSharp).await;
+		assert_eq!(sharp_anchors(&editor), 2, "Only the two selected corners should have been made smooth");
+		editor.handle_message(PathToolMessage::ToggleSelectedPointsSmoothSharp).await;
+		assert_eq!(sharp_anchors(&editor), 4, "Both selected anchors should have been made sharp again");
+
+		// With one of them smooth and the other sharp, both end up smooth
+		select_points(&mut editor, &[DVec2::new(50., 50.)]).await;
+		editor.handle_message(PathToolMessage::ToggleSelectedPointsSmoothSharp).await;
+		select_points(&mut editor, &[DVec2::new(50., 50.), DVec2::new(150., 50.)]).await;
+		editor.handle_message(PathToolMessage::ToggleSelectedPointsSmoothSharp).await;
+		assert_eq!(sharp_anchors(&editor), 2);
+
+		// The whole conversion is undone at once
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(sharp_anchors(&editor), 3);
+	}
+
+	#[tokio::test]
+	async fn selecting_all_twice_selects_the_anchors_of_every_visible_layer() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		let first = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(200., 50., 300., 150.).await;
+		let second = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(350., 50., 450., 150.).await;
+		editor.handle_message(DocumentMessage::ToggleSelectedVisibility).await;
+
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![first.to_node()] }).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), 4, "Only the selected layer's anchors should be selected at first");
+
+		// Pressing again takes in every other layer, leaving out the hidden one
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), 8);
+		let document = editor.active_document();
+		let selected_layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect::<Vec<_>>();
+		assert!(selected_layers.len() == 2 && selected_layers.contains(&first) && selected_layers.contains(&second));
+	}
+
+	/// Draws a polyline with so many anchors that selecting them is scanned over more than one chunk, returning its anchor count.
+	async fn draw_chunked_polyline(editor: &mut EditorTestUtils) -> usize {
+		// Along with every anchor, the two handles of every segment are scanned, so this is about one and a half chunks
+		let anchor_count = PATH_SELECTION_CHUNK_SIZE / 2;
+		let anchors = (0..anchor_count).map(|index| DVec2::new(50. + 300. * index as f64 / anchor_count as f64, 100. + (index % 2) as f64));
+		editor.draw_subpaths(vec![Subpath::from_anchors_linear(anchors, false)]).await;
+		anchor_count
+	}
+
+	async fn next_animation_frame(editor: &mut EditorTestUtils) {
+		editor.handle_message(BroadcastMessage::TriggerEvent(BroadcastEvent::AnimationFrame)).await;
+	}
+
+	#[tokio::test]
+	async fn chunked_box_selection_is_only_applied_once_scanned_and_escape_cancels_it() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.select_tool(ToolType::Path).await;
+		let anchor_count = draw_chunked_polyline(&mut editor).await;
+		select_points(&mut editor, &[DVec2::new(50., 100.)]).await;
+
+		// Nothing changes until the last chunk has been scanned
+		editor.drag_path(&[DVec2::new(20., 20.), DVec2::new(400., 200.)], ModifierKeys::empty()).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), 1, "The selection shouldn't be applied while the scan is unfinished");
+		next_animation_frame(&mut editor).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), anchor_count);
+
+		// Cancelling partway through keeps the selection from before, even once further frames pass
+		select_points(&mut editor, &[DVec2::new(50., 100.)]).await;
+		editor.drag_path(&[DVec2::new(20., 20.), DVec2::new(400., 200.)], ModifierKeys::empty()).await;
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+		next_animation_frame(&mut editor).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn selecting_all_anchors_is_scanned_in_chunks_and_escape_cancels_it() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.select_tool(ToolType::Path).await;
+		let anchor_count = draw_chunked_polyline(&mut editor).await;
+		select_points(&mut editor, &[DVec2::new(50., 100.)]).await;
+
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), 1, "The selection shouldn't be applied while the scan is unfinished");
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+		next_animation_frame(&mut editor).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), 1);
+
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		next_animation_frame(&mut editor).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), anchor_count);
+	}
+
+	#[tokio::test]
+	async fn selecting_the_same_kind_picks_the_anchors_as_sharp_or_smooth_as_the_selected_one() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.select_tool(ToolType::Path).await;
+		let sharp = |x: f64| ManipulatorGroup::new(DVec2::new(x, 100.), Some(DVec2::new(x, 100.)), Some(DVec2::new(x, 100.)));
+		let smooth = |x: f64| ManipulatorGroup::new(DVec2::new(x, 100.), Some(DVec2::new(x - 10., 90.)), Some(DVec2::new(x + 10., 110.)));
+		let subpath = Subpath::new(vec![sharp(50.), smooth(100.), sharp(150.), smooth(200.), sharp(250.)], false);
+		editor.draw_subpaths(vec![subpath]).await;
+		let x_positions = |anchors: Vec<DVec2>| anchors.into_iter().map(|anchor| anchor.x).collect::<Vec<_>>();
+
+		select_points(&mut editor, &[DVec2::new(50., 100.)]).await;
+		editor.handle_message(PathToolMessage::SelectSameKind).await;
+		assert_eq!(x_positions(selected_anchor_positions(&mut editor).await), [50., 150., 250.]);
+
+		select_points(&mut editor, &[DVec2::new(100., 100.)]).await;
+		editor.handle_message(PathToolMessage::SelectSameKind).await;
+		assert_eq!(x_positions(selected_anchor_positions(&mut editor).await), [100., 200.]);
+	}
+
+	#[tokio::test]
+	async fn selecting_the_next_point_walks_around_closed_paths_and_stops_at_open_ends() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[DVec2::new(50., 50.)]).await;
+
+		// Each step moves the selection to a neighboring corner, coming back around to the first one
+		let mut previous = DVec2::new(50., 50.);
+		for _ in 0..4 {
+			editor.handle_message(PathToolMessage::SelectNextPoint { extend: Key::Shift }).await;
+			let selected = selected_anchor_positions(&mut editor).await;
+			assert_eq!(selected.len(), 1);
+			assert!((selected[0].distance(previous) - 100.).abs() < 1e-6, "{} should be next to {previous}", selected[0]);
+			previous = selected[0];
+		}
+		assert!(previous.abs_diff_eq(DVec2::new(50., 50.), 1e-6), "The closed path should wrap around");
+
+		// Holding Shift adds each next anchor to the selection instead of nudging it
+		editor.press(Key::ArrowRight, ModifierKeys::ALT | ModifierKeys::SHIFT).await;
+		editor.press(Key::ArrowRight, ModifierKeys::ALT | ModifierKeys::SHIFT).await;
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert_eq!(selected.len(), 3);
+		let start_kept = selected.iter().any(|position| position.abs_diff_eq(DVec2::new(50., 50.), 1e-6));
+		assert!(start_kept, "Nothing should be nudged: {selected:?}");
+
+		// The selection stays at the end of an open path
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_spline(&[DVec2::new(50., 50.), DVec2::new(150., 50.), DVec2::new(150., 150.)]).await;
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[DVec2::new(150., 150.)]).await;
+		editor.handle_message(PathToolMessage::SelectNextPoint { extend: Key::Shift }).await;
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert!(selected.len() == 1 && selected[0].abs_diff_eq(DVec2::new(150., 150.), 1e-6), "Got {selected:?}");
+	}
+
+	#[tokio::test]
+	async fn locking_handle_length_keeps_it_bit_identical() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Give one segment a handle
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, bezier, _) = segment_curves(&editor)[0];
+		let modification_type = VectorModificationType::SetPrimaryHandle {
+			segment,
+			relative_position: DVec2::new(30., 40.),
+		};
+		editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+		let relative_handle = |editor: &EditorTestUtils| {
+			let (_, bezier, _) = segment_curves(editor).into_iter().find(|(id, _, _)| *id == segment).unwrap();
+			bezier.handle_start().unwrap() - bezier.start
+		};
+		let length = relative_handle(&editor).length();
+
+		let transform = editor.active_document().metadata().transform_to_viewport(layer);
+		let anchor = transform.transform_point2(bezier.start);
+		let handle = transform.transform_point2(bezier.start + DVec2::new(30., 40.));
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, anchor, ModifierKeys::empty()).await;
+
+		// Drag the handle far beyond its length, a few degrees off horizontal, while locking its length and snapping its angle
+		let target = anchor + DVec2::new(200., 12.);
+		editor.move_mouse(handle.x, handle.y, ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(handle.x, handle.y, ModifierKeys::empty()).await;
+		editor
+			.input(InputPreprocessorMessage::KeyDown {
+				key: Key::KeyD,
+				key_repeat: false,
+				modifier_keys: ModifierKeys::empty(),
+			})
+			.await;
+		editor.move_mouse(target.x, target.y, ModifierKeys::SHIFT, MouseKeys::LEFT).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+		let constraint_circle = recording.commands().into_iter().any(|command| match command {
+			OverlayCommand::Circle { position, .. } => position.abs_diff_eq(anchor, 0.01),
+			_ => false,
+		});
+		assert!(constraint_circle, "The circle constraining the handle should be drawn around its anchor");
+
+		let editor_mouse_state = EditorMouseState {
+			editor_position: target,
+			..Default::default()
+		};
+		editor.mouseup(editor_mouse_state, ModifierKeys::SHIFT).await;
+		editor
+			.input(InputPreprocessorMessage::KeyUp {
+				key: Key::KeyD,
+				key_repeat: false,
+				modifier_keys: ModifierKeys::empty(),
+			})
+			.await;
+
+		let relative = relative_handle(&editor);
+		assert_eq!(relative.length(), length, "The handle length should be bit-identical, but the handle moved to {relative}");
+		assert!(relative.y.abs() < 1e-9, "The handle angle should snap to horizontal, not {relative}");
+	}
+
+	fn path_modification(editor: &EditorTestUtils) -> Option<graphene_core::vector::VectorModification> {
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next()?;
+		match NodeGraphLayer::new(layer, &document.network_interface).find_input("Path", 1)? {
+			graph_craft::document::value::TaggedValue::VectorModification(modification) => Some(*modification.clone()),
+			_ => None,
+		}
+	}
+
+	fn assert_all_finite(editor: &EditorTestUtils) {
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+
+		assert!(vector_data.point_domain.positions().iter().all(|position| position.is_finite()), "Non-finite anchor position");
+		for (_, bezier, _, _) in vector_data.segment_bezier_iter() {
+			assert!(bezier.get_points().all(|position| position.is_finite()), "Non-finite segment {bezier:?}");
+		}
+	}
+
+	#[tokio::test]
+	async fn dragging_through_zero_length_colinear_handles_stays_finite() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_ellipse(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Collapse one handle onto its anchor while it stays colinear with the handle on the anchor's other side
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, bezier, _) = segment_curves(&editor)[0];
+		let modification_type = VectorModificationType::SetPrimaryHandle {
+			segment,
+			relative_position: DVec2::ZERO,
+		};
+		editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+
+		let transform = editor.active_document().metadata().transform_to_viewport(layer);
+		let anchor = transform.transform_point2(bezier.start);
+		let (_, previous_bezier, _) = segment_curves(&editor).into_iter().find(|(_, curve, _)| curve.end.abs_diff_eq(bezier.start, 1e-6)).unwrap();
+		let opposite_handle = transform.transform_point2(previous_bezier.handle_end().unwrap());
+
+		// Select the anchor to show its handles, then drag the opposite handle equidistantly through the anchor
+		editor.click_tool(ToolType::Path, MouseKeys::LEFT, anchor, ModifierKeys::empty()).await;
+		editor.drag_path(&[opposite_handle, anchor, anchor, anchor + DVec2::new(5., 0.)], ModifierKeys::ALT).await;
+
+		assert_all_finite(&editor);
+	}
+
+	#[tokio::test]
+	async fn non_finite_vector_modification_is_skipped() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, _, _) = segment_curves(&editor)[0];
+		let modification_type = VectorModificationType::SetPrimaryHandle {
+			segment,
+			relative_position: DVec2::NAN,
+		};
+		editor.handle_message(GraphOperationMessage::Vector { layer, modification_type }).await;
+
+		assert_all_finite(&editor);
+		assert_eq!(path_modification(&editor), Some(Default::default()), "The non-finite edit should not have been recorded");
+	}
+
+	#[tokio::test]
+	async fn sanitizing_repairs_non_finite_path_edits() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+
+		// Write the edit directly into the network as an older document might contain it, bypassing the message handler's validation
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, _, _) = segment_curves(&editor)[0];
+		let network_interface = &mut editor.active_document_mut().network_interface;
+		let path_node = NodeGraphLayer::new(layer, network_interface).upstream_node_id_from_name("Path").unwrap();
+		network_interface.vector_modify(
+			&path_node,
+			VectorModificationType::SetPrimaryHandle {
+				segment,
+				relative_position: DVec2::INFINITY,
+			},
+		);
+
+		assert_eq!(network_interface.sanitize_vector_modifications(), 1);
+		assert_eq!(network_interface.sanitize_vector_modifications(), 0, "Everything should have been repaired the first time");
+		assert_all_finite(&editor);
+	}
+
+	#[tokio::test]
+	async fn flatten_path_edits_keeps_path_and_clears_edits() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+		editor.drag_path(&[DVec2::new(50., 50.), DVec2::new(80., 70.)], ModifierKeys::empty()).await;
+
+		let mut before = point_positions(&mut editor).await;
+		assert_ne!(path_modification(&editor), Some(Default::default()), "The drag should be stored as an edit");
+
+		editor.handle_message(PathToolMessage::FlattenPathEdits).await;
+
+		let mut after = point_positions(&mut editor).await;
+		let sort = |positions: &mut Vec<DVec2>| positions.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+		sort(&mut before);
+		sort(&mut after);
+		assert_eq!(before.len(), after.len());
+		assert!(before.iter().zip(&after).all(|(a, b)| a.abs_diff_eq(*b, 1e-6)), "The path changed from {before:?} to {after:?}");
+		assert_eq!(path_modification(&editor), Some(Default::default()), "The edits should have been baked into the path");
+
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		assert!(
+			!is_layer_fed_by_node_of_name(layer, &document.network_interface, "Rectangle"),
+			"The procedural shape should be replaced"
+		);
+
+		// Later edits start again from an empty set of edits
+		editor.drag_path(&[DVec2::new(150., 150.), DVec2::new(160., 160.)], ModifierKeys::empty()).await;
+		let positions = point_positions(&mut editor).await;
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(80., 70.), 1e-6)),
+			"The flattened edit was lost: {positions:?}"
+		);
+		assert!(
+			positions.iter().any(|position| position.abs_diff_eq(DVec2::new(160., 160.), 1e-6)),
+			"The new edit was not applied: {positions:?}"
+		);
+	}
+
+	#[test]
+	fn nudge_acceleration_doubles_up_to_cap() {
+		let step = PATH_NUDGE_ACCELERATION_STEP;
+
+		assert_eq!(nudge_acceleration(0, 8.), 1.);
+		assert_eq!(nudge_acceleration(step - 1, 8.), 1.);
+		assert_eq!(nudge_acceleration(step, 8.), 2.);
+		assert_eq!(nudge_acceleration(step * 2, 8.), 4.);
+		assert_eq!(nudge_acceleration(step * 3, 8.), 8.);
+		assert_eq!(nudge_acceleration(step * 10, 8.), 8.);
+		assert_eq!(nudge_acceleration(step * 10, 1.), 1.);
+	}
+
+	/// Draws a circle with anchors at (100, 50), (150, 100), (100, 150), and (50, 100), then switches to the Path tool with the given overlay mode.
+	async fn circle_in_overlay_mode(editor: &mut EditorTestUtils, mode: PathOverlayMode) {
+		editor.new_document().await;
+		editor.drag_tool(ToolType::Ellipse, 50., 50., 150., 150., ModifierKeys::empty()).await;
+		editor.select_tool(ToolType::Path).await;
+		editor.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::OverlayModeType(mode))).await;
+	}
+
+	async fn select_points(editor: &mut EditorTestUtils, points: &[DVec2]) {
+		for (index, &point) in points.iter().enumerate() {
+			let modifier_keys = if index == 0 { ModifierKeys::empty() } else { ModifierKeys::SHIFT };
+			editor.click_tool(ToolType::Path, MouseKeys::LEFT, point, modifier_keys).await;
+		}
+
+		// Move away so no segment is hovered
+		editor.move_mouse(400., 400., ModifierKeys::empty(), MouseKeys::empty()).await;
+	}
+
+	async fn record_path_overlays(editor: &mut EditorTestUtils) -> OverlayRecording {
+		editor.record_overlays(|overlay_context| PathToolMessage::Overlays(overlay_context).into()).await
+	}
+
+	fn handle_positions(recording: &OverlayRecording) -> Vec<DVec2> {
+		recording
+			.commands()
+			.into_iter()
+			.filter_map(|command| match command {
+				OverlayCommand::ManipulatorHandle { position, .. } => Some(position),
+				_ => None,
+			})
+			.collect()
+	}
+
+	fn lines(recording: &OverlayRecording) -> Vec<(DVec2, DVec2, String)> {
+		recording
+			.commands()
+			.into_iter()
+			.filter_map(|command| match command {
+				OverlayCommand::Line { start, end, color, .. } => Some((start, end, color)),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn overlays_all_handles() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		select_points(&mut editor, &[DVec2::new(100., 50.)]).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+
+		assert_eq!(handle_positions(&recording).len(), 8);
+		let anchors = recording.commands().into_iter().filter(|command| matches!(command, OverlayCommand::ManipulatorAnchor { .. })).count();
+		assert_eq!(anchors, 4);
+		assert_golden("path-tool/all-handles.jsonl", &recording.to_golden());
+	}
+
+	#[tokio::test]
+	async fn cycling_overlay_mode_changes_visible_handles() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+		select_points(&mut editor, &[DVec2::new(100., 50.)]).await;
+
+		// Selected point handles, then frontier handles, then all handles
+		editor.press(Key::KeyH, ModifierKeys::ALT).await;
+		editor.press(Key::KeyH, ModifierKeys::ALT).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+		assert_eq!(handle_positions(&recording).len(), 8);
+	}
+
+	#[tokio::test]
+	async fn overlays_selected_point_handles() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+		select_points(&mut editor, &[DVec2::new(100., 50.)]).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+
+		// Only the handles of the two segments connected to the selected anchor are shown
+		let handles = handle_positions(&recording);
+		assert_eq!(handles.len(), 4);
+		assert!(handles.iter().all(|handle| handle.y < 100.), "Unexpected handles: {handles:?}");
+		assert_golden("path-tool/selected-point-handles.jsonl", &recording.to_golden());
+	}
+
+	#[tokio::test]
+	async fn overlays_frontier_handles() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::FrontierHandles).await;
+		select_points(&mut editor, &[DVec2::new(100., 50.), DVec2::new(150., 100.)]).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+
+		// Only the handles at the two ends of the selected run of anchors are shown, on the left and bottom anchors' sides
+		let mut handles = handle_positions(&recording);
+		handles.sort_by(|a, b| a.x.total_cmp(&b.x));
+		assert_eq!(handles, vec![DVec2::new(50., 72.41), DVec2::new(127.59, 150.)]);
+		assert_golden("path-tool/frontier-handles.jsonl", &recording.to_golden());
+	}
+
+	#[tokio::test]
+	async fn overlays_drawing_marquee() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+
+		editor.move_mouse(200., 200., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(200., 200., ModifierKeys::empty()).await;
+		editor.move_mouse(250., 260., ModifierKeys::empty(), MouseKeys::LEFT).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+
+		let Some(OverlayCommand::Polygon { points, dashed, .. }) = recording.commands().pop() else {
+			panic!("The selection box wasn't drawn last: {:?}", recording.commands());
+		};
+		assert_eq!(points, vec![DVec2::new(200., 200.), DVec2::new(250., 200.), DVec2::new(250., 260.), DVec2::new(200., 260.)]);
+		assert!(!dashed);
+		assert_golden("path-tool/drawing-marquee.jsonl", &recording.to_golden());
+	}
+
+	#[tokio::test]
+	async fn overlays_dragging_snap_axes() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+		select_points(&mut editor, &[DVec2::new(100., 50.)]).await;
+
+		editor.move_mouse(100., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.left_mousedown(100., 50., ModifierKeys::empty()).await;
+		editor.move_mouse(130., 55., ModifierKeys::SHIFT, MouseKeys::LEFT).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+
+		// The drag is mostly horizontal, so it's constrained to the X axis which is drawn in red through the drag start
+		let lines = lines(&recording);
+		assert!(
+			lines.iter().any(|(start, end, color)| color == COLOR_OVERLAY_RED && start.y == 50. && end.y == 50.),
+			"Missing the X axis line: {lines:?}"
+		);
+		assert_golden("path-tool/dragging-snap-axes.jsonl", &recording.to_golden());
+	}
+
+	#[tokio::test]
+	async fn overlays_insert_point() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+
+		// Hover the middle of the top right segment
+		let on_segment = DVec2::new(100., 100.) + DVec2::from_angle(-std::f64::consts::FRAC_PI_4) * 50.;
+		editor.move_mouse(on_segment.x, on_segment.y, ModifierKeys::empty(), MouseKeys::empty()).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+
+		// A single tick perpendicular to the segment shows where a point would be inserted
+		let tick = recording
+			.commands()
+			.into_iter()
+			.filter_map(|command| match command {
+				OverlayCommand::Line { start, end, color, dashed: false, .. } => Some((start, end, color)),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(tick.len(), 1);
+
+		// Along with the handles the point would get, since the segment is curved
+		assert_eq!(handle_positions(&recording).len(), 2);
+		let (start, end, _) = &tick[0];
+		assert!(
+			(*end - *start).normalize().abs().abs_diff_eq(DVec2::splat(std::f64::consts::FRAC_1_SQRT_2), 1e-2),
+			"The tick isn't perpendicular: {tick:?}"
+		);
+		assert_golden("path-tool/insert-point.jsonl", &recording.to_golden());
+	}
+
+	#[tokio::test]
+	async fn retracting_handles_makes_the_selected_anchor_sharp_in_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		select_points(&mut editor, &[DVec2::new(100., 50.)]).await;
+		let handle_lengths = |editor: &EditorTestUtils| {
+			let document = editor.active_document();
+			let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+			let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+			let mut lengths = vector_data
+				.segment_domain
+				.ids()
+				.iter()
+				.flat_map(|&segment| [HandleId::primary(segment), HandleId::end(segment)])
+				.map(|handle| handle.length(&vector_data))
+				.collect::<Vec<_>>();
+			lengths.sort_by(f64::total_cmp);
+			(lengths, vector_data.colinear_manipulators.len())
+		};
+		let (before, colinear_before) = handle_lengths(&editor);
+		assert!(before.iter().all(|&length| length > 1.));
+
+		editor.press(Key::KeyR, ModifierKeys::SHIFT).await;
+		let (after, colinear_after) = handle_lengths(&editor);
+		assert_eq!(
+			after.iter().filter(|&&length| length == 0.).count(),
+			2,
+			"Only the selected anchor's two handles should be retracted: {after:?}"
+		);
+		assert_eq!(&after[2..], &before[2..]);
+		assert_eq!(colinear_after, colinear_before - 1, "The retracted handles should no longer be colinear");
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(handle_lengths(&editor), (before, colinear_before));
+	}
+
+	#[tokio::test]
+	async fn retracting_handles_leaves_the_other_handle_of_a_selected_handles_anchor() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+		let (segment, bezier, _) = segment_curves(&editor)[0];
+		editor
+			.handle_message(PathToolMessage::SelectPoints {
+				points: vec![(layer, vec![ManipulatorPointId::PrimaryHandle(segment)])],
+			})
+			.await;
+
+		editor.handle_message(PathToolMessage::RetractHandles).await;
+		let (_, retracted, _) = segment_curves(&editor).into_iter().find(|&(id, _, _)| id == segment).unwrap();
+		assert_eq!(retracted.handle_start(), Some(bezier.start));
+		assert_eq!(retracted.handle_end(), bezier.handle_end());
+	}
+
+	#[tokio::test]
+	async fn inserting_midpoints_splits_only_segments_between_selected_anchors_keeping_their_shape() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		let original_points = point_positions(&mut editor).await;
+		let on_segment = DVec2::new(100., 100.) + DVec2::from_angle(-std::f64::consts::FRAC_PI_4) * 50.;
+		let (_, original, midpoint) = segment_curves(&editor)
+			.into_iter()
+			.min_by(|(_, _, a), (_, _, b)| a.distance(on_segment).total_cmp(&b.distance(on_segment)))
+			.unwrap();
+
+		// Only the top right segment is between two selected anchors
+		select_points(&mut editor, &[DVec2::new(100., 50.), DVec2::new(150., 100.)]).await;
+		editor.handle_message(PathToolMessage::InsertMidpoints).await;
+
+		assert_eq!(point_positions(&mut editor).await.len(), 5);
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert!(selected.len() == 1 && selected[0].abs_diff_eq(midpoint, 1e-6), "Only the new anchor should be selected: {selected:?}");
+
+		let curves = segment_curves(&editor);
+		assert_eq!(curves.len(), 5);
+		for half in original.split(TValue::Parametric(0.5)) {
+			assert!(
+				curves.iter().any(|(_, bezier, _)| bezier.abs_diff_eq(&half, 1e-9)),
+				"The segment should be split into its exact halves, but the segments are {curves:?}"
+			);
+		}
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		assert_eq!(
+			vector_data.colinear_manipulators.len(),
+			5,
+			"The new anchor's handles and those at the ends of the halves should be colinear"
+		);
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await, original_points);
+	}
+
+	#[test]
+	fn merged_segment_fit_recovers_a_cubic_from_the_pieces_it_was_split_into() {
+		let cubic = Bezier::from_cubic_dvec2(DVec2::ZERO, DVec2::new(10., 40.), DVec2::new(60., 50.), DVec2::new(100., 0.));
+		let [first, rest] = cubic.split(TValue::Parametric(0.3));
+		let [second, third] = rest.split(TValue::Parametric(0.6));
+
+		let merged = ShapeState::merged_segment_fit(&[first, second, third]).unwrap();
+		assert!(merged.abs_diff_eq(&cubic, 1e-6), "Expected {cubic:?} but got {merged:?}");
+
+		// Lines meeting at a corner can't be kept, but the merged segment still leaves its ends towards the corner
+		let corner = [
+			Bezier::from_linear_dvec2(DVec2::ZERO, DVec2::new(50., 50.)),
+			Bezier::from_linear_dvec2(DVec2::new(50., 50.), DVec2::new(100., 0.)),
+		];
+		let merged = ShapeState::merged_segment_fit(&corner).unwrap();
+		let [handle_start, handle_end] = [merged.handle_start().unwrap() - merged.start, merged.handle_end().unwrap() - merged.end];
+		assert!(handle_start.normalize().abs_diff_eq(DVec2::ONE.normalize(), 1e-9) && handle_end.normalize().abs_diff_eq(DVec2::new(-1., 1.).normalize(), 1e-9));
+
+		assert!(ShapeState::merged_segment_fit(&[]).is_none());
+	}
+
+	#[tokio::test]
+	async fn dissolving_an_anchor_while_keeping_the_shape_refits_the_merged_segment_in_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		let original_points = point_positions(&mut editor).await;
+
+		select_points(&mut editor, &[DVec2::new(150., 100.)]).await;
+		editor.press(Key::Delete, ModifierKeys::ALT).await;
+
+		assert_eq!(point_positions(&mut editor).await.len(), 3);
+		assert!(selected_anchor_positions(&mut editor).await.is_empty());
+		let curves = segment_curves(&editor);
+		assert_eq!(curves.len(), 3);
+
+		// The merged segment runs from the top to the bottom of the circle and still bulges out to its right side
+		let (_, _, midpoint) = curves.iter().max_by(|(_, _, a), (_, _, b)| a.x.total_cmp(&b.x)).unwrap();
+		assert!(
+			midpoint.distance(DVec2::new(150., 100.)) < 2.,
+			"The merged segment should pass near the dissolved anchor, but its middle is at {midpoint}"
+		);
+
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		assert_eq!(
+			vector_data.colinear_manipulators.len(),
+			3,
+			"The merged segment's handles should stay colinear with those across its ends"
+		);
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await, original_points);
+	}
+
+	#[tokio::test]
+	async fn dissolving_while_keeping_the_shape_falls_back_to_dissolving_as_usual_next_to_other_selected_anchors() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Every corner is selected, so no anchor is left to end a merged segment at
+		select_points(&mut editor, &[DVec2::new(50., 50.), DVec2::new(150., 50.), DVec2::new(150., 150.), DVec2::new(50., 150.)]).await;
+		editor
+			.handle_message(PathToolMessage::ApplyDestructivePointEdit {
+				edit: DestructivePointEdit::DissolvePreservingShape,
+			})
+			.await;
+
+		assert!(point_positions(&mut editor).await.is_empty());
+		assert!(segment_curves(&editor).is_empty());
+	}
+
+	#[tokio::test]
+	async fn merging_by_distance_welds_stacked_anchors_back_together_in_one_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// Breaking the path at a corner leaves a duplicate anchor stacked on it
+		select_points(&mut editor, &[DVec2::new(50., 50.)]).await;
+		editor.handle_message(PathToolMessage::BreakPath).await;
+		let broken_points = point_positions(&mut editor).await;
+		assert_eq!(broken_points.len(), 5);
+
+		editor.handle_message(PathToolMessage::SelectAllAnchors).await;
+		editor.handle_message(PathToolMessage::MergeByDistance { tolerance: 0.01 }).await;
+
+		let points = point_positions(&mut editor).await;
+		assert_eq!(points.len(), 4, "Only the stacked anchors should be merged: {points:?}");
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert!(
+			selected.len() == 1 && selected[0].abs_diff_eq(DVec2::new(50., 50.), 1e-6),
+			"The merged anchor should be selected: {selected:?}"
+		);
+
+		// The merged anchor takes over both segments, closing the path again
+		let document = editor.active_document();
+		let layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next().unwrap();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		assert_eq!(vector_data.segment_domain.ids().len(), 4);
+		assert!(vector_data.point_domain.ids().iter().all(|&point| vector_data.connected_count(point) == 2));
+
+		editor.handle_message(DocumentMessage::Undo).await;
+		assert_eq!(point_positions(&mut editor).await, broken_points);
+	}
+
+	#[tokio::test]
+	async fn merging_by_distance_places_the_anchor_at_the_centroid_and_drops_segments_collapsing_onto_it() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		// The left side is 100 px long, so it only collapses with a larger tolerance
+		select_points(&mut editor, &[DVec2::new(50., 50.), DVec2::new(50., 150.)]).await;
+		editor.handle_message(PathToolMessage::MergeByDistance { tolerance: 99. }).await;
+		assert_eq!(point_positions(&mut editor).await.len(), 4);
+
+		editor.handle_message(PathToolMessage::MergeByDistance { tolerance: 101. }).await;
+		let points = point_positions(&mut editor).await;
+		assert_eq!(points.len(), 3);
+		assert!(
+			points.iter().any(|point| point.abs_diff_eq(DVec2::new(50., 100.), 1e-6)),
+			"The merged anchor should be between the two: {points:?}"
+		);
+		assert_eq!(segment_curves(&editor).len(), 3, "The segment between the merged anchors should be removed");
+	}
+
+	async fn rotate_selected_points_by_typing(editor: &mut EditorTestUtils, start: Key) {
+		editor.press(start, ModifierKeys::empty()).await;
+		if start != Key::KeyR {
+			editor.press(Key::KeyR, ModifierKeys::empty()).await;
+		}
+		for digit in [Key::Digit1, Key::Digit8, Key::Digit0] {
+			editor.press(digit, ModifierKeys::empty()).await;
+		}
+		editor.press(Key::Enter, ModifierKeys::empty()).await;
+	}
+
+	#[tokio::test]
+	async fn rotating_selected_points_happens_about_the_placed_pivot() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		select_points(&mut editor, &[DVec2::new(150., 50.), DVec2::new(150., 150.)]).await;
+		editor.click_tool(ToolType::Path, MouseKeys::RIGHT, DVec2::new(50., 100.), ModifierKeys::SHIFT).await;
+		editor.move_mouse(400., 400., ModifierKeys::empty(), MouseKeys::empty()).await;
+
+		// Switching from grabbing to rotating keeps the pivot
+		rotate_selected_points_by_typing(&mut editor, Key::KeyG).await;
+
+		let points = point_positions(&mut editor).await;
+		for expected in [DVec2::new(-50., 150.), DVec2::new(-50., 50.)] {
+			assert!(points.iter().any(|point| point.abs_diff_eq(expected, 1e-6)), "Expected a point at {expected} in {points:?}");
+		}
+	}
+
+	#[tokio::test]
+	async fn escape_resets_the_placed_pivot_to_the_middle_of_the_selection() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		editor.select_tool(ToolType::Path).await;
+
+		select_points(&mut editor, &[DVec2::new(150., 50.), DVec2::new(150., 150.)]).await;
+		editor.click_tool(ToolType::Path, MouseKeys::RIGHT, DVec2::new(50., 100.), ModifierKeys::SHIFT).await;
+		editor.move_mouse(400., 400., ModifierKeys::empty(), MouseKeys::empty()).await;
+
+		// The first Escape only backs out of the pivot, keeping the selection
+		editor.press(Key::Escape, ModifierKeys::empty()).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await.len(), 2);
+
+		// Turning halfway about the middle of the selected side just swaps its ends
+		let original_points = point_positions(&mut editor).await;
+		rotate_selected_points_by_typing(&mut editor, Key::KeyR).await;
+		let points = point_positions(&mut editor).await;
+		for expected in original_points {
+			assert!(points.iter().any(|point| point.abs_diff_eq(expected, 1e-6)), "Expected a point at {expected} in {points:?}");
+		}
+	}
+
+	#[tokio::test]
+	async fn overlays_delete_segment() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+
+		// Hover the middle of the top right segment with the delete segment modifier held
+		let on_segment = DVec2::new(100., 100.) + DVec2::from_angle(-std::f64::consts::FRAC_PI_4) * 50.;
+		editor.move_mouse(on_segment.x, on_segment.y, ModifierKeys::ALT, MouseKeys::empty()).await;
+
+		let recording = record_path_overlays(&mut editor).await;
+
+		// A cross shows the segment would be deleted instead
+		assert_eq!(lines(&recording).len(), 2);
+		assert_golden("path-tool/delete-segment.jsonl", &recording.to_golden());
+	}
+
+	#[tokio::test]
+	async fn dragging_any_repeated_copy_moves_its_source_point() {
+		use crate::messages::portfolio::document::utility_types::network_interface::InputConnector;
+		use graph_craft::document::NodeInput;
+		use graph_craft::document::value::TaggedValue;
+
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+
+		// Repeat the rectangle's path into 5 copies, 150 px apart
+		for node_type in ["Path", "Repeat"] {
+			editor
+				.handle_message(NodeGraphMessage::CreateNodeInLayerNoTransaction {
+					node_type: node_type.to_string(),
+					layer,
+				})
+				.await;
+		}
+		let repeat = NodeGraphLayer::new(layer, &editor.active_document().network_interface).upstream_node_id_from_name("Repeat").unwrap();
+		for (index, value) in [(1, TaggedValue::DVec2(DVec2::new(600., 0.))), (3, TaggedValue::U32(5))] {
+			let input_connector = InputConnector::node(repeat, index);
+			editor
+				.handle_message(NodeGraphMessage::SetInput {
+					input_connector,
+					input: NodeInput::value(value, false),
+				})
+				.await;
+		}
+		editor.handle_message(NodeGraphMessage::RunDocumentGraph).await;
+		assert_eq!(editor.active_document().metadata().instance_transforms(layer).len(), 5);
+
+		// Hovering the top left corner of the fourth copy warns that its source is what gets edited
+		editor.select_tool(ToolType::Path).await;
+		let hints = pointer_move_hint_labels(&mut editor, DVec2::new(500., 50.)).unwrap();
+		assert!(hints.iter().any(|label| label == "Editing source of 5 instances"), "Missing the instances hint in {hints:?}");
+
+		// Dragging that corner moves the source corner, which every copy follows once the graph runs again
+		editor.drag_tool(ToolType::Path, 500., 50., 530., 80., ModifierKeys::empty()).await;
+		let document = editor.active_document();
+		let vector_data = document.network_interface.compute_modified_vector(layer).unwrap();
+		let transform = document.metadata().transform_to_viewport(layer);
+		let instance_transforms = document.metadata().instance_transforms(layer);
+		assert_eq!(instance_transforms.len(), 5);
+
+		for (index, &instance_transform) in instance_transforms.iter().enumerate() {
+			let expected = DVec2::new(80. + 150. * index as f64, 80.);
+			let positions = vector_data
+				.point_domain
+				.positions()
+				.iter()
+				.map(|&position| (transform * instance_transform).transform_point2(position))
+				.collect::<Vec<_>>();
+			assert!(positions.iter().any(|position| position.abs_diff_eq(expected, 1e-3)), "Copy {index} should have a corner at {expected}");
+			assert!(
+				!positions.iter().any(|position| position.abs_diff_eq(expected - DVec2::splat(30.), 1e-3)),
+				"Copy {index} still has its old corner"
+			);
+		}
+	}
+
+	/// Saves an edit session from the Path tool, returning the downloaded file.
+	fn save_edit_session(editor: &mut EditorTestUtils) -> String {
+		editor
+			.editor
+			.handle_message(PathToolMessage::SaveEditSession)
+			.into_iter()
+			.find_map(|message| match message {
+				FrontendMessage::TriggerDownloadTextFile { document, .. } => Some(document),
+				_ => None,
+			})
+			.expect("The edit session should be downloaded")
+	}
+
+	#[tokio::test]
+	async fn edit_session_restores_the_viewport_and_selections() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		select_points(&mut editor, &[DVec2::new(100., 50.), DVec2::new(150., 100.)]).await;
+		let ptz = editor.active_document().document_ptz;
+		let selected = selected_anchor_positions(&mut editor).await;
+		let session = save_edit_session(&mut editor);
+
+		editor.handle_message(PathToolMessage::DeselectAllPoints).await;
+		editor
+			.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::OverlayModeType(PathOverlayMode::SelectedPointHandles)))
+			.await;
+		editor.handle_message(NavigationMessage::CanvasPan { delta: DVec2::new(30., 20.) }).await;
+		editor.select_tool(ToolType::Select).await;
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: Vec::new() }).await;
+
+		let responses = editor.editor.handle_message(PortfolioMessage::ImportEditSession { session });
+		assert!(
+			!responses.iter().any(|response| matches!(response, FrontendMessage::DisplayDialog { .. })),
+			"Nothing should be reported missing"
+		);
+		assert_eq!(editor.active_document().document_ptz, ptz);
+		assert_eq!(selected_anchor_positions(&mut editor).await, selected);
+		assert_eq!(handle_positions(&record_path_overlays(&mut editor).await).len(), 8, "The handles of every point should be shown again");
+	}
+
+	#[tokio::test]
+	async fn edit_session_reports_the_points_missing_from_a_changed_document() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+		select_points(&mut editor, &[DVec2::new(100., 50.), DVec2::new(150., 100.)]).await;
+		let session = save_edit_session(&mut editor);
+
+		// One of the saved points is deleted before the session is imported
+		select_points(&mut editor, &[DVec2::new(150., 100.)]).await;
+		editor.handle_message(PathToolMessage::Delete).await;
+		editor.handle_message(PathToolMessage::DeselectAllPoints).await;
+
+		let mut parsed = serde_json::from_str::<EditSession>(&session).unwrap();
+		let unresolved = parsed.retain_resolved(editor.active_document());
+		assert_eq!(unresolved.len(), 1, "Only the deleted point should be missing: {unresolved:?}");
+		assert!(unresolved[0].starts_with("Anchor"));
+
+		let responses = editor.editor.handle_message(PortfolioMessage::ImportEditSession { session });
+		assert!(
+			responses.iter().any(|response| matches!(response, FrontendMessage::DisplayDialog { .. })),
+			"The missing point should be reported"
+		);
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert_eq!(selected.len(), 1);
+		assert!(selected[0].abs_diff_eq(DVec2::new(100., 50.), 1e-6), "The remaining point should be selected");
+	}
+
+	#[tokio::test]
+	async fn edit_session_with_a_deleted_layer_restores_the_rest() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		editor.select_tool(ToolType::Path).await;
+		select_points(&mut editor, &[DVec2::new(0., 0.)]).await;
+		let session = save_edit_session(&mut editor);
+
+		editor.handle_message(DocumentMessage::DeleteSelectedLayers).await;
+		editor.handle_message(NavigationMessage::CanvasPan { delta: DVec2::new(30., 20.) }).await;
+
+		let mut parsed = serde_json::from_str::<EditSession>(&session).unwrap();
+		let saved_ptz = parsed.ptz;
+		assert_eq!(
+			parsed.retain_resolved(editor.active_document()).len(),
+			1,
+			"The layer should be reported once, rather than once for it and again for its points"
+		);
+		assert!(parsed.selected_layers.is_empty() && parsed.selected_points.is_empty());
+
+		let responses = editor.editor.handle_message(PortfolioMessage::ImportEditSession { session });
+		assert!(responses.iter().any(|response| matches!(response, FrontendMessage::DisplayDialog { .. })));
+		assert_eq!(editor.active_document().document_ptz, saved_ptz, "The viewport should be restored even though the layer is gone");
+	}
+
+	#[test]
+	fn lasso_polygon_of_a_long_spiral_is_simplified_without_changing_what_it_selects() {
+		let spiral = (0..20_000)
+			.map(|index| {
+				let angle = index as f64 * 0.001;
+				DVec2::new(200., 200.) + DVec2::from_angle(angle) * (10. + 10. * angle)
+			})
+			.collect::<Vec<_>>();
+		let mut simplified = Vec::new();
+		for &position in &spiral {
+			super::extend_lasso(&mut simplified, position);
+		}
+		assert!(simplified.len() * 20 < spiral.len(), "{} vertices remain of {}", simplified.len(), spiral.len());
+
+		let distance_to_spiral = |position: DVec2| {
+			let closest = spiral.windows(2).map(|segment| {
+				let direction = segment[1] - segment[0];
+				let along = ((position - segment[0]).dot(direction) / direction.length_squared()).clamp(0., 1.);
+				position.distance(segment[0] + direction * along)
+			});
+			closest.fold(f64::INFINITY, f64::min)
+		};
+		let full = SelectionHitTester::new(SelectionShape::Lasso(&spiral)).unwrap();
+		let reduced = SelectionHitTester::new(SelectionShape::Lasso(&simplified)).unwrap();
+		for x in (0..400).step_by(7) {
+			for y in (0..400).step_by(7) {
+				let position = DVec2::new(x as f64, y as f64);
+				if full.contains(position) != reduced.contains(position) {
+					assert!(distance_to_spiral(position) <= 1., "{position} is selected differently despite being far from the lasso");
+				}
+			}
+		}
+	}
+
+	#[tokio::test]
+	async fn lasso_grabs_only_the_handles_shown_by_the_overlay_mode() {
+		let mut editor = EditorTestUtils::create();
+		let lasso_around = |center: DVec2| {
+			let corners = [DVec2::new(-9., -9.), DVec2::new(9., -9.), DVec2::new(9., 9.), DVec2::new(-9., 9.), DVec2::new(-9., -9.)];
+			let side_positions = |side: &[DVec2]| (0..16).map(|step| center + side[0].lerp(side[1], step as f64 / 16.)).collect::<Vec<_>>();
+			corners.windows(2).flat_map(side_positions).collect::<Vec<_>>()
+		};
+		let selected_handles = |recording: &OverlayRecording| {
+			let commands = recording.commands().into_iter();
+			commands.filter(|command| matches!(command, OverlayCommand::ManipulatorHandle { selected: true, .. })).count()
+		};
+		let handle = DVec2::new(100. + 50. * 0.5522847498, 50.);
+
+		// The handle isn't shown while no point is selected, so the lasso passes over it
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::SelectedPointHandles).await;
+		editor.drag_path(&lasso_around(handle), ModifierKeys::CONTROL).await;
+		let show_all_handles = PathOptionsUpdate::OverlayModeType(PathOverlayMode::AllHandles);
+		editor.handle_message(PathToolMessage::UpdateOptions(show_all_handles)).await;
+		assert_eq!(selected_handles(&record_path_overlays(&mut editor).await), 0);
+
+		// Once every handle is shown, the same lasso grabs it
+		editor.drag_path(&lasso_around(handle), ModifierKeys::CONTROL).await;
+		let recording = record_path_overlays(&mut editor).await;
+		assert_eq!(selected_handles(&recording), 1);
+		assert!(handle_positions(&recording).iter().any(|position| position.abs_diff_eq(handle, 1e-3)));
+	}
+
+	#[tokio::test]
+	async fn selection_is_filtered_to_only_anchors_or_only_handles_without_an_undo_step() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		let anchor = DVec2::new(100., 50.);
+		let handle = DVec2::new(100. + 50. * 0.5522847498, 50.);
+		let selected_handles = |recording: &OverlayRecording| {
+			let commands = recording.commands().into_iter();
+			commands.filter(|command| matches!(command, OverlayCommand::ManipulatorHandle { selected: true, .. })).count()
+		};
+		let undo_history_len = editor.active_document().undo_history_len();
+
+		select_points(&mut editor, &[anchor, handle]).await;
+		editor.handle_message(PathToolMessage::SelectOnlyAnchors).await;
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert!(selected.len() == 1 && selected[0].abs_diff_eq(anchor, 1e-6), "Only the anchor should stay selected: {selected:?}");
+		assert_eq!(selected_handles(&record_path_overlays(&mut editor).await), 0);
+
+		// Filtering again changes nothing
+		editor.handle_message(PathToolMessage::SelectOnlyAnchors).await;
+		assert_eq!(selected_anchor_positions(&mut editor).await, selected);
+
+		select_points(&mut editor, &[anchor, handle]).await;
+		editor.handle_message(PathToolMessage::SelectOnlyHandles).await;
+		assert!(selected_anchor_positions(&mut editor).await.is_empty());
+		assert_eq!(selected_handles(&record_path_overlays(&mut editor).await), 1);
+		assert_eq!(editor.active_document().undo_history_len(), undo_history_len, "Filtering the selection shouldn't add an undo step");
+	}
+
+	#[tokio::test]
+	async fn lasso_passes_over_shown_handles_while_only_selecting_anchors() {
+		let mut editor = EditorTestUtils::create();
+		circle_in_overlay_mode(&mut editor, PathOverlayMode::AllHandles).await;
+		editor.handle_message(PathToolMessage::UpdateOptions(PathOptionsUpdate::MarqueeAnchorsOnly(true))).await;
+
+		// The lasso loops around the top anchor and its right handle
+		let anchor = DVec2::new(100., 50.);
+		let lasso = [DVec2::new(90., 35.), DVec2::new(140., 35.), DVec2::new(140., 58.), DVec2::new(90., 58.), DVec2::new(90., 35.)];
+		editor.drag_path(&lasso, ModifierKeys::CONTROL).await;
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert!(selected.len() == 1 && selected[0].abs_diff_eq(anchor, 1e-6), "Only the anchor should be selected: {selected:?}");
+		let recording = record_path_overlays(&mut editor).await;
+		assert!(!recording.commands().iter().any(|command| matches!(command, OverlayCommand::ManipulatorHandle { selected: true, .. })));
+	}
+
+	#[tokio::test]
+	async fn copied_points_are_pasted_into_another_layer_under_new_ids() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let source = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(200., 0., 300., 100.).await;
+		let target = editor.get_selected_layer().await.unwrap();
+		editor.select_tool(ToolType::Path).await;
+
+		// Only the top side runs between the two copied corners
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![source.to_node()] }).await;
+		select_points(&mut editor, &[DVec2::new(0., 0.), DVec2::new(100., 0.)]).await;
+		editor.handle_message(PathToolMessage::CopySelectedPoints).await;
+		let source_ids = editor.active_document().network_interface.compute_modified_vector(source).unwrap().point_domain.ids().to_vec();
+
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![target.to_node()] }).await;
+		let undo_history_len = editor.active_document().undo_history_len();
+		editor.handle_message(PathToolMessage::PasteInPlace).await;
+		let vector_data = editor.active_document().network_interface.compute_modified_vector(target).unwrap();
+		assert_eq!(vector_data.point_domain.ids().len(), 6);
+		assert_eq!(vector_data.segment_domain.ids().len(), 5);
+		assert!(!vector_data.point_domain.ids().iter().any(|id| source_ids.contains(id)), "The pasted points should have new ids");
+		assert_eq!(editor.active_document().undo_history_len(), undo_history_len + 1);
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert_eq!(selected.len(), 2);
+		assert!(selected[0].abs_diff_eq(DVec2::new(0., 0.), 1e-6), "{selected:?}");
+		assert!(selected[1].abs_diff_eq(DVec2::new(100., 0.), 1e-6), "{selected:?}");
+
+		// Pasting at the cursor centers the copied points on it
+		editor.move_mouse(250., 250., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.handle_message(PathToolMessage::PasteAtCursor).await;
+		assert_eq!(selected_layers_document_points(&editor).len(), 8);
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert_eq!(selected.len(), 2);
+		assert!(selected[0].abs_diff_eq(DVec2::new(200., 250.), 1e-6), "{selected:?}");
+		assert!(selected[1].abs_diff_eq(DVec2::new(300., 250.), 1e-6), "{selected:?}");
+	}
+
+	/// Presses the left mouse button, returning the labels of every hint sent in response.
+	fn left_mousedown_hint_labels(editor: &mut EditorTestUtils, position: DVec2) -> Vec<String> {
+		let editor_mouse_state = EditorMouseState {
+			editor_position: position,
+			mouse_keys: MouseKeys::LEFT,
+			..Default::default()
+		};
+		let responses = editor.editor.handle_message(InputPreprocessorMessage::PointerDown {
+			editor_mouse_state,
+			modifier_keys: ModifierKeys::empty(),
+		});
+
+		responses
+			.into_iter()
+			.filter_map(|message| match message {
+				FrontendMessage::UpdateInputHints { hint_data } => Some(hint_data.0.into_iter().flat_map(|group| group.0).map(|hint| hint.label.to_string()).collect::<Vec<_>>()),
+				_ => None,
+			})
+			.flatten()
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn edit_locked_layer_points_cant_be_clicked_box_selected_or_dragged() {
+		let mut editor = EditorTestUtils::create();
+		rect_in_path_tool(&mut editor).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+		let original_points = point_positions(&mut editor).await;
+		editor.handle_message(NodeGraphMessage::ToggleSelectedEditLocked).await;
+
+		// Clicking a corner explains why it can't be selected
+		editor.move_mouse(50., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		let labels = left_mousedown_hint_labels(&mut editor, DVec2::new(50., 50.));
+		assert!(labels.iter().any(|label| label.contains("edit locked")), "Missing the edit lock hint in {labels:?}");
+		editor
+			.mouseup(
+				EditorMouseState {
+					editor_position: DVec2::new(50., 50.),
+					..Default::default()
+				},
+				ModifierKeys::empty(),
+			)
+			.await;
+
+		// Box selecting around every corner, then dragging one, changes nothing
+		editor.drag_path(&[DVec2::new(20., 20.), DVec2::new(180., 180.)], ModifierKeys::empty()).await;
+		editor.drag_path(&[DVec2::new(150., 150.), DVec2::new(180., 190.)], ModifierKeys::empty()).await;
+		assert_eq!(point_positions(&mut editor).await, original_points);
+
+		// The anchors are drawn locked, and none of them turn out to be selected once the lock is lifted
+		let recording = record_path_overlays(&mut editor).await;
+		let locked_anchors = recording.commands().into_iter().filter(|command| matches!(command, OverlayCommand::LockedAnchor { .. })).count();
+		assert_eq!(locked_anchors, 4);
+		editor.handle_message(NodeGraphMessage::ToggleSelectedEditLocked).await;
+		assert!(selected_anchor_positions(&mut editor).await.is_empty());
+
+		// The layer itself stays selected
+		assert_eq!(editor.get_selected_layer().await, Some(layer));
+	}
+
+	#[tokio::test]
+	async fn edit_locked_layer_stays_selectable_as_a_whole() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(50., 50., 150., 150.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+		editor.handle_message(NodeGraphMessage::ToggleSelectedEditLocked).await;
+
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: Vec::new() }).await;
+		editor.click_tool(ToolType::Select, MouseKeys::LEFT, DVec2::new(100., 100.), ModifierKeys::empty()).await;
+		assert_eq!(editor.get_selected_layer().await, Some(layer));
+	}
+
+	#[tokio::test]
+	async fn double_clicking_the_fill_of_a_compound_path_selects_only_that_subpath() {
+		let mut editor = EditorTestUtils::create();
+		editor.new_document().await;
+		editor.select_tool(ToolType::Path).await;
+		let squares = vec![
+			Subpath::new_rect(DVec2::new(0., 0.), DVec2::new(100., 100.)),
+			Subpath::new_rect(DVec2::new(200., 0.), DVec2::new(300., 100.)),
+		];
+		editor.draw_subpaths(squares).await;
+
+		editor.move_mouse(250., 50., ModifierKeys::empty(), MouseKeys::empty()).await;
+		editor.double_click(DVec2::new(250., 50.)).await;
+
+		let selected = selected_anchor_positions(&mut editor).await;
+		assert_eq!(selected.len(), 4);
+		assert!(selected.iter().all(|anchor| anchor.x >= 200.), "Only the right square's anchors should be selected: {selected:?}");
+	}
+}
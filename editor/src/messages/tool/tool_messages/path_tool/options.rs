@@ -0,0 +1,726 @@
+use super::*;
+
+pub struct PathToolOptions {
+	pub(super) path_overlay_mode: PathOverlayMode,
+	/// Distance, in document space, of the offset outline previewed around the selected subpaths.
+	pub(super) offset_distance: f64,
+	/// Whether committing the offset replaces the selected subpaths rather than creating a new layer.
+	pub(super) offset_replaces_path: bool,
+	/// The direction from which snapped handle angles are measured.
+	pub(super) handle_angle_reference: HandleAngleReference,
+	/// The kind of anchor created when inserting a point on a segment.
+	pub(super) new_point_type: NewPointType,
+	/// How far, as a percentage, smoothing moves the handles of the selected points towards their smoothed positions.
+	pub(super) smoothing_strength: f64,
+	/// How far, in document space, rounding the selected corners moves their new anchors along each segment.
+	pub(super) fillet_radius: f64,
+	/// The largest deviation from a straight line, in degrees, of handles flagged as colinear which repairing colinearity realigns rather than unflags.
+	pub(super) colinearity_tolerance: f64,
+	/// How close, in document space, selected anchors have to be to another to be merged with it by distance.
+	pub(super) merge_tolerance: f64,
+	/// Whether width handles are shown at the anchors of the selected layers, which are dragged to set the per-point width.
+	pub(super) edit_widths: bool,
+	/// Whether arrows along the subpaths of the selected layers show the direction they run in.
+	pub(super) show_direction: bool,
+	/// Whether dragging across paths deforms them under a brush, rather than selecting and moving points.
+	pub(super) reshape: bool,
+	/// The radius, in viewport pixels, of the brush which reshapes paths.
+	pub(super) reshape_radius: f64,
+	/// Whether box and lasso selections pass over handles, even those shown by the overlay mode, to only select anchors.
+	pub(super) marquee_anchors_only: bool,
+}
+
+impl Default for PathToolOptions {
+	fn default() -> Self {
+		Self {
+			path_overlay_mode: PathOverlayMode::default(),
+			offset_distance: 0.,
+			offset_replaces_path: false,
+			handle_angle_reference: HandleAngleReference::default(),
+			new_point_type: NewPointType::default(),
+			smoothing_strength: 50.,
+			fillet_radius: 10.,
+			colinearity_tolerance: COLINEARITY_REPAIR_TOLERANCE,
+			merge_tolerance: MERGE_BY_DISTANCE_TOLERANCE,
+			edit_widths: false,
+			show_direction: false,
+			reshape: false,
+			reshape_radius: PATH_RESHAPE_DEFAULT_RADIUS,
+			marquee_anchors_only: false,
+		}
+	}
+}
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum PathOverlayMode {
+	AllHandles = 0,
+	#[default]
+	SelectedPointHandles = 1,
+	FrontierHandles = 2,
+}
+
+impl PathOverlayMode {
+	/// The mode after this one, in the order they appear in the tool options.
+	pub fn next(self) -> Self {
+		match self {
+			PathOverlayMode::AllHandles => PathOverlayMode::SelectedPointHandles,
+			PathOverlayMode::SelectedPointHandles => PathOverlayMode::FrontierHandles,
+			PathOverlayMode::FrontierHandles => PathOverlayMode::AllHandles,
+		}
+	}
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum PathOptionsUpdate {
+	OverlayModeType(PathOverlayMode),
+	OffsetReplacesPath(bool),
+	HandleAngleReference(HandleAngleReference),
+	NewPointType(NewPointType),
+	EditWidths(bool),
+	ShowDirection(bool),
+	Reshape(bool),
+	MarqueeAnchorsOnly(bool),
+}
+
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum HandleAngleReference {
+	#[default]
+	Absolute,
+	OppositeHandle,
+	SegmentChord,
+}
+
+impl fmt::Display for HandleAngleReference {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			HandleAngleReference::Absolute => write!(f, "Absolute Angles"),
+			HandleAngleReference::OppositeHandle => write!(f, "From Opposite Handle"),
+			HandleAngleReference::SegmentChord => write!(f, "From Segment Chord"),
+		}
+	}
+}
+
+/// The kind of anchor created when inserting a point on a segment.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Hash, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum NewPointType {
+	/// Colinear handles along the segment, even when the segment is straight.
+	Smooth,
+	/// Retracted handles, leaving a corner.
+	Sharp,
+	/// Colinear handles only when the segment is curved, so the segment keeps its shape.
+	#[default]
+	MatchSegment,
+}
+
+impl fmt::Display for NewPointType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			NewPointType::Smooth => write!(f, "New Points: Smooth"),
+			NewPointType::Sharp => write!(f, "New Points: Sharp"),
+			NewPointType::MatchSegment => write!(f, "New Points: Match Segment"),
+		}
+	}
+}
+
+impl LayoutHolder for PathTool {
+	fn layout(&self) -> Layout {
+		let single_point = self.tool_data.selection_status.as_one();
+		let sampled_position = self.tool_data.sampled_position;
+
+		let selection_status = &self.tool_data.selection_status;
+		let manipulator_angle = selection_status.angle();
+
+		// Coordinates which differ between the selected points are left blank, to be set for all of them when typed
+		let (selected_x, selected_y) = selection_status.coordinates();
+		let (x, y) = sampled_position.map_or((selected_x, selected_y), |point| (Some(point.x), Some(point.y)));
+
+		// Typed expressions may refer to the selected points' current coordinates as `x` and `y`, if they share them
+		let variables = [selected_x.map(|x| ("x", x)), selected_y.map(|y| ("y", y))];
+		let evaluate = move |expression: &str| evaluate_expression(expression, &variables.into_iter().flatten().collect::<Vec<_>>());
+		let rejected = |axis| single_point.is_some_and(|point| self.tool_data.rejected_coordinate == Some((point.layer, point.id, axis)));
+
+		let x_location = NumberInput::new(x)
+			.unit(" px")
+			.label("X")
+			.min_width(120)
+			.display_decimal_places(self.tool_data.display_decimal_places)
+			.disabled(selection_status.is_none())
+			.min(-((1_u64 << f64::MANTISSA_DIGITS) as f64))
+			.max((1_u64 << f64::MANTISSA_DIGITS) as f64)
+			.forwards_expressions(true)
+			.invalid(rejected(AlignAxis::X))
+			.on_update(move |number_input: &NumberInput| {
+				if let Some(expression) = &number_input.expression {
+					return match evaluate(expression) {
+						Some(new_x) => PathToolMessage::SelectedPointXChanged { new_x }.into(),
+						None => PathToolMessage::SelectedPointCoordinateRejected { axis: AlignAxis::X }.into(),
+					};
+				}
+				if let Some(new_x) = number_input.value.or(x) {
+					PathToolMessage::SelectedPointXChanged { new_x }.into()
+				} else {
+					Message::NoOp
+				}
+			})
+			.widget_holder();
+
+		let y_location = NumberInput::new(y)
+			.unit(" px")
+			.label("Y")
+			.min_width(120)
+			.display_decimal_places(self.tool_data.display_decimal_places)
+			.disabled(selection_status.is_none())
+			.min(-((1_u64 << f64::MANTISSA_DIGITS) as f64))
+			.max((1_u64 << f64::MANTISSA_DIGITS) as f64)
+			.forwards_expressions(true)
+			.invalid(rejected(AlignAxis::Y))
+			.on_update(move |number_input: &NumberInput| {
+				if let Some(expression) = &number_input.expression {
+					return match evaluate(expression) {
+						Some(new_y) => PathToolMessage::SelectedPointYChanged { new_y }.into(),
+						None => PathToolMessage::SelectedPointCoordinateRejected { axis: AlignAxis::Y }.into(),
+					};
+				}
+				if let Some(new_y) = number_input.value.or(y) {
+					PathToolMessage::SelectedPointYChanged { new_y }.into()
+				} else {
+					Message::NoOp
+				}
+			})
+			.widget_holder();
+
+		// The length and angle of the selected handle from its anchor, in document space
+		let handle = single_point.and_then(|point| point.anchor_coordinates.map(|anchor| point.coordinates - anchor));
+		let handle_length = handle.map(|handle| handle.length());
+		let handle_angle = handle.map(|handle| handle.to_angle().to_degrees());
+
+		let handle_length_input = NumberInput::new(handle_length)
+			.unit(" px")
+			.label("Length")
+			.min_width(120)
+			.display_decimal_places(self.tool_data.display_decimal_places)
+			.disabled(handle.is_none())
+			.min(0.)
+			.tooltip("The distance of the selected handle from its anchor")
+			.on_update(move |number_input: &NumberInput| {
+				if let Some(length) = number_input.value.or(handle_length) {
+					PathToolMessage::SelectedHandleLengthChanged { length }.into()
+				} else {
+					Message::NoOp
+				}
+			})
+			.widget_holder();
+
+		let handle_angle_input = NumberInput::new(handle_angle)
+			.unit("°")
+			.label("Angle")
+			.min_width(120)
+			.display_decimal_places(self.tool_data.display_decimal_places)
+			.disabled(handle.is_none())
+			.tooltip("The angle of the selected handle around its anchor, measured from the canvas X axis")
+			.on_update(move |number_input: &NumberInput| {
+				if let Some(degrees) = number_input.value.or(handle_angle) {
+					PathToolMessage::SelectedHandleAngleChanged { degrees }.into()
+				} else {
+					Message::NoOp
+				}
+			})
+			.widget_holder();
+
+		let sample_position_button = IconButton::new("Eyedropper", 16)
+			.tooltip("Sample the position of any anchor, in any layer, into the X and Y fields")
+			.on_update(|_| PathToolMessage::SamplePosition.into())
+			.widget_holder();
+		let apply_sampled_position_button = TextButton::new("Apply")
+			.tooltip("Move the selected point to the sampled position")
+			.disabled(sampled_position.is_none() || single_point.is_none())
+			.on_update(|_| PathToolMessage::ApplySampledPosition.into())
+			.widget_holder();
+
+		let related_seperator = Separator::new(SeparatorType::Related).widget_holder();
+		let unrelated_seperator = Separator::new(SeparatorType::Unrelated).widget_holder();
+
+		let colinear_handles_tooltip = "Keep both handles unbent, each 180° apart, when moving either";
+		let colinear_handles_state = manipulator_angle == Some(ManipulatorAngle::Colinear);
+		let colinear_handle_checkbox = CheckboxInput::new(colinear_handles_state)
+			.indeterminate(manipulator_angle == Some(ManipulatorAngle::Mixed))
+			.disabled(!self.tool_data.can_toggle_colinearity)
+			.on_update(|&CheckboxInput { checked, .. }| {
+				if checked {
+					PathToolMessage::ManipulatorMakeHandlesColinear.into()
+				} else {
+					PathToolMessage::ManipulatorMakeHandlesFree.into()
+				}
+			})
+			.tooltip(colinear_handles_tooltip)
+			.widget_holder();
+		let colinear_handles_label = TextLabel::new("Colinear Handles")
+			.disabled(!self.tool_data.can_toggle_colinearity)
+			.tooltip(colinear_handles_tooltip)
+			.widget_holder();
+
+		let path_overlay_mode_widget = RadioInput::new(vec![
+			RadioEntryData::new("all")
+				.icon("HandleVisibilityAll")
+				.tooltip("Show all handles regardless of selection")
+				.on_update(move |_| PathToolMessage::UpdateOptions(PathOptionsUpdate::OverlayModeType(PathOverlayMode::AllHandles)).into()),
+			RadioEntryData::new("selected")
+				.icon("HandleVisibilitySelected")
+				.tooltip("Show only handles of the segments connected to selected points")
+				.on_update(move |_| PathToolMessage::UpdateOptions(PathOptionsUpdate::OverlayModeType(PathOverlayMode::SelectedPointHandles)).into()),
+			RadioEntryData::new("frontier")
+				.icon("HandleVisibilityFrontier")
+				.tooltip("Show only handles at the frontiers of the segments connected to selected points")
+				.on_update(move |_| PathToolMessage::UpdateOptions(PathOptionsUpdate::OverlayModeType(PathOverlayMode::FrontierHandles)).into()),
+		])
+		.selected_index(Some(self.options.path_overlay_mode as u32))
+		.widget_holder();
+
+		let offset_tooltip = "Preview an outline offset from the selected subpaths, then press Enter to apply it (also adjustable by holding O and scrolling)";
+		let offset_distance = NumberInput::new(Some(self.options.offset_distance))
+			.unit(" px")
+			.label("Offset")
+			.min_width(120)
+			.display_decimal_places(self.tool_data.display_decimal_places)
+			.tooltip(offset_tooltip)
+			.on_update(|number_input: &NumberInput| {
+				PathToolMessage::OffsetDistanceChanged {
+					distance: number_input.value.unwrap_or_default(),
+				}
+				.into()
+			})
+			.widget_holder();
+		let offset_replaces_path_tooltip = "Replace the selected subpaths with their offset instead of creating a new layer";
+		let offset_replaces_path_checkbox = CheckboxInput::new(self.options.offset_replaces_path)
+			.on_update(|&CheckboxInput { checked, .. }| PathToolMessage::UpdateOptions(PathOptionsUpdate::OffsetReplacesPath(checked)).into())
+			.tooltip(offset_replaces_path_tooltip)
+			.widget_holder();
+		let offset_replaces_path_label = TextLabel::new("Replace").tooltip(offset_replaces_path_tooltip).widget_holder();
+
+		let handle_angle_reference_entries = [HandleAngleReference::Absolute, HandleAngleReference::OppositeHandle, HandleAngleReference::SegmentChord]
+			.iter()
+			.map(|reference| {
+				MenuListEntry::new(format!("{reference:?}"))
+					.label(reference.to_string())
+					.on_commit(move |_| PathToolMessage::UpdateOptions(PathOptionsUpdate::HandleAngleReference(*reference)).into())
+			})
+			.collect();
+		let handle_angle_reference_dropdown = DropdownInput::new(vec![handle_angle_reference_entries])
+			.selected_index(Some(self.options.handle_angle_reference as u32))
+			.tooltip("Choose whether the angles of handles snapped with Shift are measured from the canvas axes, from the opposite handle, or from the line to the segment's other anchor")
+			.widget_holder();
+
+		let new_point_type_entries = [NewPointType::Smooth, NewPointType::Sharp, NewPointType::MatchSegment]
+			.iter()
+			.map(|new_point_type| {
+				MenuListEntry::new(format!("{new_point_type:?}"))
+					.label(new_point_type.to_string())
+					.on_commit(move |_| PathToolMessage::UpdateOptions(PathOptionsUpdate::NewPointType(*new_point_type)).into())
+			})
+			.collect();
+		let new_point_type_dropdown = DropdownInput::new(vec![new_point_type_entries])
+			.selected_index(Some(self.options.new_point_type as u32))
+			.tooltip("Choose whether points inserted on segments get colinear handles, retracted handles, or handles only where the segment is curved")
+			.widget_holder();
+
+		let edit_widths_tooltip = "Show handles across the anchors of the selected layers, dragged from the path to set its width there or pressed with a pen to set it by pressure";
+		let edit_widths_checkbox = CheckboxInput::new(self.options.edit_widths)
+			.on_update(|&CheckboxInput { checked, .. }| PathToolMessage::UpdateOptions(PathOptionsUpdate::EditWidths(checked)).into())
+			.tooltip(edit_widths_tooltip)
+			.widget_holder();
+		let edit_widths_label = TextLabel::new("Widths").tooltip(edit_widths_tooltip).widget_holder();
+
+		let show_direction_tooltip = "Show arrows along the subpaths of the selected layers pointing the way each one runs, from its first anchor to its last";
+		let show_direction_checkbox = CheckboxInput::new(self.options.show_direction)
+			.on_update(|&CheckboxInput { checked, .. }| PathToolMessage::UpdateOptions(PathOptionsUpdate::ShowDirection(checked)).into())
+			.tooltip(show_direction_tooltip)
+			.widget_holder();
+		let show_direction_label = TextLabel::new("Direction").tooltip(show_direction_tooltip).widget_holder();
+
+		let reshape_tooltip = "Drag across paths to push the parts of them under the brush along with the cursor, adding anchors where needed (radius adjustable with [ and ])";
+		let reshape_checkbox = CheckboxInput::new(self.options.reshape)
+			.on_update(|&CheckboxInput { checked, .. }| PathToolMessage::UpdateOptions(PathOptionsUpdate::Reshape(checked)).into())
+			.tooltip(reshape_tooltip)
+			.widget_holder();
+		let reshape_label = TextLabel::new("Reshape").tooltip(reshape_tooltip).widget_holder();
+		let reshape_radius_input = NumberInput::new(Some(self.options.reshape_radius))
+			.label("Brush")
+			.unit(" px")
+			.min(1.)
+			.min_width(120)
+			.disabled(!self.options.reshape)
+			.tooltip("The radius of the brush which reshapes paths")
+			.on_update(|number_input: &NumberInput| {
+				PathToolMessage::ReshapeRadiusChanged {
+					radius: number_input.value.unwrap_or(PATH_RESHAPE_DEFAULT_RADIUS),
+				}
+				.into()
+			})
+			.widget_holder();
+
+		let smoothing_strength = self.options.smoothing_strength;
+		let smoothing_strength_input = NumberInput::new(Some(smoothing_strength))
+			.label("Smoothing")
+			.min(0.)
+			.max(100.)
+			.mode_range()
+			.unit("%")
+			.tooltip("How far smoothing moves the handles of the selected points towards a smooth curve through their neighboring points")
+			.on_update(|number_input: &NumberInput| {
+				PathToolMessage::SmoothingStrengthChanged {
+					strength: number_input.value.unwrap_or_default(),
+				}
+				.into()
+			})
+			.widget_holder();
+		let smooth_selected_points_button = TextButton::new("Smooth")
+			.tooltip("Recompute the handles of the selected points to follow a smooth curve through their neighboring points, keeping the points in place")
+			.on_update(move |_| PathToolMessage::SmoothSelectedPoints { strength: smoothing_strength / 100. }.into())
+			.widget_holder();
+
+		let fillet_radius = self.options.fillet_radius;
+		let fillet_radius_input = NumberInput::new(Some(fillet_radius))
+			.label("Radius")
+			.unit(" px")
+			.min(0.)
+			.min_width(120)
+			.display_decimal_places(self.tool_data.display_decimal_places)
+			.disabled(!self.tool_data.can_fillet)
+			.tooltip("How far along each of its segments a rounded corner begins, limited to half the shorter segment")
+			.on_update(|number_input: &NumberInput| {
+				PathToolMessage::FilletRadiusChanged {
+					radius: number_input.value.unwrap_or_default(),
+				}
+				.into()
+			})
+			.widget_holder();
+		let fillet_selected_points_button = TextButton::new("Round")
+			.tooltip("Round the selected anchors joined to two segments with an arc between those segments")
+			.disabled(!self.tool_data.can_fillet)
+			.on_update(move |_| PathToolMessage::FilletSelectedPoints { radius: fillet_radius }.into())
+			.widget_holder();
+
+		let export_gesture_recording_button = self.tool_data.gesture_recording.is_some().then(|| {
+			TextButton::new("Export Gestures")
+				.tooltip("Download the gestures recorded since the last export, which can be replayed to reproduce a bug")
+				.on_update(|_| PathToolMessage::ExportGestureRecording.into())
+				.widget_holder()
+		});
+
+		let flatten_path_edits_button = TextButton::new("Flatten Path Edits")
+			.tooltip("Bake the point and segment edits of the selected layers into their paths, replacing any procedural nodes generating those paths")
+			.on_update(|_| PathToolMessage::FlattenPathEdits.into())
+			.widget_holder();
+
+		let align_buttons = [AlignAxis::X, AlignAxis::Y]
+			.into_iter()
+			.flat_map(|axis| [(axis, AlignAggregate::Min), (axis, AlignAggregate::Center), (axis, AlignAggregate::Max)])
+			.map(|(axis, aggregate)| {
+				let (icon, tooltip) = match (axis, aggregate) {
+					(AlignAxis::X, AlignAggregate::Min) => ("AlignLeft", "Align the selected points to the leftmost one"),
+					(AlignAxis::X, AlignAggregate::Center) => ("AlignHorizontalCenter", "Align the selected points to their average X"),
+					(AlignAxis::X, AlignAggregate::Max) => ("AlignRight", "Align the selected points to the rightmost one"),
+					(AlignAxis::Y, AlignAggregate::Min) => ("AlignTop", "Align the selected points to the topmost one"),
+					(AlignAxis::Y, AlignAggregate::Center) => ("AlignVerticalCenter", "Align the selected points to their average Y"),
+					(AlignAxis::Y, AlignAggregate::Max) => ("AlignBottom", "Align the selected points to the bottommost one"),
+				};
+				IconButton::new(icon, 24)
+					.tooltip(tooltip)
+					.on_update(move |_| PathToolMessage::AlignSelectedPoints { axis, aggregate }.into())
+					.widget_holder()
+			})
+			.collect();
+		let distribute_buttons = [(AlignAxis::X, "Horizontally"), (AlignAxis::Y, "Vertically")]
+			.into_iter()
+			.map(|(axis, label)| {
+				TextButton::new(label)
+					.tooltip("Space the selected points evenly between the two outermost ones")
+					.on_update(move |_| PathToolMessage::DistributeSelectedPoints { axis }.into())
+					.widget_holder()
+			})
+			.collect();
+
+		let colinearity_tolerance = self.options.colinearity_tolerance;
+		let merge_tolerance = self.options.merge_tolerance;
+		let marquee_anchors_only = self.options.marquee_anchors_only;
+		let marquee_anchors_only_tooltip = "Pass over the handles when dragging a box or lasso selection, even while they are shown, so only anchors are selected";
+		let overflow_popover = PopoverButton::new()
+			.style(Some("VerticalEllipsis".into()))
+			.tooltip("More Path Operations")
+			.popover_layout(vec![
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new("Select Points").bold(true).widget_holder()],
+				},
+				LayoutGroup::Row {
+					widgets: vec![
+						TextButton::new("Only Anchors")
+							.tooltip("Deselect the selected handles, keeping the selected anchors")
+							.on_update(|_| PathToolMessage::SelectOnlyAnchors.into())
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextButton::new("Only Handles")
+							.tooltip("Deselect the selected anchors, keeping the selected handles")
+							.on_update(|_| PathToolMessage::SelectOnlyHandles.into())
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextButton::new("Same Kind")
+							.tooltip("Select every anchor which is sharp or smooth like the selected anchors")
+							.on_update(|_| PathToolMessage::SelectSameKind.into())
+							.widget_holder(),
+					],
+				},
+				LayoutGroup::Row {
+					widgets: vec![
+						CheckboxInput::new(marquee_anchors_only)
+							.on_update(|&CheckboxInput { checked, .. }| PathToolMessage::UpdateOptions(PathOptionsUpdate::MarqueeAnchorsOnly(checked)).into())
+							.tooltip(marquee_anchors_only_tooltip)
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextLabel::new("Box and Lasso Select Only Anchors").tooltip(marquee_anchors_only_tooltip).widget_holder(),
+					],
+				},
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new("Align Points").bold(true).widget_holder()],
+				},
+				LayoutGroup::Row { widgets: align_buttons },
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new("Distribute Points").bold(true).widget_holder()],
+				},
+				LayoutGroup::Row { widgets: distribute_buttons },
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new("Repair Colinearity").bold(true).widget_holder()],
+				},
+				LayoutGroup::Row {
+					widgets: vec![
+						NumberInput::new(Some(colinearity_tolerance))
+							.label("Tolerance")
+							.min(0.)
+							.max(90.)
+							.unit("°")
+							.tooltip("Handles flagged as colinear which deviate from a straight line by up to this angle are realigned, and those deviating further are unflagged")
+							.on_update(|number_input: &NumberInput| {
+								PathToolMessage::ColinearityToleranceChanged {
+									tolerance: number_input.value.unwrap_or_default(),
+								}
+								.into()
+							})
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextButton::new("Repair")
+							.tooltip("Fix the selected anchors whose handles are flagged as colinear but aren't quite on one line, as often happens in imported paths")
+							.on_update(move |_| {
+								PathToolMessage::RepairColinearity {
+									tolerance: colinearity_tolerance.to_radians(),
+								}
+								.into()
+							})
+							.widget_holder(),
+					],
+				},
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new("Merge by Distance").bold(true).widget_holder()],
+				},
+				LayoutGroup::Row {
+					widgets: vec![
+						NumberInput::new(Some(merge_tolerance))
+							.label("Tolerance")
+							.min(0.)
+							.unit(" px")
+							.tooltip("Selected anchors this close to one another are merged into one")
+							.on_update(|number_input: &NumberInput| {
+								PathToolMessage::MergeToleranceChanged {
+									tolerance: number_input.value.unwrap_or_default(),
+								}
+								.into()
+							})
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextButton::new("Merge")
+							.tooltip("Weld the selected anchors stacked on top of one another, as often happens in imported paths, into one anchor which keeps all of their segments")
+							.on_update(move |_| PathToolMessage::MergeByDistance { tolerance: merge_tolerance }.into())
+							.widget_holder(),
+					],
+				},
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new("Path Direction").bold(true).widget_holder()],
+				},
+				LayoutGroup::Row {
+					widgets: vec![
+						TextButton::new("Reverse")
+							.tooltip("Reverse the direction of the subpaths containing the selected anchors, which flips where their strokes start and their winding for fills")
+							.on_update(|_| PathToolMessage::ReversePathDirection.into())
+							.widget_holder(),
+					],
+				},
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new("Insert Point").bold(true).widget_holder()],
+				},
+				LayoutGroup::Row {
+					widgets: vec![
+						TextButton::new("Add Point at Length…")
+							.tooltip("Add an anchor to the selected layer's path at a percentage of its length or at a length along it")
+							.on_update(|_| DialogMessage::RequestAddPointAtLengthDialog.into())
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextButton::new("Add Midpoints")
+							.tooltip("Add an anchor halfway along each segment between two selected anchors, keeping its shape, and select the new anchors")
+							.on_update(|_| PathToolMessage::InsertMidpoints.into())
+							.widget_holder(),
+					],
+				},
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new("Edit Session").bold(true).widget_holder()],
+				},
+				LayoutGroup::Row {
+					widgets: vec![
+						TextButton::new("Save…")
+							.tooltip("Download the view and the selected layers and points, so someone with the same document can be shown exactly what you're looking at")
+							.on_update(|_| PathToolMessage::SaveEditSession.into())
+							.widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextButton::new("Load…")
+							.tooltip("Restore the view and the selected layers and points from an edit session saved with this document")
+							.on_update(|_| PortfolioMessage::OpenEditSession.into())
+							.widget_holder(),
+					],
+				},
+			])
+			.widget_holder();
+
+		let snapping_popover = PopoverButton::new()
+			.icon(Some("Snapping".into()))
+			.tooltip("Snapping")
+			.popover_layout(snapping_options_layout(&self.tool_data.snapping_state))
+			.widget_holder();
+
+		let mut widgets = vec![
+			x_location,
+			related_seperator.clone(),
+			y_location,
+			related_seperator.clone(),
+			handle_length_input,
+			related_seperator.clone(),
+			handle_angle_input,
+			related_seperator.clone(),
+			sample_position_button,
+			related_seperator.clone(),
+			apply_sampled_position_button,
+			unrelated_seperator.clone(),
+			colinear_handle_checkbox,
+			related_seperator.clone(),
+			colinear_handles_label,
+			unrelated_seperator.clone(),
+			path_overlay_mode_widget,
+			related_seperator.clone(),
+			show_direction_checkbox,
+			related_seperator.clone(),
+			show_direction_label,
+			unrelated_seperator.clone(),
+			handle_angle_reference_dropdown,
+			unrelated_seperator.clone(),
+			new_point_type_dropdown,
+			unrelated_seperator.clone(),
+			edit_widths_checkbox,
+			related_seperator.clone(),
+			edit_widths_label,
+			unrelated_seperator.clone(),
+			reshape_checkbox,
+			related_seperator.clone(),
+			reshape_label,
+			related_seperator.clone(),
+			reshape_radius_input,
+			unrelated_seperator.clone(),
+			offset_distance,
+			related_seperator.clone(),
+			offset_replaces_path_checkbox,
+			related_seperator.clone(),
+			offset_replaces_path_label,
+			unrelated_seperator.clone(),
+			smoothing_strength_input,
+			related_seperator.clone(),
+			smooth_selected_points_button,
+			unrelated_seperator.clone(),
+			fillet_radius_input,
+			related_seperator,
+			fillet_selected_points_button,
+			unrelated_seperator.clone(),
+			flatten_path_edits_button,
+			unrelated_seperator.clone(),
+			snapping_popover,
+			related_seperator.clone(),
+			overflow_popover,
+		];
+		if let Some(export_gesture_recording_button) = export_gesture_recording_button {
+			widgets.extend([unrelated_seperator, export_gesture_recording_button]);
+		}
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
+	}
+}
+
+/// The contents of the snapping popover in the tool options, which edits the document's snapping settings relevant to editing paths.
+fn snapping_options_layout(snapping_state: &SnappingState) -> Vec<LayoutGroup> {
+	let mut snapping_state = snapping_state.clone();
+
+	let mut layout = vec![
+		LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Snapping").bold(true).widget_holder()],
+		},
+		LayoutGroup::Row {
+			widgets: vec![
+				CheckboxInput::new(snapping_state.snapping_enabled)
+					.on_update(|input: &CheckboxInput| {
+						DocumentMessage::SetSnapping {
+							closure: Some(|snapping_state| &mut snapping_state.snapping_enabled),
+							snapping_state: input.checked,
+						}
+						.into()
+					})
+					.widget_holder(),
+				TextLabel::new("Enabled").widget_holder(),
+			],
+		},
+		LayoutGroup::Row {
+			widgets: vec![
+				CheckboxInput::new(snapping_state.grid_snapping)
+					.on_update(|input: &CheckboxInput| DocumentMessage::GridVisibility(input.checked).into())
+					.tooltip("Snaps to the grid, which is also made visible")
+					.widget_holder(),
+				TextLabel::new("Grid").tooltip("Snaps to the grid, which is also made visible").widget_holder(),
+			],
+		},
+		LayoutGroup::Row {
+			widgets: vec![TextLabel::new(SnappingOptions::Paths.to_string()).widget_holder()],
+		},
+	];
+
+	layout.extend(SNAP_FUNCTIONS_FOR_PATHS.into_iter().map(|(name, closure, tooltip)| LayoutGroup::Row {
+		widgets: vec![
+			CheckboxInput::new(*closure(&mut snapping_state))
+				.on_update(move |input: &CheckboxInput| DocumentMessage::SetSnapping { closure: Some(closure), snapping_state: input.checked }.into())
+				.tooltip(tooltip)
+				.widget_holder(),
+			TextLabel::new(name).tooltip(tooltip).widget_holder(),
+		],
+	}));
+
+	layout.push(LayoutGroup::Row {
+		widgets: vec![
+			NumberInput::new(Some(snapping_state.tolerance))
+				.label("Snap Distance")
+				.unit(" px")
+				.min(1.)
+				.min_width(200)
+				.tooltip("How close, in screen pixels, the cursor must be to a snap target before it snaps")
+				.on_update(|number_input: &NumberInput| {
+					DocumentMessage::SetSnappingTolerance {
+						tolerance: number_input.value.unwrap_or(8.),
+					}
+					.into()
+				})
+				.widget_holder(),
+		],
+	});
+
+	layout
+}
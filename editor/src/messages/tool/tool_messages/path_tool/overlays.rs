@@ -0,0 +1,344 @@
+use super::*;
+
+impl PathToolFsmState {
+	/// Draws the outlines, anchors, and handles of the selected paths, along with the previews of the current interaction.
+	pub(super) fn draw_overlays(
+		self,
+		mut overlay_context: OverlayContext,
+		tool_data: &mut PathToolData,
+		tool_action_data: &mut ToolActionHandlerData,
+		tool_options: &PathToolOptions,
+		responses: &mut VecDeque<Message>,
+	) {
+		let ToolActionHandlerData {
+			document,
+			input,
+			shape_editor,
+			preferences,
+			..
+		} = tool_action_data;
+
+		let display_anchors = overlay_context.visibility_settings.anchors();
+		let display_handles = overlay_context.visibility_settings.handles();
+		if !display_handles {
+			shape_editor.ignore_selected_handles();
+		} else {
+			shape_editor.mark_selected_handles();
+		}
+		if !display_anchors {
+			shape_editor.ignore_selected_anchors();
+		} else {
+			shape_editor.mark_selected_anchors();
+		}
+
+		// TODO: find the segment ids of which the selected points are a part of
+
+		match tool_options.path_overlay_mode {
+			PathOverlayMode::AllHandles => {
+				path_overlays(document, DrawHandles::All, shape_editor, &mut overlay_context);
+			}
+			PathOverlayMode::SelectedPointHandles => {
+				let selected_segments = selected_segments(document, shape_editor);
+
+				path_overlays(document, DrawHandles::SelectedAnchors(selected_segments), shape_editor, &mut overlay_context);
+			}
+			PathOverlayMode::FrontierHandles => {
+				let selected_segments = selected_segments(document, shape_editor);
+				let selected_points = shape_editor.selected_points();
+				let selected_anchors = selected_points
+					.filter_map(|point_id| if let ManipulatorPointId::Anchor(p) = point_id { Some(*p) } else { None })
+					.collect::<Vec<_>>();
+
+				// Match the behavior of `PathOverlayMode::SelectedPointHandles` when only one point is selected
+				if shape_editor.selected_points().count() == 1 {
+					path_overlays(document, DrawHandles::SelectedAnchors(selected_segments), shape_editor, &mut overlay_context);
+				} else {
+					let mut segment_endpoints: HashMap<SegmentId, Vec<PointId>> = HashMap::new();
+
+					for layer in document.network_interface.selected_nodes().selected_layers(document.metadata()) {
+						let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+
+						// The points which are part of only one segment will be rendered
+						let mut selected_segments_by_point: HashMap<PointId, Vec<SegmentId>> = HashMap::new();
+
+						for (segment_id, _bezier, start, end) in vector_data.segment_bezier_iter() {
+							if selected_segments.contains(&segment_id) {
+								selected_segments_by_point.entry(start).or_default().push(segment_id);
+								selected_segments_by_point.entry(end).or_default().push(segment_id);
+							}
+						}
+
+						for (point, attached_segments) in selected_segments_by_point {
+							if attached_segments.len() == 1 {
+								segment_endpoints.entry(attached_segments[0]).or_default().push(point);
+							} else if !selected_anchors.contains(&point) {
+								segment_endpoints.entry(attached_segments[0]).or_default().push(point);
+								segment_endpoints.entry(attached_segments[1]).or_default().push(point);
+							}
+						}
+					}
+
+					// Now frontier anchors can be sent for rendering overlays
+					path_overlays(document, DrawHandles::FrontierHandles(segment_endpoints), shape_editor, &mut overlay_context);
+				}
+			}
+		}
+
+		// Mark the source copy of instanced geometry, since editing any copy's points edits the source that every copy is made from
+		for layer in shape_editor.selected_layers().copied() {
+			if document.metadata().instance_transforms(layer).is_empty() {
+				continue;
+			}
+			let Some(bounds) = document.network_interface.compute_modified_vector(layer).and_then(|vector_data| vector_data.bounding_box()) else {
+				continue;
+			};
+
+			let transform = document.metadata().transform_to_viewport(layer);
+			let quad = transform * Quad::from_box(bounds);
+			overlay_context.dashed_quad(quad, Some(COLOR_OVERLAY_BLUE), None, Some(4.), Some(4.), None);
+
+			let label_transform = DAffine2::from_translation(quad.top_left() - DVec2::Y * 4.);
+			overlay_context.text("Source", COLOR_OVERLAY_BLUE, None, label_transform, 0., [Pivot::Start, Pivot::End]);
+		}
+
+		// Draw the width handles across the anchors, with the dragged one following the drag before the document catches up
+		if tool_options.edit_widths {
+			for width_handle in width_handles(document) {
+				let dragged = tool_data.width_drag.filter(|dragged| dragged.layer == width_handle.layer && dragged.point == width_handle.point);
+				let [start, end] = dragged.unwrap_or(width_handle).ends();
+				overlay_context.line(start, end, Some(COLOR_OVERLAY_BLUE), None);
+				overlay_context.manipulator_handle(start, dragged.is_some(), Some(COLOR_OVERLAY_BLUE));
+				overlay_context.manipulator_handle(end, dragged.is_some(), Some(COLOR_OVERLAY_BLUE));
+			}
+		}
+
+		// Draw arrows along the selected subpaths showing which direction they run in
+		if tool_options.show_direction {
+			draw_direction_arrows(document, shape_editor, &mut overlay_context);
+		}
+
+		// Draw the pivot placed for rotating and scaling the selected points
+		if let Some(pivot) = tool_data.custom_pivot.filter(|_| overlay_context.visibility_settings.pivot()) {
+			overlay_context.pivot(document.metadata().document_to_viewport.transform_point2(pivot), 0.);
+		}
+
+		// Draw the reshape brush around the cursor
+		if tool_options.reshape {
+			overlay_context.circle(input.mouse.position, tool_options.reshape_radius, None, Some(COLOR_OVERLAY_BLUE));
+		}
+
+		// Draw the control points of procedural layers as anchors, with the dragged ones following the drag before the document catches up
+		for control_points in selected_control_points(document) {
+			let dragged = tool_data.control_point_drag.as_ref().filter(|drag| drag.control_points.layer == control_points.layer);
+			let control_points = dragged.map_or(&control_points, |drag| &drag.control_points);
+			for (index, position) in control_points.viewport_positions().enumerate() {
+				let selected = tool_data.selected_control_point == Some((control_points.layer, index));
+				overlay_context.manipulator_anchor(position, selected, Some(COLOR_OVERLAY_BLUE));
+			}
+		}
+
+		match self {
+			Self::Ready => {
+				// Ring the point under the cursor, which a click would select
+				if let Some((layer, point)) = tool_data.hovered_point {
+					if let Some(position) = document.network_interface.compute_modified_vector(layer).and_then(|vector_data| point.get_position(&vector_data)) {
+						let position = document.metadata().transform_to_viewport(layer).transform_point2(position);
+						overlay_context.circle(position, PATH_HOVER_RING_RADIUS, None, Some(COLOR_OVERLAY_BLUE));
+					}
+				}
+
+				if let Some(closest_segment) = &tool_data.segment {
+					let perp = closest_segment.calculate_perp(document);
+					let point = closest_segment.closest_point_to_viewport();
+
+					// Stroke over the segment under the cursor, which a click would insert a point on
+					overlay_context.outline_bezier(closest_segment.bezier(), document.metadata().transform_to_viewport(closest_segment.layer()));
+
+					// Draw an X on the segment
+					if tool_data.delete_segment_pressed {
+						let angle = 45_f64.to_radians();
+						let tilted_line = DVec2::from_angle(angle).rotate(perp);
+						let tilted_perp = tilted_line.perp();
+
+						overlay_context.line(point - tilted_line * SEGMENT_OVERLAY_SIZE, point + tilted_line * SEGMENT_OVERLAY_SIZE, Some(COLOR_OVERLAY_BLUE), None);
+						overlay_context.line(point - tilted_perp * SEGMENT_OVERLAY_SIZE, point + tilted_perp * SEGMENT_OVERLAY_SIZE, Some(COLOR_OVERLAY_BLUE), None);
+					}
+					// Draw a line on the segment, with the handles the inserted point would get
+					else {
+						overlay_context.line(point - perp * SEGMENT_OVERLAY_SIZE, point + perp * SEGMENT_OVERLAY_SIZE, Some(COLOR_OVERLAY_BLUE), None);
+
+						if tool_data.segment_shape_source.is_none() {
+							for handle in closest_segment.inserted_handle_positions(document.metadata(), tool_options.new_point_type) {
+								overlay_context.dashed_line(point, handle, Some(COLOR_OVERLAY_BLUE), None, Some(4.), Some(4.), None);
+								overlay_context.manipulator_handle(handle, false, Some(COLOR_OVERLAY_BLUE));
+							}
+						}
+					}
+
+					// Show which of the overlapping segments is targeted while cycling through them
+					if !tool_data.cycled_segments.is_empty() && tool_data.segment_cycle_count > 1 {
+						let text = format!("{}/{}", tool_data.cycled_segments.len(), tool_data.segment_cycle_count);
+						let transform = DAffine2::from_translation(point + DVec2::new(SEGMENT_OVERLAY_SIZE, -SEGMENT_OVERLAY_SIZE));
+						overlay_context.text(&text, COLOR_OVERLAY_WHITE, Some(COLOR_OVERLAY_LABEL_BACKGROUND), transform, 4., [Pivot::Start, Pivot::End]);
+					}
+				}
+
+				// Label the cursor while segments are being given the copied shape
+				if tool_data.segment_shape_source.is_some() {
+					let transform = DAffine2::from_translation(input.mouse.position + DVec2::splat(SEGMENT_OVERLAY_SIZE));
+					overlay_context.text("Copy Shape", COLOR_OVERLAY_WHITE, Some(COLOR_OVERLAY_LABEL_BACKGROUND), transform, 4., [Pivot::Start, Pivot::Start]);
+				}
+
+				// Trace the route just selected between two anchors, with arrows along it showing which way around the path it went
+				if let Some(highlight) = &tool_data.route_highlight {
+					if let Some(vector_data) = document.network_interface.compute_modified_vector(highlight.layer) {
+						let transform = document.metadata().transform_to_viewport(highlight.layer);
+						for (&segment, &from) in highlight.route.segments.iter().zip(&highlight.route.points) {
+							let Some((start, _, bezier)) = vector_data.segment_points_from_id(segment) else { continue };
+							let bezier = if start == from { bezier } else { bezier.reversed() };
+							overlay_context.outline_bezier(bezier, transform);
+
+							let middle = transform.transform_point2(bezier.evaluate(TValue::Parametric(0.5)));
+							let Some(direction) = transform.transform_vector2(bezier.tangent(TValue::Parametric(0.5))).try_normalize() else {
+								continue;
+							};
+							overlay_context.draw_triangle(middle - direction * SEGMENT_OVERLAY_SIZE / 2., direction, SEGMENT_OVERLAY_SIZE, Some(COLOR_OVERLAY_BLUE), None);
+						}
+					}
+				}
+
+				// Draw the offset outline of the selected subpaths
+				if tool_data.offset_preview_active && tool_options.offset_distance != 0. {
+					let document_to_viewport = document.metadata().document_to_viewport;
+					for (_, offsets) in selected_subpath_offsets(document, shape_editor, tool_options.offset_distance) {
+						overlay_context.outline(offsets.iter().map(|(_, offset)| offset), document_to_viewport, Some(COLOR_OVERLAY_BLUE));
+					}
+				}
+			}
+			Self::Drawing { selection_shape } => {
+				// Tinted by what releasing does: replacing the selection, adding to it, or removing from it
+				let stroke_color = match tool_data.marquee_selection_change {
+					SelectionChange::Clear => COLOR_OVERLAY_BLUE,
+					SelectionChange::Extend => COLOR_OVERLAY_GREEN,
+					SelectionChange::Shrink => COLOR_OVERLAY_RED,
+				};
+				let mut fill_color = graphene_std::Color::from_rgb_str(stroke_color.strip_prefix('#').unwrap()).unwrap().with_alpha(0.05).to_rgba_hex_srgb();
+				fill_color.insert(0, '#');
+				let fill_color = Some(fill_color.as_str());
+				let stroke_color = Some(stroke_color);
+
+				let selection_mode = match preferences.get_selection_mode() {
+					SelectionMode::Directional => tool_data.calculate_selection_mode_from_direction(),
+					selection_mode => selection_mode,
+				};
+
+				let quad = tool_data.selection_quad();
+				let polygon = &tool_data.lasso_polygon;
+
+				match (selection_shape, selection_mode) {
+					(SelectionShapeType::Box, SelectionMode::Enclosed) => overlay_context.dashed_quad(quad, stroke_color, fill_color, Some(4.), Some(4.), Some(0.5)),
+					(SelectionShapeType::Lasso, SelectionMode::Enclosed) => overlay_context.dashed_polygon(polygon, stroke_color, fill_color, Some(4.), Some(4.), Some(0.5)),
+					(SelectionShapeType::Box, _) => overlay_context.quad(quad, stroke_color, fill_color),
+					(SelectionShapeType::Lasso, _) => overlay_context.polygon(polygon, stroke_color, fill_color),
+				}
+			}
+			Self::DraggingWidth | Self::DraggingControlPoint | Self::Reshaping => {}
+			Self::DraggingSegment => tool_data.snap_manager.draw_overlays(SnapData::new(document, input), &mut overlay_context),
+			Self::Dragging(_) => {
+				tool_data.snap_manager.draw_overlays(SnapData::new(document, input), &mut overlay_context);
+
+				// Draw the faint circle which the handle is constrained to while its length is locked
+				if tool_data.locked_handle_length.is_some() {
+					if let Some((handle, anchor, _)) = tool_data.try_get_selected_handle_and_anchor(shape_editor, document) {
+						let document_to_viewport = document.metadata().document_to_viewport;
+						let anchor = document_to_viewport.transform_point2(anchor);
+						let radius = anchor.distance(document_to_viewport.transform_point2(handle));
+
+						let faint = |alpha: f32| {
+							let mut color = graphene_std::Color::from_rgb_str(COLOR_OVERLAY_BLUE.strip_prefix('#').unwrap())
+								.unwrap()
+								.with_alpha(alpha)
+								.to_rgba_hex_srgb();
+							color.insert(0, '#');
+							color
+						};
+						overlay_context.circle(anchor, radius, Some(&faint(0.)), Some(&faint(0.25)));
+					}
+				}
+
+				// Show the dragged handle's angle, measured from the chosen reference direction, and length, or how far the points have moved, beside the cursor
+				if let Some(readout) = tool_data.drag_readout {
+					let number = |value: f64| format_number(value, preferences.display_decimal_places);
+					let text = match readout {
+						DragReadout::Handle { degrees, length } => format!("{}°, {}", number(degrees), number(length)),
+						DragReadout::Points { delta } => format!("ΔX {}, ΔY {}", number(delta.x), number(delta.y)),
+					};
+					let transform = DAffine2::from_translation(input.mouse.position + DVec2::new(SEGMENT_OVERLAY_SIZE, -SEGMENT_OVERLAY_SIZE));
+					overlay_context.text(&text, COLOR_OVERLAY_WHITE, Some(COLOR_OVERLAY_LABEL_BACKGROUND), transform, 4., [Pivot::Start, Pivot::End]);
+				}
+
+				// Draw the snapping axis lines along the document's axes, which are turned in the viewport when the canvas is rotated
+				if tool_data.snapping_axis.is_some() {
+					let Some(axis) = tool_data.snapping_axis else { return };
+					let origin = tool_data.drag_start_pos;
+					let viewport_diagonal = input.viewport_bounds.size().length();
+					let document_to_viewport = document.metadata().document_to_viewport;
+					let [x_axis, y_axis] = [DVec2::X, DVec2::Y].map(|axis| document_to_viewport.transform_vector2(axis).try_normalize().unwrap_or(axis) * viewport_diagonal);
+
+					let faded = |color: &str| {
+						let mut color = graphene_std::Color::from_rgb_str(color.strip_prefix('#').unwrap()).unwrap().with_alpha(0.25).to_rgba_hex_srgb();
+						color.insert(0, '#');
+						color
+					};
+					match axis {
+						Axis::Y => {
+							overlay_context.line(origin - y_axis, origin + y_axis, Some(COLOR_OVERLAY_GREEN), None);
+							overlay_context.line(origin - x_axis, origin + x_axis, Some(&faded(COLOR_OVERLAY_RED)), None);
+						}
+						Axis::X | Axis::Both => {
+							overlay_context.line(origin - x_axis, origin + x_axis, Some(COLOR_OVERLAY_RED), None);
+							overlay_context.line(origin - y_axis, origin + y_axis, Some(&faded(COLOR_OVERLAY_GREEN)), None);
+						}
+					}
+				}
+			}
+		}
+
+		responses.add(PathToolMessage::SelectedPointUpdated);
+	}
+}
+
+/// Draws arrowheads spaced evenly along the subpaths of the selected layers, pointing the way each one runs and sized in viewport space so they read the same at any zoom.
+fn draw_direction_arrows(document: &DocumentMessageHandler, shape_editor: &ShapeState, overlay_context: &mut OverlayContext) {
+	for &layer in shape_editor.selected_layers() {
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+		let transform = document.metadata().transform_to_viewport(layer);
+
+		for subpath in vector_data.stroke_bezier_paths() {
+			// The arrows are measured from the start of each subpath, with the first one half the spacing in so it doesn't sit on the starting anchor
+			let mut travelled = 0.;
+			let mut next_arrow = PATH_DIRECTION_ARROW_SPACING / 2.;
+
+			for bezier in subpath.iter() {
+				let bezier = bezier.apply_transformation(|point| transform.transform_point2(point));
+				let samples = bezier.compute_lookup_table(Some(PATH_DIRECTION_ARROW_SAMPLES), None).collect::<Vec<_>>();
+
+				for pair in samples.windows(2) {
+					let [start, end] = [pair[0], pair[1]];
+					let length = start.distance(end);
+					let Some(direction) = (end - start).try_normalize() else { continue };
+
+					while next_arrow <= travelled + length {
+						let tip = start + direction * (next_arrow - travelled);
+						for side in [-1., 1.] {
+							let back = DVec2::from_angle(side * std::f64::consts::FRAC_PI_6).rotate(-direction);
+							overlay_context.line(tip, tip + back * PATH_DIRECTION_ARROW_SIZE, Some(COLOR_OVERLAY_BLUE), None);
+						}
+						next_arrow += PATH_DIRECTION_ARROW_SPACING;
+					}
+					travelled += length;
+				}
+			}
+		}
+	}
+}
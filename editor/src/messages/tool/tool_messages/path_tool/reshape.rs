@@ -0,0 +1,146 @@
+use super::*;
+use crate::consts::{PATH_RESHAPE_FIT_TOLERANCE, PATH_RESHAPE_SAMPLES};
+use bezier_rs::Bezier;
+use graphene_std::vector::{IndexedSegment, SegmentBvh};
+
+/// Fits a cubic through samples of a curve, given with the parametric `t` they were sampled at, keeping the first and last samples as its ends.
+/// The handles are found by least squares over the samples, as in Schneider's curve fitting algorithm. Returns the cubic along with the farthest any sample is from it.
+fn fit_cubic(samples: &[(f64, DVec2)]) -> (Bezier, f64) {
+	let [(first_t, start), (last_t, end)] = [samples[0], samples[samples.len() - 1]];
+	let parameter = |t: f64| (t - first_t) / (last_t - first_t);
+
+	// The normal equations for the two handles, given the weights of the Bernstein polynomials at each sample
+	let (mut matrix, mut right) = ([0.; 3], [DVec2::ZERO; 2]);
+	for &(t, position) in samples {
+		let t = parameter(t);
+		let weights = [(1. - t).powi(3), 3. * t * (1. - t).powi(2), 3. * t.powi(2) * (1. - t), t.powi(3)];
+		let residual = position - start * weights[0] - end * weights[3];
+		matrix[0] += weights[1] * weights[1];
+		matrix[1] += weights[1] * weights[2];
+		matrix[2] += weights[2] * weights[2];
+		right[0] += residual * weights[1];
+		right[1] += residual * weights[2];
+	}
+
+	let determinant = matrix[0] * matrix[2] - matrix[1] * matrix[1];
+	let [handle_start, handle_end] = if determinant.abs() > f64::EPSILON {
+		[(right[0] * matrix[2] - right[1] * matrix[1]) / determinant, (right[1] * matrix[0] - right[0] * matrix[1]) / determinant]
+	} else {
+		[start.lerp(end, 1. / 3.), start.lerp(end, 2. / 3.)]
+	};
+
+	let cubic = Bezier::from_cubic_dvec2(start, handle_start, handle_end, end);
+	let error = samples
+		.iter()
+		.map(|&(t, position)| cubic.evaluate(TValue::Parametric(parameter(t))).distance(position))
+		.fold(0., f64::max);
+	(cubic, error)
+}
+
+/// Deforms the paths of the selected layers under the reshape brush, moving the parts of them within `radius` of `center` along with `delta`, all in viewport space.
+/// Each part moves by the drag scaled by a falloff which eases from all of it at the center to none of it at the radius. The segments touched are fitted again to their displaced samples,
+/// and any that can't follow them closely enough is split with a new anchor, so a stroke only adds anchors where the existing ones are too sparse for the deformation.
+pub(super) fn reshape_paths(document: &DocumentMessageHandler, shape_editor: &ShapeState, center: DVec2, delta: DVec2, radius: f64, responses: &mut VecDeque<Message>) {
+	if radius <= 0. || delta == DVec2::ZERO {
+		return;
+	}
+	let falloff = |position: DVec2| {
+		let distance = position.distance(center) / radius;
+		if distance < 1. { (1. - distance * distance).powi(2) } else { 0. }
+	};
+
+	for &layer in shape_editor.selected_layers() {
+		if ShapeState::layer_edit_locked(&document.network_interface, layer) {
+			continue;
+		}
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+		let to_viewport = document.metadata().transform_to_viewport(layer);
+		let to_layer = to_viewport.inverse();
+		if !to_layer.is_finite() {
+			continue;
+		}
+
+		// Segments within the radius in viewport space are within this distance in layer space, since no direction is shrunk by more than the determinant divided by the Frobenius norm
+		let determinant = to_viewport.matrix2.determinant().abs();
+		let frobenius_norm = (to_viewport.matrix2.x_axis.length_squared() + to_viewport.matrix2.y_axis.length_squared()).sqrt();
+		let layer_radius = radius * frobenius_norm / determinant;
+		let bvh = SegmentBvh::build_from(&vector_data);
+
+		let relative_handles = |cubic: &Bezier| {
+			[
+				cubic.handle_start().map(|handle| to_layer.transform_vector2(handle - cubic.start)),
+				cubic.handle_end().map(|handle| to_layer.transform_vector2(handle - cubic.end)),
+			]
+		};
+
+		let (mut modifications, mut colinear) = (Vec::new(), Vec::new());
+		let mut moved_anchors = HashSet::new();
+		let mut ids = None;
+		for &IndexedSegment { id: segment, bezier, start, end } in bvh.segments_near(to_layer.transform_point2(center), layer_radius) {
+			let bezier = bezier.apply_transformation(|point| to_viewport.transform_point2(point));
+			let samples = (0..=PATH_RESHAPE_SAMPLES)
+				.map(|index| {
+					let t = index as f64 / PATH_RESHAPE_SAMPLES as f64;
+					let position = bezier.evaluate(TValue::Parametric(t));
+					(t, position, falloff(position))
+				})
+				.collect::<Vec<_>>();
+			// Segments only passing near the brush are left as they are, rather than refitted into cubics of the same shape
+			if samples.iter().all(|&(_, _, weight)| weight == 0.) {
+				continue;
+			}
+			let samples = samples.into_iter().map(|(t, position, weight)| (t, position + delta * weight)).collect::<Vec<_>>();
+
+			// An anchor shared by several reshaped segments is only moved once
+			for (anchor, position) in [(start, bezier.start), (end, bezier.end)] {
+				let anchor_delta = to_layer.transform_vector2(delta * falloff(position));
+				if moved_anchors.insert(anchor) && anchor_delta != DVec2::ZERO {
+					modifications.push(VectorModificationType::ApplyPointDelta { point: anchor, delta: anchor_delta });
+				}
+			}
+
+			let (cubic, error) = fit_cubic(&samples);
+			if error <= PATH_RESHAPE_FIT_TOLERANCE {
+				modifications.push(VectorModificationType::SetHandles {
+					segment,
+					handles: relative_handles(&cubic),
+				});
+				continue;
+			}
+
+			// The segment is too coarse to follow the deformation, so it's split at its middle with each half fitted on its own
+			let middle = PATH_RESHAPE_SAMPLES / 2;
+			let halves = [fit_cubic(&samples[..=middle]).0, fit_cubic(&samples[middle..]).0];
+			let ids = *ids.get_or_insert_with(|| OperationIds::new(document, layer, responses));
+			let point = ids.point(segment);
+			let new_segments = [ids.segment((segment, 0)), ids.segment((segment, 1))];
+			modifications.push(VectorModificationType::InsertPoint {
+				id: point,
+				position: to_layer.transform_point2(halves[0].end),
+			});
+			for (index, points) in [[start, point], [point, end]].into_iter().enumerate() {
+				let handles = relative_handles(&halves[index]);
+				modifications.push(VectorModificationType::InsertSegment {
+					id: new_segments[index],
+					points,
+					handles,
+				});
+			}
+			modifications.push(VectorModificationType::RemoveSegment { id: segment });
+
+			// The ends of the split segment keep their handles paired with those of the neighboring segments
+			for (handle, new_handle) in [
+				(HandleId::primary(segment), HandleId::primary(new_segments[0])),
+				(HandleId::end(segment), HandleId::end(new_segments[1])),
+			] {
+				colinear.extend(vector_data.other_colinear_handle(handle).map(|other| [other, new_handle]));
+			}
+		}
+
+		if modifications.is_empty() {
+			continue;
+		}
+		modifications.extend(colinear.into_iter().map(|handles| VectorModificationType::SetG1Continuous { handles, enabled: true }));
+		responses.add(GraphOperationMessage::VectorBatch { layer, modifications });
+	}
+}
@@ -0,0 +1,146 @@
+use super::*;
+
+#[derive(Debug, PartialEq, Default)]
+pub(super) enum SelectionStatus {
+	#[default]
+	None,
+	One(SingleSelectedPoint),
+	Multiple(MultipleSelectedPoints),
+}
+
+impl SelectionStatus {
+	pub(super) fn is_none(&self) -> bool {
+		self == &SelectionStatus::None
+	}
+
+	pub(super) fn as_one(&self) -> Option<&SingleSelectedPoint> {
+		match self {
+			SelectionStatus::One(one) => Some(one),
+			_ => None,
+		}
+	}
+
+	/// The document space X and Y coordinates of the selected points, each `None` if nothing is selected or the selected points differ along it.
+	pub(super) fn coordinates(&self) -> (Option<f64>, Option<f64>) {
+		match self {
+			Self::None => (None, None),
+			Self::One(one) => (Some(one.coordinates.x), Some(one.coordinates.y)),
+			Self::Multiple(multiple) => (multiple.x, multiple.y),
+		}
+	}
+
+	pub(super) fn angle(&self) -> Option<ManipulatorAngle> {
+		match self {
+			Self::None => None,
+			Self::One(one) => Some(one.manipulator_angle),
+			Self::Multiple(one) => Some(one.manipulator_angle),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct MultipleSelectedPoints {
+	/// The document space X coordinate shared by all the selected points, or `None` if they differ.
+	pub(super) x: Option<f64>,
+	/// The document space Y coordinate shared by all the selected points, or `None` if they differ.
+	pub(super) y: Option<f64>,
+	pub(super) manipulator_angle: ManipulatorAngle,
+}
+
+#[derive(Debug, PartialEq)]
+pub(super) struct SingleSelectedPoint {
+	pub(super) coordinates: DVec2,
+	/// The document space position of the anchor the point is a handle of, or `None` if it's an anchor.
+	pub(super) anchor_coordinates: Option<DVec2>,
+	pub(super) id: ManipulatorPointId,
+	pub(super) layer: LayerNodeIdentifier,
+	pub(super) manipulator_angle: ManipulatorAngle,
+}
+
+/// Sets the cumulative description of the selected points: if `None` are selected, if `One` is selected, or if `Multiple` are selected.
+/// Applies to any selected points, whether they are anchors or handles; and whether they are from a single shape or across multiple shapes.
+pub(super) fn get_selection_status(network_interface: &NodeNetworkInterface, shape_state: &mut ShapeState) -> SelectionStatus {
+	let mut selection_layers = shape_state.selected_shape_state.iter().map(|(k, v)| (*k, v.selected_points_count()));
+	let total_selected_points = selection_layers.clone().map(|(_, v)| v).sum::<usize>();
+
+	// Check to see if only one manipulator group in a single shape is selected
+	if total_selected_points == 1 {
+		let Some(layer) = selection_layers.find(|(_, v)| *v > 0).map(|(k, _)| k) else {
+			return SelectionStatus::None;
+		};
+		let Some(vector_data) = network_interface.compute_modified_vector(layer) else {
+			return SelectionStatus::None;
+		};
+		let Some(&point) = shape_state.selected_points().next() else {
+			return SelectionStatus::None;
+		};
+		let Some(local_position) = point.get_position(&vector_data) else {
+			return SelectionStatus::None;
+		};
+
+		let transform = network_interface.document_metadata().transform_to_document(layer);
+		let coordinates = transform.transform_point2(local_position);
+		let anchor_coordinates = point.as_handle().and(point.get_anchor_position(&vector_data)).map(|anchor| transform.transform_point2(anchor));
+		let manipulator_angle = if vector_data.colinear(point) { ManipulatorAngle::Colinear } else { ManipulatorAngle::Free };
+
+		return SelectionStatus::One(SingleSelectedPoint {
+			coordinates,
+			anchor_coordinates,
+			layer,
+			id: point,
+			manipulator_angle,
+		});
+	};
+
+	// Check to see if multiple manipulator groups are selected
+	if total_selected_points > 1 {
+		let (x, y) = shape_state.selected_points_common_coordinates(network_interface);
+		return SelectionStatus::Multiple(MultipleSelectedPoints {
+			x,
+			y,
+			manipulator_angle: shape_state.selected_manipulator_angles(network_interface),
+		});
+	}
+
+	SelectionStatus::None
+}
+
+/// Lists the selected points with their document space coordinates for the Point Table panel, grouped by layer and capped at [`POINT_TABLE_MAX_ROWS`].
+/// Also returns the number of selected points, which may be more than the number of rows.
+pub(super) fn point_table_rows(network_interface: &NodeNetworkInterface, shape_state: &ShapeState) -> (Vec<PointTableRow>, usize) {
+	let mut layers = shape_state.selected_shape_state.iter().filter(|(_, state)| state.selected_points_count() > 0).collect::<Vec<_>>();
+	layers.sort_by_key(|(layer, _)| **layer);
+	let total = layers.iter().map(|(_, state)| state.selected_points_count()).sum();
+
+	let mut rows = Vec::new();
+	for (&layer, state) in layers {
+		let Some(vector_data) = network_interface.compute_modified_vector(layer) else { continue };
+		let transform = network_interface.document_metadata().transform_to_document(layer);
+		let layer_name = network_interface.display_name(&layer.to_node(), &[]);
+
+		let mut points = state.selected().collect::<Vec<_>>();
+		points.sort_by_key(|point| match point {
+			ManipulatorPointId::Anchor(point) => (point.inner(), 0),
+			ManipulatorPointId::PrimaryHandle(segment) => (segment.inner(), 1),
+			ManipulatorPointId::EndHandle(segment) => (segment.inner(), 2),
+		});
+
+		for point in points {
+			if rows.len() == POINT_TABLE_MAX_ROWS {
+				return (rows, total);
+			}
+			let (Some(position), Some(anchor)) = (point.get_position(&vector_data), point.get_anchor_position(&vector_data)) else {
+				continue;
+			};
+			rows.push(PointTableRow {
+				layer,
+				layer_name: layer_name.clone(),
+				point,
+				position: transform.transform_point2(position),
+				anchor: transform.transform_point2(anchor),
+			});
+		}
+	}
+
+	(rows, total)
+}
@@ -0,0 +1,94 @@
+use super::*;
+use crate::messages::input_mapper::utility_types::input_mouse::MouseState;
+
+/// The handles drawn across an anchor, perpendicular to the path, which are dragged to set the width of the path at that point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct WidthHandle {
+	pub(super) layer: LayerNodeIdentifier,
+	pub(super) point: PointId,
+	/// The position of the anchor in viewport space.
+	pub(super) anchor: DVec2,
+	/// The unit direction in viewport space perpendicular to the path at the anchor.
+	pub(super) normal: DVec2,
+	pub(super) width: f64,
+}
+
+impl WidthHandle {
+	/// The viewport positions of the handles on either side of the anchor.
+	pub(super) fn ends(&self) -> [DVec2; 2] {
+		let offset = self.normal * self.width * PATH_WIDTH_HANDLE_LENGTH;
+		[self.anchor + offset, self.anchor - offset]
+	}
+
+	/// The width set by the pressure of a pen, which gives the default width of 1 at half pressure, or else by the cursor's distance from the anchor across the path.
+	pub(super) fn width_from_input(&self, mouse: &MouseState) -> f64 {
+		match mouse.pen {
+			Some(pen) => pen.pressure * 2.,
+			None => (mouse.position - self.anchor).dot(self.normal).abs() / PATH_WIDTH_HANDLE_LENGTH,
+		}
+	}
+
+	/// Sets the width of the point from the input, remembering it so the handles follow before the document is updated.
+	pub(super) fn drag_to(&mut self, mouse: &MouseState, responses: &mut VecDeque<Message>) {
+		self.width = self.width_from_input(mouse);
+		responses.add(GraphOperationMessage::Vector {
+			layer: self.layer,
+			modification_type: VectorModificationType::SetPointWidth { point: self.point, width: self.width },
+		});
+	}
+}
+
+/// The width handles at the anchors of the selected layers that can be edited.
+pub(super) fn width_handles(document: &DocumentMessageHandler) -> Vec<WidthHandle> {
+	let mut handles = Vec::new();
+
+	for layer in document.network_interface.selected_nodes().selected_layers(document.metadata()) {
+		if ShapeState::layer_edit_locked(&document.network_interface, layer) {
+			continue;
+		}
+		let Some(vector_data) = document.network_interface.compute_modified_vector(layer) else { continue };
+		let transform = document.metadata().transform_to_viewport(layer);
+
+		for (&point, &position) in vector_data.point_domain.ids().iter().zip(vector_data.point_domain.positions()) {
+			// The directions leaving the anchor along its segments, towards their handles or, where a handle is missing or retracted, towards the other anchor
+			let directions = vector_data
+				.all_connected(point)
+				.filter_map(|handle| {
+					let target = handle
+						.to_manipulator_point()
+						.get_position(&vector_data)
+						.filter(|handle_position| !handle_position.abs_diff_eq(position, 1e-5))
+						.or_else(|| vector_data.other_point(handle.segment, point).and_then(|other| vector_data.point_domain.position_from_id(other)))?;
+					transform.transform_vector2(target - position).try_normalize()
+				})
+				.take(2)
+				.collect::<Vec<_>>();
+
+			let tangent = match directions[..] {
+				[incoming, outgoing] => (outgoing - incoming).try_normalize().unwrap_or(incoming.perp()),
+				[direction] => direction,
+				_ => continue,
+			};
+
+			handles.push(WidthHandle {
+				layer,
+				point,
+				anchor: transform.transform_point2(position),
+				normal: tangent.perp(),
+				width: vector_data.point_domain.width_from_id(point).unwrap_or(1.),
+			});
+		}
+	}
+
+	handles
+}
+
+/// The width handle with an end closest to the given viewport position, if one is within the selection threshold.
+pub(super) fn hovered_width_handle(document: &DocumentMessageHandler, position: DVec2) -> Option<WidthHandle> {
+	width_handles(document)
+		.into_iter()
+		.map(|handle| (handle.ends().into_iter().map(|end| end.distance_squared(position)).fold(f64::INFINITY, f64::min), handle))
+		.filter(|&(distance_squared, _)| distance_squared <= SELECTION_THRESHOLD.powi(2))
+		.min_by(|(a, _), (b, _)| a.total_cmp(b))
+		.map(|(_, handle)| handle)
+}
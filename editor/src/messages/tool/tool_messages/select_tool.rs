@@ -2,8 +2,8 @@
 
 use super::tool_prelude::*;
 use crate::consts::{
-	COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_RED, COMPASS_ROSE_HOVER_RING_DIAMETER, DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD, RESIZE_HANDLE_SIZE, ROTATE_INCREMENT,
-	SELECTION_DRAG_ANGLE, SELECTION_TOLERANCE,
+	COLOR_OVERLAY_BLUE, COLOR_OVERLAY_GREEN, COLOR_OVERLAY_RED, COMPASS_ROSE_HOVER_RING_DIAMETER, DRAG_DIRECTION_MODE_DETERMINATION_THRESHOLD, LASSO_COLLINEARITY_TOLERANCE, LASSO_MIN_VERTEX_DISTANCE,
+	RESIZE_HANDLE_SIZE, ROTATE_INCREMENT, SELECTION_DRAG_ANGLE, SELECTION_TOLERANCE,
 };
 use crate::messages::input_mapper::utility_types::input_mouse::ViewportPosition;
 use crate::messages::portfolio::document::graph_operation::utility_types::TransformIn;
@@ -508,7 +508,13 @@ impl Fsm for SelectToolFsmState {
 	type ToolOptions = ();
 
 	fn transition(self, event: ToolMessage, tool_data: &mut Self::ToolData, tool_action_data: &mut ToolActionHandlerData, _tool_options: &(), responses: &mut VecDeque<Message>) -> Self {
-		let ToolActionHandlerData { document, input, font_cache, .. } = tool_action_data;
+		let ToolActionHandlerData {
+			document,
+			input,
+			font_cache,
+			preferences,
+			..
+		} = tool_action_data;
 
 		let ToolMessage::Select(event) = event else { return self };
 		match (self, event) {
@@ -635,7 +641,14 @@ impl Fsm for SelectToolFsmState {
 							// Use the viewport-aligned AABBs for measurement
 							if let (Some(selected_bounds), Some(hovered_bounds)) = (selected_bounds_viewport, hovered_bounds_viewport) {
 								// Since we're already in viewport space, use identity transform
-								measure::overlay(selected_bounds, hovered_bounds, DAffine2::IDENTITY, DAffine2::IDENTITY, &mut overlay_context);
+								measure::overlay(
+									selected_bounds,
+									hovered_bounds,
+									DAffine2::IDENTITY,
+									DAffine2::IDENTITY,
+									preferences.display_decimal_places,
+									&mut overlay_context,
+								);
 							}
 						}
 					}
@@ -1849,18 +1862,27 @@ fn edit_layer_deepest_manipulation(layer: LayerNodeIdentifier, network_interface
 	}
 }
 
+/// Appends the cursor position to a lasso polygon, simplifying it as it grows.
+///
+/// The last vertex follows the cursor until it moves on, so it is only kept once the cursor leaves it behind. It is merged away while it stays
+/// within [`LASSO_MIN_VERTEX_DISTANCE`] of the vertex before it, or while it and the cursor stay within [`LASSO_COLLINEARITY_TOLERANCE`] of the
+/// straight run set by the two vertices before it. No dropped position strays from the polygon by more than a pixel, so the polygon selects
+/// the same points as the unsimplified one would, but a long lasso has only as many vertices as it has turns.
 pub fn extend_lasso(lasso_polygon: &mut Vec<DVec2>, point: DVec2) {
-	if lasso_polygon.len() < 2 {
-		lasso_polygon.push(point);
-	} else {
-		let last_points = lasso_polygon.last_chunk::<2>().unwrap();
-		let distance = last_points[0].distance_squared(last_points[1]);
+	let should_replace_last = match lasso_polygon[..] {
+		[.., previous, last] if previous.distance_squared(last) < LASSO_MIN_VERTEX_DISTANCE.powi(2) => true,
+		[.., start, previous, last] => (previous - start).try_normalize().is_some_and(|direction| {
+			let on_run = |position: DVec2| direction.perp_dot(position - start).abs() <= LASSO_COLLINEARITY_TOLERANCE;
+
+			on_run(last) && on_run(point) && direction.dot(last - previous) > 0. && direction.dot(point - last) > 0.
+		}),
+		_ => false,
+	};
 
-		if distance < SELECTION_TOLERANCE.powi(2) {
-			lasso_polygon.pop();
-		}
-		lasso_polygon.push(point);
+	if should_replace_last {
+		lasso_polygon.pop();
 	}
+	lasso_polygon.push(point);
 }
 
 pub fn filter_nested_selection(metadata: &DocumentMetadata, new_selected: &HashSet<LayerNodeIdentifier>) -> HashSet<LayerNodeIdentifier> {
@@ -24,6 +24,7 @@ pub enum TransformLayerMessage {
 	ConstrainY,
 	PointerMove { slow_key: Key, increments_key: Key },
 	SelectionChanged,
+	SetPathPivot { pivot: Option<DVec2> },
 	TypeBackspace,
 	TypeDecimalPoint,
 	TypeDigit { digit: u8 },
@@ -37,6 +37,8 @@ pub struct TransformLayerMessageHandler {
 	pivot: ViewportPosition,
 
 	local_pivot: DocumentPosition,
+	/// The pivot placed in the Path tool to rotate and scale its selected points about, instead of their average position.
+	path_pivot: Option<DocumentPosition>,
 	local_mouse_start: DocumentPosition,
 	grab_target: DocumentPosition,
 
@@ -187,7 +189,7 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 
 				let get_location = |point: &&ManipulatorPointId| point.get_position(&vector_data).map(|position| viewspace.transform_point2(position));
 				if let Some((new_pivot, grab_target)) = calculate_pivot(&selected_points, &vector_data, viewspace, |point: &ManipulatorPointId| get_location(&point)) {
-					*selected.pivot = new_pivot;
+					*selected.pivot = self.path_pivot.map_or(new_pivot, |pivot| document_to_viewport.transform_point2(pivot));
 
 					self.local_pivot = document_to_viewport.inverse().transform_point2(*selected.pivot);
 					self.grab_target = document_to_viewport.inverse().transform_point2(grab_target);
@@ -334,6 +336,7 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 				if final_transform {
 					self.transform_operation = TransformOperation::None;
 					self.operation_count = 0;
+					self.path_pivot = None;
 				}
 
 				if using_pen_tool {
@@ -476,6 +479,7 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 					selected.original_transforms.clear();
 					self.typing.clear();
 					self.transform_operation = TransformOperation::None;
+					self.path_pivot = None;
 
 					responses.add(DocumentMessage::RepeatedAbortTransaction { undo_count: self.operation_count });
 					self.operation_count = 0;
@@ -624,6 +628,7 @@ impl MessageHandler<TransformLayerMessage, TransformData<'_>> for TransformLayer
 				let target_layers = document.network_interface.selected_nodes().selected_layers(document.metadata()).collect();
 				shape_editor.set_selected_layers(target_layers);
 			}
+			TransformLayerMessage::SetPathPivot { pivot } => self.path_pivot = pivot,
 			TransformLayerMessage::TypeBackspace => {
 				let pivot = document_to_viewport.transform_point2(self.local_pivot);
 				if self.typing.digits.is_empty() && self.typing.negative {
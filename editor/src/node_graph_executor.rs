@@ -1,21 +1,24 @@
-use crate::consts::FILE_SAVE_SUFFIX;
-use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::consts::{DEBUG_RENDER_TEXT_LINE_LENGTH, DEBUG_RENDER_TEXT_MAX_LINES, FILE_SAVE_SUFFIX, FINAL_RENDER_DELAY_MS, GRAPH_BUSY_STATE_REPORT_STEP};
+use crate::messages::frontend::utility_types::{ExportBounds, ExportStage, FileType};
+use crate::messages::portfolio::document::utility_types::misc::ViewportRenderer;
 use crate::messages::prelude::*;
 use glam::{DAffine2, DVec2, UVec2};
 use graph_craft::document::value::{RenderOutput, TaggedValue};
 use graph_craft::document::{DocumentNode, DocumentNodeImplementation, NodeId, NodeInput, generate_uuid};
 use graph_craft::proto::GraphErrors;
 use graph_craft::wasm_application_io::EditorPreferences;
-use graphene_core::application_io::{NodeGraphUpdateMessage, RenderConfig};
+use graphene_core::Color;
+use graphene_core::application_io::{ExportFormat, NodeGraphUpdateMessage, RenderConfig, RenderQualityHint, SurfaceFrame};
 use graphene_core::renderer::RenderSvgSegmentList;
 use graphene_core::renderer::{GraphicElementRendered, RenderParams, SvgRender};
 use graphene_core::text::FontCache;
 use graphene_core::transform::Footprint;
 use graphene_core::vector::style::ViewMode;
 use graphene_std::application_io::TimingInformation;
-use graphene_std::renderer::{RenderMetadata, format_transform_matrix};
+use graphene_std::renderer::{Quad, RenderMetadata, format_transform_matrix};
 use graphene_std::vector::VectorData;
 use interpreted_executor::dynamic_executor::ResolvedDocumentNodeTypesDelta;
+use std::fmt::Write;
 
 mod runtime_io;
 pub use runtime_io::NodeRuntimeIO;
@@ -23,10 +26,26 @@ pub use runtime_io::NodeRuntimeIO;
 mod runtime;
 pub use runtime::*;
 
+/// Identifies a viewport the document is rendered into, such as the Document panel or a detached preview window.
+pub type ViewportId = u64;
+
+/// The viewport of the Document panel, whose render also provides the click targets, thumbnails, and inspected values.
+pub const MAIN_VIEWPORT_ID: ViewportId = 0;
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct ExecutionRequest {
 	execution_id: u64,
 	render_config: RenderConfig,
+	/// Further viewports to render the same evaluation into, each with its own footprint. Empty unless extra viewports are open.
+	additional_viewports: Vec<(ViewportId, Footprint)>,
+}
+
+/// The render of one of the [`ExecutionRequest::additional_viewports`].
+#[cfg_attr(feature = "decouple-execution", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewportOutput {
+	viewport_id: ViewportId,
+	result: Result<TaggedValue, String>,
+	transform: DAffine2,
 }
 
 #[cfg_attr(feature = "decouple-execution", derive(serde::Serialize, serde::Deserialize))]
@@ -38,12 +57,31 @@ pub struct ExecutionResponse {
 	vector_modify: HashMap<NodeId, VectorData>,
 	/// The resulting value from the temporary inspected during execution
 	inspect_result: Option<InspectResult>,
+	statistics: ExecutionStatistics,
+	additional_outputs: Vec<ViewportOutput>,
+	/// An SVG group drawn in viewport space over the main viewport's artwork, showing how long each layer took to evaluate, if [`RenderConfig::cost_heatmap`] was set.
+	cost_heatmap: Option<String>,
+}
+
+/// What exporting a document would produce, worked out from its layer bounds without rendering it.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExportDescription {
+	/// The exported region in document space.
+	pub bounds: [DVec2; 2],
+	/// The width and height of the exported image in pixels.
+	pub size: DVec2,
+	/// The names of the artboards overlapping the exported region, in layer order.
+	pub artboards: Vec<String>,
+	/// How many layers besides artboards and groups overlap the exported region.
+	pub layer_count: usize,
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct CompilationResponse {
 	result: Result<ResolvedDocumentNodeTypesDelta, String>,
 	node_graph_errors: GraphErrors,
+	/// Why the application IO couldn't be initialized, in which case the graph was compiled against a fallback without GPU or canvas support.
+	application_io_unavailable: Option<String>,
 }
 
 #[cfg_attr(feature = "decouple-execution", derive(serde::Serialize, serde::Deserialize))]
@@ -51,28 +89,108 @@ pub enum NodeGraphUpdate {
 	ExecutionResponse(ExecutionResponse),
 	CompilationResponse(CompilationResponse),
 	NodeGraphUpdateMessage(NodeGraphUpdateMessage),
+	DocumentStatisticsResponse(DocumentStatistics),
+	/// A hover preview of a layer's content, or `None` if the layer has no rendered content available.
+	LayerPreview {
+		layer_node: NodeId,
+		preview: Option<String>,
+	},
+	/// The color sampled for a canvas probe at a document space position, or `None` if there's no raster content there.
+	ProbeResponse {
+		position: DVec2,
+		color: Option<Color>,
+	},
+	/// A page of rows of the table inspected from `inspect_node`, beginning at row `start`.
+	InspectRows {
+		inspect_node: NodeId,
+		start: usize,
+		rows: Vec<InspectRow>,
+	},
 }
 
 #[derive(Debug)]
 pub struct NodeGraphExecutor {
 	runtime_io: NodeRuntimeIO,
 	futures: HashMap<u64, ExecutionContext>,
+	/// The exports cancelled while awaiting a response, along with when they were queued, whose responses are dropped if they arrive.
+	cancelled_exports: HashMap<u64, f64>,
 	node_graph_hash: u64,
 	old_inspect_node: Option<NodeId>,
+	/// Whether the inspected node follows the document's selected layer rather than being the node requested for each execution.
+	inspect_follows_selection: bool,
+	/// Set once the canvas surface has failed to initialize, after which every document is rendered as SVG for the rest of the session.
+	canvas_unavailable: bool,
+	/// The footprints of the open viewports besides the main one, which are rendered alongside it by every viewport execution.
+	additional_viewports: HashMap<ViewportId, Footprint>,
+	/// The number of pending viewport renders and exports last sent to the frontend.
+	reported_busy_state: (usize, usize),
+	/// Whether viewport renders are overlaid with a debug heatmap of how long each layer took to evaluate.
+	cost_heatmap: bool,
+	/// The quality hint given to viewport renders, which tools set to interactive while a gesture is underway.
+	render_quality: RenderQualityHint,
+	/// The configuration of the latest interactive viewport render and when its response arrived, so it can be repeated at final quality once the interaction pauses.
+	final_render_due: Option<(RenderConfig, f64)>,
+	/// The canvas frame shown in the main viewport, or `None` if it's showing SVG artwork.
+	canvas_frame: Option<DisplayedCanvasFrame>,
+	/// The document-to-viewport transform the overlays are currently drawn with, as last given to [`NodeGraphExecutor::sync_canvas_frame_transform`].
+	document_to_viewport: Option<DAffine2>,
+	/// Set when a viewport render was skipped because the viewport had no area, such as while the window is minimized, so the next render redraws all of it.
+	viewport_render_skipped: bool,
+}
+
+/// A canvas frame shown in the main viewport, kept so its placement can follow the document's pan and zoom until a new render replaces it.
+#[derive(Debug, Clone)]
+struct DisplayedCanvasFrame {
+	frame: SurfaceFrame,
+	/// The document-to-viewport transform the frame was rendered with, which is baked into its pixels.
+	rendered_with: DAffine2,
+	/// The transform of the frame's placeholder in the viewport.
+	placement: DAffine2,
+	debug_overlay: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 struct ExecutionContext {
 	export_config: Option<ExportConfig>,
+	/// When the execution was requested, in milliseconds from [`timestamp_ms`].
+	queued_at: f64,
+	/// The document space bounds of the artwork changed since the previous render, or `None` if the whole viewport may have changed.
+	dirty_region: Option<[DVec2; 2]>,
+	/// The configuration of an interactive viewport render, which is kept to repeat it at final quality.
+	interactive_render_config: Option<RenderConfig>,
+}
+
+/// Milliseconds since an arbitrary fixed point in time, for measuring how long things take on both web and native targets.
+fn timestamp_ms() -> f64 {
+	#[cfg(target_arch = "wasm32")]
+	{
+		js_sys::Date::now()
+	}
+	#[cfg(not(target_arch = "wasm32"))]
+	{
+		static ORIGIN: once_cell::sync::Lazy<std::time::Instant> = once_cell::sync::Lazy::new(std::time::Instant::now);
+		ORIGIN.elapsed().as_secs_f64() * 1000.
+	}
 }
 
 impl Default for NodeGraphExecutor {
 	fn default() -> Self {
 		Self {
 			futures: Default::default(),
+			cancelled_exports: Default::default(),
 			runtime_io: NodeRuntimeIO::new(),
 			node_graph_hash: 0,
 			old_inspect_node: None,
+			inspect_follows_selection: false,
+			canvas_unavailable: false,
+			additional_viewports: HashMap::new(),
+			reported_busy_state: (0, 0),
+			cost_heatmap: false,
+			render_quality: RenderQualityHint::Final,
+			final_render_due: None,
+			canvas_frame: None,
+			document_to_viewport: None,
+			viewport_render_skipped: false,
 		}
 	}
 }
@@ -87,16 +205,31 @@ impl NodeGraphExecutor {
 
 		let node_executor = Self {
 			futures: Default::default(),
+			cancelled_exports: Default::default(),
 			runtime_io: NodeRuntimeIO::with_channels(request_sender, response_receiver),
 			node_graph_hash: 0,
 			old_inspect_node: None,
+			inspect_follows_selection: false,
+			canvas_unavailable: false,
+			additional_viewports: HashMap::new(),
+			reported_busy_state: (0, 0),
+			cost_heatmap: false,
+			render_quality: RenderQualityHint::Final,
+			final_render_due: None,
+			canvas_frame: None,
+			document_to_viewport: None,
+			viewport_render_skipped: false,
 		};
 		(node_runtime, node_executor)
 	}
 	/// Execute the network by flattening it and creating a borrow stack.
-	fn queue_execution(&self, render_config: RenderConfig) -> u64 {
+	fn queue_execution(&self, render_config: RenderConfig, additional_viewports: Vec<(ViewportId, Footprint)>) -> u64 {
 		let execution_id = generate_uuid();
-		let request = ExecutionRequest { execution_id, render_config };
+		let request = ExecutionRequest {
+			execution_id,
+			render_config,
+			additional_viewports,
+		};
 		self.runtime_io.send(GraphRuntimeRequest::ExecutionRequest(request)).expect("Failed to send generation request");
 
 		execution_id
@@ -112,6 +245,46 @@ impl NodeGraphExecutor {
 			.expect("Failed to send editor preferences");
 	}
 
+	/// Asks the runtime to measure the paths of every layer from its last execution. The result arrives as a [`NodeGraphUpdate::DocumentStatisticsResponse`].
+	pub fn request_document_statistics(&self) {
+		self.runtime_io
+			.send(GraphRuntimeRequest::DocumentStatisticsRequest)
+			.expect("Failed to send document statistics request");
+	}
+
+	/// Starts rendering the document into another viewport with the given footprint alongside the main viewport, or stops if `footprint` is `None`.
+	pub fn set_additional_viewport(&mut self, viewport_id: ViewportId, footprint: Option<Footprint>) {
+		if viewport_id == MAIN_VIEWPORT_ID {
+			warn!("The main viewport can't be registered as an additional viewport");
+			return;
+		}
+
+		match footprint {
+			Some(footprint) => self.additional_viewports.insert(viewport_id, footprint),
+			None => self.additional_viewports.remove(&viewport_id),
+		};
+	}
+
+	/// Asks the runtime to sample the color of a layer's raster content at a document space position, given the transform from the space of the layer's output to document space.
+	/// The result arrives as a [`NodeGraphUpdate::ProbeResponse`].
+	pub fn probe_color(&self, layer_node: NodeId, position: DVec2, to_document: DAffine2) {
+		self.runtime_io
+			.send(GraphRuntimeRequest::ProbeRequest { position, layer_node, to_document })
+			.expect("Failed to send probe request");
+	}
+
+	/// Asks the runtime to render a layer's content from its last execution at up to `max_size` pixels. The result arrives as a [`NodeGraphUpdate::LayerPreview`].
+	pub fn request_layer_preview(&self, layer_node: NodeId, max_size: f64) {
+		self.runtime_io
+			.send(GraphRuntimeRequest::RenderLayerPreview { layer_node, max_size })
+			.expect("Failed to send layer preview request");
+	}
+
+	/// Asks the runtime for up to `count` rows of the inspected table starting at `start`. The result arrives as a [`NodeGraphUpdate::InspectRows`].
+	pub fn request_inspect_rows(&self, start: usize, count: usize) {
+		self.runtime_io.send(GraphRuntimeRequest::InspectRows { start, count }).expect("Failed to send inspect rows request");
+	}
+
 	/// Updates the network to monitor all inputs. Useful for the testing.
 	#[cfg(test)]
 	pub(crate) fn update_node_graph_instrumented(&mut self, document: &mut DocumentMessageHandler) -> Result<Instrumented, String> {
@@ -126,8 +299,43 @@ impl NodeGraphExecutor {
 		Ok(instrumented)
 	}
 
+	pub fn set_inspect_follows_selection(&mut self, inspect_follows_selection: bool) {
+		self.inspect_follows_selection = inspect_follows_selection;
+	}
+
+	pub fn set_cost_heatmap(&mut self, cost_heatmap: bool) {
+		self.cost_heatmap = cost_heatmap;
+	}
+
+	pub fn set_render_quality(&mut self, quality: RenderQualityHint) {
+		self.render_quality = quality;
+	}
+
+	/// Moves the canvas frame shown in the main viewport to follow the document's new pan and zoom until it's rendered again, so it stays aligned with the overlays.
+	pub fn sync_canvas_frame_transform(&mut self, document_to_viewport: DAffine2, responses: &mut VecDeque<Message>) {
+		self.document_to_viewport = Some(document_to_viewport);
+
+		let Some(canvas_frame) = &mut self.canvas_frame else { return };
+		let placement = canvas_frame_placement(canvas_frame.frame, canvas_frame.rendered_with, document_to_viewport);
+		if placement == canvas_frame.placement {
+			return;
+		}
+		canvas_frame.placement = placement;
+
+		let svg = canvas_frame_svg(canvas_frame.frame, placement, canvas_frame.debug_overlay.as_deref());
+		responses.add(FrontendMessage::UpdateDocumentArtwork { viewport_id: MAIN_VIEWPORT_ID, svg });
+		responses.add(OverlaysMessage::Draw);
+	}
+
 	/// Update the cached network if necessary.
 	fn update_node_graph(&mut self, document: &mut DocumentMessageHandler, inspect_node: Option<NodeId>, ignore_hash: bool) -> Result<(), String> {
+		let inspect_node = if self.inspect_follows_selection {
+			let selected_layer = document.network_interface.selected_nodes().selected_layers(document.metadata()).next();
+			selected_layer.map(|layer| layer.to_node()).or(inspect_node)
+		} else {
+			inspect_node
+		};
+
 		let network_hash = document.network_interface.document_network().current_hash();
 		// Refresh the graph when it changes or the inspect node changes
 		if network_hash != self.node_graph_hash || self.old_inspect_node != inspect_node || ignore_hash {
@@ -143,7 +351,20 @@ impl NodeGraphExecutor {
 	}
 
 	/// Adds an evaluate request for whatever current network is cached.
-	pub(crate) fn submit_current_node_graph_evaluation(&mut self, document: &mut DocumentMessageHandler, viewport_resolution: UVec2, time: TimingInformation) -> Result<(), String> {
+	pub(crate) fn submit_current_node_graph_evaluation(
+		&mut self,
+		document: &mut DocumentMessageHandler,
+		viewport_resolution: UVec2,
+		time: TimingInformation,
+		subpath_click_regions: bool,
+		default_renderer: ViewportRenderer,
+	) -> Result<(), String> {
+		// A viewport without any area, such as while the window is minimized, has nothing to show and no surface to render to
+		if viewport_resolution.x == 0 || viewport_resolution.y == 0 {
+			self.viewport_render_skipped = true;
+			return Ok(());
+		}
+
 		let render_config = RenderConfig {
 			viewport: Footprint {
 				transform: document.metadata().document_to_viewport,
@@ -151,23 +372,80 @@ impl NodeGraphExecutor {
 				..Default::default()
 			},
 			time,
-			#[cfg(any(feature = "resvg", feature = "vello"))]
-			export_format: graphene_core::application_io::ExportFormat::Canvas,
-			#[cfg(not(any(feature = "resvg", feature = "vello")))]
-			export_format: graphene_core::application_io::ExportFormat::Svg,
+			export_format: self.viewport_export_format(document.viewport_renderer.unwrap_or(default_renderer)),
 			view_mode: document.view_mode,
 			hide_artboards: false,
 			for_export: false,
+			subpath_click_regions,
+			expand_strokes: false,
+			cost_heatmap: self.cost_heatmap,
+			quality: self.render_quality,
 		};
 
-		// Execute the node graph
-		let execution_id = self.queue_execution(render_config);
+		// The runtime skips to the latest request, so the regions of any still outstanding renders are carried into this one
+		let dirty_region =
+			self.futures
+				.values()
+				.filter(|context| context.export_config.is_none())
+				.map(|context| context.dirty_region)
+				.fold(document.dirty_region.take(), |dirty_region, outstanding| match (dirty_region, outstanding) {
+					(Some(a), Some(b)) => Some(Quad::combine_bounds(a, b)),
+					(dirty_region, outstanding) => dirty_region.or(outstanding),
+				});
+		// The viewport has changed size since the last render if it was skipped, so all of it is redrawn
+		let viewport_render_skipped = std::mem::take(&mut self.viewport_render_skipped);
+		let dirty_region = dirty_region.filter(|_| !viewport_render_skipped);
 
-		self.futures.insert(execution_id, ExecutionContext { export_config: None });
+		// A newer request supersedes any final quality render which was waiting on the previous interactive one
+		self.final_render_due = None;
+		self.queue_viewport_execution(render_config, dirty_region);
 
 		Ok(())
 	}
 
+	/// Queues a render of the main and additional viewports.
+	fn queue_viewport_execution(&mut self, render_config: RenderConfig, dirty_region: Option<[DVec2; 2]>) {
+		let additional_viewports = self.additional_viewports.iter().map(|(&viewport_id, &footprint)| (viewport_id, footprint)).collect();
+		let execution_id = self.queue_execution(render_config, additional_viewports);
+
+		self.futures.insert(
+			execution_id,
+			ExecutionContext {
+				export_config: None,
+				queued_at: timestamp_ms(),
+				dirty_region,
+				interactive_render_config: (render_config.quality == RenderQualityHint::Interactive).then_some(render_config),
+			},
+		);
+	}
+
+	/// Repeats the last interactive render at final quality once no other render has been requested for a short while after its response.
+	fn queue_due_final_render(&mut self) {
+		let Some((render_config, responded_at)) = self.final_render_due else { return };
+		let viewport_render_pending = self.futures.values().any(|context| context.export_config.is_none());
+		if viewport_render_pending || timestamp_ms() - responded_at < FINAL_RENDER_DELAY_MS {
+			return;
+		}
+
+		self.final_render_due = None;
+		let render_config = RenderConfig {
+			quality: RenderQualityHint::Final,
+			..render_config
+		};
+		// The whole viewport is redrawn at the improved quality
+		self.queue_viewport_execution(render_config, None);
+	}
+
+	/// The output format the viewport is rendered in for the chosen renderer, which is SVG if this build has no canvas renderer or the canvas has previously failed.
+	fn viewport_export_format(&self, renderer: ViewportRenderer) -> ExportFormat {
+		let canvas_supported = cfg!(any(feature = "resvg", feature = "vello"));
+
+		match renderer {
+			ViewportRenderer::Canvas if canvas_supported && !self.canvas_unavailable => ExportFormat::Canvas,
+			_ => ExportFormat::Svg,
+		}
+	}
+
 	/// Evaluates a node graph, computing the entire graph
 	pub fn submit_node_graph_evaluation(
 		&mut self,
@@ -176,24 +454,65 @@ impl NodeGraphExecutor {
 		time: TimingInformation,
 		inspect_node: Option<NodeId>,
 		ignore_hash: bool,
+		subpath_click_regions: bool,
+		default_renderer: ViewportRenderer,
 	) -> Result<(), String> {
 		self.update_node_graph(document, inspect_node, ignore_hash)?;
-		self.submit_current_node_graph_evaluation(document, viewport_resolution, time)?;
+		self.submit_current_node_graph_evaluation(document, viewport_resolution, time, subpath_click_regions, default_renderer)?;
 
 		Ok(())
 	}
 
 	/// Evaluates a node graph for export
-	pub fn submit_document_export(&mut self, document: &mut DocumentMessageHandler, mut export_config: ExportConfig) -> Result<(), String> {
-		let network = document.network_interface.document_network().clone();
-
-		// Calculate the bounding box of the region to be exported
-		let bounds = match export_config.bounds {
+	/// Calculates the bounding box of the region to be exported, in document space.
+	fn export_bounds(document: &DocumentMessageHandler, export_config: &ExportConfig) -> Result<[DVec2; 2], String> {
+		match export_config.bounds {
 			ExportBounds::AllArtwork => document.network_interface.document_bounds_document_space(!export_config.transparent_background),
 			ExportBounds::Selection => document.network_interface.selected_bounds_document_space(!export_config.transparent_background, &[]),
 			ExportBounds::Artboard(id) => document.metadata().bounding_box_document(id),
+			ExportBounds::Custom([corner1, corner2]) => Some([corner1.min(corner2), corner1.max(corner2)]),
+		}
+		.ok_or_else(|| "No bounding box".to_string())
+	}
+
+	/// Describes what exporting the document with this configuration would produce, without rendering anything.
+	pub fn describe_export(&self, document: &DocumentMessageHandler, export_config: &ExportConfig) -> Result<ExportDescription, String> {
+		let bounds = Self::export_bounds(document, export_config)?;
+		let overlaps_export = |layer| {
+			document
+				.metadata()
+				.bounding_box_document(layer)
+				.is_some_and(|[min, max]: [DVec2; 2]| min.cmple(bounds[1]).all() && max.cmpge(bounds[0]).all())
+		};
+
+		let mut artboards = Vec::new();
+		let mut layer_count = 0;
+		for layer in document.metadata().all_layers().filter(|&layer| overlaps_export(layer)) {
+			if document.network_interface.is_artboard(&layer.to_node(), &[]) {
+				let name = document
+					.network_interface
+					.node_metadata(&layer.to_node(), &[])
+					.map(|node| node.persistent_metadata.display_name.clone())
+					.filter(|name| !name.is_empty())
+					.unwrap_or_else(|| "Artboard".to_string());
+				artboards.push(name);
+			} else if !layer.has_children(document.metadata()) {
+				layer_count += 1;
+			}
 		}
-		.ok_or_else(|| "No bounding box".to_string())?;
+
+		Ok(ExportDescription {
+			bounds,
+			size: (bounds[1] - bounds[0]) * export_config.scale_factor,
+			artboards,
+			layer_count,
+		})
+	}
+
+	pub fn submit_document_export(&mut self, document: &mut DocumentMessageHandler, mut export_config: ExportConfig, responses: &mut VecDeque<Message>) -> Result<(), String> {
+		let network = document.network_interface.document_network().clone();
+
+		let bounds = Self::export_bounds(document, &export_config)?;
 		let size = bounds[1] - bounds[0];
 		let transform = DAffine2::from_translation(bounds[0]).inverse();
 
@@ -208,6 +527,10 @@ impl NodeGraphExecutor {
 			view_mode: document.view_mode,
 			hide_artboards: export_config.transparent_background,
 			for_export: true,
+			subpath_click_regions: false,
+			expand_strokes: export_config.expand_strokes,
+			cost_heatmap: false,
+			quality: RenderQualityHint::Final,
 		};
 		export_config.size = size;
 
@@ -215,13 +538,38 @@ impl NodeGraphExecutor {
 		self.runtime_io
 			.send(GraphRuntimeRequest::GraphUpdate(GraphUpdate { network, inspect_node: None }))
 			.map_err(|e| e.to_string())?;
-		let execution_id = self.queue_execution(render_config);
-		let execution_context = ExecutionContext { export_config: Some(export_config) };
+		let execution_id = self.queue_execution(render_config, Vec::new());
+		let execution_context = ExecutionContext {
+			export_config: Some(export_config),
+			queued_at: timestamp_ms(),
+			dirty_region: None,
+			interactive_render_config: None,
+		};
 		self.futures.insert(execution_id, execution_context);
+		self.report_export_progress(ExportStage::Compile, responses);
 
 		Ok(())
 	}
 
+	/// Abandons the pending exports so none of them are delivered, telling the runtime to skip any it hasn't evaluated yet.
+	pub fn cancel_exports(&mut self, responses: &mut VecDeque<Message>) {
+		let exports = self.futures.iter().filter(|(_, context)| context.export_config.is_some()).map(|(&id, _)| id).collect::<Vec<_>>();
+		for execution_id in exports {
+			let Some(context) = self.futures.remove(&execution_id) else { continue };
+			self.cancelled_exports.insert(execution_id, context.queued_at);
+			if let Err(error) = self.runtime_io.send(GraphRuntimeRequest::CancelExecution { execution_id }) {
+				warn!("Failed to cancel export {execution_id}: {error}");
+			}
+		}
+
+		self.report_export_progress(ExportStage::Cancelled, responses);
+		self.report_busy_state(responses);
+	}
+
+	fn report_export_progress(&self, stage: ExportStage, responses: &mut VecDeque<Message>) {
+		responses.add(ExportDialogMessage::Progress { stage, fraction: stage.fraction() });
+	}
+
 	fn export(&self, node_graph_output: TaggedValue, export_config: ExportConfig, responses: &mut VecDeque<Message>) -> Result<(), String> {
 		let TaggedValue::RenderOutput(RenderOutput {
 			data: graphene_std::wasm_application_io::RenderOutputType::Svg(svg),
@@ -255,7 +603,24 @@ impl NodeGraphExecutor {
 		Ok(())
 	}
 
+	/// Tells the frontend how many executions are awaiting a response whenever rendering starts or stops, an export is queued or finishes, or the number of pending renders changes considerably.
+	fn report_busy_state(&mut self, responses: &mut VecDeque<Message>) {
+		let pending_exports = self.futures.values().filter(|context| context.export_config.is_some()).count();
+		let pending = self.futures.len() - pending_exports;
+
+		let (reported, reported_exports) = self.reported_busy_state;
+		let started_or_stopped = (pending == 0) != (reported == 0);
+		let large_change = pending.abs_diff(reported) >= GRAPH_BUSY_STATE_REPORT_STEP;
+		if started_or_stopped || large_change || pending_exports != reported_exports {
+			self.reported_busy_state = (pending, pending_exports);
+			responses.add(FrontendMessage::UpdateGraphBusyState { pending, pending_exports });
+		}
+	}
+
 	pub fn poll_node_graph_evaluation(&mut self, document: &mut DocumentMessageHandler, responses: &mut VecDeque<Message>) -> Result<(), String> {
+		// Executions submitted since the last poll
+		self.report_busy_state(responses);
+
 		let results = self.runtime_io.receive().collect::<Vec<_>>();
 		for response in results {
 			match response {
@@ -267,16 +632,37 @@ impl NodeGraphExecutor {
 						transform,
 						vector_modify,
 						inspect_result,
+						statistics,
+						additional_outputs,
+						cost_heatmap,
 					} = execution_response;
 
+					// The export was cancelled after the runtime had already started on it
+					if let Some(queued_at) = self.cancelled_exports.remove(&execution_id) {
+						self.cancelled_exports.retain(|_, cancelled_queued_at| *cancelled_queued_at > queued_at);
+						continue;
+					}
+
 					responses.add(OverlaysMessage::Draw);
 
+					let execution_context = self.futures.remove(&execution_id).ok_or_else(|| "Invalid generation ID".to_string())?;
+					// The runtime answers executions in the order they were queued, so those cancelled before this one were skipped and will never be answered
+					self.cancelled_exports.retain(|_, queued_at| *queued_at > execution_context.queued_at);
+					// Viewport renders requested before this one were skipped by the runtime in favor of it, so they will never get a response
+					if execution_context.export_config.is_none() {
+						self.futures.retain(|_, context| context.export_config.is_some() || context.queued_at > execution_context.queued_at);
+					}
+
 					let node_graph_output = match result {
 						Ok(output) => output,
 						Err(e) => {
 							// Clear the click targets while the graph is in an un-renderable state
 							document.network_interface.update_click_targets(HashMap::new());
 							document.network_interface.update_vector_modify(HashMap::new());
+							if execution_context.export_config.is_some() {
+								self.report_export_progress(ExportStage::Failed, responses);
+							}
+							self.report_busy_state(responses);
 							return Err(format!("Node graph evaluation failed:\n{e}"));
 						}
 					};
@@ -284,12 +670,42 @@ impl NodeGraphExecutor {
 					responses.extend(existing_responses.into_iter().map(Into::into));
 					document.network_interface.update_vector_modify(vector_modify);
 
-					let execution_context = self.futures.remove(&execution_id).ok_or_else(|| "Invalid generation ID".to_string())?;
 					if let Some(export_config) = execution_context.export_config {
 						// Special handling for exporting the artwork
-						self.export(node_graph_output, export_config, responses)?
+						self.report_export_progress(ExportStage::Encode, responses);
+						if let Err(error) = self.export(node_graph_output, export_config, responses) {
+							self.report_export_progress(ExportStage::Failed, responses);
+							return Err(error);
+						}
+						self.report_export_progress(ExportStage::Finished, responses);
 					} else {
-						self.process_node_graph_output(node_graph_output, transform, responses)?
+						self.process_node_graph_output(node_graph_output, transform, MAIN_VIEWPORT_ID, cost_heatmap.as_deref(), responses)?;
+						if let Some(render_config) = execution_context.interactive_render_config {
+							self.final_render_due = Some((render_config, timestamp_ms()));
+						}
+						let dirty_region = execution_context.dirty_region.map(|bounds| (transform * Quad::from_box(bounds)).bounding_box());
+						responses.add(FrontendMessage::UpdateDocumentArtworkDirtyRegion {
+							dirty_region: dirty_region.map(|[min, max]| [min.into(), max.into()]),
+						});
+
+						for ViewportOutput { viewport_id, result, transform } in additional_outputs {
+							// A viewport which was closed while this execution was underway has nowhere to show its render
+							if !self.additional_viewports.contains_key(&viewport_id) {
+								continue;
+							}
+							match result {
+								Ok(output) => self.process_node_graph_output(output, transform, viewport_id, None, responses)?,
+								Err(e) => warn!("Rendering viewport {viewport_id} failed: {e}"),
+							}
+						}
+
+						// The total includes the time the request spent waiting in the queue before the runtime got to it
+						responses.add(FrontendMessage::UpdateRenderStatistics {
+							total_time: timestamp_ms() - execution_context.queued_at,
+							execution_time: statistics.execution_time,
+							node_count: statistics.evaluated_node_count,
+							mostly_cached: statistics.mostly_cached(),
+						});
 					}
 
 					// Update the spreadsheet on the frontend using the value of the inspect result.
@@ -299,11 +715,37 @@ impl NodeGraphExecutor {
 						}
 					}
 				}
+				NodeGraphUpdate::DocumentStatisticsResponse(statistics) => {
+					responses.add(DialogMessage::DisplayDocumentStatistics { statistics });
+				}
+				NodeGraphUpdate::LayerPreview { layer_node, preview } => {
+					responses.add(FrontendMessage::UpdateLayerPreview { id: layer_node, preview });
+				}
+				NodeGraphUpdate::ProbeResponse { position, color } => {
+					responses.add(DocumentMessage::UpdateCanvasProbeColor { position, color });
+				}
+				NodeGraphUpdate::InspectRows { inspect_node, start, rows } => {
+					responses.add(SpreadsheetMessage::UpdateRows { inspect_node, start, rows });
+				}
 				// NodeGraphUpdate::NodeGraphUpdateMessage(NodeGraphUpdateMessage::ImaginateStatusUpdate) => {
 				// 	responses.add(DocumentMessage::PropertiesPanel(PropertiesPanelMessage::Refresh));
 				// }
+				NodeGraphUpdate::NodeGraphUpdateMessage(NodeGraphUpdateMessage::ExternalResourcesChanged { urls }) => {
+					// The runtime already invalidated the nodes depending on the changed resources, so rendering again only evaluates those
+					responses.add(NodeGraphMessage::RunDocumentGraph);
+					responses.add(FrontendMessage::DisplayLinkedAssetsUpdated { urls });
+				}
+				// The runtime keeps the loaded resources to itself
+				NodeGraphUpdate::NodeGraphUpdateMessage(NodeGraphUpdateMessage::ExternalResourceLoaded { .. }) => {}
 				NodeGraphUpdate::CompilationResponse(execution_response) => {
-					let CompilationResponse { node_graph_errors, result } = execution_response;
+					let CompilationResponse {
+						node_graph_errors,
+						result,
+						application_io_unavailable,
+					} = execution_response;
+					responses.add(FrontendMessage::UpdateApplicationIoStatus {
+						unavailable_reason: application_io_unavailable,
+					});
 					let type_delta = match result {
 						Err(e) => {
 							// Clear the click targets while the graph is in an un-renderable state
@@ -329,13 +771,20 @@ impl NodeGraphExecutor {
 						node_graph_errors,
 					});
 					responses.add(NodeGraphMessage::SendGraph);
+
+					if self.futures.values().any(|context| context.export_config.is_some()) {
+						self.report_export_progress(ExportStage::Evaluate, responses);
+					}
 				}
 			}
 		}
+		self.queue_due_final_render();
+		self.report_busy_state(responses);
+
 		Ok(())
 	}
 
-	fn debug_render(render_object: impl GraphicElementRendered, transform: DAffine2, responses: &mut VecDeque<Message>) {
+	fn debug_render(render_object: impl GraphicElementRendered, transform: DAffine2, viewport_id: ViewportId, responses: &mut VecDeque<Message>) {
 		// Setup rendering
 		let mut render = SvgRender::new();
 		let render_params = RenderParams::new(ViewMode::Normal, None, false, false, false);
@@ -348,26 +797,106 @@ impl NodeGraphExecutor {
 		let svg = render.svg.to_svg_string();
 
 		// Send to frontend
-		responses.add(FrontendMessage::UpdateDocumentArtwork { svg });
+		responses.add(FrontendMessage::UpdateDocumentArtwork { viewport_id, svg });
 	}
 
-	fn process_node_graph_output(&mut self, node_graph_output: TaggedValue, transform: DAffine2, responses: &mut VecDeque<Message>) -> Result<(), String> {
+	/// Renders the `Debug` representation of a value as wrapped lines of text, cut off after [`DEBUG_RENDER_TEXT_MAX_LINES`] lines so huge values stay cheap to format and display.
+	fn debug_render_text(value: &impl std::fmt::Debug, transform: DAffine2, viewport_id: ViewportId, responses: &mut VecDeque<Message>) {
+		let limit = DEBUG_RENDER_TEXT_LINE_LENGTH * DEBUG_RENDER_TEXT_MAX_LINES;
+		let mut text = TruncatedText::new(limit);
+		let _ = write!(text, "{value:#?}");
+
+		let mut lines = text
+			.text
+			.lines()
+			.flat_map(|line| {
+				let characters = line.chars().collect::<Vec<_>>();
+				if characters.is_empty() {
+					return vec![String::new()];
+				}
+				characters.chunks(DEBUG_RENDER_TEXT_LINE_LENGTH).map(|chunk| chunk.iter().collect()).collect()
+			})
+			.take(DEBUG_RENDER_TEXT_MAX_LINES + 1)
+			.collect::<Vec<String>>();
+		if text.truncated || lines.len() > DEBUG_RENDER_TEXT_MAX_LINES {
+			lines.truncate(DEBUG_RENDER_TEXT_MAX_LINES);
+			lines.push("…".to_string());
+		}
+
+		let mut render = SvgRender::new();
+		render.parent_tag(
+			"text",
+			|attributes| {
+				attributes.push("fill", "white");
+				attributes.push("font-family", "monospace");
+				attributes.push("font-size", "14");
+				attributes.push("xml:space", "preserve");
+			},
+			|render| {
+				for line in &lines {
+					render.parent_tag(
+						"tspan",
+						|attributes| {
+							attributes.push("x", "0");
+							attributes.push("dy", "1.2em");
+						},
+						|render| render.leaf_node(escape_xml(line)),
+					);
+				}
+			},
+		);
+		render.wrap_with_transform(transform, None);
+
+		responses.add(FrontendMessage::UpdateDocumentArtwork {
+			viewport_id,
+			svg: render.svg.to_svg_string(),
+		});
+	}
+
+	/// Displays the output of an execution in the given viewport, with the `debug_overlay` SVG drawn over the artwork in viewport space.
+	fn process_node_graph_output(
+		&mut self,
+		node_graph_output: TaggedValue,
+		transform: DAffine2,
+		viewport_id: ViewportId,
+		debug_overlay: Option<&str>,
+		responses: &mut VecDeque<Message>,
+	) -> Result<(), String> {
 		let mut render_output_metadata = RenderMetadata::default();
+		if viewport_id == MAIN_VIEWPORT_ID {
+			self.canvas_frame = None;
+		}
 		match node_graph_output {
 			TaggedValue::RenderOutput(render_output) => {
 				match render_output.data {
 					graphene_std::wasm_application_io::RenderOutputType::Svg(svg) => {
 						// Send to frontend
-						responses.add(FrontendMessage::UpdateDocumentArtwork { svg });
+						let svg = with_debug_overlay(svg, debug_overlay);
+						responses.add(FrontendMessage::UpdateDocumentArtwork { viewport_id, svg });
 					}
 					graphene_std::wasm_application_io::RenderOutputType::CanvasFrame(frame) => {
-						let matrix = format_transform_matrix(frame.transform);
-						let transform = if matrix.is_empty() { String::new() } else { format!(" transform=\"{}\"", matrix) };
-						let svg = format!(
-							r#"<svg><foreignObject width="{}" height="{}"{transform}><div data-canvas-placeholder="canvas{}"></div></foreignObject></svg>"#,
-							frame.resolution.x, frame.resolution.y, frame.surface_id.0
-						);
-						responses.add(FrontendMessage::UpdateDocumentArtwork { svg });
+						let mut placement = frame.transform;
+						if viewport_id == MAIN_VIEWPORT_ID {
+							// The document may have been panned or zoomed since the frame was requested, so place it where the overlays expect it
+							placement = canvas_frame_placement(frame, transform, self.document_to_viewport.unwrap_or(transform));
+							self.canvas_frame = Some(DisplayedCanvasFrame {
+								frame,
+								rendered_with: transform,
+								placement,
+								debug_overlay: debug_overlay.map(String::from),
+							});
+						}
+
+						let svg = canvas_frame_svg(frame, placement, debug_overlay);
+						responses.add(FrontendMessage::UpdateDocumentArtwork { viewport_id, svg });
+					}
+					graphene_std::wasm_application_io::RenderOutputType::CanvasUnavailable(reason) => {
+						// Render again as SVG instead of leaving the viewport blank
+						warn!("Falling back to the SVG renderer because the canvas is unavailable: {reason}");
+						self.canvas_unavailable = true;
+						responses.add(NodeGraphMessage::RunDocumentGraph);
+
+						return Ok(());
 					}
 					_ => {
 						return Err(format!("Invalid node graph output type: {:#?}", render_output.data));
@@ -376,19 +905,33 @@ impl NodeGraphExecutor {
 
 				render_output_metadata = render_output.metadata;
 			}
-			TaggedValue::Bool(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::String(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::F64(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::DVec2(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::OptionalColor(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::VectorData(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::GraphicGroup(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::ImageFrame(render_object) => Self::debug_render(render_object, transform, responses),
-			TaggedValue::Palette(render_object) => Self::debug_render(render_object, transform, responses),
-			_ => {
-				return Err(format!("Invalid node graph output type: {node_graph_output:#?}"));
-			}
+			TaggedValue::Bool(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::String(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::F64(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::DVec2(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::OptionalColor(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::VectorData(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::GraphicGroup(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::ImageFrame(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::Palette(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::U32(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::U64(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::Color(render_object) => Self::debug_render(Some(render_object), transform, viewport_id, responses),
+			TaggedValue::VecDVec2(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::Gradient(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::GradientStops(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::GraphicElement(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::Artboard(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			TaggedValue::ArtboardGroup(render_object) => Self::debug_render(render_object, transform, viewport_id, responses),
+			// Anything without a visualization of its own is shown as its `Debug` representation
+			_ => Self::debug_render_text(&node_graph_output, transform, viewport_id, responses),
 		};
+
+		// The other viewports only show the artwork, since everything the editor measures from a render refers to the main viewport
+		if viewport_id != MAIN_VIEWPORT_ID {
+			return Ok(());
+		}
+
 		responses.add(Message::EndBuffer(render_output_metadata));
 		responses.add(DocumentMessage::RenderScrollbars);
 		responses.add(DocumentMessage::RenderRulers);
@@ -397,6 +940,75 @@ impl NodeGraphExecutor {
 	}
 }
 
+/// Where to place a canvas frame in the viewport so the artwork baked into it with `rendered_with` lines up with overlays drawn with `document_to_viewport`.
+fn canvas_frame_placement(frame: SurfaceFrame, rendered_with: DAffine2, document_to_viewport: DAffine2) -> DAffine2 {
+	if rendered_with == document_to_viewport || rendered_with.matrix2.determinant() == 0. {
+		return frame.transform;
+	}
+	document_to_viewport * rendered_with.inverse() * frame.transform
+}
+
+/// The SVG holding the placeholder which the frontend swaps for the canvas the frame was rendered into.
+fn canvas_frame_svg(frame: SurfaceFrame, placement: DAffine2, debug_overlay: Option<&str>) -> String {
+	let matrix = format_transform_matrix(placement);
+	let transform = if matrix.is_empty() { String::new() } else { format!(" transform=\"{}\"", matrix) };
+	let svg = format!(
+		r#"<svg><foreignObject width="{}" height="{}"{transform}><div data-canvas-placeholder="canvas{}"></div></foreignObject></svg>"#,
+		frame.resolution.x, frame.resolution.y, frame.surface_id.0
+	);
+	with_debug_overlay(svg, debug_overlay)
+}
+
+/// Inserts the overlay as the last element of the SVG, so it's drawn above the artwork.
+fn with_debug_overlay(mut svg: String, debug_overlay: Option<&str>) -> String {
+	if let Some(overlay) = debug_overlay {
+		let end = svg.rfind("</svg>").unwrap_or(svg.len());
+		svg.insert_str(end, overlay);
+	}
+	svg
+}
+
+/// A [`std::fmt::Write`] target that keeps only the first `limit` bytes written to it, so formatting a huge value stops early instead of building the whole string.
+struct TruncatedText {
+	text: String,
+	limit: usize,
+	truncated: bool,
+}
+
+impl TruncatedText {
+	fn new(limit: usize) -> Self {
+		Self {
+			text: String::new(),
+			limit,
+			truncated: false,
+		}
+	}
+}
+
+impl Write for TruncatedText {
+	fn write_str(&mut self, s: &str) -> std::fmt::Result {
+		let remaining = self.limit - self.text.len();
+		if s.len() <= remaining {
+			self.text.push_str(s);
+			return Ok(());
+		}
+
+		let mut end = remaining;
+		while !s.is_char_boundary(end) {
+			end -= 1;
+		}
+		self.text.push_str(&s[..end]);
+		self.truncated = true;
+
+		// Abort the rest of the formatting
+		Err(std::fmt::Error)
+	}
+}
+
+fn escape_xml(text: &str) -> String {
+	text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
 // Re-export for usage by tests in other modules
 #[cfg(test)]
 pub use test::Instrumented;
@@ -409,11 +1021,1094 @@ mod test {
 	use crate::messages::portfolio::document::utility_types::network_interface::NodeNetworkInterface;
 	use crate::test_utils::test_prelude::{self, NodeGraphLayer};
 	use graph_craft::document::NodeNetwork;
+	use graph_craft::proto::GraphErrorType;
 	use graphene_std::Context;
 	use graphene_std::NodeInputDecleration;
 	use graphene_std::memo::IORecord;
+	use graphene_std::wasm_application_io::RenderOutputType;
 	use test_prelude::LayerNodeIdentifier;
 
+	fn render_output(data: RenderOutputType) -> TaggedValue {
+		TaggedValue::RenderOutput(RenderOutput {
+			data,
+			metadata: RenderMetadata::default(),
+		})
+	}
+
+	#[test]
+	fn canvas_unavailable_output_falls_back_to_svg() {
+		let (_runtime, mut executor) = NodeGraphExecutor::new_with_local_runtime();
+		let mut responses = VecDeque::new();
+
+		let output = render_output(RenderOutputType::CanvasUnavailable("The canvas surface failed to initialize".to_string()));
+		executor
+			.process_node_graph_output(output, DAffine2::IDENTITY, MAIN_VIEWPORT_ID, None, &mut responses)
+			.expect("A canvas failure should be handled by falling back, not reported as an error");
+
+		assert_eq!(executor.viewport_export_format(ViewportRenderer::Canvas), ExportFormat::Svg);
+		assert!(responses.contains(&NodeGraphMessage::RunDocumentGraph.into()), "The document should be rendered again as SVG");
+		assert!(
+			!responses.iter().any(|message| matches!(message, Message::Frontend(FrontendMessage::UpdateDocumentArtwork { .. }))),
+			"Nothing should be drawn from the failed render"
+		);
+	}
+
+	#[test]
+	fn svg_output_keeps_chosen_renderer() {
+		let (_runtime, mut executor) = NodeGraphExecutor::new_with_local_runtime();
+		let mut responses = VecDeque::new();
+
+		let output = render_output(RenderOutputType::Svg("<svg></svg>".to_string()));
+		executor
+			.process_node_graph_output(output, DAffine2::IDENTITY, MAIN_VIEWPORT_ID, None, &mut responses)
+			.expect("SVG output should be displayed");
+
+		assert!(!executor.canvas_unavailable);
+		assert_eq!(executor.viewport_export_format(ViewportRenderer::Svg), ExportFormat::Svg);
+		assert!(!responses.contains(&NodeGraphMessage::RunDocumentGraph.into()));
+		assert!(responses.iter().any(|message| matches!(message, Message::Frontend(FrontendMessage::UpdateDocumentArtwork { .. }))));
+	}
+
+	#[test]
+	fn canvas_frame_follows_overlays_until_rendered_again() {
+		let (_runtime, mut executor) = NodeGraphExecutor::new_with_local_runtime();
+		let mut responses = VecDeque::new();
+
+		// Vello bakes the document's transform into the frame's pixels
+		let rendered_with = DAffine2::from_scale_angle_translation(DVec2::splat(2.), 0., DVec2::new(30., 40.));
+		let frame = SurfaceFrame {
+			surface_id: graphene_core::application_io::SurfaceId(1),
+			resolution: UVec2::new(800, 600),
+			transform: DAffine2::IDENTITY,
+		};
+		executor.sync_canvas_frame_transform(rendered_with, &mut responses);
+		let output = render_output(RenderOutputType::CanvasFrame(frame));
+		executor
+			.process_node_graph_output(output, rendered_with, MAIN_VIEWPORT_ID, None, &mut responses)
+			.expect("Canvas output should be displayed");
+
+		// Pan and zoom before the next render arrives
+		let document_to_viewport = DAffine2::from_scale_angle_translation(DVec2::splat(3.), 0., DVec2::new(-15., 25.));
+		responses.clear();
+		executor.sync_canvas_frame_transform(document_to_viewport, &mut responses);
+
+		// Overlays, like an SVG render of the same document and viewport, put the anchor at its document position mapped by the current transform
+		let anchor = DVec2::new(10., 20.);
+		let overlay_position = document_to_viewport.transform_point2(anchor);
+		let placement = executor.canvas_frame.as_ref().expect("The canvas frame should still be shown").placement;
+		let canvas_position = placement.transform_point2(rendered_with.transform_point2(anchor));
+		assert!(canvas_position.abs_diff_eq(overlay_position, 1e-9), "{canvas_position} should be {overlay_position}");
+
+		let svg = responses.iter().find_map(|message| match message {
+			Message::Frontend(FrontendMessage::UpdateDocumentArtwork { svg, .. }) => Some(svg),
+			_ => None,
+		});
+		assert!(svg.expect("The placeholder should be moved").contains(&format_transform_matrix(placement)));
+		assert!(responses.contains(&OverlaysMessage::Draw.into()), "Overlays should be redrawn along with the frame");
+
+		// Nothing changes if the transform stays the same
+		responses.clear();
+		executor.sync_canvas_frame_transform(document_to_viewport, &mut responses);
+		assert!(responses.is_empty());
+
+		// An SVG render replaces the frame, which then no longer needs moving
+		let output = render_output(RenderOutputType::Svg("<svg></svg>".to_string()));
+		executor
+			.process_node_graph_output(output, document_to_viewport, MAIN_VIEWPORT_ID, None, &mut responses)
+			.expect("SVG output should be displayed");
+		assert!(executor.canvas_frame.is_none());
+	}
+
+	#[tokio::test]
+	async fn additional_viewport_is_rendered_by_the_same_execution() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let executor = &mut portfolio.executor;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+
+		let preview_id = 1;
+		let preview_footprint = Footprint {
+			transform: DAffine2::from_scale_angle_translation(DVec2::splat(2.), 0., DVec2::new(30., 40.)),
+			resolution: UVec2::new(400, 300),
+			..Default::default()
+		};
+		executor.set_additional_viewport(preview_id, Some(preview_footprint));
+		executor
+			.submit_current_node_graph_evaluation(document, UVec2::new(800, 600), Default::default(), false, ViewportRenderer::Svg)
+			.expect("The evaluation should be submitted");
+		editor.runtime.run().await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let executions = portfolio
+			.executor
+			.runtime_io
+			.receive()
+			.filter_map(|update| match update {
+				NodeGraphUpdate::ExecutionResponse(response) => Some(response),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(executions.len(), 1, "Both viewports should be rendered by a single execution");
+
+		let execution = &executions[0];
+		assert!(execution.result.is_ok(), "The main viewport failed to render");
+		assert_ne!(execution.transform, preview_footprint.transform);
+		assert_eq!(execution.additional_outputs.len(), 1);
+
+		let preview = &execution.additional_outputs[0];
+		assert_eq!(preview.viewport_id, preview_id);
+		assert_eq!(preview.transform, preview_footprint.transform);
+		assert!(preview.result.is_ok(), "The additional viewport failed to render");
+	}
+
+	#[tokio::test]
+	async fn editing_one_layer_only_renders_its_thumbnail() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		for _ in 1..100 {
+			editor.handle_message(DocumentMessage::DuplicateSelectedLayers).await;
+		}
+		let layer = editor.get_selected_layer().await.unwrap();
+		assert_eq!(editor.active_document().metadata().all_layers().count(), 100);
+
+		let fill = graphene_core::vector::style::Fill::Solid(Color::RED);
+		editor.editor.handle_message(GraphOperationMessage::FillSet { layer, fill });
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let executor = &mut portfolio.executor;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		executor.update_node_graph_instrumented(document).expect("The graph should be updated");
+		executor
+			.submit_current_node_graph_evaluation(document, UVec2::ONE, Default::default(), false, ViewportRenderer::Svg)
+			.expect("The evaluation should be submitted");
+		editor.runtime.run().await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let statistics = portfolio
+			.executor
+			.runtime_io
+			.receive()
+			.filter_map(|update| match update {
+				NodeGraphUpdate::ExecutionResponse(response) => Some(response.statistics),
+				_ => None,
+			})
+			.last()
+			.expect("The graph should be executed");
+		assert_eq!(statistics.thumbnail_render_count, 1, "Only the edited layer's thumbnail should be rendered");
+	}
+
+	/// Updates the active document's graph and evaluates it, returning the statistics of the execution.
+	async fn evaluate_for_statistics(editor: &mut test_prelude::EditorTestUtils) -> ExecutionStatistics {
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let executor = &mut portfolio.executor;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		executor.update_node_graph_instrumented(document).expect("The graph should be updated");
+		executor
+			.submit_current_node_graph_evaluation(document, UVec2::ONE, Default::default(), false, ViewportRenderer::Svg)
+			.expect("The evaluation should be submitted");
+		editor.runtime.run().await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		portfolio
+			.executor
+			.runtime_io
+			.receive()
+			.filter_map(|update| match update {
+				NodeGraphUpdate::ExecutionResponse(response) => Some(response.statistics),
+				_ => None,
+			})
+			.last()
+			.expect("The graph should be executed")
+	}
+
+	#[tokio::test]
+	async fn evaluating_an_unchanged_graph_again_is_mostly_cached() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+
+		let fill = graphene_core::vector::style::Fill::Solid(Color::RED);
+		editor.editor.handle_message(GraphOperationMessage::FillSet { layer, fill });
+		let edited = evaluate_for_statistics(&mut editor).await;
+		assert!(edited.cache_miss_count > 0, "The edited layer's nodes should have to be evaluated again");
+
+		let unchanged = evaluate_for_statistics(&mut editor).await;
+		assert!(unchanged.cache_miss_count < edited.cache_miss_count, "The memoizing nodes should have their output cached");
+		assert!(unchanged.mostly_cached());
+		assert!(
+			unchanged.evaluated_node_count < edited.evaluated_node_count,
+			"The cached nodes' inputs shouldn't be evaluated again, but {} nodes were evaluated compared to {}",
+			unchanged.evaluated_node_count,
+			edited.evaluated_node_count
+		);
+	}
+
+	#[tokio::test]
+	async fn inspect_node_follows_selected_layer() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let first = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(200., 0., 300., 100.).await;
+		let second = editor.get_selected_layer().await.unwrap();
+
+		let (runtime, mut executor) = NodeGraphExecutor::new_with_local_runtime();
+		executor.set_inspect_follows_selection(true);
+		let graph_updates = || {
+			runtime
+				.receiver
+				.try_iter()
+				.filter_map(|request| match request {
+					GraphRuntimeRequest::GraphUpdate(update) => Some(update.inspect_node),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+		};
+
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![first.to_node()] }).await;
+		executor.update_node_graph(editor.active_document_mut(), None, false).unwrap();
+		assert_eq!(graph_updates(), vec![Some(first.to_node())]);
+
+		// Nothing changed, so there is no need to recompile
+		executor.update_node_graph(editor.active_document_mut(), None, false).unwrap();
+		assert_eq!(graph_updates(), vec![]);
+
+		editor.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![second.to_node()] }).await;
+		executor.update_node_graph(editor.active_document_mut(), None, false).unwrap();
+		assert_eq!(graph_updates(), vec![Some(second.to_node())]);
+	}
+
+	async fn request_layer_preview(editor: &mut test_prelude::EditorTestUtils, layer_node: NodeId, max_size: f64) -> Option<String> {
+		editor.editor.handle_message(PortfolioMessage::RequestLayerPreview { layer_node, max_size });
+		editor.runtime.run().await;
+
+		let mut messages = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut messages).expect("The preview request should be answered");
+		messages
+			.into_iter()
+			.flat_map(|message| editor.editor.handle_message(message))
+			.find_map(|message| match message {
+				FrontendMessage::UpdateLayerPreview { id, preview } if id == layer_node => Some(preview),
+				_ => None,
+			})
+			.expect("The Layers panel should be sent the preview")
+	}
+
+	#[tokio::test]
+	async fn layer_preview_is_rendered_from_the_layers_monitor_data() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+
+		let preview = request_layer_preview(&mut editor, layer.to_node(), 64.).await.expect("The rectangle layer should have a preview");
+		assert!(preview.starts_with("<svg"), "The preview should be a standalone SVG: {preview}");
+		assert!(preview.contains("<path"), "The preview should contain the rectangle: {preview}");
+
+		// Asking again without any change gives the same preview
+		assert_eq!(request_layer_preview(&mut editor, layer.to_node(), 64.).await, Some(preview));
+	}
+
+	#[tokio::test]
+	async fn layer_preview_is_not_available_without_monitor_data() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+
+		// No monitor node records the output of a node which isn't a layer in the document
+		assert_eq!(request_layer_preview(&mut editor, NodeId(u64::MAX), 64.).await, None);
+	}
+
+	#[tokio::test]
+	async fn probing_the_canvas_keeps_the_viewports_monitor_data() {
+		use graphene_core::ExtractFootprint;
+
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let layer = editor.get_selected_layer().await.unwrap();
+
+		let recorded_footprint = |runtime: &NodeRuntime| {
+			let monitor_node_path = runtime.layer_monitor_node_path(layer.to_node()).expect("The layer should have a monitor node");
+			let introspected_data = runtime.executor.introspect(monitor_node_path).expect("The monitor node should have recorded the layer");
+			let io = introspected_data.downcast_ref::<IORecord<Context, graphene_core::GraphicElement>>().unwrap();
+			io.input.try_footprint().copied()
+		};
+		let viewport_footprint = recorded_footprint(&editor.runtime);
+		assert!(viewport_footprint.is_some());
+
+		// Probe far away from the viewport, with a footprint of its own
+		let executor = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler.executor;
+		executor.probe_color(layer.to_node(), DVec2::new(5000., 5000.), DAffine2::IDENTITY);
+		editor.runtime.run().await;
+
+		let probes = executor
+			.runtime_io
+			.receive()
+			.filter_map(|update| match update {
+				NodeGraphUpdate::ProbeResponse { color, .. } => Some(color),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(probes, vec![None], "A vector layer has no raster color to probe");
+		assert_eq!(recorded_footprint(&editor.runtime), viewport_footprint, "The monitor nodes should be left with the viewport's data");
+	}
+
+	#[test]
+	fn path_statistics_measure_the_transformed_paths_of_nested_groups() {
+		use graphene_core::{GraphicElement, GraphicGroupTable};
+		use graphene_std::instances::Instance;
+		use graphene_std::vector::VectorDataTable;
+
+		let rectangle = GraphicElement::VectorData(VectorDataTable::new(VectorData::from_subpath(bezier_rs::Subpath::new_rect(DVec2::ZERO, DVec2::new(10., 20.)))));
+		let statistics = PathStatistics::from_graphic_element(&rectangle, DAffine2::IDENTITY);
+		assert_eq!((statistics.points, statistics.segments, statistics.subpaths), (4, 4, 1));
+		assert!((statistics.length - 60.).abs() < 1e-9, "The perimeter should be 60, not {}", statistics.length);
+
+		// The rectangle next to a copy of itself scaled up twice, with the whole group scaled up twice again
+		let mut group = GraphicGroupTable::new(rectangle.clone());
+		group.push(Instance {
+			instance: rectangle,
+			transform: DAffine2::from_scale(DVec2::splat(2.)),
+			alpha_blending: Default::default(),
+			source_node_id: None,
+		});
+		let statistics = PathStatistics::from_graphic_element(&GraphicElement::GraphicGroup(group), DAffine2::from_scale(DVec2::splat(2.)));
+		assert_eq!((statistics.points, statistics.segments, statistics.subpaths), (8, 8, 2));
+		assert!((statistics.length - 360.).abs() < 1e-9, "The perimeters should add up to 360, not {}", statistics.length);
+	}
+
+	#[tokio::test]
+	async fn document_statistics_count_group_layers_once_in_the_total() {
+		use crate::messages::dialog::simple_dialogs::DocumentStatisticsDialog;
+		use crate::messages::portfolio::document::utility_types::misc::GroupFolderType;
+
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		let first = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(200., 0., 300., 50.).await;
+		let second = editor.get_selected_layer().await.unwrap();
+		editor
+			.handle_message(NodeGraphMessage::SelectedNodesSet {
+				nodes: vec![first.to_node(), second.to_node()],
+			})
+			.await;
+		editor
+			.handle_message(DocumentMessage::GroupSelectedLayers {
+				group_folder_type: GroupFolderType::Layer,
+			})
+			.await;
+		let group = editor.get_selected_layer().await.unwrap();
+
+		editor.editor.handle_message(PortfolioMessage::RequestDocumentStatistics);
+		editor.runtime.run().await;
+		let statistics = editor
+			.editor
+			.dispatcher
+			.message_handlers
+			.portfolio_message_handler
+			.executor
+			.runtime_io
+			.receive()
+			.find_map(|update| match update {
+				NodeGraphUpdate::DocumentStatisticsResponse(statistics) => Some(statistics),
+				_ => None,
+			})
+			.expect("The statistics should be measured");
+
+		let length = |layer: LayerNodeIdentifier| statistics.layers[&layer.to_node()].length;
+		assert!((length(first) - 400.).abs() < 1e-6, "The first rectangle's perimeter should be 400, not {}", length(first));
+		assert!((length(second) - 300.).abs() < 1e-6, "The second rectangle's perimeter should be 300, not {}", length(second));
+		assert_eq!(statistics.layers[&group.to_node()].points, 8, "The group should include both rectangles");
+
+		// The rectangles are already counted as part of their group
+		let dialog = DocumentStatisticsDialog::new(editor.active_document(), &statistics, 2);
+		assert_eq!(dialog.layers.len(), 3);
+		assert_eq!(dialog.total, statistics.layers[&group.to_node()]);
+	}
+
+	fn vector_data_with_points(ids: std::ops::Range<u64>) -> VectorData {
+		use graphene_std::vector::PointId;
+
+		let mut vector_data = VectorData::default();
+		for id in ids {
+			vector_data.point_domain.push(PointId::derive(id), DVec2::new(id as f64, -(id as f64)));
+		}
+		vector_data
+	}
+
+	#[test]
+	fn large_inspected_table_is_summarized_and_read_a_page_at_a_time() {
+		use crate::consts::{INSPECT_SUMMARY_EDGE_ROWS, INSPECT_SUMMARY_THRESHOLD};
+		use graphene_std::instances::Instance;
+		use graphene_std::vector::{PointId, VectorDataTable};
+
+		// Two instances, so pages can straddle the boundary between them
+		let mut table = VectorDataTable::new(vector_data_with_points(0..300_000));
+		table.push(Instance {
+			instance: vector_data_with_points(300_000..500_000),
+			transform: DAffine2::IDENTITY,
+			alpha_blending: Default::default(),
+			source_node_id: None,
+		});
+		let record: Arc<dyn std::any::Any + Send + Sync> = Arc::new(IORecord {
+			input: Context::default(),
+			output: table,
+			elapsed_ms: None,
+			revision: 0,
+		});
+
+		let summary = InspectSummary::of_large_table(record.as_ref(), INSPECT_SUMMARY_THRESHOLD, INSPECT_SUMMARY_EDGE_ROWS).expect("A table this large should be summarized");
+		assert_eq!((summary.instance_count, summary.point_count), (2, 500_000));
+		assert_eq!(summary.position_bounds, Some([DVec2::new(0., -499_999.), DVec2::new(499_999., 0.)]));
+		assert_eq!(summary.head.first().map(|row| row.position), Some(DVec2::new(0., 0.)));
+		assert_eq!(summary.tail.last().map(|row| row.position), Some(DVec2::new(499_999., -499_999.)));
+		let size = serde_json::to_vec(&summary).unwrap().len();
+		assert!(size < 32 * 1024, "The summary should stay small, but it's {size} bytes");
+
+		let rows = inspect_rows(record.as_ref(), 299_998, 4);
+		let expected = (299_998..300_002).map(|id: u64| (if id < 300_000 { 0 } else { 1 }, PointId::derive(id).inner(), DVec2::new(id as f64, -(id as f64))));
+		assert_eq!(rows.iter().map(|row| (row.instance, row.id, row.position)).collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+		assert_eq!(inspect_rows(record.as_ref(), 499_998, 10).len(), 2, "The last page should stop at the end of the table");
+
+		// Small tables are still sent whole
+		let small: Arc<dyn std::any::Any + Send + Sync> = Arc::new(IORecord {
+			input: Context::default(),
+			output: VectorDataTable::new(vector_data_with_points(0..10)),
+			elapsed_ms: None,
+			revision: 0,
+		});
+		assert_eq!(InspectSummary::of_large_table(small.as_ref(), INSPECT_SUMMARY_THRESHOLD, INSPECT_SUMMARY_EDGE_ROWS), None);
+	}
+
+	async fn export_svg(editor: &mut test_prelude::EditorTestUtils, expand_strokes: bool) -> String {
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		let export_config = ExportConfig {
+			file_type: FileType::Svg,
+			scale_factor: 1.,
+			expand_strokes,
+			..Default::default()
+		};
+		portfolio
+			.executor
+			.submit_document_export(document, export_config, &mut VecDeque::new())
+			.expect("The export should be submitted");
+		editor.runtime.run().await;
+
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("The export should render");
+		responses
+			.into_iter()
+			.find_map(|message| match message {
+				Message::Frontend(FrontendMessage::TriggerDownloadTextFile { document, .. }) => Some(document),
+				_ => None,
+			})
+			.expect("The export should produce an SVG file")
+	}
+
+	fn view_box(svg: &str) -> &str {
+		let start = svg.find("viewBox=\"").expect("The SVG should have a view box") + "viewBox=\"".len();
+		&svg[start..start + svg[start..].find('"').unwrap()]
+	}
+
+	#[tokio::test]
+	async fn exporting_with_expanded_strokes_leaves_only_fills() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		editor.draw_ellipse(150., 0., 250., 60.).await;
+
+		let stroked = export_svg(&mut editor, false).await;
+		let expanded = export_svg(&mut editor, true).await;
+
+		assert!(stroked.contains("stroke="), "The shapes should be stroked by default: {stroked}");
+		assert!(!expanded.contains("stroke="), "No stroke attributes should remain: {expanded}");
+		assert!(expanded.matches("<path").count() > stroked.matches("<path").count(), "Each stroke should become its own filled path");
+		assert_eq!(view_box(&expanded), view_box(&stroked), "Outlining the strokes shouldn't change the exported bounds");
+	}
+
+	#[tokio::test]
+	async fn exporting_custom_bounds_scales_the_rectangle() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		let export_config = ExportConfig {
+			file_type: FileType::Png,
+			scale_factor: 2.,
+			// The corners are given in reverse to check that they're normalized
+			bounds: ExportBounds::Custom([DVec2::new(110., 70.), DVec2::new(10., 20.)]),
+			..Default::default()
+		};
+		portfolio
+			.executor
+			.submit_document_export(document, export_config, &mut VecDeque::new())
+			.expect("The export should be submitted");
+		editor.runtime.run().await;
+
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("The export should render");
+		let size = responses
+			.into_iter()
+			.find_map(|message| match message {
+				Message::Frontend(FrontendMessage::TriggerDownloadImage { size, .. }) => Some(size),
+				_ => None,
+			})
+			.expect("The export should produce an image");
+
+		assert_eq!(size, (200., 100.), "The resolution should be the custom rectangle's size times the scale factor");
+	}
+
+	#[tokio::test]
+	async fn export_description_matches_the_exported_image() {
+		use test_prelude::{ModifierKeys, ToolType};
+
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.drag_tool(ToolType::Artboard, 0., 0., 200., 150., ModifierKeys::empty()).await;
+		let artboard = editor.get_selected_layer().await.unwrap();
+		editor.draw_rect(20., 30., 80., 90.).await;
+		editor.draw_rect(300., 0., 400., 50.).await;
+
+		let bounds_modes = [
+			ExportBounds::AllArtwork,
+			ExportBounds::Selection,
+			ExportBounds::Artboard(artboard),
+			ExportBounds::Custom([DVec2::new(10., 20.), DVec2::new(60., 70.)]),
+		];
+		let mut descriptions = Vec::new();
+		for bounds in bounds_modes {
+			let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+			let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+			let export_config = ExportConfig {
+				file_type: FileType::Png,
+				scale_factor: 2.,
+				bounds,
+				..Default::default()
+			};
+			let description = portfolio.executor.describe_export(document, &export_config).expect("The export should be described");
+			portfolio
+				.executor
+				.submit_document_export(document, export_config, &mut VecDeque::new())
+				.expect("The export should be submitted");
+			editor.runtime.run().await;
+
+			let mut responses = VecDeque::new();
+			editor.editor.poll_node_graph_evaluation(&mut responses).expect("The export should render");
+			let size = responses
+				.into_iter()
+				.find_map(|message| match message {
+					Message::Frontend(FrontendMessage::TriggerDownloadImage { size, .. }) => Some(size),
+					_ => None,
+				})
+				.expect("The export should produce an image");
+			assert_eq!(size, (description.size.x, description.size.y), "The described size should match the exported image for {bounds:?}");
+			descriptions.push(description);
+		}
+
+		let summaries = descriptions.iter().map(|description| (description.artboards.len(), description.layer_count)).collect::<Vec<_>>();
+		assert_eq!(summaries, [(1, 2), (0, 1), (1, 1), (1, 1)], "Each description should count the artboards and layers within its bounds");
+	}
+
+	/// Submits an export of the active document, returning the progress reported for it.
+	fn submit_export(editor: &mut test_prelude::EditorTestUtils) -> Vec<ExportStage> {
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		let mut responses = VecDeque::new();
+		let export_config = ExportConfig {
+			scale_factor: 1.,
+			..Default::default()
+		};
+		portfolio
+			.executor
+			.submit_document_export(document, export_config, &mut responses)
+			.expect("The export should be submitted");
+		export_stages(&responses)
+	}
+
+	fn cancel_export(editor: &mut test_prelude::EditorTestUtils) -> Vec<ExportStage> {
+		let mut responses = VecDeque::new();
+		editor.editor.dispatcher.message_handlers.portfolio_message_handler.executor.cancel_exports(&mut responses);
+		export_stages(&responses)
+	}
+
+	/// Polls the runtime's responses, returning the progress reported for the export and how many files were delivered.
+	fn poll_export(editor: &mut test_prelude::EditorTestUtils) -> (Vec<ExportStage>, usize) {
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("The graph should render");
+		let delivered = responses
+			.iter()
+			.filter(|message| {
+				matches!(
+					message,
+					Message::Frontend(FrontendMessage::TriggerDownloadImage { .. } | FrontendMessage::TriggerDownloadTextFile { .. })
+				)
+			})
+			.count();
+		(export_stages(&responses), delivered)
+	}
+
+	fn export_stages(responses: &VecDeque<Message>) -> Vec<ExportStage> {
+		responses
+			.iter()
+			.filter_map(|message| match message {
+				Message::Dialog(DialogMessage::ExportDialog(ExportDialogMessage::Progress { stage, .. })) => Some(*stage),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn cancelled_exports_are_not_delivered() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+
+		// Cancelled before the runtime got to it, so it's skipped
+		assert_eq!(submit_export(&mut editor), vec![ExportStage::Compile]);
+		assert_eq!(cancel_export(&mut editor), vec![ExportStage::Cancelled]);
+		editor.runtime.run().await;
+		assert_eq!(poll_export(&mut editor), (vec![], 0));
+
+		// Cancelled once the runtime had evaluated it, so its result is dropped on arrival
+		submit_export(&mut editor);
+		editor.runtime.run().await;
+		cancel_export(&mut editor);
+		assert_eq!(poll_export(&mut editor), (vec![], 0));
+		let executor = &editor.editor.dispatcher.message_handlers.portfolio_message_handler.executor;
+		assert!(executor.futures.is_empty() && executor.cancelled_exports.is_empty());
+
+		// Exports submitted afterward are delivered as usual
+		submit_export(&mut editor);
+		editor.runtime.run().await;
+		assert_eq!(poll_export(&mut editor), (vec![ExportStage::Evaluate, ExportStage::Encode, ExportStage::Finished], 1));
+	}
+
+	async fn render_main_viewport(editor: &mut test_prelude::EditorTestUtils) -> String {
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		portfolio
+			.executor
+			.submit_current_node_graph_evaluation(document, UVec2::new(800, 600), Default::default(), false, ViewportRenderer::Svg)
+			.expect("The evaluation should be submitted");
+		editor.runtime.run().await;
+
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("Graph should render");
+		responses
+			.into_iter()
+			.find_map(|message| match message {
+				Message::Frontend(FrontendMessage::UpdateDocumentArtwork { viewport_id: MAIN_VIEWPORT_ID, svg }) => Some(svg),
+				_ => None,
+			})
+			.expect("The artwork should be displayed")
+	}
+
+	#[tokio::test]
+	async fn vector_documents_render_while_the_application_io_is_unavailable() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.runtime.application_io_failure = Some("WebGPU is disabled".to_string());
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		portfolio.executor.update_node_graph_instrumented(document).expect("The graph should be updated");
+		portfolio
+			.executor
+			.submit_current_node_graph_evaluation(document, UVec2::new(800, 600), Default::default(), false, ViewportRenderer::Svg)
+			.expect("The evaluation should be submitted");
+		editor.runtime.run().await;
+
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("The vector content should render using the fallback");
+		let unavailable_reason = responses.iter().find_map(|message| match message {
+			Message::Frontend(FrontendMessage::UpdateApplicationIoStatus { unavailable_reason }) => Some(unavailable_reason.clone()),
+			_ => None,
+		});
+		assert_eq!(unavailable_reason, Some(Some("WebGPU is disabled".to_string())), "The reason should be reported for the banner");
+
+		let artwork = responses
+			.into_iter()
+			.find_map(|message| match message {
+				Message::Frontend(FrontendMessage::UpdateDocumentArtwork { viewport_id: MAIN_VIEWPORT_ID, svg }) => Some(svg),
+				_ => None,
+			})
+			.expect("The artwork should be displayed");
+		assert!(artwork.contains("<path"), "The rectangle should be rendered: {artwork}");
+	}
+
+	#[tokio::test]
+	async fn cost_heatmap_covers_each_layer_only_when_enabled() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.handle_message(PreferencesMessage::CostHeatmap { enabled: true }).await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+		editor.draw_ellipse(200., 0., 300., 100.).await;
+
+		let artwork = render_main_viewport(&mut editor).await;
+		let heatmap_start = artwork.find("<g data-cost-heatmap").expect("The heatmap should be drawn over the artwork");
+		let heatmap = &artwork[heatmap_start..];
+		assert_eq!(heatmap.matches("<rect").count(), 2, "Each layer should be covered by one rectangle: {heatmap}");
+		assert_eq!(heatmap.matches(" ms</text>").count(), 2, "Each layer should be labeled with its cost: {heatmap}");
+		assert!(heatmap.trim_end().ends_with("</g></svg>"), "The heatmap should be the last thing drawn");
+
+		editor.handle_message(PreferencesMessage::CostHeatmap { enabled: false }).await;
+		let artwork = render_main_viewport(&mut editor).await;
+		assert!(!artwork.contains("data-cost-heatmap"), "The heatmap should be gone once disabled");
+	}
+
+	fn busy_states(responses: VecDeque<Message>) -> Vec<(usize, usize)> {
+		responses
+			.into_iter()
+			.filter_map(|message| match message {
+				Message::Frontend(FrontendMessage::UpdateGraphBusyState { pending, pending_exports }) => Some((pending, pending_exports)),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn busy_state_is_reported_around_an_execution() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		portfolio
+			.executor
+			.submit_current_node_graph_evaluation(document, UVec2::new(800, 600), Default::default(), false, ViewportRenderer::Svg)
+			.expect("The evaluation should be submitted");
+
+		// The runtime hasn't picked up the request yet, so it is still pending
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("Polling should succeed");
+		assert_eq!(busy_states(responses), vec![(1, 0)]);
+
+		// Polling again without any change doesn't repeat the report
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("Polling should succeed");
+		assert_eq!(busy_states(responses), vec![]);
+
+		editor.runtime.run().await;
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("Graph should render");
+		assert_eq!(busy_states(responses), vec![(0, 0)]);
+	}
+
+	#[tokio::test]
+	async fn interactive_render_is_followed_by_a_final_render_once_idle() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		portfolio.executor.set_render_quality(RenderQualityHint::Interactive);
+		portfolio
+			.executor
+			.submit_current_node_graph_evaluation(document, UVec2::new(800, 600), Default::default(), false, ViewportRenderer::Svg)
+			.expect("The evaluation should be submitted");
+		editor.runtime.run().await;
+
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("Graph should render");
+		let executor = &editor.editor.dispatcher.message_handlers.portfolio_message_handler.executor;
+		assert!(executor.futures.is_empty(), "The final render should wait for the interaction to pause");
+		assert!(executor.final_render_due.is_some());
+
+		std::thread::sleep(std::time::Duration::from_millis(FINAL_RENDER_DELAY_MS as u64 + 10));
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("Polling should succeed");
+		let executor = &editor.editor.dispatcher.message_handlers.portfolio_message_handler.executor;
+		assert_eq!(executor.futures.len(), 1, "A final render should be queued after the delay");
+		assert!(
+			executor.futures.values().all(|context| context.interactive_render_config.is_none()),
+			"The queued render should be at final quality"
+		);
+
+		// The final render doesn't queue any further render
+		editor.runtime.run().await;
+		let mut responses = VecDeque::new();
+		editor.editor.poll_node_graph_evaluation(&mut responses).expect("Graph should render");
+		let executor = &editor.editor.dispatcher.message_handlers.portfolio_message_handler.executor;
+		assert!(executor.futures.is_empty());
+		assert!(executor.final_render_due.is_none());
+	}
+
+	#[tokio::test]
+	async fn cyclic_graph_reports_the_nodes_forming_the_cycle() {
+		let (mut runtime, executor) = NodeGraphExecutor::new_with_local_runtime();
+		let identity = |upstream| DocumentNode {
+			inputs: vec![NodeInput::node(NodeId(upstream), 0)],
+			implementation: DocumentNodeImplementation::ProtoNode("graphene_core::ops::IdentityNode".into()),
+			..Default::default()
+		};
+		let network = NodeNetwork {
+			exports: vec![NodeInput::node(NodeId(1), 0)],
+			nodes: [(NodeId(1), identity(2)), (NodeId(2), identity(1))].into_iter().collect(),
+			..Default::default()
+		};
+		executor.runtime_io.send(GraphRuntimeRequest::GraphUpdate(GraphUpdate { network, inspect_node: None })).unwrap();
+		runtime.run().await;
+
+		let (result, node_graph_errors) = executor
+			.runtime_io
+			.receive()
+			.find_map(|update| match update {
+				NodeGraphUpdate::CompilationResponse(CompilationResponse { result, node_graph_errors, .. }) => Some((result, node_graph_errors)),
+				_ => None,
+			})
+			.expect("The graph update should be answered");
+
+		let error = result.expect_err("A cyclic graph shouldn't compile");
+		assert_eq!(error, "The node graph contains a cycle through nodes 1 → 2");
+		let node_paths = node_graph_errors.into_iter().map(|error| error.node_path).collect::<Vec<_>>();
+		assert_eq!(node_paths, vec![vec![NodeId(1)], vec![NodeId(2)]], "Each node in the cycle should be highlighted");
+	}
+
+	#[tokio::test]
+	async fn network_without_output_reports_an_empty_network() {
+		let (mut runtime, executor) = NodeGraphExecutor::new_with_local_runtime();
+		let network = NodeNetwork {
+			exports: Vec::new(),
+			nodes: [(
+				NodeId(1),
+				DocumentNode {
+					inputs: vec![NodeInput::value(TaggedValue::F64(1.), false)],
+					implementation: DocumentNodeImplementation::ProtoNode("graphene_core::ops::IdentityNode".into()),
+					..Default::default()
+				},
+			)]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		};
+		executor.runtime_io.send(GraphRuntimeRequest::GraphUpdate(GraphUpdate { network, inspect_node: None })).unwrap();
+		runtime.run().await;
+
+		let (result, node_graph_errors) = executor
+			.runtime_io
+			.receive()
+			.find_map(|update| match update {
+				NodeGraphUpdate::CompilationResponse(CompilationResponse { result, node_graph_errors, .. }) => Some((result, node_graph_errors)),
+				_ => None,
+			})
+			.expect("The graph update should be answered");
+
+		assert_eq!(result.expect_err("A network without an output shouldn't compile"), "The node graph has nothing to evaluate");
+		assert_eq!(node_graph_errors.len(), 1);
+		assert!(matches!(node_graph_errors[0].error, GraphErrorType::EmptyNetwork));
+	}
+
+	#[tokio::test]
+	async fn zero_area_viewport_is_skipped_and_fully_rendered_once_it_has_an_area() {
+		let mut editor = test_prelude::EditorTestUtils::create();
+		editor.new_document().await;
+		editor.draw_rect(0., 0., 100., 100.).await;
+
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let executor = &mut portfolio.executor;
+		let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
+		executor.futures.clear();
+
+		document.dirty_region = Some([DVec2::ZERO, DVec2::splat(10.)]);
+		executor
+			.submit_current_node_graph_evaluation(document, UVec2::new(0, 600), Default::default(), false, ViewportRenderer::Svg)
+			.expect("Skipping the evaluation isn't an error");
+		assert!(executor.futures.is_empty(), "Nothing should be rendered to a viewport without any area");
+
+		document.dirty_region = Some([DVec2::ZERO, DVec2::splat(10.)]);
+		executor
+			.submit_current_node_graph_evaluation(document, UVec2::new(800, 600), Default::default(), false, ViewportRenderer::Svg)
+			.expect("The evaluation should be submitted");
+		assert_eq!(executor.futures.len(), 1);
+		assert!(
+			executor.futures.values().all(|context| context.dirty_region.is_none()),
+			"The whole viewport should be redrawn after being skipped"
+		);
+
+		editor.runtime.run().await;
+		let portfolio = &mut editor.editor.dispatcher.message_handlers.portfolio_message_handler;
+		let executions = portfolio.executor.runtime_io.receive().filter(|update| matches!(update, NodeGraphUpdate::ExecutionResponse(_))).count();
+		assert_eq!(executions, 1, "Only the viewport with an area should be rendered");
+	}
+
+	#[tokio::test]
+	async fn changed_linked_resource_only_invalidates_the_nodes_depending_on_it() {
+		let (mut runtime, executor) = NodeGraphExecutor::new_with_local_runtime();
+		let load_resource = |url: &str| DocumentNode {
+			inputs: vec![
+				NodeInput::value(TaggedValue::None, false),
+				NodeInput::scope("editor-api"),
+				NodeInput::value(TaggedValue::String(url.to_string()), false),
+			],
+			manual_composition: Some(graph_craft::concrete!(Context)),
+			implementation: DocumentNodeImplementation::ProtoNode("graphene_std::wasm_application_io::LoadResourceNode".into()),
+			..Default::default()
+		};
+		let decode_image = |upstream| DocumentNode {
+			inputs: vec![NodeInput::node(NodeId(upstream), 0)],
+			manual_composition: Some(graph_craft::concrete!(Context)),
+			implementation: DocumentNodeImplementation::ProtoNode("graphene_std::wasm_application_io::DecodeImageNode".into()),
+			..Default::default()
+		};
+		let (linked, other) = ("file:///linked.png".to_string(), "file:///other.png".to_string());
+		let network = NodeNetwork {
+			exports: vec![NodeInput::node(NodeId(2), 0)],
+			nodes: [
+				(NodeId(1), load_resource(&linked)),
+				(NodeId(2), decode_image(1)),
+				(NodeId(3), load_resource(&other)),
+				(NodeId(4), decode_image(3)),
+			]
+			.into_iter()
+			.collect(),
+			..Default::default()
+		};
+		executor.runtime_io.send(GraphRuntimeRequest::GraphUpdate(GraphUpdate { network, inspect_node: None })).unwrap();
+		let execution_request = ExecutionRequest {
+			execution_id: 0,
+			render_config: RenderConfig::default(),
+			additional_viewports: Vec::new(),
+		};
+		executor.runtime_io.send(GraphRuntimeRequest::ExecutionRequest(execution_request)).unwrap();
+		runtime.run().await;
+		let _ = executor.runtime_io.receive().count();
+
+		// The linked file couldn't be loaded by the execution, so it appearing counts as a change
+		runtime.external_resource_contents.insert(linked.clone(), b"linked image".to_vec());
+		executor.runtime_io.send(GraphRuntimeRequest::PollExternalResources).unwrap();
+		runtime.run().await;
+		let changed = executor.runtime_io.receive().find_map(|update| match update {
+			NodeGraphUpdate::NodeGraphUpdateMessage(NodeGraphUpdateMessage::ExternalResourcesChanged { urls }) => Some(urls),
+			_ => None,
+		});
+		assert_eq!(changed, Some(vec![linked.clone()]), "The editor should be told to render the changed resource");
+
+		// Polling again doesn't report the same change twice
+		executor.runtime_io.send(GraphRuntimeRequest::PollExternalResources).unwrap();
+		runtime.run().await;
+		assert!(executor.runtime_io.receive().next().is_none());
+
+		let document_nodes = |paths: Vec<Vec<NodeId>>| paths.into_iter().filter_map(|path| path.first().copied()).collect::<HashSet<_>>();
+		let node_count = runtime.executor.node_count();
+
+		let invalidated = document_nodes(runtime.invalidate_external_resources(&[linked]).await);
+		assert!(
+			invalidated.contains(&NodeId(1)) && invalidated.contains(&NodeId(2)),
+			"The loader and the node decoding its image should be invalidated: {invalidated:?}"
+		);
+		assert!(
+			!invalidated.contains(&NodeId(3)) && !invalidated.contains(&NodeId(4)),
+			"The other resource's nodes should keep their cached results: {invalidated:?}"
+		);
+
+		let invalidated = document_nodes(runtime.invalidate_external_resources(&[other]).await);
+		assert!(
+			!invalidated.contains(&NodeId(1)) && !invalidated.contains(&NodeId(2)),
+			"The linked resource's nodes should keep their cached results: {invalidated:?}"
+		);
+
+		assert_eq!(runtime.executor.node_count(), node_count, "The invalidated nodes should be constructed again");
+	}
+
+	#[test]
+	fn additional_viewport_output_is_tagged_with_its_viewport() {
+		let (_runtime, mut executor) = NodeGraphExecutor::new_with_local_runtime();
+		let mut responses = VecDeque::new();
+
+		let output = render_output(RenderOutputType::Svg("<svg></svg>".to_string()));
+		executor
+			.process_node_graph_output(output, DAffine2::IDENTITY, 1, None, &mut responses)
+			.expect("SVG output should be displayed");
+
+		assert!(
+			responses.contains(
+				&FrontendMessage::UpdateDocumentArtwork {
+					viewport_id: 1,
+					svg: "<svg></svg>".to_string()
+				}
+				.into()
+			)
+		);
+		assert!(
+			!responses.iter().any(|message| matches!(message, Message::EndBuffer(_))),
+			"Click targets should only come from the main viewport"
+		);
+	}
+
+	/// Runs the output through the viewport rendering and returns the SVG it sends to the frontend.
+	fn rendered_artwork(output: TaggedValue) -> String {
+		let (_runtime, mut executor) = NodeGraphExecutor::new_with_local_runtime();
+		let mut responses = VecDeque::new();
+		executor
+			.process_node_graph_output(output, DAffine2::IDENTITY, MAIN_VIEWPORT_ID, None, &mut responses)
+			.expect("Output should be rendered");
+
+		responses
+			.into_iter()
+			.find_map(|message| match message {
+				Message::Frontend(FrontendMessage::UpdateDocumentArtwork { svg, .. }) => Some(svg),
+				_ => None,
+			})
+			.expect("Artwork should be sent to the frontend")
+	}
+
+	#[test]
+	fn debug_render_points() {
+		let svg = rendered_artwork(TaggedValue::VecDVec2(vec![DVec2::ZERO, DVec2::new(10., 0.), DVec2::new(10., 10.)]));
+
+		assert_eq!(svg.matches("<circle").count(), 3);
+		assert!(svg.contains("3 points"));
+	}
+
+	#[test]
+	fn debug_render_gradient_stops() {
+		let stops = graphene_core::vector::style::GradientStops::new(vec![(0., graphene_core::Color::RED), (1., graphene_core::Color::BLUE)]);
+		let svg = rendered_artwork(TaggedValue::GradientStops(stops));
+
+		assert!(svg.contains("<linearGradient"));
+		assert_eq!(svg.matches("<stop").count(), 2);
+	}
+
+	#[test]
+	fn debug_render_color() {
+		let svg = rendered_artwork(TaggedValue::Color(graphene_core::Color::RED));
+
+		assert!(svg.contains("<rect"));
+		assert!(svg.contains("#ff0000"));
+	}
+
+	#[test]
+	fn debug_render_falls_back_to_escaped_debug_text() {
+		let svg = rendered_artwork(TaggedValue::Footprint(Footprint::default()));
+		assert!(svg.contains("Footprint"));
+
+		let font = graphene_core::text::Font::new("<b>Family</b>".to_string(), "Regular".to_string());
+		let svg = rendered_artwork(TaggedValue::Font(font));
+		assert!(svg.contains("&lt;b&gt;Family&lt;/b&gt;"));
+		assert!(!svg.contains("<b>"), "Text must not be interpreted as markup");
+	}
+
+	#[test]
+	fn debug_render_clamps_huge_values() {
+		let svg = rendered_artwork(TaggedValue::VecF64(vec![0.; 100_000]));
+
+		assert!(svg.matches("<tspan").count() <= DEBUG_RENDER_TEXT_MAX_LINES + 1);
+		assert!(svg.contains("…"));
+	}
+
 	/// Stores all of the monitor nodes that have been attached to a graph
 	#[derive(Default)]
 	pub struct Instrumented {
@@ -1,25 +1,28 @@
 use super::*;
+use crate::consts::{APPLICATION_IO_MAX_RETRY_DELAY_MS, APPLICATION_IO_RETRY_DELAY_MS, EXTERNAL_RESOURCE_POLL_INTERVAL_MS, INSPECT_SUMMARY_EDGE_ROWS, INSPECT_SUMMARY_THRESHOLD};
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
 use glam::{DAffine2, DVec2};
 use graph_craft::concrete;
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{NodeId, NodeNetwork};
 use graph_craft::graphene_compiler::Compiler;
-use graph_craft::proto::GraphErrors;
+use graph_craft::proto::{ConstructionArgs, GraphError, GraphErrorType, GraphErrors, ProtoNetwork};
 use graph_craft::wasm_application_io::EditorPreferences;
-use graphene_core::application_io::{NodeGraphUpdateMessage, NodeGraphUpdateSender, RenderConfig};
+use graphene_core::application_io::{NodeGraphUpdateMessage, NodeGraphUpdateSender, RenderConfig, resource_content_hash};
 use graphene_core::memo::IORecord;
 use graphene_core::renderer::{GraphicElementRendered, RenderParams, SvgRender};
 use graphene_core::renderer::{RenderSvgSegmentList, SvgSegment};
 use graphene_core::text::FontCache;
 use graphene_core::vector::style::ViewMode;
 use graphene_std::Context;
+use graphene_std::renderer::RenderMetadata;
 use graphene_std::vector::{VectorData, VectorDataTable};
 use graphene_std::wasm_application_io::{WasmApplicationIo, WasmEditorApi};
 use interpreted_executor::dynamic_executor::{DynamicExecutor, IntrospectError, ResolvedDocumentNodeTypesDelta};
 use interpreted_executor::util::wrap_network_in_scope;
 use once_cell::sync::Lazy;
 use spin::Mutex;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::sync::mpsc::{Receiver, Sender};
 
@@ -31,11 +34,29 @@ pub struct NodeRuntime {
 	pub(super) executor: DynamicExecutor,
 	#[cfg(not(test))]
 	executor: DynamicExecutor,
+	#[cfg(test)]
+	pub(super) receiver: Receiver<GraphRuntimeRequest>,
+	#[cfg(not(test))]
 	receiver: Receiver<GraphRuntimeRequest>,
 	sender: InternalNodeGraphUpdateSender,
 	editor_preferences: EditorPreferences,
+	application_io: ApplicationIoState,
+	/// Makes initializing the application IO fail with this reason, to test how the runtime copes without it.
+	#[cfg(test)]
+	pub(super) application_io_failure: Option<String>,
+	/// The content hash of each external resource loaded by the graph, shared with the [`InternalNodeGraphUpdateSender`] which records them as they're loaded.
+	external_resources: ExternalResources,
+	/// The nodes of the compiled network which load external resources, and those depending on them.
+	resource_loaders: ResourceLoaders,
+	/// When the external resources are next checked for changes, in milliseconds from [`timestamp_ms`].
+	next_resource_poll: f64,
+	/// Stands in for the content of external resources when they're checked for changes, to test how the runtime reacts to a resource changing.
+	#[cfg(test)]
+	pub(super) external_resource_contents: HashMap<String, Vec<u8>>,
 	old_graph: Option<NodeNetwork>,
 	update_thumbnails: bool,
+	/// The viewport evaluated by the last execution other than an export, which is evaluated again after a probe so the monitor nodes record the viewport's data rather than the probe's.
+	last_render_config: Option<RenderConfig>,
 
 	editor_api: Arc<WasmEditorApi>,
 	node_graph_errors: GraphErrors,
@@ -47,9 +68,36 @@ pub struct NodeRuntime {
 	// TODO: Remove, it doesn't need to be persisted anymore
 	/// The current renders of the thumbnails for layer nodes.
 	thumbnail_renders: HashMap<NodeId, Vec<SvgSegment>>,
+	/// The revision of the monitored output each layer's thumbnail was last rendered from, so layers whose output hasn't changed since are skipped.
+	thumbnail_revisions: HashMap<NodeId, u64>,
+	/// The most recent hover preview rendered for each layer node, reused until the layer's content changes.
+	layer_previews: HashMap<NodeId, LayerPreview>,
 	vector_modify: HashMap<NodeId, VectorData>,
 }
 
+/// Whether the application IO given to the graph is the real one, or a fallback standing in while it can't be initialized.
+#[derive(Clone, Debug, PartialEq)]
+enum ApplicationIoState {
+	Uninitialized,
+	Ready,
+	Unavailable {
+		reason: String,
+		/// How many attempts at initializing it have failed in a row.
+		attempts: u32,
+		/// When it's tried again, in milliseconds from [`timestamp_ms`].
+		retry_at: f64,
+	},
+}
+
+impl ApplicationIoState {
+	fn unavailable_reason(&self) -> Option<String> {
+		match self {
+			ApplicationIoState::Unavailable { reason, .. } => Some(reason.clone()),
+			_ => None,
+		}
+	}
+}
+
 /// Messages passed from the editor thread to the node runtime thread.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub enum GraphRuntimeRequest {
@@ -57,6 +105,28 @@ pub enum GraphRuntimeRequest {
 	ExecutionRequest(ExecutionRequest),
 	FontCacheUpdate(FontCache),
 	EditorPreferencesUpdate(EditorPreferences),
+	DocumentStatisticsRequest,
+	RenderLayerPreview {
+		layer_node: NodeId,
+		max_size: f64,
+	},
+	/// Samples the color of a layer's raster content at a document space position, with `to_document` transforming from the space of the layer's output.
+	ProbeRequest {
+		position: DVec2,
+		layer_node: NodeId,
+		to_document: DAffine2,
+	},
+	/// Reads up to `count` rows of the inspected vector data starting at `start`, for pages of a table too large to have been sent whole with the [`InspectSummary`].
+	InspectRows {
+		start: usize,
+		count: usize,
+	},
+	/// Skips the execution if it hasn't been evaluated yet, since its result is no longer wanted.
+	CancelExecution {
+		execution_id: u64,
+	},
+	/// Checks whether the external resources loaded by the graph have changed right away, rather than waiting for the next periodic check.
+	PollExternalResources,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -74,10 +144,14 @@ pub struct ExportConfig {
 	pub bounds: ExportBounds,
 	pub transparent_background: bool,
 	pub size: DVec2,
+	pub expand_strokes: bool,
 }
 
+/// The hash of the content of each external resource loaded by the graph by its URL, or `None` for those which couldn't be loaded.
+type ExternalResources = Arc<Mutex<HashMap<String, Option<u64>>>>;
+
 #[derive(Clone)]
-struct InternalNodeGraphUpdateSender(Sender<NodeGraphUpdate>);
+struct InternalNodeGraphUpdateSender(Sender<NodeGraphUpdate>, ExternalResources);
 
 impl InternalNodeGraphUpdateSender {
 	fn send_generation_response(&self, response: CompilationResponse) {
@@ -87,11 +161,96 @@ impl InternalNodeGraphUpdateSender {
 	fn send_execution_response(&self, response: ExecutionResponse) {
 		self.0.send(NodeGraphUpdate::ExecutionResponse(response)).expect("Failed to send response")
 	}
+
+	fn send_document_statistics_response(&self, statistics: DocumentStatistics) {
+		self.0.send(NodeGraphUpdate::DocumentStatisticsResponse(statistics)).expect("Failed to send response")
+	}
+
+	fn send_layer_preview_response(&self, layer_node: NodeId, preview: Option<String>) {
+		self.0.send(NodeGraphUpdate::LayerPreview { layer_node, preview }).expect("Failed to send response")
+	}
+
+	fn send_probe_response(&self, position: DVec2, color: Option<Color>) {
+		self.0.send(NodeGraphUpdate::ProbeResponse { position, color }).expect("Failed to send response")
+	}
+
+	fn send_inspect_rows_response(&self, inspect_node: NodeId, start: usize, rows: Vec<InspectRow>) {
+		self.0.send(NodeGraphUpdate::InspectRows { inspect_node, start, rows }).expect("Failed to send response")
+	}
 }
 
 impl NodeGraphUpdateSender for InternalNodeGraphUpdateSender {
 	fn send(&self, message: NodeGraphUpdateMessage) {
-		self.0.send(NodeGraphUpdate::NodeGraphUpdateMessage(message)).expect("Failed to send response")
+		match message {
+			// The runtime keeps track of the loaded resources to watch them for changes, so they aren't forwarded to the editor
+			NodeGraphUpdateMessage::ExternalResourceLoaded { url, content_hash } => {
+				self.1.lock().insert(url, content_hash);
+			}
+			message => self.0.send(NodeGraphUpdate::NodeGraphUpdateMessage(message)).expect("Failed to send response"),
+		}
+	}
+}
+
+/// The nodes of a compiled network which load external resources, along with the nodes depending on each node, to find what has to be evaluated again when a resource changes.
+#[derive(Debug, Default)]
+struct ResourceLoaders {
+	/// Each node loading a resource, with the URL it loads if that's a constant value, or `None` if it could be loading any resource.
+	loaders: Vec<(NodeId, Option<String>)>,
+	dependents: HashMap<NodeId, Vec<NodeId>>,
+}
+
+impl ResourceLoaders {
+	fn new(proto_network: &ProtoNetwork) -> Self {
+		let constant_urls = proto_network
+			.nodes
+			.iter()
+			.filter_map(|(id, node)| match &node.construction_args {
+				ConstructionArgs::Value(value) => match &**value {
+					TaggedValue::String(url) => Some((*id, url.clone())),
+					_ => None,
+				},
+				_ => None,
+			})
+			.collect::<HashMap<_, _>>();
+
+		let loaders = proto_network
+			.nodes
+			.iter()
+			.filter(|(_, node)| node.identifier == "graphene_std::wasm_application_io::LoadResourceNode".into())
+			.map(|(id, node)| {
+				let url = match &node.construction_args {
+					ConstructionArgs::Nodes(inputs) => inputs.iter().find_map(|(input, _)| constant_urls.get(input).cloned()),
+					_ => None,
+				};
+				(*id, url)
+			})
+			.collect::<Vec<_>>();
+
+		// The edges are only needed to find what depends on the loaders, if there are any
+		let dependents = if loaders.is_empty() { HashMap::new() } else { proto_network.collect_outwards_edges() };
+
+		Self { loaders, dependents }
+	}
+
+	fn may_load(&self, url: &str) -> bool {
+		self.loaders.iter().any(|(_, loader_url)| loader_url.as_deref().is_none_or(|loader_url| loader_url == url))
+	}
+
+	/// The nodes loading any of the `urls`, together with every node depending on them directly or indirectly.
+	fn affected_nodes(&self, urls: &[String]) -> HashSet<NodeId> {
+		let mut affected = HashSet::new();
+		let mut stack = self
+			.loaders
+			.iter()
+			.filter(|(_, loader_url)| loader_url.as_ref().is_none_or(|loader_url| urls.contains(loader_url)))
+			.map(|(id, _)| *id)
+			.collect::<Vec<_>>();
+		while let Some(id) = stack.pop() {
+			if affected.insert(id) {
+				stack.extend(self.dependents.get(&id).into_iter().flatten().copied());
+			}
+		}
+		affected
 	}
 }
 
@@ -99,18 +258,29 @@ pub static NODE_RUNTIME: Lazy<Mutex<Option<NodeRuntime>>> = Lazy::new(|| Mutex::
 
 impl NodeRuntime {
 	pub fn new(receiver: Receiver<GraphRuntimeRequest>, sender: Sender<NodeGraphUpdate>) -> Self {
+		let external_resources = ExternalResources::default();
+
 		Self {
 			executor: DynamicExecutor::default(),
 			receiver,
-			sender: InternalNodeGraphUpdateSender(sender.clone()),
+			sender: InternalNodeGraphUpdateSender(sender.clone(), external_resources.clone()),
 			editor_preferences: EditorPreferences::default(),
+			application_io: ApplicationIoState::Uninitialized,
+			#[cfg(test)]
+			application_io_failure: None,
+			external_resources: external_resources.clone(),
+			resource_loaders: ResourceLoaders::default(),
+			next_resource_poll: 0.,
+			#[cfg(test)]
+			external_resource_contents: HashMap::new(),
 			old_graph: None,
 			update_thumbnails: true,
+			last_render_config: None,
 
 			editor_api: WasmEditorApi {
 				font_cache: FontCache::default(),
 				editor_preferences: Box::new(EditorPreferences::default()),
-				node_graph_message_sender: Box::new(InternalNodeGraphUpdateSender(sender)),
+				node_graph_message_sender: Box::new(InternalNodeGraphUpdateSender(sender, external_resources)),
 
 				application_io: None,
 			}
@@ -120,38 +290,53 @@ impl NodeRuntime {
 			monitor_nodes: Vec::new(),
 
 			thumbnail_renders: Default::default(),
+			thumbnail_revisions: Default::default(),
+			layer_previews: Default::default(),
 			vector_modify: Default::default(),
 			inspect_state: None,
 		}
 	}
 
 	pub async fn run(&mut self) {
-		if self.editor_api.application_io.is_none() {
-			self.editor_api = WasmEditorApi {
-				#[cfg(not(test))]
-				application_io: Some(WasmApplicationIo::new().await.into()),
-				#[cfg(test)]
-				application_io: Some(WasmApplicationIo::new_offscreen().await.into()),
-				font_cache: self.editor_api.font_cache.clone(),
-				node_graph_message_sender: Box::new(self.sender.clone()),
-				editor_preferences: Box::new(self.editor_preferences.clone()),
-			}
-			.into();
+		let initialization_due = match &self.application_io {
+			ApplicationIoState::Uninitialized => true,
+			ApplicationIoState::Ready => false,
+			ApplicationIoState::Unavailable { retry_at, .. } => timestamp_ms() >= *retry_at,
+		};
+		if initialization_due {
+			self.initialize_application_io().await;
 		}
 
 		let mut font = None;
 		let mut preferences = None;
 		let mut graph = None;
 		let mut execution = None;
+		let mut statistics = None;
+		let mut layer_previews = Vec::new();
+		let mut cancelled = HashSet::new();
+		let mut poll_resources = timestamp_ms() >= self.next_resource_poll;
 		for request in self.receiver.try_iter() {
 			match request {
 				GraphRuntimeRequest::GraphUpdate(_) => graph = Some(request),
 				GraphRuntimeRequest::ExecutionRequest(_) => execution = Some(request),
 				GraphRuntimeRequest::FontCacheUpdate(_) => font = Some(request),
 				GraphRuntimeRequest::EditorPreferencesUpdate(_) => preferences = Some(request),
+				GraphRuntimeRequest::DocumentStatisticsRequest => statistics = Some(request),
+				GraphRuntimeRequest::RenderLayerPreview { .. } | GraphRuntimeRequest::ProbeRequest { .. } | GraphRuntimeRequest::InspectRows { .. } => layer_previews.push(request),
+				GraphRuntimeRequest::CancelExecution { execution_id } => {
+					cancelled.insert(execution_id);
+				}
+				GraphRuntimeRequest::PollExternalResources => poll_resources = true,
 			}
 		}
-		let requests = [font, preferences, graph, execution].into_iter().flatten();
+
+		// Changed resources are invalidated before any execution, so it doesn't render their outdated content
+		if poll_resources {
+			self.next_resource_poll = timestamp_ms() + EXTERNAL_RESOURCE_POLL_INTERVAL_MS;
+			self.poll_external_resources().await;
+		}
+		// Statistics, layer previews, probes, and inspected rows come last so they describe the result of any execution that was requested alongside them
+		let requests = [font, preferences, graph, execution, statistics].into_iter().flatten().chain(layer_previews);
 
 		for request in requests {
 			match request {
@@ -193,20 +378,67 @@ impl NodeRuntime {
 					self.sender.send_generation_response(CompilationResponse {
 						result,
 						node_graph_errors: self.node_graph_errors.clone(),
+						application_io_unavailable: self.application_io.unavailable_reason(),
 					});
 				}
-				GraphRuntimeRequest::ExecutionRequest(ExecutionRequest { execution_id, render_config, .. }) => {
+				// The execution was cancelled before it was evaluated
+				GraphRuntimeRequest::ExecutionRequest(ExecutionRequest { execution_id, .. }) if cancelled.contains(&execution_id) => {}
+				GraphRuntimeRequest::ExecutionRequest(ExecutionRequest {
+					execution_id,
+					render_config,
+					additional_viewports,
+				}) => {
 					let transform = render_config.viewport.transform;
 
+					// The monitor nodes only time their evaluation while the heatmap needs it
+					if render_config.cost_heatmap {
+						graphene_core::memo::set_evaluation_clock(Some(timestamp_ms));
+					}
+					let execution_start = timestamp_ms();
+					let counts_before = self.executor.evaluation_counts();
+					if !render_config.for_export {
+						self.last_render_config = Some(render_config);
+					}
 					let result = self.execute_network(render_config).await;
+					if render_config.cost_heatmap {
+						graphene_core::memo::set_evaluation_clock(None);
+					}
+					let execution_time = timestamp_ms() - execution_start;
+					let counts = self.executor.evaluation_counts().since(counts_before);
+
 					let mut responses = VecDeque::new();
 					// TODO: Only process monitor nodes if the graph has changed, not when only the Footprint changes
-					self.process_monitor_nodes(&mut responses, self.update_thumbnails);
+					let thumbnail_render_count = self.process_monitor_nodes(&mut responses, self.update_thumbnails);
 					self.update_thumbnails = false;
 
+					let statistics = ExecutionStatistics {
+						execution_time,
+						evaluated_node_count: counts.evaluations as usize,
+						cache_hit_count: counts.cache_hits as usize,
+						cache_miss_count: counts.cache_misses as usize,
+						thumbnail_render_count,
+					};
+
 					// Resolve the result from the inspection by accessing the monitor node
 					let inspect_result = self.inspect_state.and_then(|state| state.access(&self.executor));
 
+					let cost_heatmap = match &result {
+						Ok(TaggedValue::RenderOutput(output)) if render_config.cost_heatmap => Some(self.cost_heatmap(&output.metadata)),
+						_ => None,
+					};
+
+					// The other viewports are rendered after everything above was read from the main viewport's evaluation.
+					// Only the nodes depending on the footprint run again, since the rest are still cached from the main viewport's evaluation.
+					let mut additional_outputs = Vec::with_capacity(additional_viewports.len());
+					for (viewport_id, viewport) in additional_viewports {
+						let result = self.execute_network(RenderConfig { viewport, ..render_config }).await;
+						additional_outputs.push(ViewportOutput {
+							viewport_id,
+							result,
+							transform: viewport.transform,
+						});
+					}
+
 					self.sender.send_execution_response(ExecutionResponse {
 						execution_id,
 						result,
@@ -214,13 +446,116 @@ impl NodeRuntime {
 						transform,
 						vector_modify: self.vector_modify.clone(),
 						inspect_result,
+						statistics,
+						additional_outputs,
+						cost_heatmap,
 					});
 				}
+				GraphRuntimeRequest::DocumentStatisticsRequest => {
+					let statistics = self.document_statistics();
+					self.sender.send_document_statistics_response(statistics);
+				}
+				GraphRuntimeRequest::RenderLayerPreview { layer_node, max_size } => {
+					let preview = self.layer_preview(layer_node, max_size);
+					self.sender.send_layer_preview_response(layer_node, preview);
+				}
+				GraphRuntimeRequest::ProbeRequest { position, layer_node, to_document } => {
+					let color = self.probe_color(position, layer_node, to_document).await;
+					self.sender.send_probe_response(position, color);
+				}
+				GraphRuntimeRequest::InspectRows { start, count } => {
+					let Some(state) = self.inspect_state else { continue };
+					// The rows are read from the output kept by the monitor node, so nothing besides them is copied
+					let Ok(introspected_data) = self.executor.introspect(&[state.monitor_node]) else { continue };
+					let rows = inspect_rows(introspected_data.as_ref(), start, count);
+					self.sender.send_inspect_rows_response(state.inspect_node, start, rows);
+				}
+				// Cancellations and resource polls were gathered along with the other requests
+				GraphRuntimeRequest::CancelExecution { .. } | GraphRuntimeRequest::PollExternalResources => {}
+			}
+		}
+	}
+
+	/// Gives the graph the application IO, or a fallback able to render vector content if it fails to initialize, in which case it's tried again after a growing delay.
+	async fn initialize_application_io(&mut self) {
+		#[cfg(not(test))]
+		let result = WasmApplicationIo::try_new().await;
+		#[cfg(test)]
+		let result = match self.application_io_failure.clone() {
+			Some(reason) => Err(reason),
+			None => Ok(WasmApplicationIo::new_offscreen().await),
+		};
+
+		let previous_attempts = match &self.application_io {
+			ApplicationIoState::Unavailable { attempts, .. } => Some(*attempts),
+			_ => None,
+		};
+		let application_io = match result {
+			Ok(application_io) => {
+				self.application_io = ApplicationIoState::Ready;
+				application_io
+			}
+			Err(reason) => {
+				let attempts = previous_attempts.unwrap_or_default() + 1;
+				let delay = (APPLICATION_IO_RETRY_DELAY_MS * 2_f64.powi(attempts as i32 - 1)).min(APPLICATION_IO_MAX_RETRY_DELAY_MS);
+				warn!("Application IO unavailable, trying again in {delay} ms: {reason}");
+				self.application_io = ApplicationIoState::Unavailable {
+					reason,
+					attempts,
+					retry_at: timestamp_ms() + delay,
+				};
+
+				// The fallback is already in use after an earlier failure
+				if previous_attempts.is_some() {
+					return;
+				}
+				WasmApplicationIo::fallback()
+			}
+		};
+
+		self.editor_api = WasmEditorApi {
+			application_io: Some(application_io.into()),
+			font_cache: self.editor_api.font_cache.clone(),
+			node_graph_message_sender: Box::new(self.sender.clone()),
+			editor_preferences: Box::new(self.editor_preferences.clone()),
+		}
+		.into();
+
+		// The graph was compiled against the fallback, so it's compiled again to make use of the real IO and the recovery is reported
+		if previous_attempts.is_some() {
+			if let Some(graph) = self.old_graph.clone() {
+				self.node_graph_errors.clear();
+				let result = self.update_network(graph).await;
+				self.update_thumbnails = true;
+				self.sender.send_generation_response(CompilationResponse {
+					result,
+					node_graph_errors: self.node_graph_errors.clone(),
+					application_io_unavailable: None,
+				});
 			}
 		}
 	}
 
 	async fn update_network(&mut self, graph: NodeNetwork) -> Result<ResolvedDocumentNodeTypesDelta, String> {
+		// A cycle can never be compiled, so it's reported along with the nodes forming it instead of failing somewhere within compilation
+		if let Some(cycle) = graph.find_cycle() {
+			let node_ids = cycle.iter().filter_map(|path| path.last().copied()).collect::<Vec<_>>();
+			let description = node_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(" → ");
+			self.node_graph_errors = cycle
+				.into_iter()
+				.map(|node_path| GraphError {
+					node_path,
+					identifier: Default::default(),
+					error: GraphErrorType::Cycle(node_ids.clone()),
+				})
+				.collect();
+			return Err(format!("The node graph contains a cycle through nodes {description}"));
+		}
+		// A network without an output, such as one loaded from a damaged document, has nothing to compile
+		if graph.exports.is_empty() {
+			return Err(self.empty_network_error());
+		}
+
 		let scoped_network = wrap_network_in_scope(graph, self.editor_api.clone());
 
 		// We assume only one output
@@ -230,20 +565,82 @@ impl NodeRuntime {
 			Ok(network) => network,
 			Err(e) => return Err(e),
 		};
+		if proto_network.nodes.is_empty() {
+			return Err(self.empty_network_error());
+		}
 		self.monitor_nodes = proto_network
 			.nodes
 			.iter()
 			.filter(|(_, node)| node.identifier == "graphene_core::memo::MonitorNode".into())
 			.map(|(_, node)| node.original_location.path.clone().unwrap_or_default())
 			.collect::<Vec<_>>();
+		self.resource_loaders = ResourceLoaders::new(&proto_network);
+		// Resources no longer loaded by any node don't need watching
+		self.external_resources.lock().retain(|url, _| self.resource_loaders.may_load(url));
 
-		assert_ne!(proto_network.nodes.len(), 0, "No proto nodes exist?");
 		self.executor.update(proto_network).await.map_err(|e| {
 			self.node_graph_errors.clone_from(&e);
 			format!("{e:?}")
 		})
 	}
 
+	/// Reports that the network has nothing to evaluate, leaving the previously compiled network in place.
+	fn empty_network_error(&mut self) -> String {
+		self.node_graph_errors = vec![GraphError {
+			node_path: Vec::new(),
+			identifier: Default::default(),
+			error: GraphErrorType::EmptyNetwork,
+		}];
+		format!("{:?}", GraphErrorType::EmptyNetwork)
+	}
+
+	/// Loads each external resource the graph has loaded again, and if any of their content changed, the nodes depending on them are evaluated again by the next execution and the editor is told to render the graph again.
+	async fn poll_external_resources(&mut self) {
+		let resources = self.external_resources.lock().clone();
+		let mut changed = Vec::new();
+		for (url, content_hash) in resources {
+			if self.external_resource_hash(&url).await != content_hash {
+				changed.push(url);
+			}
+		}
+		if changed.is_empty() {
+			return;
+		}
+		changed.sort();
+
+		self.invalidate_external_resources(&changed).await;
+		self.sender.send(NodeGraphUpdateMessage::ExternalResourcesChanged { urls: changed });
+	}
+
+	/// The hash of the current content of an external resource, or `None` if it can't be loaded.
+	async fn external_resource_hash(&self, url: &str) -> Option<u64> {
+		#[cfg(not(test))]
+		let data = {
+			use graphene_core::application_io::ApplicationIo;
+			self.editor_api.application_io.as_ref()?.load_resource(url).ok()?.await.ok()?
+		};
+		#[cfg(test)]
+		let data = self.external_resource_contents.get(url)?.clone();
+
+		Some(resource_content_hash(&data))
+	}
+
+	/// Discards the nodes loading any of the `urls` along with every node depending on them, while the rest of the graph keeps its cached results.
+	/// Returns the document paths of the discarded nodes, which are constructed again and evaluated by the next execution.
+	pub(super) async fn invalidate_external_resources(&mut self, urls: &[String]) -> Vec<Vec<NodeId>> {
+		let invalidated = self.executor.invalidate(self.resource_loaders.affected_nodes(urls));
+		// The resources are registered again once they're loaded by the next execution
+		self.external_resources.lock().retain(|url, _| !urls.contains(url));
+
+		if let Some(graph) = self.old_graph.clone() {
+			// We ignore this result as compilation errors should have been reported in an earlier iteration
+			let _ = self.update_network(graph).await;
+			self.update_thumbnails = true;
+		}
+
+		invalidated.into_iter().map(Vec::from).collect()
+	}
+
 	async fn execute_network(&mut self, render_config: RenderConfig) -> Result<TaggedValue, String> {
 		use graph_craft::graphene_compiler::Executor;
 
@@ -261,10 +658,15 @@ impl NodeRuntime {
 		Ok(result)
 	}
 
-	/// Updates state data
-	pub fn process_monitor_nodes(&mut self, responses: &mut VecDeque<FrontendMessage>, update_thumbnails: bool) {
+	/// Updates state data, returning how many thumbnails were rendered.
+	/// Only the layers whose monitored output changed since their thumbnail was last rendered are visited.
+	pub fn process_monitor_nodes(&mut self, responses: &mut VecDeque<FrontendMessage>, update_thumbnails: bool) -> usize {
 		// TODO: Consider optimizing this since it's currently O(m*n^2), with a sort it could be made O(m * n*log(n))
 		self.thumbnail_renders.retain(|id, _| self.monitor_nodes.iter().any(|monitor_node_path| monitor_node_path.contains(id)));
+		self.thumbnail_revisions.retain(|id, _| self.thumbnail_renders.contains_key(id));
+		self.layer_previews.retain(|id, _| self.thumbnail_renders.contains_key(id));
+
+		let mut thumbnail_render_count = 0;
 
 		for monitor_node_path in &self.monitor_nodes {
 			// Skip the inspect monitor node
@@ -287,10 +689,19 @@ impl NodeRuntime {
 				continue;
 			};
 
+			// A thumbnail only needs rendering again if the output it was rendered from has changed
+			let mut outdated = |revision: u64| update_thumbnails && self.thumbnail_revisions.insert(parent_network_node_id, revision) != Some(revision);
+
 			if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, graphene_core::GraphicElement>>() {
-				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnails)
+				if outdated(io.revision) {
+					Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses);
+					thumbnail_render_count += 1;
+				}
 			} else if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, graphene_core::Artboard>>() {
-				Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses, update_thumbnails)
+				if outdated(io.revision) {
+					Self::process_graphic_element(&mut self.thumbnail_renders, parent_network_node_id, &io.output, responses);
+					thumbnail_render_count += 1;
+				}
 			// Insert the vector modify if we are dealing with vector data
 			} else if let Some(record) = introspected_data.downcast_ref::<IORecord<Context, VectorDataTable>>() {
 				self.vector_modify.insert(parent_network_node_id, record.output.one_instance_ref().instance.clone());
@@ -298,6 +709,168 @@ impl NodeRuntime {
 				log::warn!("failed to downcast monitor node output {parent_network_node_id:?}");
 			}
 		}
+
+		thumbnail_render_count
+	}
+
+	/// Measures the vector content of every layer from the data its monitor node recorded during the last execution, so nothing needs to be re-evaluated or re-rendered.
+	fn document_statistics(&self) -> DocumentStatistics {
+		let layers = self
+			.monitor_nodes
+			.iter()
+			.filter(|monitor_node_path| !self.inspect_state.is_some_and(|inspect_state| monitor_node_path.last().copied() == Some(inspect_state.monitor_node)))
+			.filter_map(|monitor_node_path| {
+				// The monitor nodes are located within a document node, and are thus children in that network, so this gets the parent document node's ID
+				let parent_network_node_id = monitor_node_path.len().checked_sub(2).and_then(|index| monitor_node_path.get(index)).copied()?;
+
+				// Only layers record their content as a `GraphicElement`
+				let introspected_data = self.executor.introspect(monitor_node_path).ok()?;
+				let io = introspected_data.downcast_ref::<IORecord<Context, graphene_core::GraphicElement>>()?;
+
+				Some((parent_network_node_id, PathStatistics::from_graphic_element(&io.output, DAffine2::IDENTITY)))
+			})
+			.collect();
+
+		DocumentStatistics { layers }
+	}
+
+	/// Draws a translucent rectangle over the bounds of every layer timed during the last execution, colored from green to red by how long the layer took to evaluate compared to the slowest one.
+	/// The layers are placed in viewport space using the footprints recorded in the render's metadata.
+	fn cost_heatmap(&self, metadata: &RenderMetadata) -> String {
+		let mut layers = self
+			.monitor_nodes
+			.iter()
+			.filter(|monitor_node_path| !self.inspect_state.is_some_and(|inspect_state| monitor_node_path.last().copied() == Some(inspect_state.monitor_node)))
+			.filter_map(|monitor_node_path| {
+				// The monitor nodes are located within a document node, and are thus children in that network, so this gets the parent document node's ID
+				let parent_network_node_id = monitor_node_path.len().checked_sub(2).and_then(|index| monitor_node_path.get(index)).copied()?;
+				let footprint = metadata.upstream_footprints.get(&parent_network_node_id)?;
+
+				let introspected_data = self.executor.introspect(monitor_node_path).ok()?;
+				if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, graphene_core::GraphicElement>>() {
+					Some((io.output.bounding_box(footprint.transform, true)?, io.elapsed_ms?))
+				} else if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, graphene_core::Artboard>>() {
+					Some((io.output.bounding_box(footprint.transform, true)?, io.elapsed_ms?))
+				} else {
+					None
+				}
+			})
+			.collect::<Vec<_>>();
+
+		// Draw the largest layers first so the smaller ones above them stay visible
+		layers.sort_by(|([a_min, a_max], _), ([b_min, b_max], _)| (b_max - b_min).element_product().total_cmp(&(a_max - a_min).element_product()));
+		let slowest = layers.iter().map(|(_, elapsed_ms)| *elapsed_ms).fold(0., f64::max);
+
+		let mut svg = String::from(r#"<g data-cost-heatmap="" style="pointer-events: none">"#);
+		for ([min, max], elapsed_ms) in layers {
+			let cost = if slowest > 0. { elapsed_ms / slowest } else { 0. };
+			let hue = 120. * (1. - cost);
+			let size = max - min;
+			svg.push_str(&format!(
+				r#"<rect x="{}" y="{}" width="{}" height="{}" fill="hsl({hue}, 100%, 50%)" fill-opacity="0.35" stroke="hsl({hue}, 100%, 35%)" />"#,
+				min.x, min.y, size.x, size.y
+			));
+			svg.push_str(&format!(r#"<text x="{}" y="{}" font-size="11" fill="black">{elapsed_ms:.2} ms</text>"#, min.x + 2., min.y + 12.));
+		}
+		svg.push_str("</g>");
+
+		svg
+	}
+
+	/// Renders the content recorded by a layer's monitor node during the last execution, scaled so its larger side is `max_size`.
+	/// Returns `None` if the layer has no monitor data or no visible content to render.
+	fn layer_preview(&mut self, layer_node: NodeId, max_size: f64) -> Option<String> {
+		// The thumbnail render changes exactly when the layer's content does, so it serves as the cache key
+		let content_hash = self.thumbnail_renders.get(&layer_node).map(|thumbnail| {
+			let mut hasher = std::collections::hash_map::DefaultHasher::new();
+			thumbnail.to_svg_string().hash(&mut hasher);
+			hasher.finish()
+		});
+		if let Some(preview) = self.layer_previews.get(&layer_node) {
+			if Some(preview.content_hash) == content_hash && preview.max_size == max_size {
+				return Some(preview.svg.clone());
+			}
+		}
+
+		let monitor_node_path = self.layer_monitor_node_path(layer_node)?;
+		let introspected_data = self.executor.introspect(monitor_node_path).ok()?;
+
+		let svg = if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, graphene_core::GraphicElement>>() {
+			Self::render_preview(&io.output, max_size)
+		} else if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, graphene_core::Artboard>>() {
+			Self::render_preview(&io.output, max_size)
+		} else {
+			None
+		}?;
+
+		if let Some(content_hash) = content_hash {
+			let preview = LayerPreview {
+				content_hash,
+				max_size,
+				svg: svg.clone(),
+			};
+			self.layer_previews.insert(layer_node, preview);
+		}
+
+		Some(svg)
+	}
+
+	/// The path to the monitor node recording a layer's output, excluding the one used for inspecting a node.
+	pub(super) fn layer_monitor_node_path(&self, layer_node: NodeId) -> Option<&Vec<NodeId>> {
+		self.monitor_nodes
+			.iter()
+			.filter(|monitor_node_path| !self.inspect_state.is_some_and(|inspect_state| monitor_node_path.last().copied() == Some(inspect_state.monitor_node)))
+			.find(|monitor_node_path| monitor_node_path.len().checked_sub(2).and_then(|index| monitor_node_path.get(index)) == Some(&layer_node))
+	}
+
+	/// Samples the color of a layer's raster content at a document space position, or returns `None` if it has none there.
+	/// The graph is evaluated with a single pixel footprint centered on the position, so raster content depending on the footprint is only computed where it's needed.
+	/// Afterwards the last viewport is evaluated again, since the layer previews, document statistics, and inspected rows are read from what the monitor nodes recorded.
+	async fn probe_color(&mut self, position: DVec2, layer_node: NodeId, to_document: DAffine2) -> Option<Color> {
+		let render_config = RenderConfig {
+			viewport: Footprint {
+				transform: DAffine2::from_translation(DVec2::splat(0.5) - position),
+				resolution: glam::UVec2::ONE,
+				..Default::default()
+			},
+			..Default::default()
+		};
+		let color = match self.execute_network(render_config).await {
+			Ok(_) => self.sample_layer_color(position, layer_node, to_document),
+			Err(_) => None,
+		};
+
+		// Only the nodes depending on the footprint run again, the rest are still cached from the probe
+		if let Some(render_config) = self.last_render_config {
+			let _ = self.execute_network(render_config).await;
+		}
+
+		color
+	}
+
+	/// Samples the color of the raster content recorded by a layer's monitor node at a document space position.
+	fn sample_layer_color(&self, position: DVec2, layer_node: NodeId, to_document: DAffine2) -> Option<Color> {
+		let monitor_node_path = self.layer_monitor_node_path(layer_node)?;
+		let introspected_data = self.executor.introspect(monitor_node_path).ok()?;
+		let io = introspected_data.downcast_ref::<IORecord<Context, graphene_core::GraphicElement>>()?;
+
+		sample_color(&io.output, to_document, position)
+	}
+
+	fn render_preview(graphic_element: &impl GraphicElementRendered, max_size: f64) -> Option<String> {
+		let [min, max] = graphic_element.bounding_box(DAffine2::IDENTITY, true)?;
+		let size = max - min;
+		if size.max_element() <= 0. || max_size <= 0. {
+			return None;
+		}
+		let scale = max_size / size.max_element();
+
+		let render_params = RenderParams::new(ViewMode::Normal, Some([min, max]), true, false, false);
+		let mut render = SvgRender::new();
+		graphic_element.render_svg(&mut render, &render_params);
+		render.wrap_with_transform(DAffine2::from_scale(DVec2::splat(scale)) * DAffine2::from_translation(-min), Some(size * scale));
+
+		Some(render.svg.to_svg_string())
 	}
 
 	// If this is `GraphicElement` data:
@@ -307,14 +880,9 @@ impl NodeRuntime {
 		parent_network_node_id: NodeId,
 		graphic_element: &impl GraphicElementRendered,
 		responses: &mut VecDeque<FrontendMessage>,
-		update_thumbnails: bool,
 	) {
 		// RENDER THUMBNAIL
 
-		if !update_thumbnails {
-			return;
-		}
-
 		let bounds = graphic_element.bounding_box(DAffine2::IDENTITY, true);
 
 		// Render the thumbnail from a `GraphicElement` into an SVG string
@@ -341,6 +909,26 @@ impl NodeRuntime {
 	}
 }
 
+/// The color of the topmost raster content at a document space position within a graphic element, given the transform from the element's space to document space.
+fn sample_color(graphic_element: &graphene_core::GraphicElement, transform: DAffine2, position: DVec2) -> Option<Color> {
+	match graphic_element {
+		graphene_core::GraphicElement::GraphicGroup(group) => group
+			.instance_ref_iter()
+			.rev()
+			.find_map(|element| sample_color(element.instance, transform * *element.transform, position)),
+		graphene_core::GraphicElement::RasterFrame(graphene_core::RasterFrame::ImageFrame(images)) => images.instance_ref_iter().rev().find_map(|image| {
+			// Images occupy the unit square of their own space
+			let unit_position = (transform * *image.transform).inverse().transform_point2(position);
+			if !(0. ..1.).contains(&unit_position.x) || !(0. ..1.).contains(&unit_position.y) {
+				return None;
+			}
+			let size = DVec2::new(image.instance.width as f64, image.instance.height as f64);
+			(size.min_element() > 0.).then(|| image.instance.sample(unit_position * size))
+		}),
+		_ => None,
+	}
+}
+
 pub async fn introspect_node(path: &[NodeId]) -> Result<Arc<dyn std::any::Any + Send + Sync + 'static>, IntrospectError> {
 	let runtime = NODE_RUNTIME.lock();
 	if let Some(ref mut runtime) = runtime.as_ref() {
@@ -362,6 +950,90 @@ pub async fn replace_node_runtime(runtime: NodeRuntime) -> Option<NodeRuntime> {
 	node_runtime.replace(runtime)
 }
 
+/// Counts describing the vector paths of a layer, used to diagnose performance problems and bloated imports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PathStatistics {
+	pub points: usize,
+	pub segments: usize,
+	pub subpaths: usize,
+	/// The approximate total length of all segments, measured after applying the transforms within the layer.
+	pub length: f64,
+}
+
+impl PathStatistics {
+	pub(super) fn from_graphic_element(graphic_element: &graphene_core::GraphicElement, transform: DAffine2) -> Self {
+		let mut statistics = Self::default();
+
+		match graphic_element {
+			graphene_core::GraphicElement::VectorData(vector_data) => {
+				for instance in vector_data.instance_ref_iter() {
+					statistics.add_vector_data(instance.instance, transform * *instance.transform);
+				}
+			}
+			graphene_core::GraphicElement::GraphicGroup(graphic_group) => {
+				for instance in graphic_group.instance_ref_iter() {
+					statistics.add(Self::from_graphic_element(instance.instance, transform * *instance.transform));
+				}
+			}
+			graphene_core::GraphicElement::RasterFrame(_) => {}
+		}
+
+		statistics
+	}
+
+	fn add_vector_data(&mut self, vector_data: &VectorData, transform: DAffine2) {
+		self.points += vector_data.point_domain.ids().len();
+		self.segments += vector_data.segment_domain.ids().len();
+		self.subpaths += vector_data.stroke_bezier_paths().count();
+		self.length += vector_data
+			.segment_bezier_iter()
+			.map(|(_, bezier, _, _)| bezier.apply_transformation(|point| transform.transform_point2(point)).length(None))
+			.sum::<f64>();
+	}
+
+	pub fn add(&mut self, other: Self) {
+		self.points += other.points;
+		self.segments += other.segments;
+		self.subpaths += other.subpaths;
+		self.length += other.length;
+	}
+}
+
+/// Measurements of a single graph execution made by the runtime, which are cheap enough to take on every execution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExecutionStatistics {
+	/// Milliseconds spent evaluating the graph, not counting the time the request waited to be picked up.
+	pub execution_time: f64,
+	/// The number of times a proto node was evaluated, which leaves out the nodes whose output was already cached further downstream.
+	pub evaluated_node_count: usize,
+	/// The number of times a memoizing node returned its cached output.
+	pub cache_hit_count: usize,
+	/// The number of times a memoizing node had nothing cached for its input, so it had to be evaluated.
+	pub cache_miss_count: usize,
+	/// The number of layer thumbnails rendered after the execution, which only includes the layers whose output changed.
+	pub thumbnail_render_count: usize,
+}
+
+impl ExecutionStatistics {
+	/// Whether most of the memoizing nodes reached by the execution returned their cached output.
+	pub fn mostly_cached(&self) -> bool {
+		self.cache_hit_count > self.cache_miss_count
+	}
+}
+
+/// The path statistics of every layer in the document, keyed by the layer's node ID. The content of group layers includes that of their children.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DocumentStatistics {
+	pub layers: HashMap<NodeId, PathStatistics>,
+}
+
+/// A rendered layer preview along with the hash of the layer's thumbnail render at the time, used to tell when it has gone stale.
+struct LayerPreview {
+	content_hash: u64,
+	max_size: f64,
+	svg: String,
+}
+
 /// Which node is inspected and which monitor node is used (if any) for the current execution
 #[derive(Debug, Clone, Copy)]
 struct InspectState {
@@ -377,6 +1049,95 @@ pub struct InspectResult {
 	#[cfg(feature = "decouple-execution")]
 	introspected_data: Option<TaggedValue>,
 	pub inspect_node: NodeId,
+	/// Stands in for the data when it's a vector data table too large to send whole, whose rows are then read a page at a time with [`GraphRuntimeRequest::InspectRows`].
+	pub summary: Option<InspectSummary>,
+}
+
+/// A compact description of an inspected [`VectorDataTable`] with more points than [`INSPECT_SUMMARY_THRESHOLD`], whose rows are the points of all its instances in order.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InspectSummary {
+	pub instance_count: usize,
+	pub point_count: usize,
+	pub segment_count: usize,
+	pub region_count: usize,
+	/// The smallest and largest point position along each axis, or `None` if there are no points.
+	pub position_bounds: Option<[DVec2; 2]>,
+	/// The first rows of the table.
+	pub head: Vec<InspectRow>,
+	/// The last rows of the table, ending with the final point.
+	pub tail: Vec<InspectRow>,
+}
+
+/// A point of an inspected [`VectorDataTable`], as one row of the spreadsheet.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InspectRow {
+	/// The index of the instance the point belongs to.
+	pub instance: usize,
+	pub id: u64,
+	pub position: DVec2,
+}
+
+fn inspected_vector_data(introspected_data: &(dyn std::any::Any + Send + Sync)) -> Option<&VectorDataTable> {
+	if let Some(io) = introspected_data.downcast_ref::<IORecord<Context, VectorDataTable>>() {
+		Some(&io.output)
+	} else {
+		introspected_data.downcast_ref::<IORecord<(), VectorDataTable>>().map(|io| &io.output)
+	}
+}
+
+impl InspectSummary {
+	/// Summarizes the inspected data if it's a vector data table with more than `threshold` points, keeping `edge_rows` rows from either end.
+	pub fn of_large_table(introspected_data: &(dyn std::any::Any + Send + Sync), threshold: usize, edge_rows: usize) -> Option<Self> {
+		let table = inspected_vector_data(introspected_data)?;
+		let point_count = table.instance_ref_iter().map(|instance| instance.instance.point_domain.len()).sum::<usize>();
+		if point_count <= threshold {
+			return None;
+		}
+
+		let position_bounds = table
+			.instance_ref_iter()
+			.flat_map(|instance| instance.instance.point_domain.positions().iter().copied())
+			.fold(None, |bounds: Option<[DVec2; 2]>, position| {
+				Some(bounds.map_or([position, position], |[min, max]| [min.min(position), max.max(position)]))
+			});
+		let edge_rows = edge_rows.min(point_count);
+
+		Some(Self {
+			instance_count: table.len(),
+			point_count,
+			segment_count: table.instance_ref_iter().map(|instance| instance.instance.segment_domain.ids().len()).sum(),
+			region_count: table.instance_ref_iter().map(|instance| instance.instance.region_domain.ids().len()).sum(),
+			position_bounds,
+			head: inspect_rows(introspected_data, 0, edge_rows),
+			tail: inspect_rows(introspected_data, point_count - edge_rows, edge_rows),
+		})
+	}
+}
+
+/// Reads up to `count` rows of the points of an inspected vector data table starting at row `start`, skipping whole instances before it without visiting their points.
+pub fn inspect_rows(introspected_data: &(dyn std::any::Any + Send + Sync), start: usize, count: usize) -> Vec<InspectRow> {
+	let Some(table) = inspected_vector_data(introspected_data) else { return Vec::new() };
+
+	let mut rows = Vec::with_capacity(count);
+	let mut skip = start;
+	for (instance, vector_data) in table.instance_ref_iter().map(|instance| instance.instance).enumerate() {
+		let points = &vector_data.point_domain;
+		if skip >= points.len() {
+			skip -= points.len();
+			continue;
+		}
+
+		let taken = (points.len() - skip).min(count - rows.len());
+		let ids = &points.ids()[skip..skip + taken];
+		let positions = &points.positions()[skip..skip + taken];
+		rows.extend(ids.iter().zip(positions).map(|(id, &position)| InspectRow { instance, id: id.inner(), position }));
+		skip = 0;
+
+		if rows.len() == count {
+			break;
+		}
+	}
+	rows
 }
 
 impl InspectResult {
@@ -430,12 +1191,19 @@ impl InspectState {
 	fn access(&self, executor: &DynamicExecutor) -> Option<InspectResult> {
 		let introspected_data = executor.introspect(&[self.monitor_node]).inspect_err(|e| warn!("Failed to introspect monitor node {e}")).ok();
 		// TODO: Consider displaying the error instead of ignoring it
+
+		// A large table is described by a summary instead, leaving the data with the monitor node to be read a page at a time
+		let summary = introspected_data
+			.as_ref()
+			.and_then(|data| InspectSummary::of_large_table(data.as_ref(), INSPECT_SUMMARY_THRESHOLD, INSPECT_SUMMARY_EDGE_ROWS));
+		let introspected_data = introspected_data.filter(|_| summary.is_none());
 		#[cfg(feature = "decouple-execution")]
 		let introspected_data = introspected_data.as_ref().and_then(|data| TaggedValue::try_from_std_any_ref(data).ok());
 
 		Some(InspectResult {
 			inspect_node: self.inspect_node,
 			introspected_data,
+			summary,
 		})
 	}
 }
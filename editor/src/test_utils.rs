@@ -2,6 +2,8 @@ use crate::application::Editor;
 use crate::application::set_uuid_seed;
 use crate::messages::input_mapper::utility_types::input_keyboard::ModifierKeys;
 use crate::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, MouseKeys, ScrollDelta, ViewportPosition};
+use crate::messages::portfolio::document::overlays::utility_types::{OverlayContext, OverlayRecording, OverlaysVisibilitySettings};
+use crate::messages::portfolio::document::utility_types::misc::ViewportRenderer;
 use crate::messages::portfolio::utility_types::Platform;
 use crate::messages::prelude::*;
 use crate::messages::tool::tool_messages::tool_prelude::Key;
@@ -9,10 +11,12 @@ use crate::messages::tool::utility_types::ToolType;
 use crate::node_graph_executor::Instrumented;
 use crate::node_graph_executor::NodeRuntime;
 use crate::test_utils::test_prelude::LayerNodeIdentifier;
+use bezier_rs::Subpath;
 use glam::DVec2;
-use graph_craft::document::DocumentNode;
+use graph_craft::document::{DocumentNode, NodeId};
 use graphene_core::InputAccessor;
 use graphene_core::raster::color::Color;
+use graphene_core::vector::PointId;
 
 /// A set of utility functions to make the writing of editor test more declarative
 pub struct EditorTestUtils {
@@ -39,6 +43,8 @@ impl EditorTestUtils {
 	pub fn eval_graph<'a>(&'a mut self) -> impl std::future::Future<Output = Instrumented> + 'a {
 		// An inner function is required since async functions in traits are a bit weird
 		async fn run<'a>(editor: &'a mut Editor, runtime: &'a mut NodeRuntime) -> Instrumented {
+			// The subpath click regions are only collected while the Path tool is active, as the portfolio does when it submits a render
+			let subpath_click_regions = editor.dispatcher.active_tool_type() == ToolType::Path;
 			let portfolio = &mut editor.dispatcher.message_handlers.portfolio_message_handler;
 			let exector = &mut portfolio.executor;
 			let document = portfolio.documents.get_mut(&portfolio.active_document_id.unwrap()).unwrap();
@@ -47,7 +53,7 @@ impl EditorTestUtils {
 
 			let viewport_resolution = glam::UVec2::ONE;
 			exector
-				.submit_current_node_graph_evaluation(document, viewport_resolution, Default::default())
+				.submit_current_node_graph_evaluation(document, viewport_resolution, Default::default(), subpath_click_regions, ViewportRenderer::Svg)
 				.expect("submit_current_node_graph_evaluation failed");
 			runtime.run().await;
 
@@ -128,6 +134,7 @@ impl EditorTestUtils {
 				editor_position: (x2, y2).into(),
 				mouse_keys: MouseKeys::empty(),
 				scroll_delta: ScrollDelta::default(),
+				pen: None,
 			},
 			modifier_keys,
 		)
@@ -148,6 +155,7 @@ impl EditorTestUtils {
 				editor_position: (100., 100.).into(),
 				mouse_keys: MouseKeys::LEFT | MouseKeys::RIGHT,
 				scroll_delta: ScrollDelta::default(),
+				pen: None,
 			},
 			ModifierKeys::default(),
 		)
@@ -200,6 +208,7 @@ impl EditorTestUtils {
 				editor_position: (x, y).into(),
 				mouse_keys: MouseKeys::LEFT,
 				scroll_delta: ScrollDelta::default(),
+				pen: None,
 			},
 			modifier_keys,
 		)
@@ -241,6 +250,21 @@ impl EditorTestUtils {
 		self.press(Key::Enter, ModifierKeys::empty()).await;
 	}
 
+	/// Creates a vector layer made of the given subpaths at the top of the document, and selects it.
+	pub async fn draw_subpaths(&mut self, subpaths: Vec<Subpath<PointId>>) -> LayerNodeIdentifier {
+		let id = NodeId::new();
+		self.handle_message(GraphOperationMessage::NewVectorLayer {
+			id,
+			subpaths,
+			parent: LayerNodeIdentifier::ROOT_PARENT,
+			insert_index: 0,
+		})
+		.await;
+		self.handle_message(NodeGraphMessage::SelectedNodesSet { nodes: vec![id] }).await;
+
+		LayerNodeIdentifier::new(id, &self.active_document().network_interface, &[])
+	}
+
 	pub async fn get_selected_layer(&mut self) -> Option<LayerNodeIdentifier> {
 		self.active_document().network_interface.selected_nodes().selected_layers(self.active_document().metadata()).next()
 	}
@@ -251,12 +275,23 @@ impl EditorTestUtils {
 				editor_position: position,
 				mouse_keys: MouseKeys::LEFT,
 				scroll_delta: ScrollDelta::default(),
+				pen: None,
 			},
 			modifier_keys: ModifierKeys::empty(),
 		})
 		.await;
 	}
 
+	/// Sends a recording overlay context to the given overlay provider and returns the drawing commands it captured.
+	pub async fn record_overlays(&mut self, provider: impl FnOnce(OverlayContext) -> Message) -> OverlayRecording {
+		let recording = OverlayRecording::default();
+		let overlay_context = OverlayContext::new_recording(DVec2::new(1920., 1080.), OverlaysVisibilitySettings::default(), recording.clone());
+
+		self.handle_message(provider(overlay_context)).await;
+
+		recording
+	}
+
 	pub async fn drag_path(&mut self, points: &[DVec2], modifier_keys: ModifierKeys) {
 		if points.is_empty() {
 			return;
@@ -275,6 +310,7 @@ impl EditorTestUtils {
 				editor_position: points[points.len() - 1],
 				mouse_keys: MouseKeys::empty(),
 				scroll_delta: ScrollDelta::default(),
+				pen: None,
 			},
 			modifier_keys,
 		)
@@ -298,6 +334,47 @@ impl FrontendMessageTestUtils for FrontendMessage {
 	}
 }
 
+/// Compares `actual` against the golden file at `editor/test-goldens/<name>`.
+///
+/// Set the `GRAPHITE_UPDATE_GOLDENS` environment variable to write golden files from `actual`, either to record a new one or to rewrite one after an intended change.
+/// Otherwise a missing golden file fails the test, so a golden that was never committed can't pass silently.
+pub fn assert_golden(name: &str, actual: &str) {
+	let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("test-goldens").join(name);
+
+	if std::env::var_os("GRAPHITE_UPDATE_GOLDENS").is_some() {
+		std::fs::create_dir_all(path.parent().unwrap()).expect("Failed to create the golden file directory");
+		std::fs::write(&path, actual).expect("Failed to write the golden file");
+		return;
+	}
+
+	if !path.exists() {
+		panic!(
+			"Missing the golden file {}\n\nRun with GRAPHITE_UPDATE_GOLDENS=1 to record it, then review and commit it",
+			path.display()
+		);
+	}
+
+	let expected = std::fs::read_to_string(&path).expect("Failed to read the golden file");
+	if expected == actual {
+		return;
+	}
+
+	let (line, expected_line, actual_line) = expected
+		.lines()
+		.map(Some)
+		.chain(std::iter::repeat(None))
+		.zip(actual.lines().map(Some).chain(std::iter::repeat(None)))
+		.take(expected.lines().count().max(actual.lines().count()))
+		.enumerate()
+		.find(|(_, (expected, actual))| expected != actual)
+		.map(|(line, (expected, actual))| (line + 1, expected.unwrap_or("<end of file>"), actual.unwrap_or("<end of file>")))
+		.unwrap_or((0, "", ""));
+	panic!(
+		"Output differs from the golden file {}\nfirst difference on line {line}:\nexpected: {expected_line}\n  actual: {actual_line}\n\nRun with GRAPHITE_UPDATE_GOLDENS=1 to update the golden file if this change is intended",
+		path.display()
+	);
+}
+
 #[cfg(test)]
 pub mod test_prelude {
 	pub use super::FrontendMessageTestUtils;
@@ -311,7 +388,7 @@ pub mod test_prelude {
 	pub use crate::messages::tool::common_functionality::graph_modification_utils::{NodeGraphLayer, is_layer_fed_by_node_of_name};
 	pub use crate::messages::tool::utility_types::ToolType;
 	pub use crate::node_graph_executor::NodeRuntime;
-	pub use crate::test_utils::EditorTestUtils;
+	pub use crate::test_utils::{EditorTestUtils, assert_golden};
 	pub use core::f64;
 	pub use glam::DVec2;
 	pub use glam::IVec2;
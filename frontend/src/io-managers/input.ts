@@ -160,7 +160,12 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		}
 
 		const modifiers = makeKeyboardModifiersBitfield(e);
-		editor.handle.onMouseMove(e.clientX, e.clientY, e.buttons, modifiers);
+		editor.handle.onMouseMove(e.clientX, e.clientY, e.buttons, modifiers, penPressure(e), e.tiltX, e.tiltY);
+	}
+
+	// Only pens report a meaningful pressure, since browsers make up a fixed one for other pointers while a button is down
+	function penPressure(e: PointerEvent): number | undefined {
+		return e.pointerType === "pen" ? e.pressure : undefined;
 	}
 
 	function onMouseDown(e: MouseEvent) {
@@ -189,7 +194,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 
 		if (viewportPointerInteractionOngoing) {
 			const modifiers = makeKeyboardModifiersBitfield(e);
-			editor.handle.onMouseDown(e.clientX, e.clientY, e.buttons, modifiers);
+			editor.handle.onMouseDown(e.clientX, e.clientY, e.buttons, modifiers, penPressure(e), e.tiltX, e.tiltY);
 		}
 	}
 
@@ -205,7 +210,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, portfoli
 		if (textToolInteractiveInputElement) return;
 
 		const modifiers = makeKeyboardModifiersBitfield(e);
-		editor.handle.onMouseUp(e.clientX, e.clientY, e.buttons, modifiers);
+		editor.handle.onMouseUp(e.clientX, e.clientY, e.buttons, modifiers, penPressure(e), e.tiltX, e.tiltY);
 	}
 
 	function onPotentialDoubleClick(e: MouseEvent) {
@@ -125,6 +125,36 @@ export class UpdateNodeThumbnail extends JsMessage {
 	readonly value!: string;
 }
 
+export class UpdateRenderStatistics extends JsMessage {
+	readonly totalTime!: number;
+
+	readonly executionTime!: number;
+
+	readonly nodeCount!: number;
+
+	readonly mostlyCached!: boolean;
+}
+
+export class UpdateGraphBusyState extends JsMessage {
+	readonly pending!: number;
+
+	readonly pendingExports!: number;
+}
+
+export type ExportStage = "Compile" | "Evaluate" | "Encode" | "Finished" | "Cancelled" | "Failed";
+
+export class UpdateExportProgress extends JsMessage {
+	readonly stage!: ExportStage;
+
+	readonly fraction!: number;
+}
+
+export class UpdateLayerPreview extends JsMessage {
+	readonly id!: bigint;
+
+	readonly preview!: string | undefined;
+}
+
 export class UpdateNodeGraphSelection extends JsMessage {
 	@Type(() => BigInt)
 	readonly selected!: bigint[];
@@ -703,6 +733,10 @@ export class UpdateActiveDocument extends JsMessage {
 	readonly documentId!: bigint;
 }
 
+export class UpdateApplicationIoStatus extends JsMessage {
+	readonly unavailableReason!: string | undefined;
+}
+
 export class DisplayDialogPanic extends JsMessage {
 	readonly panicInfo!: string;
 }
@@ -713,9 +747,20 @@ export class DisplayDialog extends JsMessage {
 }
 
 export class UpdateDocumentArtwork extends JsMessage {
+	readonly viewportId!: bigint;
+
 	readonly svg!: string;
 }
 
+const TuplesToBounds = Transform(({ value }: { value: [[number, number], [number, number]] | undefined }) =>
+	value === undefined ? undefined : value.map(([x, y]) => ({ x, y })),
+);
+
+export class UpdateDocumentArtworkDirtyRegion extends JsMessage {
+	@TuplesToBounds
+	readonly dirtyRegion!: [XY, XY] | undefined;
+}
+
 export class UpdateDocumentScrollbars extends JsMessage {
 	@TupleToVec2
 	readonly position!: XY;
@@ -781,6 +826,10 @@ export class UpdateSpreadsheetState extends JsMessage {
 	readonly node!: bigint | undefined;
 }
 
+export class UpdatePointTableState extends JsMessage {
+	readonly open!: boolean;
+}
+
 export class UpdateMouseCursor extends JsMessage {
 	@Transform(({ value }: { value: MouseCursor }) => mouseCursorIconCSSNames[value] || "alias")
 	readonly cursor!: MouseCursorIcon;
@@ -799,6 +848,8 @@ export class TriggerFetchAndOpenDocument extends JsMessage {
 
 export class TriggerOpenDocument extends JsMessage {}
 
+export class TriggerOpenEditSession extends JsMessage {}
+
 export class TriggerImport extends JsMessage {}
 
 export class TriggerPaste extends JsMessage {}
@@ -866,6 +917,10 @@ export class DisplayEditableTextboxTransform extends JsMessage {
 
 export class DisplayRemoveEditableTextbox extends JsMessage {}
 
+export class DisplayLinkedAssetsUpdated extends JsMessage {
+	readonly urls!: string[];
+}
+
 export class UpdateDocumentLayerDetails extends JsMessage {
 	@Type(() => LayerPanelEntry)
 	readonly data!: LayerPanelEntry;
@@ -900,6 +955,8 @@ export class LayerPanelEntry {
 
 	parentsUnlocked!: boolean;
 
+	editLocked!: boolean;
+
 	parentId!: bigint | undefined;
 
 	selected!: boolean;
@@ -954,6 +1011,8 @@ export abstract class WidgetProps {
 export class CheckboxInput extends WidgetProps {
 	checked!: boolean;
 
+	indeterminate!: boolean;
+
 	disabled!: boolean;
 
 	icon!: IconName;
@@ -1182,9 +1241,13 @@ export class NumberInput extends WidgetProps {
 
 	rangeMax!: number | undefined;
 
+	forwardsExpressions!: boolean;
+
 	// Styling
 
 	minWidth!: number;
+
+	invalid!: boolean;
 }
 
 export class NodeCatalog extends WidgetProps {
@@ -1595,6 +1658,8 @@ export class UpdateMenuBarLayout extends JsMessage {
 
 export class UpdateNodeGraphControlBarLayout extends WidgetDiffUpdate {}
 
+export class UpdatePointTableLayout extends WidgetDiffUpdate {}
+
 export class UpdatePropertyPanelSectionsLayout extends WidgetDiffUpdate {}
 
 export class UpdateSpreadsheetLayout extends WidgetDiffUpdate {}
@@ -1635,6 +1700,7 @@ export const messageMakers: Record<string, MessageMaker> = {
 	DisplayDialogPanic,
 	DisplayEditableTextbox,
 	DisplayEditableTextboxTransform,
+	DisplayLinkedAssetsUpdated,
 	DisplayRemoveEditableTextbox,
 	SendUIMetadata,
 	TriggerAboutGraphiteLocalizedCommitDate,
@@ -1650,6 +1716,7 @@ export const messageMakers: Record<string, MessageMaker> = {
 	TriggerLoadPreferences,
 	TriggerLoadRestAutoSaveDocuments,
 	TriggerOpenDocument,
+	TriggerOpenEditSession,
 	TriggerPaste,
 	TriggerSaveActiveDocument,
 	TriggerSavePreferences,
@@ -1658,6 +1725,7 @@ export const messageMakers: Record<string, MessageMaker> = {
 	TriggerUpgradeDocumentToVectorManipulationFormat,
 	TriggerVisitLink,
 	UpdateActiveDocument,
+	UpdateApplicationIoStatus,
 	UpdateBox,
 	UpdateClickTargets,
 	UpdateContextMenuInformation,
@@ -1665,14 +1733,17 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateDialogColumn1,
 	UpdateDialogColumn2,
 	UpdateDocumentArtwork,
+	UpdateDocumentArtworkDirtyRegion,
 	UpdateDocumentBarLayout,
 	UpdateDocumentLayerDetails,
 	UpdateDocumentLayerStructureJs,
 	UpdateDocumentModeLayout,
 	UpdateDocumentRulers,
 	UpdateDocumentScrollbars,
+	UpdateExportProgress,
 	UpdateExportReorderIndex,
 	UpdateEyedropperSamplingState,
+	UpdateGraphBusyState,
 	UpdateGraphFadeArtwork,
 	UpdateGraphViewOverlay,
 	UpdateSpreadsheetState,
@@ -1680,6 +1751,7 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateImportsExports,
 	UpdateInputHints,
 	UpdateInSelectedNetwork,
+	UpdateLayerPreview,
 	UpdateLayersPanelControlBarLayout,
 	UpdateLayerWidths,
 	UpdateMenuBarLayout,
@@ -1690,7 +1762,10 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateNodeGraphTransform,
 	UpdateNodeThumbnail,
 	UpdateOpenDocumentsList,
+	UpdatePointTableLayout,
+	UpdatePointTableState,
 	UpdatePropertyPanelSectionsLayout,
+	UpdateRenderStatistics,
 	UpdateSpreadsheetLayout,
 	UpdateToolOptionsLayout,
 	UpdateToolShelfLayout,
@@ -18,6 +18,7 @@ import {
 	UpdateImportReorderIndex,
 	UpdateExportReorderIndex,
 	UpdateImportsExports,
+	UpdateLayerPreview,
 	UpdateLayerWidths,
 	UpdateNodeGraph,
 	UpdateNodeGraphSelection,
@@ -46,6 +47,7 @@ export function createNodeGraphState(editor: Editor) {
 		nodeDescriptions: new Map<string, string>(),
 		nodeTypes: [] as FrontendNodeType[],
 		thumbnails: new Map<bigint, string>(),
+		layerPreviews: new Map<bigint, string | undefined>(),
 		selected: [] as bigint[],
 		transform: { scale: 1, x: 0, y: 0 },
 		inSelectedNetwork: true,
@@ -144,6 +146,12 @@ export function createNodeGraphState(editor: Editor) {
 			return state;
 		});
 	});
+	editor.subscriptions.subscribeJsMessage(UpdateLayerPreview, (updateLayerPreview) => {
+		update((state) => {
+			state.layerPreviews.set(updateLayerPreview.id, updateLayerPreview.preview);
+			return state;
+		});
+	});
 	editor.subscriptions.subscribeJsMessage(UpdateWirePathInProgress, (updateWirePathInProgress) => {
 		update((state) => {
 			state.wirePathInProgress = updateWirePathInProgress.wirePath;
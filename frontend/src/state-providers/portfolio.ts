@@ -10,6 +10,7 @@ import {
 	TriggerDownloadTextFile,
 	TriggerImport,
 	TriggerOpenDocument,
+	TriggerOpenEditSession,
 	TriggerUpgradeDocumentToVectorManipulationFormat,
 	UpdateActiveDocument,
 	UpdateOpenDocumentsList,
@@ -17,6 +18,8 @@ import {
 	defaultWidgetLayout,
 	patchWidgetLayout,
 	UpdateSpreadsheetLayout,
+	UpdatePointTableState,
+	UpdatePointTableLayout,
 } from "@graphite/messages";
 import { downloadFileText, downloadFileBlob, upload } from "@graphite/utility-functions/files";
 import { extractPixelData, rasterizeSVG } from "@graphite/utility-functions/rasterization";
@@ -30,6 +33,8 @@ export function createPortfolioState(editor: Editor) {
 		spreadsheetOpen: false,
 		spreadsheetNode: BigInt(0) as bigint | undefined,
 		spreadsheetWidgets: defaultWidgetLayout(),
+		pointTableOpen: false,
+		pointTableWidgets: defaultWidgetLayout(),
 	});
 
 	// Set up message subscriptions on creation
@@ -67,6 +72,10 @@ export function createPortfolioState(editor: Editor) {
 		const data = await upload(extension, "text");
 		editor.handle.openDocumentFile(data.filename, data.content);
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerOpenEditSession, async () => {
+		const data = await upload(".json", "text");
+		editor.handle.importEditSession(data.content);
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerImport, async () => {
 		const data = await upload("image/*", "both");
 
@@ -125,6 +134,20 @@ export function createPortfolioState(editor: Editor) {
 		});
 	});
 
+	editor.subscriptions.subscribeJsMessage(UpdatePointTableState, (updatePointTableState) => {
+		update((state) => {
+			state.pointTableOpen = updatePointTableState.open;
+			return state;
+		});
+	});
+
+	editor.subscriptions.subscribeJsMessage(UpdatePointTableLayout, (updatePointTableLayout) => {
+		update((state) => {
+			patchWidgetLayout(state.pointTableWidgets, updatePointTableLayout);
+			return state;
+		});
+	});
+
 	return {
 		subscribe,
 	};
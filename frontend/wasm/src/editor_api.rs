@@ -278,6 +278,12 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	#[wasm_bindgen(js_name = importEditSession)]
+	pub fn import_edit_session(&self, session: String) {
+		let message = PortfolioMessage::ImportEditSession { session };
+		self.dispatch(message);
+	}
+
 	#[wasm_bindgen(js_name = openAutoSavedDocument)]
 	pub fn open_auto_saved_document(&self, document_id: u64, document_name: String, document_is_saved: bool, document_serialized_content: String, to_front: bool) {
 		let document_id = DocumentId(document_id);
@@ -339,10 +345,10 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
-	/// Mouse movement within the screenspace bounds of the viewport
+	/// Mouse movement within the screenspace bounds of the viewport, with the pressure (given only for a pen) and tilt of a pen
 	#[wasm_bindgen(js_name = onMouseMove)]
-	pub fn on_mouse_move(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_move(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8, pen_pressure: Option<f64>, pen_tilt_x: f64, pen_tilt_y: f64) {
+		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into()).with_pen(pen_pressure, (pen_tilt_x, pen_tilt_y).into());
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -364,8 +370,8 @@ impl EditorHandle {
 
 	/// A mouse button depressed within screenspace the bounds of the viewport
 	#[wasm_bindgen(js_name = onMouseDown)]
-	pub fn on_mouse_down(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_down(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8, pen_pressure: Option<f64>, pen_tilt_x: f64, pen_tilt_y: f64) {
+		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into()).with_pen(pen_pressure, (pen_tilt_x, pen_tilt_y).into());
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -375,8 +381,8 @@ impl EditorHandle {
 
 	/// A mouse button released
 	#[wasm_bindgen(js_name = onMouseUp)]
-	pub fn on_mouse_up(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_up(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8, pen_pressure: Option<f64>, pen_tilt_x: f64, pen_tilt_y: f64) {
+		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into()).with_pen(pen_pressure, (pen_tilt_x, pen_tilt_y).into());
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -504,6 +510,13 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Paste rows of coordinates into the Point Table panel, starting at the cell with the given row and column
+	#[wasm_bindgen(js_name = pastePointTableCoordinates)]
+	pub fn paste_point_table_coordinates(&self, text: String, row: usize, column: usize) {
+		let message = PointTableMessage::PasteCoordinates { text, row, column };
+		self.dispatch(message);
+	}
+
 	/// Modify the layer selection based on the layer which is clicked while holding down the <kbd>Ctrl</kbd> and/or <kbd>Shift</kbd> modifier keys used for range selection behavior
 	#[wasm_bindgen(js_name = selectLayer)]
 	pub fn select_layer(&self, id: u64, ctrl: bool, shift: bool) {
@@ -687,6 +700,23 @@ impl EditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Toggle the edit lock state of a layer from the layer list, which protects its points from the Path tool
+	#[wasm_bindgen(js_name = toggleLayerEditLock)]
+	pub fn toggle_layer_edit_lock(&self, node_id: u64) {
+		let message = NodeGraphMessage::ToggleEditLocked { node_id: NodeId(node_id) };
+		self.dispatch(message);
+	}
+
+	/// Request a larger render of a layer's content for the hover preview in the layer list, sized to fit within `max_size` pixels
+	#[wasm_bindgen(js_name = requestLayerPreview)]
+	pub fn request_layer_preview(&self, node_id: u64, max_size: f64) {
+		let message = PortfolioMessage::RequestLayerPreview {
+			layer_node: NodeId(node_id),
+			max_size,
+		};
+		self.dispatch(message);
+	}
+
 	/// Toggle expansions state of a layer from the layer list
 	#[wasm_bindgen(js_name = toggleLayerExpansion)]
 	pub fn toggle_layer_expansion(&self, id: u64, recursive: bool) {
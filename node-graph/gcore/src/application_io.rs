@@ -223,6 +223,17 @@ pub enum ApplicationError {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum NodeGraphUpdateMessage {
 	// ImaginateStatusUpdate,
+	/// An external resource was loaded during an execution, along with the hash of its content, or `None` if it couldn't be loaded, so the runtime can watch it for changes.
+	ExternalResourceLoaded { url: String, content_hash: Option<u64> },
+	/// Some of the external resources loaded by the graph changed since they were last loaded, so the nodes depending on them were invalidated and need to be evaluated again.
+	ExternalResourcesChanged { urls: Vec<String> },
+}
+
+/// Hashes the content of an external resource, to tell whether it changed since it was last loaded.
+pub fn resource_content_hash(data: &[u8]) -> u64 {
+	let mut hasher = std::collections::hash_map::DefaultHasher::new();
+	data.hash(&mut hasher);
+	hasher.finish()
 }
 
 pub trait NodeGraphUpdateSender {
@@ -257,6 +268,14 @@ pub struct TimingInformation {
 	pub animation_time: Duration,
 }
 
+/// Whether a render is shown only briefly while the user interacts with the document, so it may trade quality for speed, or is the final result.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, DynAny, serde::Serialize, serde::Deserialize)]
+pub enum RenderQualityHint {
+	#[default]
+	Final,
+	Interactive,
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, DynAny, serde::Serialize, serde::Deserialize)]
 pub struct RenderConfig {
 	pub viewport: Footprint,
@@ -265,6 +284,14 @@ pub struct RenderConfig {
 	pub view_mode: ViewMode,
 	pub hide_artboards: bool,
 	pub for_export: bool,
+	/// Whether to collect subpath-level click regions in the render metadata, which is only needed by the Path tool.
+	pub subpath_click_regions: bool,
+	/// Whether strokes should be rendered as filled outlines, which is an export option.
+	pub expand_strokes: bool,
+	/// Whether the runtime should time each layer's evaluation and overlay the artwork with a heatmap of those costs, for debugging.
+	pub cost_heatmap: bool,
+	/// Interactive renders evaluate the graph at reduced resolution, to be followed by a final quality render once the interaction pauses.
+	pub quality: RenderQualityHint,
 }
 
 struct Logger;
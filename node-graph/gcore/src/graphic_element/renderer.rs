@@ -5,7 +5,8 @@ use crate::raster::image::ImageFrameTable;
 use crate::raster::{BlendMode, Image};
 use crate::transform::{Footprint, Transform};
 use crate::uuid::{NodeId, generate_uuid};
-use crate::vector::style::{Fill, Stroke, ViewMode};
+use crate::vector::algorithms::bezpath_algorithms::stroke_outline;
+use crate::vector::style::{Fill, Gradient, GradientStops, Stroke, ViewMode};
 use crate::vector::{PointId, VectorDataTable};
 use crate::{Artboard, ArtboardGroupTable, Color, GraphicElement, GraphicGroupTable, RasterFrame};
 use base64::Engine;
@@ -226,6 +227,8 @@ pub struct RenderParams {
 	pub hide_artboards: bool,
 	/// Are we exporting? Causes the text above an artboard to be hidden.
 	pub for_export: bool,
+	/// Render strokes as filled outlines of the area they cover, so the output doesn't depend on how other software draws strokes.
+	pub expand_strokes: bool,
 }
 
 impl RenderParams {
@@ -236,6 +239,7 @@ impl RenderParams {
 			thumbnail,
 			hide_artboards,
 			for_export,
+			expand_strokes: false,
 		}
 	}
 }
@@ -267,6 +271,19 @@ pub struct RenderMetadata {
 	pub local_transforms: HashMap<NodeId, DAffine2>,
 	pub click_targets: HashMap<NodeId, Vec<ClickTarget>>,
 	pub clip_targets: HashSet<NodeId>,
+	/// The transforms of the copies of a layer's content made by an instancing node (such as Repeat), relative to the layer's own geometry.
+	pub instance_transforms: HashMap<NodeId, Vec<DAffine2>>,
+	/// The subpath-level regions of each layer's click targets, only collected when requested by the [`crate::application_io::RenderConfig`] (`None` otherwise).
+	pub subpath_click_regions: Option<HashMap<NodeId, Vec<SubpathClickRegion>>>,
+}
+
+/// Identifies which part of a layer's vector data a single subpath click target was generated from.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SubpathClickRegion {
+	/// The index of the click target in the layer's list of click targets.
+	pub click_target_index: usize,
+	/// The ranges of indices into the segment domain which make up the subpath.
+	pub segment_ranges: Vec<core::ops::Range<usize>>,
 }
 
 // TODO: Rename to "Graphical"
@@ -440,6 +457,59 @@ impl GraphicElementRendered for VectorDataTable {
 				let _ = subpath.subpath_to_svg(&mut path, applied_stroke_transform);
 			}
 
+			let visible_stroke = has_real_stroke.filter(|stroke| stroke.color.is_some_and(|color| color.a() > 0.));
+			if let Some(stroke) = visible_stroke.filter(|_| render_params.expand_strokes && render_params.view_mode != ViewMode::Outline) {
+				// Non-scaling strokes keep their weight in the rendered space, so they are outlined there instead of before the element's transform
+				let (outline_transform, outline_element_transform) = if stroke.non_scaling {
+					(multiplied_transform, DAffine2::IDENTITY)
+				} else {
+					(applied_stroke_transform, element_transform)
+				};
+				let mut outline = kurbo::BezPath::new();
+				for mut bezpath in instance.instance.stroke_bezpath_iter() {
+					bezpath.apply_affine(kurbo::Affine::new(outline_transform.to_cols_array()));
+					outline.extend(stroke_outline(&bezpath, stroke));
+				}
+
+				let opacity = instance.alpha_blending.opacity;
+				let blend_mode = instance.alpha_blending.blend_mode;
+				let fill = instance.instance.style.fill();
+				render.parent_tag(
+					"g",
+					|attributes| {
+						if opacity < 1. {
+							attributes.push("opacity", opacity.to_string());
+						}
+						if blend_mode != BlendMode::default() {
+							attributes.push("style", blend_mode.render());
+						}
+					},
+					|render| {
+						if *fill != Fill::None {
+							render.leaf_tag("path", |attributes| {
+								attributes.push("d", path);
+								let matrix = format_transform_matrix(element_transform);
+								if !matrix.is_empty() {
+									attributes.push("transform", matrix);
+								}
+								let fill_attribute = fill.render(&mut attributes.0.svg_defs, element_transform, applied_stroke_transform, layer_bounds, transformed_bounds);
+								attributes.push_val(fill_attribute);
+							});
+						}
+						render.leaf_tag("path", |attributes| {
+							attributes.push("d", outline.to_svg());
+							let matrix = format_transform_matrix(outline_element_transform);
+							if !matrix.is_empty() {
+								attributes.push("transform", matrix);
+							}
+							let stroke_fill = Fill::solid_or_none(stroke.color).render(&mut attributes.0.svg_defs, element_transform, applied_stroke_transform, layer_bounds, transformed_bounds);
+							attributes.push_val(stroke_fill);
+						});
+					},
+				);
+				continue;
+			}
+
 			render.leaf_tag("path", |attributes| {
 				attributes.push("d", path);
 				let matrix = format_transform_matrix(element_transform);
@@ -659,6 +729,16 @@ impl GraphicElementRendered for VectorDataTable {
 					.collect::<Vec<ClickTarget>>();
 
 				metadata.click_targets.insert(element_id, click_targets);
+
+				if let Some(subpath_click_regions) = &mut metadata.subpath_click_regions {
+					let regions = instance
+						.stroke_subpath_segment_ranges()
+						.enumerate()
+						.map(|(click_target_index, segment_ranges)| SubpathClickRegion { click_target_index, segment_ranges })
+						.collect();
+
+					subpath_click_regions.insert(element_id, regions);
+				}
 			}
 
 			if let Some(upstream_graphic_group) = &instance.upstream_graphic_group {
@@ -1045,6 +1125,36 @@ impl GraphicElementRendered for GraphicElement {
 			GraphicElement::RasterFrame(raster) => raster.collect_metadata(metadata, footprint, element_id),
 			GraphicElement::GraphicGroup(graphic_group) => graphic_group.collect_metadata(metadata, footprint, element_id),
 		}
+
+		// Copies which don't come from a layer of their own were made by an instancing node (such as Repeat) from the layer's geometry,
+		// so the layer is described in the space of its first copy, with the other copies placed relative to it
+		if let (Some(element_id), GraphicElement::GraphicGroup(graphic_group)) = (element_id, self) {
+			let instanced = graphic_group.len() > 1 && graphic_group.instance_ref_iter().all(|instance| instance.source_node_id.is_none());
+			let copy_transforms = graphic_group
+				.instance_ref_iter()
+				.filter(|_| instanced)
+				.map(|instance| {
+					let element_transform = match instance.instance {
+						GraphicElement::VectorData(vector_data) => vector_data.transform(),
+						GraphicElement::RasterFrame(raster_frame) => raster_frame.transform(),
+						GraphicElement::GraphicGroup(_) => DAffine2::IDENTITY,
+					};
+					*instance.transform * element_transform
+				})
+				.collect::<Vec<_>>();
+
+			if let Some(&source_transform) = copy_transforms.first().filter(|transform| transform.matrix2.determinant() != 0.) {
+				let to_source = source_transform.inverse();
+
+				metadata.local_transforms.insert(element_id, source_transform);
+				metadata
+					.instance_transforms
+					.insert(element_id, copy_transforms.iter().map(|&transform| to_source * transform).collect());
+				for click_target in metadata.click_targets.get_mut(&element_id).into_iter().flatten() {
+					click_target.apply_transform(to_source);
+				}
+			}
+		}
 	}
 
 	fn add_upstream_click_targets(&self, click_targets: &mut Vec<ClickTarget>) {
@@ -1078,6 +1188,8 @@ impl Primitive for String {}
 impl Primitive for bool {}
 impl Primitive for f32 {}
 impl Primitive for f64 {}
+impl Primitive for u32 {}
+impl Primitive for u64 {}
 impl Primitive for DVec2 {}
 
 fn text_attributes(attributes: &mut SvgRenderAttrs) {
@@ -1151,6 +1263,68 @@ impl GraphicElementRendered for Vec<Color> {
 	fn render_to_vello(&self, _scene: &mut Scene, _transform: DAffine2, _context: &mut RenderContext, _render_params: &RenderParams) {}
 }
 
+impl GraphicElementRendered for Vec<DVec2> {
+	fn render_svg(&self, render: &mut SvgRender, _render_params: &RenderParams) {
+		for point in self {
+			render.leaf_tag("circle", |attributes| {
+				attributes.push("cx", point.x.to_string());
+				attributes.push("cy", point.y.to_string());
+				attributes.push("r", "4");
+				attributes.push("fill", "white");
+			});
+		}
+		render.parent_tag("text", text_attributes, |render| render.leaf_node(format!("{} points", self.len())));
+	}
+
+	fn bounding_box(&self, _transform: DAffine2, _include_stroke: bool) -> Option<[DVec2; 2]> {
+		None
+	}
+
+	#[cfg(feature = "vello")]
+	fn render_to_vello(&self, _scene: &mut Scene, _transform: DAffine2, _context: &mut RenderContext, _render_params: &RenderParams) {}
+}
+
+impl GraphicElementRendered for GradientStops {
+	fn render_svg(&self, render: &mut SvgRender, _render_params: &RenderParams) {
+		let gradient_id = generate_uuid();
+		let _ = write!(render.svg_defs, r#"<linearGradient id="{gradient_id}">{}</linearGradient>"#, self.render_svg_stops());
+
+		render.leaf_tag("rect", |attributes| {
+			attributes.push("width", "400");
+			attributes.push("height", "100");
+			attributes.push("y", "40");
+			attributes.push("fill", format!("url('#{gradient_id}')"));
+		});
+
+		let stops_info = self
+			.iter()
+			.map(|(position, color)| format!("{}%: #{}", (position * 100.).round(), color.to_rgba_hex_srgb()))
+			.collect::<Vec<_>>()
+			.join(", ");
+		render.parent_tag("text", text_attributes, |render| render.leaf_node(stops_info));
+	}
+
+	fn bounding_box(&self, _transform: DAffine2, _include_stroke: bool) -> Option<[DVec2; 2]> {
+		None
+	}
+
+	#[cfg(feature = "vello")]
+	fn render_to_vello(&self, _scene: &mut Scene, _transform: DAffine2, _context: &mut RenderContext, _render_params: &RenderParams) {}
+}
+
+impl GraphicElementRendered for Gradient {
+	fn render_svg(&self, render: &mut SvgRender, render_params: &RenderParams) {
+		self.stops.render_svg(render, render_params);
+	}
+
+	fn bounding_box(&self, _transform: DAffine2, _include_stroke: bool) -> Option<[DVec2; 2]> {
+		None
+	}
+
+	#[cfg(feature = "vello")]
+	fn render_to_vello(&self, _scene: &mut Scene, _transform: DAffine2, _context: &mut RenderContext, _render_params: &RenderParams) {}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SvgSegment {
 	Slice(&'static str),
@@ -3,9 +3,112 @@ use crate::{Node, WasmNotSend};
 use alloc::sync::Arc;
 use core::future::Future;
 use core::ops::Deref;
+use core::pin::Pin;
+use core::task::{Context, Poll};
 use dyn_any::DynFuture;
+use std::cell::RefCell;
 use std::hash::DefaultHasher;
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counts the node evaluations and the cache lookups of memoizing nodes made while a future is polled within [`EvaluationCounter::scope`].
+/// Each executor owns its own counter, so the counts of one evaluation aren't mixed with those of another executor evaluating at the same time.
+#[derive(Clone, Debug, Default)]
+pub struct EvaluationCounter(Arc<EvaluationCounterInner>);
+
+#[derive(Debug, Default)]
+struct EvaluationCounterInner {
+	evaluations: AtomicU64,
+	cache_hits: AtomicU64,
+	cache_misses: AtomicU64,
+}
+
+/// A snapshot of the counts of an [`EvaluationCounter`], or the difference between two snapshots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EvaluationCounts {
+	/// How many times a node was evaluated.
+	pub evaluations: u64,
+	/// How many times a memoizing node returned its cached output.
+	pub cache_hits: u64,
+	/// How many times a memoizing node had to evaluate its input because nothing was cached for it.
+	pub cache_misses: u64,
+}
+
+impl EvaluationCounts {
+	/// The counts made after `earlier` was taken from the same counter.
+	pub fn since(self, earlier: Self) -> Self {
+		Self {
+			evaluations: self.evaluations - earlier.evaluations,
+			cache_hits: self.cache_hits - earlier.cache_hits,
+			cache_misses: self.cache_misses - earlier.cache_misses,
+		}
+	}
+}
+
+thread_local! {
+	/// The counter of the evaluation being polled on this thread, if any.
+	static CURRENT_COUNTER: RefCell<Option<EvaluationCounter>> = const { RefCell::new(None) };
+}
+
+impl EvaluationCounter {
+	pub fn counts(&self) -> EvaluationCounts {
+		EvaluationCounts {
+			evaluations: self.0.evaluations.load(Ordering::Relaxed),
+			cache_hits: self.0.cache_hits.load(Ordering::Relaxed),
+			cache_misses: self.0.cache_misses.load(Ordering::Relaxed),
+		}
+	}
+
+	/// Counts the evaluations and cache lookups made while `future` is polled with this counter.
+	pub fn scope<F: Future>(&self, future: F) -> CountedFuture<F> {
+		CountedFuture {
+			counter: self.clone(),
+			future: Box::pin(future),
+		}
+	}
+
+	/// The counter of the evaluation being polled, if any.
+	fn current() -> Option<Self> {
+		CURRENT_COUNTER.with(|current| current.borrow().clone())
+	}
+
+	fn record(count: impl FnOnce(&EvaluationCounterInner) -> &AtomicU64) {
+		CURRENT_COUNTER.with(|current| {
+			if let Some(counter) = current.borrow().as_ref() {
+				count(&counter.0).fetch_add(1, Ordering::Relaxed);
+			}
+		});
+	}
+
+	/// Counts a node evaluation towards the evaluation being polled.
+	pub fn record_evaluation() {
+		Self::record(|counter| &counter.evaluations);
+	}
+}
+
+/// A future polled with an [`EvaluationCounter`], created by [`EvaluationCounter::scope`].
+pub struct CountedFuture<F> {
+	counter: EvaluationCounter,
+	future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CountedFuture<F> {
+	type Output = F::Output;
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		/// Puts back the counter which was current before, even if polling panics.
+		struct RestoreCounter(Option<EvaluationCounter>);
+		impl Drop for RestoreCounter {
+			fn drop(&mut self) {
+				CURRENT_COUNTER.with(|current| *current.borrow_mut() = self.0.take());
+			}
+		}
+
+		let counter = self.counter.clone();
+		let _restore = RestoreCounter(CURRENT_COUNTER.with(|current| current.replace(Some(counter))));
+		self.future.as_mut().poll(cx)
+	}
+}
 
 /// Caches the output of a given Node and acts as a proxy
 #[derive(Default)]
@@ -27,8 +130,10 @@ where
 		let hash = hasher.finish();
 
 		if let Some(data) = self.cache.lock().as_ref().unwrap().as_ref().and_then(|data| (data.0 == hash).then_some(data.1.clone())) {
+			EvaluationCounter::record(|counter| &counter.cache_hits);
 			Box::pin(async move { data })
 		} else {
+			EvaluationCounter::record(|counter| &counter.cache_misses);
 			let fut = self.node.eval(input);
 			let cache = self.cache.clone();
 			Box::pin(async move {
@@ -72,8 +177,10 @@ where
 	fn eval(&'i self, input: I) -> Self::Output {
 		if let Some(cached_value) = self.cache.lock().as_ref().unwrap().deref() {
 			let data = cached_value.clone();
+			EvaluationCounter::record(|counter| &counter.cache_hits);
 			Box::pin(async move { data })
 		} else {
+			EvaluationCounter::record(|counter| &counter.cache_misses);
 			let fut = self.node.eval(input);
 			let cache = self.cache.clone();
 			Box::pin(async move {
@@ -104,6 +211,24 @@ impl<T, I, CachedNode> ImpureMemoNode<I, T, CachedNode> {
 pub struct IORecord<I, O> {
 	pub input: I,
 	pub output: O,
+	/// How long it took to produce the output in milliseconds, if an [evaluation clock](set_evaluation_clock) was set while it was evaluated.
+	pub elapsed_ms: Option<f64>,
+	/// Identifies the output, staying the same while it's known to be unchanged from the previous evaluation's.
+	/// A new revision, unique across all monitor nodes, is given on a monitor's first evaluation, whenever a memoizing node missed its cache while its input was evaluated,
+	/// and whenever it's evaluated without an [`EvaluationCounter`] to tell.
+	pub revision: u64,
+}
+
+/// The source of the revisions given to the outputs recorded by [`MonitorNode`]s.
+/// Revisions only need to be unique, so unlike the cache misses they can be drawn from a single source shared by all executors.
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
+/// The clock [`MonitorNode`]s read to measure how long their input takes to evaluate, returning milliseconds from an arbitrary fixed point.
+static EVALUATION_CLOCK: std::sync::RwLock<Option<fn() -> f64>> = std::sync::RwLock::new(None);
+
+/// Sets the clock used by [`MonitorNode`]s to time the evaluation of their input, or stops timing if `None`.
+pub fn set_evaluation_clock(clock: Option<fn() -> f64>) {
+	*EVALUATION_CLOCK.write().unwrap() = clock;
 }
 
 #[cfg(feature = "alloc")]
@@ -125,10 +250,26 @@ where
 	type Output = DynFuture<'i, T>;
 	fn eval(&'i self, input: I) -> Self::Output {
 		let io = self.io.clone();
+		let clock = *EVALUATION_CLOCK.read().unwrap();
+		let start = clock.map(|now| now());
+		let counter = EvaluationCounter::current();
+		let cache_misses = counter.as_ref().map(|counter| counter.counts().cache_misses);
 		let output_fut = self.node.eval(input.clone());
 		Box::pin(async move {
 			let output = output_fut.await;
-			*io.lock().unwrap() = Some(Arc::new(IORecord { input, output: output.clone() }));
+			let elapsed_ms = clock.zip(start).map(|(now, start)| now() - start);
+			let recomputed = cache_misses.is_none() || counter.map(|counter| counter.counts().cache_misses) != cache_misses;
+			let mut io = io.lock().unwrap();
+			let revision = match io.as_ref() {
+				Some(previous) if !recomputed => previous.revision,
+				_ => NEXT_REVISION.fetch_add(1, Ordering::Relaxed),
+			};
+			*io = Some(Arc::new(IORecord {
+				input,
+				output: output.clone(),
+				elapsed_ms,
+				revision,
+			}));
 			output
 		})
 	}
@@ -243,3 +384,71 @@ impl<T: Hash> core::ops::DerefMut for MemoHashGuard<'_, T> {
 		&mut self.inner.value
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[derive(Clone)]
+	pub struct FutureWrapperNode<T: Clone>(T);
+
+	impl<'i, T: 'i + Clone + Send> Node<'i, ()> for FutureWrapperNode<T> {
+		type Output = Pin<Box<dyn Future<Output = T> + 'i + Send>>;
+		fn eval(&'i self, _input: ()) -> Self::Output {
+			let value = self.0.clone();
+			Box::pin(async move { value })
+		}
+	}
+
+	fn revision<T: Clone + Send + Sync + 'static, N>(monitor: &MonitorNode<(), T, N>) -> u64 {
+		monitor.io.lock().unwrap().as_ref().unwrap().revision
+	}
+
+	#[tokio::test]
+	async fn memo_nodes_count_their_cache_lookups_only_within_a_scope() {
+		let memo = MemoNode::<u32, _>::new(FutureWrapperNode(7));
+		let counter = EvaluationCounter::default();
+
+		assert_eq!(counter.scope(async { memo.eval(()).await }).await, 7);
+		assert_eq!(counter.scope(async { memo.eval(()).await }).await, 7);
+		assert_eq!(memo.eval(()).await, 7);
+
+		let counts = counter.counts();
+		assert_eq!(counts.cache_misses, 1);
+		assert_eq!(counts.cache_hits, 1);
+	}
+
+	#[tokio::test]
+	async fn each_counter_only_counts_its_own_scopes() {
+		let first = EvaluationCounter::default();
+		let second = EvaluationCounter::default();
+		let memo = ImpureMemoNode::<(), u32, _>::new(FutureWrapperNode(7));
+
+		first.scope(async { memo.eval(()).await }).await;
+		let before = second.counts();
+		second.scope(async { memo.eval(()).await }).await;
+
+		assert_eq!((first.counts().cache_hits, first.counts().cache_misses), (0, 1));
+		let second_counts = second.counts().since(before);
+		assert_eq!((second_counts.cache_hits, second_counts.cache_misses), (1, 0));
+	}
+
+	#[tokio::test]
+	async fn monitor_nodes_keep_their_revision_until_a_memo_node_upstream_misses_its_cache() {
+		let monitor = MonitorNode::<(), u32, _>::new(MemoNode::<u32, _>::new(FutureWrapperNode(7)));
+		let counter = EvaluationCounter::default();
+
+		counter.scope(async { monitor.eval(()).await }).await;
+		let first = revision(&monitor);
+		counter.scope(async { monitor.eval(()).await }).await;
+		assert_eq!(revision(&monitor), first, "Nothing was recomputed, so the output is unchanged");
+
+		monitor.node.reset();
+		counter.scope(async { monitor.eval(()).await }).await;
+		let recomputed = revision(&monitor);
+		assert_ne!(recomputed, first, "The memo node missed its cache, so the output may have changed");
+
+		monitor.eval(()).await;
+		assert_ne!(revision(&monitor), recomputed, "Without a counter, the output can't be known to be unchanged");
+	}
+}
@@ -1,5 +1,6 @@
 use super::poisson_disk::poisson_disk_sample;
 use crate::vector::misc::dvec2_to_point;
+use crate::vector::style::{LineCap, LineJoin, Stroke};
 use glam::DVec2;
 use kurbo::{Affine, BezPath, Line, ParamCurve, ParamCurveDeriv, PathSeg, Point, Rect, Shape};
 
@@ -7,6 +8,8 @@ use kurbo::{Affine, BezPath, Line, ParamCurve, ParamCurveDeriv, PathSeg, Point,
 const POSITION_ACCURACY: f64 = 1e-5;
 /// Accuracy to find the length of the [kurbo::PathSeg].
 pub const PERIMETER_ACCURACY: f64 = 1e-5;
+/// Accuracy of the outline generated by [stroke_outline]. 0.25 is balanced between performace and accuracy of the curve.
+pub const STROKE_OUTLINE_TOLERANCE: f64 = 0.25;
 
 pub fn position_on_bezpath(bezpath: &BezPath, t: f64, euclidian: bool, segments_length: Option<&[f64]>) -> Point {
 	let (segment_index, t) = t_value_to_parametric(bezpath, t, euclidian, segments_length);
@@ -272,3 +275,68 @@ fn bezpath_rectangle_intersections_exist(bezpath: &BezPath, rect: Rect) -> bool
 
 	false
 }
+
+/// The outline of the area covered by drawing the stroke along the path, with its joins, caps, dashes, and miter limit, which can be filled to look like the stroke.
+pub fn stroke_outline(bezpath: &BezPath, stroke: &Stroke) -> BezPath {
+	let join = match stroke.line_join {
+		LineJoin::Miter => kurbo::Join::Miter,
+		LineJoin::Bevel => kurbo::Join::Bevel,
+		LineJoin::Round => kurbo::Join::Round,
+	};
+	let cap = match stroke.line_cap {
+		LineCap::Butt => kurbo::Cap::Butt,
+		LineCap::Round => kurbo::Cap::Round,
+		LineCap::Square => kurbo::Cap::Square,
+	};
+
+	let stroke_style = kurbo::Stroke::new(stroke.weight)
+		.with_caps(cap)
+		.with_join(join)
+		.with_dashes(stroke.dash_offset, stroke.dash_lengths.iter().copied())
+		.with_miter_limit(stroke.line_join_miter_limit);
+
+	kurbo::stroke(bezpath.iter(), &stroke_style, &kurbo::StrokeOpts::default(), STROKE_OUTLINE_TOLERANCE)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn outline_bounds(bezpath: &BezPath, stroke: &Stroke) -> Rect {
+		stroke_outline(bezpath, stroke).bounding_box()
+	}
+
+	#[test]
+	fn stroke_outline_covers_joins_and_caps() {
+		let square = Rect::new(0., 0., 100., 100.).to_path(0.);
+		let stroke = Stroke::new(Some(crate::Color::BLACK), 10.);
+
+		// Miter joins reach the corners of the stroked square
+		let bounds = outline_bounds(&square, &stroke);
+		assert!(
+			(bounds.x0 + 5.).abs() < 1e-6 && (bounds.y0 + 5.).abs() < 1e-6 && (bounds.x1 - 105.).abs() < 1e-6 && (bounds.y1 - 105.).abs() < 1e-6,
+			"Unexpected bounds {bounds:?}"
+		);
+
+		// Rounded joins don't, but the edges are still offset by half the weight
+		let round = Stroke {
+			line_join: LineJoin::Round,
+			..stroke.clone()
+		};
+		let bounds = outline_bounds(&square, &round);
+		assert!((bounds.x0 + 5.).abs() < 0.5 && (bounds.x1 - 105.).abs() < 0.5, "Unexpected bounds {bounds:?}");
+		let corner = Point::new(-5., -5.);
+		assert!(!stroke_outline(&square, &round).contains(corner), "The rounded corner shouldn't cover the miter's tip");
+
+		// Square caps extend an open line along its direction, butt caps don't
+		let mut line = BezPath::new();
+		line.move_to((0., 0.));
+		line.line_to((100., 0.));
+		let square_cap = Stroke {
+			line_cap: LineCap::Square,
+			..stroke.clone()
+		};
+		assert!((outline_bounds(&line, &square_cap).x0 + 5.).abs() < 1e-6);
+		assert!(outline_bounds(&line, &stroke).x0.abs() < 1e-6);
+	}
+}
@@ -1,5 +1,6 @@
 use crate::vector::PointId;
-use bezier_rs::{Bezier, BezierHandles, Join, Subpath, TValue};
+use bezier_rs::{Bezier, BezierHandles, Join, ManipulatorGroup, Subpath, TValue};
+use glam::DVec2;
 
 /// Value to control smoothness and mathematical accuracy to offset a cubic Bezier.
 const CUBIC_REGULARIZATION_ACCURACY: f64 = 0.5;
@@ -7,6 +8,16 @@ const CUBIC_REGULARIZATION_ACCURACY: f64 = 0.5;
 const CUBIC_TO_BEZPATH_ACCURACY: f64 = 1e-3;
 /// Constant used to determine if `f64`s are equivalent.
 pub const MAX_ABSOLUTE_DIFFERENCE: f64 = 1e-3;
+/// Approximate length of each line segment when flattening curves into a polyline.
+const POLYLINE_FLATTEN_STEP: f64 = 2.;
+/// Maximum number of line segments each Bezier segment is flattened into.
+const POLYLINE_MAX_STEPS_PER_SEGMENT: usize = 64;
+/// Limit on the length of a mitered corner, as a multiple of the offset distance, which stops sharp corners from producing long spikes.
+const POLYLINE_MITER_LIMIT: f64 = 4.;
+/// Maximum distance the re-fitted curve may deviate from the offset polyline when dropping redundant points.
+const POLYLINE_FIT_TOLERANCE: f64 = 0.25;
+/// Turning angle (in radians) above which a polyline vertex is kept as a sharp corner instead of being smoothed.
+const POLYLINE_CORNER_ANGLE: f64 = std::f64::consts::FRAC_PI_6;
 
 fn segment_to_bezier(seg: kurbo::PathSeg) -> bezier_rs::Bezier {
 	match seg {
@@ -171,3 +182,273 @@ pub fn offset_subpath(subpath: &Subpath<PointId>, distance: f64, join: Join) ->
 
 	Subpath::new(manipulator_groups, subpath.closed)
 }
+
+/// A fast approximation of [`offset_subpath`] intended for interactive use, such as live previews.
+/// The subpath is flattened into a polyline, each vertex is moved along the averaged normal of its neighboring edges, and Bezier curves are re-fitted to the result.
+/// For closed subpaths, a positive `distance` grows the shape outward regardless of its winding direction.
+pub fn offset_subpath_polyline(subpath: &Subpath<PointId>, distance: f64) -> Subpath<PointId> {
+	if distance == 0. || subpath.len_segments() < 1 {
+		return subpath.clone();
+	}
+
+	let polyline = flatten_subpath(subpath);
+	if polyline.len() < 2 {
+		return subpath.clone();
+	}
+
+	let offset = offset_polyline(&polyline, subpath.closed, distance);
+	fit_polyline(&offset, subpath.closed)
+}
+
+/// Samples the segments of a subpath into a polyline without repeated consecutive points. Closed subpaths don't repeat their first point at the end.
+pub fn flatten_subpath(subpath: &Subpath<PointId>) -> Vec<DVec2> {
+	let mut points: Vec<DVec2> = Vec::new();
+
+	for bezier in subpath.iter() {
+		let steps = match bezier.handles {
+			BezierHandles::Linear => 1,
+			_ => ((bezier.length(None) / POLYLINE_FLATTEN_STEP).ceil() as usize).clamp(1, POLYLINE_MAX_STEPS_PER_SEGMENT),
+		};
+
+		for step in 0..=steps {
+			let point = bezier.evaluate(TValue::Parametric(step as f64 / steps as f64));
+			if !points.last().is_some_and(|last| last.abs_diff_eq(point, MAX_ABSOLUTE_DIFFERENCE)) {
+				points.push(point);
+			}
+		}
+	}
+
+	if subpath.closed && points.len() > 1 && points[0].abs_diff_eq(points[points.len() - 1], MAX_ABSOLUTE_DIFFERENCE) {
+		points.pop();
+	}
+
+	points
+}
+
+/// Moves each vertex of a polyline `distance` away along the averaged normal of its neighboring edges, mitering the corners up to [`POLYLINE_MITER_LIMIT`].
+/// Open polylines are offset to the left of their direction of travel (in a Y-up coordinate system), and closed polylines are offset outward.
+pub fn offset_polyline(points: &[DVec2], closed: bool, distance: f64) -> Vec<DVec2> {
+	let count = points.len();
+
+	// The left normal points inward for a counterclockwise polygon, so flip it to always grow closed shapes for positive distances
+	let signed_area: f64 = (0..count).map(|index| points[index].perp_dot(points[(index + 1) % count])).sum();
+	let sign = if closed && signed_area > 0. { -1. } else { 1. };
+	let edge_normal = |start: DVec2, end: DVec2| (end - start).normalize_or_zero().perp() * sign;
+
+	(0..count)
+		.map(|index| {
+			let point = points[index];
+			let previous = if index > 0 { Some(points[index - 1]) } else { closed.then(|| points[count - 1]) };
+			let next = if index + 1 < count { Some(points[index + 1]) } else { closed.then(|| points[0]) };
+
+			let normal_in = previous.map(|previous| edge_normal(previous, point));
+			let normal_out = next.map(|next| edge_normal(point, next));
+
+			let offset = match (normal_in, normal_out) {
+				(Some(normal_in), Some(normal_out)) => {
+					let bisector = (normal_in + normal_out).normalize_or_zero();
+					if bisector == DVec2::ZERO {
+						// The path doubles back on itself, so there is no meaningful miter
+						normal_in * distance
+					} else {
+						let cos = bisector.dot(normal_in).max(1. / POLYLINE_MITER_LIMIT);
+						bisector * distance / cos
+					}
+				}
+				(Some(normal), None) | (None, Some(normal)) => normal * distance,
+				(None, None) => DVec2::ZERO,
+			};
+
+			point + offset
+		})
+		.collect()
+}
+
+/// Re-fits a polyline with Bezier curves by dropping points which are within [`POLYLINE_FIT_TOLERANCE`] of the simplified path
+/// and then smoothing the remaining vertices, except for those which form a corner sharper than [`POLYLINE_CORNER_ANGLE`].
+pub fn fit_polyline(points: &[DVec2], closed: bool) -> Subpath<PointId> {
+	let points = simplify_polyline(points, closed, POLYLINE_FIT_TOLERANCE);
+	let count = points.len();
+	if count < 3 {
+		return Subpath::from_anchors_linear(points, closed);
+	}
+
+	let manipulator_groups = (0..count)
+		.map(|index| {
+			let point = points[index];
+			let previous = if index > 0 { Some(points[index - 1]) } else { closed.then(|| points[count - 1]) };
+			let next = if index + 1 < count { Some(points[index + 1]) } else { closed.then(|| points[0]) };
+
+			let (Some(previous), Some(next)) = (previous, next) else {
+				return ManipulatorGroup::new_anchor_linear(point);
+			};
+
+			let turning_angle = (point - previous).angle_to(next - point).abs();
+			if turning_angle > POLYLINE_CORNER_ANGLE {
+				return ManipulatorGroup::new_anchor_linear(point);
+			}
+
+			let tangent = (next - previous).normalize_or_zero();
+			let in_handle = point - tangent * point.distance(previous) / 3.;
+			let out_handle = point + tangent * point.distance(next) / 3.;
+			ManipulatorGroup::new(point, Some(in_handle), Some(out_handle))
+		})
+		.collect();
+
+	Subpath::new(manipulator_groups, closed)
+}
+
+/// Removes polyline points which deviate from the simplified path by less than `tolerance`, using the Ramer-Douglas-Peucker algorithm.
+fn simplify_polyline(points: &[DVec2], closed: bool, tolerance: f64) -> Vec<DVec2> {
+	if points.len() < 3 {
+		return points.to_vec();
+	}
+
+	// A closed polyline is simplified as an open one which starts and ends at the same point, which is then dropped again afterwards
+	let mut open_points = points.to_vec();
+	if closed {
+		open_points.push(points[0]);
+	}
+
+	let mut keep = vec![false; open_points.len()];
+	keep[0] = true;
+	keep[open_points.len() - 1] = true;
+
+	let mut stack = vec![(0, open_points.len() - 1)];
+	while let Some((start, end)) = stack.pop() {
+		if end <= start + 1 {
+			continue;
+		}
+
+		let (farthest_index, farthest_distance) = (start + 1..end)
+			.map(|index| (index, distance_to_segment(open_points[index], open_points[start], open_points[end])))
+			.fold((start, 0.), |best, current| if current.1 > best.1 { current } else { best });
+
+		if farthest_distance > tolerance {
+			keep[farthest_index] = true;
+			stack.push((start, farthest_index));
+			stack.push((farthest_index, end));
+		}
+	}
+
+	let mut simplified: Vec<DVec2> = open_points.into_iter().zip(keep).filter_map(|(point, keep)| keep.then_some(point)).collect();
+	if closed {
+		simplified.pop();
+	}
+	simplified
+}
+
+fn distance_to_segment(point: DVec2, start: DVec2, end: DVec2) -> f64 {
+	let segment = end - start;
+	let length_squared = segment.length_squared();
+	if length_squared == 0. {
+		return point.distance(start);
+	}
+
+	let t = ((point - start).dot(segment) / length_squared).clamp(0., 1.);
+	point.distance(start + segment * t)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn bounds(subpath: &Subpath<PointId>) -> [DVec2; 2] {
+		subpath.bounding_box().expect("The offset subpath should not be empty")
+	}
+
+	#[test]
+	fn offset_polyline_convex() {
+		let square = Subpath::new_rect(DVec2::ZERO, DVec2::splat(100.));
+
+		let grown = offset_subpath_polyline(&square, 10.);
+		let [min, max] = bounds(&grown);
+		assert!(min.abs_diff_eq(DVec2::splat(-10.), 1e-6), "Grown square has the wrong minimum: {min}");
+		assert!(max.abs_diff_eq(DVec2::splat(110.), 1e-6), "Grown square has the wrong maximum: {max}");
+		assert!(grown.closed);
+		assert_eq!(grown.len(), 4);
+
+		let shrunk = offset_subpath_polyline(&square, -10.);
+		let [min, max] = bounds(&shrunk);
+		assert!(min.abs_diff_eq(DVec2::splat(10.), 1e-6), "Shrunk square has the wrong minimum: {min}");
+		assert!(max.abs_diff_eq(DVec2::splat(90.), 1e-6), "Shrunk square has the wrong maximum: {max}");
+	}
+
+	#[test]
+	fn offset_polyline_ignores_winding_direction() {
+		let anchors = [DVec2::ZERO, DVec2::new(0., 100.), DVec2::splat(100.), DVec2::new(100., 0.)];
+		let reversed_square = Subpath::from_anchors_linear(anchors, true);
+
+		let [min, max] = bounds(&offset_subpath_polyline(&reversed_square, 10.));
+		assert!(min.abs_diff_eq(DVec2::splat(-10.), 1e-6));
+		assert!(max.abs_diff_eq(DVec2::splat(110.), 1e-6));
+	}
+
+	#[test]
+	fn offset_polyline_concave() {
+		// An L shape, with a concave corner at (50, 50)
+		let anchors = [
+			DVec2::ZERO,
+			DVec2::new(100., 0.),
+			DVec2::new(100., 50.),
+			DVec2::new(50., 50.),
+			DVec2::new(50., 100.),
+			DVec2::new(0., 100.),
+		];
+		let l_shape = Subpath::from_anchors_linear(anchors, true);
+
+		let grown = offset_subpath_polyline(&l_shape, 5.);
+		let [min, max] = bounds(&grown);
+		assert!(min.abs_diff_eq(DVec2::splat(-5.), 1e-6));
+		assert!(max.abs_diff_eq(DVec2::splat(105.), 1e-6));
+
+		// The concave corner moves diagonally into the notch of the L
+		let concave_corner = grown.manipulator_groups().iter().map(|group| group.anchor).find(|anchor| anchor.abs_diff_eq(DVec2::splat(55.), 1e-6));
+		assert!(concave_corner.is_some(), "The concave corner should be offset to (55, 55)");
+	}
+
+	#[test]
+	fn offset_polyline_curved() {
+		let circle = Subpath::new_ellipse(DVec2::splat(-50.), DVec2::splat(50.));
+
+		let grown = offset_subpath_polyline(&circle, 10.);
+		for group in grown.manipulator_groups() {
+			let radius = group.anchor.length();
+			assert!((radius - 60.).abs() < 1., "Offset circle anchor is at radius {radius} instead of 60");
+		}
+	}
+
+	#[test]
+	fn offset_polyline_open() {
+		let line = Subpath::from_anchors_linear([DVec2::ZERO, DVec2::new(100., 0.)], false);
+
+		let offset = offset_subpath_polyline(&line, 10.);
+		assert!(!offset.closed);
+		assert_eq!(offset.len(), 2);
+		assert!(offset.manipulator_groups()[0].anchor.abs_diff_eq(DVec2::new(0., 10.), 1e-6));
+		assert!(offset.manipulator_groups()[1].anchor.abs_diff_eq(DVec2::new(100., 10.), 1e-6));
+	}
+
+	#[test]
+	fn offset_polyline_self_intersecting() {
+		// A bow tie, whose two lobes wind in opposite directions
+		let anchors = [DVec2::ZERO, DVec2::new(100., 100.), DVec2::new(100., 0.), DVec2::new(0., 100.)];
+		let bow_tie = Subpath::from_anchors_linear(anchors, true);
+
+		let offset = offset_subpath_polyline(&bow_tie, 5.);
+		assert!(offset.closed);
+		assert_eq!(offset.len(), 4);
+		assert!(offset.manipulator_groups().iter().all(|group| group.anchor.is_finite()));
+
+		// Each vertex moves no further than the miter limit allows
+		for (original, offset) in anchors.iter().zip(offset.manipulator_groups()) {
+			assert!(original.distance(offset.anchor) <= 5. * POLYLINE_MITER_LIMIT + 1e-6);
+		}
+	}
+
+	#[test]
+	fn offset_polyline_zero_distance() {
+		let square = Subpath::new_rect(DVec2::ZERO, DVec2::splat(100.));
+		assert_eq!(offset_subpath_polyline(&square, 0.), square);
+	}
+}
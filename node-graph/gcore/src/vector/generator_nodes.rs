@@ -134,6 +134,12 @@ fn line(_: impl Ctx, _primary: (), #[default((0., -50.))] start: DVec2, #[defaul
 	VectorDataTable::new(VectorData::from_subpath(Subpath::new_line(start, end)))
 }
 
+/// A closed shape with straight sides between the given vertices, which can be dragged, inserted, and deleted with the Path tool.
+#[node_macro::node(category("Vector: Shape"))]
+fn polygon(_: impl Ctx, _primary: (), #[default([(0., -50.), (50., 50.), (-50., 50.)])] vertices: Vec<DVec2>) -> VectorDataTable {
+	VectorDataTable::new(VectorData::from_subpath(Subpath::from_anchors_linear(vertices, true)))
+}
+
 trait GridSpacing {
 	fn as_dvec2(&self) -> DVec2;
 }
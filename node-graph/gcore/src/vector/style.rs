@@ -119,6 +119,23 @@ impl GradientStops {
 	pub fn map_colors<F: Fn(&Color) -> Color>(&self, f: F) -> Self {
 		Self(self.0.iter().map(|(position, color)| (*position, f(color))).collect())
 	}
+
+	/// Renders the `<stop />` elements that go inside an SVG gradient definition.
+	pub fn render_svg_stops(&self) -> String {
+		let mut stops = String::new();
+		for (position, color) in self.0.iter() {
+			stops.push_str("<stop");
+			if *position != 0. {
+				let _ = write!(stops, r#" offset="{}""#, (position * 1_000_000.).round() / 1_000_000.);
+			}
+			let _ = write!(stops, r##" stop-color="#{}""##, color.to_rgb_hex_srgb_from_gamma());
+			if color.a() < 1. {
+				let _ = write!(stops, r#" stop-opacity="{}""#, (color.a() * 1000.).round() / 1000.);
+			}
+			stops.push_str(" />")
+		}
+		stops
+	}
 }
 
 /// A gradient fill.
@@ -206,18 +223,7 @@ impl Gradient {
 		let bound_transform = DAffine2::from_scale_angle_translation(bounds[1] - bounds[0], 0., bounds[0]);
 		let transformed_bound_transform = element_transform * DAffine2::from_scale_angle_translation(transformed_bounds[1] - transformed_bounds[0], 0., transformed_bounds[0]);
 
-		let mut stop = String::new();
-		for (position, color) in self.stops.0.iter() {
-			stop.push_str("<stop");
-			if *position != 0. {
-				let _ = write!(stop, r#" offset="{}""#, (position * 1_000_000.).round() / 1_000_000.);
-			}
-			let _ = write!(stop, r##" stop-color="#{}""##, color.to_rgb_hex_srgb_from_gamma());
-			if color.a() < 1. {
-				let _ = write!(stop, r#" stop-opacity="{}""#, (color.a() * 1000.).round() / 1000.);
-			}
-			stop.push_str(" />")
-		}
+		let stop = self.stops.render_svg_stops();
 
 		let mod_gradient = if transformed_bound_transform.matrix2.determinant() != 0. {
 			transformed_bound_transform.inverse()
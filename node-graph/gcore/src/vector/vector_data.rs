@@ -1,6 +1,7 @@
 mod attributes;
 mod indexed;
 mod modification;
+mod segment_bvh;
 
 use super::misc::point_to_dvec2;
 use super::style::{PathStyle, Stroke};
@@ -13,6 +14,7 @@ use dyn_any::DynAny;
 use glam::{DAffine2, DVec2};
 pub use indexed::VectorDataIndex;
 pub use modification::*;
+pub use segment_bvh::{IndexedSegment, SegmentBvh};
 use std::collections::HashMap;
 
 // TODO: Eventually remove this migration document upgrade code
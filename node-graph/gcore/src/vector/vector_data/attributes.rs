@@ -32,6 +32,16 @@ macro_rules! create_ids {
 					Self(hash_value)
 				}
 
+				/// Derive an id from the context of the operation creating it, so repeating the operation on another copy of the document creates the same id.
+				pub fn derive(context: impl Hash) -> Self {
+					// Unlike `DefaultHasher`, whose algorithm may change between Rust releases, SipHash-2-4 with fixed keys always gives the same output
+					#[allow(deprecated)]
+					let mut hasher = std::hash::SipHasher::new_with_keys(0, 0);
+					stringify!($id).hash(&mut hasher);
+					context.hash(&mut hasher);
+					Self(hasher.finish())
+				}
+
 				/// Gets the inner raw value.
 				pub fn inner(self) -> u64 {
 					self.0
@@ -84,28 +94,41 @@ pub struct PointDomain {
 	id: Vec<PointId>,
 	#[serde(alias = "positions")]
 	pub(crate) position: Vec<DVec2>,
+	/// The width of a stroke at each point, as a multiple of the stroke's weight. Nothing renders it yet, but it can be edited for a stroke width node to use later.
+	/// Points from documents saved before the attribute existed have no width stored, which is taken to be 1.
+	#[serde(default)]
+	width: Vec<f64>,
 }
 
 impl core::hash::Hash for PointDomain {
 	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
 		self.id.hash(state);
 		self.position.iter().for_each(|pos| pos.to_array().map(|v| v.to_bits()).hash(state));
+		self.width.iter().for_each(|width| width.to_bits().hash(state));
 	}
 }
 
 impl PointDomain {
 	pub const fn new() -> Self {
-		Self { id: Vec::new(), position: Vec::new() }
+		Self {
+			id: Vec::new(),
+			position: Vec::new(),
+			width: Vec::new(),
+		}
 	}
 
 	pub fn clear(&mut self) {
 		self.id.clear();
 		self.position.clear();
+		self.width.clear();
 	}
 
 	pub fn retain(&mut self, segment_domain: &mut SegmentDomain, f: impl Fn(&PointId) -> bool) {
+		self.fill_widths();
 		let mut keep = self.id.iter().map(&f);
 		self.position.retain(|_| keep.next().unwrap_or_default());
+		let mut keep = self.id.iter().map(&f);
+		self.width.retain(|_| keep.next().unwrap_or_default());
 
 		// TODO(TrueDoctor): Consider using a prefix sum to avoid this Vec allocation (https://github.com/GraphiteEditor/Graphite/pull/1949#discussion_r1741711562)
 		let mut id_map = Vec::with_capacity(self.ids().len());
@@ -129,8 +152,10 @@ impl PointDomain {
 
 	pub fn push(&mut self, id: PointId, position: DVec2) {
 		debug_assert!(!self.id.contains(&id));
+		self.fill_widths();
 		self.id.push(id);
 		self.position.push(position);
+		self.width.push(1.);
 	}
 
 	pub fn positions(&self) -> &[DVec2] {
@@ -149,6 +174,21 @@ impl PointDomain {
 		&self.id
 	}
 
+	/// The stroke width multiplier of the point, which is 1 unless it was set otherwise.
+	pub fn width_from_id(&self, id: PointId) -> Option<f64> {
+		self.resolve_id(id).map(|index| self.width.get(index).copied().unwrap_or(1.))
+	}
+
+	pub fn set_width(&mut self, index: usize, width: f64) {
+		self.fill_widths();
+		self.width[index] = width;
+	}
+
+	/// Gives the points lacking a stored width, from documents saved before the attribute existed, the default width of 1.
+	fn fill_widths(&mut self) {
+		self.width.resize(self.id.len(), 1.);
+	}
+
 	pub fn next_id(&self) -> PointId {
 		self.ids().iter().copied().max_by(|a, b| a.0.cmp(&b.0)).map(|mut id| id.next_id()).unwrap_or(PointId::ZERO)
 	}
@@ -167,8 +207,10 @@ impl PointDomain {
 	}
 
 	pub fn concat(&mut self, other: &Self, transform: DAffine2, id_map: &IdMap) {
+		self.fill_widths();
 		self.id.extend(other.id.iter().map(|id| *id_map.point_map.get(id).unwrap_or(id)));
 		self.position.extend(other.position.iter().map(|&pos| transform.transform_point2(pos)));
+		self.width.extend((0..other.id.len()).map(|index| other.width.get(index).copied().unwrap_or(1.)));
 	}
 
 	pub fn map_ids(&mut self, id_map: &IdMap) {
@@ -869,6 +911,25 @@ impl VectorData {
 		self.build_stroke_path_iter().map(|(group, closed)| bezier_rs::Subpath::new(group, closed))
 	}
 
+	/// Construct the ranges of indices into the segment domain which make up each subpath, in the same order as [`Self::stroke_bezier_paths`].
+	pub fn stroke_subpath_segment_ranges(&self) -> impl Iterator<Item = Vec<core::ops::Range<usize>>> + '_ {
+		let mut iter = self.build_stroke_path_iter();
+		std::iter::from_fn(move || {
+			let mut segments = Vec::new();
+			iter.next_subpath(Some(&mut segments))?;
+
+			segments.sort_unstable();
+			let mut ranges: Vec<core::ops::Range<usize>> = Vec::new();
+			for segment_index in segments {
+				match ranges.last_mut() {
+					Some(range) if range.end == segment_index => range.end += 1,
+					_ => ranges.push(segment_index..segment_index + 1),
+				}
+			}
+			Some(ranges)
+		})
+	}
+
 	/// Construct a [`kurbo::BezPath`] curve for stroke.
 	pub fn stroke_bezpath_iter(&self) -> impl Iterator<Item = kurbo::BezPath> {
 		self.build_stroke_path_iter().map(|(group, closed)| {
@@ -987,10 +1048,9 @@ pub struct StrokePathIter<'a> {
 	done_one: bool,
 }
 
-impl Iterator for StrokePathIter<'_> {
-	type Item = (Vec<bezier_rs::ManipulatorGroup<PointId>>, bool);
-
-	fn next(&mut self) -> Option<Self::Item> {
+impl StrokePathIter<'_> {
+	/// Traverse the next subpath, optionally recording the indices of the segments it is made from.
+	fn next_subpath(&mut self, mut segments: Option<&mut Vec<usize>>) -> Option<(Vec<bezier_rs::ManipulatorGroup<PointId>>, bool)> {
 		let current_start = if let Some((index, _)) = self.points.iter().enumerate().skip(self.skip).find(|(_, val)| val.connected() == 1) {
 			index
 		} else {
@@ -1020,6 +1080,10 @@ impl Iterator for StrokePathIter<'_> {
 				break;
 			};
 
+			if let Some(segments) = segments.as_deref_mut() {
+				segments.push(val.segment_index);
+			}
+
 			let mut handles = self.vector_data.segment_domain.handles()[val.segment_index];
 			if val.start_from_end {
 				handles = handles.reversed();
@@ -1051,6 +1115,14 @@ impl Iterator for StrokePathIter<'_> {
 	}
 }
 
+impl Iterator for StrokePathIter<'_> {
+	type Item = (Vec<bezier_rs::ManipulatorGroup<PointId>>, bool);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.next_subpath(None)
+	}
+}
+
 impl bezier_rs::Identifier for PointId {
 	fn new() -> Self {
 		Self::generate()
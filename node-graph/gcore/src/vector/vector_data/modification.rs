@@ -15,6 +15,8 @@ pub struct PointModification {
 	remove: HashSet<PointId>,
 	#[serde(serialize_with = "serialize_hashmap", deserialize_with = "deserialize_hashmap")]
 	delta: HashMap<PointId, DVec2>,
+	#[serde(default, serialize_with = "serialize_hashmap", deserialize_with = "deserialize_hashmap")]
+	width: HashMap<PointId, f64>,
 }
 
 impl Hash for PointModification {
@@ -56,6 +58,16 @@ impl PointModification {
 
 			point_domain.push(add_id, position);
 		}
+
+		for (&id, &width) in &self.width {
+			let Some(index) = point_domain.resolve_id(id) else { continue };
+			if !width.is_finite() {
+				warn!("Invalid width when applying a point modification");
+				continue;
+			}
+
+			point_domain.set_width(index, width);
+		}
 	}
 
 	/// Create a new modification that will convert an empty [`VectorData`] into the target [`VectorData`].
@@ -64,6 +76,13 @@ impl PointModification {
 			add: vector_data.point_domain.ids().to_vec(),
 			remove: HashSet::new(),
 			delta: vector_data.point_domain.ids().iter().copied().zip(vector_data.point_domain.positions().iter().cloned()).collect(),
+			width: vector_data
+				.point_domain
+				.ids()
+				.iter()
+				.filter_map(|&id| Some((id, vector_data.point_domain.width_from_id(id)?)))
+				.filter(|&(_, width)| width != 1.)
+				.collect(),
 		}
 	}
 
@@ -76,6 +95,7 @@ impl PointModification {
 		self.remove.insert(id);
 		self.add.retain(|&add| add != id);
 		self.delta.remove(&id);
+		self.width.remove(&id);
 	}
 }
 
@@ -323,13 +343,50 @@ pub enum VectorModificationType {
 	SetEndHandle { segment: SegmentId, relative_position: DVec2 },
 	SetStartPoint { segment: SegmentId, id: PointId },
 	SetEndPoint { segment: SegmentId, id: PointId },
+	SetPointWidth { point: PointId, width: f64 },
 
 	ApplyPointDelta { point: PointId, delta: DVec2 },
 	ApplyPrimaryDelta { segment: SegmentId, delta: DVec2 },
 	ApplyEndDelta { segment: SegmentId, delta: DVec2 },
 }
 
+impl VectorModificationType {
+	/// Whether every coordinate in this modification is finite, since a single NaN or infinity would corrupt the resulting [`VectorData`].
+	pub fn is_finite(&self) -> bool {
+		match self {
+			Self::InsertSegment { handles, .. } | Self::SetHandles { handles, .. } => handles.iter().flatten().all(|handle| handle.is_finite()),
+			Self::InsertPoint { position, .. } => position.is_finite(),
+			Self::SetPrimaryHandle { relative_position, .. } | Self::SetEndHandle { relative_position, .. } => relative_position.is_finite(),
+			Self::SetPointWidth { width, .. } => width.is_finite(),
+			Self::ApplyPointDelta { delta, .. } | Self::ApplyPrimaryDelta { delta, .. } | Self::ApplyEndDelta { delta, .. } => delta.is_finite(),
+			Self::RemoveSegment { .. } | Self::RemovePoint { .. } | Self::SetG1Continuous { .. } | Self::SetStartPoint { .. } | Self::SetEndPoint { .. } => true,
+		}
+	}
+}
+
 impl VectorModification {
+	/// Repairs any non-finite coordinates by zeroing point positions and deltas, making handles linear, and resetting point widths. Returns the number of coordinates repaired.
+	pub fn sanitize(&mut self) -> usize {
+		let mut repaired = 0;
+
+		for delta in self.points.delta.values_mut().filter(|delta| !delta.is_finite()) {
+			*delta = DVec2::ZERO;
+			repaired += 1;
+		}
+		for handle in self.segments.handle_primary.values_mut().chain(self.segments.handle_end.values_mut()) {
+			if handle.is_some_and(|handle| !handle.is_finite()) {
+				*handle = None;
+				repaired += 1;
+			}
+		}
+		for width in self.points.width.values_mut().filter(|width| !width.is_finite()) {
+			*width = 1.;
+			repaired += 1;
+		}
+
+		repaired
+	}
+
 	/// Apply this modification to the specified [`VectorData`].
 	pub fn apply(&self, vector_data: &mut VectorData) {
 		self.points.apply(&mut vector_data.point_domain, &mut vector_data.segment_domain);
@@ -388,6 +445,9 @@ impl VectorModification {
 			VectorModificationType::SetEndPoint { segment, id } => {
 				self.segments.end_point.insert(*segment, *id);
 			}
+			VectorModificationType::SetPointWidth { point, width } => {
+				self.points.width.insert(*point, *width);
+			}
 
 			VectorModificationType::ApplyPointDelta { point, delta } => {
 				*self.points.delta.entry(*point).or_default() += *delta;
@@ -492,6 +552,26 @@ fn modify_existing() {
 	);
 }
 
+#[test]
+fn modify_point_width() {
+	let mut vector_data = VectorData::from_subpath(bezier_rs::Subpath::new_rect(DVec2::ZERO, DVec2::ONE));
+	let [point, other] = [0, 1].map(|index| vector_data.point_domain.ids()[index]);
+
+	let mut modification = VectorModification::default();
+	modification.modify(&VectorModificationType::SetPointWidth { point, width: 2.5 });
+
+	// The width survives serialization, so it is saved with the document
+	let modification = serde_json::from_str::<VectorModification>(&serde_json::to_string(&modification).unwrap()).unwrap();
+	modification.apply(&mut vector_data);
+	assert_eq!(vector_data.point_domain.width_from_id(point), Some(2.5));
+	assert_eq!(vector_data.point_domain.width_from_id(other), Some(1.));
+
+	// Recreating the vector data from scratch keeps the width
+	let mut new = VectorData::empty();
+	VectorModification::create_from_vector(&vector_data).apply(&mut new);
+	assert_eq!(new.point_domain.width_from_id(point), Some(2.5));
+}
+
 // Do we want to enforce that all serialized/deserialized hashmaps are a vec of tuples?
 // TODO: Eventually remove this document upgrade code
 use serde::de::{SeqAccess, Visitor};
@@ -0,0 +1,207 @@
+use super::{PointId, SegmentId, VectorData};
+use bezier_rs::Bezier;
+use glam::DVec2;
+
+/// The most segments stored together in one leaf of a [`SegmentBvh`].
+const LEAF_SIZE: usize = 4;
+
+/// A segment stored in a [`SegmentBvh`] along with its curve, so it can be measured without the [`VectorData`] it came from.
+#[derive(Clone, Debug)]
+pub struct IndexedSegment {
+	pub id: SegmentId,
+	pub bezier: Bezier,
+	pub start: PointId,
+	pub end: PointId,
+}
+
+#[derive(Clone, Debug)]
+enum BvhNode {
+	Leaf { bounds: [DVec2; 2], start: usize, end: usize },
+	Branch { bounds: [DVec2; 2], children: [usize; 2] },
+}
+
+impl BvhNode {
+	fn bounds(&self) -> [DVec2; 2] {
+		match *self {
+			BvhNode::Leaf { bounds, .. } | BvhNode::Branch { bounds, .. } => bounds,
+		}
+	}
+}
+
+/// A bounding volume hierarchy over the segments of a [`VectorData`], used to find the segments near a position without measuring every segment.
+///
+/// Important: Like [`super::VectorDataIndex`], it describes the data it was built from and must be rebuilt after that data changes.
+#[derive(Clone, Debug, Default)]
+pub struct SegmentBvh {
+	/// The segments, ordered so that those in each leaf are contiguous.
+	segments: Vec<IndexedSegment>,
+	/// The nodes of the hierarchy, with the root first.
+	nodes: Vec<BvhNode>,
+}
+
+impl SegmentBvh {
+	/// Construct a [`SegmentBvh`] over the segments of the given [`VectorData`]. Takes `O(n log n)` time.
+	pub fn build_from(data: &VectorData) -> Self {
+		let segments = data.segment_bezier_iter().map(|(id, bezier, start, end)| IndexedSegment { id, bezier, start, end }).collect::<Vec<_>>();
+		let bounds = segments.iter().map(|segment| segment.bezier.bounding_box_of_anchors_and_handles()).collect::<Vec<_>>();
+		let mut order = (0..segments.len()).collect::<Vec<_>>();
+
+		let mut nodes = Vec::new();
+		if !order.is_empty() {
+			Self::build_node(&mut nodes, &mut order, &bounds, 0);
+		}
+
+		let mut segments = segments.into_iter().map(Some).collect::<Vec<_>>();
+		let segments = order.into_iter().filter_map(|index| segments[index].take()).collect();
+
+		Self { segments, nodes }
+	}
+
+	/// Adds the node covering the segments in `order`, which start at `offset` in the final ordering, splitting them at the median of their longest axis.
+	fn build_node(nodes: &mut Vec<BvhNode>, order: &mut [usize], bounds: &[[DVec2; 2]], offset: usize) -> usize {
+		let node_bounds = order
+			.iter()
+			.map(|&index| bounds[index])
+			.reduce(|[min_a, max_a], [min_b, max_b]| [min_a.min(min_b), max_a.max(max_b)])
+			.unwrap_or_default();
+
+		let index = nodes.len();
+		if order.len() <= LEAF_SIZE {
+			nodes.push(BvhNode::Leaf {
+				bounds: node_bounds,
+				start: offset,
+				end: offset + order.len(),
+			});
+			return index;
+		}
+
+		let size = node_bounds[1] - node_bounds[0];
+		let axis = if size.x >= size.y { 0 } else { 1 };
+		let center = |index: &usize| (bounds[*index][0][axis] + bounds[*index][1][axis]) / 2.;
+
+		let middle = order.len() / 2;
+		order.select_nth_unstable_by(middle, |a, b| center(a).total_cmp(&center(b)));
+
+		// Reserve this node's place before its children so the root stays first
+		nodes.push(BvhNode::Leaf {
+			bounds: node_bounds,
+			start: 0,
+			end: 0,
+		});
+		let (left, right) = order.split_at_mut(middle);
+		let left = Self::build_node(nodes, left, bounds, offset);
+		let right = Self::build_node(nodes, right, bounds, offset + middle);
+		nodes[index] = BvhNode::Branch {
+			bounds: node_bounds,
+			children: [left, right],
+		};
+
+		index
+	}
+
+	/// The number of indexed segments.
+	pub fn len(&self) -> usize {
+		self.segments.len()
+	}
+
+	/// Whether there are no indexed segments.
+	pub fn is_empty(&self) -> bool {
+		self.segments.is_empty()
+	}
+
+	/// The segments whose anchors and handles come within `radius` of `position`, which includes every segment whose curve does.
+	pub fn segments_near(&self, position: DVec2, radius: f64) -> Vec<&IndexedSegment> {
+		let mut found = Vec::new();
+		if self.nodes.is_empty() {
+			return found;
+		}
+
+		let radius_squared = radius * radius;
+		let mut stack = vec![0];
+		while let Some(index) = stack.pop() {
+			let node = &self.nodes[index];
+			let [min, max] = node.bounds();
+			if position.clamp(min, max).distance_squared(position) > radius_squared {
+				continue;
+			}
+
+			match *node {
+				BvhNode::Leaf { start, end, .. } => {
+					let leaf = &self.segments[start..end];
+					found.extend(leaf.iter().filter(|segment| {
+						let [min, max] = segment.bezier.bounding_box_of_anchors_and_handles();
+						position.clamp(min, max).distance_squared(position) <= radius_squared
+					}));
+				}
+				BvhNode::Branch { children, .. } => stack.extend(children),
+			}
+		}
+
+		found
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Rows of wavy segments, `count` rows of `count` segments each.
+	fn grid(count: usize) -> VectorData {
+		let rows = (0..count).map(|row| {
+			let beziers = (0..count)
+				.map(|column| {
+					let start = DVec2::new(column as f64, row as f64) * 10.;
+					Bezier::from_cubic_dvec2(start, start + DVec2::new(3., 4.), start + DVec2::new(7., -4.), start + DVec2::new(10., 0.))
+				})
+				.collect::<Vec<_>>();
+			bezier_rs::Subpath::from_beziers(&beziers, false)
+		});
+		VectorData::from_subpaths(rows, false)
+	}
+
+	#[test]
+	fn segments_near_matches_exhaustive_search() {
+		let vector_data = grid(30);
+		let bvh = SegmentBvh::build_from(&vector_data);
+		assert_eq!(bvh.len(), 900);
+
+		for (position, radius) in [(DVec2::new(55., 42.), 3.), (DVec2::new(-20., -20.), 5.), (DVec2::new(150., 150.), 40.), (DVec2::new(291., 5.), 0.5)] {
+			let mut expected = vector_data
+				.segment_bezier_iter()
+				.filter(|(_, bezier, _, _)| {
+					let [min, max] = bezier.bounding_box_of_anchors_and_handles();
+					position.clamp(min, max).distance(position) <= radius
+				})
+				.map(|(id, ..)| id)
+				.collect::<Vec<_>>();
+			let mut found = bvh.segments_near(position, radius).into_iter().map(|segment| segment.id).collect::<Vec<_>>();
+
+			expected.sort_unstable();
+			found.sort_unstable();
+			assert_eq!(found, expected, "Different segments found near {position} within {radius}");
+		}
+	}
+
+	#[test]
+	#[cfg_attr(debug_assertions, ignore = "Timing is only meaningful in optimized builds")]
+	fn hover_query_on_large_path_is_fast() {
+		let vector_data = grid(224);
+		let bvh = SegmentBvh::build_from(&vector_data);
+		assert!(bvh.len() >= 50_000);
+
+		const QUERIES: u32 = 1000;
+		let start = std::time::Instant::now();
+		for query in 0..QUERIES {
+			let position = DVec2::new(query as f64 * 2.2, query as f64 * 1.7 + 3.);
+			let closest = bvh
+				.segments_near(position, 5.)
+				.into_iter()
+				.map(|segment| segment.bezier.evaluate(bezier_rs::TValue::Parametric(segment.bezier.project(position))).distance(position))
+				.fold(f64::INFINITY, f64::min);
+			std::hint::black_box(closest);
+		}
+
+		let per_query = start.elapsed() / QUERIES;
+		assert!(per_query < std::time::Duration::from_millis(1), "Hover queries took {per_query:?} each");
+	}
+}
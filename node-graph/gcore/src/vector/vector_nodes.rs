@@ -1,4 +1,4 @@
-use super::algorithms::bezpath_algorithms::{self, PERIMETER_ACCURACY, position_on_bezpath, sample_points_on_bezpath, tangent_on_bezpath};
+use super::algorithms::bezpath_algorithms::{self, PERIMETER_ACCURACY, position_on_bezpath, sample_points_on_bezpath, stroke_outline, tangent_on_bezpath};
 use super::algorithms::offset_subpath::offset_subpath;
 use super::misc::{CentroidType, point_to_dvec2};
 use super::style::{Fill, Gradient, GradientStops, Stroke};
@@ -10,7 +10,7 @@ use crate::renderer::GraphicElementRendered;
 use crate::transform::{Footprint, ReferencePoint, Transform, TransformMut};
 use crate::vector::PointDomain;
 use crate::vector::misc::dvec2_to_point;
-use crate::vector::style::{LineCap, LineJoin};
+use crate::vector::style::LineJoin;
 use crate::{CloneVarArgs, Color, Context, Ctx, ExtractAll, GraphicElement, GraphicGroupTable, OwnedContextImpl};
 use bezier_rs::{Join, ManipulatorGroup, Subpath, SubpathTValue};
 use core::f64::consts::PI;
@@ -1128,34 +1128,9 @@ async fn solidify_stroke(_: impl Ctx, vector_data: VectorDataTable) -> VectorDat
 		let bezpaths = vector_data.stroke_bezpath_iter();
 		let mut result = VectorData::empty();
 
-		// Taking the existing stroke data and passing it to kurbo::stroke to generate new fill paths.
-		let join = match stroke.line_join {
-			LineJoin::Miter => kurbo::Join::Miter,
-			LineJoin::Bevel => kurbo::Join::Bevel,
-			LineJoin::Round => kurbo::Join::Round,
-		};
-		let cap = match stroke.line_cap {
-			LineCap::Butt => kurbo::Cap::Butt,
-			LineCap::Round => kurbo::Cap::Round,
-			LineCap::Square => kurbo::Cap::Square,
-		};
-		let dash_offset = stroke.dash_offset;
-		let dash_pattern = stroke.dash_lengths;
-		let miter_limit = stroke.line_join_miter_limit;
-
-		let stroke_style = kurbo::Stroke::new(stroke.weight)
-			.with_caps(cap)
-			.with_join(join)
-			.with_dashes(dash_offset, dash_pattern)
-			.with_miter_limit(miter_limit);
-
-		let stroke_options = kurbo::StrokeOpts::default();
-
-		// 0.25 is balanced between performace and accuracy of the curve.
-		const STROKE_TOLERANCE: f64 = 0.25;
-
+		// Taking the existing stroke data and generating the fill paths which cover the same area.
 		for path in bezpaths {
-			let solidified = kurbo::stroke(path, &stroke_style, &stroke_options, STROKE_TOLERANCE);
+			let solidified = stroke_outline(&path, &stroke);
 			result.append_bezpath(solidified);
 		}
 
@@ -10,8 +10,8 @@ pub use graphene_core::uuid::generate_uuid;
 use graphene_core::{Cow, MemoHash, ProtoNodeIdentifier, Type};
 use log::Metadata;
 use rustc_hash::FxHashMap;
-use std::collections::HashMap;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
 /// Hash two IDs together, returning a new ID that is always consistent for two input IDs in a specific order.
@@ -795,6 +795,51 @@ impl NodeNetwork {
 		}
 		true
 	}
+
+	/// Finds a cycle in the connections between the nodes of this network or any network nested within it.
+	/// Returns the paths of the nodes forming the cycle, with each node followed by the one it receives its input from.
+	pub fn find_cycle(&self) -> Option<Vec<Vec<NodeId>>> {
+		self.find_cycle_in(&[])
+	}
+
+	fn find_cycle_in(&self, prefix: &[NodeId]) -> Option<Vec<Vec<NodeId>>> {
+		fn visit(network: &NodeNetwork, node_id: NodeId, visited: &mut HashSet<NodeId>, stack: &mut Vec<NodeId>) -> Option<Vec<NodeId>> {
+			// Reaching a node which is still being visited further downstream means the connections loop back around to it
+			if let Some(start) = stack.iter().position(|&id| id == node_id) {
+				return Some(stack[start..].to_vec());
+			}
+			if !visited.insert(node_id) {
+				return None;
+			}
+			let node = network.nodes.get(&node_id)?;
+
+			stack.push(node_id);
+			for upstream in node.inputs.iter().filter_map(NodeInput::as_node) {
+				if let Some(cycle) = visit(network, upstream, visited, stack) {
+					return Some(cycle);
+				}
+			}
+			stack.pop();
+
+			None
+		}
+
+		// Sorted so the same cycle is reported each time
+		let mut node_ids = self.nodes.keys().copied().collect::<Vec<_>>();
+		node_ids.sort();
+
+		let mut visited = HashSet::new();
+		for &node_id in &node_ids {
+			if let Some(cycle) = visit(self, node_id, &mut visited, &mut Vec::new()) {
+				return Some(cycle.into_iter().map(|id| [prefix, &[id]].concat()).collect());
+			}
+		}
+
+		node_ids.into_iter().find_map(|node_id| {
+			let network = self.nodes[&node_id].implementation.get_network()?;
+			network.find_cycle_in(&[prefix, &[node_id]].concat())
+		})
+	}
 }
 
 /// Functions for compiling the network
@@ -1331,6 +1376,21 @@ mod test {
 		}
 	}
 
+	#[test]
+	fn find_cycle() {
+		let mut network = add_network();
+		assert_eq!(network.find_cycle(), None);
+
+		// Feed the add node's output back into the cons node, with the cycle nested inside another node
+		network.nodes.get_mut(&NodeId(0)).unwrap().inputs[1] = NodeInput::node(NodeId(1), 0);
+		let outer = NodeNetwork::value_network(DocumentNode {
+			implementation: DocumentNodeImplementation::Network(network),
+			..Default::default()
+		});
+
+		assert_eq!(outer.find_cycle(), Some(vec![vec![NodeId(0), NodeId(0)], vec![NodeId(0), NodeId(1)]]));
+	}
+
 	#[test]
 	fn map_ids() {
 		let mut network = add_network();
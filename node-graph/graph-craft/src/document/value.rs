@@ -273,6 +273,17 @@ impl TaggedValue {
 			Some(DVec2::new(x, y))
 		}
 
+		// List syntax (e.g. "[(0., -50.), (50., 50.)]")
+		fn to_dvec2_list(input: &str) -> Option<Vec<DVec2>> {
+			let input = input.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+			let list = input.strip_prefix('[')?.strip_suffix(']')?;
+			list.split(')')
+				.map(|item| item.trim_start_matches(',').trim_start_matches('('))
+				.filter(|item| !item.is_empty())
+				.map(to_dvec2)
+				.collect()
+		}
+
 		fn to_color(input: &str) -> Option<Color> {
 			// String syntax (e.g. "000000ff")
 			if input.starts_with('"') && input.ends_with('"') {
@@ -352,6 +363,7 @@ impl TaggedValue {
 					x if x == TypeId::of::<u64>() => FromStr::from_str(string).map(TaggedValue::U64).ok()?,
 					x if x == TypeId::of::<u32>() => FromStr::from_str(string).map(TaggedValue::U32).ok()?,
 					x if x == TypeId::of::<DVec2>() => to_dvec2(string).map(TaggedValue::DVec2)?,
+					x if x == TypeId::of::<Vec<DVec2>>() => to_dvec2_list(string).map(TaggedValue::VecDVec2)?,
 					x if x == TypeId::of::<bool>() => FromStr::from_str(string).map(TaggedValue::Bool).ok()?,
 					x if x == TypeId::of::<Color>() => to_color(string).map(TaggedValue::Color)?,
 					x if x == TypeId::of::<Option<Color>>() => to_color(string).map(|color| TaggedValue::OptionalColor(Some(color)))?,
@@ -432,6 +444,8 @@ pub enum RenderOutputType {
 	CanvasFrame(graphene_core::SurfaceFrame),
 	Svg(String),
 	Image(Vec<u8>),
+	/// Canvas output was requested but the canvas surface couldn't be used, with the reason why, so the editor can fall back to another format.
+	CanvasUnavailable(String),
 }
 
 impl Hash for RenderOutput {
@@ -536,11 +536,24 @@ impl ProtoNetwork {
 pub enum GraphErrorType {
 	NodeNotFound(NodeId),
 	InputNodeNotFound(NodeId),
-	UnexpectedGenerics { index: usize, inputs: Vec<Type> },
+	UnexpectedGenerics {
+		index: usize,
+		inputs: Vec<Type>,
+	},
 	NoImplementations,
 	NoConstructor,
-	InvalidImplementations { inputs: String, error_inputs: Vec<Vec<(usize, (Type, Type))>> },
-	MultipleImplementations { inputs: String, valid: Vec<NodeIOTypes> },
+	InvalidImplementations {
+		inputs: String,
+		error_inputs: Vec<Vec<(usize, (Type, Type))>>,
+	},
+	MultipleImplementations {
+		inputs: String,
+		valid: Vec<NodeIOTypes>,
+	},
+	/// The node is part of a cycle of connections through these nodes, which can never be evaluated.
+	Cycle(Vec<NodeId>),
+	/// The network has no output or no nodes, so there's nothing to evaluate.
+	EmptyNetwork,
 }
 impl core::fmt::Debug for GraphErrorType {
 	// TODO: format with the document graph context so the input index is the same as in the graph UI.
@@ -568,6 +581,11 @@ impl core::fmt::Debug for GraphErrorType {
 				)
 			}
 			GraphErrorType::MultipleImplementations { inputs, valid } => write!(f, "Multiple implementations found ({inputs}):\n{valid:#?}"),
+			GraphErrorType::Cycle(cycle) => {
+				let cycle = cycle.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(" → ");
+				write!(f, "This node's connections form a cycle through nodes {cycle}, so it can never be evaluated")
+			}
+			GraphErrorType::EmptyNetwork => write!(f, "The node graph has nothing to evaluate"),
 		}
 	}
 }
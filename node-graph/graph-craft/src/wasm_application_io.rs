@@ -85,6 +85,28 @@ pub fn wgpu_available() -> Option<bool> {
 
 impl WasmApplicationIo {
 	pub async fn new() -> Self {
+		let mut io = Self::without_windows(Self::request_executor().await);
+		let window = io.create_window();
+		io.windows.push(WindowWrapper { window });
+
+		io
+	}
+
+	/// Like [`Self::new`], but reports why the IO can't be created instead of panicking, such as when there's no GPU or no browser document to create its canvas in.
+	pub async fn try_new() -> Result<Self, String> {
+		let mut io = Self::without_windows(Self::request_executor().await);
+		#[cfg(feature = "wgpu")]
+		if io.gpu_executor.is_none() {
+			return Err("GPU unavailable".to_string());
+		}
+
+		let window = io.try_create_window()?;
+		io.windows.push(WindowWrapper { window });
+
+		Ok(io)
+	}
+
+	async fn request_executor() -> Option<WgpuExecutor> {
 		#[cfg(target_arch = "wasm32")]
 		let executor = if let Some(gpu) = web_sys::window().map(|w| w.navigator().gpu()) {
 			let request_adapter = || {
@@ -105,6 +127,10 @@ impl WasmApplicationIo {
 		let executor = WgpuExecutor::new().await;
 		WGPU_AVAILABLE.store(executor.is_some() as i8, ::std::sync::atomic::Ordering::SeqCst);
 
+		executor
+	}
+
+	fn without_windows(executor: Option<WgpuExecutor>) -> Self {
 		let mut io = Self {
 			#[cfg(target_arch = "wasm32")]
 			ids: AtomicU64::new(0),
@@ -113,13 +139,81 @@ impl WasmApplicationIo {
 			windows: Vec::new(),
 			resources: HashMap::new(),
 		};
-		let window = io.create_window();
-		io.windows.push(WindowWrapper { window });
 		io.resources.insert("null".to_string(), Arc::from(include_bytes!("null.png").to_vec()));
 
 		io
 	}
 
+	/// Creates a canvas in the browser document, stored in the global scope so it doesn't get garbage collected, or reports why it can't be.
+	#[cfg(target_arch = "wasm32")]
+	pub fn try_create_window(&self) -> Result<SurfaceHandle<<Self as ApplicationIo>::Surface>, String> {
+		let window = window().ok_or("there is no browser window to create the canvas in")?;
+		let document = window.document().ok_or("there is no browser document to create the canvas in")?;
+
+		let wrapper = || {
+			let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into::<HtmlCanvasElement>()?;
+			let id = self.ids.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			let window = Object::from(window.clone());
+
+			let image_canvases_key = JsValue::from_str("imageCanvases");
+
+			let mut canvases = Reflect::get(&window, &image_canvases_key);
+			if canvases.is_err() {
+				Reflect::set(&window, &image_canvases_key, &Object::new())?;
+				canvases = Reflect::get(&window, &image_canvases_key);
+			}
+
+			// Convert key and value to JsValue
+			let js_key = JsValue::from_str(format!("canvas{}", id).as_str());
+			let js_value = JsValue::from(canvas.clone());
+
+			let canvases = Object::from(canvases?);
+
+			// Use Reflect API to set property
+			Reflect::set(&canvases, &js_key, &js_value)?;
+			Ok::<_, JsValue>(SurfaceHandle {
+				window_id: graphene_core::SurfaceId(id),
+				surface: canvas,
+			})
+		};
+
+		wrapper().map_err(|error| format!("the canvas couldn't be set in the global scope: {error:?}"))
+	}
+
+	/// Spawns a window along with its event loop, or reports why it can't be.
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn try_create_window(&self) -> Result<SurfaceHandle<<Self as ApplicationIo>::Surface>, String> {
+		log::trace!("Spawning window");
+
+		#[cfg(not(test))]
+		use winit::platform::wayland::EventLoopBuilderExtWayland;
+
+		#[cfg(not(test))]
+		let event_loop = winit::event_loop::EventLoopBuilder::new().with_any_thread(true).build();
+		#[cfg(test)]
+		let event_loop = winit::event_loop::EventLoop::new();
+		let event_loop = event_loop.map_err(|error| format!("the event loop couldn't be created: {error}"))?;
+
+		let window = winit::window::WindowBuilder::new()
+			.with_title("Graphite")
+			.with_inner_size(winit::dpi::PhysicalSize::new(800, 600))
+			.build(&event_loop)
+			.map_err(|error| format!("the window couldn't be created: {error}"))?;
+
+		Ok(SurfaceHandle {
+			window_id: SurfaceId(window.id().into()),
+			surface: Arc::new(window),
+		})
+	}
+
+	/// A minimal IO without a GPU or any windows, which is enough for graphs that only render vector content.
+	/// It stands in for the full IO while that can't be initialized.
+	pub fn fallback() -> Self {
+		let mut io = Self::default();
+		io.resources.insert("null".to_string(), Arc::from(include_bytes!("null.png").to_vec()));
+		io
+	}
+
 	pub async fn new_offscreen() -> Self {
 		let executor = WgpuExecutor::new().await;
 
@@ -169,63 +263,8 @@ impl ApplicationIo for WasmApplicationIo {
 	#[cfg(not(feature = "wgpu"))]
 	type Executor = ();
 
-	#[cfg(target_arch = "wasm32")]
-	fn create_window(&self) -> SurfaceHandle<Self::Surface> {
-		let wrapper = || {
-			let document = window().expect("should have a window in this context").document().expect("window should have a document");
-
-			let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into::<HtmlCanvasElement>()?;
-			let id = self.ids.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-			// store the canvas in the global scope so it doesn't get garbage collected
-			let window = window().expect("should have a window in this context");
-			let window = Object::from(window);
-
-			let image_canvases_key = JsValue::from_str("imageCanvases");
-
-			let mut canvases = Reflect::get(&window, &image_canvases_key);
-			if canvases.is_err() {
-				Reflect::set(&JsValue::from(web_sys::window().unwrap()), &image_canvases_key, &Object::new()).unwrap();
-				canvases = Reflect::get(&window, &image_canvases_key);
-			}
-
-			// Convert key and value to JsValue
-			let js_key = JsValue::from_str(format!("canvas{}", id).as_str());
-			let js_value = JsValue::from(canvas.clone());
-
-			let canvases = Object::from(canvases.unwrap());
-
-			// Use Reflect API to set property
-			Reflect::set(&canvases, &js_key, &js_value)?;
-			Ok::<_, JsValue>(SurfaceHandle {
-				window_id: graphene_core::SurfaceId(id),
-				surface: canvas,
-			})
-		};
-
-		wrapper().expect("should be able to set canvas in global scope")
-	}
-	#[cfg(not(target_arch = "wasm32"))]
 	fn create_window(&self) -> SurfaceHandle<Self::Surface> {
-		log::trace!("Spawning window");
-
-		#[cfg(not(test))]
-		use winit::platform::wayland::EventLoopBuilderExtWayland;
-
-		#[cfg(not(test))]
-		let event_loop = winit::event_loop::EventLoopBuilder::new().with_any_thread(true).build().unwrap();
-
-		#[cfg(test)]
-		let event_loop = winit::event_loop::EventLoop::new().unwrap();
-		let window = winit::window::WindowBuilder::new()
-			.with_title("Graphite")
-			.with_inner_size(winit::dpi::PhysicalSize::new(800, 600))
-			.build(&event_loop)
-			.unwrap();
-
-		SurfaceHandle {
-			window_id: SurfaceId(window.id().into()),
-			surface: Arc::new(window),
-		}
+		self.try_create_window().expect("should be able to create a window")
 	}
 
 	#[cfg(target_arch = "wasm32")]
@@ -3,7 +3,7 @@ pub use graph_craft::document::value::RenderOutputType;
 pub use graph_craft::wasm_application_io::*;
 #[cfg(target_arch = "wasm32")]
 use graphene_core::application_io::SurfaceHandle;
-use graphene_core::application_io::{ApplicationIo, ExportFormat, RenderConfig};
+use graphene_core::application_io::{ApplicationIo, ExportFormat, NodeGraphUpdateMessage, RenderConfig, RenderQualityHint, resource_content_hash};
 #[cfg(target_arch = "wasm32")]
 use graphene_core::instances::Instances;
 #[cfg(target_arch = "wasm32")]
@@ -11,9 +11,9 @@ use graphene_core::raster::bbox::Bbox;
 use graphene_core::raster::image::{Image, ImageFrameTable};
 use graphene_core::renderer::RenderMetadata;
 use graphene_core::renderer::{GraphicElementRendered, RenderParams, RenderSvgSegmentList, SvgRender, format_transform_matrix};
-use graphene_core::transform::Footprint;
 #[cfg(target_arch = "wasm32")]
 use graphene_core::transform::TransformMut;
+use graphene_core::transform::{Footprint, RenderQuality};
 use graphene_core::vector::VectorDataTable;
 use graphene_core::{Color, Context, Ctx, ExtractFootprint, GraphicGroupTable, OwnedContextImpl, WasmNotSend};
 
@@ -66,14 +66,16 @@ async fn load_resource<'a: 'n>(_: impl Ctx, _primary: (), #[scope("editor-api")]
 	let Some(api) = editor.application_io.as_ref() else {
 		return Arc::from(include_bytes!("../../graph-craft/src/null.png").to_vec());
 	};
-	let Ok(data) = api.load_resource(url) else {
-		return Arc::from(include_bytes!("../../graph-craft/src/null.png").to_vec());
-	};
-	let Ok(data) = data.await else {
-		return Arc::from(include_bytes!("../../graph-craft/src/null.png").to_vec());
+	let data = match api.load_resource(&url) {
+		Ok(data) => data.await.ok(),
+		Err(_) => None,
 	};
 
-	data
+	// The resource is registered with the runtime even when it failed to load, so it's picked up once it becomes available
+	let content_hash = data.as_deref().map(resource_content_hash);
+	editor.node_graph_message_sender.send(NodeGraphUpdateMessage::ExternalResourceLoaded { url, content_hash });
+
+	data.unwrap_or_else(|| Arc::from(include_bytes!("../../graph-craft/src/null.png").to_vec()))
 }
 
 #[node_macro::node(category("Network"))]
@@ -147,9 +149,12 @@ async fn render_canvas(
 	if !data.contains_artboard() && !render_config.hide_artboards {
 		background = Color::WHITE;
 	}
-	exec.render_vello_scene(&scene, &surface_handle, footprint.resolution.x, footprint.resolution.y, &context, background)
+	if let Err(error) = exec
+		.render_vello_scene(&scene, &surface_handle, footprint.resolution.x, footprint.resolution.y, &context, background)
 		.await
-		.expect("Failed to render Vello scene");
+	{
+		return RenderOutputType::CanvasUnavailable(format!("Failed to render Vello scene: {error}"));
+	}
 
 	let frame = SurfaceFrame {
 		surface_id: surface_handle.window_id,
@@ -244,15 +249,33 @@ async fn render<'a: 'n, T: 'n + GraphicElementRendered + WasmNotSend>(
 	_surface_handle: impl Node<Context<'static>, Output = Option<wgpu_executor::WgpuSurface>>,
 ) -> RenderOutput {
 	let footprint = render_config.viewport;
+	// Interactive renders evaluate the graph at half resolution, while the result is still drawn at the full viewport size
+	let evaluation_footprint = match render_config.quality {
+		RenderQualityHint::Final => footprint,
+		RenderQualityHint::Interactive => Footprint {
+			transform: DAffine2::from_scale(glam::DVec2::splat(0.5)) * footprint.transform,
+			resolution: footprint.resolution / 2,
+			quality: RenderQuality::Scale(0.5),
+		},
+	};
 	let ctx = OwnedContextImpl::default()
-		.with_footprint(footprint)
+		.with_footprint(evaluation_footprint)
 		.with_real_time(render_config.time.time)
 		.with_animation_time(render_config.time.animation_time.as_secs_f64())
 		.into_context();
 	ctx.footprint();
 
-	let RenderConfig { hide_artboards, for_export, .. } = render_config;
-	let render_params = RenderParams::new(render_config.view_mode, None, false, hide_artboards, for_export);
+	let RenderConfig {
+		hide_artboards,
+		for_export,
+		subpath_click_regions,
+		expand_strokes,
+		..
+	} = render_config;
+	let render_params = RenderParams {
+		expand_strokes,
+		..RenderParams::new(render_config.view_mode, None, false, hide_artboards, for_export)
+	};
 
 	let data = data.eval(ctx.clone()).await;
 	let editor_api = editor_api.eval(None).await;
@@ -261,33 +284,35 @@ async fn render<'a: 'n, T: 'n + GraphicElementRendered + WasmNotSend>(
 	let surface_handle = _surface_handle.eval(None).await;
 
 	let use_vello = editor_api.editor_preferences.use_vello();
-	#[cfg(all(feature = "vello", not(test)))]
-	let use_vello = use_vello && surface_handle.is_some();
 
 	let mut metadata = RenderMetadata {
 		upstream_footprints: HashMap::new(),
 		local_transforms: HashMap::new(),
 		click_targets: HashMap::new(),
 		clip_targets: HashSet::new(),
+		instance_transforms: HashMap::new(),
+		subpath_click_regions: subpath_click_regions.then(HashMap::new),
 	};
 	data.collect_metadata(&mut metadata, footprint, None);
 
 	let output_format = render_config.export_format;
 	let data = match output_format {
 		ExportFormat::Svg => render_svg(data, SvgRender::new(), render_params, footprint),
-		ExportFormat::Canvas => {
-			if use_vello && editor_api.application_io.as_ref().unwrap().gpu_executor().is_some() {
-				#[cfg(all(feature = "vello", not(test)))]
-				return RenderOutput {
-					data: render_canvas(render_config, data, editor_api, surface_handle.unwrap(), render_params).await,
-					metadata,
+		ExportFormat::Canvas if use_vello => {
+			#[cfg(all(feature = "vello", not(test)))]
+			{
+				// Report a failure to set up the canvas instead of silently rendering something else, so the editor can decide how to fall back
+				let data = match surface_handle {
+					None => RenderOutputType::CanvasUnavailable("The canvas surface failed to initialize".to_string()),
+					Some(_) if editor_api.application_io.as_ref().unwrap().gpu_executor().is_none() => RenderOutputType::CanvasUnavailable("No GPU executor is available".to_string()),
+					Some(surface_handle) => render_canvas(render_config, data, editor_api, surface_handle, render_params).await,
 				};
-				#[cfg(any(not(feature = "vello"), test))]
-				render_svg(data, SvgRender::new(), render_params, footprint)
-			} else {
-				render_svg(data, SvgRender::new(), render_params, footprint)
+				return RenderOutput { data, metadata };
 			}
+			#[cfg(any(not(feature = "vello"), test))]
+			render_svg(data, SvgRender::new(), render_params, footprint)
 		}
+		ExportFormat::Canvas => render_svg(data, SvgRender::new(), render_params, footprint),
 		_ => todo!("Non-SVG render output for {output_format:?}"),
 	};
 	RenderOutput { data, metadata }
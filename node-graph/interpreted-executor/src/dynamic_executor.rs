@@ -4,8 +4,10 @@ use graph_craft::Type;
 use graph_craft::document::NodeId;
 use graph_craft::document::value::{TaggedValue, UpcastAsRefNode, UpcastNode};
 use graph_craft::graphene_compiler::Executor;
-use graph_craft::proto::{ConstructionArgs, GraphError, LocalFuture, NodeContainer, ProtoNetwork, ProtoNode, SharedNodeContainer, TypeErasedBox, TypingContext};
+use graph_craft::proto::{Any, ConstructionArgs, FutureAny, GraphError, LocalFuture, NodeContainer, ProtoNetwork, ProtoNode, SharedNodeContainer, TypeErasedBox, TypingContext};
 use graph_craft::proto::{GraphErrorType, GraphErrors};
+use graphene_core::Node;
+use graphene_core::memo::{EvaluationCounter, EvaluationCounts};
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::panic::UnwindSafe;
@@ -21,6 +23,8 @@ pub struct DynamicExecutor {
 	typing_context: TypingContext,
 	// This allows us to keep the nodes around for one more frame which is used for introspection
 	orphaned_nodes: HashSet<NodeId>,
+	/// Counts the node evaluations and cache lookups made by this executor's evaluations.
+	evaluation_counter: EvaluationCounter,
 }
 
 impl Default for DynamicExecutor {
@@ -30,6 +34,7 @@ impl Default for DynamicExecutor {
 			tree: Default::default(),
 			typing_context: TypingContext::new(&node_registry::NODE_REGISTRY),
 			orphaned_nodes: HashSet::new(),
+			evaluation_counter: EvaluationCounter::default(),
 		}
 	}
 }
@@ -68,6 +73,7 @@ impl DynamicExecutor {
 			output,
 			typing_context,
 			orphaned_nodes: HashSet::new(),
+			evaluation_counter: EvaluationCounter::default(),
 		})
 	}
 
@@ -92,11 +98,28 @@ impl DynamicExecutor {
 		Ok(ResolvedDocumentNodeTypesDelta { add, remove })
 	}
 
+	/// Removes the nodes from the [`BorrowTree`], discarding anything they cached, so they're constructed again by the next [`Self::update`].
+	/// Every node depending on them must be invalidated along with them, since it would otherwise keep using the removed nodes.
+	/// Returns the document paths of the removed nodes.
+	pub fn invalidate(&mut self, node_ids: impl IntoIterator<Item = NodeId>) -> Vec<Path> {
+		node_ids.into_iter().filter_map(|node_id| self.tree.free_node(node_id)).collect()
+	}
+
 	/// Calls the `Node::serialize` for that specific node, returning for example the cached value for a monitor node. The node path must match the document node path.
 	pub fn introspect(&self, node_path: &[NodeId]) -> Result<Arc<dyn std::any::Any + Send + Sync + 'static>, IntrospectError> {
 		self.tree.introspect(node_path)
 	}
 
+	/// The number of proto nodes in the compiled network.
+	pub fn node_count(&self) -> usize {
+		self.tree.node_count()
+	}
+
+	/// The node evaluations and cache lookups made by all evaluations so far, which can be compared with an earlier snapshot using [`EvaluationCounts::since`].
+	pub fn evaluation_counts(&self) -> EvaluationCounts {
+		self.evaluation_counter.counts()
+	}
+
 	pub fn input_type(&self) -> Option<Type> {
 		self.typing_context.type_of(self.output).map(|node_io| node_io.call_argument.clone())
 	}
@@ -120,7 +143,7 @@ where
 		Box::pin(async move {
 			use futures::FutureExt;
 
-			let result = self.tree.eval_tagged_value(self.output, input);
+			let result = self.evaluation_counter.scope(self.tree.eval_tagged_value(self.output, input));
 			let wrapped_result = std::panic::AssertUnwindSafe(result).catch_unwind().await;
 
 			match wrapped_result {
@@ -133,6 +156,31 @@ where
 		})
 	}
 }
+
+/// Counts each evaluation of the node it wraps towards the evaluation being polled, so the executor can tell how many nodes an evaluation ran.
+struct CountedNode(TypeErasedBox<'static>);
+
+impl<'i> Node<'i, Any<'i>> for CountedNode {
+	type Output = FutureAny<'i>;
+
+	fn eval(&'i self, input: Any<'i>) -> Self::Output {
+		EvaluationCounter::record_evaluation();
+		self.0.eval(input)
+	}
+
+	fn reset(&self) {
+		self.0.reset();
+	}
+
+	fn node_name(&self) -> &'static str {
+		self.0.node_name()
+	}
+
+	fn serialize(&self) -> Option<Arc<dyn std::any::Any + Send + Sync>> {
+		self.0.serialize()
+	}
+}
+
 pub struct InputMapping {}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -221,6 +269,10 @@ impl BorrowTree {
 		node.serialize().ok_or(IntrospectError::NoData)
 	}
 
+	pub fn node_count(&self) -> usize {
+		self.nodes.len()
+	}
+
 	pub fn get(&self, id: NodeId) -> Option<SharedNodeContainer> {
 		self.nodes.get(&id).map(|(node, _)| node.clone())
 	}
@@ -379,11 +431,11 @@ impl BorrowTree {
 				let node = if let TaggedValue::EditorApi(api) = &**value {
 					let editor_api = UpcastAsRefNode::new(api.clone());
 					let node = Box::new(editor_api) as TypeErasedBox<'_>;
-					NodeContainer::new(node)
+					NodeContainer::new(Box::new(CountedNode(node)))
 				} else {
 					let upcasted = UpcastNode::new(value.to_owned());
 					let node = Box::new(upcasted) as TypeErasedBox<'_>;
-					NodeContainer::new(node)
+					NodeContainer::new(Box::new(CountedNode(node)))
 				};
 				self.store_node(node, id, path.into());
 			}
@@ -393,7 +445,7 @@ impl BorrowTree {
 				let construction_nodes = self.node_deps(&ids);
 				let constructor = typing_context.constructor(id).ok_or_else(|| vec![GraphError::new(&proto_node, GraphErrorType::NoConstructor)])?;
 				let node = constructor(construction_nodes).await;
-				let node = NodeContainer::new(node);
+				let node = NodeContainer::new(Box::new(CountedNode(node)));
 				self.store_node(node, id, path.into());
 			}
 		};
@@ -422,4 +474,18 @@ mod test {
 		let result = futures::executor::block_on(tree.eval(NodeId(0), ()));
 		assert_eq!(result, Some(2u32));
 	}
+
+	#[test]
+	fn evaluations_are_counted_only_within_a_scope() {
+		let mut tree = BorrowTree::default();
+		let val_1_protonode = ProtoNode::value(ConstructionArgs::Value(TaggedValue::U32(2u32).into()), vec![]);
+		let context = TypingContext::default();
+		futures::executor::block_on(tree.push_node(NodeId(0), val_1_protonode, &context)).unwrap();
+
+		let counter = EvaluationCounter::default();
+		let result = futures::executor::block_on(counter.scope(tree.eval(NodeId(0), ())));
+		assert_eq!(result, Some(2u32));
+		let _: Option<u32> = futures::executor::block_on(tree.eval(NodeId(0), ()));
+		assert_eq!(counter.counts().evaluations, 1);
+	}
 }